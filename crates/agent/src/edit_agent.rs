@@ -733,6 +733,7 @@ impl EditAgent {
             temperature: None,
             thinking_allowed: true,
             thinking_effort: None,
+            credential_profile: None,
         };
 
         Ok(self.model.stream_completion_text(request, cx).await?.stream)
@@ -2762,6 +2762,8 @@ async fn test_truncate_first_message(cx: &mut TestAppContext) {
                 max_output_tokens: None,
                 input_tokens: 32_000,
                 output_tokens: 16_000,
+                cache_read_input_tokens: 0,
+                cache_creation_input_tokens: 0,
             })
         );
     });
@@ -2824,6 +2826,8 @@ async fn test_truncate_first_message(cx: &mut TestAppContext) {
                 max_output_tokens: None,
                 input_tokens: 40_000,
                 output_tokens: 20_000,
+                cache_read_input_tokens: 0,
+                cache_creation_input_tokens: 0,
             })
         );
     });
@@ -2875,6 +2879,8 @@ async fn test_truncate_second_message(cx: &mut TestAppContext) {
                     max_output_tokens: None,
                     input_tokens: 32_000,
                     output_tokens: 16_000,
+                    cache_read_input_tokens: 0,
+                    cache_creation_input_tokens: 0,
                 })
             );
         });
@@ -2932,6 +2938,8 @@ async fn test_truncate_second_message(cx: &mut TestAppContext) {
                 max_output_tokens: None,
                 input_tokens: 40_000,
                 output_tokens: 20_000,
+                cache_read_input_tokens: 0,
+                cache_creation_input_tokens: 0,
             })
         );
     });
@@ -3532,6 +3540,66 @@ fn stop_events(result_events: Vec<Result<ThreadEvent>>) -> Vec<acp::StopReason>
         .collect()
 }
 
+#[gpui::test]
+async fn test_credential_profile_resolution(cx: &mut TestAppContext) {
+    let ThreadTest { model, thread, .. } = setup(cx, TestModel::Fake).await;
+    let fake_model = model.as_fake();
+    let provider_id: Arc<str> = model.provider_id().0.to_string().into();
+
+    // Without a thread or project selection, the provider picks its default profile.
+    thread.read_with(cx, |thread, cx| {
+        assert_eq!(thread.credential_profile_for_model(&model, cx), None);
+    });
+
+    // The project's setting for the model's provider applies next.
+    cx.update(|cx| {
+        let mut settings = ProjectSettings::get_global(cx).clone();
+        settings
+            .language_model_credential_profiles
+            .insert(provider_id.clone(), "team".into());
+        settings
+            .language_model_credential_profiles
+            .insert("other-provider".into(), "personal".into());
+        ProjectSettings::override_global(settings, cx);
+    });
+    thread.read_with(cx, |thread, cx| {
+        assert_eq!(
+            thread.credential_profile_for_model(&model, cx),
+            Some("team".into())
+        );
+    });
+
+    // A profile selected for the thread overrides the project's setting.
+    thread.update(cx, |thread, cx| {
+        thread.set_credential_profile(Some("personal".into()), cx);
+        assert_eq!(
+            thread.credential_profile_for_model(&model, cx),
+            Some("personal".into())
+        );
+    });
+
+    // The resolved profile is attached to completion requests.
+    thread
+        .update(cx, |thread, cx| {
+            thread.send(UserMessageId::new(), ["Hello"], cx)
+        })
+        .unwrap();
+    cx.run_until_parked();
+    let completion = fake_model.pending_completions().pop().unwrap();
+    assert_eq!(completion.credential_profile.as_deref(), Some("personal"));
+    fake_model.end_last_completion_stream();
+    cx.run_until_parked();
+
+    // Clearing the thread's selection falls back to the project's setting again.
+    thread.update(cx, |thread, cx| {
+        thread.set_credential_profile(None, cx);
+        assert_eq!(
+            thread.credential_profile_for_model(&model, cx),
+            Some("team".into())
+        );
+    });
+}
+
 struct ThreadTest {
     model: Arc<dyn LanguageModel>,
     thread: Entity<Thread>,
@@ -40,7 +40,7 @@ use language_model::{
     LanguageModelToolResultContent, LanguageModelToolSchemaFormat, LanguageModelToolUse,
     LanguageModelToolUseId, Role, SelectedModel, StopReason, TokenUsage, ZED_CLOUD_PROVIDER_ID,
 };
-use project::Project;
+use project::{Project, project_settings::ProjectSettings};
 use prompt_store::ProjectContext;
 use schemars::{JsonSchema, Schema};
 use serde::{Deserialize, Serialize};
@@ -55,7 +55,9 @@ use std::{
     time::{Duration, Instant},
 };
 use std::{fmt::Write, path::PathBuf};
-use util::{ResultExt, debug_panic, markdown::MarkdownCodeBlock, paths::PathStyle};
+use util::{
+    ResultExt, debug_panic, markdown::MarkdownCodeBlock, paths::PathStyle, rel_path::RelPath,
+};
 use uuid::Uuid;
 
 const TOOL_CANCELED_MESSAGE: &str = "Tool canceled by user";
@@ -904,6 +906,9 @@ pub struct Thread {
     running_subagents: Vec<WeakEntity<Thread>>,
     /// Git worktree info if this thread is running in an agent worktree.
     git_worktree_info: Option<AgentGitWorktreeInfo>,
+    /// The credential profile selected for this thread, overriding the project's profile for the
+    /// model's provider.
+    credential_profile: Option<SharedString>,
 }
 
 impl Thread {
@@ -928,6 +933,7 @@ impl Thread {
             model,
             cx,
         );
+        thread.credential_profile = parent_thread.read(cx).credential_profile.clone();
         thread.subagent_context = Some(SubagentContext {
             parent_thread_id: parent_thread.read(cx).id().clone(),
             depth: parent_thread.read(cx).depth() + 1,
@@ -995,6 +1001,7 @@ impl Thread {
             subagent_context: None,
             running_subagents: Vec::new(),
             git_worktree_info: None,
+            credential_profile: None,
         }
     }
 
@@ -1220,6 +1227,7 @@ impl Thread {
             subagent_context: db_thread.subagent_context,
             running_subagents: Vec::new(),
             git_worktree_info: db_thread.git_worktree_info,
+            credential_profile: db_thread.credential_profile.map(SharedString::from),
         }
     }
 
@@ -1241,6 +1249,10 @@ impl Thread {
             imported: self.imported,
             subagent_context: self.subagent_context.clone(),
             git_worktree_info: self.git_worktree_info.clone(),
+            credential_profile: self
+                .credential_profile
+                .as_ref()
+                .map(|profile| profile.to_string()),
         };
 
         cx.background_spawn(async move {
@@ -1329,6 +1341,46 @@ impl Thread {
         cx.notify();
     }
 
+    /// The credential profile explicitly selected for this thread, if any.
+    pub fn credential_profile(&self) -> Option<&SharedString> {
+        self.credential_profile.as_ref()
+    }
+
+    pub fn set_credential_profile(
+        &mut self,
+        profile: Option<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        self.credential_profile = profile;
+        cx.notify();
+    }
+
+    /// Resolves the credential profile that requests to the given model use: the thread's own
+    /// selection, then the project's setting for the model's provider. `None` lets the provider
+    /// fall back to its default profile.
+    pub fn credential_profile_for_model(
+        &self,
+        model: &Arc<dyn LanguageModel>,
+        cx: &App,
+    ) -> Option<SharedString> {
+        if let Some(profile) = self.credential_profile.clone() {
+            return Some(profile);
+        }
+        let location = self
+            .project
+            .read(cx)
+            .visible_worktrees(cx)
+            .next()
+            .map(|worktree| settings::SettingsLocation {
+                worktree_id: worktree.read(cx).id(),
+                path: RelPath::empty(),
+            });
+        ProjectSettings::get(location, cx)
+            .language_model_credential_profiles
+            .get(model.provider_id().0.as_ref())
+            .cloned()
+    }
+
     pub fn last_message(&self) -> Option<Message> {
         if let Some(message) = self.pending_message.clone() {
             Some(Message::Agent(message))
@@ -1495,6 +1547,8 @@ impl Thread {
             used_tokens: usage.total_tokens(),
             input_tokens: usage.input_tokens,
             output_tokens: usage.output_tokens,
+            cache_read_input_tokens: usage.cache_read_input_tokens,
+            cache_creation_input_tokens: usage.cache_creation_input_tokens,
         })
     }
 
@@ -2227,6 +2281,9 @@ impl Thread {
         let mut request = LanguageModelRequest {
             intent: Some(CompletionIntent::ThreadContextSummarization),
             temperature: AgentSettings::temperature_for_model(&model, cx),
+            credential_profile: self
+                .credential_profile_for_model(&model, cx)
+                .map(|profile| profile.to_string()),
             ..Default::default()
         };
 
@@ -2285,6 +2342,9 @@ impl Thread {
         let mut request = LanguageModelRequest {
             intent: Some(CompletionIntent::ThreadSummarization),
             temperature: AgentSettings::temperature_for_model(&model, cx),
+            credential_profile: self
+                .credential_profile_for_model(&model, cx)
+                .map(|profile| profile.to_string()),
             ..Default::default()
         };
 
@@ -2437,6 +2497,9 @@ impl Thread {
             temperature: AgentSettings::temperature_for_model(model, cx),
             thinking_allowed: self.thinking_enabled,
             thinking_effort: self.thinking_effort.clone(),
+            credential_profile: self
+                .credential_profile_for_model(model, cx)
+                .map(|profile| profile.to_string()),
         };
 
         log::debug!("Completion request built successfully");
@@ -2493,6 +2493,7 @@ impl AcpThreadView {
                         h_flex()
                             .gap_1()
                             .children(self.render_token_usage(cx))
+                            .children(self.render_credential_profile_selector(cx))
                             .children(self.profile_selector.clone())
                             .map(|this| {
                                 // Either config_options_view OR (mode_selector + model_selector)
@@ -2730,6 +2731,15 @@ impl AcpThreadView {
         let show_split = self.supports_split_token_display(cx);
 
         let separator_color = Color::Custom(cx.theme().colors().text_muted.opacity(0.5));
+        let cache_read = usage.cache_read_input_tokens;
+        let cache_written = usage.cache_creation_input_tokens;
+        let cache_summary = (cache_read > 0 || cache_written > 0).then(|| {
+            format!(
+                "{} cache hit • {} cache write",
+                crate::text_thread_editor::humanize_token_count(cache_read),
+                crate::text_thread_editor::humanize_token_count(cache_written),
+            )
+        });
         let token_label = |text: String, animation_id: &'static str| {
             Label::new(text)
                 .size(LabelSize::Small)
@@ -2810,6 +2820,26 @@ impl AcpThreadView {
                                     .color(Color::Muted),
                             ),
                     )
+                    .when_some(cache_summary, |this, cache_summary| {
+                        this.child(
+                            h_flex()
+                                .id("prompt-cache-tokens")
+                                .gap_0p5()
+                                .child(
+                                    Icon::new(IconName::DatabaseZap)
+                                        .size(IconSize::XSmall)
+                                        .color(Color::Muted),
+                                )
+                                .child(
+                                    Label::new(crate::text_thread_editor::humanize_token_count(
+                                        cache_read,
+                                    ))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                                )
+                                .tooltip(Tooltip::text(cache_summary)),
+                        )
+                    })
                     .into_any_element(),
             )
         } else {
@@ -2877,6 +2907,21 @@ impl AcpThreadView {
                                         .child(Label::new("/").color(separator_color))
                                         .child(Label::new(max.clone()).color(Color::Muted)),
                                 )
+                                .when_some(cache_summary.clone(), |this, cache_summary| {
+                                    this.child(
+                                        v_flex()
+                                            .mt_1p5()
+                                            .pt_1p5()
+                                            .border_t_1()
+                                            .border_color(cx.theme().colors().border_variant)
+                                            .child(
+                                                Label::new("Prompt Cache")
+                                                    .color(Color::Muted)
+                                                    .size(LabelSize::Small),
+                                            )
+                                            .child(Label::new(cache_summary)),
+                                    )
+                                })
                                 .when(user_rules_count > 0 || project_rules_count > 0, |this| {
                                     this.child(
                                         v_flex()
@@ -2911,6 +2956,87 @@ impl AcpThreadView {
         }
     }
 
+    fn render_credential_profile_selector(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
+        let thread = self.as_native_thread(cx)?.read(cx);
+        let model = thread.model()?.clone();
+        let provider = LanguageModelRegistry::read_global(cx).provider(&model.provider_id())?;
+        let profiles = provider.credential_profiles(cx);
+        if profiles.is_empty() {
+            return None;
+        }
+
+        let thread_profile = thread.credential_profile().cloned();
+        let active_profile = thread
+            .credential_profile_for_model(&model, cx)
+            .filter(|profile| profiles.contains(profile))
+            .or_else(|| provider.default_credential_profile(cx))?;
+        let weak_self = cx.weak_entity();
+
+        Some(
+            PopoverMenu::new("credential-profile-selector")
+                .trigger_with_tooltip(
+                    ButtonLike::new("credential-profile-selector-trigger")
+                        .child(
+                            Icon::new(IconName::LockOutlined)
+                                .size(IconSize::XSmall)
+                                .color(Color::Muted),
+                        )
+                        .child(
+                            Label::new(active_profile.clone())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                    Tooltip::text(format!("Credential Profile for {}", provider.name().0)),
+                )
+                .menu(move |window, cx| {
+                    let profiles = profiles.clone();
+                    let thread_profile = thread_profile.clone();
+                    let active_profile = active_profile.clone();
+                    let weak_self = weak_self.clone();
+                    Some(ContextMenu::build(
+                        window,
+                        cx,
+                        move |mut menu, _window, _cx| {
+                            menu = menu.header("Credential Profile");
+                            let entries =
+                                std::iter::once(None).chain(profiles.into_iter().map(Some));
+                            for profile in entries {
+                                let (label, is_selected) = match &profile {
+                                    None => ("Project Default".into(), thread_profile.is_none()),
+                                    Some(profile) => (
+                                        profile.clone(),
+                                        thread_profile.is_some() && *profile == active_profile,
+                                    ),
+                                };
+                                let weak_self = weak_self.clone();
+                                menu.push_item(
+                                    ContextMenuEntry::new(label)
+                                        .toggleable(IconPosition::End, is_selected)
+                                        .handler(move |_window, cx| {
+                                            let profile = profile.clone();
+                                            weak_self
+                                                .update(cx, |this, cx| {
+                                                    if let Some(thread) = this.as_native_thread(cx)
+                                                    {
+                                                        thread.update(cx, |thread, cx| {
+                                                            thread
+                                                                .set_credential_profile(profile, cx)
+                                                        });
+                                                    }
+                                                })
+                                                .ok();
+                                        }),
+                                );
+                            }
+                            menu
+                        },
+                    ))
+                })
+                .anchor(Corner::BottomRight)
+                .into_any_element(),
+        )
+    }
+
     fn render_thinking_control(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
         let thread = self.as_native_thread(cx)?.read(cx);
         let model = thread.model()?;
@@ -100,6 +100,7 @@ struct ModelCapabilityToggles {
     pub supports_images: ToggleState,
     pub supports_parallel_tool_calls: ToggleState,
     pub supports_prompt_cache_key: ToggleState,
+    pub supports_cache_control: ToggleState,
     pub supports_chat_completions: ToggleState,
 }
 
@@ -153,6 +154,7 @@ impl ModelInput {
             images,
             parallel_tool_calls,
             prompt_cache_key,
+            cache_control,
             chat_completions,
         } = ModelCapabilities::default();
 
@@ -166,6 +168,7 @@ impl ModelInput {
                 supports_images: images.into(),
                 supports_parallel_tool_calls: parallel_tool_calls.into(),
                 supports_prompt_cache_key: prompt_cache_key.into(),
+                supports_cache_control: cache_control.into(),
                 supports_chat_completions: chat_completions.into(),
             },
         }
@@ -204,6 +207,7 @@ impl ModelInput {
                 images: self.capabilities.supports_images.selected(),
                 parallel_tool_calls: self.capabilities.supports_parallel_tool_calls.selected(),
                 prompt_cache_key: self.capabilities.supports_prompt_cache_key.selected(),
+                cache_control: self.capabilities.supports_cache_control.selected(),
                 chat_completions: self.capabilities.supports_chat_completions.selected(),
             },
         })
@@ -273,6 +277,10 @@ fn save_provider_to_settings(
                         OpenAiCompatibleSettingsContent {
                             api_url,
                             available_models: models,
+                            credential_profiles: Vec::new(),
+                            default_credential_profile: None,
+                            headers: Default::default(),
+                            auth_command: None,
                         },
                     );
             });
@@ -428,6 +436,20 @@ impl AddLlmProviderModal {
                             },
                         )),
                     )
+                    .child(
+                        Checkbox::new(
+                            ("supports-cache-control", ix),
+                            model.capabilities.supports_cache_control,
+                        )
+                        .label("Supports cache_control")
+                        .on_click(cx.listener(
+                            move |this, checked, _window, cx| {
+                                this.input.models[ix].capabilities.supports_cache_control =
+                                    *checked;
+                                cx.notify();
+                            },
+                        )),
+                    )
                     .child(
                         Checkbox::new(
                             ("supports-chat-completions", ix),
@@ -739,6 +761,10 @@ mod tests {
                 model_input.capabilities.supports_prompt_cache_key,
                 ToggleState::Unselected
             );
+            assert_eq!(
+                model_input.capabilities.supports_cache_control,
+                ToggleState::Unselected
+            );
             assert_eq!(
                 model_input.capabilities.supports_chat_completions,
                 ToggleState::Selected
@@ -545,6 +545,7 @@ impl CodegenAlternative {
                 messages,
                 thinking_allowed: false,
                 thinking_effort: None,
+                credential_profile: None,
             }
         }))
     }
@@ -624,6 +625,7 @@ impl CodegenAlternative {
                 messages: vec![request_message],
                 thinking_allowed: false,
                 thinking_effort: None,
+                credential_profile: None,
             }
         }))
     }
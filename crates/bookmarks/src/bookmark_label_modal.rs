@@ -0,0 +1,99 @@
+use editor::Editor;
+use gpui::{
+    App, AppContext as _, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    Render, Window,
+};
+use ui::prelude::*;
+use workspace::{ModalView, Workspace};
+
+/// A modal to edit the label of the bookmark on the line of the cursor in the active editor.
+pub struct BookmarkLabelModal {
+    editor: Entity<Editor>,
+    label_editor: Entity<Editor>,
+}
+
+impl EventEmitter<DismissEvent> for BookmarkLabelModal {}
+impl ModalView for BookmarkLabelModal {}
+
+impl Focusable for BookmarkLabelModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.label_editor.focus_handle(cx)
+    }
+}
+
+impl BookmarkLabelModal {
+    pub(crate) fn toggle(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+            return;
+        };
+        if editor.read(cx).bookmark_store().is_none() {
+            return;
+        }
+        workspace.toggle_modal(window, cx, move |window, cx| Self::new(editor, window, cx));
+    }
+
+    fn new(editor: Entity<Editor>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let label = editor.read(cx).bookmark_label_at_cursor(cx);
+        let label_editor = cx.new(|cx| {
+            let mut label_editor = Editor::single_line(window, cx);
+            label_editor.set_placeholder_text("Bookmark label", window, cx);
+            if let Some(label) = label {
+                label_editor.set_text(label, window, cx);
+                label_editor.select_all(&Default::default(), window, cx);
+            }
+            label_editor
+        });
+
+        Self {
+            editor,
+            label_editor,
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        let label = self.label_editor.read(cx).text(cx);
+        self.editor.update(cx, |editor, cx| {
+            editor.set_bookmark_label_at_cursor(Some(label), cx);
+        });
+        cx.emit(DismissEvent);
+    }
+}
+
+impl Render for BookmarkLabelModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("BookmarkLabelModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .elevation_3(cx)
+            .w_96()
+            .overflow_hidden()
+            .child(
+                div()
+                    .p_2()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(self.label_editor.clone()),
+            )
+            .child(
+                h_flex()
+                    .bg(cx.theme().colors().editor_background)
+                    .rounded_b_sm()
+                    .w_full()
+                    .p_2()
+                    .child(
+                        Label::new("Leave empty to remove the label.")
+                            .color(Color::Muted)
+                            .size(LabelSize::Small),
+                    ),
+            )
+    }
+}
@@ -0,0 +1,224 @@
+use crate::{display_path, open_bookmark};
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, ParentElement,
+    Render, Styled, Task, WeakEntity, Window,
+};
+use picker::{Picker, PickerDelegate};
+use std::{path::Path, sync::Arc};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::{ModalView, Workspace};
+
+/// A modal to search the bookmarks of the project by label or location, and jump to one of them.
+pub struct BookmarkPicker {
+    picker: Entity<Picker<BookmarkPickerDelegate>>,
+}
+
+impl BookmarkPicker {
+    pub(crate) fn toggle(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let project = workspace.project().read(cx);
+        let bookmarks = project
+            .bookmark_store()
+            .read(cx)
+            .all_bookmarks(cx)
+            .into_iter()
+            .flat_map(|(abs_path, bookmarks)| {
+                let display_path = display_path(project, &abs_path, cx);
+                bookmarks.into_iter().map(move |bookmark| BookmarkEntry {
+                    abs_path: abs_path.clone(),
+                    row: bookmark.row,
+                    text: match bookmark.label {
+                        Some(label) => format!("{label}  {display_path}:{}", bookmark.row + 1),
+                        None => format!("{display_path}:{}", bookmark.row + 1),
+                    },
+                })
+            })
+            .collect::<Vec<_>>();
+        let workspace_handle = workspace.weak_handle();
+
+        workspace.toggle_modal(window, cx, move |window, cx| {
+            let delegate =
+                BookmarkPickerDelegate::new(cx.entity().downgrade(), workspace_handle, bookmarks);
+            let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+            Self { picker }
+        });
+    }
+}
+
+impl Render for BookmarkPicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("BookmarkPicker")
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+impl Focusable for BookmarkPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for BookmarkPicker {}
+impl ModalView for BookmarkPicker {}
+
+struct BookmarkEntry {
+    abs_path: Arc<Path>,
+    row: u32,
+    text: String,
+}
+
+pub struct BookmarkPickerDelegate {
+    bookmark_picker: WeakEntity<BookmarkPicker>,
+    workspace: WeakEntity<Workspace>,
+    bookmarks: Vec<BookmarkEntry>,
+    candidates: Vec<StringMatchCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl BookmarkPickerDelegate {
+    fn new(
+        bookmark_picker: WeakEntity<BookmarkPicker>,
+        workspace: WeakEntity<Workspace>,
+        bookmarks: Vec<BookmarkEntry>,
+    ) -> Self {
+        let candidates = bookmarks
+            .iter()
+            .enumerate()
+            .map(|(candidate_id, bookmark)| StringMatchCandidate::new(candidate_id, &bookmark.text))
+            .collect();
+
+        Self {
+            bookmark_picker,
+            workspace,
+            bookmarks,
+            candidates,
+            matches: Vec::new(),
+            selected_index: 0,
+        }
+    }
+}
+
+impl PickerDelegate for BookmarkPickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Search bookmarks…".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some(if self.bookmarks.is_empty() {
+            "No bookmarks in this project.".into()
+        } else {
+            "No matching bookmarks.".into()
+        })
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(mat) = self.matches.get(self.selected_index) {
+            let bookmark = &self.bookmarks[mat.candidate_id];
+            self.workspace
+                .update(cx, |workspace, cx| {
+                    open_bookmark(workspace, &bookmark.abs_path, bookmark.row, window, cx);
+                })
+                .log_err();
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.bookmark_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self.candidates.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, candidate)| StringMatch {
+                        candidate_id: index,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot(Icon::new(IconName::Bookmark).color(Color::Muted))
+                .child(HighlightedLabel::new(
+                    mat.string.clone(),
+                    mat.positions.clone(),
+                )),
+        )
+    }
+}
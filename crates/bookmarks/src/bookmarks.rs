@@ -0,0 +1,108 @@
+mod bookmark_label_modal;
+mod bookmark_picker;
+mod bookmarks_panel;
+
+use editor::Editor;
+use gpui::{App, Context, Window, actions, px};
+use language::Point;
+use project::Project;
+use settings::{RegisterSetting, Settings};
+use std::path::Path;
+use ui::Pixels;
+use workspace::{OpenOptions, OpenVisible, Workspace, dock::DockPosition};
+
+pub use bookmark_label_modal::BookmarkLabelModal;
+pub use bookmark_picker::BookmarkPicker;
+pub use bookmarks_panel::BookmarksPanel;
+
+actions!(
+    bookmarks,
+    [
+        /// Searches the bookmarks of the project by label or location.
+        Toggle,
+        /// Toggles focus on the bookmarks panel.
+        ToggleFocus,
+        /// Edits the label of the bookmark at the cursor, bookmarking the line if needed.
+        EditBookmarkLabel,
+        /// Removes every bookmark in the project.
+        ClearBookmarks
+    ]
+);
+
+#[derive(Debug, RegisterSetting)]
+pub struct BookmarksSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+}
+
+impl Settings for BookmarksSettings {
+    fn from_settings(content: &settings::SettingsContent) -> Self {
+        let bookmarks = content.bookmarks.as_ref().unwrap();
+
+        Self {
+            button: bookmarks.button.unwrap(),
+            dock: bookmarks.dock.unwrap().into(),
+            default_width: bookmarks.default_width.map(px).unwrap(),
+        }
+    }
+}
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<BookmarksPanel>(window, cx);
+        });
+        workspace.register_action(|workspace, _: &Toggle, window, cx| {
+            BookmarkPicker::toggle(workspace, window, cx);
+        });
+        workspace.register_action(|workspace, _: &EditBookmarkLabel, window, cx| {
+            BookmarkLabelModal::toggle(workspace, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ClearBookmarks, _, cx| {
+            let bookmark_store = workspace.project().read(cx).bookmark_store();
+            bookmark_store.update(cx, |bookmark_store, cx| bookmark_store.clear_bookmarks(cx));
+        });
+    })
+    .detach();
+}
+
+/// Opens the file of a bookmark and moves the cursor to its line.
+fn open_bookmark(
+    workspace: &mut Workspace,
+    abs_path: &Path,
+    row: u32,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let open = workspace.open_abs_path(
+        abs_path.to_path_buf(),
+        OpenOptions {
+            visible: Some(OpenVisible::None),
+            ..Default::default()
+        },
+        window,
+        cx,
+    );
+    cx.spawn_in(window, async move |_, cx| {
+        let item = open.await?;
+        let Some(editor) = cx.update(|_, cx| item.act_as::<Editor>(cx))? else {
+            return Ok(());
+        };
+        editor.update_in(cx, |editor, window, cx| {
+            editor.go_to_singleton_buffer_point(Point::new(row, 0), window, cx);
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Returns the path of a bookmarked file relative to its worktree, or its absolute path for files
+/// outside of the project.
+fn display_path(project: &Project, abs_path: &Path, cx: &App) -> String {
+    match project.find_worktree(abs_path, cx) {
+        Some((_, relative_path)) if !relative_path.is_empty() => {
+            relative_path.display(project.path_style(cx)).into_owned()
+        }
+        _ => abs_path.to_string_lossy().into_owned(),
+    }
+}
@@ -0,0 +1,280 @@
+use crate::{BookmarksSettings, ToggleFocus, display_path, open_bookmark};
+use anyhow::Result;
+use db::kvp::KEY_VALUE_STORE;
+use gpui::{
+    App, AsyncWindowContext, Context, Entity, EventEmitter, FocusHandle, Focusable, Render,
+    Subscription, Task, WeakEntity,
+};
+use project::{Fs, Project, bookmark_store::SerializedBookmark};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use std::{path::Path, sync::Arc};
+use ui::{ListItem, ListItemSpacing, ListSubHeader, Tab, Tooltip, prelude::*};
+use util::{ResultExt, TryFutureExt};
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+const BOOKMARKS_PANEL_KEY: &str = "BookmarksPanel";
+
+/// Lists every bookmark in the project, grouped by file.
+pub struct BookmarksPanel {
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    pending_serialization: Task<Option<()>>,
+    _subscription: Subscription,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedBookmarksPanel {
+    width: Option<Pixels>,
+}
+
+impl BookmarksPanel {
+    pub fn new(workspace: &mut Workspace, cx: &mut Context<Workspace>) -> Entity<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let project = workspace.project().clone();
+        let bookmark_store = project.read(cx).bookmark_store();
+        let workspace = workspace.weak_handle();
+
+        cx.new(|cx| Self {
+            workspace,
+            project,
+            fs,
+            focus_handle: cx.focus_handle(),
+            width: None,
+            pending_serialization: Task::ready(None),
+            _subscription: cx.observe(&bookmark_store, |_, _, cx| cx.notify()),
+        })
+    }
+
+    pub fn load(
+        workspace: WeakEntity<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<Entity<Self>>> {
+        cx.spawn(async move |cx| {
+            let serialized_panel = if let Some(panel) = cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(BOOKMARKS_PANEL_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedBookmarksPanel>(&panel)?)
+            } else {
+                None
+            };
+
+            workspace.update(cx, |workspace, cx| {
+                let panel = Self::new(workspace, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width.map(|w| w.round());
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        BOOKMARKS_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedBookmarksPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn open(&mut self, abs_path: &Path, row: u32, window: &mut Window, cx: &mut Context<Self>) {
+        self.workspace
+            .update(cx, |workspace, cx| {
+                open_bookmark(workspace, abs_path, row, window, cx)
+            })
+            .log_err();
+    }
+
+    fn remove(&mut self, abs_path: &Arc<Path>, row: u32, cx: &mut Context<Self>) {
+        let bookmark_store = self.project.read(cx).bookmark_store();
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.remove_bookmark(abs_path, row, cx)
+        });
+    }
+
+    fn render_file(
+        &self,
+        abs_path: &Arc<Path>,
+        bookmarks: &[SerializedBookmark],
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let display_path = display_path(self.project.read(cx), abs_path, cx);
+
+        v_flex()
+            .child(ListSubHeader::new(display_path).inset(true))
+            .children(
+                bookmarks
+                    .iter()
+                    .map(|bookmark| self.render_bookmark(abs_path, bookmark, cx)),
+            )
+    }
+
+    fn render_bookmark(
+        &self,
+        abs_path: &Arc<Path>,
+        bookmark: &SerializedBookmark,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let row = bookmark.row;
+        let id = SharedString::from(format!("{}:{row}", abs_path.display()));
+
+        ListItem::new(id.clone())
+            .spacing(ListItemSpacing::Sparse)
+            .indent_level(1)
+            .start_slot(
+                Icon::new(IconName::Bookmark)
+                    .size(IconSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Label::new(format!("Line {}", row + 1)))
+                    .when_some(bookmark.label.clone(), |this, label| {
+                        this.child(Label::new(label).color(Color::Muted).truncate())
+                    }),
+            )
+            .end_hover_slot(
+                IconButton::new(id, IconName::Close)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text("Remove Bookmark"))
+                    .on_click(cx.listener({
+                        let abs_path = abs_path.clone();
+                        move |this, _, _, cx| this.remove(&abs_path, row, cx)
+                    })),
+            )
+            .on_click(cx.listener({
+                let abs_path = abs_path.clone();
+                move |this, _, window, cx| this.open(&abs_path, row, window, cx)
+            }))
+    }
+}
+
+impl Render for BookmarksPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let bookmarks = self
+            .project
+            .read(cx)
+            .bookmark_store()
+            .read(cx)
+            .all_bookmarks(cx);
+
+        v_flex()
+            .key_context("BookmarksPanel")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    // Match the height of the tab bar so they line up.
+                    .h(Tab::container_height(cx))
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(Label::new("Bookmarks")),
+            )
+            .map(|this| {
+                if bookmarks.is_empty() {
+                    this.child(
+                        v_flex().p_4().child(
+                            Label::new("Bookmark a line to see it here.")
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        v_flex()
+                            .id("bookmarks")
+                            .p_1()
+                            .flex_1()
+                            .overflow_y_scroll()
+                            .children(bookmarks.iter().map(|(abs_path, bookmarks)| {
+                                self.render_file(abs_path, bookmarks, cx)
+                            })),
+                    )
+                }
+            })
+    }
+}
+
+impl Focusable for BookmarksPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for BookmarksPanel {}
+
+impl Panel for BookmarksPanel {
+    fn persistent_name() -> &'static str {
+        "BookmarksPanel"
+    }
+
+    fn panel_key() -> &'static str {
+        BOOKMARKS_PANEL_KEY
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        BookmarksSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            settings.bookmarks.get_or_insert_default().dock = Some(position.into())
+        });
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| BookmarksSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        BookmarksSettings::get_global(cx)
+            .button
+            .then_some(IconName::Bookmark)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
+        Some("Bookmarks")
+    }
+
+    fn toggle_action(&self) -> Box<dyn gpui::Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        11
+    }
+}
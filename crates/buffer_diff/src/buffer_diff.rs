@@ -110,6 +110,20 @@ pub struct DiffHunk {
     pub base_word_diffs: Vec<Range<usize>>,
 }
 
+/// The part of a [`DiffHunk`] to stage or unstage, given by the offsets that were selected in the
+/// buffer and in the diff base text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialHunk {
+    /// The range in the buffer to which the hunk corresponds.
+    pub buffer_range: Range<Anchor>,
+    /// The range in the buffer's diff base text to which the hunk corresponds.
+    pub diff_base_byte_range: Range<usize>,
+    /// The selected offsets of the buffer, within `buffer_range`.
+    pub buffer_selection: Range<usize>,
+    /// The selected offsets of the diff base text, within `diff_base_byte_range`.
+    pub base_selection: Range<usize>,
+}
+
 /// We store [`InternalDiffHunk`]s internally so we don't need to store the additional row range.
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct InternalDiffHunk {
@@ -1340,6 +1354,159 @@ fn compare_hunks(
     }
 }
 
+/// Finds the range of the index text that corresponds to `buffer_range`, given the buffer and
+/// index ranges of the hunks between the index and the buffer.
+///
+/// Returns `None` if one of those hunks straddles either end of `buffer_range`.
+fn index_range_for_buffer_range(
+    unstaged_hunks: &[(Range<usize>, Range<usize>)],
+    buffer_range: Range<usize>,
+) -> Option<Range<usize>> {
+    let to_index_offset = |offset: usize, is_end: bool| {
+        let mut index_offset = offset;
+        for (hunk_buffer_range, hunk_index_range) in unstaged_hunks {
+            if hunk_buffer_range.start < offset && offset < hunk_buffer_range.end {
+                return None;
+            }
+            // Hunks that only delete lines at either end of the range belong to it.
+            let precedes = if is_end {
+                hunk_buffer_range.end <= offset
+            } else {
+                hunk_buffer_range.end < offset
+                    || (hunk_buffer_range.end == offset && hunk_buffer_range.start < offset)
+            };
+            if !precedes {
+                break;
+            }
+            index_offset = offset - hunk_buffer_range.end + hunk_index_range.end;
+        }
+        Some(index_offset)
+    };
+    Some(to_index_offset(buffer_range.start, false)?..to_index_offset(buffer_range.end, true)?)
+}
+
+/// Maps `range` in `old_text` to the corresponding range in `new_text`, growing it to cover any
+/// change that it overlaps.
+fn map_range_through_diff(old_text: &str, new_text: &str, range: Range<usize>) -> Range<usize> {
+    let edits = language::text_diff(old_text, new_text);
+    let map_offset = |offset: usize, is_end: bool| {
+        let mut delta = 0isize;
+        for (old_range, replacement) in &edits {
+            let precedes = if is_end {
+                old_range.end < offset || (old_range.end == offset && old_range.start < offset)
+            } else {
+                old_range.end <= offset
+            };
+            let new_start = old_range.start.saturating_add_signed(delta);
+            if precedes {
+                delta += replacement.len() as isize - old_range.len() as isize;
+            } else if old_range.start < offset {
+                return if is_end {
+                    new_start + replacement.len()
+                } else {
+                    new_start
+                };
+            } else {
+                break;
+            }
+        }
+        offset.saturating_add_signed(delta)
+    };
+    let start = map_offset(range.start, false);
+    let end = map_offset(range.end, true).max(start);
+    start..end
+}
+
+/// Applies to `old_text` the changes from `old_text` to `new_text` that overlap `old_selection`
+/// or `new_selection`.
+///
+/// Changed lines are applied one at a time, unless one of the selections starts or ends within
+/// them, in which case the changes within those lines are applied one word at a time.
+fn apply_selected_changes(
+    old_text: &str,
+    new_text: &str,
+    old_selection: Range<usize>,
+    new_selection: Range<usize>,
+) -> String {
+    fn line_starts(text: &str) -> Vec<usize> {
+        iter::once(0)
+            .chain(
+                text.match_indices('\n')
+                    .map(|(ix, _)| ix + 1)
+                    .filter(|offset| *offset < text.len()),
+            )
+            .chain(iter::once(text.len()))
+            .collect()
+    }
+    let overlaps = |range: &Range<usize>, selection: &Range<usize>| {
+        range.start < selection.end && selection.start < range.end
+    };
+    let splits_line = |text: &str, range: &Range<usize>, selection: &Range<usize>| {
+        !selection.is_empty()
+            && [selection.start, selection.end].into_iter().any(|offset| {
+                range.start < offset && offset < range.end && text.as_bytes()[offset - 1] != b'\n'
+            })
+    };
+
+    let old_line_starts = line_starts(old_text);
+    let new_line_starts = line_starts(new_text);
+    let mut result = String::with_capacity(old_text.len());
+    let mut old_offset = 0;
+    for (old_rows, new_rows) in language::line_diff(old_text, new_text) {
+        let old_range =
+            old_line_starts[old_rows.start as usize]..old_line_starts[old_rows.end as usize];
+        let new_range =
+            new_line_starts[new_rows.start as usize]..new_line_starts[new_rows.end as usize];
+        result.push_str(&old_text[old_offset..old_range.start]);
+        old_offset = old_range.end;
+
+        if splits_line(old_text, &old_range, &old_selection)
+            || splits_line(new_text, &new_range, &new_selection)
+        {
+            let old_lines = &old_text[old_range.clone()];
+            let mut old_lines_offset = 0;
+            let mut delta = 0isize;
+            for (range, replacement) in language::text_diff(old_lines, &new_text[new_range.clone()])
+            {
+                let old_edit_range = old_range.start + range.start..old_range.start + range.end;
+                let new_edit_start = new_range.start + range.start.saturating_add_signed(delta);
+                let new_edit_range = new_edit_start..new_edit_start + replacement.len();
+                delta += replacement.len() as isize - range.len() as isize;
+
+                result.push_str(&old_lines[old_lines_offset..range.start]);
+                old_lines_offset = range.end;
+                if overlaps(&old_edit_range, &old_selection)
+                    || overlaps(&new_edit_range, &new_selection)
+                {
+                    result.push_str(&replacement);
+                } else {
+                    result.push_str(&old_lines[range]);
+                }
+            }
+            result.push_str(&old_lines[old_lines_offset..]);
+        } else {
+            let mut line_start = old_range.start;
+            for line in old_text[old_range].split_inclusive('\n') {
+                let line_range = line_start..line_start + line.len();
+                line_start = line_range.end;
+                if !overlaps(&line_range, &old_selection) {
+                    result.push_str(line);
+                }
+            }
+            let mut line_start = new_range.start;
+            for line in new_text[new_range].split_inclusive('\n') {
+                let line_range = line_start..line_start + line.len();
+                line_start = line_range.end;
+                if overlaps(&line_range, &new_selection) {
+                    result.push_str(line);
+                }
+            }
+        }
+    }
+    result.push_str(&old_text[old_offset..]);
+    result
+}
+
 fn process_patch_hunk(
     patch: &GitPatch<'_>,
     hunk_index: usize,
@@ -1605,6 +1772,104 @@ impl BufferDiff {
         new_index_text
     }
 
+    /// Stages or unstages only the selected lines of each hunk, or the selected characters when
+    /// a selection starts or ends within a line, and returns the new index text.
+    ///
+    /// Unlike [`Self::stage_or_unstage_hunks`], no hunks are marked as pending: their status is
+    /// updated once the new index text has been written and the diff recalculated.
+    pub fn stage_or_unstage_partial_hunks(
+        &mut self,
+        stage: bool,
+        hunks: &[PartialHunk],
+        buffer: &text::BufferSnapshot,
+        cx: &mut Context<Self>,
+    ) -> Option<Rope> {
+        let unstaged_diff = &self.secondary_diff.as_ref()?.read(cx).inner;
+        let head_text = self.inner.base_text.read(cx).as_rope().clone();
+        let index_text = unstaged_diff.base_text.read(cx).as_rope().clone();
+        let index_exists = unstaged_diff.base_text_exists;
+        let unstaged_hunks = unstaged_diff
+            .hunks
+            .iter()
+            .map(|hunk| {
+                (
+                    hunk.buffer_range.to_offset(buffer),
+                    hunk.diff_base_byte_range.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut edits = Vec::<(Range<usize>, String)>::new();
+        for hunk in hunks {
+            let buffer_range = hunk.buffer_range.to_offset(buffer);
+            let index_range = if index_exists {
+                let Some(index_range) =
+                    index_range_for_buffer_range(&unstaged_hunks, buffer_range.clone())
+                else {
+                    log::debug!("hunk {buffer_range:?} overlaps another index change, skipping");
+                    continue;
+                };
+                index_range
+            } else {
+                0..0
+            };
+
+            let head_hunk_text = head_text
+                .chunks_in_range(hunk.diff_base_byte_range.clone())
+                .collect::<String>();
+            let index_hunk_text = index_text
+                .chunks_in_range(index_range.clone())
+                .collect::<String>();
+            let buffer_hunk_text = buffer
+                .text_for_range(buffer_range.clone())
+                .collect::<String>();
+            let relative = |selection: &Range<usize>, range: &Range<usize>| {
+                selection.start.clamp(range.start, range.end) - range.start
+                    ..selection.end.clamp(range.start, range.end) - range.start
+            };
+            let buffer_selection = relative(&hunk.buffer_selection, &buffer_range);
+            let base_selection = relative(&hunk.base_selection, &hunk.diff_base_byte_range);
+
+            let new_index_hunk_text = if stage {
+                log::debug!("staging {buffer_selection:?} of hunk {buffer_range:?}");
+                let index_selection =
+                    map_range_through_diff(&head_hunk_text, &index_hunk_text, base_selection);
+                apply_selected_changes(
+                    &index_hunk_text,
+                    &buffer_hunk_text,
+                    index_selection,
+                    buffer_selection,
+                )
+            } else {
+                log::debug!("unstaging {buffer_selection:?} of hunk {buffer_range:?}");
+                let index_selection =
+                    map_range_through_diff(&buffer_hunk_text, &index_hunk_text, buffer_selection);
+                apply_selected_changes(
+                    &index_hunk_text,
+                    &head_hunk_text,
+                    index_selection,
+                    base_selection,
+                )
+            };
+            if new_index_hunk_text != index_hunk_text {
+                edits.push((index_range, new_index_hunk_text));
+            }
+        }
+        if edits.is_empty() {
+            return None;
+        }
+
+        edits.sort_by_key(|(range, _)| range.start);
+        let mut new_index_text = index_text;
+        for (range, text) in edits.into_iter().rev() {
+            new_index_text.replace(range, &text);
+        }
+        cx.emit(BufferDiffEvent::HunksStagedOrUnstaged(Some(
+            new_index_text.clone(),
+        )));
+        Some(new_index_text)
+    }
+
     pub fn stage_or_unstage_all_hunks(
         &mut self,
         stage: bool,
@@ -2733,6 +2998,117 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_stage_and_unstage_partial_hunks(cx: &mut TestAppContext) {
+        let head_text = "
+            one
+            two
+            three
+        "
+        .unindent();
+        let buffer_text = "
+            one
+            2
+            3
+            three
+        "
+        .unindent();
+        let buffer = Buffer::new(
+            ReplicaId::LOCAL,
+            BufferId::new(1).unwrap(),
+            buffer_text.clone(),
+        );
+        let diff_with_index_text = |index_text: &str, cx: &mut TestAppContext| {
+            let unstaged_diff =
+                cx.new(|cx| BufferDiff::new_with_base_text(index_text, &buffer, cx));
+            cx.new(|cx| {
+                let mut diff = BufferDiff::new_with_base_text(&head_text, &buffer, cx);
+                diff.set_secondary_diff(unstaged_diff);
+                diff
+            })
+        };
+        let partial_hunk = |diff: &BufferDiff,
+                            buffer_selection: Range<usize>,
+                            base_selection: Range<usize>,
+                            cx: &App| {
+            let hunk = diff.snapshot(cx).hunks(&buffer).next().unwrap();
+            PartialHunk {
+                buffer_range: hunk.buffer_range,
+                diff_base_byte_range: hunk.diff_base_byte_range,
+                buffer_selection,
+                base_selection,
+            }
+        };
+
+        // Stage the addition of "2", but not the removal of "two" or the addition of "3".
+        let diff = diff_with_index_text(&head_text, cx);
+        diff.update(cx, |diff, cx| {
+            let hunk = partial_hunk(diff, 4..6, 4..4, cx);
+            let new_index_text = diff
+                .stage_or_unstage_partial_hunks(true, &[hunk], &buffer, cx)
+                .unwrap()
+                .to_string();
+            assert_eq!(new_index_text, "one\ntwo\n2\nthree\n");
+        });
+
+        // Stage only the removal of "two".
+        let diff = diff_with_index_text(&head_text, cx);
+        diff.update(cx, |diff, cx| {
+            let hunk = partial_hunk(diff, 4..4, 4..8, cx);
+            let new_index_text = diff
+                .stage_or_unstage_partial_hunks(true, &[hunk], &buffer, cx)
+                .unwrap()
+                .to_string();
+            assert_eq!(new_index_text, "one\nthree\n");
+        });
+
+        // Unstage the addition of "2" once it's staged, keeping the staged removal of "two".
+        let diff = diff_with_index_text("one\n2\nthree\n", cx);
+        diff.update(cx, |diff, cx| {
+            let hunk = partial_hunk(diff, 4..6, 4..4, cx);
+            let new_index_text = diff
+                .stage_or_unstage_partial_hunks(false, &[hunk], &buffer, cx)
+                .unwrap()
+                .to_string();
+            assert_eq!(new_index_text, "one\nthree\n");
+
+            // Selecting lines that are already unstaged changes nothing.
+            let hunk = partial_hunk(diff, 6..8, 4..4, cx);
+            assert_eq!(
+                diff.stage_or_unstage_partial_hunks(false, &[hunk], &buffer, cx),
+                None
+            );
+        });
+    }
+
+    #[test]
+    fn test_apply_selected_changes() {
+        let old_text = "let x = 1;\nlet y = 2;\n";
+        let new_text = "let x = 3;\nlet z = 4;\n";
+
+        // Selecting whole lines applies the removal or addition of each of them.
+        assert_eq!(
+            apply_selected_changes(old_text, new_text, 11..22, 11..22),
+            "let x = 1;\nlet z = 4;\n"
+        );
+        assert_eq!(
+            apply_selected_changes(old_text, new_text, 0..0, 11..22),
+            "let x = 1;\nlet y = 2;\nlet z = 4;\n"
+        );
+        // Selecting within a line applies only the words it overlaps.
+        assert_eq!(
+            apply_selected_changes(old_text, new_text, 0..0, 15..16),
+            "let x = 1;\nlet z = 2;\n"
+        );
+        assert_eq!(
+            apply_selected_changes(old_text, new_text, 8..9, 0..0),
+            "let x = 3;\nlet y = 2;\n"
+        );
+
+        assert_eq!(map_range_through_diff(old_text, new_text, 11..22), 11..22);
+        assert_eq!(map_range_through_diff("a\nc\n", "a\nb\nc\n", 2..4), 4..6);
+    }
+
     #[gpui::test]
     async fn test_buffer_diff_compare(cx: &mut TestAppContext) {
         let base_text = "
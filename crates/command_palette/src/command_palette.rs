@@ -10,7 +10,7 @@ use std::{
 use client::parse_zed_link;
 use command_palette_hooks::{
     CommandInterceptItem, CommandInterceptResult, CommandPaletteFilter,
-    GlobalCommandPaletteInterceptor,
+    GlobalCommandPaletteInterceptor, GlobalCommandPaletteQueryHistory,
 };
 
 use fuzzy::{StringMatch, StringMatchCandidate};
@@ -118,6 +118,7 @@ impl CommandPalette {
             entity,
             commands,
             previous_focus_handle,
+            GlobalCommandPaletteQueryHistory::is_persisted(cx),
         );
 
         let picker = cx.new(|cx| {
@@ -172,16 +173,30 @@ struct Command {
     action: Box<dyn Action>,
 }
 
-#[derive(Default)]
 struct QueryHistory {
     history: Option<VecDeque<String>>,
     cursor: Option<usize>,
     prefix: Option<String>,
+    /// Whether queries are remembered across restarts.
+    persisted: bool,
 }
 
 impl QueryHistory {
+    fn new(persisted: bool) -> Self {
+        Self {
+            history: None,
+            cursor: None,
+            prefix: None,
+            persisted,
+        }
+    }
+
     fn history(&mut self) -> &mut VecDeque<String> {
+        let persisted = self.persisted;
         self.history.get_or_insert_with(|| {
+            if !persisted {
+                return VecDeque::new();
+            }
             COMMAND_PALETTE_HISTORY
                 .list_recent_queries()
                 .unwrap_or_default()
@@ -272,6 +287,7 @@ impl CommandPaletteDelegate {
         workspace: WeakEntity<Workspace>,
         commands: Vec<Command>,
         previous_focus_handle: FocusHandle,
+        persist_queries: bool,
     ) -> Self {
         Self {
             command_palette,
@@ -283,7 +299,7 @@ impl CommandPaletteDelegate {
             previous_focus_handle,
             latest_query: String::new(),
             updating_matches: None,
-            query_history: Default::default(),
+            query_history: QueryHistory::new(persist_queries),
         }
     }
 
@@ -580,11 +596,22 @@ impl PickerDelegate for CommandPaletteDelegate {
         self.matches.clear();
         self.commands.clear();
         let command_name = command.name.clone();
-        let latest_query = self.latest_query.clone();
+        let persisted = self.query_history.persisted;
+        // When queries aren't remembered, the invocation still counts towards the command's
+        // usage, but its query is left out and the ones remembered before are forgotten.
+        let latest_query = if persisted {
+            self.latest_query.clone()
+        } else {
+            String::new()
+        };
         cx.background_spawn(async move {
             COMMAND_PALETTE_HISTORY
                 .write_command_invocation(command_name, latest_query)
-                .await
+                .await?;
+            if !persisted {
+                COMMAND_PALETTE_HISTORY.forget_queries().await?;
+            }
+            anyhow::Ok(())
         })
         .detach_and_log_err(cx);
         let action = command.action;
@@ -1214,4 +1241,69 @@ mod tests {
             assert_eq!(palette.query(cx), "");
         });
     }
+
+    #[gpui::test]
+    async fn test_query_history_persistence(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        let project = Project::test(app_state.fs.clone(), [], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let editor = cx.new_window_entity(|window, cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_text("abcdef", window, cx);
+            editor
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(editor.clone()), None, true, window, cx);
+            editor.update(cx, |editor, cx| window.focus(&editor.focus_handle(cx), cx))
+        });
+        let recent_query = |cx: &mut VisualTestContext| {
+            cx.simulate_keystrokes("cmd-shift-p");
+            cx.run_until_parked();
+            cx.simulate_keystrokes("up");
+            cx.run_until_parked();
+            let query = workspace.update(cx, |workspace, cx| {
+                workspace
+                    .active_modal::<CommandPalette>(cx)
+                    .unwrap()
+                    .read(cx)
+                    .picker
+                    .read(cx)
+                    .query(cx)
+            });
+            cx.simulate_keystrokes("cmd-shift-p");
+            query
+        };
+
+        // A palette opened later, as after a restart, reads the queries from the database.
+        cx.simulate_keystrokes("cmd-shift-p");
+        cx.simulate_input("editor: backspace");
+        cx.simulate_keystrokes("enter");
+        cx.run_until_parked();
+        editor.read_with(cx, |editor, cx| assert_eq!(editor.text(cx), "abcde"));
+        assert!(
+            COMMAND_PALETTE_HISTORY
+                .list_recent_queries()
+                .unwrap()
+                .contains(&"editor: backspace".to_string())
+        );
+        assert_eq!(recent_query(cx), "editor: backspace");
+
+        // Without persistence, the query isn't written and the earlier ones are forgotten.
+        cx.update(|_, cx| GlobalCommandPaletteQueryHistory::set_persisted(cx, false));
+        assert_eq!(recent_query(cx), "");
+        cx.simulate_keystrokes("cmd-shift-p");
+        cx.simulate_input("editor backspace");
+        cx.simulate_keystrokes("enter");
+        cx.run_until_parked();
+        editor.read_with(cx, |editor, cx| assert_eq!(editor.text(cx), "abcd"));
+        let recent_queries = COMMAND_PALETTE_HISTORY.list_recent_queries().unwrap();
+        assert!(!recent_queries.contains(&"editor: backspace".to_string()));
+        assert!(!recent_queries.contains(&"editor backspace".to_string()));
+
+        cx.update(|_, cx| GlobalCommandPaletteQueryHistory::clear(cx));
+        assert_eq!(recent_query(cx), "");
+    }
 }
@@ -122,6 +122,12 @@ impl CommandPaletteDB {
         }
     }
 
+    query! {
+        pub async fn forget_queries() -> Result<()> {
+            UPDATE command_invocations SET user_query = "" WHERE user_query != ""
+        }
+    }
+
     query! {
         pub fn list_commands_used() -> Result<Vec<SerializedCommandUsage>> {
             SELECT command_name, COUNT(1), MAX(last_invoked)
@@ -151,3 +151,32 @@ impl GlobalCommandPaletteInterceptor {
         Some(handler(query, workspace, cx))
     }
 }
+
+/// Whether the queries run from the command palette are remembered across restarts.
+#[derive(Clone, Copy)]
+pub struct GlobalCommandPaletteQueryHistory {
+    persisted: bool,
+}
+
+impl Global for GlobalCommandPaletteQueryHistory {}
+
+impl GlobalCommandPaletteQueryHistory {
+    /// Sets whether queries are remembered across restarts.
+    pub fn set_persisted(cx: &mut App, persisted: bool) {
+        cx.set_global(Self { persisted });
+    }
+
+    /// Clears the global setting, so that queries are remembered again.
+    pub fn clear(cx: &mut App) {
+        if cx.has_global::<Self>() {
+            cx.remove_global::<Self>();
+        }
+    }
+
+    /// Returns whether queries are remembered across restarts, which they are unless told
+    /// otherwise.
+    pub fn is_persisted(cx: &App) -> bool {
+        cx.try_global::<Self>()
+            .is_none_or(|query_history| query_history.persisted)
+    }
+}
@@ -450,7 +450,7 @@ async fn predict_openai(
                     open_ai::MessageContent::Multipart(parts) => parts
                         .into_iter()
                         .filter_map(|p| match p {
-                            open_ai::MessagePart::Text { text } => Some(text),
+                            open_ai::MessagePart::Text { text, .. } => Some(text),
                             _ => None,
                         })
                         .collect::<Vec<_>>()
@@ -813,12 +813,23 @@ actions!(
         EnableBreakpoint,
         /// Edits the log message for a breakpoint.
         EditLogBreakpoint,
+        /// Adds or removes a bookmark at the current line.
+        ToggleBookmark,
+        /// Moves the cursor to the next bookmark in the editor.
+        GoToNextBookmark,
+        /// Moves the cursor to the previous bookmark in the editor.
+        GoToPreviousBookmark,
         /// Toggles automatic signature help.
         ToggleAutoSignatureHelp,
         /// Toggles inline git blame display.
         ToggleGitBlameInline,
         /// Opens the git commit for the blame at cursor.
         OpenGitBlameCommit,
+        /// Blames the file as it was before the commit that last changed the line at cursor,
+        /// in a read-only editor that steps further back in place when used again.
+        BlameBeforeCommit,
+        /// Goes back to the revision blamed before the last "blame before commit".
+        UndoBlameBeforeCommit,
         /// Toggles the diagnostics panel.
         ToggleDiagnostics,
         /// Toggles indent guides display.
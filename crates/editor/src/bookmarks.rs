@@ -0,0 +1,207 @@
+use collections::HashSet;
+use gpui::{App, Context, Entity, SharedString, Window};
+use language::{Buffer, Point};
+use multi_buffer::{Anchor, ToPoint as _};
+use project::bookmark_store::BookmarkStore;
+use ui::ActiveTheme as _;
+
+use crate::{
+    Direction, Editor, SelectionEffects,
+    actions::{GoToNextBookmark, GoToPreviousBookmark, ToggleBookmark},
+    scroll::Autoscroll,
+};
+
+enum BookmarkGutterHighlight {}
+
+impl Editor {
+    pub fn bookmark_store(&self) -> Option<Entity<BookmarkStore>> {
+        self.bookmark_store.clone()
+    }
+
+    pub fn toggle_bookmark(
+        &mut self,
+        _: &ToggleBookmark,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(bookmark_store) = self.bookmark_store.clone() else {
+            return;
+        };
+        let multi_buffer = self.buffer.read(cx);
+        let snapshot = multi_buffer.snapshot(cx);
+        let mut toggled_rows = HashSet::default();
+        let positions = self
+            .selections
+            .disjoint_anchors_arc()
+            .iter()
+            .filter_map(|selection| {
+                let row = selection.head().to_point(&snapshot).row;
+                let (buffer, position) =
+                    multi_buffer.text_anchor_for_position(Point::new(row, 0), cx)?;
+                let buffer_row = position
+                    .summary::<Point>(&buffer.read(cx).text_snapshot())
+                    .row;
+                // There might be multiple cursors on the same line, which should only toggle its
+                // bookmark once.
+                toggled_rows
+                    .insert((buffer.entity_id(), buffer_row))
+                    .then_some((buffer, position))
+            })
+            .collect::<Vec<_>>();
+
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            for (buffer, position) in positions {
+                bookmark_store.toggle_bookmark(&buffer, position, cx);
+            }
+        });
+    }
+
+    /// Returns the label of the bookmark on the line of the newest cursor, if that line is
+    /// bookmarked.
+    pub fn bookmark_label_at_cursor(&self, cx: &App) -> Option<SharedString> {
+        let bookmark_store = self.bookmark_store.as_ref()?;
+        let (buffer, position) = self.buffer_position_at_cursor(cx)?;
+        let row = position
+            .summary::<Point>(&buffer.read(cx).text_snapshot())
+            .row;
+        bookmark_store
+            .read(cx)
+            .bookmark_at_row(&buffer, row, cx)?
+            .label
+            .clone()
+    }
+
+    /// Sets the label of the bookmark on the line of the newest cursor, bookmarking the line if
+    /// needed.
+    pub fn set_bookmark_label_at_cursor(&mut self, label: Option<String>, cx: &mut Context<Self>) {
+        let Some(bookmark_store) = self.bookmark_store.clone() else {
+            return;
+        };
+        let Some((buffer, position)) = self.buffer_position_at_cursor(cx) else {
+            return;
+        };
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.set_bookmark_label(&buffer, position, label, cx);
+        });
+    }
+
+    pub fn go_to_next_bookmark(
+        &mut self,
+        _: &GoToNextBookmark,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.go_to_bookmark(Direction::Next, window, cx);
+    }
+
+    pub fn go_to_previous_bookmark(
+        &mut self,
+        _: &GoToPreviousBookmark,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.go_to_bookmark(Direction::Prev, window, cx);
+    }
+
+    fn go_to_bookmark(
+        &mut self,
+        direction: Direction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let cursor_row = self
+            .selections
+            .newest_anchor()
+            .head()
+            .to_point(&snapshot)
+            .row;
+        let rows = self
+            .bookmark_anchors(cx)
+            .into_iter()
+            .map(|anchor| anchor.to_point(&snapshot).row)
+            .collect::<Vec<_>>();
+
+        // Wrap around to the first (or last) bookmark when there's none past the cursor.
+        let row = match direction {
+            Direction::Next => rows
+                .iter()
+                .find(|row| **row > cursor_row)
+                .or_else(|| rows.first()),
+            Direction::Prev => rows
+                .iter()
+                .rev()
+                .find(|row| **row < cursor_row)
+                .or_else(|| rows.last()),
+        };
+        let Some(&row) = row else {
+            return;
+        };
+
+        let destination = Point::new(row, 0);
+        self.unfold_ranges(&[destination..destination], false, false, cx);
+        self.change_selections(
+            SelectionEffects::scroll(Autoscroll::center()),
+            window,
+            cx,
+            |selections| selections.select_ranges([destination..destination]),
+        );
+    }
+
+    pub(super) fn refresh_bookmark_highlights(&mut self, cx: &mut Context<Self>) {
+        if self.bookmark_store.is_none() {
+            return;
+        }
+        let ranges = self
+            .bookmark_anchors(cx)
+            .into_iter()
+            .map(|anchor| anchor..anchor)
+            .collect::<Vec<_>>();
+        if ranges.is_empty() {
+            self.clear_gutter_highlights::<BookmarkGutterHighlight>(cx);
+        } else {
+            self.highlight_gutter::<BookmarkGutterHighlight>(
+                ranges,
+                |cx| cx.theme().status().info,
+                cx,
+            );
+        }
+    }
+
+    /// Returns the positions of the bookmarks shown in this editor, in order.
+    fn bookmark_anchors(&self, cx: &App) -> Vec<Anchor> {
+        let Some(bookmark_store) = self.bookmark_store.as_ref() else {
+            return Vec::new();
+        };
+        let bookmark_store = bookmark_store.read(cx);
+        let multi_buffer = self.buffer.read(cx);
+        let snapshot = multi_buffer.snapshot(cx);
+        let mut anchors = multi_buffer
+            .all_buffers()
+            .into_iter()
+            .flat_map(|buffer| {
+                bookmark_store
+                    .bookmarks_for_buffer(&buffer, cx)
+                    .iter()
+                    .filter_map(|bookmark| {
+                        multi_buffer.buffer_anchor_to_anchor(&buffer, bookmark.position, cx)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        anchors.sort_by(|a, b| a.cmp(b, &snapshot));
+        anchors
+    }
+
+    fn buffer_position_at_cursor(&self, cx: &App) -> Option<(Entity<Buffer>, text::Anchor)> {
+        let multi_buffer = self.buffer.read(cx);
+        let snapshot = multi_buffer.snapshot(cx);
+        let row = self
+            .selections
+            .newest_anchor()
+            .head()
+            .to_point(&snapshot)
+            .row;
+        multi_buffer.text_anchor_for_position(Point::new(row, 0), cx)
+    }
+}
@@ -13,6 +13,7 @@
 //! If you're looking to improve Vim mode, you should check out Vim crate that wraps Editor and overrides its behavior.
 pub mod actions;
 pub mod blink_manager;
+mod bookmarks;
 mod bracket_colorization;
 mod clangd_ext;
 pub mod code_context_menus;
@@ -111,7 +112,7 @@ use futures::{
     future::{self, Shared, join},
 };
 use fuzzy::{StringMatch, StringMatchCandidate};
-use git::blame::{GitBlame, GlobalBlameRenderer};
+use git::blame::{BlameRevision, GitBlame, GlobalBlameRenderer};
 use gpui::{
     Action, Animation, AnimationExt, AnyElement, App, AppContext, AsyncWindowContext,
     AvailableSpace, Background, Bounds, ClickEvent, ClipboardEntry, ClipboardItem, Context,
@@ -159,6 +160,7 @@ use project::{
     CompletionResponse, CompletionSource, DisableAiSettings, DocumentHighlight, InlayHint, InlayId,
     InvalidationStrategy, Location, LocationLink, LspAction, PrepareRenameResponse, Project,
     ProjectItem, ProjectPath, ProjectTransaction, TaskSourceKind,
+    bookmark_store::BookmarkStore,
     debugger::{
         breakpoint_store::{
             Breakpoint, BreakpointEditAction, BreakpointSessionState, BreakpointState,
@@ -166,7 +168,7 @@ use project::{
         },
         session::{Session, SessionEvent},
     },
-    git_store::GitStoreEvent,
+    git_store::{GitStoreEvent, Repository},
     lsp_store::{
         BufferSemanticTokens, CacheInlayHints, CompletionDocumentation, FormatTrigger,
         LspFormatTarget, OpenLspBufferHandle, RefreshForServer,
@@ -1295,6 +1297,7 @@ pub struct Editor {
     tasks: BTreeMap<(BufferId, BufferRow), RunnableTasks>,
     tasks_update_task: Option<Task<()>>,
     breakpoint_store: Option<Entity<BreakpointStore>>,
+    bookmark_store: Option<Entity<BookmarkStore>>,
     gutter_breakpoint_indicator: (Option<PhantomBreakpointIndicator>, Option<Task<()>>),
     pub(crate) gutter_diff_review_indicator: (Option<PhantomDiffReviewIndicator>, Option<Task<()>>),
     pub(crate) diff_review_drag_state: Option<DiffReviewDragState>,
@@ -1832,6 +1835,23 @@ pub(crate) struct NavigationData {
     scroll_top_row: u32,
 }
 
+/// Navigation data for a position in a file that wasn't open when the entry was added to the
+/// navigation history, such as a jump restored from a previous session.
+#[derive(Debug, Clone, Copy)]
+pub struct RestoredNavigationData {
+    pub cursor_position: Point,
+}
+
+/// Returns the cursor position stored in an editor's navigation history entry.
+pub fn navigation_cursor_position(data: &(dyn Any + Send + Sync)) -> Option<Point> {
+    if let Some(data) = data.downcast_ref::<NavigationData>() {
+        Some(data.cursor_position)
+    } else {
+        data.downcast_ref::<RestoredNavigationData>()
+            .map(|data| data.cursor_position)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GotoDefinitionKind {
     Symbol,
@@ -2352,6 +2372,10 @@ impl Editor {
             (EditorMode::Full { .. }, Some(project)) => Some(project.read(cx).breakpoint_store()),
             _ => None,
         };
+        let bookmark_store = match (&mode, project.as_ref()) {
+            (EditorMode::Full { .. }, Some(project)) => Some(project.read(cx).bookmark_store()),
+            _ => None,
+        };
 
         let mut code_action_providers = Vec::new();
         let mut load_uncommitted_diff = None;
@@ -2523,6 +2547,7 @@ impl Editor {
             tasks: BTreeMap::default(),
 
             breakpoint_store,
+            bookmark_store,
             gutter_breakpoint_indicator: (None, None),
             gutter_diff_review_indicator: (None, None),
             diff_review_drag_state: None,
@@ -2622,6 +2647,14 @@ impl Editor {
                     cx.notify();
                 }));
         }
+        if let Some(bookmarks) = editor.bookmark_store.clone() {
+            editor
+                ._subscriptions
+                .push(cx.observe(&bookmarks, |editor, _, cx| {
+                    editor.refresh_bookmark_highlights(cx);
+                }));
+            editor.refresh_bookmark_highlights(cx);
+        }
         editor.tasks_update_task = Some(editor.refresh_runnables(window, cx));
         editor._subscriptions.extend(project_subscriptions);
 
@@ -7942,6 +7975,9 @@ impl Editor {
                 return Some(false);
             }
             let buffer = buffer.read(cx);
+            if buffer.is_large_file() {
+                return Some(false);
+            }
             let Some(file) = buffer.file() else {
                 return Some(true);
             };
@@ -20640,6 +20676,110 @@ impl Editor {
         self.do_stage_or_unstage_and_next(false, window, cx);
     }
 
+    pub fn stage_selection(
+        &mut self,
+        _: &::git::StageSelection,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.stage_or_unstage_selection(true, cx);
+    }
+
+    pub fn unstage_selection(
+        &mut self,
+        _: &::git::UnstageSelection,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.stage_or_unstage_selection(false, cx);
+    }
+
+    /// Stages or unstages the selected parts of the diff hunks in the selections, falling back
+    /// to whole hunks when nothing is selected.
+    fn stage_or_unstage_selection(&mut self, stage: bool, cx: &mut Context<Self>) {
+        let ranges = self.selections.disjoint_anchor_ranges().collect::<Vec<_>>();
+        if ranges.iter().all(|range| range.start == range.end) {
+            self.stage_or_unstage_diff_hunks(stage, ranges, cx);
+            return;
+        }
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        if self.delegate_stage_and_restore {
+            let mut hunks = Vec::<(MultiBufferDiffHunk, Range<usize>)>::new();
+            for range in ranges.iter().filter(|range| range.start != range.end) {
+                for hunk in self.diff_hunks_in_ranges(std::slice::from_ref(range), &snapshot) {
+                    let Some(selection) = selected_hunk_buffer_range(&hunk, range, &snapshot)
+                    else {
+                        continue;
+                    };
+                    if let Some((_, existing)) = hunks.iter_mut().find(|(existing, _)| {
+                        existing.buffer_id == hunk.buffer_id
+                            && existing.diff_base_byte_range == hunk.diff_base_byte_range
+                    }) {
+                        existing.start = existing.start.min(selection.start);
+                        existing.end = existing.end.max(selection.end);
+                    } else {
+                        hunks.push((hunk, selection));
+                    }
+                }
+            }
+            if !hunks.is_empty() {
+                cx.emit(EditorEvent::PartialStageOrUnstageRequested { stage, hunks });
+            }
+            return;
+        }
+        // Diffs that aren't against the index, like the one between two files, can't be staged.
+        if !self
+            .diff_hunks_in_ranges(&ranges, &snapshot)
+            .any(|hunk| self.can_stage_diff_hunk(&hunk, cx))
+        {
+            return;
+        }
+        let task = self.save_buffers_for_ranges_if_needed(&ranges, cx);
+        cx.spawn(async move |this, cx| {
+            task.await?;
+            this.update(cx, |this, cx| {
+                let snapshot = this.buffer.read(cx).snapshot(cx);
+                let mut hunks_by_buffer =
+                    HashMap::<BufferId, Vec<buffer_diff::PartialHunk>>::default();
+                for range in ranges.iter().filter(|range| range.start != range.end) {
+                    for hunk in this.diff_hunks_in_ranges(std::slice::from_ref(range), &snapshot) {
+                        let Some(partial_hunk) = partial_diff_hunk(&hunk, range, &snapshot) else {
+                            continue;
+                        };
+                        let hunks = hunks_by_buffer.entry(hunk.buffer_id).or_default();
+                        // Merge the parts of a hunk that several selections overlap.
+                        if let Some(existing) = hunks.iter_mut().find(|existing| {
+                            existing.diff_base_byte_range == partial_hunk.diff_base_byte_range
+                        }) {
+                            existing.buffer_selection.start = existing
+                                .buffer_selection
+                                .start
+                                .min(partial_hunk.buffer_selection.start);
+                            existing.buffer_selection.end = existing
+                                .buffer_selection
+                                .end
+                                .max(partial_hunk.buffer_selection.end);
+                            existing.base_selection.start = existing
+                                .base_selection
+                                .start
+                                .min(partial_hunk.base_selection.start);
+                            existing.base_selection.end = existing
+                                .base_selection
+                                .end
+                                .max(partial_hunk.base_selection.end);
+                        } else {
+                            hunks.push(partial_hunk);
+                        }
+                    }
+                }
+                for (buffer_id, hunks) in hunks_by_buffer {
+                    this.do_stage_or_unstage_partial(stage, buffer_id, &hunks, cx);
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn stage_or_unstage_diff_hunks(
         &mut self,
         stage: bool,
@@ -20787,6 +20927,34 @@ impl Editor {
         None
     }
 
+    /// Whether staging `hunk` changes the index, which only holds for diffs against it or for
+    /// editors that delegate staging to the editor they are paired with.
+    pub(crate) fn can_stage_diff_hunk(&self, hunk: &MultiBufferDiffHunk, cx: &App) -> bool {
+        self.delegate_stage_and_restore
+            || self
+                .buffer
+                .read(cx)
+                .diff_for(hunk.buffer_id)
+                .is_some_and(|diff| diff.read(cx).secondary_diff().is_some())
+    }
+
+    pub(crate) fn do_stage_or_unstage_partial(
+        &self,
+        stage: bool,
+        buffer_id: BufferId,
+        hunks: &[buffer_diff::PartialHunk],
+        cx: &mut App,
+    ) -> Option<()> {
+        let project = self.project()?;
+        let buffer = project.read(cx).buffer_for_id(buffer_id, cx)?;
+        let diff = self.buffer.read(cx).diff_for(buffer_id)?;
+        let buffer_snapshot = buffer.read(cx).snapshot();
+        diff.update(cx, |diff, cx| {
+            diff.stage_or_unstage_partial_hunks(stage, hunks, &buffer_snapshot, cx)
+        });
+        None
+    }
+
     pub fn expand_selected_diff_hunks(&mut self, cx: &mut Context<Self>) {
         let ranges: Vec<_> = self
             .selections
@@ -22895,6 +23063,99 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Option<()> {
+        let (buffer_id, blame_entry) = self.blame_entry_at_cursor(window, cx)?;
+        let blame = self.blame.as_ref()?;
+        let renderer = cx.global::<GlobalBlameRenderer>().0.clone();
+        let repo = blame.read(cx).repository(cx, buffer_id)?;
+        let workspace = self.workspace()?.downgrade();
+        renderer.open_blame_commit(blame_entry, repo, workspace, window, cx);
+        None
+    }
+
+    pub fn blame_before_commit(
+        &mut self,
+        _: &BlameBeforeCommit,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((buffer_id, blame_entry)) = self.blame_entry_at_cursor(window, cx) else {
+            return;
+        };
+        let Some(repository) = self
+            .blame
+            .as_ref()
+            .and_then(|blame| blame.read(cx).repository(cx, buffer_id))
+        else {
+            return;
+        };
+        self.blame_before(&blame_entry, repository, window, cx);
+    }
+
+    /// Blames the file as it was before the commit of `blame_entry`. An editor already showing
+    /// a past revision moves back in place; otherwise a read-only editor is opened for it.
+    pub fn blame_before(
+        &mut self,
+        blame_entry: &BlameEntry,
+        repository: Entity<Repository>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(revision) = BlameRevision::before(blame_entry) else {
+            return;
+        };
+        if let Some(blame) = self.blame.as_ref()
+            && blame.update(cx, |blame, cx| {
+                blame.blame_before_commit(revision.clone(), cx)
+            })
+        {
+            return;
+        }
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        let Some(workspace) = self.workspace() else {
+            return;
+        };
+
+        let buffer = cx.new(|cx| {
+            let mut buffer = Buffer::local("", cx);
+            buffer.set_capability(Capability::ReadOnly, cx);
+            buffer
+        });
+        let multi_buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+        let editor = cx.new(|cx| {
+            let mut editor =
+                Editor::for_multibuffer(multi_buffer.clone(), Some(project.clone()), window, cx);
+            editor.set_read_only(true);
+            let blame = cx
+                .new(|cx| GitBlame::for_revision(multi_buffer, project, repository, revision, cx));
+            editor.blame_subscription =
+                Some(cx.observe_in(&blame, window, |_, _, _, cx| cx.notify()));
+            editor.blame = Some(blame);
+            editor.show_git_blame_gutter = true;
+            editor
+        });
+        workspace.update(cx, |workspace, cx| {
+            workspace.add_item_to_active_pane(Box::new(editor), None, true, window, cx);
+        });
+    }
+
+    pub fn undo_blame_before_commit(
+        &mut self,
+        _: &UndoBlameBeforeCommit,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(blame) = self.blame.as_ref() {
+            blame.update(cx, |blame, cx| blame.undo_blame_before_commit(cx));
+        }
+    }
+
+    fn blame_entry_at_cursor(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<(BufferId, BlameEntry)> {
         let blame = self.blame.as_ref()?;
         let snapshot = self.snapshot(window, cx);
         let cursor = self
@@ -22902,7 +23163,7 @@ impl Editor {
             .newest::<Point>(&snapshot.display_snapshot)
             .head();
         let (buffer, point, _) = snapshot.buffer_snapshot().point_to_buffer_point(cursor)?;
-        let (_, blame_entry) = blame
+        blame
             .update(cx, |blame, cx| {
                 blame
                     .blame_for_rows(
@@ -22915,12 +23176,7 @@ impl Editor {
                     )
                     .next()
             })
-            .flatten()?;
-        let renderer = cx.global::<GlobalBlameRenderer>().0.clone();
-        let repo = blame.read(cx).repository(cx, buffer.remote_id())?;
-        let workspace = self.workspace()?.downgrade();
-        renderer.open_blame_commit(blame_entry, repo, workspace, window, cx);
-        None
+            .flatten()
     }
 
     pub fn git_blame_inline_enabled(&self) -> bool {
@@ -24060,6 +24316,7 @@ impl Editor {
                 self.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
                 self.colorize_brackets(false, cx);
                 self.refresh_selected_text_highlights(true, window, cx);
+                self.refresh_bookmark_highlights(cx);
                 cx.emit(EditorEvent::ExcerptsAdded {
                     buffer: buffer.clone(),
                     predecessor: *predecessor,
@@ -25528,6 +25785,86 @@ fn edit_for_markdown_paste<'a>(
     (range, new_text)
 }
 
+/// Finds the part of `hunk` that the selection `range` covers, as offsets in the hunk's buffer
+/// and in its diff base text. The deleted lines of a hunk are displayed before the added ones.
+fn partial_diff_hunk(
+    hunk: &MultiBufferDiffHunk,
+    range: &Range<Anchor>,
+    snapshot: &MultiBufferSnapshot,
+) -> Option<buffer_diff::PartialHunk> {
+    let buffer = snapshot.buffer_for_excerpt(hunk.excerpt_id)?;
+    let base_text = snapshot.diff_for_buffer_id(hunk.buffer_id)?.base_text();
+    let buffer_range = hunk.buffer_range.to_offset(buffer);
+    let base_range = hunk.diff_base_byte_range.start.0..hunk.diff_base_byte_range.end.0;
+    let before = (buffer_range.start, base_range.start);
+    let after = (buffer_range.end, base_range.end);
+
+    let resolve = |anchor: &Anchor| {
+        match snapshot
+            .latest_excerpt_id(anchor.excerpt_id)
+            .cmp(&hunk.excerpt_id, snapshot)
+        {
+            Ordering::Less => return before,
+            Ordering::Greater => return after,
+            Ordering::Equal => {}
+        }
+        if let Some(base_anchor) = anchor.diff_base_anchor {
+            let offset = base_anchor.to_offset(base_text);
+            if offset < base_range.start {
+                before
+            } else if offset > base_range.end {
+                after
+            } else {
+                (buffer_range.start, offset)
+            }
+        } else {
+            let offset = anchor.text_anchor.to_offset(buffer);
+            if offset < buffer_range.start {
+                before
+            } else {
+                (offset.min(buffer_range.end), base_range.end)
+            }
+        }
+    };
+
+    let (buffer_start, base_start) = resolve(&range.start);
+    let (buffer_end, base_end) = resolve(&range.end);
+    if buffer_start >= buffer_end && base_start >= base_end {
+        return None;
+    }
+    Some(buffer_diff::PartialHunk {
+        buffer_range: hunk.buffer_range.clone(),
+        diff_base_byte_range: base_range,
+        buffer_selection: buffer_start..buffer_end,
+        base_selection: base_start..base_end,
+    })
+}
+
+/// Finds the offsets of `hunk`'s buffer that the selection `range` covers. Unlike
+/// [`partial_diff_hunk`], this doesn't look into the deleted lines, so it suits editors that
+/// show the diff base text as their buffer.
+fn selected_hunk_buffer_range(
+    hunk: &MultiBufferDiffHunk,
+    range: &Range<Anchor>,
+    snapshot: &MultiBufferSnapshot,
+) -> Option<Range<usize>> {
+    let buffer = snapshot.buffer_for_excerpt(hunk.excerpt_id)?;
+    let hunk_range = hunk.buffer_range.to_offset(buffer);
+    let resolve = |anchor: &Anchor| match snapshot
+        .latest_excerpt_id(anchor.excerpt_id)
+        .cmp(&hunk.excerpt_id, snapshot)
+    {
+        Ordering::Less => hunk_range.start,
+        Ordering::Greater => hunk_range.end,
+        Ordering::Equal => anchor
+            .text_anchor
+            .to_offset(buffer)
+            .clamp(hunk_range.start, hunk_range.end),
+    };
+    let selection = resolve(&range.start)..resolve(&range.end);
+    (selection.start < selection.end).then_some(selection)
+}
+
 fn process_completion_for_edit(
     completion: &Completion,
     intent: CompletionIntent,
@@ -27790,6 +28127,12 @@ pub enum EditorEvent {
         stage: bool,
         hunks: Vec<MultiBufferDiffHunk>,
     },
+    /// Requests staging or unstaging the selected part of each hunk, given as the range of
+    /// the hunk's buffer that the selections cover.
+    PartialStageOrUnstageRequested {
+        stage: bool,
+        hunks: Vec<(MultiBufferDiffHunk, Range<usize>)>,
+    },
     OpenExcerptsRequested {
         selections_by_buffer: HashMap<BufferId, (Vec<Range<BufferOffset>>, Option<u32>)>,
         split: bool,
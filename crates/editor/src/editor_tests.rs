@@ -33037,3 +33037,161 @@ comment */ˇ»;"#},
         assert_text_with_selections(editor, indoc! {r#"let arr = [«1, 2, 3]ˇ»;"#}, cx);
     });
 }
+
+#[gpui::test]
+async fn test_blame_before_commit(cx: &mut TestAppContext) {
+    use ::git::{
+        blame::{Blame, RevisionBlame},
+        repository::repo_path,
+    };
+
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/my-repo"),
+        json!({
+            ".git": {},
+            "new.txt": "one\ntwo\nthree\n",
+        }),
+    )
+    .await;
+
+    let blame_entry = |sha: char, range: Range<u32>, previous: Option<(char, &str)>| BlameEntry {
+        sha: sha.to_string().repeat(40).parse().unwrap(),
+        range,
+        previous: previous.map(|(sha, path)| format!("{} {path}", sha.to_string().repeat(40))),
+        ..Default::default()
+    };
+    let dot_git = Path::new(path!("/my-repo/.git"));
+    // The third commit renamed `old.txt` to `new.txt` and changed it, the second changed its
+    // second line, and the first added it.
+    fs.set_blame_for_repo(
+        dot_git,
+        vec![(
+            repo_path("new.txt"),
+            Blame {
+                entries: vec![blame_entry('3', 0..3, Some(('2', "old.txt")))],
+                ..Default::default()
+            },
+        )],
+    );
+    fs.set_blame_at_revision(
+        dot_git,
+        "2".repeat(40),
+        repo_path("old.txt"),
+        RevisionBlame {
+            text: "one\nTWO\n".into(),
+            blame: Blame {
+                entries: vec![
+                    blame_entry('1', 0..1, None),
+                    blame_entry('2', 1..2, Some(('1', "old.txt"))),
+                ],
+                ..Default::default()
+            },
+        },
+    );
+    fs.set_blame_at_revision(
+        dot_git,
+        "1".repeat(40),
+        repo_path("old.txt"),
+        RevisionBlame {
+            text: "one\ntwo\n".into(),
+            blame: Blame {
+                entries: vec![blame_entry('1', 0..2, None)],
+                ..Default::default()
+            },
+        },
+    );
+
+    let project = Project::test(fs, [path!("/my-repo").as_ref()], cx).await;
+    let window = cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+    let workspace = window
+        .read_with(cx, |mw, _| mw.workspace().clone())
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*window, cx);
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let editor = workspace
+        .update_in(cx, |workspace, window, cx| {
+            workspace.open_path((worktree_id, rel_path("new.txt")), None, true, window, cx)
+        })
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    editor.update_in(cx, |editor, window, cx| {
+        window.focus(&editor.focus_handle(cx), cx);
+        editor.toggle_git_blame(&::git::Blame, window, cx);
+    });
+    cx.run_until_parked();
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.blame_before_commit(&BlameBeforeCommit, window, cx);
+    });
+    cx.run_until_parked();
+
+    let revision_editor = workspace.update(cx, |workspace, cx| {
+        workspace.active_item_as::<Editor>(cx).unwrap()
+    });
+    assert_ne!(revision_editor, editor);
+    let assert_revision = |text: &str, title: &str, shas: &[char], cx: &mut VisualTestContext| {
+        revision_editor.update(cx, |editor, cx| {
+            assert!(editor.read_only(cx));
+            assert_eq!(editor.text(cx), text);
+            assert_eq!(editor.buffer().read(cx).title(cx), title);
+            let blame = editor.blame().unwrap().clone();
+            let buffer_id = editor
+                .buffer()
+                .read(cx)
+                .as_singleton()
+                .unwrap()
+                .read(cx)
+                .remote_id();
+            let rows = (0..shas.len() as u32)
+                .map(|row| RowInfo {
+                    buffer_id: Some(buffer_id),
+                    buffer_row: Some(row),
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>();
+            let blamed_shas = blame.update(cx, |blame, cx| {
+                blame
+                    .blame_for_rows(&rows, cx)
+                    .map(|entry| entry.unwrap().1.sha.to_string())
+                    .collect::<Vec<_>>()
+            });
+            assert_eq!(
+                blamed_shas,
+                shas.iter()
+                    .map(|sha| sha.to_string().repeat(40))
+                    .collect::<Vec<_>>()
+            );
+        });
+    };
+    assert_revision("one\nTWO\n", "old.txt (before 3333333)", &['1', '2'], cx);
+
+    // Blaming before a commit again goes further back in the same editor.
+    revision_editor.update_in(cx, |editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([Point::new(1, 0)..Point::new(1, 0)])
+        });
+        editor.blame_before_commit(&BlameBeforeCommit, window, cx);
+    });
+    cx.run_until_parked();
+    assert_eq!(
+        workspace.update(cx, |workspace, cx| workspace.active_item_as::<Editor>(cx)),
+        Some(revision_editor.clone())
+    );
+    assert_revision("one\ntwo\n", "old.txt (before 2222222)", &['1', '1'], cx);
+
+    // Undoing steps forward again, but not past the first revision.
+    for _ in 0..2 {
+        revision_editor.update_in(cx, |editor, window, cx| {
+            editor.undo_blame_before_commit(&UndoBlameBeforeCommit, window, cx);
+        });
+        cx.run_until_parked();
+        assert_revision("one\nTWO\n", "old.txt (before 3333333)", &['1', '2'], cx);
+    }
+}
@@ -6,6 +6,7 @@ use git::{
     GitHostingProviderRegistry, Oid,
     blame::{Blame, BlameEntry},
     commit::ParsedCommitMessage,
+    repository::RepoPath,
 };
 use gpui::{
     AnyElement, App, AppContext as _, Context, Entity, Hsla, ScrollHandle, Subscription, Task,
@@ -71,6 +72,35 @@ struct GitBlameBuffer {
     commit_details: HashMap<Oid, ParsedCommitMessage>,
 }
 
+/// A step of "blame before commit": the parent revision of a commit, blamed instead of it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlameRevision {
+    /// The commit that's looked past.
+    pub commit: Oid,
+    /// The parent of `commit`, whose version of the file is shown.
+    pub parent: Oid,
+    /// The file's path in `parent`, which differs from its current path if it was renamed since.
+    pub path: RepoPath,
+}
+
+impl BlameRevision {
+    /// The revision to blame before the commit of `entry`, unless the commit added the file.
+    pub fn before(entry: &BlameEntry) -> Option<Self> {
+        let (parent, path) = entry.previous_revision()?;
+        Some(Self {
+            commit: entry.sha,
+            parent,
+            path,
+        })
+    }
+}
+
+struct RevisionHistory {
+    repository: Entity<Repository>,
+    /// The revisions blamed so far, the one shown last.
+    revisions: Vec<BlameRevision>,
+}
+
 pub struct GitBlame {
     project: Entity<Project>,
     multi_buffer: WeakEntity<MultiBuffer>,
@@ -79,6 +109,9 @@ pub struct GitBlame {
     focused: bool,
     changed_while_blurred: bool,
     user_triggered: bool,
+    /// Set when blaming a past revision of a file in a read-only buffer, instead of the buffers
+    /// of the multibuffer as they are.
+    revision_history: Option<RevisionHistory>,
     regenerate_on_edit_task: Task<Result<()>>,
     _regenerate_subscriptions: Vec<Subscription>,
 }
@@ -251,6 +284,7 @@ impl GitBlame {
             buffers: HashMap::default(),
             user_triggered,
             focused,
+            revision_history: None,
             changed_while_blurred: false,
             task: Task::ready(Ok(())),
             regenerate_on_edit_task: Task::ready(Ok(())),
@@ -264,7 +298,38 @@ impl GitBlame {
         this
     }
 
+    /// Blames the file at `revision` in `multi_buffer`, which must be a singleton of a
+    /// read-only buffer whose text is replaced by the file's contents there.
+    pub fn for_revision(
+        multi_buffer: Entity<MultiBuffer>,
+        project: Entity<Project>,
+        repository: Entity<Repository>,
+        revision: BlameRevision,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut this = Self {
+            project,
+            multi_buffer: multi_buffer.downgrade(),
+            buffers: HashMap::default(),
+            user_triggered: true,
+            focused: true,
+            revision_history: Some(RevisionHistory {
+                repository,
+                revisions: vec![revision],
+            }),
+            changed_while_blurred: false,
+            task: Task::ready(Ok(())),
+            regenerate_on_edit_task: Task::ready(Ok(())),
+            _regenerate_subscriptions: Vec::new(),
+        };
+        this.generate(cx);
+        this
+    }
+
     pub fn repository(&self, cx: &App, id: BufferId) -> Option<Entity<Repository>> {
+        if let Some(history) = &self.revision_history {
+            return Some(history.repository.clone());
+        }
         self.project
             .read(cx)
             .git_store()
@@ -277,6 +342,35 @@ impl GitBlame {
         !self.buffers.is_empty()
     }
 
+    /// The revisions blamed so far when blaming a past revision of a file, the one shown last.
+    /// Empty otherwise.
+    pub fn revisions(&self) -> &[BlameRevision] {
+        self.revision_history
+            .as_ref()
+            .map_or(&[], |history| history.revisions.as_slice())
+    }
+
+    /// Shows and blames `revision` in place of the current one. Only a blame of a past
+    /// revision can step further back; returns `false` otherwise.
+    pub fn blame_before_commit(&mut self, revision: BlameRevision, cx: &mut Context<Self>) -> bool {
+        let Some(history) = &mut self.revision_history else {
+            return false;
+        };
+        history.revisions.push(revision);
+        self.generate(cx);
+        true
+    }
+
+    /// Goes back to the revision shown before the last [`Self::blame_before_commit`].
+    pub fn undo_blame_before_commit(&mut self, cx: &mut Context<Self>) {
+        if let Some(history) = &mut self.revision_history
+            && history.revisions.len() > 1
+        {
+            history.revisions.pop();
+            self.generate(cx);
+        }
+    }
+
     pub fn details_for_entry(
         &self,
         buffer: BufferId,
@@ -495,6 +589,10 @@ impl GitBlame {
             self.changed_while_blurred = true;
             return;
         }
+        if self.revision_history.is_some() {
+            self.generate_for_revision(cx);
+            return;
+        }
         let buffers_to_blame = self
             .multi_buffer
             .update(cx, |multi_buffer, _| {
@@ -608,32 +706,122 @@ impl GitBlame {
                     );
                 }
                 cx.notify();
-                if !all_errors.is_empty() {
-                    this.project.update(cx, |_, cx| {
-                        let all_errors = all_errors
-                            .into_iter()
-                            .map(|e| format!("{e:#}"))
-                            .dedup()
-                            .collect::<Vec<_>>();
-                        let all_errors = all_errors.join(", ");
-                        if this.user_triggered {
-                            log::error!("failed to get git blame data: {all_errors}");
-                            cx.emit(project::Event::Toast {
-                                notification_id: "git-blame".into(),
-                                message: all_errors,
-                                link: None,
-                            });
-                        } else {
-                            // If we weren't triggered by a user, we just log errors in the background, instead of sending
-                            // notifications.
-                            log::debug!("failed to get git blame data: {all_errors}");
-                        }
+                this.report_errors(all_errors, cx);
+            })
+        });
+    }
+
+    fn generate_for_revision(&mut self, cx: &mut Context<Self>) {
+        let Some(history) = &self.revision_history else {
+            return;
+        };
+        let Some(revision) = history.revisions.last().cloned() else {
+            return;
+        };
+        let Some(multi_buffer) = self.multi_buffer.upgrade() else {
+            return;
+        };
+        let Some(buffer) = multi_buffer.read(cx).as_singleton() else {
+            return;
+        };
+        let repository = history.repository.clone();
+        let remote_url = repository.read(cx).default_remote_url();
+        let provider_registry = GitHostingProviderRegistry::default_global(cx);
+        let languages = self.project.read(cx).languages().clone();
+        let blame = repository.update(cx, |repository, cx| {
+            repository.blame_at_revision(revision.parent.to_string(), revision.path.clone(), cx)
+        });
+
+        self.task = cx.spawn(async move |this, cx| {
+            let blame = async { blame.await? }.await;
+            let language = languages
+                .load_language_for_file_path(revision.path.as_std_path())
+                .await
+                .ok();
+
+            this.update(cx, |this, cx| {
+                let blame = match blame {
+                    Ok(blame) => blame,
+                    Err(error) => {
+                        this.report_errors(vec![error], cx);
+                        return;
+                    }
+                };
+                buffer.update(cx, |buffer, cx| {
+                    buffer.set_text(blame.text, cx);
+                    buffer.set_language(language, cx);
+                });
+                multi_buffer.update(cx, |multi_buffer, cx| {
+                    multi_buffer.set_title(
+                        format!(
+                            "{} (before {})",
+                            revision.path.as_unix_str(),
+                            revision.commit.display_short()
+                        ),
+                        cx,
+                    );
+                });
+
+                let snapshot = buffer.read(cx).snapshot();
+                let buffer_edits = buffer.update(cx, |buffer, _| buffer.subscribe());
+                let entries =
+                    build_blame_entry_sum_tree(blame.blame.entries, snapshot.max_point().row);
+                let commit_details = blame
+                    .blame
+                    .messages
+                    .into_iter()
+                    .map(|(oid, message)| {
+                        let parsed_commit_message = ParsedCommitMessage::parse(
+                            oid.to_string(),
+                            message,
+                            remote_url.as_deref(),
+                            Some(provider_registry.clone()),
+                        );
+                        (oid, parsed_commit_message)
                     })
-                }
+                    .collect();
+                this.buffers.clear();
+                this.buffers.insert(
+                    snapshot.remote_id(),
+                    GitBlameBuffer {
+                        buffer_edits,
+                        buffer_snapshot: snapshot,
+                        entries,
+                        commit_details,
+                    },
+                );
+                cx.notify();
             })
         });
     }
 
+    fn report_errors(&mut self, errors: Vec<anyhow::Error>, cx: &mut Context<Self>) {
+        if errors.is_empty() {
+            return;
+        }
+        let user_triggered = self.user_triggered;
+        self.project.update(cx, |_, cx| {
+            let all_errors = errors
+                .into_iter()
+                .map(|e| format!("{e:#}"))
+                .dedup()
+                .collect::<Vec<_>>();
+            let all_errors = all_errors.join(", ");
+            if user_triggered {
+                log::error!("failed to get git blame data: {all_errors}");
+                cx.emit(project::Event::Toast {
+                    notification_id: "git-blame".into(),
+                    message: all_errors,
+                    link: None,
+                });
+            } else {
+                // If we weren't triggered by a user, we just log errors in the background, instead of sending
+                // notifications.
+                log::debug!("failed to get git blame data: {all_errors}");
+            }
+        })
+    }
+
     fn regenerate_on_edit(&mut self, cx: &mut Context<Self>) {
         // todo(lw): hot foreground spawn
         self.regenerate_on_edit_task = cx.spawn(async move |this, cx| {
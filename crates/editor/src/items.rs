@@ -1,7 +1,7 @@
 use crate::{
     Anchor, Autoscroll, BufferSerialization, Capability, Editor, EditorEvent, EditorSettings,
     ExcerptId, ExcerptRange, FormatTarget, MultiBuffer, MultiBufferSnapshot, NavigationData,
-    ReportEditorEvent, SelectionEffects, ToPoint as _,
+    ReportEditorEvent, RestoredNavigationData, SelectionEffects, ToPoint as _,
     display_map::HighlightKey,
     editor_settings::SeedQuerySetting,
     persistence::{DB, SerializedEditor},
@@ -635,6 +635,17 @@ impl Item for Editor {
                 );
                 true
             }
+        } else if let Some(data) = data.downcast_ref::<RestoredNavigationData>() {
+            let buffer = self.buffer.read(cx).read(cx);
+            let offset = buffer.clip_point(data.cursor_position, Bias::Left);
+            drop(buffer);
+            self.change_selections(
+                SelectionEffects::scroll(Autoscroll::center()).nav_history(false),
+                window,
+                cx,
+                |s| s.select_ranges([offset..offset]),
+            );
+            true
         } else {
             false
         }
@@ -1671,17 +1682,22 @@ impl SearchableItem for Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let text = self.buffer.read(cx);
-        let text = text.snapshot(cx);
-        let text = text.text_for_range(identifier.clone()).collect::<Vec<_>>();
+        let snapshot = self.buffer.read(cx);
+        let snapshot = snapshot.snapshot(cx);
+        let text = snapshot
+            .text_for_range(identifier.clone())
+            .collect::<Vec<_>>();
         let text: Cow<_> = if text.len() == 1 {
             text.first().cloned().unwrap().into()
         } else {
             let joined_chunks = text.join("");
             joined_chunks.into()
         };
+        let language = snapshot
+            .point_to_buffer_offset(identifier.start)
+            .and_then(|(buffer, _)| buffer.language());
 
-        if let Some(replacement) = query.replacement_for(&text) {
+        if let Some(replacement) = query.replacement_for(&text, language) {
             self.transact(window, cx, |this, _, cx| {
                 this.edit([(identifier.clone(), Arc::from(&*replacement))], cx);
             });
@@ -1695,16 +1711,16 @@ impl SearchableItem for Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let text = self.buffer.read(cx);
-        let text = text.snapshot(cx);
+        let snapshot = self.buffer.read(cx);
+        let snapshot = snapshot.snapshot(cx);
         let mut edits = vec![];
 
-        // A regex might have replacement variables so we cannot apply
+        // A regex or structural query might have replacement variables so we cannot apply
         // the same replacement to all matches
-        if query.is_regex() {
+        if query.is_regex() || query.is_structural() {
             edits = matches
                 .filter_map(|m| {
-                    let text = text.text_for_range(m.clone()).collect::<Vec<_>>();
+                    let text = snapshot.text_for_range(m.clone()).collect::<Vec<_>>();
 
                     let text: Cow<_> = if text.len() == 1 {
                         text.first().cloned().unwrap().into()
@@ -1712,9 +1728,12 @@ impl SearchableItem for Editor {
                         let joined_chunks = text.join("");
                         joined_chunks.into()
                     };
+                    let language = snapshot
+                        .point_to_buffer_offset(m.start)
+                        .and_then(|(buffer, _)| buffer.language());
 
                     query
-                        .replacement_for(&text)
+                        .replacement_for(&text, language)
                         .map(|replacement| (m.clone(), Arc::from(&*replacement)))
                 })
                 .collect();
@@ -216,6 +216,18 @@ pub fn deploy_context_menu(
                     .is_some()
             });
 
+        let has_selected_diff_hunks =
+            has_selections && (has_git_repo || editor.delegate_stage_and_restore) && {
+                let ranges = editor
+                    .selections
+                    .disjoint_anchor_ranges()
+                    .filter(|range| range.start != range.end)
+                    .collect::<Vec<_>>();
+                editor
+                    .diff_hunks_in_ranges(&ranges, buffer)
+                    .any(|hunk| editor.can_stage_diff_hunk(&hunk, cx))
+            };
+
         let evaluate_selection = window.is_action_available(&EvaluateSelectedText, cx);
         let run_to_cursor = window.is_action_available(&RunToCursor, cx);
         let disable_ai = DisableAiSettings::is_ai_disabled_for_buffer(
@@ -315,7 +327,13 @@ pub fn deploy_context_menu(
                     !has_git_repo,
                     "View File History",
                     Box::new(git::FileHistory),
-                );
+                )
+                .when(has_selected_diff_hunks, |builder| {
+                    builder
+                        .separator()
+                        .action("Stage Selection", Box::new(git::StageSelection))
+                        .action("Unstage Selection", Box::new(git::UnstageSelection))
+                });
             match focus {
                 Some(focus) => builder.context(focus),
                 None => builder,
@@ -3,7 +3,7 @@ use std::{
     sync::Arc,
 };
 
-use buffer_diff::{BufferDiff, BufferDiffSnapshot};
+use buffer_diff::{BufferDiff, BufferDiffSnapshot, PartialHunk};
 use collections::HashMap;
 
 use gpui::{Action, AppContext as _, Entity, EventEmitter, Focusable, Subscription, WeakEntity};
@@ -16,7 +16,7 @@ use multi_buffer::{
 use project::Project;
 use rope::Point;
 use settings::DiffViewStyle;
-use text::{Bias, BufferId, OffsetRangeExt as _, Patch, ToPoint as _};
+use text::{Bias, BufferId, OffsetRangeExt as _, Patch, ToOffset as _, ToPoint as _};
 use ui::{
     App, Context, InteractiveElement as _, IntoElement as _, ParentElement as _, Render,
     Styled as _, Window, div,
@@ -164,6 +164,36 @@ fn translate_lhs_hunks_to_rhs(
     translated
 }
 
+/// Translates the selected base text of hunks in the left-hand editor into partial hunks of
+/// the right-hand buffers. The left-hand side only shows deleted lines, so no added lines are
+/// selected.
+fn translate_lhs_partial_hunks_to_rhs(
+    lhs_hunks: &[(MultiBufferDiffHunk, Range<usize>)],
+    splittable: &SplittableEditor,
+    cx: &App,
+) -> Vec<(BufferId, PartialHunk)> {
+    let rhs_snapshot = splittable.rhs_multibuffer.read(cx).snapshot(cx);
+    lhs_hunks
+        .iter()
+        .filter_map(|(lhs_hunk, base_selection)| {
+            let rhs_hunk =
+                translate_lhs_hunks_to_rhs(std::slice::from_ref(lhs_hunk), splittable, cx).pop()?;
+            let buffer = rhs_snapshot.buffer_for_excerpt(rhs_hunk.excerpt_id)?;
+            let buffer_start = rhs_hunk.buffer_range.start.to_offset(buffer);
+            Some((
+                rhs_hunk.buffer_id,
+                PartialHunk {
+                    buffer_range: rhs_hunk.buffer_range,
+                    diff_base_byte_range: rhs_hunk.diff_base_byte_range.start.0
+                        ..rhs_hunk.diff_base_byte_range.end.0,
+                    buffer_selection: buffer_start..buffer_start,
+                    base_selection: base_selection.clone(),
+                },
+            ))
+        })
+        .collect()
+}
+
 fn patches_for_range<F>(
     excerpt_map: &HashMap<ExcerptId, ExcerptId>,
     source_snapshot: &MultiBufferSnapshot,
@@ -573,6 +603,23 @@ impl SplittableEditor {
                         }
                     }
                 }
+                EditorEvent::PartialStageOrUnstageRequested { stage, hunks } => {
+                    if this.lhs.is_some() {
+                        let translated = translate_lhs_partial_hunks_to_rhs(hunks, this, cx);
+                        if !translated.is_empty() {
+                            let stage = *stage;
+                            this.rhs_editor.update(cx, |editor, cx| {
+                                let chunk_by =
+                                    translated.into_iter().chunk_by(|(buffer_id, _)| *buffer_id);
+                                for (buffer_id, hunks) in &chunk_by {
+                                    let hunks = hunks.map(|(_, hunk)| hunk).collect::<Vec<_>>();
+                                    editor
+                                        .do_stage_or_unstage_partial(stage, buffer_id, &hunks, cx);
+                                }
+                            });
+                        }
+                    }
+                }
                 EditorEvent::RestoreRequested { hunks } => {
                     if this.lhs.is_some() {
                         let translated = translate_lhs_hunks_to_rhs(hunks, this, cx);
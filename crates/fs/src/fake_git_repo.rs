@@ -4,16 +4,20 @@ use collections::{HashMap, HashSet};
 use futures::future::{self, BoxFuture, join_all};
 use git::{
     Oid, RunHook,
-    blame::Blame,
+    bisect::{BisectMark, BisectState, BisectStep},
+    blame::{Blame, RevisionBlame},
+    rebase::{RebaseAction, RebaseControl, RebasePlan, RebaseProgress, RebaseTodoEntry},
     repository::{
-        AskPassDelegate, Branch, CommitDataReader, CommitDetails, CommitOptions, FetchOptions,
-        GRAPH_CHUNK_SIZE, GitRepository, GitRepositoryCheckpoint, InitialGraphCommitData, LogOrder,
-        LogSource, PushOptions, Remote, RepoPath, ResetMode, Worktree,
+        AskPassDelegate, Branch, CommitDataReader, CommitDetails, CommitOptions, CommitSignature,
+        FetchOptions, GRAPH_CHUNK_SIZE, GitRepository, GitRepositoryCheckpoint,
+        InitialGraphCommitData, LogOrder, LogSource, PushOptions, ReflogEntry, Remote,
+        RemoteCommandOutput, RepoPath, ResetMode, Tag, Worktree,
     },
     status::{
         DiffTreeType, FileStatus, GitStatus, StatusCode, TrackedStatus, TreeDiff, TreeDiffStatus,
         UnmergedStatus,
     },
+    submodule::{Submodule, SubmoduleCommand, SubmoduleStatus},
 };
 use gpui::{AsyncApp, BackgroundExecutor, SharedString, Task};
 use ignore::gitignore::GitignoreBuilder;
@@ -44,6 +48,8 @@ pub struct FakeGitRepositoryState {
     pub merge_base_contents: HashMap<RepoPath, Oid>,
     pub oids: HashMap<Oid, String>,
     pub blames: HashMap<RepoPath, Blame>,
+    /// Files and their blames at a revision, keyed by revision and path.
+    pub revision_blames: HashMap<(String, RepoPath), RevisionBlame>,
     pub current_branch_name: Option<String>,
     pub branches: HashSet<String>,
     /// List of remotes, keys are names and values are URLs
@@ -53,6 +59,20 @@ pub struct FakeGitRepositoryState {
     pub refs: HashMap<String, String>,
     pub graph_commits: Vec<Arc<InitialGraphCommitData>>,
     pub worktrees: Vec<Worktree>,
+    pub tags: Vec<Tag>,
+    /// Reflog entries keyed by reference (`HEAD` or a branch name), newest first.
+    pub reflogs: HashMap<String, Vec<ReflogEntry>>,
+    /// Commits applied by `cherry_pick`, in order.
+    pub cherry_picks: Vec<String>,
+    /// Commits undone by `revert`, in order.
+    pub reverts: Vec<String>,
+    /// The todo list of the last interactive rebase.
+    pub rebase_todo: Vec<RebaseTodoEntry>,
+    pub rebase: Option<RebaseProgress>,
+    pub bisect: Option<BisectState>,
+    /// Git's `commit.gpgSign` config.
+    pub signs_commits_by_default: bool,
+    pub submodules: Vec<Submodule>,
 }
 
 impl FakeGitRepositoryState {
@@ -63,6 +83,7 @@ impl FakeGitRepositoryState {
             index_contents: Default::default(),
             unmerged_paths: Default::default(),
             blames: Default::default(),
+            revision_blames: Default::default(),
             current_branch_name: Default::default(),
             branches: Default::default(),
             simulated_index_write_error_message: Default::default(),
@@ -73,8 +94,92 @@ impl FakeGitRepositoryState {
             remotes: HashMap::default(),
             graph_commits: Vec::new(),
             worktrees: Vec::new(),
+            tags: Vec::new(),
+            reflogs: HashMap::default(),
+            cherry_picks: Vec::new(),
+            reverts: Vec::new(),
+            rebase_todo: Vec::new(),
+            rebase: None,
+            bisect: None,
+            signs_commits_by_default: false,
+            submodules: Vec::new(),
         }
     }
+
+    fn head(&self) -> String {
+        self.refs.get("HEAD").cloned().unwrap_or_default()
+    }
+
+    /// Points `HEAD` at `sha`, recording the move in its reflog.
+    fn move_head(&mut self, sha: String, message: String) {
+        self.refs.insert("HEAD".into(), sha.clone());
+        let entries = self.reflogs.entry("HEAD".into()).or_default();
+        entries.insert(
+            0,
+            ReflogEntry {
+                selector: SharedString::default(),
+                sha: sha.into(),
+                message: message.into(),
+                subject: SharedString::default(),
+                timestamp: 0,
+            },
+        );
+        for (ix, entry) in entries.iter_mut().enumerate() {
+            entry.selector = format!("HEAD@{{{ix}}}").into();
+        }
+    }
+
+    /// Stops the rebase in progress at the first `edit` step from `start` on, or finishes it.
+    fn continue_rebase(&mut self, onto: SharedString, start: usize) {
+        self.rebase = self
+            .rebase_todo
+            .iter()
+            .enumerate()
+            .skip(start)
+            .find(|(_, entry)| entry.action == RebaseAction::Edit)
+            .map(|(ix, entry)| RebaseProgress {
+                head_name: self.current_branch_name.clone().map(SharedString::from),
+                onto,
+                step: ix + 1,
+                total: self.rebase_todo.len(),
+                stopped_sha: Some(entry.sha.clone()),
+            });
+    }
+
+    /// Checks out the middle of the commits left to test in `graph_commits`, or reports the
+    /// first bad commit once none are left.
+    fn next_bisect_step(&mut self) -> Option<BisectStep> {
+        let bisect = self.bisect.as_mut()?;
+        let bad = bisect.bad.clone()?;
+        let shas = self
+            .graph_commits
+            .iter()
+            .map(|commit| commit.sha.to_string())
+            .collect::<Vec<_>>();
+        let bad_ix = shas.iter().position(|sha| *sha == bad)?;
+        let good_ix = shas
+            .iter()
+            .enumerate()
+            .skip(bad_ix + 1)
+            .find(|(_, sha)| bisect.is_good(sha))?
+            .0;
+        let candidates = shas[bad_ix + 1..good_ix]
+            .iter()
+            .filter(|sha| !bisect.is_skipped(sha))
+            .collect::<Vec<_>>();
+        let Some(sha) = candidates.get(candidates.len() / 2) else {
+            bisect.first_bad = Some(bad.clone());
+            return Some(BisectStep::Found { sha: bad });
+        };
+        let sha = (*sha).clone();
+        let remaining = candidates.len() - 1;
+        self.move_head(sha.clone(), format!("checkout: moving to {sha}"));
+        Some(BisectStep::Testing {
+            sha: sha.into(),
+            remaining,
+            steps: (usize::BITS - remaining.leading_zeros()) as usize,
+        })
+    }
 }
 
 impl FakeGitRepository {
@@ -165,6 +270,11 @@ impl GitRepository for FakeGitRepository {
         async move { fut.await.ok() }.boxed()
     }
 
+    fn signs_commits_by_default(&self) -> BoxFuture<'_, bool> {
+        let fut = self.with_state_async(false, |state| Ok(state.signs_commits_by_default));
+        async move { fut.await.unwrap_or(false) }.boxed()
+    }
+
     fn diff_tree(&self, _request: DiffTreeType) -> BoxFuture<'_, Result<TreeDiff>> {
         let mut entries = HashMap::default();
         self.with_state_async(false, |state| {
@@ -213,6 +323,13 @@ impl GitRepository for FakeGitRepository {
         .boxed()
     }
 
+    fn commit_signatures(
+        &self,
+        commits: Vec<Oid>,
+    ) -> BoxFuture<'_, Result<HashMap<Oid, Option<CommitSignature>>>> {
+        async move { Ok(commits.into_iter().map(|commit| (commit, None)).collect()) }.boxed()
+    }
+
     fn reset(
         &self,
         _commit: String,
@@ -571,11 +688,58 @@ impl GitRepository for FakeGitRepository {
         })
     }
 
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>> {
+        self.with_state_async(false, |state| {
+            let mut tags = state.tags.clone();
+            tags.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(tags)
+        })
+    }
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let len = state.tags.len();
+            state.tags.retain(|tag| tag.name != name);
+            if state.tags.len() == len {
+                bail!("tag '{name}' not found");
+            }
+            Ok(())
+        })
+    }
+
+    fn reflog(&self, reference: String) -> BoxFuture<'_, Result<Vec<ReflogEntry>>> {
+        self.with_state_async(false, move |state| {
+            if reference != "HEAD" && !state.branches.contains(&reference) {
+                bail!("unknown reference: {reference}");
+            }
+            Ok(state.reflogs.get(&reference).cloned().unwrap_or_default())
+        })
+    }
+
+    fn checkout_commit(
+        &self,
+        commit: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let from = state
+                .current_branch_name
+                .take()
+                .unwrap_or_else(|| state.head());
+            state.move_head(
+                commit.clone(),
+                format!("checkout: moving from {from} to {commit}"),
+            );
+            Ok(())
+        })
+    }
+
     fn blame(
         &self,
         path: RepoPath,
         _content: Rope,
         _line_ending: LineEnding,
+        _options: git::blame::BlameOptions,
     ) -> BoxFuture<'_, Result<git::blame::Blame>> {
         self.with_state_async(false, move |state| {
             state
@@ -586,6 +750,21 @@ impl GitRepository for FakeGitRepository {
         })
     }
 
+    fn blame_at_revision(
+        &self,
+        revision: String,
+        path: RepoPath,
+        _options: git::blame::BlameOptions,
+    ) -> BoxFuture<'_, Result<RevisionBlame>> {
+        self.with_state_async(false, move |state| {
+            state
+                .revision_blames
+                .get(&(revision.clone(), path.clone()))
+                .with_context(|| format!("failed to get blame for {path:?} at {revision}"))
+                .cloned()
+        })
+    }
+
     fn file_history(&self, path: RepoPath) -> BoxFuture<'_, Result<git::repository::FileHistory>> {
         self.file_history_paginated(path, 0, None)
     }
@@ -739,6 +918,209 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn rebase_plan(&self, base: Option<String>) -> BoxFuture<'_, Result<RebasePlan>> {
+        self.with_state_async(false, move |state| {
+            // `graph_commits` is newest first, so the plan is every commit above `base`,
+            // or above the root commit when no base is given.
+            let mut shas = state
+                .graph_commits
+                .iter()
+                .map(|commit| commit.sha.to_string())
+                .collect::<Vec<_>>();
+            let base = match base {
+                Some(base) => base,
+                None => shas.last().cloned().context("no commits to rebase")?,
+            };
+            let base_ix = shas
+                .iter()
+                .position(|sha| *sha == base)
+                .with_context(|| format!("unknown commit: {base}"))?;
+            shas.truncate(base_ix);
+            Ok(RebasePlan {
+                base: base.into(),
+                entries: shas
+                    .into_iter()
+                    .rev()
+                    .map(|sha| RebaseTodoEntry {
+                        sha: sha.into(),
+                        subject: SharedString::default(),
+                        action: RebaseAction::Pick,
+                        message: None,
+                    })
+                    .collect(),
+            })
+        })
+    }
+
+    fn rebase_interactive(
+        &self,
+        base: String,
+        entries: Vec<RebaseTodoEntry>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.rebase.is_some() {
+                bail!("a rebase is already in progress");
+            }
+            state.rebase_todo = entries;
+            state.continue_rebase(base.into(), 0);
+            Ok(())
+        })
+    }
+
+    fn rebase_control(
+        &self,
+        control: RebaseControl,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let progress = state.rebase.take().context("no rebase in progress")?;
+            if control != RebaseControl::Abort {
+                state.continue_rebase(progress.onto, progress.step);
+            }
+            Ok(())
+        })
+    }
+
+    fn rebase_progress(&self) -> BoxFuture<'_, Option<RebaseProgress>> {
+        let fut = self.with_state_async(false, |state| Ok(state.rebase.clone()));
+        async move { fut.await.ok().flatten() }.boxed()
+    }
+
+    fn bisect_start(
+        &self,
+        bad: Option<String>,
+        good: Vec<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<Option<BisectStep>>> {
+        self.with_state_async(true, move |state| {
+            state.bisect = Some(BisectState {
+                bad: bad.map(SharedString::from),
+                good: good.into_iter().map(SharedString::from).collect(),
+                ..Default::default()
+            });
+            Ok(state.next_bisect_step())
+        })
+    }
+
+    fn bisect_mark(
+        &self,
+        mark: BisectMark,
+        commit: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<Option<BisectStep>>> {
+        self.with_state_async(true, move |state| {
+            let commit = SharedString::from(commit.unwrap_or_else(|| state.head()));
+            let bisect = state.bisect.as_mut().context("no bisect in progress")?;
+            match mark {
+                BisectMark::Good => bisect.good.push(commit),
+                BisectMark::Bad => bisect.bad = Some(commit),
+                BisectMark::Skip => bisect.skipped.push(commit),
+            }
+            Ok(state.next_bisect_step())
+        })
+    }
+
+    fn bisect_reset(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, |state| {
+            state.bisect = None;
+            Ok(())
+        })
+    }
+
+    fn bisect_state(&self) -> BoxFuture<'_, Option<BisectState>> {
+        let fut = self.with_state_async(false, |state| Ok(state.bisect.clone()));
+        async move { fut.await.ok().flatten() }.boxed()
+    }
+
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        self.with_state_async(false, |state| Ok(state.submodules.clone()))
+    }
+
+    fn submodule_command(
+        &self,
+        command: SubmoduleCommand,
+        paths: Vec<RepoPath>,
+        _ask_pass: AskPassDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+        self.with_state_async(true, move |state| {
+            // Only `update` changes what is checked out; `init` and `sync` just edit config.
+            if command == SubmoduleCommand::Update {
+                for submodule in &mut state.submodules {
+                    if paths.is_empty() || paths.contains(&submodule.path) {
+                        submodule.status = SubmoduleStatus::Current;
+                    }
+                }
+            }
+            Ok(RemoteCommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            })
+        })
+    }
+
+    fn cherry_pick(
+        &self,
+        commits: Vec<String>,
+        _mainline: Option<u32>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if commits.is_empty() {
+                bail!("no commits to cherry-pick");
+            }
+            state.cherry_picks.extend(commits);
+            Ok(())
+        })
+    }
+
+    fn revert(
+        &self,
+        commits: Vec<String>,
+        _mainline: Option<u32>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if commits.is_empty() {
+                bail!("no commits to revert");
+            }
+            state.reverts.extend(commits);
+            Ok(())
+        })
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        commit: String,
+        message: Option<String>,
+        _sign: bool,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.tags.iter().any(|tag| tag.name == name) {
+                bail!("tag '{name}' already exists");
+            }
+            let sha = state.refs.get(&commit).cloned().unwrap_or(commit);
+            state.tags.push(Tag {
+                name: name.into(),
+                sha: sha.into(),
+                message: message.map(|message| {
+                    message
+                        .lines()
+                        .next()
+                        .unwrap_or_default()
+                        .to_string()
+                        .into()
+                }),
+                timestamp: 0,
+            });
+            Ok(())
+        })
+    }
+
     fn get_all_remotes(&self) -> BoxFuture<'_, Result<Vec<Remote>>> {
         self.with_state_async(false, move |state| {
             let remotes = state
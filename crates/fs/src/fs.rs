@@ -1972,6 +1972,19 @@ impl FakeFs {
         .unwrap();
     }
 
+    pub fn set_blame_at_revision(
+        &self,
+        dot_git: &Path,
+        revision: impl Into<String>,
+        path: RepoPath,
+        blame: git::blame::RevisionBlame,
+    ) {
+        self.with_git_state(dot_git, true, |state| {
+            state.revision_blames.insert((revision.into(), path), blame);
+        })
+        .unwrap();
+    }
+
     pub fn set_graph_commits(&self, dot_git: &Path, commits: Vec<Arc<InitialGraphCommitData>>) {
         self.with_git_state(dot_git, true, |state| {
             state.graph_commits = commits;
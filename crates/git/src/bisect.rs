@@ -0,0 +1,213 @@
+use gpui::SharedString;
+use std::path::Path;
+
+/// How a commit tested during a bisect behaves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BisectMark {
+    /// The commit does not have the regression.
+    Good,
+    /// The commit has the regression.
+    Bad,
+    /// The commit can't be tested, for example because it does not build.
+    Skip,
+}
+
+impl BisectMark {
+    pub fn as_arg(&self) -> &'static str {
+        match self {
+            Self::Good => "good",
+            Self::Bad => "bad",
+            Self::Skip => "skip",
+        }
+    }
+
+    /// Interprets the exit code of a test command the way `git bisect run` does: zero is good,
+    /// 125 skips the commit, and any other code below 128 is bad.
+    ///
+    /// Returns `None` for codes that should stop the bisect instead.
+    pub fn from_exit_code(code: i32) -> Option<Self> {
+        match code {
+            0 => Some(Self::Good),
+            125 => Some(Self::Skip),
+            1..128 => Some(Self::Bad),
+            _ => None,
+        }
+    }
+}
+
+/// The commits marked so far in a bisect that is in progress.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BisectState {
+    pub bad: Option<SharedString>,
+    pub good: Vec<SharedString>,
+    pub skipped: Vec<SharedString>,
+    /// The first bad commit, once the bisect has narrowed it down.
+    pub first_bad: Option<SharedString>,
+}
+
+impl BisectState {
+    /// Reads the state of a bisect from the refs and log git keeps in `git_dir`.
+    pub fn load(git_dir: &Path) -> Option<Self> {
+        if !git_dir.join("BISECT_START").is_file() {
+            return None;
+        }
+        let mut state = Self::default();
+        if let Ok(entries) = std::fs::read_dir(git_dir.join("refs").join("bisect")) {
+            for entry in entries.flatten() {
+                let Ok(sha) = std::fs::read_to_string(entry.path()) else {
+                    continue;
+                };
+                let sha = SharedString::from(sha.trim().to_string());
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                if name == "bad" {
+                    state.bad = Some(sha);
+                } else if name.starts_with("good-") {
+                    state.good.push(sha);
+                } else if name.starts_with("skip-") {
+                    state.skipped.push(sha);
+                }
+            }
+        }
+        state.good.sort();
+        state.skipped.sort();
+        state.first_bad = std::fs::read_to_string(git_dir.join("BISECT_LOG"))
+            .ok()
+            .and_then(|log| {
+                log.lines().rev().find_map(|line| {
+                    let rest = line.strip_prefix("# first bad commit: [")?;
+                    let (sha, _) = rest.split_once(']')?;
+                    Some(sha.to_string().into())
+                })
+            });
+        Some(state)
+    }
+
+    pub fn is_good(&self, sha: &str) -> bool {
+        self.good.iter().any(|good| good == sha)
+    }
+
+    pub fn is_bad(&self, sha: &str) -> bool {
+        self.bad.as_ref().is_some_and(|bad| bad == sha)
+    }
+
+    pub fn is_skipped(&self, sha: &str) -> bool {
+        self.skipped.iter().any(|skipped| skipped == sha)
+    }
+}
+
+/// Where a bisect stands after marking a commit.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BisectStep {
+    /// `sha` was checked out to be tested next.
+    Testing {
+        sha: SharedString,
+        /// The number of commits left to test after this one.
+        remaining: usize,
+        /// Roughly how many more steps the bisect will take.
+        steps: usize,
+    },
+    /// The bisect is over and `sha` is the first bad commit.
+    Found { sha: SharedString },
+}
+
+/// Parses the output of `git bisect good`, `bad` or `skip`.
+///
+/// Returns `None` while git is still waiting for a good or a bad commit.
+pub fn parse_bisect_step(output: &str) -> Option<BisectStep> {
+    let mut lines = output.lines();
+    while let Some(line) = lines.next() {
+        if let Some(sha) = line.strip_suffix(" is the first bad commit") {
+            return Some(BisectStep::Found {
+                sha: sha.trim().to_string().into(),
+            });
+        }
+        if let Some(rest) = line.strip_prefix("Bisecting: ") {
+            let remaining = rest
+                .split_whitespace()
+                .next()
+                .and_then(|count| count.parse().ok())
+                .unwrap_or(0);
+            let steps = rest
+                .split_once("(roughly ")
+                .and_then(|(_, steps)| steps.split_whitespace().next())
+                .and_then(|steps| steps.parse().ok())
+                .unwrap_or(0);
+            let sha = lines
+                .next()?
+                .strip_prefix('[')?
+                .split_once(']')?
+                .0
+                .to_string();
+            return Some(BisectStep::Testing {
+                sha: sha.into(),
+                remaining,
+                steps,
+            });
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_bisect_step() {
+        assert_eq!(
+            parse_bisect_step(
+                "Bisecting: 6 revisions left to test after this (roughly 3 steps)\n\
+                 [1111111111111111111111111111111111111111] Add a feature\n"
+            ),
+            Some(BisectStep::Testing {
+                sha: "1111111111111111111111111111111111111111".into(),
+                remaining: 6,
+                steps: 3,
+            })
+        );
+        assert_eq!(
+            parse_bisect_step(
+                "2222222222222222222222222222222222222222 is the first bad commit\n\
+                 commit 2222222222222222222222222222222222222222\n\
+                 Author: Someone <someone@example.com>\n"
+            ),
+            Some(BisectStep::Found {
+                sha: "2222222222222222222222222222222222222222".into(),
+            })
+        );
+        assert_eq!(
+            parse_bisect_step("status: waiting for good commit(s), bad commit known\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_load_state() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(BisectState::load(dir.path()), None);
+
+        std::fs::write(dir.path().join("BISECT_START"), "main\n").unwrap();
+        let refs = dir.path().join("refs").join("bisect");
+        std::fs::create_dir_all(&refs).unwrap();
+        std::fs::write(refs.join("bad"), "bbb\n").unwrap();
+        std::fs::write(refs.join("good-ggg"), "ggg\n").unwrap();
+        std::fs::write(refs.join("skip-sss"), "sss\n").unwrap();
+        std::fs::write(
+            dir.path().join("BISECT_LOG"),
+            "git bisect start\n# bad: [bbb] Break it\ngit bisect bad bbb\n\
+             # first bad commit: [bbb] Break it\n",
+        )
+        .unwrap();
+        assert_eq!(
+            BisectState::load(dir.path()),
+            Some(BisectState {
+                bad: Some("bbb".into()),
+                good: vec!["ggg".into()],
+                skipped: vec!["sss".into()],
+                first_bad: Some("bbb".into()),
+            })
+        );
+    }
+}
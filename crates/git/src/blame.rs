@@ -20,6 +20,60 @@ pub struct Blame {
     pub messages: HashMap<Oid, String>,
 }
 
+/// A file's text as of a revision, and its blame there.
+#[derive(Debug, Clone, Default)]
+pub struct RevisionBlame {
+    pub text: String,
+    pub blame: Blame,
+}
+
+/// What `git blame` annotates.
+#[derive(Clone, Copy)]
+enum BlameTarget<'a> {
+    /// Text descending from `HEAD`, like a buffer with unsaved changes.
+    Contents(&'a Rope, LineEnding),
+    /// The file as committed in a revision.
+    Revision(&'a str),
+}
+
+/// Options that change which commit a line is attributed to.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BlameOptions {
+    /// Follow lines moved or copied within the file (`-M`).
+    pub detect_moves: bool,
+    /// Follow lines moved or copied from other files modified in the same commit (`-C`).
+    pub detect_copies: bool,
+    /// A file listing commits to skip, relative to the working directory. Git's
+    /// `blame.ignoreRevsFile` config applies in addition to this.
+    pub ignore_revs_file: Option<String>,
+    /// Commits to skip. Lines they changed are blamed on the parent revision instead.
+    pub ignore_revs: Vec<String>,
+}
+
+impl BlameOptions {
+    fn args(&self, working_directory: &Path) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.detect_moves {
+            args.push("-M".to_string());
+        }
+        if self.detect_copies {
+            args.push("-C".to_string());
+        }
+        if let Some(file) = &self.ignore_revs_file {
+            // Git bails on a file it can't open, so leave out one this checkout doesn't have.
+            if working_directory.join(file).is_file() {
+                args.push(format!("--ignore-revs-file={file}"));
+            } else {
+                log::warn!("ignoring missing blame ignore_revs_file {file:?}");
+            }
+        }
+        for rev in &self.ignore_revs {
+            args.push(format!("--ignore-rev={rev}"));
+        }
+        args
+    }
+}
+
 impl Blame {
     pub async fn for_path(
         git_binary: &Path,
@@ -27,9 +81,59 @@ impl Blame {
         path: &RepoPath,
         content: &Rope,
         line_ending: LineEnding,
+        options: &BlameOptions,
     ) -> Result<Self> {
-        let output =
-            run_git_blame(git_binary, working_directory, path, content, line_ending).await?;
+        Self::generate(
+            git_binary,
+            working_directory,
+            path,
+            BlameTarget::Contents(content, line_ending),
+            options,
+        )
+        .await
+    }
+
+    /// Blames the file at `path` as it was committed in `revision`.
+    pub async fn for_revision(
+        git_binary: &Path,
+        working_directory: &Path,
+        path: &RepoPath,
+        revision: &str,
+        options: &BlameOptions,
+    ) -> Result<Self> {
+        Self::generate(
+            git_binary,
+            working_directory,
+            path,
+            BlameTarget::Revision(revision),
+            options,
+        )
+        .await
+    }
+
+    async fn generate(
+        git_binary: &Path,
+        working_directory: &Path,
+        path: &RepoPath,
+        target: BlameTarget<'_>,
+        options: &BlameOptions,
+    ) -> Result<Self> {
+        let mut args = options.args(working_directory);
+        let output = match run_git_blame(git_binary, working_directory, path, target, &args).await {
+            // A `blame.ignoreRevsFile` that doesn't exist in this checkout makes git bail,
+            // so retry without the configured file rather than showing no blame at all. The
+            // empty option only resets the configured files, so the ones in `args` still apply.
+            Err(error)
+                if error
+                    .to_string()
+                    .contains(GIT_BLAME_MISSING_IGNORE_REVS_FILE) =>
+            {
+                log::warn!("ignoring unreadable blame.ignoreRevsFile: {error:#}");
+                args.insert(0, "--ignore-revs-file=".to_string());
+                run_git_blame(git_binary, working_directory, path, target, &args).await?
+            }
+            result => result?,
+        };
         let mut entries = parse_git_blame(&output)?;
         entries.sort_unstable_by(|a, b| a.range.start.cmp(&b.range.start));
 
@@ -50,23 +154,30 @@ impl Blame {
 
 const GIT_BLAME_NO_COMMIT_ERROR: &str = "fatal: no such ref: HEAD";
 const GIT_BLAME_NO_PATH: &str = "fatal: no such path";
+const GIT_BLAME_MISSING_IGNORE_REVS_FILE: &str = "could not open object name list";
 
 async fn run_git_blame(
     git_binary: &Path,
     working_directory: &Path,
     path: &RepoPath,
-    contents: &Rope,
-    line_ending: LineEnding,
+    target: BlameTarget<'_>,
+    extra_args: &[String],
 ) -> Result<String> {
     let mut child = {
         let span = ztracing::debug_span!("spawning git-blame command", path = path.as_unix_str());
         let _enter = span.enter();
-        util::command::new_command(git_binary)
+        let mut command = util::command::new_command(git_binary);
+        command
             .current_dir(working_directory)
             .arg("blame")
-            .arg("--incremental")
-            .arg("--contents")
-            .arg("-")
+            .args(extra_args)
+            .arg("--incremental");
+        match target {
+            BlameTarget::Contents(..) => command.arg("--contents").arg("-"),
+            BlameTarget::Revision(revision) => command.arg(revision),
+        };
+        command
+            .arg("--")
             .arg(path.as_unix_str())
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
@@ -80,8 +191,10 @@ async fn run_git_blame(
         .as_mut()
         .context("failed to get pipe to stdin of git blame command")?;
 
-    for chunk in text::chunks_with_line_ending(contents, line_ending) {
-        stdin.write_all(chunk.as_bytes()).await?;
+    if let BlameTarget::Contents(contents, line_ending) = target {
+        for chunk in text::chunks_with_line_ending(contents, line_ending) {
+            stdin.write_all(chunk.as_bytes()).await?;
+        }
     }
     stdin.flush().await?;
 
@@ -124,6 +237,13 @@ pub struct BlameEntry {
 }
 
 impl BlameEntry {
+    /// The parent of the commit that last changed these lines, and the file's path there, which
+    /// differs from [`Self::filename`] when the commit renamed the file.
+    pub fn previous_revision(&self) -> Option<(Oid, RepoPath)> {
+        let (sha, path) = self.previous.as_ref()?.split_once(' ')?;
+        Some((sha.parse().ok()?, RepoPath::new(path).ok()?))
+    }
+
     // Returns a BlameEntry by parsing the first line of a `git blame --incremental`
     // entry. The line MUST have this format:
     //
@@ -299,6 +419,7 @@ mod tests {
     use std::path::PathBuf;
 
     use super::BlameEntry;
+    use super::BlameOptions;
     use super::parse_git_blame;
 
     fn read_test_data(filename: &str) -> String {
@@ -353,6 +474,37 @@ mod tests {
         assert_eq_golden(&entries, "blame_incremental_simple");
     }
 
+    #[test]
+    fn test_blame_options_args() {
+        let working_directory = tempfile::tempdir().unwrap();
+        let working_directory = working_directory.path();
+        assert!(BlameOptions::default().args(working_directory).is_empty());
+
+        let options = BlameOptions {
+            detect_moves: true,
+            detect_copies: true,
+            ignore_revs_file: Some(".git-blame-ignore-revs".into()),
+            ignore_revs: vec!["abc123".into(), "def456".into()],
+        };
+        // A missing ignore-revs file is left out, since git fails on it.
+        assert_eq!(
+            options.args(working_directory),
+            ["-M", "-C", "--ignore-rev=abc123", "--ignore-rev=def456"]
+        );
+
+        std::fs::write(working_directory.join(".git-blame-ignore-revs"), "").unwrap();
+        assert_eq!(
+            options.args(working_directory),
+            [
+                "-M",
+                "-C",
+                "--ignore-revs-file=.git-blame-ignore-revs",
+                "--ignore-rev=abc123",
+                "--ignore-rev=def456",
+            ]
+        );
+    }
+
     #[test]
     fn test_parse_git_blame_complex() {
         let output = read_test_data("blame_incremental_complex");
@@ -1,10 +1,13 @@
+pub mod bisect;
 pub mod blame;
 pub mod commit;
 mod hosting_provider;
+pub mod rebase;
 mod remote;
 pub mod repository;
 pub mod stash;
 pub mod status;
+pub mod submodule;
 
 pub use crate::hosting_provider::*;
 pub use crate::remote::*;
@@ -37,6 +40,10 @@ actions!(
         StageAndNext,
         /// Unstages the current hunk and moves to the next one.
         UnstageAndNext,
+        /// Stages only the selected lines, or characters, of the hunks in the selection.
+        StageSelection,
+        /// Unstages only the selected lines, or characters, of the hunks in the selection.
+        UnstageSelection,
         /// Restores the selected hunks to their original state.
         #[action(deprecated_aliases = ["editor::RevertSelectedHunks"])]
         Restore,
@@ -77,6 +84,34 @@ actions!(
         Pull,
         /// Pulls changes from the remote repository with rebase.
         PullRebase,
+        /// Opens the interactive rebase editor for the commits of the current branch.
+        InteractiveRebase,
+        /// Continues a rebase that stopped for conflicts or to edit a commit.
+        RebaseContinue,
+        /// Skips the commit a rebase stopped at.
+        RebaseSkip,
+        /// Aborts the rebase in progress, restoring the branch to its original state.
+        RebaseAbort,
+        /// Registers the submodules listed in .gitmodules.
+        InitSubmodules,
+        /// Checks out the commit recorded for each initialized submodule.
+        UpdateSubmodules,
+        /// Copies submodule URLs from .gitmodules into the repository config.
+        SyncSubmodules,
+        /// Marks the commit being tested in a bisect as good.
+        BisectGood,
+        /// Marks the commit being tested in a bisect as bad.
+        BisectBad,
+        /// Skips the commit being tested in a bisect.
+        BisectSkip,
+        /// Ends the bisect in progress and returns to the commit it started from.
+        BisectReset,
+        /// Finishes the bisect in progress by running a task on each commit it tests.
+        BisectRun,
+        /// Opens one of the repository's open pull requests for review.
+        ReviewPullRequest,
+        /// Posts the review comments written in a pull request view.
+        SubmitPullRequestReview,
         /// Fetches changes from the remote repository.
         Fetch,
         /// Fetches changes from a specific remote.
@@ -87,6 +122,8 @@ actions!(
         Amend,
         /// Enable the --signoff option.
         Signoff,
+        /// Toggles signing of the next commit with the configured GPG or SSH key.
+        SignCommit,
         /// Cancels the current git operation.
         Cancel,
         /// Expands the commit message editor.
@@ -148,7 +185,7 @@ impl Oid {
         self.0.as_bytes()
     }
 
-    pub(crate) fn is_zero(&self) -> bool {
+    pub fn is_zero(&self) -> bool {
         self.0.is_zero()
     }
 
@@ -17,6 +17,55 @@ pub struct PullRequest {
     pub url: Url,
 }
 
+/// A pull request, or merge request, that is open on a hosting provider.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PullRequestSummary {
+    pub number: u32,
+    pub title: String,
+    pub author: String,
+    pub url: Url,
+    pub source_branch: String,
+    pub target_branch: String,
+    /// The commit at the tip of the source branch.
+    pub head_sha: String,
+}
+
+/// Which side of a diff a review comment is attached to.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ReviewCommentSide {
+    /// A line of the diff's base, typically one that was removed.
+    Old,
+    /// A line of the diff's head.
+    New,
+}
+
+/// A comment left on a line of a pull request's diff.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ReviewComment {
+    pub id: String,
+    /// Identifies the thread the comment belongs to, for posting replies.
+    pub thread_id: String,
+    pub author: String,
+    pub body: String,
+    pub path: String,
+    /// The 1-based line the comment is attached to, or `None` if the line is no longer part of
+    /// the diff.
+    pub line: Option<u32>,
+    pub side: ReviewCommentSide,
+}
+
+/// A review comment to post on a pull request.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct NewReviewComment {
+    pub body: String,
+    pub path: String,
+    /// The 1-based line to attach the comment to.
+    pub line: u32,
+    pub side: ReviewCommentSide,
+    /// The thread to reply to, or `None` to start a new one.
+    pub thread_id: Option<String>,
+}
+
 #[derive(Clone)]
 pub struct GitRemote {
     pub host: Arc<dyn GitHostingProvider + Send + Sync + 'static>,
@@ -51,6 +100,13 @@ impl GitRemote {
             .ok()
             .flatten()
     }
+
+    pub fn parsed_remote(&self) -> ParsedGitRemote {
+        ParsedGitRemote {
+            owner: self.owner.as_ref().into(),
+            repo: self.repo.as_ref().into(),
+        }
+    }
 }
 
 pub struct BuildCommitPermalinkParams<'a> {
@@ -145,6 +201,62 @@ pub trait GitHostingProvider {
     ) -> Result<Option<Url>> {
         Ok(None)
     }
+
+    /// Returns whether pull requests can be listed and reviewed through this provider's API.
+    fn supports_pull_request_reviews(&self) -> bool {
+        false
+    }
+
+    /// Returns the open pull requests of a repository.
+    async fn list_pull_requests(
+        &self,
+        _remote: &ParsedGitRemote,
+        _http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<PullRequestSummary>> {
+        anyhow::bail!("{} does not support pull request reviews", self.name())
+    }
+
+    /// Returns the changes of a pull request as a unified diff.
+    async fn pull_request_diff(
+        &self,
+        _remote: &ParsedGitRemote,
+        _number: u32,
+        _http_client: Arc<dyn HttpClient>,
+    ) -> Result<String> {
+        anyhow::bail!("{} does not support pull request reviews", self.name())
+    }
+
+    /// Returns the contents of a file as of the given commit.
+    async fn file_at_commit(
+        &self,
+        _remote: &ParsedGitRemote,
+        _path: &str,
+        _sha: &str,
+        _http_client: Arc<dyn HttpClient>,
+    ) -> Result<String> {
+        anyhow::bail!("{} does not support pull request reviews", self.name())
+    }
+
+    /// Returns the comments left on the lines of a pull request's diff.
+    async fn pull_request_review_comments(
+        &self,
+        _remote: &ParsedGitRemote,
+        _number: u32,
+        _http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<ReviewComment>> {
+        anyhow::bail!("{} does not support pull request reviews", self.name())
+    }
+
+    /// Posts a comment on a line of a pull request's diff, returning the comment as posted.
+    async fn post_pull_request_review_comment(
+        &self,
+        _remote: &ParsedGitRemote,
+        _pull_request: &PullRequestSummary,
+        _comment: NewReviewComment,
+        _http_client: Arc<dyn HttpClient>,
+    ) -> Result<ReviewComment> {
+        anyhow::bail!("{} does not support pull request reviews", self.name())
+    }
 }
 
 #[derive(Default, Deref, DerefMut)]
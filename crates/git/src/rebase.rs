@@ -0,0 +1,232 @@
+use anyhow::{Result, anyhow};
+use gpui::SharedString;
+use std::{
+    fmt::Write as _,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Directory inside the git directory where reworded commit messages are kept while an
+/// interactive rebase is in progress.
+pub const REBASE_MESSAGES_DIR: &str = "zed-rebase-messages";
+
+/// What an interactive rebase does with a commit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RebaseAction {
+    #[default]
+    Pick,
+    Reword,
+    Edit,
+    Squash,
+    Fixup,
+    Drop,
+}
+
+impl RebaseAction {
+    pub const ALL: [Self; 6] = [
+        Self::Pick,
+        Self::Reword,
+        Self::Edit,
+        Self::Squash,
+        Self::Fixup,
+        Self::Drop,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pick => "pick",
+            Self::Reword => "reword",
+            Self::Edit => "edit",
+            Self::Squash => "squash",
+            Self::Fixup => "fixup",
+            Self::Drop => "drop",
+        }
+    }
+
+    /// Whether this action folds the commit into the one before it.
+    pub fn melds_into_previous(&self) -> bool {
+        matches!(self, Self::Squash | Self::Fixup)
+    }
+}
+
+impl FromStr for RebaseAction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.as_str() == s)
+            .ok_or_else(|| anyhow!("unknown rebase action: {s}"))
+    }
+}
+
+/// A line of an interactive rebase todo list.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RebaseTodoEntry {
+    pub sha: SharedString,
+    pub subject: SharedString,
+    pub action: RebaseAction,
+    /// The new commit message, used when `action` is [`RebaseAction::Reword`].
+    pub message: Option<SharedString>,
+}
+
+/// Resumes or cancels a rebase that stopped for conflicts or an `edit` step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RebaseControl {
+    Continue,
+    Skip,
+    Abort,
+}
+
+impl RebaseControl {
+    pub fn as_arg(&self) -> &'static str {
+        match self {
+            Self::Continue => "--continue",
+            Self::Skip => "--skip",
+            Self::Abort => "--abort",
+        }
+    }
+}
+
+/// The commits an interactive rebase would replay onto `base`, oldest first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RebasePlan {
+    pub base: SharedString,
+    pub entries: Vec<RebaseTodoEntry>,
+}
+
+/// The state of a rebase that is in progress.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RebaseProgress {
+    /// The branch being rebased, or `None` when rebasing a detached HEAD.
+    pub head_name: Option<SharedString>,
+    pub onto: SharedString,
+    /// The one-based number of the step the rebase is at.
+    pub step: usize,
+    pub total: usize,
+    /// The commit the rebase stopped at, for conflicts or an `edit` step.
+    pub stopped_sha: Option<SharedString>,
+}
+
+impl RebaseProgress {
+    /// Reads the progress of a rebase from the state git keeps in `git_dir`.
+    pub fn load(git_dir: &Path) -> Option<Self> {
+        let state_dir = ["rebase-merge", "rebase-apply"]
+            .into_iter()
+            .map(|name| git_dir.join(name))
+            .find(|dir| dir.is_dir())?;
+        let read = |names: &[&str]| {
+            names.iter().find_map(|name| {
+                std::fs::read_to_string(state_dir.join(name))
+                    .ok()
+                    .map(|contents| contents.trim().to_string())
+            })
+        };
+        let head_name = read(&["head-name"])
+            .filter(|name| name != "detached HEAD")
+            .map(|name| {
+                name.strip_prefix("refs/heads/")
+                    .unwrap_or(&name)
+                    .to_string()
+                    .into()
+            });
+        Some(Self {
+            head_name,
+            onto: read(&["onto"]).unwrap_or_default().into(),
+            step: read(&["msgnum", "next"])
+                .and_then(|step| step.parse().ok())
+                .unwrap_or(0),
+            total: read(&["end", "last"])
+                .and_then(|total| total.parse().ok())
+                .unwrap_or(0),
+            stopped_sha: read(&["stopped-sha"]).map(SharedString::from),
+        })
+    }
+}
+
+/// Renders the todo list git reads at the start of an interactive rebase.
+///
+/// Git would open an editor for `reword` steps, so they are written as a `pick` followed by
+/// an `exec` that amends the commit with the message stored at `message_path(ix)`.
+pub fn todo_list(entries: &[RebaseTodoEntry], message_path: impl Fn(usize) -> PathBuf) -> String {
+    let mut todo = String::new();
+    for (ix, entry) in entries.iter().enumerate() {
+        match (entry.action, &entry.message) {
+            (RebaseAction::Reword, Some(_)) => {
+                writeln!(todo, "pick {} {}", entry.sha, entry.subject).ok();
+                writeln!(
+                    todo,
+                    "exec git commit --amend --allow-empty --no-verify --cleanup=strip -F {}",
+                    shell_quote(&message_path(ix).to_string_lossy())
+                )
+                .ok();
+            }
+            (action, _) => {
+                writeln!(todo, "{} {} {}", action.as_str(), entry.sha, entry.subject).ok();
+            }
+        }
+    }
+    todo
+}
+
+pub(crate) fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn entry(sha: &str, action: RebaseAction, message: Option<&str>) -> RebaseTodoEntry {
+        RebaseTodoEntry {
+            sha: sha.to_string().into(),
+            subject: format!("subject of {sha}").into(),
+            action,
+            message: message.map(|message| message.to_string().into()),
+        }
+    }
+
+    #[test]
+    fn test_todo_list() {
+        let entries = [
+            entry("aaa", RebaseAction::Pick, None),
+            entry("bbb", RebaseAction::Fixup, None),
+            entry("ccc", RebaseAction::Reword, Some("New message")),
+            entry("ddd", RebaseAction::Drop, None),
+        ];
+        let todo = todo_list(&entries, |ix| PathBuf::from(format!("/tmp/it's/{ix}")));
+        assert_eq!(
+            todo,
+            "pick aaa subject of aaa\n\
+             fixup bbb subject of bbb\n\
+             pick ccc subject of ccc\n\
+             exec git commit --amend --allow-empty --no-verify --cleanup=strip -F '/tmp/it'\\''s/2'\n\
+             drop ddd subject of ddd\n"
+        );
+    }
+
+    #[test]
+    fn test_load_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(RebaseProgress::load(dir.path()), None);
+
+        let state = dir.path().join("rebase-merge");
+        std::fs::create_dir(&state).unwrap();
+        std::fs::write(state.join("head-name"), "refs/heads/feature\n").unwrap();
+        std::fs::write(state.join("onto"), "abc123\n").unwrap();
+        std::fs::write(state.join("msgnum"), "2\n").unwrap();
+        std::fs::write(state.join("end"), "5\n").unwrap();
+        std::fs::write(state.join("stopped-sha"), "def456\n").unwrap();
+        assert_eq!(
+            RebaseProgress::load(dir.path()),
+            Some(RebaseProgress {
+                head_name: Some("feature".into()),
+                onto: "abc123".into(),
+                step: 2,
+                total: 5,
+                stopped_sha: Some("def456".into()),
+            })
+        );
+    }
+}
@@ -1,6 +1,12 @@
+use crate::bisect::{BisectMark, BisectState, BisectStep, parse_bisect_step};
 use crate::commit::parse_git_diff_name_status;
+use crate::rebase::{
+    REBASE_MESSAGES_DIR, RebaseAction, RebaseControl, RebasePlan, RebaseProgress, RebaseTodoEntry,
+    shell_quote, todo_list,
+};
 use crate::stash::GitStash;
 use crate::status::{DiffTreeType, GitStatus, StatusCode, TreeDiff};
+use crate::submodule::{Submodule, SubmoduleCommand, parse_submodule_status};
 use crate::{Oid, RunHook, SHORT_SHA_LENGTH};
 use anyhow::{Context as _, Result, anyhow, bail};
 use collections::HashMap;
@@ -156,6 +162,8 @@ pub struct GraphCommitData {
     pub author_email: SharedString,
     pub commit_timestamp: i64,
     pub subject: SharedString,
+    /// Whether the commit object carries a signature. This is not verified.
+    pub is_signed: bool,
 }
 
 #[derive(Debug)]
@@ -195,6 +203,7 @@ fn parse_cat_file_commit(sha: Oid, content: &str) -> Option<GraphCommitData> {
     let mut commit_timestamp = 0i64;
     let mut in_headers = true;
     let mut subject = None;
+    let mut is_signed = false;
 
     for line in content.lines() {
         if in_headers {
@@ -207,6 +216,8 @@ fn parse_cat_file_commit(sha: Oid, content: &str) -> Option<GraphCommitData> {
                 if let Ok(oid) = Oid::from_str(parent_sha.trim()) {
                     parents.push(oid);
                 }
+            } else if line.starts_with("gpgsig ") || line.starts_with("gpgsig-sha256 ") {
+                is_signed = true;
             } else if let Some(author_line) = line.strip_prefix("author ") {
                 if let Some((name_email, _timestamp_tz)) = author_line.rsplit_once(' ') {
                     if let Some((name_email, timestamp_str)) = name_email.rsplit_once(' ') {
@@ -233,6 +244,7 @@ fn parse_cat_file_commit(sha: Oid, content: &str) -> Option<GraphCommitData> {
         author_email,
         commit_timestamp,
         subject: subject.unwrap_or_default(),
+        is_signed,
     })
 }
 
@@ -279,6 +291,37 @@ impl Branch {
     }
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Tag {
+    pub name: SharedString,
+    /// The commit the tag points at.
+    pub sha: SharedString,
+    /// The subject of the tag message, or `None` for lightweight tags.
+    pub message: Option<SharedString>,
+    /// When the tag was created, or for lightweight tags, when its commit was made.
+    pub timestamp: i64,
+}
+
+impl Tag {
+    pub fn is_annotated(&self) -> bool {
+        self.message.is_some()
+    }
+}
+
+/// An entry in the reflog of `HEAD` or a branch, recording a commit the ref pointed at.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ReflogEntry {
+    /// The selector for this entry, such as `HEAD@{2}`.
+    pub selector: SharedString,
+    pub sha: SharedString,
+    /// What moved the ref, such as `checkout: moving from main to feature`.
+    pub message: SharedString,
+    /// The subject of the commit's message.
+    pub subject: SharedString,
+    /// When the ref was moved.
+    pub timestamp: i64,
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct Worktree {
     pub path: PathBuf,
@@ -360,10 +403,72 @@ impl Upstream {
     }
 }
 
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 pub struct CommitOptions {
     pub amend: bool,
     pub signoff: bool,
+    pub signing: CommitSigning,
+}
+
+/// The kind of signature git produces, as accepted by `gpg.format`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SigningFormat {
+    OpenPgp,
+    Ssh,
+    X509,
+}
+
+impl SigningFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SigningFormat::OpenPgp => "openpgp",
+            SigningFormat::Ssh => "ssh",
+            SigningFormat::X509 => "x509",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "openpgp" => Some(SigningFormat::OpenPgp),
+            "ssh" => Some(SigningFormat::Ssh),
+            "x509" => Some(SigningFormat::X509),
+            _ => None,
+        }
+    }
+}
+
+/// Overrides for how a commit is signed.
+///
+/// Fields left as `None` defer to the repository's git config
+/// (`commit.gpgSign`, `gpg.format` and `user.signingKey`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommitSigning {
+    pub enabled: Option<bool>,
+    pub format: Option<SigningFormat>,
+    pub key: Option<String>,
+}
+
+impl CommitSigning {
+    /// Config overrides that must precede the `commit` subcommand.
+    fn config_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(format) = self.format {
+            args.push("-c".to_string());
+            args.push(format!("gpg.format={}", format.as_str()));
+        }
+        if let Some(key) = self.key.as_ref().filter(|key| !key.is_empty()) {
+            args.push("-c".to_string());
+            args.push(format!("user.signingKey={key}"));
+        }
+        args
+    }
+
+    fn commit_flag(&self) -> Option<&'static str> {
+        match self.enabled? {
+            true => Some("--gpg-sign"),
+            false => Some("--no-gpg-sign"),
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
@@ -428,6 +533,73 @@ pub struct CommitDetails {
     pub commit_timestamp: i64,
     pub author_email: SharedString,
     pub author_name: SharedString,
+    /// `None` when the commit is not signed.
+    pub signature: Option<CommitSignature>,
+}
+
+/// The verification result for a signed commit, as reported by `%G?`.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// A good signature from a trusted key.
+    Good,
+    /// A good signature from a key whose validity is unknown.
+    GoodUnknownValidity,
+    /// A good signature that has expired, or was made by an expired key.
+    Expired,
+    /// A good signature made by a revoked key.
+    Revoked,
+    Bad,
+    /// The signature could not be checked, e.g. because the key is missing.
+    Unverified,
+}
+
+impl SignatureStatus {
+    /// Parses the `%G?` placeholder. Returns `None` for unsigned commits.
+    pub fn from_git_code(code: &str) -> Option<Self> {
+        match code.trim() {
+            "G" => Some(Self::Good),
+            "U" => Some(Self::GoodUnknownValidity),
+            "X" | "Y" => Some(Self::Expired),
+            "R" => Some(Self::Revoked),
+            "B" => Some(Self::Bad),
+            "E" => Some(Self::Unverified),
+            _ => None,
+        }
+    }
+
+    pub fn is_verified(&self) -> bool {
+        matches!(self, Self::Good | Self::GoodUnknownValidity)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Good => "Verified",
+            Self::GoodUnknownValidity => "Signed (unknown validity)",
+            Self::Expired => "Signature expired",
+            Self::Revoked => "Signed with a revoked key",
+            Self::Bad => "Bad signature",
+            Self::Unverified => "Signature unverified",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct CommitSignature {
+    pub status: SignatureStatus,
+    /// The signer, as reported by `%GS`.
+    pub signer: SharedString,
+    /// The signing key, as reported by `%GK`.
+    pub key: SharedString,
+}
+
+impl CommitSignature {
+    fn parse(status: &str, signer: &str, key: &str) -> Option<Self> {
+        Some(Self {
+            status: SignatureStatus::from_git_code(status)?,
+            signer: signer.trim().to_string().into(),
+            key: key.trim().to_string().into(),
+        })
+    }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -484,6 +656,8 @@ pub enum ResetMode {
     /// Reset the branch pointer and index, leave worktree unchanged (this makes it look as though things that were
     /// committed are now unstaged).
     Mixed,
+    /// Reset the branch pointer, index and worktree, discarding all uncommitted changes to tracked files.
+    Hard,
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -693,6 +867,9 @@ pub trait GitRepository: Send + Sync {
     /// Returns the URL of the remote with the given name.
     fn remote_url(&self, name: &str) -> BoxFuture<'_, Option<String>>;
 
+    /// Whether git's config signs commits by default (`commit.gpgSign`).
+    fn signs_commits_by_default(&self) -> BoxFuture<'_, bool>;
+
     /// Resolve a list of refs to SHAs.
     fn revparse_batch(&self, revs: Vec<String>) -> BoxFuture<'_, Result<Vec<Option<String>>>>;
 
@@ -724,6 +901,21 @@ pub trait GitRepository: Send + Sync {
 
     fn delete_branch(&self, name: String) -> BoxFuture<'_, Result<()>>;
 
+    /// Lists the tags in the repository, most recently created first.
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>>;
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>>;
+
+    /// Lists the reflog of `reference`, which is `HEAD` or a branch name, most recent first.
+    fn reflog(&self, reference: String) -> BoxFuture<'_, Result<Vec<ReflogEntry>>>;
+
+    /// Checks out `commit` with a detached `HEAD`.
+    fn checkout_commit(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
     fn worktrees(&self) -> BoxFuture<'_, Result<Vec<Worktree>>>;
 
     fn create_worktree(
@@ -753,13 +945,27 @@ pub trait GitRepository: Send + Sync {
 
     fn show(&self, commit: String) -> BoxFuture<'_, Result<CommitDetails>>;
 
+    /// Verifies the signatures of `commits`. Unsigned commits map to `None`.
+    fn commit_signatures(
+        &self,
+        commits: Vec<Oid>,
+    ) -> BoxFuture<'_, Result<HashMap<Oid, Option<CommitSignature>>>>;
+
     fn load_commit(&self, commit: String, cx: AsyncApp) -> BoxFuture<'_, Result<CommitDiff>>;
     fn blame(
         &self,
         path: RepoPath,
         content: Rope,
         line_ending: LineEnding,
+        options: crate::blame::BlameOptions,
     ) -> BoxFuture<'_, Result<crate::blame::Blame>>;
+    /// Loads the file at `path` as it was committed in `revision`, and blames it there.
+    fn blame_at_revision(
+        &self,
+        revision: String,
+        path: RepoPath,
+        options: crate::blame::BlameOptions,
+    ) -> BoxFuture<'_, Result<crate::blame::RevisionBlame>>;
     fn file_history(&self, path: RepoPath) -> BoxFuture<'_, Result<FileHistory>>;
     fn file_history_paginated(
         &self,
@@ -865,6 +1071,100 @@ pub trait GitRepository: Send + Sync {
         cx: AsyncApp,
     ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
 
+    /// Lists the commits an interactive rebase onto `base` would replay.
+    ///
+    /// Without a `base`, the merge base of HEAD with its upstream, or with the default branch
+    /// when there is no upstream, is used.
+    fn rebase_plan(&self, base: Option<String>) -> BoxFuture<'_, Result<RebasePlan>>;
+
+    /// Starts an interactive rebase of the commits after `base`, using `entries` as the todo list.
+    ///
+    /// Stopping for conflicts or at an `edit` step is not an error; the rebase is then resumed
+    /// with [`GitRepository::rebase_control`].
+    fn rebase_interactive(
+        &self,
+        base: String,
+        entries: Vec<RebaseTodoEntry>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    fn rebase_control(
+        &self,
+        control: RebaseControl,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    fn rebase_progress(&self) -> BoxFuture<'_, Option<RebaseProgress>>;
+
+    /// Starts a bisect with `bad` marked as bad and each of `good` marked as good.
+    ///
+    /// Returns the commit checked out for testing once both a bad and a good commit are known.
+    fn bisect_start(
+        &self,
+        bad: Option<String>,
+        good: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<Option<BisectStep>>>;
+
+    /// Marks `commit`, or the commit being tested when `None`, in the bisect in progress.
+    fn bisect_mark(
+        &self,
+        mark: BisectMark,
+        commit: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<Option<BisectStep>>>;
+
+    /// Ends the bisect in progress, checking out the commit it started from.
+    fn bisect_reset(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    fn bisect_state(&self) -> BoxFuture<'_, Option<BisectState>>;
+
+    /// Lists the submodules of the repository, which is empty when it has no `.gitmodules`.
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>>;
+
+    /// Runs `git submodule <command>` for `paths`, or for every submodule when `paths` is empty.
+    fn submodule_command(
+        &self,
+        command: SubmoduleCommand,
+        paths: Vec<RepoPath>,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
+
+    /// Applies the changes introduced by `commits` on top of HEAD.
+    ///
+    /// `mainline` selects the parent to diff against when a commit is a merge.
+    fn cherry_pick(
+        &self,
+        commits: Vec<String>,
+        mainline: Option<u32>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Creates commits that undo the changes introduced by `commits`.
+    ///
+    /// `mainline` selects the parent to diff against when a commit is a merge.
+    fn revert(
+        &self,
+        commits: Vec<String>,
+        mainline: Option<u32>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Creates a tag pointing at `commit`, annotated when a `message` is given.
+    ///
+    /// Signed tags are always annotated, and use the tag name as their message when none is
+    /// given.
+    fn create_tag(
+        &self,
+        name: String,
+        commit: String,
+        message: Option<String>,
+        sign: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
     fn get_push_remote(&self, branch: String) -> BoxFuture<'_, Result<Option<Remote>>>;
 
     fn get_branch_remote(&self, branch: String) -> BoxFuture<'_, Result<Option<Remote>>>;
@@ -991,6 +1291,34 @@ impl RealGitRepository {
         *self.any_git_binary_help_output.lock() = Some(output.clone());
         output
     }
+
+    /// Runs `git cherry-pick` or `git revert` for `commits`, without opening an editor.
+    fn apply_commits(
+        &self,
+        command: &'static str,
+        commits: Vec<String>,
+        mainline: Option<u32>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let executor = self.executor.clone();
+        self.executor
+            .spawn(async move {
+                anyhow::ensure!(!commits.is_empty(), "no commits to {command}");
+                let mut args = vec![command.to_string(), "--no-edit".to_string()];
+                if let Some(mainline) = mainline {
+                    args.extend(["--mainline".into(), mainline.to_string()]);
+                }
+                args.extend(commits);
+                GitBinary::new(git_binary_path, working_directory?, executor)
+                    .envs((*env).clone())
+                    .run(&args)
+                    .await?;
+                Ok(())
+            })
+            .boxed()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -1069,14 +1397,14 @@ impl GitRepository for RealGitRepository {
                         "--no-optional-locks",
                         "show",
                         "--no-patch",
-                        "--format=%H%x00%B%x00%at%x00%ae%x00%an%x00",
+                        "--format=%H%x00%B%x00%at%x00%ae%x00%an%x00%G?%x00%GS%x00%GK%x00",
                         &commit,
                     ])
                     .output()
                     .await?;
                 let output = std::str::from_utf8(&output.stdout)?;
                 let fields = output.split('\0').collect::<Vec<_>>();
-                if fields.len() != 6 {
+                if fields.len() != 9 {
                     bail!("unexpected git-show output for {commit:?}: {output:?}")
                 }
                 let sha = fields[0].to_string().into();
@@ -1084,17 +1412,64 @@ impl GitRepository for RealGitRepository {
                 let commit_timestamp = fields[2].parse()?;
                 let author_email = fields[3].to_string().into();
                 let author_name = fields[4].to_string().into();
+                let signature = CommitSignature::parse(fields[5], fields[6], fields[7]);
                 Ok(CommitDetails {
                     sha,
                     message,
                     commit_timestamp,
                     author_email,
                     author_name,
+                    signature,
                 })
             })
             .boxed()
     }
 
+    fn commit_signatures(
+        &self,
+        commits: Vec<Oid>,
+    ) -> BoxFuture<'_, Result<HashMap<Oid, Option<CommitSignature>>>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        self.executor
+            .spawn(async move {
+                // Without any commits, `git log` would show `HEAD`.
+                if commits.is_empty() {
+                    return Ok(HashMap::default());
+                }
+                let working_directory = working_directory?;
+                let output = new_command(git_binary_path)
+                    .current_dir(&working_directory)
+                    .args([
+                        "--no-optional-locks",
+                        "log",
+                        "--no-walk=unsorted",
+                        "--format=%H%x00%G?%x00%GS%x00%GK%x00",
+                    ])
+                    .args(commits.iter().map(|commit| commit.to_string()))
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "git log failed: {}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                let output = std::str::from_utf8(&output.stdout)?;
+                let mut signatures = HashMap::default();
+                let mut fields = output.split('\0');
+                while let (Some(sha), Some(status), Some(signer), Some(key)) =
+                    (fields.next(), fields.next(), fields.next(), fields.next())
+                {
+                    signatures.insert(
+                        sha.trim().parse()?,
+                        CommitSignature::parse(status, signer, key),
+                    );
+                }
+                Ok(signatures)
+            })
+            .boxed()
+    }
+
     fn load_commit(&self, commit: String, cx: AsyncApp) -> BoxFuture<'_, Result<CommitDiff>> {
         let Some(working_directory) = self.repository.lock().workdir().map(ToOwned::to_owned)
         else {
@@ -1240,6 +1615,7 @@ impl GitRepository for RealGitRepository {
             let mode_flag = match mode {
                 ResetMode::Mixed => "--mixed",
                 ResetMode::Soft => "--soft",
+                ResetMode::Hard => "--hard",
             };
 
             let output = new_command(&self.any_git_binary_path)
@@ -1431,6 +1807,18 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn signs_commits_by_default(&self) -> BoxFuture<'_, bool> {
+        let repo = self.repository.clone();
+        self.executor
+            .spawn(async move {
+                let repo = repo.lock();
+                repo.config()
+                    .and_then(|config| config.get_bool("commit.gpgSign"))
+                    .unwrap_or(false)
+            })
+            .boxed()
+    }
+
     fn revparse_batch(&self, revs: Vec<String>) -> BoxFuture<'_, Result<Vec<Option<String>>>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.any_git_binary_path.clone();
@@ -1862,11 +2250,99 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                let fields = [
+                    "%(refname:strip=2)",
+                    "%(objecttype)",
+                    "%(objectname)",
+                    "%(*objectname)",
+                    "%(creatordate:unix)",
+                    "%(contents:subject)",
+                ]
+                .join("%00");
+                let output = GitBinary::new(git_binary_path, working_directory?, executor)
+                    .run(&[
+                        "for-each-ref",
+                        "refs/tags",
+                        "--sort=-creatordate",
+                        "--format",
+                        fields.as_str(),
+                    ])
+                    .await?;
+                Ok(parse_tag_input(&output))
+            })
+            .boxed()
+    }
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                GitBinary::new(git_binary_path, working_directory?, executor)
+                    .run(&["tag", "--delete", &name])
+                    .await?;
+                anyhow::Ok(())
+            })
+            .boxed()
+    }
+
+    fn reflog(&self, reference: String) -> BoxFuture<'_, Result<Vec<ReflogEntry>>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                let output = GitBinary::new(git_binary_path, working_directory?, executor)
+                    .run(&[
+                        "log",
+                        "--walk-reflogs",
+                        "--date=unix",
+                        "--format=%H%x00%gd%x00%gs%x00%s",
+                        &reference,
+                        "--",
+                    ])
+                    .await?;
+                Ok(parse_reflog_input(&reference, &output))
+            })
+            .boxed()
+    }
+
+    fn checkout_commit(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                GitBinary::new(git_binary_path, working_directory?, executor)
+                    .envs((*env).clone())
+                    .run(&["switch", "--detach", &commit])
+                    .await?;
+                anyhow::Ok(())
+            })
+            .boxed()
+    }
+
     fn blame(
         &self,
         path: RepoPath,
         content: Rope,
         line_ending: LineEnding,
+        options: crate::blame::BlameOptions,
     ) -> BoxFuture<'_, Result<crate::blame::Blame>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.any_git_binary_path.clone();
@@ -1880,12 +2356,45 @@ impl GitRepository for RealGitRepository {
                     &path,
                     &content,
                     line_ending,
+                    &options,
                 )
                 .await
             })
             .boxed()
     }
 
+    fn blame_at_revision(
+        &self,
+        revision: String,
+        path: RepoPath,
+        options: crate::blame::BlameOptions,
+    ) -> BoxFuture<'_, Result<crate::blame::RevisionBlame>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                let working_directory = working_directory?;
+                let git =
+                    GitBinary::new(git_binary_path.clone(), working_directory.clone(), executor);
+                let mut text = git
+                    .run_raw(["show", &format!("{revision}:{}", path.as_unix_str())])
+                    .await?;
+                LineEnding::normalize(&mut text);
+                let blame = crate::blame::Blame::for_revision(
+                    &git_binary_path,
+                    &working_directory,
+                    &path,
+                    &revision,
+                    &options,
+                )
+                .await?;
+                Ok(crate::blame::RevisionBlame { text, blame })
+            })
+            .boxed()
+    }
+
     fn file_history(&self, path: RepoPath) -> BoxFuture<'_, Result<FileHistory>> {
         self.file_history_paginated(path, 0, None)
     }
@@ -2208,6 +2717,7 @@ impl GitRepository for RealGitRepository {
             let mut cmd = new_command(git_binary_path);
             cmd.current_dir(&working_directory?)
                 .envs(env.iter())
+                .args(options.signing.config_args())
                 .args(["commit", "--quiet", "-m"])
                 .arg(&message.to_string())
                 .arg("--cleanup=strip")
@@ -2223,6 +2733,10 @@ impl GitRepository for RealGitRepository {
                 cmd.arg("--signoff");
             }
 
+            if let Some(flag) = options.signing.commit_flag() {
+                cmd.arg(flag);
+            }
+
             if let Some((name, email)) = name_and_email {
                 cmd.arg("--author").arg(&format!("{name} <{email}>"));
             }
@@ -2337,16 +2851,335 @@ impl GitRepository for RealGitRepository {
         .boxed()
     }
 
-    fn get_push_remote(&self, branch: String) -> BoxFuture<'_, Result<Option<Remote>>> {
+    fn rebase_plan(&self, base: Option<String>) -> BoxFuture<'_, Result<RebasePlan>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.any_git_binary_path.clone();
-        self.executor
-            .spawn(async move {
-                let working_directory = working_directory?;
-                let output = new_command(&git_binary_path)
-                    .current_dir(&working_directory)
-                    .args(["rev-parse", "--abbrev-ref"])
-                    .arg(format!("{branch}@{{push}}"))
+        let executor = self.executor.clone();
+        async move {
+            let git = GitBinary::new(git_binary_path, working_directory?, executor);
+            let base = match base {
+                Some(base) => base,
+                None => {
+                    let upstream = match git
+                        .run([
+                            "rev-parse",
+                            "--abbrev-ref",
+                            "--symbolic-full-name",
+                            "@{upstream}",
+                        ])
+                        .await
+                    {
+                        Ok(upstream) => upstream,
+                        Err(_) => self
+                            .default_branch(true)
+                            .await?
+                            .context("no upstream or default branch to rebase onto")?
+                            .to_string(),
+                    };
+                    git.run(["merge-base", "HEAD", &upstream]).await?
+                }
+            };
+            let base = git
+                .run(["rev-parse", "--verify", &format!("{base}^{{commit}}")])
+                .await?;
+            let log = git
+                .run([
+                    "log",
+                    "--reverse",
+                    "--no-merges",
+                    "--format=%H%x00%s",
+                    &format!("{base}..HEAD"),
+                ])
+                .await?;
+            let entries = log
+                .lines()
+                .filter_map(|line| {
+                    let (sha, subject) = line.split_once('\0')?;
+                    Some(RebaseTodoEntry {
+                        sha: sha.to_string().into(),
+                        subject: subject.to_string().into(),
+                        action: RebaseAction::Pick,
+                        message: None,
+                    })
+                })
+                .collect();
+            Ok(RebasePlan {
+                base: base.into(),
+                entries,
+            })
+        }
+        .boxed()
+    }
+
+    fn rebase_interactive(
+        &self,
+        base: String,
+        entries: Vec<RebaseTodoEntry>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let git_dir = self.path();
+        let executor = self.executor.clone();
+        self.executor
+            .spawn(async move {
+                anyhow::ensure!(
+                    RebaseProgress::load(&git_dir).is_none(),
+                    "a rebase is already in progress"
+                );
+                let messages_dir = git_dir.join(REBASE_MESSAGES_DIR);
+                smol::fs::remove_dir_all(&messages_dir).await.ok();
+                smol::fs::create_dir_all(&messages_dir).await?;
+                let message_path = |ix: usize| messages_dir.join(format!("{ix}.txt"));
+                for (ix, entry) in entries.iter().enumerate() {
+                    if entry.action == RebaseAction::Reword
+                        && let Some(message) = &entry.message
+                    {
+                        smol::fs::write(message_path(ix), message.as_bytes()).await?;
+                    }
+                }
+                let todo_path = messages_dir.join("git-rebase-todo");
+                smol::fs::write(&todo_path, todo_list(&entries, message_path)).await?;
+
+                let mut envs = (*env).clone();
+                envs.insert(
+                    "GIT_SEQUENCE_EDITOR".into(),
+                    format!("cp {}", shell_quote(&todo_path.to_string_lossy())),
+                );
+                envs.insert("GIT_EDITOR".into(), "true".into());
+                let git = GitBinary::new(git_binary_path, working_directory?, executor).envs(envs);
+                let result = git
+                    .run(["rebase", "--interactive", "--no-autosquash", &base])
+                    .await;
+                rebase_result(result, None, &git_dir)
+            })
+            .boxed()
+    }
+
+    fn rebase_control(
+        &self,
+        control: RebaseControl,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let git_dir = self.path();
+        let executor = self.executor.clone();
+        self.executor
+            .spawn(async move {
+                let before = RebaseProgress::load(&git_dir);
+                anyhow::ensure!(before.is_some(), "no rebase in progress");
+                let mut envs = (*env).clone();
+                envs.insert("GIT_EDITOR".into(), "true".into());
+                let git = GitBinary::new(git_binary_path, working_directory?, executor).envs(envs);
+                let result = git.run(["rebase", control.as_arg()]).await;
+                rebase_result(result, before, &git_dir)
+            })
+            .boxed()
+    }
+
+    fn rebase_progress(&self) -> BoxFuture<'_, Option<RebaseProgress>> {
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move { RebaseProgress::load(&git_dir) })
+            .boxed()
+    }
+
+    fn bisect_start(
+        &self,
+        bad: Option<String>,
+        good: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<Option<BisectStep>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let git_dir = self.path();
+        let executor = self.executor.clone();
+        self.executor
+            .spawn(async move {
+                anyhow::ensure!(
+                    BisectState::load(&git_dir).is_none(),
+                    "a bisect is already in progress"
+                );
+                let git = GitBinary::new(git_binary_path, working_directory?, executor)
+                    .envs((*env).clone());
+                let output = match bad {
+                    // With a bad commit, the good ones can be given to `start` directly.
+                    Some(bad) => {
+                        let mut args = vec!["bisect".to_string(), "start".to_string(), bad];
+                        args.extend(good);
+                        args.push("--".to_string());
+                        git.run(args).await?
+                    }
+                    None => {
+                        let mut output = git.run(["bisect", "start"]).await?;
+                        for good in good {
+                            output = git.run(["bisect", "good", &good]).await?;
+                        }
+                        output
+                    }
+                };
+                Ok(parse_bisect_step(&output))
+            })
+            .boxed()
+    }
+
+    fn bisect_mark(
+        &self,
+        mark: BisectMark,
+        commit: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<Option<BisectStep>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let git_dir = self.path();
+        let executor = self.executor.clone();
+        self.executor
+            .spawn(async move {
+                anyhow::ensure!(
+                    BisectState::load(&git_dir).is_some(),
+                    "no bisect in progress"
+                );
+                let git = GitBinary::new(git_binary_path, working_directory?, executor)
+                    .envs((*env).clone());
+                let mut args = vec!["bisect", mark.as_arg()];
+                args.extend(commit.as_deref());
+                let output = git.run(args).await?;
+                Ok(parse_bisect_step(&output))
+            })
+            .boxed()
+    }
+
+    fn bisect_reset(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let executor = self.executor.clone();
+        self.executor
+            .spawn(async move {
+                let git = GitBinary::new(git_binary_path, working_directory?, executor)
+                    .envs((*env).clone());
+                git.run(["bisect", "reset"]).await?;
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn bisect_state(&self) -> BoxFuture<'_, Option<BisectState>> {
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move { BisectState::load(&git_dir) })
+            .boxed()
+    }
+
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let executor = self.executor.clone();
+        self.executor
+            .spawn(async move {
+                let working_directory = working_directory?;
+                if !working_directory.join(".gitmodules").exists() {
+                    return Ok(Vec::new());
+                }
+                let output = GitBinary::new(git_binary_path, working_directory, executor)
+                    .run(["--no-optional-locks", "submodule", "status"])
+                    .await?;
+                Ok(parse_submodule_status(&output))
+            })
+            .boxed()
+    }
+
+    fn submodule_command(
+        &self,
+        command: SubmoduleCommand,
+        paths: Vec<RepoPath>,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.system_git_binary_path.clone();
+        let executor = cx.background_executor().clone();
+        // Updating may fetch from the submodule's remote, so like fetch this must not run on
+        // the background thread in case it opens a credential helper.
+        async move {
+            let git_binary_path =
+                git_binary_path.context("git not found on $PATH, can't update submodules")?;
+            let mut command_builder = new_command(git_binary_path);
+            command_builder
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .args(["submodule", command.as_arg(), "--"])
+                .args(paths.iter().map(|path| path.as_std_path()))
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+
+            run_git_command(env, ask_pass, command_builder, executor).await
+        }
+        .boxed()
+    }
+
+    fn cherry_pick(
+        &self,
+        commits: Vec<String>,
+        mainline: Option<u32>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.apply_commits("cherry-pick", commits, mainline, env)
+    }
+
+    fn revert(
+        &self,
+        commits: Vec<String>,
+        mainline: Option<u32>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.apply_commits("revert", commits, mainline, env)
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        commit: String,
+        message: Option<String>,
+        sign: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let executor = self.executor.clone();
+        self.executor
+            .spawn(async move {
+                let mut args = vec!["tag".to_string()];
+                let message = match message {
+                    Some(message) => Some(message),
+                    None if sign => Some(name.clone()),
+                    None => None,
+                };
+                if let Some(message) = message {
+                    args.push(if sign { "--sign" } else { "--annotate" }.into());
+                    args.extend(["--message".into(), message]);
+                }
+                args.extend([name, commit]);
+                GitBinary::new(git_binary_path, working_directory?, executor)
+                    .envs((*env).clone())
+                    .run(&args)
+                    .await?;
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn get_push_remote(&self, branch: String) -> BoxFuture<'_, Result<Option<Remote>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let working_directory = working_directory?;
+                let output = new_command(&git_binary_path)
+                    .current_dir(&working_directory)
+                    .args(["rev-parse", "--abbrev-ref"])
+                    .arg(format!("{branch}@{{push}}"))
                     .output()
                     .await?;
                 if !output.status.success() {
@@ -2831,6 +3664,26 @@ impl GitRepository for RealGitRepository {
     }
 }
 
+/// Interprets the outcome of a `git rebase` invocation.
+///
+/// Git exits with an error when a rebase stops for conflicts, which is expected, so the command
+/// only failed if the rebase did not move to a different step.
+fn rebase_result(
+    result: Result<String>,
+    before: Option<RebaseProgress>,
+    git_dir: &Path,
+) -> Result<()> {
+    let after = RebaseProgress::load(git_dir);
+    if after.is_none() {
+        std::fs::remove_dir_all(git_dir.join(REBASE_MESSAGES_DIR)).ok();
+    }
+    match result {
+        Ok(_) => Ok(()),
+        Err(_) if after.is_some() && after != before => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
 async fn run_commit_data_reader(
     git_binary_path: PathBuf,
     working_directory: PathBuf,
@@ -3352,6 +4205,59 @@ fn parse_branch_input(input: &str) -> Result<Vec<Branch>> {
     Ok(branches)
 }
 
+/// Parses `git log --walk-reflogs --date=unix` output, where each entry's selector holds the
+/// time the ref was moved rather than the entry's position.
+fn parse_reflog_input(reference: &str, input: &str) -> Vec<ReflogEntry> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\x00');
+            let sha = fields.next().filter(|sha| !sha.is_empty())?;
+            let timestamp = fields
+                .next()?
+                .rsplit_once("@{")
+                .and_then(|(_, timestamp)| timestamp.strip_suffix('}'))
+                .and_then(|timestamp| timestamp.parse::<i64>().ok())
+                .unwrap_or_default();
+            let message = fields.next()?;
+            let subject = fields.next().unwrap_or_default();
+            Some((sha, timestamp, message, subject))
+        })
+        .enumerate()
+        .map(|(ix, (sha, timestamp, message, subject))| ReflogEntry {
+            selector: format!("{reference}@{{{ix}}}").into(),
+            sha: sha.to_string().into(),
+            message: message.to_string().into(),
+            subject: subject.to_string().into(),
+            timestamp,
+        })
+        .collect()
+}
+
+fn parse_tag_input(input: &str) -> Vec<Tag> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\x00');
+            let name = fields.next().filter(|name| !name.is_empty())?;
+            let object_type = fields.next()?;
+            let object_sha = fields.next()?;
+            let peeled_sha = fields.next()?;
+            let timestamp = fields.next()?.parse::<i64>().unwrap_or_default();
+            let subject = fields.next().unwrap_or_default();
+            let annotated = object_type == "tag";
+            Some(Tag {
+                name: name.to_string().into(),
+                sha: if annotated { peeled_sha } else { object_sha }
+                    .to_string()
+                    .into(),
+                message: annotated.then(|| subject.to_string().into()),
+                timestamp,
+            })
+        })
+        .collect()
+}
+
 fn parse_upstream_track(upstream_track: &str) -> Result<UpstreamTracking> {
     if upstream_track.is_empty() {
         return Ok(UpstreamTracking::Tracked(UpstreamTrackingStatus {
@@ -4256,6 +5162,620 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_commit_signing_args() {
+        let signing = CommitSigning::default();
+        assert!(signing.config_args().is_empty());
+        assert_eq!(signing.commit_flag(), None);
+
+        let signing = CommitSigning {
+            enabled: Some(true),
+            format: Some(SigningFormat::Ssh),
+            key: Some("~/.ssh/id_ed25519.pub".into()),
+        };
+        assert_eq!(
+            signing.config_args(),
+            [
+                "-c",
+                "gpg.format=ssh",
+                "-c",
+                "user.signingKey=~/.ssh/id_ed25519.pub"
+            ]
+        );
+        assert_eq!(signing.commit_flag(), Some("--gpg-sign"));
+
+        let signing = CommitSigning {
+            enabled: Some(false),
+            format: None,
+            key: Some(String::new()),
+        };
+        assert!(signing.config_args().is_empty());
+        assert_eq!(signing.commit_flag(), Some("--no-gpg-sign"));
+    }
+
+    #[test]
+    fn test_signature_parsing() {
+        assert_eq!(CommitSignature::parse("N", "", ""), None);
+        assert_eq!(
+            CommitSignature::parse("G", "Jane <jane@example.com>", "SHA256:abc"),
+            Some(CommitSignature {
+                status: SignatureStatus::Good,
+                signer: "Jane <jane@example.com>".into(),
+                key: "SHA256:abc".into(),
+            })
+        );
+        assert_eq!(
+            CommitSignature::parse("E", "", "ABCDEF").map(|signature| signature.status),
+            Some(SignatureStatus::Unverified)
+        );
+
+        let sha = Oid::from_str("1111111111111111111111111111111111111111").unwrap();
+        let signed = "tree 2222222222222222222222222222222222222222\n\
+            author Jane <jane@example.com> 1700000000 +0000\n\
+            committer Jane <jane@example.com> 1700000000 +0000\n\
+            gpgsig -----BEGIN SSH SIGNATURE-----\n \
+            U1NIU0lH\n \
+            -----END SSH SIGNATURE-----\n\
+            \n\
+            Signed commit\n";
+        let data = parse_cat_file_commit(sha, signed).unwrap();
+        assert!(data.is_signed);
+        assert_eq!(data.subject.as_ref(), "Signed commit");
+        assert_eq!(data.author_name.as_ref(), "Jane");
+
+        let unsigned = "tree 2222222222222222222222222222222222222222\n\
+            author Jane <jane@example.com> 1700000000 +0000\n\
+            \n\
+            Unsigned commit\n";
+        assert!(!parse_cat_file_commit(sha, unsigned).unwrap().is_signed);
+    }
+
+    #[gpui::test]
+    async fn test_cherry_pick_revert_and_tag(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        let mut shas = Vec::new();
+        for file in ["a.txt", "b.txt"] {
+            smol::fs::write(repo_dir.path().join(file), file)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path(file)], env.clone())
+                .await
+                .unwrap();
+            repo.commit(
+                format!("Add {file}").into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+            shas.push(repo.head_sha().await.unwrap());
+        }
+        let b_path = repo_dir.path().join("b.txt");
+
+        repo.reset(shas[0].clone(), ResetMode::Hard, env.clone())
+            .await
+            .unwrap();
+        assert!(!b_path.exists());
+
+        repo.cherry_pick(vec![shas[1].clone()], None, env.clone())
+            .await
+            .unwrap();
+        assert!(b_path.exists());
+        let picked = repo.head_sha().await.unwrap();
+        assert_ne!(picked, shas[1]);
+        assert_eq!(repo.show(picked).await.unwrap().message.trim(), "Add b.txt");
+
+        repo.revert(vec!["HEAD".into()], None, env.clone())
+            .await
+            .unwrap();
+        assert!(!b_path.exists());
+
+        repo.create_tag(
+            "v1".into(),
+            shas[0].clone(),
+            Some("First release".into()),
+            false,
+            env.clone(),
+        )
+        .await
+        .unwrap();
+        repo.create_tag("lightweight".into(), shas[1].clone(), None, false, env)
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.revparse_batch(vec!["v1^{commit}".into(), "lightweight".into()])
+                .await
+                .unwrap(),
+            vec![Some(shas[0].clone()), Some(shas[1].clone())]
+        );
+
+        let mut tags = repo.tags().await.unwrap();
+        tags.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(
+            tags.iter()
+                .map(|tag| (tag.name.as_ref(), tag.sha.to_string(), tag.message.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("lightweight", shas[1].clone(), None),
+                ("v1", shas[0].clone(), Some("First release".into())),
+            ]
+        );
+
+        repo.delete_tag("v1".into()).await.unwrap();
+        let tags = repo.tags().await.unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].name.as_ref(), "lightweight");
+    }
+
+    #[gpui::test]
+    async fn test_commit_signing(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let key_dir = tempfile::tempdir().unwrap();
+        let key_path = key_dir.path().join("key");
+        let Ok(status) = std::process::Command::new("ssh-keygen")
+            .args(["-t", "ed25519", "-N", "", "-C", "zed", "-q", "-f"])
+            .arg(&key_path)
+            .status()
+        else {
+            // SSH signing needs `ssh-keygen`.
+            return;
+        };
+        assert!(status.success());
+        let public_key_path = key_path.with_extension("pub");
+        let public_key = smol::fs::read_to_string(&public_key_path).await.unwrap();
+        let allowed_signers_path = key_dir.path().join("allowed_signers");
+        smol::fs::write(&allowed_signers_path, format!("hi@zed.dev {public_key}"))
+            .await
+            .unwrap();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        let git2_repo = git2::Repository::init(repo_dir.path()).unwrap();
+        let mut config = git2_repo.config().unwrap();
+        config
+            .set_str(
+                "gpg.ssh.allowedSignersFile",
+                allowed_signers_path.to_str().unwrap(),
+            )
+            .unwrap();
+        config.set_bool("commit.gpgSign", true).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        assert!(repo.signs_commits_by_default().await);
+        let env = Arc::new(checkpoint_author_envs());
+
+        let mut shas = Vec::new();
+        for (content, enabled) in [("signed", true), ("unsigned", false)] {
+            smol::fs::write(repo_dir.path().join("file"), content)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path("file")], env.clone())
+                .await
+                .unwrap();
+            repo.commit(
+                content.to_string().into(),
+                None,
+                CommitOptions {
+                    signing: CommitSigning {
+                        enabled: Some(enabled),
+                        format: Some(SigningFormat::Ssh),
+                        key: Some(public_key_path.to_str().unwrap().to_string()),
+                    },
+                    ..CommitOptions::default()
+                },
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+            shas.push(repo.head_sha().await.unwrap());
+        }
+
+        let signature = repo.show(shas[0].clone()).await.unwrap().signature.unwrap();
+        assert_eq!(signature.status, SignatureStatus::Good);
+        assert_eq!(signature.signer.as_ref(), "hi@zed.dev");
+        assert_eq!(repo.show(shas[1].clone()).await.unwrap().signature, None);
+
+        let oids = shas
+            .iter()
+            .map(|sha| sha.parse::<Oid>().unwrap())
+            .collect::<Vec<_>>();
+        let signatures = repo.commit_signatures(oids.clone()).await.unwrap();
+        assert_eq!(signatures[&oids[0]], Some(signature));
+        assert_eq!(signatures[&oids[1]], None);
+    }
+
+    #[gpui::test]
+    async fn test_interactive_rebase(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        let mut shas = Vec::new();
+        for (file, content, message) in [
+            ("a.txt", "a", "Add a.txt"),
+            ("b.txt", "b", "Add b.txt"),
+            ("c.txt", "c", "Add c.txt"),
+            ("b.txt", "bb", "Change b.txt"),
+            ("a.txt", "2", "Write 2"),
+            ("a.txt", "3", "Write 3"),
+        ] {
+            smol::fs::write(repo_dir.path().join(file), content)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path(file)], env.clone())
+                .await
+                .unwrap();
+            repo.commit(
+                message.into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+            shas.push(repo.head_sha().await.unwrap());
+        }
+        let message = async |rev: &str| repo.show(rev.into()).await.unwrap().message;
+
+        // Put `c.txt` first with a new message, and squash the change to `b.txt` into the commit
+        // that added it.
+        let plan = repo.rebase_plan(Some(shas[0].clone())).await.unwrap();
+        assert_eq!(plan.base.as_ref(), shas[0]);
+        let mut entries = plan.entries;
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.subject.as_ref())
+                .collect::<Vec<_>>(),
+            [
+                "Add b.txt",
+                "Add c.txt",
+                "Change b.txt",
+                "Write 2",
+                "Write 3"
+            ]
+        );
+        entries.swap(0, 1);
+        entries[0].action = RebaseAction::Reword;
+        entries[0].message = Some("Add c.txt first".into());
+        entries[2].action = RebaseAction::Squash;
+        repo.rebase_interactive(shas[0].clone(), entries, env.clone())
+            .await
+            .unwrap();
+        assert_eq!(repo.rebase_progress().await, None);
+        assert!(
+            !repo_dir
+                .path()
+                .join(".git")
+                .join(REBASE_MESSAGES_DIR)
+                .exists()
+        );
+        assert_eq!(message("HEAD~4").await.trim(), "Add a.txt");
+        assert_eq!(message("HEAD~3").await.trim(), "Add c.txt first");
+        assert_eq!(message("HEAD~2").await.trim(), "Add b.txt\n\nChange b.txt");
+        assert_eq!(message("HEAD").await.trim(), "Write 3");
+        assert_eq!(
+            smol::fs::read_to_string(repo_dir.path().join("b.txt"))
+                .await
+                .unwrap(),
+            "bb"
+        );
+
+        // Dropping "Write 2" makes "Write 3" conflict, which stops the rebase.
+        let head = repo.head_sha().await.unwrap();
+        let base = repo
+            .revparse_batch(vec!["HEAD~2".into()])
+            .await
+            .unwrap()
+            .remove(0)
+            .unwrap();
+        let mut entries = repo.rebase_plan(Some(base.clone())).await.unwrap().entries;
+        entries[0].action = RebaseAction::Drop;
+        let write_3 = entries[1].sha.clone();
+        repo.rebase_interactive(base.clone(), entries.clone(), env.clone())
+            .await
+            .unwrap();
+        let progress = repo.rebase_progress().await.unwrap();
+        assert_eq!(progress.stopped_sha, Some(write_3));
+        assert_eq!((progress.step, progress.total), (2, 2));
+
+        repo.rebase_control(RebaseControl::Abort, env.clone())
+            .await
+            .unwrap();
+        assert_eq!(repo.rebase_progress().await, None);
+        assert_eq!(repo.head_sha().await.unwrap(), head);
+
+        // Resolving the conflict and continuing finishes the rebase.
+        repo.rebase_interactive(base.clone(), entries, env.clone())
+            .await
+            .unwrap();
+        assert!(repo.rebase_progress().await.is_some());
+        smol::fs::write(repo_dir.path().join("a.txt"), "3")
+            .await
+            .unwrap();
+        repo.stage_paths(vec![repo_path("a.txt")], env.clone())
+            .await
+            .unwrap();
+        repo.rebase_control(RebaseControl::Continue, env)
+            .await
+            .unwrap();
+        assert_eq!(repo.rebase_progress().await, None);
+        assert_eq!(message("HEAD").await.trim(), "Write 3");
+        assert_eq!(
+            repo.revparse_batch(vec!["HEAD~1".into()]).await.unwrap(),
+            vec![Some(base)]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_blame_attribution(cx: &mut TestAppContext) {
+        use crate::blame::{Blame, BlameOptions};
+
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        let first = "fn first_function_in_this_file() {}\n";
+        let second = "fn second_function_in_this_file() {}\n";
+        let third = "fn third_function_in_this_file() {}\n";
+        let copied = "fn helper_function_that_was_copied_from_the_other_file() {}\n";
+        let mut shas = Vec::new();
+        for files in [
+            // Add both files.
+            vec![
+                ("a.rs", format!("{first}{second}{third}")),
+                ("b.rs", copied.to_string()),
+            ],
+            // Move the first line of `a.rs` to the end, and copy a line from `b.rs` while
+            // changing it.
+            vec![
+                ("a.rs", format!("{second}{third}{first}{copied}")),
+                ("b.rs", format!("{copied}// changed\n")),
+            ],
+            // Reformat a line.
+            vec![(
+                "a.rs",
+                format!("{}{third}{first}{copied}", second.replace("{}", "{ }")),
+            )],
+        ] {
+            for (file, content) in &files {
+                smol::fs::write(repo_dir.path().join(file), content)
+                    .await
+                    .unwrap();
+            }
+            repo.stage_paths(
+                files.iter().map(|(file, _)| repo_path(file)).collect(),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+            repo.commit(
+                "Change files".into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+            shas.push(repo.head_sha().await.unwrap());
+        }
+
+        let line_shas = |blame: Blame| {
+            let mut line_shas = Vec::new();
+            for entry in blame.entries {
+                for row in entry.range {
+                    line_shas.resize(line_shas.len().max(row as usize + 1), String::new());
+                    line_shas[row as usize] = entry.sha.to_string();
+                }
+            }
+            line_shas
+        };
+        let expected = |commits: [usize; 4]| commits.map(|commit| shas[commit].clone());
+        let content = Rope::from(
+            smol::fs::read_to_string(repo_dir.path().join("a.rs"))
+                .await
+                .unwrap()
+                .as_str(),
+        );
+        let blame_lines = async |options: BlameOptions| {
+            line_shas(
+                repo.blame(
+                    repo_path("a.rs"),
+                    content.clone(),
+                    LineEnding::Unix,
+                    options,
+                )
+                .await
+                .unwrap(),
+            )
+        };
+
+        assert_eq!(
+            blame_lines(BlameOptions::default()).await,
+            expected([2, 0, 1, 1])
+        );
+        assert_eq!(
+            blame_lines(BlameOptions {
+                detect_moves: true,
+                ..Default::default()
+            })
+            .await,
+            expected([2, 0, 0, 1])
+        );
+        assert_eq!(
+            blame_lines(BlameOptions {
+                detect_copies: true,
+                ..Default::default()
+            })
+            .await,
+            expected([2, 0, 0, 0])
+        );
+        assert_eq!(
+            blame_lines(BlameOptions {
+                ignore_revs: vec![shas[2].clone()],
+                ..Default::default()
+            })
+            .await,
+            expected([0, 0, 1, 1])
+        );
+
+        // Blaming before a commit follows the file to its path in the commit's parent.
+        smol::fs::rename(
+            repo_dir.path().join("a.rs"),
+            repo_dir.path().join("renamed.rs"),
+        )
+        .await
+        .unwrap();
+        repo.stage_paths(
+            vec![repo_path("a.rs"), repo_path("renamed.rs")],
+            env.clone(),
+        )
+        .await
+        .unwrap();
+        repo.commit(
+            "Rename a.rs".into(),
+            None,
+            CommitOptions::default(),
+            AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+            env,
+        )
+        .await
+        .unwrap();
+        let blame = repo
+            .blame_at_revision(
+                "HEAD".into(),
+                repo_path("renamed.rs"),
+                BlameOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(blame.text, content.to_string());
+        let reformatted = blame
+            .blame
+            .entries
+            .iter()
+            .find(|entry| entry.range.contains(&0))
+            .unwrap();
+        assert_eq!(reformatted.sha.to_string(), shas[2]);
+        let (parent, path) = reformatted.previous_revision().unwrap();
+        assert_eq!(parent.to_string(), shas[1]);
+        assert_eq!(path, repo_path("a.rs"));
+
+        let before = repo
+            .blame_at_revision(parent.to_string(), path, BlameOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(before.text, format!("{second}{third}{first}{copied}"));
+        assert_eq!(line_shas(before.blame), expected([0, 0, 1, 1]));
+    }
+
+    #[gpui::test]
+    async fn test_reflog_and_checkout_commit(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        let mut shas = Vec::new();
+        for file in ["a.txt", "b.txt"] {
+            smol::fs::write(repo_dir.path().join(file), file)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path(file)], env.clone())
+                .await
+                .unwrap();
+            repo.commit(
+                format!("Add {file}").into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+            shas.push(repo.head_sha().await.unwrap());
+        }
+
+        // Lose the second commit, then find it again through the reflog.
+        repo.reset(shas[0].clone(), ResetMode::Hard, env.clone())
+            .await
+            .unwrap();
+        let reflog = repo.reflog("HEAD".into()).await.unwrap();
+        assert_eq!(
+            reflog
+                .iter()
+                .map(|entry| (entry.selector.as_ref(), entry.sha.to_string()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("HEAD@{0}", shas[0].clone()),
+                ("HEAD@{1}", shas[1].clone()),
+                ("HEAD@{2}", shas[0].clone()),
+            ]
+        );
+        assert!(reflog[0].message.starts_with("reset: moving to"));
+        assert_eq!(reflog[1].subject.as_ref(), "Add b.txt");
+        assert!(reflog.iter().all(|entry| entry.timestamp > 0));
+
+        repo.checkout_commit(reflog[1].sha.to_string(), env)
+            .await
+            .unwrap();
+        assert_eq!(repo.head_sha().await.unwrap(), shas[1]);
+        assert!(repo_dir.path().join("b.txt").exists());
+    }
+
     impl RealGitRepository {
         /// Force a Git garbage collection on the repository.
         fn gc(&self) -> BoxFuture<'_, Result<()>> {
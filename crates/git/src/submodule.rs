@@ -0,0 +1,132 @@
+use crate::{SHORT_SHA_LENGTH, repository::RepoPath};
+use gpui::SharedString;
+
+/// The state of a submodule relative to the commit the superproject records for it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SubmoduleStatus {
+    /// The submodule has not been initialized, so nothing is checked out.
+    Uninitialized,
+    /// The submodule has the recorded commit checked out.
+    Current,
+    /// The submodule has a different commit checked out than the recorded one.
+    OutOfSync,
+    /// The submodule has merge conflicts in the superproject.
+    Conflicted,
+}
+
+impl SubmoduleStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Uninitialized => "not initialized",
+            Self::Current => "up to date",
+            Self::OutOfSync => "out of sync",
+            Self::Conflicted => "conflicted",
+        }
+    }
+}
+
+/// A submodule of a repository, as reported by `git submodule status`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Submodule {
+    pub path: RepoPath,
+    /// The commit checked out in the submodule, or the recorded commit when it is not
+    /// initialized.
+    pub sha: SharedString,
+    pub status: SubmoduleStatus,
+}
+
+impl Submodule {
+    pub fn short_sha(&self) -> &str {
+        &self.sha[..SHORT_SHA_LENGTH.min(self.sha.len())]
+    }
+}
+
+/// Brings submodules in line with the superproject.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SubmoduleCommand {
+    /// Registers submodules from `.gitmodules` in the repository's config.
+    Init,
+    /// Checks out the recorded commit in each initialized submodule.
+    Update,
+    /// Copies submodule URLs from `.gitmodules` into the repository's config.
+    Sync,
+}
+
+impl SubmoduleCommand {
+    pub fn as_arg(&self) -> &'static str {
+        match self {
+            Self::Init => "init",
+            Self::Update => "update",
+            Self::Sync => "sync",
+        }
+    }
+}
+
+/// Parses the output of `git submodule status`, where each line is a status character,
+/// the commit, the path, and an optional description of the commit in parentheses.
+pub fn parse_submodule_status(output: &str) -> Vec<Submodule> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut chars = line.chars();
+            let status = match chars.next()? {
+                '-' => SubmoduleStatus::Uninitialized,
+                '+' => SubmoduleStatus::OutOfSync,
+                'U' => SubmoduleStatus::Conflicted,
+                _ => SubmoduleStatus::Current,
+            };
+            let (sha, rest) = chars.as_str().split_once(' ')?;
+            let path = match rest.rsplit_once(" (") {
+                Some((path, description)) if description.ends_with(')') => path,
+                _ => rest,
+            };
+            Some(Submodule {
+                path: RepoPath::new(path).ok()?,
+                sha: sha.to_string().into(),
+                status,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::repo_path;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_submodule_status() {
+        let output = "\
+ 1111111111111111111111111111111111111111 libs/current (v1.0)
+-2222222222222222222222222222222222222222 libs/uninitialized
++3333333333333333333333333333333333333333 libs/with space (heads/main)
+U4444444444444444444444444444444444444444 libs/conflicted
+";
+        assert_eq!(
+            parse_submodule_status(output),
+            vec![
+                Submodule {
+                    path: repo_path("libs/current"),
+                    sha: "1111111111111111111111111111111111111111".into(),
+                    status: SubmoduleStatus::Current,
+                },
+                Submodule {
+                    path: repo_path("libs/uninitialized"),
+                    sha: "2222222222222222222222222222222222222222".into(),
+                    status: SubmoduleStatus::Uninitialized,
+                },
+                Submodule {
+                    path: repo_path("libs/with space"),
+                    sha: "3333333333333333333333333333333333333333".into(),
+                    status: SubmoduleStatus::OutOfSync,
+                },
+                Submodule {
+                    path: repo_path("libs/conflicted"),
+                    sha: "4444444444444444444444444444444444444444".into(),
+                    status: SubmoduleStatus::Conflicted,
+                },
+            ]
+        );
+    }
+}
@@ -1,17 +1,30 @@
-use collections::{BTreeMap, HashMap};
+use anyhow::anyhow;
+use collections::{BTreeMap, HashMap, HashSet};
 use feature_flags::{FeatureFlag, FeatureFlagAppExt as _};
+use futures::channel::oneshot;
 use git::{
     BuildCommitPermalinkParams, GitHostingProviderRegistry, GitRemote, Oid, ParsedGitRemote,
+    SHORT_SHA_LENGTH,
+    bisect::{BisectMark, BisectState},
     parse_git_remote_url,
-    repository::{CommitDiff, InitialGraphCommitData, LogOrder, LogSource},
+    repository::{
+        CommitDiff, CommitSignature, InitialGraphCommitData, LogOrder, LogSource, ResetMode,
+    },
+};
+use git_ui::{
+    bisect,
+    commit_tooltip::{CommitAvatar, SignatureBadge},
+    commit_view::CommitView,
+    create_ref_modal::{CreateRefModal, RefKind},
+    project_diff::ProjectDiff,
+    rebase_view::RebaseView,
 };
-use git_ui::{commit_tooltip::CommitAvatar, commit_view::CommitView};
 use gpui::{
-    AnyElement, App, Bounds, ClickEvent, ClipboardItem, Context, Corner, DefiniteLength,
-    DragMoveEvent, ElementId, Entity, EventEmitter, FocusHandle, Focusable, FontWeight, Hsla,
-    InteractiveElement, ParentElement, PathBuilder, Pixels, Point, Render, ScrollStrategy,
-    ScrollWheelEvent, SharedString, Styled, Subscription, Task, WeakEntity, Window, actions,
-    anchored, deferred, point, px,
+    Action, AnyElement, App, Bounds, ClickEvent, ClipboardItem, Context, Corner, DefiniteLength,
+    DismissEvent, DragMoveEvent, ElementId, Entity, EventEmitter, FocusHandle, Focusable,
+    FontWeight, Hsla, InteractiveElement, MouseButton, ParentElement, PathBuilder, Pixels, Point,
+    PromptLevel, Render, ScrollStrategy, ScrollWheelEvent, SharedString, Styled, Subscription,
+    Task, WeakEntity, Window, actions, anchored, deferred, point, px,
 };
 use menu::{SelectNext, SelectPrevious};
 use project::{
@@ -30,6 +43,7 @@ use ui::{
 use workspace::{
     Workspace,
     item::{Item, ItemEvent, SerializableItem},
+    notifications::DetachAndPromptErr,
 };
 
 const COMMIT_CIRCLE_RADIUS: Pixels = px(4.5);
@@ -90,6 +104,30 @@ actions!(
     [
         /// Opens the commit view for the selected commit.
         OpenCommitView,
+        /// Opens an interactive rebase of the selected commit and the commits after it.
+        RebaseFromSelectedCommit,
+        /// Applies the changes of the selected commit on top of HEAD.
+        CherryPickCommit,
+        /// Creates a commit that undoes the changes of the selected commit.
+        RevertCommit,
+        /// Creates a branch at the selected commit and switches to it.
+        CreateBranchAtCommit,
+        /// Creates a tag at the selected commit.
+        CreateTagAtCommit,
+        /// Moves the current branch to the selected commit, keeping its changes staged.
+        ResetSoftToCommit,
+        /// Moves the current branch to the selected commit, keeping its changes unstaged.
+        ResetMixedToCommit,
+        /// Moves the current branch to the selected commit, discarding all uncommitted changes.
+        ResetHardToCommit,
+        /// Opens a diff of the working tree against the selected commit.
+        CompareWithWorkingTree,
+        /// Marks the selected commit as good, starting a bisect if none is in progress.
+        BisectMarkGood,
+        /// Marks the selected commit as bad, starting a bisect if none is in progress.
+        BisectMarkBad,
+        /// Skips the selected commit in the bisect in progress.
+        BisectMarkSkip,
     ]
 );
 
@@ -585,6 +623,44 @@ pub fn init(cx: &mut App) {
     .detach();
 }
 
+/// Returns the rows of the commits a bisect still has to search: the loaded ancestors of the bad
+/// commit that are not ancestors of a good one.
+fn bisect_range_rows(commits: &[Rc<CommitEntry>], state: &BisectState) -> HashSet<usize> {
+    let row_by_sha: HashMap<Oid, usize> = commits
+        .iter()
+        .enumerate()
+        .map(|(row, commit)| (commit.data.sha, row))
+        .collect();
+    let row_for_sha = |sha: &SharedString| {
+        sha.parse::<Oid>()
+            .ok()
+            .and_then(|oid| row_by_sha.get(&oid).copied())
+    };
+    let ancestors = |mut stack: Vec<usize>| {
+        let mut visited = HashSet::default();
+        while let Some(row) = stack.pop() {
+            if visited.insert(row) {
+                stack.extend(
+                    commits[row]
+                        .data
+                        .parents
+                        .iter()
+                        .filter_map(|parent| row_by_sha.get(parent).copied()),
+                );
+            }
+        }
+        visited
+    };
+
+    let Some(bad_row) = state.bad.as_ref().and_then(row_for_sha) else {
+        return HashSet::default();
+    };
+    let good = ancestors(state.good.iter().filter_map(row_for_sha).collect());
+    let mut rows = ancestors(vec![bad_row]);
+    rows.retain(|row| !good.contains(row));
+    rows
+}
+
 fn lane_center_x(bounds: Bounds<Pixels>, lane: f32, horizontal_scroll_offset: Pixels) -> Pixels {
     bounds.origin.x + LEFT_PADDING + lane * LANE_WIDTH + LANE_WIDTH / 2.0 - horizontal_scroll_offset
 }
@@ -645,8 +721,21 @@ pub struct GitGraph {
     log_order: LogOrder,
     selected_commit_diff: Option<CommitDiff>,
     _commit_diff_task: Option<Task<()>>,
+    /// Verification results for signed commits whose rows have been shown, keyed by sha.
+    commit_signatures: HashMap<Oid, Option<CommitSignature>>,
+    /// Signed commits whose verification is in flight.
+    pending_signatures: HashSet<Oid>,
     _load_task: Option<Task<()>>,
     commit_details_split_state: Entity<SplitState>,
+    bisect_range: Option<BisectRange>,
+}
+
+/// The rows of the loaded commits a bisect still has to search, cached until the bisect state
+/// or the number of loaded commits changes.
+struct BisectRange {
+    state: BisectState,
+    commit_count: usize,
+    rows: Rc<HashSet<usize>>,
 }
 
 impl GitGraph {
@@ -686,6 +775,8 @@ impl GitGraph {
             }
             GitStoreEvent::ActiveRepositoryChanged(_) => {
                 this.graph_data.clear();
+                this.commit_signatures.clear();
+                this.pending_signatures.clear();
                 cx.notify();
             }
             _ => {}
@@ -735,9 +826,12 @@ impl GitGraph {
             graph_viewport_width: px(88.),
             selected_entry_idx: None,
             selected_commit_diff: None,
+            commit_signatures: HashMap::default(),
+            pending_signatures: HashSet::default(),
             log_source,
             log_order,
             commit_details_split_state: cx.new(|_cx| SplitState::new()),
+            bisect_range: None,
         }
     }
 
@@ -773,26 +867,84 @@ impl GitGraph {
         cx.notify();
     }
 
+    /// Renders a ref name as printed by `git log --decorate`, where tags are prefixed with
+    /// `tag: `.
     fn render_badge(&self, name: &SharedString, accent_color: gpui::Hsla) -> impl IntoElement {
+        let tag = name.strip_prefix("tag: ");
         div()
             .px_1p5()
             .py_0p5()
             .h(self.row_height - px(4.0))
             .flex()
+            .gap_0p5()
             .items_center()
             .justify_center()
             .rounded_md()
             .bg(accent_color.opacity(0.18))
             .border_1()
             .border_color(accent_color.opacity(0.55))
+            .when(tag.is_some(), |this| {
+                this.child(
+                    Icon::new(IconName::Hash)
+                        .size(IconSize::XSmall)
+                        .color(Color::Muted),
+                )
+            })
             .child(
-                Label::new(name.clone())
+                Label::new(tag.map_or_else(|| name.clone(), |tag| tag.to_string().into()))
                     .size(LabelSize::Small)
                     .color(Color::Default)
                     .single_line(),
             )
     }
 
+    /// Verifies the signatures of the signed commits in `rows` that haven't been verified yet,
+    /// in one batch.
+    fn load_signatures(
+        &mut self,
+        rows: Range<usize>,
+        repository: &Entity<Repository>,
+        cx: &mut Context<Self>,
+    ) {
+        let commit_count = self.graph_data.commits.len();
+        let commits = repository.update(cx, |repository, cx| {
+            self.graph_data.commits[rows.start.min(commit_count)..rows.end.min(commit_count)]
+                .iter()
+                .map(|commit| commit.data.sha)
+                .filter(|sha| {
+                    !self.commit_signatures.contains_key(sha)
+                        && !self.pending_signatures.contains(sha)
+                        && matches!(
+                            repository.fetch_commit_data(*sha, cx),
+                            CommitDataState::Loaded(data) if data.is_signed
+                        )
+                })
+                .collect::<Vec<_>>()
+        });
+        if commits.is_empty() {
+            return;
+        }
+
+        self.pending_signatures.extend(commits.iter().copied());
+        let signatures = repository.update(cx, |repository, _| {
+            repository.commit_signatures(commits.clone())
+        });
+        cx.spawn(async move |this, cx| {
+            let signatures = signatures.await;
+            this.update(cx, |this, cx| {
+                for commit in &commits {
+                    this.pending_signatures.remove(commit);
+                }
+                if let Ok(Ok(signatures)) = signatures {
+                    this.commit_signatures.extend(signatures);
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
     fn render_table_rows(
         &mut self,
         range: Range<usize>,
@@ -818,6 +970,7 @@ impl GitGraph {
                         repository.fetch_commit_data(commit.data.sha, cx);
                     });
             });
+            self.load_signatures(range.clone(), repository, cx);
         }
 
         range
@@ -841,11 +994,13 @@ impl GitGraph {
                 let mut formatted_time = String::new();
                 let subject;
                 let author_name;
+                let mut is_signed = false;
 
                 if let CommitDataState::Loaded(data) = data {
                     subject = data.subject.clone();
                     author_name = data.author_name.clone();
                     formatted_time = format_timestamp(data.commit_timestamp);
+                    is_signed = data.is_signed;
                 } else {
                     subject = "Loading...".into();
                     author_name = "".into();
@@ -858,8 +1013,32 @@ impl GitGraph {
                     .copied()
                     .unwrap_or_else(|| accent_colors.0.first().copied().unwrap_or_default());
                 let is_selected = self.selected_entry_idx == Some(idx);
+                let bisect_badge = self.bisect_range.as_ref().and_then(|bisect_range| {
+                    let sha = commit.data.sha.to_string();
+                    let state = &bisect_range.state;
+                    if state
+                        .first_bad
+                        .as_ref()
+                        .is_some_and(|first_bad| *first_bad == sha)
+                    {
+                        Some(("first bad", Color::Error))
+                    } else if state.is_bad(&sha) {
+                        Some(("bad", Color::Error))
+                    } else if state.is_good(&sha) {
+                        Some(("good", Color::Success))
+                    } else if state.is_skipped(&sha) {
+                        Some(("skipped", Color::Muted))
+                    } else {
+                        None
+                    }
+                });
+                let outside_bisect_range = self.bisect_range.as_ref().is_some_and(|bisect_range| {
+                    !bisect_range.rows.is_empty() && !bisect_range.rows.contains(&idx)
+                });
                 let text_color = if is_selected {
                     Color::Default
+                } else if outside_bisect_range {
+                    Color::Disabled
                 } else {
                     Color::Muted
                 };
@@ -883,6 +1062,24 @@ impl GitGraph {
                                             .map(|name| self.render_badge(name, accent_color)),
                                     )
                                 }))
+                                .children(bisect_badge.map(|(label, color)| {
+                                    Label::new(label)
+                                        .size(LabelSize::Small)
+                                        .color(color)
+                                        .single_line()
+                                }))
+                                .when(is_signed, |this| {
+                                    this.child(SignatureBadge::new(
+                                        ElementId::NamedInteger(
+                                            "commit-signature".into(),
+                                            idx as u64,
+                                        ),
+                                        self.commit_signatures
+                                            .get(&commit.data.sha)
+                                            .cloned()
+                                            .flatten(),
+                                    ))
+                                })
                                 .child(
                                     Label::new(subject)
                                         .color(text_color)
@@ -974,6 +1171,290 @@ impl GitGraph {
         self.open_commit_view(selected_entry_index, window, cx);
     }
 
+    fn selected_commit(&self) -> Option<Arc<InitialGraphCommitData>> {
+        let idx = self.selected_entry_idx?;
+        Some(self.graph_data.commits.get(idx)?.data.clone())
+    }
+
+    fn deploy_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        idx: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_entry(idx, cx);
+        let focus_handle = self.focus_handle.clone();
+        let bisecting = self
+            .project
+            .read(cx)
+            .active_repository(cx)
+            .is_some_and(|repository| repository.read(cx).bisect.is_some());
+        let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
+            context_menu
+                .context(focus_handle)
+                .action("View Commit", OpenCommitView.boxed_clone())
+                .action(
+                    "Compare with Working Tree",
+                    CompareWithWorkingTree.boxed_clone(),
+                )
+                .separator()
+                .action("Cherry-Pick", CherryPickCommit.boxed_clone())
+                .action("Revert", RevertCommit.boxed_clone())
+                .action(
+                    "Interactive Rebase from Here",
+                    RebaseFromSelectedCommit.boxed_clone(),
+                )
+                .separator()
+                .action("Create Branch Here…", CreateBranchAtCommit.boxed_clone())
+                .action("Create Tag Here…", CreateTagAtCommit.boxed_clone())
+                .separator()
+                .action("Reset Soft to Here", ResetSoftToCommit.boxed_clone())
+                .action("Reset Mixed to Here", ResetMixedToCommit.boxed_clone())
+                .action("Reset Hard to Here", ResetHardToCommit.boxed_clone())
+                .separator()
+                .map(|menu| {
+                    if bisecting {
+                        menu.action("Mark as Good", BisectMarkGood.boxed_clone())
+                            .action("Mark as Bad", BisectMarkBad.boxed_clone())
+                            .action("Skip in Bisect", BisectMarkSkip.boxed_clone())
+                    } else {
+                        menu.action("Start Bisect: Mark as Good", BisectMarkGood.boxed_clone())
+                            .action("Start Bisect: Mark as Bad", BisectMarkBad.boxed_clone())
+                    }
+                })
+        });
+        let subscription = cx.subscribe_in(
+            &context_menu,
+            window,
+            |this, _, _: &DismissEvent, window, cx| {
+                if this.context_menu.as_ref().is_some_and(|context_menu| {
+                    context_menu.0.focus_handle(cx).contains_focused(window, cx)
+                }) {
+                    cx.focus_self(window);
+                }
+                this.context_menu.take();
+                cx.notify();
+            },
+        );
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
+    /// Runs `operation` on the active repository once the user accepts `confirmation`, if any,
+    /// showing `error_message` when it fails.
+    fn run_git_operation(
+        &mut self,
+        error_message: &'static str,
+        confirmation: Option<oneshot::Receiver<usize>>,
+        operation: impl FnOnce(&mut Repository, &mut App) -> oneshot::Receiver<anyhow::Result<()>>
+        + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repository) = self.project.read(cx).active_repository(cx) else {
+            return;
+        };
+        cx.spawn(async move |_, cx| {
+            if let Some(confirmation) = confirmation
+                && confirmation.await != Ok(0)
+            {
+                return Ok(());
+            }
+            match repository
+                .update(cx, |repository, cx| operation(repository, cx))
+                .await
+            {
+                Ok(result) => result,
+                Err(_) => Err(anyhow!("Operation was canceled")),
+            }
+        })
+        .detach_and_prompt_err(error_message, window, cx, |_, _, _| None);
+    }
+
+    fn cherry_pick(&mut self, _: &CherryPickCommit, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(commit) = self.selected_commit() else {
+            return;
+        };
+        let mainline = (commit.parents.len() > 1).then_some(1);
+        self.run_git_operation(
+            "Failed to cherry-pick commit",
+            None,
+            move |repository, _| repository.cherry_pick(vec![commit.sha.to_string()], mainline),
+            window,
+            cx,
+        );
+    }
+
+    fn revert(&mut self, _: &RevertCommit, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(commit) = self.selected_commit() else {
+            return;
+        };
+        let mainline = (commit.parents.len() > 1).then_some(1);
+        self.run_git_operation(
+            "Failed to revert commit",
+            None,
+            move |repository, _| repository.revert(vec![commit.sha.to_string()], mainline),
+            window,
+            cx,
+        );
+    }
+
+    fn reset_to_selected_commit(
+        &mut self,
+        mode: ResetMode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(commit) = self.selected_commit() else {
+            return;
+        };
+        let sha = commit.sha.to_string();
+        let (level, detail) = match mode {
+            ResetMode::Soft => (
+                PromptLevel::Warning,
+                "The changes from later commits will be kept as staged changes.",
+            ),
+            ResetMode::Mixed => (
+                PromptLevel::Warning,
+                "The changes from later commits will be kept as unstaged changes.",
+            ),
+            ResetMode::Hard => (
+                PromptLevel::Critical,
+                "The changes from later commits and all uncommitted changes will be discarded.",
+            ),
+        };
+        let confirmation = window.prompt(
+            level,
+            &format!(
+                "Reset the current branch to {}?",
+                &sha[..SHORT_SHA_LENGTH.min(sha.len())]
+            ),
+            Some(detail),
+            &["Reset", "Cancel"],
+            cx,
+        );
+        self.run_git_operation(
+            "Failed to reset",
+            Some(confirmation),
+            move |repository, cx| repository.reset(sha, mode, cx),
+            window,
+            cx,
+        );
+    }
+
+    fn create_ref_at_selected_commit(
+        &mut self,
+        kind: RefKind,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(commit) = self.selected_commit() else {
+            return;
+        };
+        let Some(repository) = self.project.read(cx).active_repository(cx) else {
+            return;
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    CreateRefModal::new(kind, commit.sha.to_string().into(), repository, window, cx)
+                });
+            })
+            .ok();
+    }
+
+    fn compare_with_working_tree(
+        &mut self,
+        _: &CompareWithWorkingTree,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(commit) = self.selected_commit() else {
+            return;
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                ProjectDiff::deploy_against_commit(
+                    workspace,
+                    commit.sha.to_string().into(),
+                    window,
+                    cx,
+                );
+            })
+            .ok();
+    }
+
+    fn rebase_from_selected_commit(
+        &mut self,
+        _: &RebaseFromSelectedCommit,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(commit) = self.selected_commit() else {
+            return;
+        };
+        let base = format!("{}^", commit.sha);
+        self.workspace
+            .update(cx, |workspace, cx| {
+                RebaseView::open(Some(base), workspace, window, cx);
+            })
+            .ok();
+    }
+
+    fn bisect_mark_selected_commit(
+        &mut self,
+        mark: BisectMark,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(commit) = self.selected_commit() else {
+            return;
+        };
+        let Some(repository) = self.project.read(cx).active_repository(cx) else {
+            return;
+        };
+        let sha = commit.sha.to_string();
+        let workspace = self.workspace.clone();
+        if repository.read(cx).bisect.is_some() {
+            bisect::mark(repository, mark, Some(sha), workspace, window, cx);
+        } else {
+            match mark {
+                BisectMark::Good => {
+                    bisect::start(repository, None, vec![sha], workspace, window, cx)
+                }
+                BisectMark::Bad => {
+                    bisect::start(repository, Some(sha), Vec::new(), workspace, window, cx)
+                }
+                BisectMark::Skip => {}
+            }
+        }
+    }
+
+    fn update_bisect_range(&mut self, cx: &App) {
+        let state = self
+            .project
+            .read(cx)
+            .active_repository(cx)
+            .and_then(|repository| repository.read(cx).bisect.clone());
+        let Some(state) = state else {
+            self.bisect_range = None;
+            return;
+        };
+        let commit_count = self.graph_data.commits.len();
+        if self.bisect_range.as_ref().is_some_and(|bisect_range| {
+            bisect_range.state == state && bisect_range.commit_count == commit_count
+        }) {
+            return;
+        }
+        let rows = Rc::new(bisect_range_rows(&self.graph_data.commits, &state));
+        self.bisect_range = Some(BisectRange {
+            state,
+            commit_count,
+            rows,
+        });
+    }
+
     fn open_commit_view(
         &mut self,
         entry_index: usize,
@@ -1065,6 +1546,11 @@ impl GitGraph {
             }
         };
         let ref_names = commit_entry.data.ref_names.clone();
+        let signature = self
+            .commit_signatures
+            .get(&commit_entry.data.sha)
+            .cloned()
+            .flatten();
         let accent_colors = cx.theme().accents();
         let accent_color = accent_colors
             .0
@@ -1159,7 +1645,20 @@ impl GitGraph {
                     .child(
                         v_flex()
                             .gap_0p5()
-                            .child(Label::new(author_name.clone()).weight(FontWeight::SEMIBOLD))
+                            .child(
+                                h_flex()
+                                    .gap_1()
+                                    .child(
+                                        Label::new(author_name.clone())
+                                            .weight(FontWeight::SEMIBOLD),
+                                    )
+                                    .when_some(signature, |this, signature| {
+                                        this.child(SignatureBadge::new(
+                                            "detail-signature",
+                                            Some(signature),
+                                        ))
+                                    }),
+                            )
                             .child(
                                 Label::new(date_string)
                                     .color(Color::Muted)
@@ -1626,6 +2125,8 @@ impl GitGraph {
 
 impl Render for GitGraph {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.update_bisect_range(cx);
+
         let description_width_fraction = 0.72;
         let date_width_fraction = 0.12;
         let author_width_fraction = 0.10;
@@ -1700,6 +2201,10 @@ impl Render for GitGraph {
                 .child({
                     let row_height = self.row_height;
                     let selected_entry_idx = self.selected_entry_idx;
+                    let bisect_rows = self
+                        .bisect_range
+                        .as_ref()
+                        .map(|bisect_range| bisect_range.rows.clone());
                     let weak_self = cx.weak_entity();
                     div().flex_1().size_full().child(
                         Table::new(4)
@@ -1734,8 +2239,15 @@ impl Render for GitGraph {
                             )
                             .map_row(move |(index, row), _window, cx| {
                                 let is_selected = selected_entry_idx == Some(index);
+                                let in_bisect_range = bisect_rows
+                                    .as_ref()
+                                    .is_some_and(|rows| rows.contains(&index));
                                 let weak = weak_self.clone();
+                                let weak_for_menu = weak_self.clone();
                                 row.h(row_height)
+                                    .when(in_bisect_range && !is_selected, |row| {
+                                        row.bg(cx.theme().status().warning_background.opacity(0.3))
+                                    })
                                     .when(is_selected, |row| {
                                         row.bg(cx.theme().colors().element_selected)
                                     })
@@ -1749,6 +2261,18 @@ impl Render for GitGraph {
                                         })
                                         .ok();
                                     })
+                                    .on_mouse_down(MouseButton::Right, move |event, window, cx| {
+                                        weak_for_menu
+                                            .update(cx, |this, cx| {
+                                                this.deploy_context_menu(
+                                                    event.position,
+                                                    index,
+                                                    window,
+                                                    cx,
+                                                );
+                                            })
+                                            .ok();
+                                    })
                                     .into_any_element()
                             })
                             .uniform_list(
@@ -1782,6 +2306,34 @@ impl Render for GitGraph {
             .on_action(cx.listener(|this, _: &OpenCommitView, window, cx| {
                 this.open_selected_commit_view(window, cx);
             }))
+            .on_action(cx.listener(Self::rebase_from_selected_commit))
+            .on_action(cx.listener(Self::cherry_pick))
+            .on_action(cx.listener(Self::revert))
+            .on_action(cx.listener(Self::compare_with_working_tree))
+            .on_action(cx.listener(|this, _: &CreateBranchAtCommit, window, cx| {
+                this.create_ref_at_selected_commit(RefKind::Branch, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &CreateTagAtCommit, window, cx| {
+                this.create_ref_at_selected_commit(RefKind::Tag, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &ResetSoftToCommit, window, cx| {
+                this.reset_to_selected_commit(ResetMode::Soft, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &ResetMixedToCommit, window, cx| {
+                this.reset_to_selected_commit(ResetMode::Mixed, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &ResetHardToCommit, window, cx| {
+                this.reset_to_selected_commit(ResetMode::Hard, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &BisectMarkGood, window, cx| {
+                this.bisect_mark_selected_commit(BisectMark::Good, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &BisectMarkBad, window, cx| {
+                this.bisect_mark_selected_commit(BisectMark::Bad, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &BisectMarkSkip, window, cx| {
+                this.bisect_mark_selected_commit(BisectMark::Skip, window, cx);
+            }))
             .on_action(cx.listener(Self::select_prev))
             .on_action(cx.listener(Self::select_next))
             .child(content)
@@ -2457,6 +3009,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bisect_range_rows() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let oids: Vec<Oid> = (0..5).map(|_| Oid::random(&mut rng)).collect();
+
+        // 0 -> 1 -> 2 -> 4 and 0 -> 3 -> 4, with 0 a merge commit.
+        let parents: [&[usize]; 5] = [&[1, 3], &[2], &[4], &[4], &[]];
+        let commits: Vec<Arc<InitialGraphCommitData>> = parents
+            .iter()
+            .enumerate()
+            .map(|(row, parents)| {
+                Arc::new(InitialGraphCommitData {
+                    sha: oids[row],
+                    parents: parents.iter().map(|parent| oids[*parent]).collect(),
+                    ref_names: vec![],
+                })
+            })
+            .collect();
+        let mut graph_data = GraphData::new(8);
+        graph_data.add_commits(&commits);
+
+        let sha = |row: usize| SharedString::from(oids[row].to_string());
+        let mut state = BisectState {
+            bad: Some(sha(0)),
+            good: vec![sha(2)],
+            ..BisectState::default()
+        };
+        assert_eq!(
+            bisect_range_rows(&graph_data.commits, &state),
+            HashSet::from_iter([0, 1, 3])
+        );
+
+        state.good.push(sha(3));
+        assert_eq!(
+            bisect_range_rows(&graph_data.commits, &state),
+            HashSet::from_iter([0, 1])
+        );
+
+        state.bad = None;
+        assert!(bisect_range_rows(&graph_data.commits, &state).is_empty());
+    }
+
     #[test]
     fn test_git_graph_linear_commits() {
         let mut rng = StdRng::seed_from_u64(42);
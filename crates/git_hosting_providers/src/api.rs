@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result, bail};
+use futures::AsyncReadExt;
+use http_client::{
+    AsyncBody, HttpClient, HttpRequestExt, Request,
+    http::{HeaderMap, header::LINK, request::Builder},
+};
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Starts a request to a hosting provider's REST API.
+pub(crate) fn request(method: http_client::Method, url: &str) -> Builder {
+    Request::builder()
+        .method(method)
+        .uri(url)
+        .follow_redirects(http_client::RedirectPolicy::FollowAll)
+}
+
+/// Sends a request and returns the body of the response, failing on any unsuccessful status.
+pub(crate) async fn send(
+    client: &Arc<dyn HttpClient>,
+    request: Builder,
+    body: AsyncBody,
+) -> Result<Vec<u8>> {
+    let (_, body) = send_with_headers(client, request, body).await?;
+    Ok(body)
+}
+
+async fn send_with_headers(
+    client: &Arc<dyn HttpClient>,
+    request: Builder,
+    body: AsyncBody,
+) -> Result<(HeaderMap, Vec<u8>)> {
+    let request = request.body(body)?;
+    let url = request.uri().to_string();
+    let mut response = client
+        .send(request)
+        .await
+        .with_context(|| format!("error sending request to {url:?}"))?;
+
+    let mut body = Vec::new();
+    response.body_mut().read_to_end(&mut body).await?;
+
+    if !response.status().is_success() {
+        let text = String::from_utf8_lossy(body.as_slice());
+        bail!(
+            "status error {}, response: {text:?}",
+            response.status().as_u16()
+        );
+    }
+    Ok((response.headers().clone(), body))
+}
+
+pub(crate) async fn get_text(client: &Arc<dyn HttpClient>, request: Builder) -> Result<String> {
+    let body = send(client, request, AsyncBody::default()).await?;
+    String::from_utf8(body).context("response is not valid UTF-8")
+}
+
+pub(crate) async fn get_json<T: DeserializeOwned>(
+    client: &Arc<dyn HttpClient>,
+    request: Builder,
+) -> Result<T> {
+    let body = send(
+        client,
+        request.header("Accept", "application/json"),
+        AsyncBody::default(),
+    )
+    .await?;
+    serde_json::from_slice(&body).context("failed to deserialize response")
+}
+
+/// Fetches every page of a paginated list, requesting pages by number until a response's
+/// `Link` header no longer points to a next page.
+pub(crate) async fn get_json_pages<T: DeserializeOwned>(
+    client: &Arc<dyn HttpClient>,
+    request_page: impl Fn(u32) -> Result<Builder>,
+) -> Result<Vec<T>> {
+    let mut items = Vec::new();
+    let mut page = 1;
+    loop {
+        let request = request_page(page)?.header("Accept", "application/json");
+        let (headers, body) = send_with_headers(client, request, AsyncBody::default()).await?;
+        let page_items: Vec<T> =
+            serde_json::from_slice(&body).context("failed to deserialize response")?;
+        let is_last_page = page_items.is_empty() || !has_next_page(&headers);
+        items.extend(page_items);
+        if is_last_page {
+            return Ok(items);
+        }
+        page += 1;
+    }
+}
+
+/// Whether a `Link` header, as sent by GitHub, GitLab and Gitea, has a `rel="next"` link.
+fn has_next_page(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|link| {
+            link.split(';').skip(1).any(|param| {
+                param.trim().strip_prefix("rel=").is_some_and(|rel| {
+                    rel.trim_matches('"')
+                        .split_whitespace()
+                        .any(|rel| rel == "next")
+                })
+            })
+        })
+}
+
+pub(crate) async fn post_json<T: DeserializeOwned>(
+    client: &Arc<dyn HttpClient>,
+    request: Builder,
+    body: &impl Serialize,
+) -> Result<T> {
+    let body = serde_json::to_string(body)?;
+    let body = send(
+        client,
+        request
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json"),
+        body.into(),
+    )
+    .await?;
+    serde_json::from_slice(&body).context("failed to deserialize response")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_client::http::HeaderValue;
+
+    #[test]
+    fn test_has_next_page() {
+        let headers_with_link = |link: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(LINK, HeaderValue::from_static(link));
+            headers
+        };
+
+        assert!(!has_next_page(&HeaderMap::new()));
+        assert!(has_next_page(&headers_with_link(
+            r#"<https://api.github.com/repositories/1/pulls?page=2>; rel="next", <https://api.github.com/repositories/1/pulls?page=5>; rel="last""#
+        )));
+        assert!(!has_next_page(&headers_with_link(
+            r#"<https://gitlab.com/api/v4/projects/1/merge_requests?page=1>; rel="first", <https://gitlab.com/api/v4/projects/1/merge_requests?page=4>; rel="prev""#
+        )));
+        assert!(has_next_page(&headers_with_link(
+            "<https://gitea.com/api/v1/repos/o/r/pulls?page=2>; rel=next"
+        )));
+    }
+}
@@ -10,10 +10,11 @@ use serde::Deserialize;
 use url::Url;
 
 use git::{
-    BuildCommitPermalinkParams, BuildPermalinkParams, GitHostingProvider, ParsedGitRemote,
-    RemoteUrl,
+    BuildCommitPermalinkParams, BuildPermalinkParams, GitHostingProvider, NewReviewComment,
+    ParsedGitRemote, PullRequestSummary, RemoteUrl, ReviewComment,
 };
 
+use super::gitea::GiteaApi;
 use crate::get_host_from_git_remote_url;
 
 #[derive(Debug, Deserialize)]
@@ -105,6 +106,18 @@ impl Forgejo {
         ))
     }
 
+    fn api(&self) -> GiteaApi<'_> {
+        let token = if self.base_url.host_str() == Some("codeberg.org") {
+            std::env::var("CODEBERG_TOKEN")
+        } else {
+            std::env::var("FORGEJO_TOKEN")
+        };
+        GiteaApi {
+            base_url: &self.base_url,
+            token: token.ok(),
+        }
+    }
+
     async fn fetch_forgejo_commit_author(
         &self,
         repo_owner: &str,
@@ -262,6 +275,64 @@ impl GitHostingProvider for Forgejo {
             .transpose()?;
         Ok(avatar_url)
     }
+
+    fn supports_pull_request_reviews(&self) -> bool {
+        true
+    }
+
+    async fn list_pull_requests(
+        &self,
+        remote: &ParsedGitRemote,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<PullRequestSummary>> {
+        self.api().list_pull_requests(remote, &http_client).await
+    }
+
+    async fn pull_request_diff(
+        &self,
+        remote: &ParsedGitRemote,
+        number: u32,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<String> {
+        self.api()
+            .pull_request_diff(remote, number, &http_client)
+            .await
+    }
+
+    async fn file_at_commit(
+        &self,
+        remote: &ParsedGitRemote,
+        path: &str,
+        sha: &str,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<String> {
+        self.api()
+            .file_at_commit(remote, path, sha, &http_client)
+            .await
+    }
+
+    async fn pull_request_review_comments(
+        &self,
+        remote: &ParsedGitRemote,
+        number: u32,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<ReviewComment>> {
+        self.api()
+            .pull_request_review_comments(remote, number, &http_client)
+            .await
+    }
+
+    async fn post_pull_request_review_comment(
+        &self,
+        remote: &ParsedGitRemote,
+        pull_request: &PullRequestSummary,
+        comment: NewReviewComment,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<ReviewComment> {
+        self.api()
+            .post_pull_request_review_comment(remote, pull_request, comment, &http_client)
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -5,16 +5,19 @@ use anyhow::{Context as _, Result, bail};
 use async_trait::async_trait;
 use futures::AsyncReadExt;
 use gpui::SharedString;
-use http_client::{AsyncBody, HttpClient, HttpRequestExt, Request};
+use http_client::{AsyncBody, HttpClient, HttpRequestExt, Method, Request, http::request::Builder};
+use itertools::Itertools as _;
 use serde::Deserialize;
+use serde_json::json;
 use url::Url;
+use urlencoding::encode;
 
 use git::{
-    BuildCommitPermalinkParams, BuildPermalinkParams, GitHostingProvider, ParsedGitRemote,
-    RemoteUrl,
+    BuildCommitPermalinkParams, BuildPermalinkParams, GitHostingProvider, NewReviewComment,
+    ParsedGitRemote, PullRequestSummary, RemoteUrl, ReviewComment, ReviewCommentSide,
 };
 
-use crate::get_host_from_git_remote_url;
+use crate::{api, get_host_from_git_remote_url};
 
 #[derive(Debug, Deserialize)]
 struct CommitDetails {
@@ -26,6 +29,243 @@ struct User {
     pub avatar_url: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct Account {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestRef {
+    #[serde(rename = "ref")]
+    name: String,
+    sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestDetails {
+    number: u32,
+    title: String,
+    html_url: Url,
+    user: Option<Account>,
+    head: PullRequestRef,
+    base: PullRequestRef,
+}
+
+impl From<PullRequestDetails> for PullRequestSummary {
+    fn from(pull_request: PullRequestDetails) -> Self {
+        Self {
+            number: pull_request.number,
+            title: pull_request.title,
+            author: pull_request.user.map(|user| user.login).unwrap_or_default(),
+            url: pull_request.html_url,
+            source_branch: pull_request.head.name,
+            target_branch: pull_request.base.name,
+            head_sha: pull_request.head.sha,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PullReview {
+    id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullReviewComment {
+    id: u64,
+    user: Option<Account>,
+    body: String,
+    path: String,
+    /// The line in the head of the diff, or zero for comments on the base.
+    #[serde(default)]
+    position: u32,
+    /// The line in the base of the diff, or zero for comments on the head.
+    #[serde(default)]
+    original_position: u32,
+}
+
+impl From<PullReviewComment> for ReviewComment {
+    fn from(comment: PullReviewComment) -> Self {
+        let (line, side) = if comment.position > 0 {
+            (comment.position, ReviewCommentSide::New)
+        } else {
+            (comment.original_position, ReviewCommentSide::Old)
+        };
+        // Review comments aren't threaded, so comments on the same line make up a thread.
+        let thread_id = format!(
+            "{}:{}:{line}",
+            comment.path,
+            match side {
+                ReviewCommentSide::Old => "old",
+                ReviewCommentSide::New => "new",
+            }
+        );
+        Self {
+            id: comment.id.to_string(),
+            thread_id,
+            author: comment.user.map(|user| user.login).unwrap_or_default(),
+            body: comment.body,
+            path: comment.path,
+            line: (line > 0).then_some(line),
+            side,
+        }
+    }
+}
+
+/// The pull request API that Gitea shares with Forgejo.
+pub(crate) struct GiteaApi<'a> {
+    pub base_url: &'a Url,
+    pub token: Option<String>,
+}
+
+impl GiteaApi<'_> {
+    fn request(&self, method: Method, path: &str) -> Result<Builder> {
+        let Some(host) = self.base_url.host_str() else {
+            bail!("failed to get host from base url");
+        };
+        let mut request = api::request(method, &format!("https://{host}/api/v1/{path}"));
+        if let Some(token) = &self.token {
+            request = request.header("Authorization", format!("token {token}"));
+        }
+        Ok(request)
+    }
+
+    pub async fn list_pull_requests(
+        &self,
+        remote: &ParsedGitRemote,
+        http_client: &Arc<dyn HttpClient>,
+    ) -> Result<Vec<PullRequestSummary>> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let pull_requests: Vec<PullRequestDetails> = api::get_json_pages(http_client, |page| {
+            self.request(
+                Method::GET,
+                &format!("repos/{owner}/{repo}/pulls?state=open&limit=50&page={page}"),
+            )
+        })
+        .await
+        .context("error fetching pull requests")?;
+        Ok(pull_requests.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn pull_request_diff(
+        &self,
+        remote: &ParsedGitRemote,
+        number: u32,
+        http_client: &Arc<dyn HttpClient>,
+    ) -> Result<String> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let request = self.request(
+            Method::GET,
+            &format!("repos/{owner}/{repo}/pulls/{number}.diff"),
+        )?;
+        api::get_text(http_client, request)
+            .await
+            .with_context(|| format!("error fetching the diff of pull request #{number}"))
+    }
+
+    pub async fn file_at_commit(
+        &self,
+        remote: &ParsedGitRemote,
+        path: &str,
+        sha: &str,
+        http_client: &Arc<dyn HttpClient>,
+    ) -> Result<String> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let encoded_path = path.split('/').map(encode).join("/");
+        let request = self.request(
+            Method::GET,
+            &format!("repos/{owner}/{repo}/raw/{encoded_path}?ref={sha}"),
+        )?;
+        api::get_text(http_client, request)
+            .await
+            .with_context(|| format!("error fetching {path} at {sha}"))
+    }
+
+    pub async fn pull_request_review_comments(
+        &self,
+        remote: &ParsedGitRemote,
+        number: u32,
+        http_client: &Arc<dyn HttpClient>,
+    ) -> Result<Vec<ReviewComment>> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let reviews: Vec<PullReview> = api::get_json_pages(http_client, |page| {
+            self.request(
+                Method::GET,
+                &format!("repos/{owner}/{repo}/pulls/{number}/reviews?limit=50&page={page}"),
+            )
+        })
+        .await
+        .with_context(|| format!("error fetching reviews of pull request #{number}"))?;
+
+        let mut comments = Vec::new();
+        for review in reviews {
+            comments.extend(
+                self.review_comments(remote, number, review.id, http_client)
+                    .await?,
+            );
+        }
+        Ok(comments)
+    }
+
+    async fn review_comments(
+        &self,
+        remote: &ParsedGitRemote,
+        number: u32,
+        review_id: u64,
+        http_client: &Arc<dyn HttpClient>,
+    ) -> Result<Vec<ReviewComment>> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let request = self.request(
+            Method::GET,
+            &format!("repos/{owner}/{repo}/pulls/{number}/reviews/{review_id}/comments"),
+        )?;
+        let comments: Vec<PullReviewComment> = api::get_json(http_client, request)
+            .await
+            .with_context(|| format!("error fetching comments of review {review_id}"))?;
+        Ok(comments.into_iter().map(Into::into).collect())
+    }
+
+    /// Posts the comment as a review of its own, since replies can't be posted to a thread.
+    pub async fn post_pull_request_review_comment(
+        &self,
+        remote: &ParsedGitRemote,
+        pull_request: &PullRequestSummary,
+        comment: NewReviewComment,
+        http_client: &Arc<dyn HttpClient>,
+    ) -> Result<ReviewComment> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let number = pull_request.number;
+        let line_key = match comment.side {
+            ReviewCommentSide::Old => "old_position",
+            ReviewCommentSide::New => "new_position",
+        };
+        let mut review_comment = json!({ "path": comment.path, "body": comment.body });
+        review_comment[line_key] = json!(comment.line);
+        let request = self.request(
+            Method::POST,
+            &format!("repos/{owner}/{repo}/pulls/{number}/reviews"),
+        )?;
+        let review: PullReview = api::post_json(
+            http_client,
+            request,
+            &json!({
+                "event": "COMMENT",
+                "body": "",
+                "commit_id": pull_request.head_sha,
+                "comments": [review_comment],
+            }),
+        )
+        .await
+        .with_context(|| format!("error commenting on pull request #{number}"))?;
+
+        self.review_comments(remote, number, review.id, http_client)
+            .await?
+            .into_iter()
+            .next()
+            .with_context(|| format!("review {} has no comments", review.id))
+    }
+}
+
 pub struct Gitea {
     name: String,
     base_url: Url,
@@ -62,6 +302,13 @@ impl Gitea {
         ))
     }
 
+    fn api(&self) -> GiteaApi<'_> {
+        GiteaApi {
+            base_url: &self.base_url,
+            token: std::env::var("GITEA_TOKEN").ok(),
+        }
+    }
+
     async fn fetch_gitea_commit_author(
         &self,
         repo_owner: &str,
@@ -210,6 +457,64 @@ impl GitHostingProvider for Gitea {
             .transpose()?;
         Ok(avatar_url)
     }
+
+    fn supports_pull_request_reviews(&self) -> bool {
+        true
+    }
+
+    async fn list_pull_requests(
+        &self,
+        remote: &ParsedGitRemote,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<PullRequestSummary>> {
+        self.api().list_pull_requests(remote, &http_client).await
+    }
+
+    async fn pull_request_diff(
+        &self,
+        remote: &ParsedGitRemote,
+        number: u32,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<String> {
+        self.api()
+            .pull_request_diff(remote, number, &http_client)
+            .await
+    }
+
+    async fn file_at_commit(
+        &self,
+        remote: &ParsedGitRemote,
+        path: &str,
+        sha: &str,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<String> {
+        self.api()
+            .file_at_commit(remote, path, sha, &http_client)
+            .await
+    }
+
+    async fn pull_request_review_comments(
+        &self,
+        remote: &ParsedGitRemote,
+        number: u32,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<ReviewComment>> {
+        self.api()
+            .pull_request_review_comments(remote, number, &http_client)
+            .await
+    }
+
+    async fn post_pull_request_review_comment(
+        &self,
+        remote: &ParsedGitRemote,
+        pull_request: &PullRequestSummary,
+        comment: NewReviewComment,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<ReviewComment> {
+        self.api()
+            .post_pull_request_review_comment(remote, pull_request, comment, &http_client)
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -378,4 +683,58 @@ mod tests {
         let expected_url = "https://gitea-instance.big-co.com/zed-industries/zed/src/commit/b2efec9824c45fcc90c9a7eb107a50d1772a60aa/crates/zed/src/main.rs";
         assert_eq!(permalink.to_string(), expected_url.to_string())
     }
+
+    #[gpui::test]
+    async fn test_review_comments() {
+        let http_client = http_client::FakeHttpClient::create(|request| async move {
+            let body = match request.uri().to_string().as_str() {
+                "https://gitea.com/api/v1/repos/zed-industries/zed/pulls/3/reviews?limit=50&page=1" =>
+                {
+                    serde_json::json!([{ "id": 10 }])
+                }
+                "https://gitea.com/api/v1/repos/zed-industries/zed/pulls/3/reviews/10/comments" => {
+                    serde_json::json!([
+                        {
+                            "id": 100,
+                            "user": { "login": "gitea" },
+                            "body": "Removed on purpose?",
+                            "path": "src/lib.rs",
+                            "position": 0,
+                            "original_position": 12,
+                        },
+                        {
+                            "id": 101,
+                            "user": { "login": "gitea" },
+                            "body": "Nice",
+                            "path": "src/lib.rs",
+                            "position": 4,
+                            "original_position": 0,
+                        },
+                    ])
+                }
+                uri => panic!("unexpected request to {uri}"),
+            };
+            Ok(http_client::Response::new(body.to_string().into()))
+        });
+        let remote = ParsedGitRemote {
+            owner: "zed-industries".into(),
+            repo: "zed".into(),
+        };
+
+        let comments = Gitea::public_instance()
+            .pull_request_review_comments(&remote, 3, http_client)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            comments
+                .iter()
+                .map(|comment| (comment.thread_id.as_str(), comment.line, comment.side))
+                .collect::<Vec<_>>(),
+            vec![
+                ("src/lib.rs:old:12", Some(12), ReviewCommentSide::Old),
+                ("src/lib.rs:new:4", Some(4), ReviewCommentSide::New),
+            ]
+        );
+    }
 }
@@ -5,18 +5,20 @@ use anyhow::{Context as _, Result, bail};
 use async_trait::async_trait;
 use futures::AsyncReadExt;
 use gpui::SharedString;
-use http_client::{AsyncBody, HttpClient, HttpRequestExt, Request};
+use http_client::{AsyncBody, HttpClient, HttpRequestExt, Method, Request, http::request::Builder};
+use itertools::Itertools as _;
 use regex::Regex;
 use serde::Deserialize;
+use serde_json::json;
 use url::Url;
 use urlencoding::encode;
 
 use git::{
-    BuildCommitPermalinkParams, BuildPermalinkParams, GitHostingProvider, ParsedGitRemote,
-    PullRequest, RemoteUrl,
+    BuildCommitPermalinkParams, BuildPermalinkParams, GitHostingProvider, NewReviewComment,
+    ParsedGitRemote, PullRequest, PullRequestSummary, RemoteUrl, ReviewComment, ReviewCommentSide,
 };
 
-use crate::get_host_from_git_remote_url;
+use crate::{api, get_host_from_git_remote_url};
 
 fn pull_request_number_regex() -> &'static Regex {
     static PULL_REQUEST_NUMBER_REGEX: LazyLock<Regex> =
@@ -62,6 +64,71 @@ struct User {
     pub avatar_url: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct Account {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestRef {
+    #[serde(rename = "ref")]
+    name: String,
+    sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestDetails {
+    number: u32,
+    title: String,
+    html_url: Url,
+    user: Option<Account>,
+    head: PullRequestRef,
+    base: PullRequestRef,
+}
+
+impl From<PullRequestDetails> for PullRequestSummary {
+    fn from(pull_request: PullRequestDetails) -> Self {
+        Self {
+            number: pull_request.number,
+            title: pull_request.title,
+            author: pull_request.user.map(|user| user.login).unwrap_or_default(),
+            url: pull_request.html_url,
+            source_branch: pull_request.head.name,
+            target_branch: pull_request.base.name,
+            head_sha: pull_request.head.sha,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestComment {
+    id: u64,
+    in_reply_to_id: Option<u64>,
+    user: Option<Account>,
+    body: String,
+    path: String,
+    line: Option<u32>,
+    side: Option<String>,
+}
+
+impl From<PullRequestComment> for ReviewComment {
+    fn from(comment: PullRequestComment) -> Self {
+        Self {
+            id: comment.id.to_string(),
+            thread_id: comment.in_reply_to_id.unwrap_or(comment.id).to_string(),
+            author: comment.user.map(|user| user.login).unwrap_or_default(),
+            body: comment.body,
+            path: comment.path,
+            line: comment.line,
+            side: if comment.side.as_deref() == Some("LEFT") {
+                ReviewCommentSide::Old
+            } else {
+                ReviewCommentSide::New
+            },
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Github {
     name: String,
@@ -108,6 +175,23 @@ impl Github {
         ))
     }
 
+    /// Starts a request to the REST API, which self-hosted instances serve under `/api/v3`.
+    fn api_request(&self, method: Method, path: &str) -> Result<Builder> {
+        let Some(host) = self.base_url.host_str() else {
+            bail!("failed to get host from github base url");
+        };
+        let url = if host == "github.com" {
+            format!("https://api.github.com/{path}")
+        } else {
+            format!("https://{host}/api/v3/{path}")
+        };
+        let mut request = api::request(method, &url);
+        if let Ok(github_token) = std::env::var("GITHUB_TOKEN") {
+            request = request.header("Authorization", format!("Bearer {}", github_token));
+        }
+        Ok(request)
+    }
+
     async fn fetch_github_commit_author(
         &self,
         repo_owner: &str,
@@ -283,6 +367,117 @@ impl GitHostingProvider for Github {
             .transpose()?;
         Ok(avatar_url)
     }
+
+    fn supports_pull_request_reviews(&self) -> bool {
+        true
+    }
+
+    async fn list_pull_requests(
+        &self,
+        remote: &ParsedGitRemote,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<PullRequestSummary>> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let pull_requests: Vec<PullRequestDetails> = api::get_json_pages(&http_client, |page| {
+            self.api_request(
+                Method::GET,
+                &format!("repos/{owner}/{repo}/pulls?state=open&per_page=100&page={page}"),
+            )
+        })
+        .await
+        .context("error fetching GitHub pull requests")?;
+        Ok(pull_requests.into_iter().map(Into::into).collect())
+    }
+
+    async fn pull_request_diff(
+        &self,
+        remote: &ParsedGitRemote,
+        number: u32,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<String> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let request = self
+            .api_request(Method::GET, &format!("repos/{owner}/{repo}/pulls/{number}"))?
+            .header("Accept", "application/vnd.github.diff");
+        api::get_text(&http_client, request)
+            .await
+            .with_context(|| format!("error fetching the diff of GitHub pull request #{number}"))
+    }
+
+    async fn file_at_commit(
+        &self,
+        remote: &ParsedGitRemote,
+        path: &str,
+        sha: &str,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<String> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let encoded_path = path.split('/').map(encode).join("/");
+        let request = self
+            .api_request(
+                Method::GET,
+                &format!("repos/{owner}/{repo}/contents/{encoded_path}?ref={sha}"),
+            )?
+            .header("Accept", "application/vnd.github.raw");
+        api::get_text(&http_client, request)
+            .await
+            .with_context(|| format!("error fetching {path} at {sha} from GitHub"))
+    }
+
+    async fn pull_request_review_comments(
+        &self,
+        remote: &ParsedGitRemote,
+        number: u32,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<ReviewComment>> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let comments: Vec<PullRequestComment> = api::get_json_pages(&http_client, |page| {
+            self.api_request(
+                Method::GET,
+                &format!("repos/{owner}/{repo}/pulls/{number}/comments?per_page=100&page={page}"),
+            )
+        })
+        .await
+        .with_context(|| format!("error fetching comments of GitHub pull request #{number}"))?;
+        Ok(comments.into_iter().map(Into::into).collect())
+    }
+
+    async fn post_pull_request_review_comment(
+        &self,
+        remote: &ParsedGitRemote,
+        pull_request: &PullRequestSummary,
+        comment: NewReviewComment,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<ReviewComment> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let number = pull_request.number;
+        let posted: PullRequestComment = if let Some(thread_id) = &comment.thread_id {
+            let request = self.api_request(
+                Method::POST,
+                &format!("repos/{owner}/{repo}/pulls/{number}/comments/{thread_id}/replies"),
+            )?;
+            api::post_json(&http_client, request, &json!({ "body": comment.body })).await
+        } else {
+            let request = self.api_request(
+                Method::POST,
+                &format!("repos/{owner}/{repo}/pulls/{number}/comments"),
+            )?;
+            let side = match comment.side {
+                ReviewCommentSide::Old => "LEFT",
+                ReviewCommentSide::New => "RIGHT",
+            };
+            let body = json!({
+                "body": comment.body,
+                "commit_id": pull_request.head_sha,
+                "path": comment.path,
+                "line": comment.line,
+                "side": side,
+            });
+            api::post_json(&http_client, request, &body).await
+        }
+        .with_context(|| format!("error commenting on GitHub pull request #{number}"))?;
+        Ok(posted.into())
+    }
 }
 
 #[cfg(test)]
@@ -629,4 +824,152 @@ mod tests {
             "https://avatars.githubusercontent.com/u/e?email=user%2Btag%40example.com&s=128"
         );
     }
+
+    #[gpui::test]
+    async fn test_list_pull_requests() {
+        let http_client = http_client::FakeHttpClient::create(|request| async move {
+            let pull_request = |number: u32| {
+                serde_json::json!({
+                    "number": number,
+                    "title": "Add a feature",
+                    "html_url": format!("https://github.com/zed-industries/zed/pull/{number}"),
+                    "user": { "login": "octocat" },
+                    "head": { "ref": "feature", "sha": "abc123" },
+                    "base": { "ref": "main", "sha": "def456" },
+                })
+            };
+            let response = http_client::Response::builder();
+            let (response, body) = match request.uri().to_string().as_str() {
+                "https://api.github.com/repos/zed-industries/zed/pulls?state=open&per_page=100&page=1" => (
+                    response.header(
+                        "link",
+                        r#"<https://api.github.com/repositories/1/pulls?state=open&per_page=100&page=2>; rel="next""#,
+                    ),
+                    serde_json::json!([pull_request(42)]),
+                ),
+                "https://api.github.com/repos/zed-industries/zed/pulls?state=open&per_page=100&page=2" => {
+                    (response, serde_json::json!([pull_request(43)]))
+                }
+                uri => panic!("unexpected request to {uri}"),
+            };
+            Ok(response.body(body.to_string().into()).unwrap())
+        });
+        let remote = ParsedGitRemote {
+            owner: "zed-industries".into(),
+            repo: "zed".into(),
+        };
+
+        let pull_requests = Github::public_instance()
+            .list_pull_requests(&remote, http_client)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            pull_requests,
+            [42, 43]
+                .into_iter()
+                .map(|number| PullRequestSummary {
+                    number,
+                    title: "Add a feature".into(),
+                    author: "octocat".into(),
+                    url: Url::parse(&format!(
+                        "https://github.com/zed-industries/zed/pull/{number}"
+                    ))
+                    .unwrap(),
+                    source_branch: "feature".into(),
+                    target_branch: "main".into(),
+                    head_sha: "abc123".into(),
+                })
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[gpui::test]
+    async fn test_review_comments() {
+        let http_client = http_client::FakeHttpClient::create(|request| async move {
+            let body = match request.uri().to_string().as_str() {
+                "https://api.github.com/repos/zed-industries/zed/pulls/42/comments?per_page=100&page=1" =>
+                {
+                    serde_json::json!([
+                        {
+                            "id": 1,
+                            "user": { "login": "octocat" },
+                            "body": "Why?",
+                            "path": "src/main.rs",
+                            "line": 3,
+                            "side": "LEFT",
+                        },
+                        {
+                            "id": 2,
+                            "in_reply_to_id": 1,
+                            "user": { "login": "hubot" },
+                            "body": "Because.",
+                            "path": "src/main.rs",
+                            "line": 3,
+                            "side": "LEFT",
+                        },
+                    ])
+                }
+                "https://api.github.com/repos/zed-industries/zed/pulls/42/comments/1/replies" => {
+                    serde_json::json!({
+                        "id": 3,
+                        "in_reply_to_id": 1,
+                        "user": { "login": "octocat" },
+                        "body": "Thanks!",
+                        "path": "src/main.rs",
+                        "line": 3,
+                        "side": "LEFT",
+                    })
+                }
+                uri => panic!("unexpected request to {uri}"),
+            };
+            Ok(http_client::Response::new(body.to_string().into()))
+        });
+        let github = Github::public_instance();
+        let remote = ParsedGitRemote {
+            owner: "zed-industries".into(),
+            repo: "zed".into(),
+        };
+
+        let comments = github
+            .pull_request_review_comments(&remote, 42, http_client.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            comments
+                .iter()
+                .map(|comment| (comment.thread_id.as_str(), comment.body.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("1", "Why?"), ("1", "Because.")]
+        );
+        assert_eq!(comments[0].line, Some(3));
+        assert_eq!(comments[0].side, ReviewCommentSide::Old);
+
+        let pull_request = PullRequestSummary {
+            number: 42,
+            title: "Add a feature".into(),
+            author: "octocat".into(),
+            url: Url::parse("https://github.com/zed-industries/zed/pull/42").unwrap(),
+            source_branch: "feature".into(),
+            target_branch: "main".into(),
+            head_sha: "abc123".into(),
+        };
+        let reply = github
+            .post_pull_request_review_comment(
+                &remote,
+                &pull_request,
+                NewReviewComment {
+                    body: "Thanks!".into(),
+                    path: "src/main.rs".into(),
+                    line: 3,
+                    side: ReviewCommentSide::Old,
+                    thread_id: Some("1".into()),
+                },
+                http_client,
+            )
+            .await
+            .unwrap();
+        assert_eq!(reply.id, "3");
+        assert_eq!(reply.thread_id, "1");
+    }
 }
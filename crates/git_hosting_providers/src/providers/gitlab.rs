@@ -5,15 +5,16 @@ use anyhow::{Context as _, Result, bail};
 use async_trait::async_trait;
 use futures::AsyncReadExt;
 use gpui::SharedString;
-use http_client::{AsyncBody, HttpClient, HttpRequestExt, Request};
+use http_client::{AsyncBody, HttpClient, HttpRequestExt, Method, Request, http::request::Builder};
 use regex::Regex;
 use serde::Deserialize;
+use serde_json::json;
 use url::Url;
 use urlencoding::encode;
 
 use git::{
-    BuildCommitPermalinkParams, BuildPermalinkParams, GitHostingProvider, ParsedGitRemote,
-    PullRequest, RemoteUrl,
+    BuildCommitPermalinkParams, BuildPermalinkParams, GitHostingProvider, NewReviewComment,
+    ParsedGitRemote, PullRequest, PullRequestSummary, RemoteUrl, ReviewComment, ReviewCommentSide,
 };
 
 fn merge_request_number_regex() -> &'static Regex {
@@ -26,7 +27,7 @@ fn merge_request_number_regex() -> &'static Regex {
     &MERGE_REQUEST_NUMBER_REGEX
 }
 
-use crate::get_host_from_git_remote_url;
+use crate::{api, get_host_from_git_remote_url};
 
 #[derive(Debug, Deserialize)]
 struct CommitDetails {
@@ -38,6 +39,101 @@ struct AvatarInfo {
     avatar_url: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct Account {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeRequest {
+    iid: u32,
+    title: String,
+    web_url: Url,
+    author: Option<Account>,
+    source_branch: String,
+    target_branch: String,
+    sha: String,
+}
+
+impl From<MergeRequest> for PullRequestSummary {
+    fn from(merge_request: MergeRequest) -> Self {
+        Self {
+            number: merge_request.iid,
+            title: merge_request.title,
+            author: merge_request
+                .author
+                .map(|author| author.username)
+                .unwrap_or_default(),
+            url: merge_request.web_url,
+            source_branch: merge_request.source_branch,
+            target_branch: merge_request.target_branch,
+            head_sha: merge_request.sha,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeRequestDiffRefs {
+    diff_refs: DiffRefs,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiffRefs {
+    base_sha: String,
+    head_sha: String,
+    start_sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Discussion {
+    id: String,
+    notes: Vec<Note>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Note {
+    id: u64,
+    author: Option<Account>,
+    body: String,
+    #[serde(default)]
+    system: bool,
+    position: Option<NotePosition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NotePosition {
+    new_path: String,
+    new_line: Option<u32>,
+    old_line: Option<u32>,
+}
+
+impl Note {
+    /// Converts a note on a diff line into a [`ReviewComment`], skipping system notes and
+    /// notes on the merge request as a whole.
+    fn into_review_comment(self, thread_id: &str) -> Option<ReviewComment> {
+        if self.system {
+            return None;
+        }
+        let position = self.position?;
+        let (line, side) = match position.new_line {
+            Some(line) => (Some(line), ReviewCommentSide::New),
+            None => (position.old_line, ReviewCommentSide::Old),
+        };
+        Some(ReviewComment {
+            id: self.id.to_string(),
+            thread_id: thread_id.to_string(),
+            author: self
+                .author
+                .map(|author| author.username)
+                .unwrap_or_default(),
+            body: self.body,
+            path: position.new_path,
+            line,
+            side,
+        })
+    }
+}
+
 #[derive(Debug)]
 pub struct Gitlab {
     name: String,
@@ -75,6 +171,22 @@ impl Gitlab {
         ))
     }
 
+    fn api_request(&self, method: Method, path: &str) -> Result<Builder> {
+        let Some(host) = self.base_url.host_str() else {
+            bail!("failed to get host from gitlab base url");
+        };
+        let mut request = api::request(method, &format!("https://{host}/api/v4/{path}"));
+        if let Ok(gitlab_token) = std::env::var("GITLAB_TOKEN") {
+            request = request.header("PRIVATE-TOKEN", gitlab_token);
+        }
+        Ok(request)
+    }
+
+    fn merge_requests_path(remote: &ParsedGitRemote) -> String {
+        let project_path = format!("{}/{}", remote.owner, remote.repo);
+        format!("projects/{}/merge_requests", encode(&project_path))
+    }
+
     async fn fetch_gitlab_commit_author(
         &self,
         repo_owner: &str,
@@ -295,6 +407,158 @@ impl GitHostingProvider for Gitlab {
             .transpose()?;
         Ok(avatar_url)
     }
+
+    fn supports_pull_request_reviews(&self) -> bool {
+        true
+    }
+
+    async fn list_pull_requests(
+        &self,
+        remote: &ParsedGitRemote,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<PullRequestSummary>> {
+        let merge_requests_path = Self::merge_requests_path(remote);
+        let merge_requests: Vec<MergeRequest> = api::get_json_pages(&http_client, |page| {
+            self.api_request(
+                Method::GET,
+                &format!("{merge_requests_path}?state=opened&per_page=100&page={page}"),
+            )
+        })
+        .await
+        .context("error fetching GitLab merge requests")?;
+        Ok(merge_requests.into_iter().map(Into::into).collect())
+    }
+
+    async fn pull_request_diff(
+        &self,
+        remote: &ParsedGitRemote,
+        number: u32,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<String> {
+        let merge_requests_path = Self::merge_requests_path(remote);
+        let request = self.api_request(
+            Method::GET,
+            &format!("{merge_requests_path}/{number}/raw_diffs"),
+        )?;
+        api::get_text(&http_client, request)
+            .await
+            .with_context(|| format!("error fetching the diff of GitLab merge request !{number}"))
+    }
+
+    async fn file_at_commit(
+        &self,
+        remote: &ParsedGitRemote,
+        path: &str,
+        sha: &str,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<String> {
+        let project_path = format!("{}/{}", remote.owner, remote.repo);
+        let request = self.api_request(
+            Method::GET,
+            &format!(
+                "projects/{}/repository/files/{}/raw?ref={sha}",
+                encode(&project_path),
+                encode(path)
+            ),
+        )?;
+        api::get_text(&http_client, request)
+            .await
+            .with_context(|| format!("error fetching {path} at {sha} from GitLab"))
+    }
+
+    async fn pull_request_review_comments(
+        &self,
+        remote: &ParsedGitRemote,
+        number: u32,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<ReviewComment>> {
+        let merge_requests_path = Self::merge_requests_path(remote);
+        let discussions: Vec<Discussion> = api::get_json_pages(&http_client, |page| {
+            self.api_request(
+                Method::GET,
+                &format!("{merge_requests_path}/{number}/discussions?per_page=100&page={page}"),
+            )
+        })
+        .await
+        .with_context(|| format!("error fetching discussions of GitLab merge request !{number}"))?;
+        Ok(discussions
+            .into_iter()
+            .flat_map(|discussion| {
+                let thread_id = discussion.id;
+                discussion
+                    .notes
+                    .into_iter()
+                    .filter_map(move |note| note.into_review_comment(&thread_id))
+            })
+            .collect())
+    }
+
+    async fn post_pull_request_review_comment(
+        &self,
+        remote: &ParsedGitRemote,
+        pull_request: &PullRequestSummary,
+        comment: NewReviewComment,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<ReviewComment> {
+        let merge_requests_path = Self::merge_requests_path(remote);
+        let number = pull_request.number;
+        let posted = if let Some(thread_id) = &comment.thread_id {
+            let request = self.api_request(
+                Method::POST,
+                &format!("{merge_requests_path}/{number}/discussions/{thread_id}/notes"),
+            )?;
+            let note: Note =
+                api::post_json(&http_client, request, &json!({ "body": comment.body })).await?;
+            Some(ReviewComment {
+                id: note.id.to_string(),
+                thread_id: thread_id.clone(),
+                author: note
+                    .author
+                    .map(|author| author.username)
+                    .unwrap_or_default(),
+                body: note.body,
+                path: comment.path,
+                line: Some(comment.line),
+                side: comment.side,
+            })
+        } else {
+            // Positions on the diff are relative to the merge request's current diff refs.
+            let request =
+                self.api_request(Method::GET, &format!("{merge_requests_path}/{number}"))?;
+            let MergeRequestDiffRefs { diff_refs } = api::get_json(&http_client, request).await?;
+            let mut position = json!({
+                "position_type": "text",
+                "base_sha": diff_refs.base_sha,
+                "head_sha": diff_refs.head_sha,
+                "start_sha": diff_refs.start_sha,
+                "old_path": comment.path,
+                "new_path": comment.path,
+            });
+            let line_key = match comment.side {
+                ReviewCommentSide::Old => "old_line",
+                ReviewCommentSide::New => "new_line",
+            };
+            position[line_key] = json!(comment.line);
+
+            let request = self.api_request(
+                Method::POST,
+                &format!("{merge_requests_path}/{number}/discussions"),
+            )?;
+            let discussion: Discussion = api::post_json(
+                &http_client,
+                request,
+                &json!({ "body": comment.body, "position": position }),
+            )
+            .await?;
+            let thread_id = discussion.id;
+            discussion
+                .notes
+                .into_iter()
+                .next()
+                .and_then(|note| note.into_review_comment(&thread_id))
+        };
+        posted.with_context(|| format!("error commenting on GitLab merge request !{number}"))
+    }
 }
 
 #[cfg(test)]
@@ -596,4 +860,85 @@ mod tests {
 
         assert!(pull_request.is_none());
     }
+
+    #[gpui::test]
+    async fn test_review_comments() {
+        let http_client = http_client::FakeHttpClient::create(|request| async move {
+            assert_eq!(
+                request.uri().to_string(),
+                "https://gitlab.com/api/v4/projects/zed-industries%2Fzed/merge_requests/7/discussions?per_page=100&page=1"
+            );
+            Ok(http_client::Response::new(
+                serde_json::json!([
+                    {
+                        "id": "general",
+                        "notes": [{
+                            "id": 1,
+                            "author": { "username": "tanuki" },
+                            "body": "Looks good overall",
+                        }],
+                    },
+                    {
+                        "id": "abcdef",
+                        "notes": [
+                            {
+                                "id": 2,
+                                "author": { "username": "tanuki" },
+                                "body": "Typo here",
+                                "position": { "new_path": "README.md", "new_line": 5, "old_line": null },
+                            },
+                            {
+                                "id": 3,
+                                "author": { "username": "tanuki" },
+                                "body": "changed this line in version 2",
+                                "system": true,
+                                "position": { "new_path": "README.md", "new_line": 5, "old_line": null },
+                            },
+                            {
+                                "id": 4,
+                                "author": { "username": "octocat" },
+                                "body": "Fixed",
+                                "position": { "new_path": "README.md", "new_line": 5, "old_line": null },
+                            },
+                        ],
+                    },
+                ])
+                .to_string()
+                .into(),
+            ))
+        });
+        let remote = ParsedGitRemote {
+            owner: "zed-industries".into(),
+            repo: "zed".into(),
+        };
+
+        let comments = Gitlab::public_instance()
+            .pull_request_review_comments(&remote, 7, http_client)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            comments,
+            vec![
+                ReviewComment {
+                    id: "2".into(),
+                    thread_id: "abcdef".into(),
+                    author: "tanuki".into(),
+                    body: "Typo here".into(),
+                    path: "README.md".into(),
+                    line: Some(5),
+                    side: ReviewCommentSide::New,
+                },
+                ReviewComment {
+                    id: "4".into(),
+                    thread_id: "abcdef".into(),
+                    author: "octocat".into(),
+                    body: "Fixed".into(),
+                    path: "README.md".into(),
+                    line: Some(5),
+                    side: ReviewCommentSide::New,
+                },
+            ]
+        );
+    }
 }
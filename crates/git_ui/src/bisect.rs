@@ -0,0 +1,192 @@
+use anyhow::{Context as _, anyhow};
+use git::{
+    SHORT_SHA_LENGTH,
+    bisect::{BisectMark, BisectStep},
+};
+use gpui::{App, Entity, SharedString, WeakEntity, Window};
+use notifications::status_toast::{StatusToast, ToastIcon};
+use project::git_store::Repository;
+use task::TaskContext;
+use ui::prelude::*;
+use workspace::{Workspace, notifications::DetachAndPromptErr};
+
+use crate::{commit_view::CommitView, picker_prompt};
+
+/// Starts a bisect with `bad` marked as bad and each of `good` marked as good.
+pub fn start(
+    repository: Entity<Repository>,
+    bad: Option<String>,
+    good: Vec<String>,
+    workspace: WeakEntity<Workspace>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let step = repository.update(cx, |repository, _| repository.bisect_start(bad, good));
+    cx.spawn(async move |cx| {
+        let step = step.await??;
+        workspace.update(cx, |workspace, cx| {
+            show_step(step, &repository, workspace, cx)
+        })
+    })
+    .detach_and_prompt_err("Failed to start bisect", window, cx, |_, _, _| None);
+}
+
+/// Marks `commit`, or the commit being tested when `None`, in the bisect in progress.
+pub fn mark(
+    repository: Entity<Repository>,
+    mark: BisectMark,
+    commit: Option<String>,
+    workspace: WeakEntity<Workspace>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let step = repository.update(cx, |repository, _| repository.bisect_mark(mark, commit));
+    cx.spawn(async move |cx| {
+        let step = step.await??;
+        workspace.update(cx, |workspace, cx| {
+            show_step(step, &repository, workspace, cx)
+        })
+    })
+    .detach_and_prompt_err("Failed to mark commit", window, cx, |_, _, _| None);
+}
+
+pub fn reset(repository: Entity<Repository>, window: &mut Window, cx: &mut App) {
+    let reset = repository.update(cx, |repository, _| repository.bisect_reset());
+    cx.spawn(async move |_| reset.await?).detach_and_prompt_err(
+        "Failed to end bisect",
+        window,
+        cx,
+        |_, _, _| None,
+    );
+}
+
+/// Asks for one of the project's tasks and runs it on each commit the bisect checks out,
+/// marking the commit from the task's exit code until the first bad commit is found.
+pub fn run_with_task(
+    repository: Entity<Repository>,
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let project = workspace.project().read(cx);
+    let Some(inventory) = project.task_store().read(cx).task_inventory().cloned() else {
+        return;
+    };
+    let work_directory = repository.read(cx).work_directory_abs_path.clone();
+    let worktree_id = project
+        .find_worktree(&work_directory, cx)
+        .map(|(worktree, _)| worktree.read(cx).id());
+    let tasks = inventory.read(cx).list_tasks(None, None, worktree_id, cx);
+    let workspace = cx.weak_entity();
+
+    cx.spawn_in(window, async move |_, cx| {
+        let tasks = tasks.await;
+        let labels = tasks
+            .iter()
+            .map(|(_, template)| SharedString::from(template.label.clone()))
+            .collect();
+        let selection = cx.update(|window, cx| {
+            picker_prompt::prompt(
+                "Select a task to test each commit with",
+                labels,
+                workspace.clone(),
+                window,
+                cx,
+            )
+        })?;
+        let Some(ix) = selection.await else {
+            return Ok(());
+        };
+        let (source_kind, template) = tasks.into_iter().nth(ix).context("task not found")?;
+        let task_context = TaskContext {
+            cwd: Some(work_directory.to_path_buf()),
+            ..TaskContext::default()
+        };
+        let task = template
+            .resolve_task(&source_kind.to_id_base(), &task_context)
+            .with_context(|| format!("failed to resolve task {:?}", template.label))?;
+
+        while repository.read_with(cx, |repository, _| repository.bisect.is_some()) {
+            let exit_status = workspace
+                .update_in(cx, |workspace, window, cx| {
+                    workspace.spawn_in_terminal(task.resolved.clone(), window, cx)
+                })?
+                .await;
+            let exit_status = match exit_status {
+                Some(exit_status) => exit_status?,
+                None => return Ok(()),
+            };
+            let mark = exit_status
+                .code()
+                .and_then(BisectMark::from_exit_code)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "task {:?} exited with {exit_status}, stopping",
+                        template.label
+                    )
+                })?;
+            let step = repository
+                .update(cx, |repository, _| repository.bisect_mark(mark, None))
+                .await??;
+            let done = !matches!(step, Some(BisectStep::Testing { .. }));
+            workspace.update(cx, |workspace, cx| {
+                show_step(step, &repository, workspace, cx)
+            })?;
+            if done {
+                break;
+            }
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_prompt_err("Failed to run bisect", window, cx, |_, _, _| None);
+}
+
+fn show_step(
+    step: Option<BisectStep>,
+    repository: &Entity<Repository>,
+    workspace: &mut Workspace,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(step) = step else {
+        return;
+    };
+    let short_sha = |sha: &SharedString| sha[..SHORT_SHA_LENGTH.min(sha.len())].to_string();
+    let status_toast = match step {
+        BisectStep::Testing {
+            sha,
+            remaining,
+            steps,
+        } => {
+            let message = format!(
+                "Testing {}: {remaining} left, roughly {steps} more step{}",
+                short_sha(&sha),
+                if steps == 1 { "" } else { "s" }
+            );
+            StatusToast::new(message, cx, |this, _| {
+                this.icon(ToastIcon::new(IconName::GitBranchAlt).color(Color::Muted))
+                    .dismiss_button(true)
+            })
+        }
+        BisectStep::Found { sha } => {
+            let message = format!("{} is the first bad commit", short_sha(&sha));
+            let repository = repository.downgrade();
+            let workspace = cx.weak_entity();
+            StatusToast::new(message, cx, move |this, _| {
+                this.icon(ToastIcon::new(IconName::Check).color(Color::Success))
+                    .action("View Commit", move |window, cx| {
+                        CommitView::open(
+                            sha.to_string(),
+                            repository.clone(),
+                            workspace.clone(),
+                            None,
+                            None,
+                            window,
+                            cx,
+                        );
+                    })
+                    .dismiss_button(true)
+            })
+        }
+    };
+    workspace.toggle_status_toast(status_toast, cx);
+}
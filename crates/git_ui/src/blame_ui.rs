@@ -2,7 +2,11 @@ use crate::{
     commit_tooltip::{CommitAvatar, CommitTooltip},
     commit_view::CommitView,
 };
-use editor::{BlameRenderer, Editor, hover_markdown_style};
+use editor::{
+    BlameRenderer, Editor,
+    actions::{BlameBeforeCommit, UndoBlameBeforeCommit},
+    hover_markdown_style,
+};
 use git::{blame::BlameEntry, commit::ParsedCommitMessage, repository::CommitSummary};
 use gpui::{
     ClipboardItem, Entity, Hsla, MouseButton, ScrollHandle, Subscription, TextStyle,
@@ -89,6 +93,7 @@ impl BlameRenderer for GitBlameRenderer {
                         .on_mouse_down(MouseButton::Right, {
                             let blame_entry = blame_entry.clone();
                             let details = details.clone();
+                            let repository = repository.clone();
                             let editor = editor.clone();
                             move |event, window, cx| {
                                 cx.stop_propagation();
@@ -96,6 +101,7 @@ impl BlameRenderer for GitBlameRenderer {
                                 deploy_blame_entry_context_menu(
                                     &blame_entry,
                                     details.as_ref(),
+                                    repository.clone(),
                                     editor.clone(),
                                     event.position,
                                     window,
@@ -392,11 +398,23 @@ impl BlameRenderer for GitBlameRenderer {
 fn deploy_blame_entry_context_menu(
     blame_entry: &BlameEntry,
     details: Option<&ParsedCommitMessage>,
+    repository: Entity<Repository>,
     editor: Entity<Editor>,
     position: gpui::Point<Pixels>,
     window: &mut Window,
     cx: &mut App,
 ) {
+    let blame = editor.read(cx).blame().cloned();
+    // The first revision of a past revision's blame can't be undone, only closed.
+    let undoable_commit = blame
+        .as_ref()
+        .and_then(|blame| match blame.read(cx).revisions() {
+            [_, .., last] => Some(last.commit),
+            _ => None,
+        });
+    let has_parent = blame_entry.previous.is_some();
+    let blame_entry = blame_entry.clone();
+    let weak_editor = editor.downgrade();
     let context_menu = ContextMenu::build(window, cx, move |menu, _, _| {
         let sha = format!("{}", blame_entry.sha);
         menu.on_blur_subscription(Subscription::new(|| {}))
@@ -411,6 +429,36 @@ fn deploy_blame_entry_context_menu(
                     })
                 },
             )
+            .when_some(blame, |this, blame| {
+                this.separator()
+                    .when(has_parent, |this| {
+                        this.entry(
+                            "Blame Before This Commit",
+                            Some(Box::new(BlameBeforeCommit)),
+                            move |window, cx| {
+                                weak_editor
+                                    .update(cx, |editor, cx| {
+                                        editor.blame_before(
+                                            &blame_entry,
+                                            repository.clone(),
+                                            window,
+                                            cx,
+                                        )
+                                    })
+                                    .ok();
+                            },
+                        )
+                    })
+                    .when_some(undoable_commit, |this, commit| {
+                        this.entry(
+                            format!("Undo Blame Before {}", commit.display_short()),
+                            Some(Box::new(UndoBlameBeforeCommit)),
+                            move |_, cx| {
+                                blame.update(cx, |blame, cx| blame.undo_blame_before_commit(cx))
+                            },
+                        )
+                    })
+            })
     });
 
     editor.update(cx, move |editor, cx| {
@@ -1,7 +1,6 @@
 use crate::branch_picker::{self, BranchList};
 use crate::git_panel::{GitPanel, commit_message_editor, panel_editor_style};
-use git::repository::CommitOptions;
-use git::{Amend, Commit, GenerateCommitMessage, Signoff};
+use git::{Amend, Commit, GenerateCommitMessage, SignCommit, Signoff};
 use panel::panel_button;
 use project::DisableAiSettings;
 use settings::Settings;
@@ -281,6 +280,7 @@ impl CommitModal {
                     let git_panel = git_panel_entity.read(cx);
                     let amend_enabled = git_panel.amend_pending();
                     let signoff_enabled = git_panel.signoff_enabled();
+                    let sign_enabled = git_panel.sign_commits_enabled(cx);
                     let has_previous_commit = git_panel.head_commit(cx).is_some();
 
                     Some(ContextMenu::build(window, cx, |context_menu, _, _| {
@@ -320,6 +320,20 @@ impl CommitModal {
                                     }
                                 },
                             )
+                            .toggleable_entry(
+                                "Sign",
+                                sign_enabled,
+                                IconPosition::Start,
+                                Some(Box::new(SignCommit)),
+                                {
+                                    let git_panel = git_panel_entity.clone();
+                                    move |window, cx| {
+                                        git_panel.update(cx, |git_panel, cx| {
+                                            git_panel.toggle_sign_commits(&SignCommit, window, cx);
+                                        })
+                                    }
+                                },
+                            )
                     }))
                 }
             })
@@ -337,6 +351,8 @@ impl CommitModal {
             active_repo,
             is_amend_pending,
             is_signoff_enabled,
+            is_sign_enabled,
+            commit_options,
             workspace,
         ) = self.git_panel.update(cx, |git_panel, cx| {
             let (can_commit, tooltip) = git_panel.configure_commit_button(cx);
@@ -346,6 +362,8 @@ impl CommitModal {
             let active_repo = git_panel.active_repository.clone();
             let is_amend_pending = git_panel.amend_pending();
             let is_signoff_enabled = git_panel.signoff_enabled();
+            let is_sign_enabled = git_panel.sign_commits_enabled(cx);
+            let commit_options = git_panel.commit_options(is_amend_pending, cx);
             (
                 can_commit,
                 tooltip,
@@ -355,6 +373,8 @@ impl CommitModal {
                 active_repo,
                 is_amend_pending,
                 is_signoff_enabled,
+                is_sign_enabled,
+                commit_options,
                 git_panel.workspace.clone(),
             )
         });
@@ -448,14 +468,7 @@ impl CommitModal {
                         .on_click(cx.listener(move |this, _: &ClickEvent, window, cx| {
                             telemetry::event!("Git Committed", source = "Git Modal");
                             this.git_panel.update(cx, |git_panel, cx| {
-                                git_panel.commit_changes(
-                                    CommitOptions {
-                                        amend: is_amend_pending,
-                                        signoff: is_signoff_enabled,
-                                    },
-                                    window,
-                                    cx,
-                                )
+                                git_panel.commit_changes(commit_options.clone(), window, cx)
                             });
                             cx.emit(DismissEvent);
                         }))
@@ -472,9 +485,10 @@ impl CommitModal {
                                             &git::Commit
                                         }),
                                         format!(
-                                            "git commit{}{}",
+                                            "git commit{}{}{}",
                                             if is_amend_pending { " --amend" } else { "" },
-                                            if is_signoff_enabled { " --signoff" } else { "" }
+                                            if is_signoff_enabled { " --signoff" } else { "" },
+                                            if is_sign_enabled { " --gpg-sign" } else { "" }
                                         ),
                                         &focus_handle.clone(),
                                         cx,
@@ -2,11 +2,11 @@ use crate::commit_view::CommitView;
 use editor::hover_markdown_style;
 use futures::Future;
 use git::blame::BlameEntry;
-use git::repository::CommitSummary;
+use git::repository::{CommitSignature, CommitSummary, SignatureStatus};
 use git::{GitRemote, commit::ParsedCommitMessage};
 use gpui::{
-    App, Asset, Element, Entity, MouseButton, ParentElement, Render, ScrollHandle,
-    StatefulInteractiveElement, WeakEntity, prelude::*,
+    App, Asset, Element, ElementId, Entity, MouseButton, ParentElement, Render, ScrollHandle,
+    StatefulInteractiveElement, Task, WeakEntity, prelude::*,
 };
 use markdown::{Markdown, MarkdownElement};
 use project::git_store::Repository;
@@ -14,7 +14,7 @@ use settings::Settings;
 use std::hash::Hash;
 use theme::ThemeSettings;
 use time::{OffsetDateTime, UtcOffset};
-use ui::{Avatar, CopyButton, Divider, prelude::*, tooltip_container};
+use ui::{Avatar, CopyButton, Divider, Tooltip, prelude::*, tooltip_container};
 use workspace::Workspace;
 
 #[derive(Clone, Debug)]
@@ -134,12 +134,65 @@ impl Asset for CommitAvatarAsset {
     }
 }
 
+/// An icon showing that a commit is signed, colored by its verification status.
+#[derive(IntoElement)]
+pub struct SignatureBadge {
+    id: ElementId,
+    /// `None` when the commit is known to be signed but hasn't been verified yet.
+    signature: Option<CommitSignature>,
+}
+
+impl SignatureBadge {
+    pub fn new(id: impl Into<ElementId>, signature: Option<CommitSignature>) -> Self {
+        Self {
+            id: id.into(),
+            signature,
+        }
+    }
+}
+
+impl RenderOnce for SignatureBadge {
+    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+        let (icon, color, title) = match self.signature.as_ref().map(|s| s.status) {
+            None => (IconName::ShieldCheck, Color::Muted, "Signed"),
+            Some(SignatureStatus::Good) => (
+                IconName::ShieldCheck,
+                Color::Success,
+                SignatureStatus::Good.label(),
+            ),
+            Some(status @ (SignatureStatus::Bad | SignatureStatus::Revoked)) => {
+                (IconName::Warning, Color::Error, status.label())
+            }
+            Some(status) => (IconName::ShieldCheck, Color::Warning, status.label()),
+        };
+        let meta = self.signature.and_then(|signature| {
+            let meta = if signature.signer.is_empty() {
+                signature.key
+            } else {
+                signature.signer
+            };
+            (!meta.is_empty()).then_some(meta)
+        });
+
+        div()
+            .id(self.id)
+            .flex_none()
+            .child(Icon::new(icon).size(IconSize::Small).color(color))
+            .tooltip(move |_, cx| match meta.clone() {
+                Some(meta) => Tooltip::with_meta(title, None, meta, cx),
+                None => Tooltip::simple(title, cx),
+            })
+    }
+}
+
 pub struct CommitTooltip {
     commit: CommitDetails,
+    signature: Option<CommitSignature>,
     scroll_handle: ScrollHandle,
     markdown: Entity<Markdown>,
     repository: Entity<Repository>,
     workspace: WeakEntity<Workspace>,
+    _load_signature: Task<()>,
 }
 
 impl CommitTooltip {
@@ -191,12 +244,25 @@ impl CommitTooltip {
                 cx,
             )
         });
+        let details =
+            repository.update(cx, |repository, _| repository.show(commit.sha.to_string()));
+        let _load_signature = cx.spawn(async move |this, cx| {
+            if let Ok(Ok(details)) = details.await {
+                this.update(cx, |this, cx| {
+                    this.signature = details.signature;
+                    cx.notify();
+                })
+                .ok();
+            }
+        });
         Self {
             commit,
+            signature: None,
             repository,
             workspace,
             scroll_handle: ScrollHandle::new(),
             markdown,
+            _load_signature,
         }
     }
 }
@@ -209,6 +275,8 @@ impl Render for CommitTooltip {
 
         let author_email = self.commit.author_email.clone();
 
+        let signature = self.signature.clone();
+
         let short_commit_id = self
             .commit
             .sha
@@ -308,7 +376,15 @@ impl Render for CommitTooltip {
                                 .pt_1p5()
                                 .border_t_1()
                                 .border_color(cx.theme().colors().border_variant)
-                                .child(absolute_timestamp)
+                                .child(h_flex().gap_1p5().child(absolute_timestamp).when_some(
+                                    signature,
+                                    |this, signature| {
+                                        this.child(SignatureBadge::new(
+                                            "commit-signature",
+                                            Some(signature),
+                                        ))
+                                    },
+                                ))
                                 .child(
                                     h_flex()
                                         .gap_1p5()
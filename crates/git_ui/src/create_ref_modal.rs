@@ -0,0 +1,156 @@
+use anyhow::anyhow;
+use editor::Editor;
+use git::SHORT_SHA_LENGTH;
+use gpui::{App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Window};
+use menu::{Cancel, Confirm};
+use project::git_store::Repository;
+use ui::{Checkbox, Headline, HeadlineSize, ToggleState, prelude::*};
+use workspace::{ModalView, notifications::DetachAndPromptErr};
+
+/// The kind of ref created by a [`CreateRefModal`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RefKind {
+    Branch,
+    Tag,
+}
+
+/// Asks for the name of a branch or tag to create at a commit. Tags get an optional message,
+/// which makes them annotated, and can be signed.
+pub struct CreateRefModal {
+    kind: RefKind,
+    commit: SharedString,
+    name_editor: Entity<Editor>,
+    message_editor: Entity<Editor>,
+    sign: bool,
+    repo: Entity<Repository>,
+}
+
+impl CreateRefModal {
+    pub fn new(
+        kind: RefKind,
+        commit: SharedString,
+        repo: Entity<Repository>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let name_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text(
+                match kind {
+                    RefKind::Branch => "Branch name",
+                    RefKind::Tag => "Tag name",
+                },
+                window,
+                cx,
+            );
+            editor
+        });
+        let message_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Message (leave empty for a lightweight tag)", window, cx);
+            editor
+        });
+        Self {
+            kind,
+            commit,
+            name_editor,
+            message_editor,
+            sign: false,
+            repo,
+        }
+    }
+
+    pub fn set_name(&mut self, name: &str, window: &mut Window, cx: &mut Context<Self>) {
+        self.name_editor
+            .update(cx, |editor, cx| editor.set_text(name, window, cx));
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let name = self.name_editor.read(cx).text(cx).trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        let message = self.message_editor.read(cx).text(cx).trim().to_string();
+        let commit = self.commit.to_string();
+        let sign = self.sign;
+
+        let (task, error_message) = match self.kind {
+            RefKind::Branch => (
+                self.repo
+                    .update(cx, |repo, _| repo.create_branch(name, Some(commit))),
+                "Failed to create branch",
+            ),
+            RefKind::Tag => (
+                self.repo.update(cx, |repo, _| {
+                    repo.create_tag(name, commit, Some(message).filter(|m| !m.is_empty()), sign)
+                }),
+                "Failed to create tag",
+            ),
+        };
+        cx.spawn(async move |_, _| match task.await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow!("Operation was canceled")),
+        })
+        .detach_and_prompt_err(error_message, window, cx, |_, _, _| None);
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for CreateRefModal {}
+impl ModalView for CreateRefModal {}
+impl Focusable for CreateRefModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.name_editor.focus_handle(cx)
+    }
+}
+
+impl Render for CreateRefModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let (icon, title) = match self.kind {
+            RefKind::Branch => (IconName::GitBranch, "Create Branch"),
+            RefKind::Tag => (IconName::Hash, "Create Tag"),
+        };
+        let commit = &self.commit[..SHORT_SHA_LENGTH.min(self.commit.len())];
+
+        v_flex()
+            .key_context("CreateRefModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .elevation_2(cx)
+            .w(rems(34.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(icon).size(IconSize::XSmall))
+                    .child(
+                        Headline::new(format!("{title} at {commit}")).size(HeadlineSize::XSmall),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .px_3()
+                    .pb_3()
+                    .w_full()
+                    .gap_2()
+                    .child(self.name_editor.clone())
+                    .when(self.kind == RefKind::Tag, |this| {
+                        this.child(self.message_editor.clone()).child(
+                            Checkbox::new("sign-tag", self.sign.into())
+                                .label("Sign tag")
+                                .on_click(cx.listener(|this, checked: &ToggleState, _, cx| {
+                                    this.sign = checked.selected();
+                                    cx.notify();
+                                })),
+                        )
+                    }),
+            )
+    }
+}
@@ -585,4 +585,72 @@ mod tests {
             assert!(!buffer.is_dirty(), "Buffer should not be dirty after save");
         });
     }
+
+    #[gpui::test]
+    async fn test_stage_selection_in_diff_view(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            serde_json::json!({
+                ".git": {},
+                "old_file.txt": "old line 1\nline 2\n",
+                "new_file.txt": "new line 1\nline 2\n"
+            }),
+        )
+        .await;
+        fs.set_head_and_index_for_repo(
+            path!("/test/.git").as_ref(),
+            &[("new_file.txt", "new line 1\nline 2\n".into())],
+        );
+
+        let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let diff_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                FileDiffView::open(
+                    path!("/test/old_file.txt").into(),
+                    path!("/test/new_file.txt").into(),
+                    workspace.weak_handle(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        // The diff is between two files rather than against the index, so staging a selection
+        // neither saves the edited file nor touches the index.
+        diff_view.update_in(cx, |diff_view, window, cx| {
+            diff_view.editor.update(cx, |editor, cx| {
+                editor.insert("modified ", window, cx);
+                editor.select_all(&editor::actions::SelectAll, window, cx);
+                editor.stage_selection(&git::StageSelection, window, cx);
+            });
+        });
+        cx.run_until_parked();
+
+        diff_view.read_with(cx, |diff_view, cx| {
+            assert!(diff_view.new_buffer.read(cx).is_dirty());
+        });
+        assert_eq!(
+            fs.load(path!("/test/new_file.txt").as_ref()).await.unwrap(),
+            "new line 1\nline 2\n"
+        );
+        let index_text = fs
+            .with_git_state(path!("/test/.git").as_ref(), false, |state| {
+                state
+                    .index_contents
+                    .get(&git::repository::repo_path("new_file.txt"))
+                    .cloned()
+            })
+            .unwrap();
+        assert_eq!(index_text.as_deref(), Some("new line 1\nline 2\n"));
+    }
 }
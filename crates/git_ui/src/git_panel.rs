@@ -21,15 +21,18 @@ use editor::{
 };
 use editor::{EditorStyle, RewrapOptions};
 use futures::StreamExt as _;
+use git::SHORT_SHA_LENGTH;
 use git::commit::ParsedCommitMessage;
+use git::rebase::RebaseControl;
 use git::repository::{
-    Branch, CommitDetails, CommitOptions, CommitSummary, DiffType, FetchOptions, GitCommitter,
-    PushOptions, Remote, RemoteCommandOutput, ResetMode, Upstream, UpstreamTracking,
-    UpstreamTrackingStatus, get_git_committer,
+    Branch, CommitDetails, CommitOptions, CommitSigning, CommitSummary, DiffType, FetchOptions,
+    GitCommitter, PushOptions, Remote, RemoteCommandOutput, ResetMode, SigningFormat, Upstream,
+    UpstreamTracking, UpstreamTrackingStatus, get_git_committer,
 };
 use git::stash::GitStash;
 use git::status::StageStatus;
-use git::{Amend, Signoff, ToggleStaged, repository::RepoPath, status::FileStatus};
+use git::submodule::SubmoduleCommand;
+use git::{Amend, SignCommit, Signoff, ToggleStaged, repository::RepoPath, status::FileStatus};
 use git::{
     ExpandCommitEditor, GitHostingProviderRegistry, RestoreTrackedFiles, StageAll, StashAll,
     StashApply, StashPop, TrashUntrackedFiles, UnstageAll,
@@ -56,7 +59,10 @@ use project::{
 };
 use prompt_store::{BuiltInPrompt, PromptId, PromptStore, RULES_FILE_NAMES};
 use serde::{Deserialize, Serialize};
-use settings::{Settings, SettingsStore, StatusStyle};
+use settings::{
+    CommitSigningFormat, CommitSigningSettingsContent, Settings, SettingsLocation, SettingsStore,
+    StatusStyle,
+};
 use smallvec::SmallVec;
 use std::future::Future;
 use std::ops::Range;
@@ -150,6 +156,7 @@ struct GitMenuState {
     has_new_changes: bool,
     sort_by_path: bool,
     has_stash_items: bool,
+    has_submodules: bool,
     tree_view: bool,
 }
 
@@ -180,8 +187,16 @@ fn git_panel_context_menu(
             )
             .action_disabled_when(!state.has_stash_items, "Stash Pop", StashPop.boxed_clone())
             .action("View Stash", zed_actions::git::ViewStash.boxed_clone())
+            .action("View Reflog", zed_actions::git::ViewReflog.boxed_clone())
+            .when(state.has_submodules, |this| {
+                this.separator()
+                    .action("Initialize Submodules", git::InitSubmodules.boxed_clone())
+                    .action("Update Submodules", git::UpdateSubmodules.boxed_clone())
+                    .action("Sync Submodules", git::SyncSubmodules.boxed_clone())
+            })
             .separator()
             .action("Open Diff", project_diff::Diff.boxed_clone())
+            .action("Review Pull Request", git::ReviewPullRequest.boxed_clone())
             .separator()
             .action_disabled_when(
                 !state.has_tracked_changes,
@@ -626,6 +641,8 @@ pub struct GitPanel {
     amend_pending: bool,
     original_commit_message: Option<String>,
     signoff_enabled: bool,
+    /// Overrides `git.commit_signing.enabled` once toggled in the commit menu.
+    sign_commits: Option<bool>,
     pending_serialization: Task<()>,
     pub(crate) project: Entity<Project>,
     scroll_handle: UniformListScrollHandle,
@@ -789,6 +806,7 @@ impl GitPanel {
                 amend_pending: false,
                 original_commit_message: None,
                 signoff_enabled: false,
+                sign_commits: None,
                 pending_serialization: Task::ready(()),
                 single_staged_entry: None,
                 single_tracked_entry: None,
@@ -2112,14 +2130,7 @@ impl GitPanel {
         }
 
         if commit_editor_focus_handle.contains_focused(window, cx) {
-            self.commit_changes(
-                CommitOptions {
-                    amend: false,
-                    signoff: self.signoff_enabled,
-                },
-                window,
-                cx,
-            );
+            self.commit_changes(self.commit_options(false, cx), window, cx);
             true
         } else {
             cx.propagate();
@@ -2152,14 +2163,7 @@ impl GitPanel {
 
                     return false;
                 } else {
-                    self.commit_changes(
-                        CommitOptions {
-                            amend: true,
-                            signoff: self.signoff_enabled,
-                        },
-                        window,
-                        cx,
-                    );
+                    self.commit_changes(self.commit_options(true, cx), window, cx);
 
                     return true;
                 }
@@ -2292,6 +2296,7 @@ impl GitPanel {
             self.fill_co_authors(&mut message, cx);
         }
 
+        let amend = options.amend;
         let task = if self.has_staged_changes() {
             // Repository serializes all git operations, so we can just send a commit immediately
             let commit_task = active_repository.update(cx, |repo, cx| {
@@ -2307,7 +2312,7 @@ impl GitPanel {
                 .map(|status_entry| status_entry.repo_path.clone())
                 .collect::<Vec<_>>();
 
-            if changed_files.is_empty() && !options.amend {
+            if changed_files.is_empty() && !amend {
                 error_spawn("No changes to commit", window, cx);
                 return;
             }
@@ -2329,7 +2334,7 @@ impl GitPanel {
 
                 match result {
                     Ok(()) => {
-                        if options.amend {
+                        if amend {
                             this.set_amend_pending(false, cx);
                         } else {
                             this.commit_editor
@@ -2346,6 +2351,51 @@ impl GitPanel {
         self.pending_commit = Some(task);
     }
 
+    pub(crate) fn rebase_control(
+        &mut self,
+        control: RebaseControl,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        if repo.read(cx).rebase.is_none() {
+            return;
+        }
+
+        let confirmation = if control == RebaseControl::Abort {
+            cx.background_spawn(window.prompt(
+                PromptLevel::Warning,
+                "Abort the rebase in progress?",
+                Some("The branch will be restored to its state before the rebase started."),
+                &["Abort Rebase", "Cancel"],
+                cx,
+            ))
+        } else {
+            Task::ready(Ok(0))
+        };
+
+        cx.spawn(async move |this, cx| {
+            if !matches!(confirmation.await, Ok(0)) {
+                return;
+            }
+            let result = async {
+                repo.update(cx, |repo, _| repo.rebase_control(control))
+                    .await?
+            }
+            .await;
+            this.update(cx, |this, cx| {
+                if let Err(e) = result {
+                    this.show_error_toast(format!("rebase {}", control.as_arg()), e, cx);
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
     pub(crate) fn uncommit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let Some(repo) = self.active_repository.clone() else {
             return;
@@ -2735,6 +2785,7 @@ impl GitPanel {
                     temperature,
                     thinking_allowed: false,
                     thinking_effort: None,
+                    credential_profile: None,
                 };
 
                 let stream = model.stream_completion_text(request, cx);
@@ -3103,6 +3154,115 @@ impl GitPanel {
         .detach_and_log_err(cx);
     }
 
+    /// Pushes a tag to a remote picked by the user.
+    pub(crate) fn push_tag(
+        &mut self,
+        tag: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_push_and_pull(cx) {
+            return;
+        }
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let remote = self.get_remote(true, true, window, cx);
+
+        cx.spawn_in(window, async move |this, cx| {
+            let remote = match remote.await {
+                Ok(Some(remote)) => remote,
+                Ok(None) => {
+                    return Ok(());
+                }
+                Err(e) => {
+                    log::error!("Failed to get current remote: {}", e);
+                    this.update(cx, |this, cx| this.show_error_toast("push", e, cx))
+                        .ok();
+                    return Ok(());
+                }
+            };
+
+            let askpass_delegate = this.update_in(cx, |this, window, cx| {
+                this.askpass_delegate(format!("git push {}", remote.name), window, cx)
+            })?;
+
+            let tag_ref: SharedString = format!("refs/tags/{tag}").into();
+            let push = repo.update(cx, |repo, cx| {
+                repo.push(
+                    tag_ref.clone(),
+                    tag_ref,
+                    remote.name.clone(),
+                    None,
+                    askpass_delegate,
+                    cx,
+                )
+            });
+
+            let remote_output = push.await?;
+
+            let action = RemoteAction::Push(format!("tag {tag}").into(), remote);
+            this.update(cx, |this, cx| match remote_output {
+                Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
+                Err(e) => {
+                    log::error!("Error while pushing tag {:?}", e);
+                    this.show_error_toast(action.name(), e, cx)
+                }
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    pub(crate) fn submodule_command(
+        &mut self,
+        command: SubmoduleCommand,
+        paths: Vec<RepoPath>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        self.submodule_command_in_repository(repo, command, paths, window, cx);
+    }
+
+    pub(crate) fn submodule_command_in_repository(
+        &mut self,
+        repo: Entity<Repository>,
+        command: SubmoduleCommand,
+        paths: Vec<RepoPath>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_push_and_pull(cx) {
+            return;
+        }
+        let askpass =
+            self.askpass_delegate(format!("git submodule {}", command.as_arg()), window, cx);
+
+        cx.spawn(async move |this, cx| {
+            let remote_output = repo
+                .update(cx, |repo, cx| {
+                    repo.submodule_command(command, paths, askpass, cx)
+                })
+                .await?;
+
+            let action = RemoteAction::Submodule(command);
+            this.update(cx, |this, cx| match remote_output {
+                Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
+                Err(e) => {
+                    log::error!("Error while running {}: {:?}", action.name(), e);
+                    this.show_error_toast(action.name(), e, cx)
+                }
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn create_pull_request(&self, window: &mut Window, cx: &mut Context<Self>) {
         let result = (|| -> anyhow::Result<()> {
             let repo = self
@@ -3918,6 +4078,7 @@ impl GitPanel {
         let has_unstaged_changes = self.has_unstaged_changes();
         let has_new_changes = self.new_count > 0;
         let has_stash_items = self.stash_entries.entries.len() > 0;
+        let active_repository = self.active_repository.clone();
 
         PopoverMenu::new(id.into())
             .trigger(
@@ -3935,6 +4096,9 @@ impl GitPanel {
                         has_new_changes,
                         sort_by_path: GitPanelSettings::get_global(cx).sort_by_path,
                         has_stash_items,
+                        has_submodules: active_repository
+                            .as_ref()
+                            .is_some_and(|repo| !repo.read(cx).submodules.is_empty()),
                         tree_view: GitPanelSettings::get_global(cx).tree_view,
                     },
                     window,
@@ -4078,6 +4242,7 @@ impl GitPanel {
                 let has_previous_commit = self.head_commit(cx).is_some();
                 let amend = self.amend_pending();
                 let signoff = self.signoff_enabled;
+                let sign = self.sign_commits_enabled(cx);
 
                 move |window, cx| {
                     Some(ContextMenu::build(window, cx, |context_menu, _, _| {
@@ -4110,6 +4275,13 @@ impl GitPanel {
                                 Some(Box::new(Signoff)),
                                 move |window, cx| window.dispatch_action(Box::new(Signoff), cx),
                             )
+                            .toggleable_entry(
+                                "Sign",
+                                sign,
+                                IconPosition::Start,
+                                Some(Box::new(SignCommit)),
+                                move |window, cx| window.dispatch_action(Box::new(SignCommit), cx),
+                            )
                     }))
                 }
             })
@@ -4382,6 +4554,8 @@ impl GitPanel {
         let commit_tooltip_focus_handle = self.commit_editor.focus_handle(cx);
         let amend = self.amend_pending();
         let signoff = self.signoff_enabled;
+        let sign = self.sign_commits_enabled(cx);
+        let options = self.commit_options(amend, cx);
 
         let label_color = if self.pending_commit.is_some() {
             Color::Disabled
@@ -4414,11 +4588,7 @@ impl GitPanel {
                         telemetry::event!("Git Committed", source = "Git Panel");
                         git_panel
                             .update(cx, |git_panel, cx| {
-                                git_panel.commit_changes(
-                                    CommitOptions { amend, signoff },
-                                    window,
-                                    cx,
-                                );
+                                git_panel.commit_changes(options.clone(), window, cx);
                             })
                             .ok();
                     }
@@ -4432,9 +4602,10 @@ impl GitPanel {
                                 tooltip,
                                 Some(if amend { &git::Amend } else { &git::Commit }),
                                 format!(
-                                    "git commit{}{}",
+                                    "git commit{}{}{}",
                                     if amend { " --amend" } else { "" },
-                                    if signoff { " --signoff" } else { "" }
+                                    if signoff { " --signoff" } else { "" },
+                                    if sign { " --gpg-sign" } else { "" }
                                 ),
                                 &handle.clone(),
                                 cx,
@@ -4453,6 +4624,191 @@ impl GitPanel {
             ))
     }
 
+    fn render_rebase_progress(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let repo = self.active_repository.as_ref()?.read(cx);
+        let progress = repo.rebase.as_ref()?;
+        let has_conflicts = self.conflicted_count > 0;
+
+        let mut message = match &progress.head_name {
+            Some(branch) => format!("Rebasing {branch}"),
+            None => "Rebasing".to_string(),
+        };
+        if progress.total > 0 {
+            message.push_str(&format!(" ({}/{})", progress.step, progress.total));
+        }
+        let detail = if has_conflicts {
+            Some("Resolve conflicts, then continue".to_string())
+        } else {
+            progress
+                .stopped_sha
+                .as_ref()
+                .map(|sha| format!("Stopped at {}", &sha[..sha.len().min(SHORT_SHA_LENGTH)]))
+        };
+
+        Some(
+            h_flex()
+                .py_1p5()
+                .px_2()
+                .gap_1p5()
+                .justify_between()
+                .border_t_1()
+                .border_color(cx.theme().colors().border.opacity(0.8))
+                .child(
+                    v_flex()
+                        .flex_grow()
+                        .overflow_hidden()
+                        .child(Label::new(message).size(LabelSize::Small).truncate())
+                        .children(detail.map(|detail| {
+                            Label::new(detail)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .truncate()
+                        })),
+                )
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(panel_button("Abort").on_click(cx.listener(
+                            |this, _, window, cx| {
+                                this.rebase_control(RebaseControl::Abort, window, cx)
+                            },
+                        )))
+                        .child(
+                            panel_button("Skip").on_click(cx.listener(|this, _, window, cx| {
+                                this.rebase_control(RebaseControl::Skip, window, cx)
+                            })),
+                        )
+                        .child(
+                            panel_filled_button("Continue")
+                                .disabled(has_conflicts)
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.rebase_control(RebaseControl::Continue, window, cx)
+                                })),
+                        ),
+                ),
+        )
+    }
+
+    fn render_bisect_progress(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let active_repository = self.active_repository.as_ref()?;
+        let repo = active_repository.read(cx);
+        let state = repo.bisect.as_ref()?;
+        let short_sha = |sha: &str| sha[..sha.len().min(SHORT_SHA_LENGTH)].to_string();
+
+        let (message, detail) = if let Some(first_bad) = &state.first_bad {
+            (
+                format!("Bisect found {}", short_sha(first_bad)),
+                "The first bad commit".to_string(),
+            )
+        } else if state.bad.is_none() {
+            ("Bisecting".to_string(), "Mark a bad commit".to_string())
+        } else if state.good.is_empty() {
+            ("Bisecting".to_string(), "Mark a good commit".to_string())
+        } else {
+            let testing = repo
+                .head_commit
+                .as_ref()
+                .map(|commit| short_sha(&commit.sha))
+                .unwrap_or_default();
+            (
+                format!("Bisecting: testing {testing}"),
+                format!("{} good, {} skipped", state.good.len(), state.skipped.len()),
+            )
+        };
+
+        let buttons = if let Some(first_bad) = state.first_bad.clone() {
+            let workspace = self.workspace.clone();
+            let repo = active_repository.downgrade();
+            h_flex()
+                .gap_1()
+                .child(panel_button("View Commit").on_click(move |_, window, cx| {
+                    CommitView::open(
+                        first_bad.to_string(),
+                        repo.clone(),
+                        workspace.clone(),
+                        None,
+                        None,
+                        window,
+                        cx,
+                    );
+                }))
+                .child(
+                    panel_filled_button("Done")
+                        .tooltip(Tooltip::for_action_title(
+                            "End the bisect and return to where it started",
+                            &git::BisectReset,
+                        ))
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(git::BisectReset.boxed_clone(), cx)
+                        }),
+                )
+        } else {
+            h_flex()
+                .gap_1()
+                .child(
+                    panel_button("End")
+                        .tooltip(Tooltip::for_action_title("End Bisect", &git::BisectReset))
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(git::BisectReset.boxed_clone(), cx)
+                        }),
+                )
+                .child(
+                    panel_button("Run…")
+                        .tooltip(Tooltip::for_action_title(
+                            "Test each commit with a task",
+                            &git::BisectRun,
+                        ))
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(git::BisectRun.boxed_clone(), cx)
+                        }),
+                )
+                .child(
+                    panel_button("Skip")
+                        .tooltip(Tooltip::for_action_title("Skip Commit", &git::BisectSkip))
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(git::BisectSkip.boxed_clone(), cx)
+                        }),
+                )
+                .child(
+                    panel_button("Good")
+                        .tooltip(Tooltip::for_action_title("Mark as Good", &git::BisectGood))
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(git::BisectGood.boxed_clone(), cx)
+                        }),
+                )
+                .child(
+                    panel_filled_button("Bad")
+                        .tooltip(Tooltip::for_action_title("Mark as Bad", &git::BisectBad))
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(git::BisectBad.boxed_clone(), cx)
+                        }),
+                )
+        };
+
+        Some(
+            h_flex()
+                .py_1p5()
+                .px_2()
+                .gap_1p5()
+                .justify_between()
+                .border_t_1()
+                .border_color(cx.theme().colors().border.opacity(0.8))
+                .child(
+                    v_flex()
+                        .flex_grow()
+                        .overflow_hidden()
+                        .child(Label::new(message).size(LabelSize::Small).truncate())
+                        .child(
+                            Label::new(detail)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .truncate(),
+                        ),
+                )
+                .child(buttons),
+        )
+    }
+
     fn render_pending_amend(&self, cx: &mut Context<Self>) -> impl IntoElement {
         h_flex()
             .py_1p5()
@@ -4962,6 +5318,10 @@ impl GitPanel {
                 has_new_changes: self.new_count > 0,
                 sort_by_path: GitPanelSettings::get_global(cx).sort_by_path,
                 has_stash_items: self.stash_entries.entries.len() > 0,
+                has_submodules: self
+                    .active_repository
+                    .as_ref()
+                    .is_some_and(|repo| !repo.read(cx).submodules.is_empty()),
                 tree_view: GitPanelSettings::get_global(cx).tree_view,
             },
             window,
@@ -5108,7 +5468,31 @@ impl GitPanel {
                         status.is_deleted(),
                     ))
                 }
-            });
+            })
+            .when_some(
+                repo.submodule_for_path(&entry.repo_path),
+                |this, submodule| {
+                    this.child(
+                        h_flex()
+                            .flex_none()
+                            .gap_0p5()
+                            .child(
+                                Icon::new(IconName::Box)
+                                    .size(IconSize::XSmall)
+                                    .color(Color::Muted),
+                            )
+                            .child(
+                                Label::new(format!(
+                                    "{} · {}",
+                                    submodule.short_sha(),
+                                    submodule.status.label()
+                                ))
+                                .size(LabelSize::XSmall)
+                                .color(Color::Muted),
+                            ),
+                    )
+                },
+            );
 
         h_flex()
             .id(id)
@@ -5435,6 +5819,59 @@ impl GitPanel {
         self.set_signoff_enabled(!self.signoff_enabled, cx);
     }
 
+    /// Whether the next commit will be signed. When neither this panel nor the
+    /// settings decide, the repository's `commit.gpgSign` config does.
+    pub fn sign_commits_enabled(&self, cx: &App) -> bool {
+        self.sign_commits
+            .or(self.commit_signing_settings(cx).enabled)
+            .unwrap_or_else(|| {
+                self.active_repository
+                    .as_ref()
+                    .is_some_and(|repository| repository.read(cx).signs_commits_by_default)
+            })
+    }
+
+    /// The commit signing settings for the active repository's worktree.
+    fn commit_signing_settings<'a>(&self, cx: &'a App) -> &'a CommitSigningSettingsContent {
+        let project_path = self.active_repository.as_ref().and_then(|repository| {
+            repository
+                .read(cx)
+                .repo_path_to_project_path(&RepoPath::from_rel_path(RelPath::empty()), cx)
+        });
+        let location = project_path.as_ref().map(|project_path| SettingsLocation {
+            worktree_id: project_path.worktree_id,
+            path: &project_path.path,
+        });
+        &ProjectSettings::get(location, cx).git.commit_signing
+    }
+
+    pub fn toggle_sign_commits(
+        &mut self,
+        _: &SignCommit,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.sign_commits = Some(!self.sign_commits_enabled(cx));
+        cx.notify();
+    }
+
+    pub(crate) fn commit_options(&self, amend: bool, cx: &App) -> CommitOptions {
+        let signing = self.commit_signing_settings(cx);
+        CommitOptions {
+            amend,
+            signoff: self.signoff_enabled,
+            signing: CommitSigning {
+                enabled: self.sign_commits.or(signing.enabled),
+                format: signing.format.map(|format| match format {
+                    CommitSigningFormat::Openpgp => SigningFormat::OpenPgp,
+                    CommitSigningFormat::Ssh => SigningFormat::Ssh,
+                    CommitSigningFormat::X509 => SigningFormat::X509,
+                }),
+                key: signing.key.clone(),
+            },
+        }
+    }
+
     pub async fn load(
         workspace: WeakEntity<Workspace>,
         mut cx: AsyncWindowContext,
@@ -5545,6 +5982,7 @@ impl Render for GitPanel {
                     .on_action(cx.listener(GitPanel::on_commit))
                     .on_action(cx.listener(GitPanel::on_amend))
                     .on_action(cx.listener(GitPanel::toggle_signoff_enabled))
+                    .on_action(cx.listener(GitPanel::toggle_sign_commits))
                     .on_action(cx.listener(Self::stage_all))
                     .on_action(cx.listener(Self::unstage_all))
                     .on_action(cx.listener(Self::stage_selected))
@@ -5595,6 +6033,8 @@ impl Render for GitPanel {
                             this.child(self.render_empty_state(cx).into_any_element())
                         }
                     })
+                    .children(self.render_rebase_progress(cx))
+                    .children(self.render_bisect_progress(cx))
                     .children(self.render_footer(window, cx))
                     .when(self.amend_pending, |this| {
                         this.child(self.render_pending_amend(cx))
@@ -5876,7 +6316,13 @@ impl RenderOnce for PanelRepoFooter {
 
         let single_repo = project
             .as_ref()
-            .map(|project| project.read(cx).git_store().read(cx).repositories().len() == 1)
+            .map(|project| {
+                let repositories = project.read(cx).git_store().read(cx).repositories();
+                repositories.len() == 1
+                    && repositories
+                        .values()
+                        .all(|repository| repository.read(cx).submodules.is_empty())
+            })
             .unwrap_or(true);
 
         const MAX_BRANCH_LEN: usize = 16;
@@ -12,8 +12,11 @@ mod blame_ui;
 pub mod clone;
 
 use git::{
+    bisect::BisectMark,
+    rebase::RebaseControl,
     repository::{Branch, Upstream, UpstreamTracking, UpstreamTrackingStatus},
     status::{FileStatus, StatusCode, UnmergedStatus, UnmergedStatusCode},
+    submodule::SubmoduleCommand,
 };
 use gpui::{
     App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, SharedString, Window,
@@ -25,14 +28,16 @@ use ui::prelude::*;
 use workspace::{ModalView, Workspace, notifications::DetachAndPromptErr};
 use zed_actions;
 
-use crate::{git_panel::GitPanel, text_diff_view::TextDiffView};
+use crate::{git_panel::GitPanel, rebase_view::RebaseView, text_diff_view::TextDiffView};
 
 mod askpass_modal;
+pub mod bisect;
 pub mod branch_picker;
 mod commit_modal;
 pub mod commit_tooltip;
 pub mod commit_view;
 mod conflict_view;
+pub mod create_ref_modal;
 pub mod file_diff_view;
 pub mod file_history_view;
 pub mod git_panel;
@@ -41,9 +46,13 @@ pub mod git_picker;
 pub mod multi_diff_view;
 pub mod picker_prompt;
 pub mod project_diff;
+pub mod pull_request_view;
+pub mod rebase_view;
+pub mod reflog_picker;
 pub(crate) mod remote_output;
 pub mod repository_selector;
 pub mod stash_picker;
+pub mod tag_picker;
 pub mod text_diff_view;
 pub mod worktree_picker;
 
@@ -118,6 +127,30 @@ pub fn init(cx: &mut App) {
                     panel.push(true, false, window, cx);
                 });
             });
+            workspace.register_action(|workspace, _: &git::InitSubmodules, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.submodule_command(SubmoduleCommand::Init, Vec::new(), window, cx);
+                });
+            });
+            workspace.register_action(|workspace, _: &git::UpdateSubmodules, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.submodule_command(SubmoduleCommand::Update, Vec::new(), window, cx);
+                });
+            });
+            workspace.register_action(|workspace, _: &git::SyncSubmodules, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.submodule_command(SubmoduleCommand::Sync, Vec::new(), window, cx);
+                });
+            });
             workspace.register_action(|workspace, _: &git::Pull, window, cx| {
                 let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
                     return;
@@ -183,6 +216,79 @@ pub fn init(cx: &mut App) {
                 panel.uncommit(window, cx);
             })
         });
+        workspace.register_action(|workspace, _: &git::RebaseContinue, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.rebase_control(RebaseControl::Continue, window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::RebaseSkip, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.rebase_control(RebaseControl::Skip, window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::RebaseAbort, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.rebase_control(RebaseControl::Abort, window, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::BisectGood, window, cx| {
+            if let Some(repository) = workspace.project().read(cx).active_repository(cx) {
+                bisect::mark(
+                    repository,
+                    BisectMark::Good,
+                    None,
+                    cx.weak_entity(),
+                    window,
+                    cx,
+                );
+            }
+        });
+        workspace.register_action(|workspace, _: &git::BisectBad, window, cx| {
+            if let Some(repository) = workspace.project().read(cx).active_repository(cx) {
+                bisect::mark(
+                    repository,
+                    BisectMark::Bad,
+                    None,
+                    cx.weak_entity(),
+                    window,
+                    cx,
+                );
+            }
+        });
+        workspace.register_action(|workspace, _: &git::BisectSkip, window, cx| {
+            if let Some(repository) = workspace.project().read(cx).active_repository(cx) {
+                bisect::mark(
+                    repository,
+                    BisectMark::Skip,
+                    None,
+                    cx.weak_entity(),
+                    window,
+                    cx,
+                );
+            }
+        });
+        workspace.register_action(|workspace, _: &git::BisectReset, window, cx| {
+            if let Some(repository) = workspace.project().read(cx).active_repository(cx) {
+                bisect::reset(repository, window, cx);
+            }
+        });
+        workspace.register_action(|workspace, _: &git::BisectRun, window, cx| {
+            if let Some(repository) = workspace.project().read(cx).active_repository(cx) {
+                bisect::run_with_task(repository, workspace, window, cx);
+            }
+        });
+        workspace.register_action(|workspace, _: &git::InteractiveRebase, window, cx| {
+            RebaseView::open(None, workspace, window, cx);
+        });
         workspace.register_action(|workspace, _action: &git::Init, window, cx| {
             let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
                 return;
@@ -206,6 +312,9 @@ pub fn init(cx: &mut App) {
         workspace.register_action(|workspace, _: &git::RenameBranch, window, cx| {
             rename_current_branch(workspace, window, cx);
         });
+        workspace.register_action(|workspace, _: &git::ReviewPullRequest, window, cx| {
+            pull_request_view::review_pull_request(workspace, window, cx);
+        });
         workspace.register_action(
             |workspace, action: &DiffClipboardWithSelectionData, window, cx| {
                 if let Some(task) = TextDiffView::open(action, workspace, window, cx) {
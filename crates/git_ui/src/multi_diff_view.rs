@@ -5,7 +5,7 @@ use gpui::{
     AnyElement, App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, FocusHandle,
     Focusable, IntoElement, Render, SharedString, Task, Window,
 };
-use language::{Buffer, Capability, OffsetRangeExt};
+use language::{Buffer, Capability, OffsetRangeExt, Point};
 use multi_buffer::PathKey;
 use project::Project;
 use std::{
@@ -215,7 +215,7 @@ impl MultiDiffView {
         })
     }
 
-    fn new(
+    pub(crate) fn new(
         multibuffer: Entity<MultiBuffer>,
         project: Entity<Project>,
         file_count: usize,
@@ -238,6 +238,42 @@ impl MultiDiffView {
         Self { editor, file_count }
     }
 
+    pub(crate) fn editor(&self) -> &Entity<Editor> {
+        &self.editor
+    }
+
+    /// Adds the changes of a file to the view. Files without changes, like renamed ones, are shown
+    /// whole.
+    pub(crate) fn add_file(
+        &mut self,
+        path_key: PathKey,
+        buffer: Entity<Buffer>,
+        diff: Entity<BufferDiff>,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = buffer.read(cx).snapshot();
+        let diff_snapshot = diff.read(cx).snapshot(cx);
+        let mut ranges = diff_snapshot
+            .hunks(&snapshot)
+            .map(|hunk| hunk.buffer_range.to_point(&snapshot))
+            .collect::<Vec<_>>();
+        if ranges.is_empty() {
+            ranges.push(Point::zero()..snapshot.max_point());
+        }
+
+        let context_lines = multibuffer_context_lines(cx);
+        self.editor
+            .read(cx)
+            .buffer()
+            .clone()
+            .update(cx, |multibuffer, cx| {
+                multibuffer.set_excerpts_for_path(path_key, buffer, ranges, context_lines, cx);
+                multibuffer.add_diff(diff, cx);
+            });
+        self.file_count += 1;
+        cx.notify();
+    }
+
     fn title(&self) -> SharedString {
         let suffix = if self.file_count == 1 {
             "1 file".to_string()
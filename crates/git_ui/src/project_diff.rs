@@ -18,7 +18,7 @@ use editor::{
 use git::repository::DiffType;
 
 use git::{
-    Commit, StageAll, StageAndNext, ToggleStaged, UnstageAll, UnstageAndNext,
+    Commit, SHORT_SHA_LENGTH, StageAll, StageAndNext, ToggleStaged, UnstageAll, UnstageAndNext,
     repository::{Branch, RepoPath, Upstream, UpstreamTracking, UpstreamTrackingStatus},
     status::FileStatus,
 };
@@ -264,6 +264,32 @@ impl ProjectDiff {
         }
     }
 
+    /// Opens a diff of the working tree against the commit `sha` of the active repository.
+    pub fn deploy_against_commit(
+        workspace: &mut Workspace,
+        sha: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let diff_base = DiffBase::Commit { sha };
+        let existing = workspace
+            .items_of_type::<Self>(cx)
+            .find(|item| item.read(cx).diff_base(cx) == &diff_base);
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, true, true, window, cx);
+            return;
+        }
+
+        let project = workspace.project().clone();
+        let workspace_handle = cx.entity();
+        let project_diff = cx.new(|cx| {
+            let branch_diff =
+                cx.new(|cx| branch_diff::BranchDiff::new(diff_base, project.clone(), window, cx));
+            Self::new_impl(branch_diff, project, workspace_handle, window, cx)
+        });
+        workspace.add_item_to_active_pane(Box::new(project_diff), None, true, window, cx);
+    }
+
     pub fn deploy_at_project_path(
         workspace: &mut Workspace,
         project_path: ProjectPath,
@@ -370,10 +396,11 @@ impl ProjectDiff {
             );
             match branch_diff.read(cx).diff_base() {
                 DiffBase::Head => {}
-                DiffBase::Merge { .. } => diff_display_editor.set_render_diff_hunk_controls(
-                    Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
-                    cx,
-                ),
+                DiffBase::Merge { .. } | DiffBase::Commit { .. } => diff_display_editor
+                    .set_render_diff_hunk_controls(
+                        Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+                        cx,
+                    ),
             }
             diff_display_editor.rhs_editor().update(cx, |editor, cx| {
                 editor.disable_diagnostics(cx);
@@ -385,7 +412,7 @@ impl ProjectDiff {
                             workspace: workspace.downgrade(),
                         });
                     }
-                    DiffBase::Merge { .. } => {
+                    DiffBase::Merge { .. } | DiffBase::Commit { .. } => {
                         editor.register_addon(BranchDiffAddon {
                             branch_diff: branch_diff.clone(),
                         });
@@ -984,6 +1011,7 @@ impl Item for ProjectDiff {
         match self.diff_base(cx) {
             DiffBase::Head => Some("Project Diff".into()),
             DiffBase::Merge { .. } => Some("Branch Diff".into()),
+            DiffBase::Commit { sha } => Some(format!("Changes since commit {sha}").into()),
         }
     }
 
@@ -1001,6 +1029,9 @@ impl Item for ProjectDiff {
         match self.branch_diff.read(cx).diff_base() {
             DiffBase::Head => "Uncommitted Changes".into(),
             DiffBase::Merge { base_ref } => format!("Changes since {}", base_ref).into(),
+            DiffBase::Commit { sha } => {
+                format!("Changes since {}", &sha[..SHORT_SHA_LENGTH.min(sha.len())]).into()
+            }
         }
     }
 
@@ -1991,9 +2022,15 @@ impl Addon for BranchDiffAddon {
 mod tests {
     use collections::HashMap;
     use db::indoc;
-    use editor::test::editor_test_context::{EditorTestContext, assert_state_with_diff};
-    use git::status::{TrackedStatus, UnmergedStatus, UnmergedStatusCode};
-    use gpui::TestAppContext;
+    use editor::{
+        MultiBufferOffset,
+        test::editor_test_context::{EditorTestContext, assert_state_with_diff},
+    };
+    use git::{
+        repository::repo_path,
+        status::{TrackedStatus, UnmergedStatus, UnmergedStatusCode},
+    };
+    use gpui::{TestAppContext, VisualTestContext};
     use project::FakeFs;
     use serde_json::json;
     use settings::{DiffViewStyle, SettingsStore};
@@ -2684,6 +2721,97 @@ mod tests {
         cx.run_until_parked();
     }
 
+    #[gpui::test]
+    async fn test_stage_selection_in_split_diff(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(|cx| {
+            cx.update_global::<SettingsStore, _>(|store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings.editor.diff_view_style = Some(DiffViewStyle::Split);
+                });
+            });
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "foo.txt": "one\nfour\nfive\nsix\n",
+            }),
+        )
+        .await;
+        fs.set_head_and_index_for_repo(
+            path!("/project/.git").as_ref(),
+            &[("foo.txt", "one\ntwo\nthree\nfour\n".into())],
+        );
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+        let diff = cx.new_window_entity(|window, cx| {
+            ProjectDiff::new(project.clone(), workspace, window, cx)
+        });
+        cx.run_until_parked();
+
+        let (lhs_editor, rhs_editor) = diff.read_with(cx, |diff, cx| {
+            let editor = diff.editor.read(cx);
+            (
+                editor.lhs_editor().cloned().expect("diff should be split"),
+                editor.rhs_editor().clone(),
+            )
+        });
+        let index_text = || {
+            fs.with_git_state(path!("/project/.git").as_ref(), false, |state| {
+                state.index_contents.get(&repo_path("foo.txt")).cloned()
+            })
+            .unwrap()
+        };
+        let select_text = |editor: &Entity<Editor>, text: &str, cx: &mut VisualTestContext| {
+            editor.update_in(cx, |editor, window, cx| {
+                let start = editor
+                    .buffer()
+                    .read(cx)
+                    .snapshot(cx)
+                    .text()
+                    .find(text)
+                    .expect("text should be in the editor");
+                editor.change_selections(Default::default(), window, cx, |s| {
+                    s.select_ranges([
+                        MultiBufferOffset(start)..MultiBufferOffset(start + text.len())
+                    ]);
+                });
+            });
+        };
+
+        // Staging part of the deleted lines from the left-hand side only removes those lines.
+        select_text(&lhs_editor, "two\n", cx);
+        lhs_editor.update_in(cx, |editor, window, cx| {
+            editor.stage_selection(&git::StageSelection, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(index_text().as_deref(), Some("one\nthree\nfour\n"));
+
+        // Staging part of the added lines from the right-hand side only adds those lines.
+        select_text(&rhs_editor, "five\n", cx);
+        rhs_editor.update_in(cx, |editor, window, cx| {
+            editor.stage_selection(&git::StageSelection, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(index_text().as_deref(), Some("one\nthree\nfour\nfive\n"));
+
+        // Unstaging the staged deletion from the left-hand side restores the line.
+        select_text(&lhs_editor, "two\n", cx);
+        lhs_editor.update_in(cx, |editor, window, cx| {
+            editor.unstage_selection(&git::UnstageSelection, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            index_text().as_deref(),
+            Some("one\ntwo\nthree\nfour\nfive\n")
+        );
+    }
+
     #[gpui::test]
     async fn test_new_hunk_in_modified_file(cx: &mut TestAppContext) {
         init_test(cx);
@@ -0,0 +1,1101 @@
+use anyhow::{Context as _, Result};
+use buffer_diff::BufferDiff;
+use collections::{HashMap, HashSet};
+use editor::display_map::{
+    BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId,
+};
+use editor::{Editor, EditorEvent, MultiBuffer};
+use git::repository::{RepoPath, is_binary_content};
+use git::{
+    GitHostingProviderRegistry, GitRemote, NewReviewComment, PullRequestSummary, ReviewComment,
+    ReviewCommentSide, parse_git_remote_url,
+};
+use gpui::{
+    AnyElement, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    IntoElement, Render, SharedString, Task, WeakEntity, Window,
+};
+use language::{Buffer, Capability, Point, ToPoint as _};
+use multi_buffer::PathKey;
+use project::{Project, git_store::Repository};
+use std::{
+    any::{Any, TypeId},
+    sync::Arc,
+};
+use theme::ActiveTheme;
+use ui::{Tooltip, prelude::*};
+use util::{paths::PathStyle, truncate_and_trailoff};
+use workspace::{
+    Item, ItemNavHistory, Workspace,
+    item::{ItemEvent, TabContentParams},
+    notifications::DetachAndPromptErr,
+    searchable::SearchableItemHandle,
+};
+
+use crate::commit_view::{GitBlob, build_buffer, build_buffer_diff};
+use crate::multi_diff_view::MultiDiffView;
+use crate::picker_prompt;
+
+/// Lets the user pick one of the active repository's open pull requests and opens it for review.
+pub fn review_pull_request(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+        return;
+    };
+    let remote = repository_remote(&repository, cx);
+    let http_client = cx.http_client();
+    let workspace_handle = cx.weak_entity();
+
+    cx.spawn_in(window, async move |workspace, cx| {
+        let remote = remote.context("The repository has no remote on a known hosting provider.")?;
+        anyhow::ensure!(
+            remote.host.supports_pull_request_reviews(),
+            "{} does not support pull request reviews.",
+            remote.host.name()
+        );
+        let mut pull_requests = remote
+            .host
+            .list_pull_requests(&remote.parsed_remote(), http_client)
+            .await?;
+        anyhow::ensure!(
+            !pull_requests.is_empty(),
+            "There are no open pull requests."
+        );
+
+        let labels = pull_requests
+            .iter()
+            .map(|pull_request| {
+                format!(
+                    "#{} {} ({})",
+                    pull_request.number, pull_request.title, pull_request.author
+                )
+                .into()
+            })
+            .collect();
+        let selection = workspace.update_in(cx, |_, window, cx| {
+            picker_prompt::prompt("Review Pull Request", labels, workspace_handle, window, cx)
+        })?;
+        let Some(ix) = selection.await else {
+            return anyhow::Ok(());
+        };
+        let pull_request = pull_requests.swap_remove(ix);
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            PullRequestView::open(pull_request, remote, repository, workspace, window, cx);
+        })
+    })
+    .detach_and_prompt_err("Failed to load pull requests", window, cx, |_, _, _| None);
+}
+
+fn repository_remote(repository: &Entity<Repository>, cx: &App) -> Option<GitRemote> {
+    let snapshot = repository.read(cx).snapshot();
+    let remote_url = snapshot
+        .remote_upstream_url
+        .as_ref()
+        .or(snapshot.remote_origin_url.as_ref())?;
+    let provider_registry = GitHostingProviderRegistry::default_global(cx);
+    let (host, parsed) = parse_git_remote_url(provider_registry, remote_url)?;
+    Some(GitRemote {
+        host,
+        owner: parsed.owner.into(),
+        repo: parsed.repo.into(),
+    })
+}
+
+/// One file of a pull request's diff.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct FilePatch {
+    path: String,
+    /// The path the file had before the pull request, if it was renamed.
+    old_path: Option<String>,
+    is_created: bool,
+    is_deleted: bool,
+    is_binary: bool,
+    /// The file's hunks, preceded by `---` and `+++` headers.
+    patch: String,
+    has_hunks: bool,
+}
+
+/// Splits a unified diff of several files, as produced by `git diff`, into one patch per file.
+fn split_diff(diff: &str) -> Vec<FilePatch> {
+    let mut files = Vec::new();
+    let mut current: Option<FilePatch> = None;
+    let mut in_hunks = false;
+
+    for line in diff.lines() {
+        if let Some(paths) = line.strip_prefix("diff --git ") {
+            files.extend(current.take());
+            in_hunks = false;
+            let path = paths
+                .rsplit_once(" b/")
+                .map_or(paths, |(_, new_path)| new_path);
+            current = Some(FilePatch {
+                path: path.to_string(),
+                ..Default::default()
+            });
+            continue;
+        }
+        let Some(file) = current.as_mut() else {
+            continue;
+        };
+
+        if in_hunks {
+            if line.starts_with([' ', '+', '-', '\\']) || line.starts_with("@@") {
+                file.patch.push_str(line);
+                file.patch.push('\n');
+            }
+        } else if line.starts_with("@@") {
+            in_hunks = true;
+            file.has_hunks = true;
+            let old_path = file.old_path.as_ref().unwrap_or(&file.path);
+            file.patch = format!("--- a/{old_path}\n+++ b/{}\n{line}\n", file.path);
+        } else if line.starts_with("new file mode") {
+            file.is_created = true;
+        } else if line.starts_with("deleted file mode") {
+            file.is_deleted = true;
+        } else if line.starts_with("Binary files") || line.starts_with("GIT binary patch") {
+            file.is_binary = true;
+        } else if let Some(path) = line.strip_prefix("rename from ") {
+            file.old_path = Some(path.to_string());
+        } else if let Some(path) = line.strip_prefix("rename to ") {
+            file.path = path.to_string();
+        } else if let Some(path) = line.strip_prefix("+++ b/") {
+            file.path = path.to_string();
+        } else if let Some(path) = line.strip_prefix("--- a/")
+            && file.is_deleted
+        {
+            file.path = path.to_string();
+        }
+    }
+    files.extend(current);
+    files
+}
+
+struct ReviewFile {
+    old_path: Option<String>,
+    buffer: Entity<Buffer>,
+    diff: Entity<BufferDiff>,
+}
+
+#[derive(Clone)]
+struct ReviewThread {
+    id: String,
+    path: String,
+    line: u32,
+    side: ReviewCommentSide,
+    comments: Vec<ReviewComment>,
+}
+
+impl ReviewThread {
+    fn height(&self) -> u32 {
+        let comment_lines = self
+            .comments
+            .iter()
+            .map(|comment| comment.body.lines().count().max(1) as u32 + 1)
+            .sum::<u32>();
+        comment_lines + 3
+    }
+}
+
+/// Shows a pull request's changes with its review threads, and posts new review comments.
+pub struct PullRequestView {
+    pull_request: PullRequestSummary,
+    remote: GitRemote,
+    repository: Entity<Repository>,
+    diff_view: Entity<MultiDiffView>,
+    editor: Entity<Editor>,
+    multibuffer: Entity<MultiBuffer>,
+    /// The pull request's files, keyed by their path after it.
+    files: HashMap<String, ReviewFile>,
+    threads: Vec<ReviewThread>,
+    thread_blocks: HashSet<CustomBlockId>,
+    reply_editors: HashMap<String, Entity<Editor>>,
+    pending_comment_count: usize,
+    _subscription: gpui::Subscription,
+}
+
+impl PullRequestView {
+    fn open(
+        pull_request: PullRequestSummary,
+        remote: GitRemote,
+        repository: Entity<Repository>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let existing = workspace
+            .active_pane()
+            .read(cx)
+            .items()
+            .filter_map(|item| item.downcast::<PullRequestView>())
+            .find(|view| view.read(cx).pull_request.url == pull_request.url);
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, true, true, window, cx);
+            return;
+        }
+
+        let project = workspace.project().clone();
+        let view = cx
+            .new(|cx| PullRequestView::new(pull_request, remote, repository, project, window, cx));
+        workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+    }
+
+    fn new(
+        pull_request: PullRequestSummary,
+        remote: GitRemote,
+        repository: Entity<Repository>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::new(Capability::ReadOnly);
+            multibuffer.set_all_diff_hunks_expanded(cx);
+            multibuffer
+        });
+        let diff_view =
+            cx.new(|cx| MultiDiffView::new(multibuffer.clone(), project.clone(), 0, window, cx));
+        let editor = diff_view.read(cx).editor().clone();
+        editor.update(cx, |editor, cx| {
+            editor.disable_inline_diagnostics();
+            editor.set_show_breakpoints(false, cx);
+            editor.set_show_diff_review_button(true, cx);
+        });
+        let subscription = cx.subscribe(&editor, |this, _, event: &EditorEvent, cx| {
+            if let EditorEvent::ReviewCommentsChanged { total_count } = event {
+                this.pending_comment_count = *total_count;
+                cx.notify();
+            }
+        });
+
+        cx.spawn_in(window, async move |this, cx| {
+            Self::load_files(this.clone(), &project, cx).await?;
+            this.update_in(cx, |this, window, cx| this.reload_threads(window, cx))?
+                .await
+        })
+        .detach_and_prompt_err("Failed to load pull request", window, cx, |_, _, _| None);
+
+        Self {
+            pull_request,
+            remote,
+            repository,
+            diff_view,
+            editor,
+            multibuffer,
+            files: HashMap::default(),
+            threads: Vec::new(),
+            thread_blocks: HashSet::default(),
+            reply_editors: HashMap::default(),
+            pending_comment_count: 0,
+            _subscription: subscription,
+        }
+    }
+
+    async fn load_files(
+        this: WeakEntity<Self>,
+        project: &Entity<Project>,
+        cx: &mut gpui::AsyncWindowContext,
+    ) -> Result<()> {
+        let (host, remote, pull_request, repository, http_client) =
+            this.update(cx, |this, cx| {
+                (
+                    this.remote.host.clone(),
+                    this.remote.parsed_remote(),
+                    this.pull_request.clone(),
+                    this.repository.clone(),
+                    cx.http_client(),
+                )
+            })?;
+        let (language_registry, first_worktree_id) = project.read_with(cx, |project, cx| {
+            (
+                project.languages().clone(),
+                project
+                    .worktrees(cx)
+                    .next()
+                    .map(|worktree| worktree.read(cx).id()),
+            )
+        });
+
+        let diff = host
+            .pull_request_diff(&remote, pull_request.number, http_client.clone())
+            .await?;
+
+        for file in split_diff(&diff) {
+            let new_text = if file.is_deleted || file.is_binary {
+                String::new()
+            } else {
+                host.file_at_commit(
+                    &remote,
+                    &file.path,
+                    &pull_request.head_sha,
+                    http_client.clone(),
+                )
+                .await?
+            };
+            let is_binary = file.is_binary || is_binary_content(new_text.as_bytes());
+            let old_text = if file.is_created || is_binary {
+                None
+            } else if file.has_hunks {
+                Some(
+                    language::apply_reversed_diff_patch(&new_text, &file.patch)
+                        .with_context(|| format!("failed to apply the diff of {}", file.path))?,
+                )
+            } else {
+                Some(new_text.clone())
+            };
+            let new_text = if is_binary {
+                "(binary file not shown)".to_string()
+            } else {
+                new_text
+            };
+
+            let path = RepoPath::new(&file.path)?;
+            let worktree_id = repository
+                .update(cx, |repository, cx| {
+                    repository
+                        .repo_path_to_project_path(&path, cx)
+                        .map(|path| path.worktree_id)
+                        .or(first_worktree_id)
+                })
+                .context("project has no worktrees")?;
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| path.display(PathStyle::local()).to_string());
+            let blob = Arc::new(GitBlob {
+                path: path.clone(),
+                is_deleted: file.is_deleted,
+                is_binary,
+                worktree_id,
+                display_name: match &file.old_path {
+                    Some(old_path) => {
+                        format!("#{} - {old_path} → {file_name}", pull_request.number)
+                    }
+                    None => format!("#{} - {file_name}", pull_request.number),
+                },
+            }) as Arc<dyn language::File>;
+
+            let buffer = build_buffer(new_text, blob, &language_registry, cx).await?;
+            let buffer_diff = build_buffer_diff(old_text, &buffer, &language_registry, cx).await?;
+
+            this.update(cx, |this, cx| {
+                let path = buffer.read(cx).file().unwrap().path().clone();
+                this.diff_view.update(cx, |diff_view, cx| {
+                    diff_view.add_file(
+                        PathKey::with_sort_prefix(0, path),
+                        buffer.clone(),
+                        buffer_diff.clone(),
+                        cx,
+                    )
+                });
+                this.files.insert(
+                    file.path,
+                    ReviewFile {
+                        old_path: file.old_path,
+                        buffer,
+                        diff: buffer_diff,
+                    },
+                );
+            })?;
+        }
+        Ok(())
+    }
+
+    fn reload_threads(&mut self, window: &mut Window, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let host = self.remote.host.clone();
+        let remote = self.remote.parsed_remote();
+        let number = self.pull_request.number;
+        let http_client = cx.http_client();
+        cx.spawn_in(window, async move |this, cx| {
+            let comments = host
+                .pull_request_review_comments(&remote, number, http_client)
+                .await?;
+            this.update_in(cx, |this, window, cx| {
+                this.set_threads(comments, window, cx)
+            })
+        })
+    }
+
+    fn set_threads(
+        &mut self,
+        comments: Vec<ReviewComment>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let mut threads: Vec<ReviewThread> = Vec::new();
+        for comment in comments {
+            if let Some(thread) = threads
+                .iter_mut()
+                .find(|thread| thread.id == comment.thread_id)
+            {
+                thread.comments.push(comment);
+                continue;
+            }
+            // Threads on lines that no longer exist in the pull request are outdated.
+            let Some(line) = comment.line else {
+                continue;
+            };
+            threads.push(ReviewThread {
+                id: comment.thread_id.clone(),
+                path: comment.path.clone(),
+                line,
+                side: comment.side,
+                comments: vec![comment],
+            });
+        }
+
+        let this = cx.weak_entity();
+        let mut blocks = Vec::new();
+        for thread in &threads {
+            let Some(anchor) = self.thread_anchor(thread, cx) else {
+                continue;
+            };
+            let reply_editor = self.reply_editor(&thread.id, window, cx);
+            let thread = thread.clone();
+            let this = this.clone();
+            blocks.push(BlockProperties {
+                placement: BlockPlacement::Below(anchor),
+                height: Some(thread.height()),
+                style: BlockStyle::Flex,
+                render: Arc::new(move |cx| {
+                    render_thread(&thread, reply_editor.clone(), this.clone(), cx)
+                }),
+                priority: 0,
+            });
+        }
+
+        let old_blocks = std::mem::take(&mut self.thread_blocks);
+        self.thread_blocks = self.editor.update(cx, |editor, cx| {
+            editor.remove_blocks(old_blocks, None, cx);
+            editor.insert_blocks(blocks, None, cx).into_iter().collect()
+        });
+        self.reply_editors
+            .retain(|id, _| threads.iter().any(|thread| &thread.id == id));
+        self.threads = threads;
+        cx.notify();
+    }
+
+    /// Returns the position after the line a thread is on, in the deleted text when the thread is
+    /// on the old side of the diff.
+    fn thread_anchor(&self, thread: &ReviewThread, cx: &App) -> Option<editor::Anchor> {
+        // Some hosts name renamed files by their old path in threads on the old side.
+        let file = self.files.get(&thread.path).or_else(|| {
+            self.files
+                .values()
+                .find(|file| file.old_path.as_ref() == Some(&thread.path))
+        })?;
+        let buffer = file.buffer.read(cx).snapshot();
+        let multibuffer = self.multibuffer.read(cx);
+        let row = thread.line.checked_sub(1)?;
+        match thread.side {
+            ReviewCommentSide::New => {
+                if row > buffer.max_point().row {
+                    return None;
+                }
+                let point = Point::new(row, buffer.line_len(row));
+                multibuffer.buffer_anchor_to_anchor(&file.buffer, buffer.anchor_after(point), cx)
+            }
+            ReviewCommentSide::Old => {
+                let diff = file.diff.read(cx).snapshot(cx);
+                let base_text = diff.base_text();
+                if row > base_text.max_point().row {
+                    return None;
+                }
+                let base_point = Point::new(row, base_text.line_len(row));
+                let buffer_point = diff.base_text_point_to_buffer_point(base_point, &buffer);
+                multibuffer
+                    .buffer_anchor_to_anchor(&file.buffer, buffer.anchor_before(buffer_point), cx)
+                    .map(|anchor| anchor.with_diff_base_anchor(base_text.anchor_after(base_point)))
+            }
+        }
+    }
+
+    fn reply_editor(
+        &mut self,
+        thread_id: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<Editor> {
+        self.reply_editors
+            .entry(thread_id.to_string())
+            .or_insert_with(|| {
+                cx.new(|cx| {
+                    let mut editor = Editor::auto_height(1, 4, window, cx);
+                    editor.set_placeholder_text("Reply…", window, cx);
+                    editor
+                })
+            })
+            .clone()
+    }
+
+    fn reply(&mut self, thread_id: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(thread), Some(editor)) = (
+            self.threads.iter().find(|thread| thread.id == thread_id),
+            self.reply_editors.get(thread_id),
+        ) else {
+            return;
+        };
+        let body = editor.read(cx).text(cx).trim().to_string();
+        if body.is_empty() {
+            return;
+        }
+        editor.update(cx, |editor, cx| editor.clear(window, cx));
+        let comment = NewReviewComment {
+            body,
+            path: thread.path.clone(),
+            line: thread.line,
+            side: thread.side,
+            thread_id: Some(thread.id.clone()),
+        };
+        self.post_comments(vec![comment], window, cx);
+    }
+
+    fn submit_review(
+        &mut self,
+        _: &git::SubmitPullRequestReview,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let review_comments = self
+            .editor
+            .update(cx, |editor, cx| editor.take_all_review_comments(cx));
+        let snapshot = self.multibuffer.read(cx).snapshot(cx);
+        let comments = review_comments
+            .into_iter()
+            .flat_map(|(_, comments)| comments)
+            .filter_map(|comment| {
+                let anchor = comment.range.end;
+                let buffer = snapshot.buffer_for_excerpt(anchor.excerpt_id)?;
+                let path = buffer.file()?.path().as_unix_str().to_string();
+                let (row, side) = match anchor.diff_base_anchor {
+                    Some(base_anchor) => {
+                        let diff = snapshot.diff_for_buffer_id(buffer.remote_id())?;
+                        let row = base_anchor.to_point(diff.base_text()).row;
+                        (row, ReviewCommentSide::Old)
+                    }
+                    None => (
+                        anchor.text_anchor.to_point(buffer).row,
+                        ReviewCommentSide::New,
+                    ),
+                };
+                Some(NewReviewComment {
+                    body: comment.comment,
+                    path,
+                    line: row + 1,
+                    side,
+                    thread_id: None,
+                })
+            })
+            .collect::<Vec<_>>();
+        if !comments.is_empty() {
+            self.post_comments(comments, window, cx);
+        }
+    }
+
+    fn post_comments(
+        &mut self,
+        comments: Vec<NewReviewComment>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let host = self.remote.host.clone();
+        let remote = self.remote.parsed_remote();
+        let pull_request = self.pull_request.clone();
+        let http_client = cx.http_client();
+        cx.spawn_in(window, async move |this, cx| {
+            for comment in comments {
+                host.post_pull_request_review_comment(
+                    &remote,
+                    &pull_request,
+                    comment,
+                    http_client.clone(),
+                )
+                .await?;
+            }
+            this.update_in(cx, |this, window, cx| this.reload_threads(window, cx))?
+                .await
+        })
+        .detach_and_prompt_err("Failed to post review comments", window, cx, |_, _, _| None);
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let pull_request = &self.pull_request;
+        let url = pull_request.url.to_string();
+        let comment_count = self.pending_comment_count;
+
+        h_flex()
+            .px_4()
+            .py_3()
+            .w_full()
+            .gap_4()
+            .justify_between()
+            .flex_wrap()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                v_flex()
+                    .child(Label::new(format!(
+                        "#{} {}",
+                        pull_request.number, pull_request.title
+                    )))
+                    .child(
+                        Label::new(format!(
+                            "{} wants to merge {} into {}",
+                            pull_request.author,
+                            pull_request.source_branch,
+                            pull_request.target_branch
+                        ))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new("open-in-browser", "Open in Browser")
+                            .icon(IconName::ArrowUpRight)
+                            .icon_size(IconSize::Small)
+                            .icon_color(Color::Muted)
+                            .icon_position(IconPosition::Start)
+                            .on_click(move |_, _, cx| cx.open_url(&url)),
+                    )
+                    .child(
+                        Button::new(
+                            "submit-review",
+                            match comment_count {
+                                1 => "Submit 1 Comment".to_string(),
+                                count => format!("Submit {count} Comments"),
+                            },
+                        )
+                        .style(ButtonStyle::Filled)
+                        .disabled(comment_count == 0)
+                        .tooltip(Tooltip::text(format!(
+                            "Post your comments to {}",
+                            self.remote.host.name()
+                        )))
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(Box::new(git::SubmitPullRequestReview), cx)
+                        }),
+                    ),
+            )
+    }
+}
+
+fn render_thread(
+    thread: &ReviewThread,
+    reply_editor: Entity<Editor>,
+    view: WeakEntity<PullRequestView>,
+    cx: &mut BlockContext,
+) -> AnyElement {
+    let colors = cx.theme().colors();
+    let thread_id = thread.id.clone();
+
+    v_flex()
+        .id(cx.block_id)
+        .ml(cx.margins.gutter.width)
+        .mr_4()
+        .my_1()
+        .px_2()
+        .py_1()
+        .gap_1()
+        .border_1()
+        .border_color(colors.border_variant)
+        .rounded_sm()
+        .bg(colors.editor_background)
+        .children(thread.comments.iter().map(|comment| {
+            v_flex()
+                .child(
+                    Label::new(comment.author.clone())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .child(div().text_ui_sm(cx).child(comment.body.clone()))
+        }))
+        .child(
+            h_flex()
+                .gap_1()
+                .child(div().flex_1().child(reply_editor))
+                .child(
+                    Button::new("reply", "Reply")
+                        .label_size(LabelSize::Small)
+                        .on_click(move |_, window, cx| {
+                            view.update(cx, |view, cx| view.reply(&thread_id, window, cx))
+                                .ok();
+                        }),
+                ),
+        )
+        .into_any_element()
+}
+
+impl EventEmitter<EditorEvent> for PullRequestView {}
+
+impl Focusable for PullRequestView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for PullRequestView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::PullRequest).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        let title = truncate_and_trailoff(&self.pull_request.title, 20);
+        format!("#{} — {title}", self.pull_request.number).into()
+    }
+
+    fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
+        Some(self.pull_request.title.clone().into())
+    }
+
+    fn to_item_events(event: &EditorEvent, f: &mut dyn FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Pull Request View Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<gpui::AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.clone().into())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>, _: &App) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn navigate(
+        &mut self,
+        data: Arc<dyn Any + Send>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+}
+
+impl Render for PullRequestView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("PullRequestView")
+            .on_action(cx.listener(Self::submit_review))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(self.render_header(cx))
+            .child(div().flex_grow().child(self.diff_view.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use git_hosting_providers::Github;
+    use gpui::TestAppContext;
+    use http_client::FakeHttpClient;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    use super::*;
+
+    #[test]
+    fn test_split_diff() {
+        let diff = indoc! {"
+            diff --git a/src/main.rs b/src/main.rs
+            index 1111111..2222222 100644
+            --- a/src/main.rs
+            +++ b/src/main.rs
+            @@ -1,2 +1,2 @@
+             fn main() {
+            -    println!(\"hello\");
+            +    println!(\"goodbye\");
+            diff --git a/notes.txt b/notes.txt
+            deleted file mode 100644
+            index 3333333..0000000
+            --- a/notes.txt
+            +++ /dev/null
+            @@ -1 +0,0 @@
+            -remember
+            diff --git a/old name.md b/new name.md
+            similarity index 100%
+            rename from old name.md
+            rename to new name.md
+            diff --git a/src/lib.rs b/src/core.rs
+            similarity index 80%
+            rename from src/lib.rs
+            rename to src/core.rs
+            index 5555555..6666666 100644
+            --- a/src/lib.rs
+            +++ b/src/core.rs
+            @@ -1 +1 @@
+            -mod a;
+            +mod b;
+            diff --git a/logo.png b/logo.png
+            new file mode 100644
+            index 0000000..4444444
+            Binary files /dev/null and b/logo.png differ
+        "};
+
+        assert_eq!(
+            split_diff(diff),
+            vec![
+                FilePatch {
+                    path: "src/main.rs".into(),
+                    patch: indoc! {"
+                        --- a/src/main.rs
+                        +++ b/src/main.rs
+                        @@ -1,2 +1,2 @@
+                         fn main() {
+                        -    println!(\"hello\");
+                        +    println!(\"goodbye\");
+                    "}
+                    .into(),
+                    has_hunks: true,
+                    ..Default::default()
+                },
+                FilePatch {
+                    path: "notes.txt".into(),
+                    is_deleted: true,
+                    patch: indoc! {"
+                        --- a/notes.txt
+                        +++ b/notes.txt
+                        @@ -1 +0,0 @@
+                        -remember
+                    "}
+                    .into(),
+                    has_hunks: true,
+                    ..Default::default()
+                },
+                FilePatch {
+                    path: "new name.md".into(),
+                    old_path: Some("old name.md".into()),
+                    ..Default::default()
+                },
+                FilePatch {
+                    path: "src/core.rs".into(),
+                    old_path: Some("src/lib.rs".into()),
+                    patch: indoc! {"
+                        --- a/src/lib.rs
+                        +++ b/src/core.rs
+                        @@ -1 +1 @@
+                        -mod a;
+                        +mod b;
+                    "}
+                    .into(),
+                    has_hunks: true,
+                    ..Default::default()
+                },
+                FilePatch {
+                    path: "logo.png".into(),
+                    is_created: true,
+                    is_binary: true,
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_pull_request_view(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+            crate::init(cx);
+            cx.set_http_client(FakeHttpClient::create(|request| async move {
+                let body = match request.uri().to_string().as_str() {
+                    "https://api.github.com/repos/zed-industries/zed/pulls/42" => indoc! {"
+                        diff --git a/src/main.rs b/src/main.rs
+                        index 1111111..2222222 100644
+                        --- a/src/main.rs
+                        +++ b/src/main.rs
+                        @@ -1,3 +1,3 @@
+                         fn main() {
+                        -    println!(\"hello\");
+                        +    println!(\"goodbye\");
+                         }
+                        diff --git a/src/lib.rs b/src/core.rs
+                        similarity index 80%
+                        rename from src/lib.rs
+                        rename to src/core.rs
+                        index 3333333..4444444 100644
+                        --- a/src/lib.rs
+                        +++ b/src/core.rs
+                        @@ -1,2 +1,2 @@
+                        -mod a;
+                        +mod b;
+                         mod c;
+                    "}
+                    .to_string(),
+                    "https://api.github.com/repos/zed-industries/zed/contents/src/main.rs?ref=abc123" => {
+                        "fn main() {\n    println!(\"goodbye\");\n}\n".to_string()
+                    }
+                    "https://api.github.com/repos/zed-industries/zed/contents/src/core.rs?ref=abc123" => {
+                        "mod b;\nmod c;\n".to_string()
+                    }
+                    "https://api.github.com/repos/zed-industries/zed/pulls/42/comments?per_page=100&page=1" => {
+                        json!([
+                            {
+                                "id": 1,
+                                "user": { "login": "octocat" },
+                                "body": "Why not hello?",
+                                "path": "src/main.rs",
+                                "line": 2,
+                                "side": "RIGHT",
+                            },
+                            {
+                                "id": 2,
+                                "in_reply_to_id": 1,
+                                "user": { "login": "hubot" },
+                                "body": "Because.",
+                                "path": "src/main.rs",
+                                "line": 2,
+                                "side": "RIGHT",
+                            },
+                            {
+                                "id": 3,
+                                "user": { "login": "octocat" },
+                                "body": "Is a still used?",
+                                "path": "src/core.rs",
+                                "line": 1,
+                                "side": "LEFT",
+                            },
+                        ])
+                        .to_string()
+                    }
+                    uri => panic!("unexpected request to {uri}"),
+                };
+                Ok(http_client::Response::new(body.into()))
+            }));
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "src": { "main.rs": "", "lib.rs": "" },
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+        cx.run_until_parked();
+        let repository = project
+            .read_with(cx, |project, cx| project.active_repository(cx))
+            .unwrap();
+
+        let pull_request = PullRequestSummary {
+            number: 42,
+            title: "Say goodbye".into(),
+            author: "octocat".into(),
+            url: "https://github.com/zed-industries/zed/pull/42"
+                .parse()
+                .unwrap(),
+            source_branch: "goodbye".into(),
+            target_branch: "main".into(),
+            head_sha: "abc123".into(),
+        };
+        let remote = GitRemote {
+            host: Arc::new(Github::public_instance()),
+            owner: "zed-industries".into(),
+            repo: "zed".into(),
+        };
+        workspace.update_in(cx, |workspace, window, cx| {
+            PullRequestView::open(pull_request, remote, repository, workspace, window, cx)
+        });
+        cx.run_until_parked();
+
+        let view = workspace.read_with(cx, |workspace, cx| {
+            workspace.active_item_as::<PullRequestView>(cx).unwrap()
+        });
+        view.read_with(cx, |view, cx| {
+            let main = &view.files["src/main.rs"];
+            assert_eq!(main.old_path, None);
+            assert_eq!(
+                main.diff.read(cx).snapshot(cx).base_text().text(),
+                "fn main() {\n    println!(\"hello\");\n}\n"
+            );
+            let core = &view.files["src/core.rs"];
+            assert_eq!(core.old_path.as_deref(), Some("src/lib.rs"));
+            assert_eq!(
+                core.diff.read(cx).snapshot(cx).base_text().text(),
+                "mod a;\nmod c;\n"
+            );
+            assert_eq!(view.editor.read(cx).text(cx).matches("goodbye").count(), 1);
+
+            assert_eq!(
+                view.threads
+                    .iter()
+                    .map(|thread| (thread.path.as_str(), thread.side, thread.comments.len()))
+                    .collect::<Vec<_>>(),
+                [
+                    ("src/main.rs", ReviewCommentSide::New, 2),
+                    ("src/core.rs", ReviewCommentSide::Old, 1),
+                ]
+            );
+            assert_eq!(view.thread_blocks.len(), 2);
+        });
+    }
+}
@@ -0,0 +1,630 @@
+use anyhow::Result;
+use editor::{Editor, EditorEvent};
+use git::{
+    SHORT_SHA_LENGTH,
+    rebase::{RebaseAction, RebasePlan, RebaseTodoEntry},
+};
+use gpui::{
+    AnyElement, AnyEntity, App, Context, Entity, EventEmitter, FocusHandle, Focusable, IntoElement,
+    Render, ScrollStrategy, Subscription, Task, UniformListScrollHandle, WeakEntity, Window,
+    actions, uniform_list,
+};
+use project::{Project, ProjectPath, git_store::Repository};
+use std::any::{Any, TypeId};
+use std::sync::Arc;
+use ui::{KeyBinding, ListItem, WithScrollbar, prelude::*};
+use util::ResultExt;
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, SaveOptions},
+    notifications::DetachAndPromptErr,
+};
+
+actions!(
+    rebase_view,
+    [
+        /// Replays the selected commit unchanged.
+        PickCommit,
+        /// Replays the selected commit with a new message.
+        RewordCommit,
+        /// Stops the rebase after the selected commit so it can be amended.
+        EditCommit,
+        /// Melds the selected commit into the previous one, combining their messages.
+        SquashCommit,
+        /// Melds the selected commit into the previous one, keeping the previous message.
+        FixupCommit,
+        /// Removes the selected commit.
+        DropCommit,
+        /// Moves the selected commit earlier in the history.
+        MoveCommitUp,
+        /// Moves the selected commit later in the history.
+        MoveCommitDown,
+        /// Starts the rebase with the edited todo list.
+        StartRebase,
+    ]
+);
+
+/// An editable todo list for an interactive rebase of the active repository.
+pub struct RebaseView {
+    repository: WeakEntity<Repository>,
+    base: SharedString,
+    entries: Vec<RebaseTodoEntry>,
+    selected: usize,
+    message_editor: Entity<Editor>,
+    error: Option<SharedString>,
+    scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+    running: Option<Task<()>>,
+    _message_subscription: Subscription,
+}
+
+impl RebaseView {
+    /// Opens the todo list for rebasing the commits after `base`, which defaults to the
+    /// merge base with the upstream branch, or with the default branch.
+    pub fn open(
+        base: Option<String>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+            return;
+        };
+        let already_rebasing = repository.read(cx).snapshot().rebase.is_some();
+        let plan = repository.update(cx, |repository, _| repository.rebase_plan(base));
+
+        cx.spawn_in(window, async move |workspace, cx| {
+            anyhow::ensure!(
+                !already_rebasing,
+                "A rebase is already in progress. Continue or abort it first."
+            );
+            let plan = plan.await??;
+            anyhow::ensure!(!plan.entries.is_empty(), "There are no commits to rebase.");
+            workspace.update_in(cx, |workspace, window, cx| {
+                let view = cx.new(|cx| RebaseView::new(plan, repository.downgrade(), window, cx));
+                workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+            })
+        })
+        .detach_and_prompt_err(
+            "Failed to start interactive rebase",
+            window,
+            cx,
+            |_, _, _| None,
+        );
+    }
+
+    fn new(
+        plan: RebasePlan,
+        repository: WeakEntity<Repository>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let message_editor = cx.new(|cx| {
+            let mut editor = Editor::auto_height(3, 12, window, cx);
+            editor.set_placeholder_text("Commit message", window, cx);
+            editor
+        });
+        let message_subscription =
+            cx.subscribe(&message_editor, |this, editor, event: &EditorEvent, cx| {
+                if !matches!(event, EditorEvent::BufferEdited) {
+                    return;
+                }
+                let message = editor.read(cx).text(cx);
+                if let Some(entry) = this
+                    .entries
+                    .get_mut(this.selected)
+                    .filter(|entry| entry.action == RebaseAction::Reword)
+                {
+                    // An empty message keeps the original one.
+                    entry.message = Some(message.trim())
+                        .filter(|message| !message.is_empty())
+                        .map(|message| message.to_string().into());
+                }
+            });
+
+        Self {
+            repository,
+            base: plan.base,
+            entries: plan.entries,
+            selected: 0,
+            message_editor,
+            error: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+            running: None,
+            _message_subscription: message_subscription,
+        }
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected + 1 < self.entries.len() {
+            self.select_ix(self.selected + 1, window, cx);
+        }
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.selected > 0 {
+            self.select_ix(self.selected - 1, window, cx);
+        }
+    }
+
+    fn select_first(&mut self, _: &menu::SelectFirst, window: &mut Window, cx: &mut Context<Self>) {
+        self.select_ix(0, window, cx);
+    }
+
+    fn select_last(&mut self, _: &menu::SelectLast, window: &mut Window, cx: &mut Context<Self>) {
+        self.select_ix(self.entries.len().saturating_sub(1), window, cx);
+    }
+
+    fn select_ix(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.selected = ix;
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Nearest);
+        self.sync_message_editor(window, cx);
+        cx.notify();
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        window.focus(&self.focus_handle, cx);
+    }
+
+    fn pick(&mut self, _: &PickCommit, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseAction::Pick, window, cx);
+    }
+
+    fn reword(&mut self, _: &RewordCommit, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseAction::Reword, window, cx);
+    }
+
+    fn edit(&mut self, _: &EditCommit, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseAction::Edit, window, cx);
+    }
+
+    fn squash(&mut self, _: &SquashCommit, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseAction::Squash, window, cx);
+    }
+
+    fn fixup(&mut self, _: &FixupCommit, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseAction::Fixup, window, cx);
+    }
+
+    fn drop_commit(&mut self, _: &DropCommit, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(RebaseAction::Drop, window, cx);
+    }
+
+    fn move_up(&mut self, _: &MoveCommitUp, window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected > 0 {
+            self.entries.swap(self.selected, self.selected - 1);
+            self.select_ix(self.selected - 1, window, cx);
+        }
+    }
+
+    fn move_down(&mut self, _: &MoveCommitDown, window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected + 1 < self.entries.len() {
+            self.entries.swap(self.selected, self.selected + 1);
+            self.select_ix(self.selected + 1, window, cx);
+        }
+    }
+
+    fn set_action(&mut self, action: RebaseAction, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get_mut(self.selected) else {
+            return;
+        };
+        entry.action = action;
+        let sha_to_load = entry.message.is_none().then(|| entry.sha.clone());
+        self.error = None;
+        if action == RebaseAction::Reword {
+            if let Some(sha) = sha_to_load {
+                self.load_message(sha, window, cx);
+            }
+            self.sync_message_editor(window, cx);
+            window.focus(&self.message_editor.focus_handle(cx), cx);
+        }
+        cx.notify();
+    }
+
+    /// Fills in the full message of the commit being reworded, so that it can be edited
+    /// rather than written from scratch.
+    fn load_message(&mut self, sha: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(repository) = self.repository.upgrade() else {
+            return;
+        };
+        let details = repository.update(cx, |repository, _| repository.show(sha.to_string()));
+        cx.spawn_in(window, async move |this, cx| {
+            let details = details.await??;
+            this.update_in(cx, |this, window, cx| {
+                if let Some(entry) = this
+                    .entries
+                    .iter_mut()
+                    .find(|entry| entry.sha == sha && entry.message.is_none())
+                {
+                    entry.message = Some(details.message.trim().to_string().into());
+                    this.sync_message_editor(window, cx);
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn sync_message_editor(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let message = self
+            .entries
+            .get(self.selected)
+            .filter(|entry| entry.action == RebaseAction::Reword)
+            .and_then(|entry| entry.message.clone())
+            .unwrap_or_default();
+        self.message_editor.update(cx, |editor, cx| {
+            if editor.text(cx) != message.as_ref() {
+                editor.set_text(message.as_ref(), window, cx);
+            }
+        });
+    }
+
+    fn start_rebase(&mut self, _: &StartRebase, window: &mut Window, cx: &mut Context<Self>) {
+        if self.running.is_some() {
+            return;
+        }
+        if let Some(first) = self
+            .entries
+            .iter()
+            .find(|entry| entry.action != RebaseAction::Drop)
+            && first.action.melds_into_previous()
+        {
+            self.error = Some(
+                format!(
+                    "Cannot {} {}, there is no earlier commit to meld it into.",
+                    first.action.as_str(),
+                    &first.sha[..SHORT_SHA_LENGTH.min(first.sha.len())]
+                )
+                .into(),
+            );
+            cx.notify();
+            return;
+        }
+        let Some(repository) = self.repository.upgrade() else {
+            return;
+        };
+
+        let rebase = repository.update(cx, |repository, _| {
+            repository.rebase_interactive(self.base.to_string(), self.entries.clone())
+        });
+        self.error = None;
+        self.running = Some(cx.spawn_in(window, async move |this, cx| {
+            let result = rebase.await;
+            this.update(cx, |this, cx| {
+                this.running = None;
+                match result {
+                    // The rebase may have stopped for conflicts or an `edit` step, in which case
+                    // the git panel takes over.
+                    Ok(Ok(())) => cx.emit(ItemEvent::CloseItem),
+                    Ok(Err(error)) => this.error = Some(format!("{error:#}").into()),
+                    Err(_) => this.error = Some("The rebase was canceled".into()),
+                }
+                cx.notify();
+            })
+            .log_err();
+        }));
+        cx.notify();
+    }
+
+    fn render_entry(
+        &self,
+        ix: usize,
+        entry: &RebaseTodoEntry,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let dropped = entry.action == RebaseAction::Drop;
+        let action_color = match entry.action {
+            RebaseAction::Pick => Color::Muted,
+            RebaseAction::Reword | RebaseAction::Edit => Color::Accent,
+            RebaseAction::Squash | RebaseAction::Fixup => Color::Warning,
+            RebaseAction::Drop => Color::Error,
+        };
+        let subject = entry
+            .message
+            .as_ref()
+            .filter(|_| entry.action == RebaseAction::Reword)
+            .and_then(|message| message.lines().next())
+            .map(SharedString::new)
+            .unwrap_or_else(|| entry.subject.clone());
+
+        ListItem::new(("rebase-entry", ix))
+            .toggle_state(ix == self.selected)
+            .child(
+                h_flex()
+                    .h_8()
+                    .w_full()
+                    .px_2()
+                    .gap_2()
+                    .child(
+                        div().w(rems_from_px(52.)).flex_none().child(
+                            Label::new(entry.action.as_str())
+                                .size(LabelSize::Small)
+                                .color(action_color),
+                        ),
+                    )
+                    .child(
+                        Label::new(entry.sha[..SHORT_SHA_LENGTH.min(entry.sha.len())].to_string())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .buffer_font(cx),
+                    )
+                    .child(
+                        Label::new(subject)
+                            .size(LabelSize::Small)
+                            .when(dropped, |label| {
+                                label.strikethrough().color(Color::Disabled)
+                            })
+                            .truncate(),
+                    ),
+            )
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.select_ix(ix, window, cx);
+                window.focus(&this.focus_handle, cx);
+            }))
+            .into_any_element()
+    }
+
+    fn render_action_buttons(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let selected_action = self.entries.get(self.selected).map(|entry| entry.action);
+        h_flex()
+            .gap_1()
+            .children(RebaseAction::ALL.into_iter().map(|action| {
+                Button::new(action.as_str(), action.as_str())
+                    .label_size(LabelSize::Small)
+                    .toggle_state(selected_action == Some(action))
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.set_action(action, window, cx);
+                    }))
+            }))
+    }
+}
+
+impl EventEmitter<ItemEvent> for RebaseView {}
+
+impl Focusable for RebaseView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for RebaseView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entry_count = self.entries.len();
+        let rewording = self
+            .entries
+            .get(self.selected)
+            .is_some_and(|entry| entry.action == RebaseAction::Reword);
+        let base = self.base[..SHORT_SHA_LENGTH.min(self.base.len())].to_string();
+
+        v_flex()
+            .id("rebase_view")
+            .on_action(cx.listener(Self::start_rebase))
+            .on_action(cx.listener(Self::cancel))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .h(rems_from_px(41.))
+                    .pl_3()
+                    .pr_2()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        Label::new(format!(
+                            "Rebase {entry_count} commits onto {base}, oldest first"
+                        ))
+                        .color(Color::Muted),
+                    )
+                    .child(
+                        Button::new("start-rebase", "Start Rebase")
+                            .disabled(self.running.is_some())
+                            .label_size(LabelSize::Small)
+                            .key_binding(
+                                KeyBinding::for_action_in(&StartRebase, &self.focus_handle, cx)
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.start_rebase(&StartRebase, window, cx);
+                            })),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(self.render_action_buttons(cx)),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    h_flex()
+                        .px_3()
+                        .py_1()
+                        .gap_1()
+                        .child(
+                            Icon::new(IconName::XCircle)
+                                .size(IconSize::Small)
+                                .color(Color::Error),
+                        )
+                        .child(Label::new(error).size(LabelSize::Small).color(Color::Error)),
+                )
+            })
+            .child(
+                v_flex()
+                    .id("rebase-todo-list")
+                    .key_context("RebaseView")
+                    .track_focus(&self.focus_handle)
+                    .on_action(cx.listener(Self::select_next))
+                    .on_action(cx.listener(Self::select_previous))
+                    .on_action(cx.listener(Self::select_first))
+                    .on_action(cx.listener(Self::select_last))
+                    .on_action(cx.listener(Self::pick))
+                    .on_action(cx.listener(Self::reword))
+                    .on_action(cx.listener(Self::edit))
+                    .on_action(cx.listener(Self::squash))
+                    .on_action(cx.listener(Self::fixup))
+                    .on_action(cx.listener(Self::drop_commit))
+                    .on_action(cx.listener(Self::move_up))
+                    .on_action(cx.listener(Self::move_down))
+                    .flex_1()
+                    .size_full()
+                    .child({
+                        let view = cx.weak_entity();
+                        uniform_list("rebase-todo-entries", entry_count, move |range, _, cx| {
+                            let Some(view) = view.upgrade() else {
+                                return Vec::new();
+                            };
+                            view.update(cx, |this, cx| {
+                                range
+                                    .filter_map(|ix| {
+                                        let entry = this.entries.get(ix)?;
+                                        Some(this.render_entry(ix, entry, cx))
+                                    })
+                                    .collect()
+                            })
+                        })
+                        .flex_1()
+                        .size_full()
+                        .track_scroll(&self.scroll_handle)
+                    })
+                    .vertical_scrollbar_for(&self.scroll_handle, window, cx),
+            )
+            .when(rewording, |this| {
+                this.child(
+                    v_flex()
+                        .key_context("RebaseMessage")
+                        .p_2()
+                        .gap_1()
+                        .border_t_1()
+                        .border_color(cx.theme().colors().border_variant)
+                        .child(
+                            Label::new("New commit message")
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .child(self.message_editor.clone()),
+                )
+            })
+    }
+}
+
+impl Item for RebaseView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, f: &mut dyn FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        "Interactive Rebase".into()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        Some(format!("Rebase onto {}", self.base).into())
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("interactive rebase")
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<workspace::WorkspaceId>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Task<Option<Entity<Self>>> {
+        Task::ready(None)
+    }
+
+    fn navigate(
+        &mut self,
+        _: Arc<dyn Any + Send>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> bool {
+        false
+    }
+
+    fn can_save(&self, _: &App) -> bool {
+        false
+    }
+
+    fn save(
+        &mut self,
+        _options: SaveOptions,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn save_as(
+        &mut self,
+        _project: Entity<Project>,
+        _path: ProjectPath,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn reload(
+        &mut self,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn is_dirty(&self, _: &App) -> bool {
+        false
+    }
+
+    fn has_conflict(&self, _: &App) -> bool {
+        false
+    }
+
+    fn breadcrumbs(&self, _cx: &App) -> Option<Vec<workspace::item::BreadcrumbText>> {
+        None
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        _workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.focus_handle, cx);
+    }
+
+    fn pixel_position_of_cursor(&self, _: &App) -> Option<gpui::Point<gpui::Pixels>> {
+        None
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,634 @@
+use fuzzy::StringMatchCandidate;
+
+use git::repository::{ReflogEntry, ResetMode};
+use gpui::{
+    Action, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, ParentElement, PromptLevel, Render, SharedString, Styled,
+    Subscription, Task, WeakEntity, Window, actions,
+};
+use picker::{Picker, PickerDelegate};
+use project::git_store::Repository;
+use std::sync::Arc;
+use time::{OffsetDateTime, UtcOffset};
+use time_format;
+use ui::{
+    ContextMenu, DropdownMenu, DropdownStyle, HighlightedLabel, KeyBinding, ListItem,
+    ListItemSpacing, Tooltip, prelude::*,
+};
+use util::ResultExt;
+use workspace::Workspace;
+use workspace::notifications::DetachAndPromptErr;
+
+use crate::commit_view::CommitView;
+use crate::create_ref_modal::{CreateRefModal, RefKind};
+use crate::reflog_picker;
+
+actions!(
+    reflog_picker,
+    [
+        /// Create a branch at the commit of the selected reflog entry.
+        CreateBranchFromEntry,
+        /// Reset the current branch to the commit of the selected reflog entry.
+        ResetToEntry,
+    ]
+);
+
+const HEAD: &str = "HEAD";
+
+pub fn create_embedded(
+    repository: Option<Entity<Repository>>,
+    workspace: WeakEntity<Workspace>,
+    width: Rems,
+    window: &mut Window,
+    cx: &mut Context<ReflogList>,
+) -> ReflogList {
+    ReflogList::new_embedded(repository, workspace, width, window, cx)
+}
+
+pub struct ReflogList {
+    width: Rems,
+    pub picker: Entity<Picker<ReflogListDelegate>>,
+    picker_focus_handle: FocusHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ReflogList {
+    fn new_embedded(
+        repository: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        width: Rems,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = ReflogListDelegate::new(repository, workspace, cx);
+        let picker = cx.new(|cx| {
+            Picker::uniform_list(delegate, window, cx)
+                .show_scrollbar(true)
+                .modal(false)
+        });
+        let picker_focus_handle = picker.focus_handle(cx);
+        picker.update(cx, |picker, cx| {
+            picker.delegate.focus_handle = picker_focus_handle.clone();
+            picker.delegate.load_branches(cx);
+            picker.delegate.load_reflog(window, cx);
+        });
+
+        let _subscriptions = vec![cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        })];
+
+        Self {
+            picker,
+            picker_focus_handle,
+            width,
+            _subscriptions,
+        }
+    }
+
+    pub fn handle_create_branch(
+        &mut self,
+        _: &CreateBranchFromEntry,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .create_branch_at(picker.delegate.selected_index(), window, cx);
+        });
+    }
+
+    pub fn handle_reset(&mut self, _: &ResetToEntry, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .reset_to(picker.delegate.selected_index(), window, cx);
+        });
+    }
+}
+
+impl EventEmitter<DismissEvent> for ReflogList {}
+impl Focusable for ReflogList {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.picker_focus_handle.clone()
+    }
+}
+
+impl Render for ReflogList {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("ReflogList")
+            .w(self.width)
+            .on_action(cx.listener(Self::handle_create_branch))
+            .on_action(cx.listener(Self::handle_reset))
+            .child(self.picker.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ReflogMatch {
+    entry: ReflogEntry,
+    positions: Vec<usize>,
+    formatted_timestamp: String,
+}
+
+pub struct ReflogListDelegate {
+    matches: Vec<ReflogMatch>,
+    all_entries: Option<Vec<ReflogEntry>>,
+    /// The ref whose reflog is listed: `HEAD` or a local branch name.
+    reference: SharedString,
+    branches: Vec<SharedString>,
+    repo: Option<Entity<Repository>>,
+    workspace: WeakEntity<Workspace>,
+    selected_index: usize,
+    focus_handle: FocusHandle,
+    timezone: UtcOffset,
+}
+
+impl ReflogListDelegate {
+    fn new(
+        repo: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<ReflogList>,
+    ) -> Self {
+        Self {
+            matches: vec![],
+            all_entries: None,
+            reference: HEAD.into(),
+            branches: Vec::new(),
+            repo,
+            workspace,
+            selected_index: 0,
+            focus_handle: cx.focus_handle(),
+            timezone: UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC),
+        }
+    }
+
+    fn format_timestamp(timestamp: i64, timezone: UtcOffset) -> String {
+        let timestamp =
+            OffsetDateTime::from_unix_timestamp(timestamp).unwrap_or(OffsetDateTime::now_utc());
+        time_format::format_localized_timestamp(
+            timestamp,
+            OffsetDateTime::now_utc(),
+            timezone,
+            time_format::TimestampFormat::Relative,
+        )
+    }
+
+    fn load_reflog(&self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let reflog = repo.update(cx, |repo, _| repo.reflog(self.reference.to_string()));
+        cx.spawn_in(window, async move |picker, cx| {
+            let entries = reflog.await??;
+            picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.all_entries = Some(entries);
+                picker.delegate.selected_index = 0;
+                picker.refresh(window, cx);
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn load_branches(&self, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let branches = repo.update(cx, |repo, _| repo.branches());
+        cx.spawn(async move |picker, cx| {
+            let branches = branches.await??;
+            picker.update(cx, |picker, cx| {
+                picker.delegate.branches = branches
+                    .iter()
+                    .filter(|branch| !branch.is_remote())
+                    .map(|branch| SharedString::from(branch.name().to_string()))
+                    .collect();
+                cx.notify();
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn set_reference(
+        &mut self,
+        reference: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        if self.reference == reference {
+            return;
+        }
+        self.reference = reference;
+        self.all_entries = None;
+        self.matches.clear();
+        self.load_reflog(window, cx);
+        cx.notify();
+    }
+
+    fn entry_at(&self, ix: usize) -> Option<&ReflogEntry> {
+        Some(&self.matches.get(ix)?.entry)
+    }
+
+    fn show_entry_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.entry_at(ix) else {
+            return;
+        };
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        CommitView::open(
+            entry.sha.to_string(),
+            repo.downgrade(),
+            self.workspace.clone(),
+            None,
+            None,
+            window,
+            cx,
+        );
+        cx.emit(DismissEvent);
+    }
+
+    fn checkout_entry_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.entry_at(ix) else {
+            return;
+        };
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let checkout = repo.update(cx, |repo, _| repo.checkout_commit(entry.sha.to_string()));
+        cx.spawn(async move |_, _| checkout.await?)
+            .detach_and_prompt_err("Failed to check out commit", window, cx, |e, _, _| {
+                Some(e.to_string())
+            });
+        cx.emit(DismissEvent);
+    }
+
+    fn create_branch_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.entry_at(ix) else {
+            return;
+        };
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let sha = entry.sha.clone();
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_modal(window, cx, |window, cx| {
+                CreateRefModal::new(RefKind::Branch, sha, repo, window, cx)
+            });
+        });
+    }
+
+    fn reset_to(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.entry_at(ix) else {
+            return;
+        };
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let sha = entry.sha.to_string();
+        let confirmation = window.prompt(
+            PromptLevel::Warning,
+            &format!("Reset the current branch to {}?", entry.selector),
+            Some(
+                "A soft reset keeps the changes from later commits staged, and a mixed reset \
+                 keeps them unstaged. A hard reset discards them along with all uncommitted \
+                 changes.",
+            ),
+            &["Soft", "Mixed", "Hard", "Cancel"],
+            cx,
+        );
+        cx.spawn(async move |picker, cx| {
+            let mode = match confirmation.await {
+                Ok(0) => ResetMode::Soft,
+                Ok(1) => ResetMode::Mixed,
+                Ok(2) => ResetMode::Hard,
+                _ => return Ok(()),
+            };
+            repo.update(cx, |repo, cx| repo.reset(sha, mode, cx))
+                .await??;
+            picker.update(cx, |_, cx| cx.emit(DismissEvent))?;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to reset", window, cx, |e, _, _| Some(e.to_string()));
+    }
+
+    fn render_reference_dropdown(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> impl IntoElement {
+        let picker = cx.weak_entity();
+        let current = self.reference.clone();
+        let references = std::iter::once(SharedString::from(HEAD))
+            .chain(self.branches.iter().cloned())
+            .collect::<Vec<_>>();
+        let menu = ContextMenu::build(window, cx, move |mut menu, _, _| {
+            for reference in references {
+                let picker = picker.clone();
+                menu = menu.toggleable_entry(
+                    reference.clone(),
+                    reference == current,
+                    IconPosition::Start,
+                    None,
+                    move |window, cx| {
+                        picker
+                            .update(cx, |picker, cx| {
+                                picker.delegate.set_reference(reference.clone(), window, cx);
+                            })
+                            .ok();
+                    },
+                );
+            }
+            menu
+        });
+        DropdownMenu::new("reflog-reference", self.reference.clone(), menu)
+            .style(DropdownStyle::Subtle)
+            .trigger_size(ButtonSize::Compact)
+    }
+}
+
+impl PickerDelegate for ReflogListDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Search the reflog…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let Some(all_entries) = self.all_entries.clone() else {
+            return Task::ready(());
+        };
+
+        let timezone = self.timezone;
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let matches: Vec<ReflogMatch> = if query.is_empty() {
+                all_entries
+                    .into_iter()
+                    .map(|entry| {
+                        let formatted_timestamp = Self::format_timestamp(entry.timestamp, timezone);
+                        ReflogMatch {
+                            entry,
+                            positions: Vec::new(),
+                            formatted_timestamp,
+                        }
+                    })
+                    .collect()
+            } else {
+                // Match against what moved the ref as well as the commit's subject, but only
+                // highlight the subject, which is the part shown as the entry's title.
+                let candidates = all_entries
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, entry)| {
+                        StringMatchCandidate::new(
+                            ix,
+                            &format!("{} {} {}", entry.subject, entry.message, entry.sha),
+                        )
+                    })
+                    .collect::<Vec<StringMatchCandidate>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| {
+                    let entry = all_entries[candidate.candidate_id].clone();
+                    let formatted_timestamp = Self::format_timestamp(entry.timestamp, timezone);
+                    let positions = candidate
+                        .positions
+                        .into_iter()
+                        .filter(|position| *position < entry.subject.len())
+                        .collect();
+                    ReflogMatch {
+                        entry,
+                        positions,
+                        formatted_timestamp,
+                    }
+                })
+                .collect()
+            };
+
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    if delegate.matches.is_empty() {
+                        delegate.selected_index = 0;
+                    } else {
+                        delegate.selected_index =
+                            core::cmp::min(delegate.selected_index, delegate.matches.len() - 1);
+                    }
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if secondary {
+            self.checkout_entry_at(self.selected_index(), window, cx);
+        } else {
+            self.show_entry_at(self.selected_index(), window, cx);
+        }
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let ReflogMatch {
+            entry,
+            positions,
+            formatted_timestamp,
+        } = self.matches.get(ix)?;
+
+        let short_sha = &entry.sha[..git::SHORT_SHA_LENGTH.min(entry.sha.len())];
+        let separator = || {
+            Label::new("•")
+                .alpha(0.5)
+                .color(Color::Muted)
+                .size(LabelSize::Small)
+        };
+        let details = h_flex()
+            .gap_1p5()
+            .w_full()
+            .child(
+                Label::new(short_sha.to_string())
+                    .color(Color::Muted)
+                    .size(LabelSize::Small)
+                    .buffer_font(cx),
+            )
+            .child(separator())
+            .child(
+                Label::new(formatted_timestamp.clone())
+                    .color(Color::Muted)
+                    .size(LabelSize::Small),
+            )
+            .child(separator())
+            .child(
+                Label::new(entry.message.clone())
+                    .truncate()
+                    .color(Color::Muted)
+                    .size(LabelSize::Small),
+            );
+
+        Some(
+            ListItem::new(format!("reflog-{ix}"))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .tooltip(Tooltip::text(entry.selector.clone()))
+                .child(
+                    h_flex()
+                        .w_full()
+                        .gap_3()
+                        .child(Icon::new(IconName::HistoryRerun).color(Color::Muted))
+                        .child(
+                            v_flex()
+                                .w_full()
+                                .child(
+                                    HighlightedLabel::new(entry.subject.clone(), positions.clone())
+                                        .truncate(),
+                                )
+                                .child(details),
+                        ),
+                ),
+        )
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some(if self.all_entries.is_none() {
+            "Loading reflog…".into()
+        } else {
+            "No reflog entries found".into()
+        })
+    }
+
+    fn render_footer(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<AnyElement> {
+        let focus_handle = self.focus_handle.clone();
+        let has_selection = self.entry_at(self.selected_index).is_some();
+
+        Some(
+            h_flex()
+                .w_full()
+                .p_1p5()
+                .gap_0p5()
+                .justify_between()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(self.render_reference_dropdown(window, cx))
+                .child(
+                    h_flex()
+                        .gap_0p5()
+                        .child(
+                            Button::new("reset-to-entry", "Reset")
+                                .disabled(!has_selection)
+                                .key_binding(
+                                    KeyBinding::for_action_in(
+                                        &reflog_picker::ResetToEntry,
+                                        &focus_handle,
+                                        cx,
+                                    )
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                                )
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(
+                                        reflog_picker::ResetToEntry.boxed_clone(),
+                                        cx,
+                                    )
+                                }),
+                        )
+                        .child(
+                            Button::new("branch-from-entry", "Branch")
+                                .disabled(!has_selection)
+                                .key_binding(
+                                    KeyBinding::for_action_in(
+                                        &reflog_picker::CreateBranchFromEntry,
+                                        &focus_handle,
+                                        cx,
+                                    )
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                                )
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(
+                                        reflog_picker::CreateBranchFromEntry.boxed_clone(),
+                                        cx,
+                                    )
+                                }),
+                        )
+                        .child(
+                            Button::new("checkout-entry", "Checkout")
+                                .disabled(!has_selection)
+                                .key_binding(
+                                    KeyBinding::for_action_in(
+                                        &menu::SecondaryConfirm,
+                                        &focus_handle,
+                                        cx,
+                                    )
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                                )
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(menu::SecondaryConfirm.boxed_clone(), cx)
+                                }),
+                        )
+                        .child(
+                            Button::new("view-entry", "View")
+                                .disabled(!has_selection)
+                                .key_binding(
+                                    KeyBinding::for_action_in(&menu::Confirm, &focus_handle, cx)
+                                        .map(|kb| kb.size(rems_from_px(12.))),
+                                )
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(menu::Confirm.boxed_clone(), cx)
+                                }),
+                        ),
+                )
+                .into_any(),
+        )
+    }
+}
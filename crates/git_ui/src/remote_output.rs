@@ -1,6 +1,9 @@
 use anyhow::Context as _;
 
-use git::repository::{Remote, RemoteCommandOutput};
+use git::{
+    repository::{Remote, RemoteCommandOutput},
+    submodule::SubmoduleCommand,
+};
 use linkify::{LinkFinder, LinkKind};
 use ui::SharedString;
 use util::ResultExt as _;
@@ -10,6 +13,7 @@ pub enum RemoteAction {
     Fetch(Option<Remote>),
     Pull(Remote),
     Push(SharedString, Remote),
+    Submodule(SubmoduleCommand),
 }
 
 impl RemoteAction {
@@ -18,6 +22,9 @@ impl RemoteAction {
             RemoteAction::Fetch(_) => "fetch",
             RemoteAction::Pull(_) => "pull",
             RemoteAction::Push(_, _) => "push",
+            RemoteAction::Submodule(SubmoduleCommand::Init) => "submodule init",
+            RemoteAction::Submodule(SubmoduleCommand::Update) => "submodule update",
+            RemoteAction::Submodule(SubmoduleCommand::Sync) => "submodule sync",
         }
     }
 }
@@ -157,6 +164,24 @@ pub fn format_output(action: &RemoteAction, output: RemoteCommandOutput) -> Succ
                 style: style.unwrap_or(SuccessStyle::ToastWithLog { output }),
             }
         }
+        RemoteAction::Submodule(command) => {
+            if output.stdout.is_empty() && output.stderr.is_empty() {
+                SuccessMessage {
+                    message: "Submodules: Already up to date".into(),
+                    style: SuccessStyle::Toast,
+                }
+            } else {
+                let message = match command {
+                    SubmoduleCommand::Init => "Initialized submodules",
+                    SubmoduleCommand::Update => "Updated submodules",
+                    SubmoduleCommand::Sync => "Synchronized submodule URLs",
+                };
+                SuccessMessage {
+                    message: message.into(),
+                    style: SuccessStyle::ToastWithLog { output },
+                }
+            }
+        }
     }
 }
 
@@ -1,9 +1,15 @@
-use crate::git_status_icon;
-use git::status::{FileStatus, StatusCode, TrackedStatus, UnmergedStatus, UnmergedStatusCode};
+use crate::{git_panel::GitPanel, git_status_icon};
+use git::{
+    status::{FileStatus, StatusCode, TrackedStatus, UnmergedStatus, UnmergedStatusCode},
+    submodule::{Submodule, SubmoduleCommand, SubmoduleStatus},
+};
 use gpui::{App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity};
 use itertools::Itertools;
 use picker::{Picker, PickerDelegate, PickerEditorPosition};
-use project::{Project, git_store::Repository};
+use project::{
+    Project,
+    git_store::{GitStore, Repository},
+};
 use std::sync::Arc;
 use ui::{ListItem, ListItemSpacing, prelude::*};
 use workspace::{ModalView, Workspace};
@@ -37,31 +43,21 @@ impl RepositorySelector {
         cx: &mut Context<Self>,
     ) -> Self {
         let git_store = project_handle.read(cx).git_store().clone();
-        let repository_entries = git_store.update(cx, |git_store, _cx| {
-            let mut repos: Vec<_> = git_store.repositories().values().cloned().collect();
-
-            repos.sort_by(|a, b| {
-                a.read(_cx)
-                    .display_name()
-                    .to_lowercase()
-                    .cmp(&b.read(_cx).display_name().to_lowercase())
-            });
-
-            repos
-        });
+        let repository_entries = repository_entries(git_store.read(cx), cx);
         let filtered_repositories = repository_entries.clone();
 
-        let widest_item_ix = repository_entries.iter().position_max_by(|a, b| {
-            a.read(cx)
-                .display_name()
-                .len()
-                .cmp(&b.read(cx).display_name().len())
-        });
+        let widest_item_ix = repository_entries
+            .iter()
+            .position_max_by(|a, b| a.display_name(cx).len().cmp(&b.display_name(cx).len()));
 
         let active_repository = git_store.read(cx).active_repository();
         let selected_index = active_repository
             .as_ref()
-            .and_then(|active| filtered_repositories.iter().position(|repo| repo == active))
+            .and_then(|active| {
+                filtered_repositories
+                    .iter()
+                    .position(|entry| entry.repository() == Some(active))
+            })
             .unwrap_or(0);
         let delegate = RepositorySelectorDelegate {
             repository_selector: cx.entity().downgrade(),
@@ -82,6 +78,82 @@ impl RepositorySelector {
     }
 }
 
+#[derive(Clone)]
+pub enum RepositoryEntry {
+    Repository {
+        repository: Entity<Repository>,
+        /// The name of the repository this one is a submodule of, if any.
+        superproject: Option<SharedString>,
+    },
+    /// A submodule that has not been initialized yet, so it has no repository of its own.
+    UninitializedSubmodule {
+        superproject: Entity<Repository>,
+        submodule: Submodule,
+    },
+}
+
+impl RepositoryEntry {
+    fn repository(&self) -> Option<&Entity<Repository>> {
+        match self {
+            Self::Repository { repository, .. } => Some(repository),
+            Self::UninitializedSubmodule { .. } => None,
+        }
+    }
+
+    fn display_name(&self, cx: &App) -> SharedString {
+        match self {
+            Self::Repository { repository, .. } => repository.read(cx).display_name(),
+            Self::UninitializedSubmodule { submodule, .. } => submodule
+                .path
+                .file_name()
+                .unwrap_or(submodule.path.as_unix_str())
+                .to_string()
+                .into(),
+        }
+    }
+}
+
+fn repository_entries(git_store: &GitStore, cx: &App) -> Vec<RepositoryEntry> {
+    let repositories = git_store
+        .repositories()
+        .values()
+        .cloned()
+        .collect::<Vec<_>>();
+    let mut entries = Vec::new();
+    for repository in &repositories {
+        let snapshot = repository.read(cx);
+        let superproject = repositories.iter().find_map(|other| {
+            let other = other.read(cx);
+            other
+                .submodules
+                .iter()
+                .any(|submodule| {
+                    other
+                        .work_directory_abs_path
+                        .join(submodule.path.as_std_path())
+                        == *snapshot.work_directory_abs_path
+                })
+                .then(|| other.display_name())
+        });
+        entries.push(RepositoryEntry::Repository {
+            repository: repository.clone(),
+            superproject,
+        });
+        entries.extend(
+            snapshot
+                .submodules
+                .iter()
+                .filter(|submodule| submodule.status == SubmoduleStatus::Uninitialized)
+                .map(|submodule| RepositoryEntry::UninitializedSubmodule {
+                    superproject: repository.clone(),
+                    submodule: submodule.clone(),
+                }),
+        );
+    }
+    entries.sort_by_cached_key(|entry| entry.display_name(cx).to_lowercase());
+    entries
+}
+
 //pub(crate) fn filtered_repository_entries(
 //    git_store: &GitStore,
 //    cx: &App,
@@ -134,14 +206,14 @@ impl ModalView for RepositorySelector {}
 
 pub struct RepositorySelectorDelegate {
     repository_selector: WeakEntity<RepositorySelector>,
-    repository_entries: Vec<Entity<Repository>>,
-    filtered_repositories: Vec<Entity<Repository>>,
+    repository_entries: Vec<RepositoryEntry>,
+    filtered_repositories: Vec<RepositoryEntry>,
     active_repository: Option<Entity<Repository>>,
     selected_index: usize,
 }
 
 impl RepositorySelectorDelegate {
-    pub fn update_repository_entries(&mut self, all_repositories: Vec<Entity<Repository>>) {
+    pub fn update_repository_entries(&mut self, all_repositories: Vec<RepositoryEntry>) {
         self.repository_entries = all_repositories.clone();
         self.filtered_repositories = all_repositories;
         self.selected_index = self
@@ -150,7 +222,7 @@ impl RepositorySelectorDelegate {
             .and_then(|active| {
                 self.filtered_repositories
                     .iter()
-                    .position(|repo| repo == active)
+                    .position(|entry| entry.repository() == Some(active))
             })
             .unwrap_or(0);
     }
@@ -193,9 +265,9 @@ impl PickerDelegate for RepositorySelectorDelegate {
     ) -> Task<()> {
         let all_repositories = self.repository_entries.clone();
 
-        let repo_names: Vec<(Entity<Repository>, String)> = all_repositories
+        let repo_names: Vec<(RepositoryEntry, String)> = all_repositories
             .iter()
-            .map(|repo| (repo.clone(), repo.read(cx).display_name().to_lowercase()))
+            .map(|entry| (entry.clone(), entry.display_name(cx).to_lowercase()))
             .collect();
 
         cx.spawn_in(window, async move |this, cx| {
@@ -216,17 +288,17 @@ impl PickerDelegate for RepositorySelectorDelegate {
 
             this.update_in(cx, |this, window, cx| {
                 let mut sorted_repositories = filtered_repositories;
-                sorted_repositories.sort_by(|a, b| {
-                    a.read(cx)
-                        .display_name()
-                        .to_lowercase()
-                        .cmp(&b.read(cx).display_name().to_lowercase())
-                });
+                sorted_repositories
+                    .sort_by_cached_key(|entry| entry.display_name(cx).to_lowercase());
                 let selected_index = this
                     .delegate
                     .active_repository
                     .as_ref()
-                    .and_then(|active| sorted_repositories.iter().position(|repo| repo == active))
+                    .and_then(|active| {
+                        sorted_repositories
+                            .iter()
+                            .position(|entry| entry.repository() == Some(active))
+                    })
                     .unwrap_or(0);
                 this.delegate.filtered_repositories = sorted_repositories;
                 this.delegate.set_selected_index(selected_index, window, cx);
@@ -237,12 +309,36 @@ impl PickerDelegate for RepositorySelectorDelegate {
     }
 
     fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
-        let Some(selected_repo) = self.filtered_repositories.get(self.selected_index) else {
+        let Some(selected_entry) = self.filtered_repositories.get(self.selected_index) else {
             return;
         };
-        selected_repo.update(cx, |selected_repo, cx| {
-            selected_repo.set_as_active_repository(cx)
-        });
+        match selected_entry {
+            RepositoryEntry::Repository { repository, .. } => {
+                repository.update(cx, |repository, cx| repository.set_as_active_repository(cx));
+            }
+            RepositoryEntry::UninitializedSubmodule {
+                superproject,
+                submodule,
+            } => {
+                // The worktree picks the submodule up as its own repository once it is checked out.
+                if let Some(panel) = Workspace::for_window(window, cx)
+                    .and_then(|workspace| workspace.read(cx).panel::<GitPanel>(cx))
+                {
+                    let paths = vec![submodule.path.clone()];
+                    panel.update(cx, |panel, cx| {
+                        for command in [SubmoduleCommand::Init, SubmoduleCommand::Update] {
+                            panel.submodule_command_in_repository(
+                                superproject.clone(),
+                                command,
+                                paths.clone(),
+                                window,
+                                cx,
+                            );
+                        }
+                    });
+                }
+            }
+        }
         self.dismissed(window, cx);
     }
 
@@ -259,9 +355,49 @@ impl PickerDelegate for RepositorySelectorDelegate {
         _window: &mut Window,
         cx: &mut Context<Picker<Self>>,
     ) -> Option<Self::ListItem> {
-        let repo_info = self.filtered_repositories.get(ix)?;
+        let entry = self.filtered_repositories.get(ix)?;
+        let display_name = entry.display_name(cx);
+        let (repo_info, superproject) = match entry {
+            RepositoryEntry::Repository {
+                repository,
+                superproject,
+            } => (repository, superproject.clone()),
+            RepositoryEntry::UninitializedSubmodule {
+                superproject,
+                submodule,
+            } => {
+                let superproject_name = superproject.read(cx).display_name();
+                return Some(
+                    ListItem::new(ix)
+                        .inset(true)
+                        .spacing(ListItemSpacing::Sparse)
+                        .toggle_state(selected)
+                        .start_slot(
+                            Icon::new(IconName::Box)
+                                .size(IconSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .child(Label::new(display_name).color(Color::Muted))
+                                .child(
+                                    Label::new(format!("in {superproject_name}"))
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted),
+                                ),
+                        )
+                        .end_slot(
+                            div().pr_2().child(
+                                Label::new(format!("{} · Initialize", submodule.status.label()))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                        ),
+                );
+            }
+        };
         let repo = repo_info.read(cx);
-        let display_name = repo.display_name();
         let summary = repo.status_summary();
         let is_active = self
             .active_repository
@@ -272,10 +408,24 @@ impl PickerDelegate for RepositorySelectorDelegate {
             .inset(true)
             .spacing(ListItemSpacing::Sparse)
             .toggle_state(selected)
+            .when(superproject.is_some(), |this| {
+                this.start_slot(
+                    Icon::new(IconName::Box)
+                        .size(IconSize::Small)
+                        .color(Color::Muted),
+                )
+            })
             .child(
                 h_flex()
                     .gap_1()
                     .child(Label::new(display_name))
+                    .when_some(superproject, |this, superproject| {
+                        this.child(
+                            Label::new(format!("in {superproject}"))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    })
                     .when(is_active, |this| {
                         this.child(
                             Icon::new(IconName::Check)
@@ -0,0 +1,580 @@
+use fuzzy::StringMatchCandidate;
+
+use git::repository::Tag;
+use gpui::{
+    Action, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, ParentElement, Render, SharedString, Styled, Subscription,
+    Task, WeakEntity, Window, actions,
+};
+use picker::{Picker, PickerDelegate};
+use project::git_store::Repository;
+use std::sync::Arc;
+use time::{OffsetDateTime, UtcOffset};
+use time_format;
+use ui::{HighlightedLabel, KeyBinding, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::Workspace;
+use workspace::notifications::DetachAndPromptErr;
+
+use crate::commit_view::CommitView;
+use crate::create_ref_modal::{CreateRefModal, RefKind};
+use crate::git_panel::GitPanel;
+use crate::tag_picker;
+
+actions!(
+    tag_picker,
+    [
+        /// Delete the selected tag.
+        DeleteTag,
+        /// Push the selected tag to a remote.
+        PushTag,
+    ]
+);
+
+pub fn create_embedded(
+    repository: Option<Entity<Repository>>,
+    workspace: WeakEntity<Workspace>,
+    width: Rems,
+    window: &mut Window,
+    cx: &mut Context<TagList>,
+) -> TagList {
+    TagList::new_embedded(repository, workspace, width, window, cx)
+}
+
+pub struct TagList {
+    width: Rems,
+    pub picker: Entity<Picker<TagListDelegate>>,
+    picker_focus_handle: FocusHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl TagList {
+    fn new_embedded(
+        repository: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        width: Rems,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = TagListDelegate::new(repository, workspace, cx);
+        let picker = cx.new(|cx| {
+            Picker::uniform_list(delegate, window, cx)
+                .show_scrollbar(true)
+                .modal(false)
+        });
+        let picker_focus_handle = picker.focus_handle(cx);
+        picker.update(cx, |picker, cx| {
+            picker.delegate.focus_handle = picker_focus_handle.clone();
+            picker.delegate.load_tags(window, cx);
+        });
+
+        let _subscriptions = vec![cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        })];
+
+        Self {
+            picker,
+            picker_focus_handle,
+            width,
+            _subscriptions,
+        }
+    }
+
+    pub fn handle_delete_tag(
+        &mut self,
+        _: &DeleteTag,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .delete_tag_at(picker.delegate.selected_index(), window, cx);
+        });
+        cx.notify();
+    }
+
+    pub fn handle_push_tag(&mut self, _: &PushTag, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .push_tag_at(picker.delegate.selected_index(), window, cx);
+        });
+    }
+}
+
+impl EventEmitter<DismissEvent> for TagList {}
+impl Focusable for TagList {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.picker_focus_handle.clone()
+    }
+}
+
+impl Render for TagList {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("TagList")
+            .w(self.width)
+            .on_action(cx.listener(Self::handle_delete_tag))
+            .on_action(cx.listener(Self::handle_push_tag))
+            .child(self.picker.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Entry {
+    Tag {
+        tag: Tag,
+        positions: Vec<usize>,
+        formatted_timestamp: String,
+    },
+    NewTag {
+        name: String,
+    },
+}
+
+pub struct TagListDelegate {
+    matches: Vec<Entry>,
+    all_tags: Option<Vec<Tag>>,
+    repo: Option<Entity<Repository>>,
+    workspace: WeakEntity<Workspace>,
+    selected_index: usize,
+    focus_handle: FocusHandle,
+    timezone: UtcOffset,
+}
+
+impl TagListDelegate {
+    fn new(
+        repo: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<TagList>,
+    ) -> Self {
+        Self {
+            matches: vec![],
+            all_tags: None,
+            repo,
+            workspace,
+            selected_index: 0,
+            focus_handle: cx.focus_handle(),
+            timezone: UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC),
+        }
+    }
+
+    fn format_timestamp(timestamp: i64, timezone: UtcOffset) -> String {
+        let timestamp =
+            OffsetDateTime::from_unix_timestamp(timestamp).unwrap_or(OffsetDateTime::now_utc());
+        time_format::format_localized_timestamp(
+            timestamp,
+            OffsetDateTime::now_utc(),
+            timezone,
+            time_format::TimestampFormat::Relative,
+        )
+    }
+
+    fn load_tags(&self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let tags = repo.update(cx, |repo, _| repo.tags());
+        cx.spawn_in(window, async move |picker, cx| {
+            let tags = tags.await??;
+            picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.all_tags = Some(tags);
+                picker.refresh(window, cx);
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn tag_at(&self, ix: usize) -> Option<&Tag> {
+        match self.matches.get(ix)? {
+            Entry::Tag { tag, .. } => Some(tag),
+            Entry::NewTag { .. } => None,
+        }
+    }
+
+    fn delete_tag_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(tag) = self.tag_at(ix) else {
+            return;
+        };
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let name = tag.name.to_string();
+
+        let delete = repo.update(cx, |repo, _| repo.delete_tag(name));
+        cx.spawn_in(window, async move |picker, cx| {
+            delete.await??;
+            picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.load_tags(window, cx);
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to delete tag", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+
+    fn push_tag_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(tag) = self.tag_at(ix) else {
+            return;
+        };
+        let Some(panel) = self
+            .workspace
+            .upgrade()
+            .and_then(|workspace| workspace.read(cx).panel::<GitPanel>(cx))
+        else {
+            return;
+        };
+        let name = tag.name.clone();
+        cx.emit(DismissEvent);
+        panel.update(cx, |panel, cx| panel.push_tag(name, window, cx));
+    }
+
+    fn show_tag_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(tag) = self.tag_at(ix) else {
+            return;
+        };
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        CommitView::open(
+            tag.sha.to_string(),
+            repo.downgrade(),
+            self.workspace.clone(),
+            None,
+            None,
+            window,
+            cx,
+        );
+        cx.emit(DismissEvent);
+    }
+
+    /// Creates a lightweight tag at `HEAD`.
+    fn create_tag(&self, name: String, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let create = repo.update(cx, |repo, _| {
+            repo.create_tag(name, "HEAD".into(), None, false)
+        });
+        cx.spawn(async move |_, _| create.await?)
+            .detach_and_prompt_err("Failed to create tag", window, cx, |e, _, _| {
+                Some(e.to_string())
+            });
+        cx.emit(DismissEvent);
+    }
+
+    /// Opens the tag creation modal at `HEAD`, for annotated or signed tags.
+    fn create_tag_with_message(
+        &self,
+        name: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let Some(head) = repo
+            .read(cx)
+            .head_commit
+            .as_ref()
+            .map(|commit| commit.sha.clone())
+        else {
+            return;
+        };
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_modal(window, cx, |window, cx| {
+                let mut modal = CreateRefModal::new(RefKind::Tag, head, repo, window, cx);
+                modal.set_name(&name, window, cx);
+                modal
+            });
+        });
+    }
+}
+
+impl PickerDelegate for TagListDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select or create a tag…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let Some(all_tags) = self.all_tags.clone() else {
+            return Task::ready(());
+        };
+
+        let timezone = self.timezone;
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut matches: Vec<Entry> = if query.is_empty() {
+                all_tags
+                    .into_iter()
+                    .map(|tag| {
+                        let formatted_timestamp = Self::format_timestamp(tag.timestamp, timezone);
+                        Entry::Tag {
+                            tag,
+                            positions: Vec::new(),
+                            formatted_timestamp,
+                        }
+                    })
+                    .collect()
+            } else {
+                let candidates = all_tags
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, tag)| StringMatchCandidate::new(ix, &tag.name))
+                    .collect::<Vec<StringMatchCandidate>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| {
+                    let tag = all_tags[candidate.candidate_id].clone();
+                    let formatted_timestamp = Self::format_timestamp(tag.timestamp, timezone);
+                    Entry::Tag {
+                        tag,
+                        positions: candidate.positions,
+                        formatted_timestamp,
+                    }
+                })
+                .collect()
+            };
+
+            let query = query.trim().replace(' ', "-");
+            if !query.is_empty()
+                && !matches.iter().any(
+                    |entry| matches!(entry, Entry::Tag { tag, .. } if tag.name.as_ref() == query),
+                )
+            {
+                matches.push(Entry::NewTag { name: query });
+            }
+
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    if delegate.matches.is_empty() {
+                        delegate.selected_index = 0;
+                    } else {
+                        delegate.selected_index =
+                            core::cmp::min(delegate.selected_index, delegate.matches.len() - 1);
+                    }
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.matches.get(self.selected_index()) else {
+            return;
+        };
+        match entry {
+            Entry::Tag { .. } => self.show_tag_at(self.selected_index(), window, cx),
+            Entry::NewTag { name } if secondary => {
+                self.create_tag_with_message(name.clone(), window, cx)
+            }
+            Entry::NewTag { name } => self.create_tag(name.clone(), window, cx),
+        }
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let entry = self.matches.get(ix)?;
+
+        let (icon, title, details) = match entry {
+            Entry::Tag {
+                tag,
+                positions,
+                formatted_timestamp,
+            } => {
+                let short_sha = &tag.sha[..git::SHORT_SHA_LENGTH.min(tag.sha.len())];
+                let mut details = h_flex()
+                    .gap_1p5()
+                    .w_full()
+                    .child(
+                        Label::new(short_sha.to_string())
+                            .color(Color::Muted)
+                            .size(LabelSize::Small)
+                            .buffer_font(cx),
+                    )
+                    .child(
+                        Label::new("•")
+                            .alpha(0.5)
+                            .color(Color::Muted)
+                            .size(LabelSize::Small),
+                    )
+                    .child(
+                        Label::new(formatted_timestamp.clone())
+                            .color(Color::Muted)
+                            .size(LabelSize::Small),
+                    );
+                if let Some(message) = tag.message.clone() {
+                    details = details
+                        .child(
+                            Label::new("•")
+                                .alpha(0.5)
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                        )
+                        .child(
+                            Label::new(message)
+                                .truncate()
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                        );
+                }
+                (
+                    IconName::Hash,
+                    HighlightedLabel::new(tag.name.clone(), positions.clone())
+                        .truncate()
+                        .into_any_element(),
+                    Some(details),
+                )
+            }
+            Entry::NewTag { name } => (
+                IconName::Plus,
+                Label::new(format!("Create Tag: \"{name}\""))
+                    .single_line()
+                    .truncate()
+                    .into_any_element(),
+                None,
+            ),
+        };
+
+        Some(
+            ListItem::new(format!("tag-{ix}"))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .w_full()
+                        .gap_3()
+                        .child(Icon::new(icon).color(Color::Muted))
+                        .child(v_flex().w_full().child(title).children(details)),
+                ),
+        )
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No tags found".into())
+    }
+
+    fn render_footer(&self, _: &mut Window, cx: &mut Context<Picker<Self>>) -> Option<AnyElement> {
+        let focus_handle = self.focus_handle.clone();
+        let is_new_tag = matches!(
+            self.matches.get(self.selected_index),
+            Some(Entry::NewTag { .. })
+        );
+
+        let footer = h_flex()
+            .w_full()
+            .p_1p5()
+            .gap_0p5()
+            .justify_end()
+            .border_t_1()
+            .border_color(cx.theme().colors().border_variant);
+
+        let footer = if is_new_tag {
+            footer
+                .child(
+                    Button::new("create-annotated-tag", "Create with Message")
+                        .key_binding(
+                            KeyBinding::for_action_in(&menu::SecondaryConfirm, &focus_handle, cx)
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(menu::SecondaryConfirm.boxed_clone(), cx)
+                        }),
+                )
+                .child(
+                    Button::new("create-tag", "Create")
+                        .key_binding(
+                            KeyBinding::for_action_in(&menu::Confirm, &focus_handle, cx)
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(menu::Confirm.boxed_clone(), cx)
+                        }),
+                )
+        } else {
+            footer
+                .child(
+                    Button::new("delete-tag", "Delete")
+                        .key_binding(
+                            KeyBinding::for_action_in(&tag_picker::DeleteTag, &focus_handle, cx)
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(tag_picker::DeleteTag.boxed_clone(), cx)
+                        }),
+                )
+                .child(
+                    Button::new("push-tag", "Push")
+                        .key_binding(
+                            KeyBinding::for_action_in(&tag_picker::PushTag, &focus_handle, cx)
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(tag_picker::PushTag.boxed_clone(), cx)
+                        }),
+                )
+                .child(
+                    Button::new("view-tag", "View")
+                        .key_binding(
+                            KeyBinding::for_action_in(&menu::Confirm, &focus_handle, cx)
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(menu::Confirm.boxed_clone(), cx)
+                        }),
+                )
+        };
+
+        Some(footer.into_any())
+    }
+}
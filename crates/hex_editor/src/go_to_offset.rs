@@ -0,0 +1,101 @@
+use crate::{HexEditor, parse_offset};
+use editor::Editor;
+use gpui::{
+    App, AppContext as _, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    Render, Window,
+};
+use ui::prelude::*;
+use workspace::ModalView;
+
+/// A modal to move the cursor of a hex editor to a byte offset.
+pub struct GoToOffsetModal {
+    hex_editor: Entity<HexEditor>,
+    offset_editor: Entity<Editor>,
+    last_error: Option<SharedString>,
+}
+
+impl EventEmitter<DismissEvent> for GoToOffsetModal {}
+impl ModalView for GoToOffsetModal {}
+
+impl Focusable for GoToOffsetModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.offset_editor.focus_handle(cx)
+    }
+}
+
+impl GoToOffsetModal {
+    pub fn new(hex_editor: Entity<HexEditor>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let cursor = hex_editor.read(cx).selection().start;
+        let offset_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text(&format!("{cursor:#X}"), window, cx);
+            editor
+        });
+
+        Self {
+            hex_editor,
+            offset_editor,
+            last_error: None,
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        let text = self.offset_editor.read(cx).text(cx);
+        let len = self.hex_editor.read(cx).hex_file().read(cx).len();
+        match parse_offset(&text) {
+            Some(offset) if offset < len => {
+                self.hex_editor
+                    .update(cx, |hex_editor, cx| hex_editor.go_to_offset(offset, cx));
+                cx.emit(DismissEvent);
+            }
+            Some(_) => {
+                self.last_error = Some(format!("The file is only {len:#X} bytes long.").into());
+                cx.notify();
+            }
+            None => {
+                self.last_error = Some(format!("Invalid offset: {}", text.trim()).into());
+                cx.notify();
+            }
+        }
+    }
+}
+
+impl Render for GoToOffsetModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("GoToOffsetModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .elevation_3(cx)
+            .w_96()
+            .overflow_hidden()
+            .child(
+                div()
+                    .p_2()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(self.offset_editor.clone()),
+            )
+            .child(
+                h_flex()
+                    .bg(cx.theme().colors().editor_background)
+                    .rounded_b_sm()
+                    .w_full()
+                    .p_2()
+                    .map(|this| match self.last_error.clone() {
+                        Some(error) => {
+                            this.child(Label::new(error).size(LabelSize::Small).color(Color::Error))
+                        }
+                        None => this.child(
+                            Label::new("Enter a decimal offset, or a hexadecimal one like 0x1F0.")
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                        ),
+                    }),
+            )
+    }
+}
@@ -0,0 +1,905 @@
+mod go_to_offset;
+mod hex_file;
+
+use std::{cmp::Ordering, ops::Range, path::Path};
+
+use editor::{Editor, EditorEvent, EditorSettings, items::entry_git_aware_label_color};
+use file_icons::FileIcons;
+use gpui::{
+    AnyElement, App, Context, Entity, EventEmitter, FocusHandle, Focusable, KeyDownEvent,
+    MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Render, ScrollStrategy, Stateful,
+    Subscription, Task, UniformListScrollHandle, Window, actions, uniform_list,
+};
+use language::File as _;
+use project::Project;
+use settings::Settings;
+use theme::ThemeSettings;
+use ui::{Tooltip, prelude::*};
+use util::paths::PathExt;
+use workspace::{
+    ItemSettings, Pane, ToolbarItemLocation, Workspace,
+    invalid_item_view::InvalidItemView,
+    item::{BreadcrumbText, Item, ItemEvent, ProjectItem, SaveOptions, TabContentParams},
+    notifications::DetachAndPromptErr,
+};
+
+pub use go_to_offset::GoToOffsetModal;
+pub use hex_file::*;
+
+actions!(
+    hex_editor,
+    [
+        /// Shows or hides the byte pattern search bar.
+        ToggleSearch,
+        /// Selects the next match of the byte pattern search.
+        SelectNextMatch,
+        /// Selects the previous match of the byte pattern search.
+        SelectPreviousMatch,
+        /// Moves the cursor to a byte offset.
+        GoToOffset,
+        /// Moves the cursor between the hexadecimal and the text columns.
+        SwitchColumn,
+        /// Reverts the last edit.
+        Undo,
+        /// Reapplies the last reverted edit.
+        Redo
+    ]
+);
+
+const BYTES_PER_ROW: usize = 16;
+const ROWS_PER_PAGE: usize = 32;
+
+pub fn init(cx: &mut App) {
+    workspace::register_project_item::<HexEditor>(cx);
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &GoToOffset, window, cx| {
+            let Some(hex_editor) = workspace.active_item_as::<HexEditor>(cx) else {
+                return;
+            };
+            workspace.toggle_modal(window, cx, move |window, cx| {
+                GoToOffsetModal::new(hex_editor, window, cx)
+            });
+        });
+        workspace.register_action(open_as_hex);
+    })
+    .detach();
+}
+
+/// Reopens the file of the active item in the hex editor, replacing the item if it couldn't
+/// display the file.
+fn open_as_hex(
+    workspace: &mut Workspace,
+    _: &zed_actions::hex_editor::OpenAsHex,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(active_item) = workspace.active_item(cx) else {
+        return;
+    };
+    let project = workspace.project().clone();
+    let invalid_item = active_item.downcast::<InvalidItemView>();
+    let project_path = match &invalid_item {
+        Some(invalid_item) => project
+            .read(cx)
+            .find_project_path(&invalid_item.read(cx).abs_path, cx),
+        None => active_item.project_path(cx),
+    };
+    let Some(project_path) = project_path else {
+        return;
+    };
+
+    let pane = workspace.active_pane().clone();
+    let open_file = HexFile::open(&project, project_path, cx);
+    cx.spawn_in(window, async move |workspace, cx| {
+        let hex_file = open_file.await?;
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.open_project_item::<HexEditor>(
+                pane.clone(),
+                hex_file,
+                true,
+                true,
+                false,
+                false,
+                window,
+                cx,
+            );
+            if let Some(invalid_item) = invalid_item {
+                pane.update(cx, |pane, cx| {
+                    pane.remove_item(invalid_item.entity_id(), false, false, window, cx)
+                });
+            }
+        })
+    })
+    .detach_and_prompt_err(
+        "Failed to open file in hex editor",
+        window,
+        cx,
+        |_, _, _| None,
+    );
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Column {
+    Hex,
+    Text,
+}
+
+pub enum HexEditorEvent {
+    Edited,
+    TitleChanged,
+}
+
+/// Shows the bytes of a file as rows of offsets, hexadecimal values and their ASCII rendering.
+pub struct HexEditor {
+    hex_file: Entity<HexFile>,
+    project: Entity<Project>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    cursor: usize,
+    /// The other end of the selection, if more than the byte at the cursor is selected.
+    selection_tail: Option<usize>,
+    column: Column,
+    /// Whether the high nibble of the byte at the cursor was just typed in the hex column.
+    pending_low_nibble: bool,
+    is_selecting: bool,
+    search: Option<Search>,
+    _subscription: Subscription,
+}
+
+struct Search {
+    query_editor: Entity<Editor>,
+    matches: Vec<Range<usize>>,
+    active_match: Option<usize>,
+    error: Option<SharedString>,
+    pending_matches: Task<()>,
+    _subscription: Subscription,
+}
+
+impl HexEditor {
+    pub fn new(
+        hex_file: Entity<HexFile>,
+        project: Entity<Project>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let subscription = cx.subscribe(&hex_file, |this, _, event, cx| match event {
+            HexFileEvent::Edited => {
+                this.clamp_cursor(cx);
+                this.update_matches(false, cx);
+                cx.emit(HexEditorEvent::Edited);
+            }
+            HexFileEvent::DirtyChanged | HexFileEvent::Saved => {
+                cx.emit(HexEditorEvent::TitleChanged)
+            }
+        });
+
+        Self {
+            hex_file,
+            project,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            cursor: 0,
+            selection_tail: None,
+            column: Column::Hex,
+            pending_low_nibble: false,
+            is_selecting: false,
+            search: None,
+            _subscription: subscription,
+        }
+    }
+
+    pub fn hex_file(&self) -> &Entity<HexFile> {
+        &self.hex_file
+    }
+
+    /// Returns the range of selected bytes, which always includes the byte at the cursor.
+    pub fn selection(&self) -> Range<usize> {
+        let tail = self.selection_tail.unwrap_or(self.cursor);
+        self.cursor.min(tail)..self.cursor.max(tail) + 1
+    }
+
+    /// Moves the cursor to `offset` and scrolls it into view.
+    pub fn go_to_offset(&mut self, offset: usize, cx: &mut Context<Self>) {
+        self.selection_tail = None;
+        self.move_cursor(offset, cx);
+    }
+
+    fn move_cursor(&mut self, offset: usize, cx: &mut Context<Self>) {
+        let len = self.hex_file.read(cx).len();
+        self.cursor = offset.min(len.saturating_sub(1));
+        self.pending_low_nibble = false;
+        self.scroll_handle
+            .scroll_to_item(self.cursor / BYTES_PER_ROW, ScrollStrategy::Nearest);
+        cx.notify();
+    }
+
+    fn select_range(&mut self, range: Range<usize>, cx: &mut Context<Self>) {
+        self.selection_tail = Some(range.start);
+        self.move_cursor(range.end.saturating_sub(1), cx);
+    }
+
+    fn clamp_cursor(&mut self, cx: &App) {
+        let last_offset = self.hex_file.read(cx).len().saturating_sub(1);
+        self.cursor = self.cursor.min(last_offset);
+        self.selection_tail = self.selection_tail.map(|tail| tail.min(last_offset));
+    }
+
+    fn key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.focus_handle.is_focused(window) || self.hex_file.read(cx).is_empty() {
+            return;
+        }
+        let keystroke = &event.keystroke;
+        let modifiers = keystroke.modifiers;
+        if modifiers.control || modifiers.platform || modifiers.function {
+            return;
+        }
+
+        let row_start = self.cursor - self.cursor % BYTES_PER_ROW;
+        let target = match keystroke.key.as_str() {
+            "left" => Some(self.cursor.saturating_sub(1)),
+            "right" => Some(self.cursor + 1),
+            "up" => Some(self.cursor.saturating_sub(BYTES_PER_ROW)),
+            "down" => Some(self.cursor + BYTES_PER_ROW),
+            "pageup" => Some(self.cursor.saturating_sub(BYTES_PER_ROW * ROWS_PER_PAGE)),
+            "pagedown" => Some(self.cursor + BYTES_PER_ROW * ROWS_PER_PAGE),
+            "home" => Some(row_start),
+            "end" => Some(row_start + BYTES_PER_ROW - 1),
+            _ => None,
+        };
+        if let Some(target) = target {
+            if modifiers.shift {
+                self.selection_tail.get_or_insert(self.cursor);
+            } else {
+                self.selection_tail = None;
+            }
+            self.move_cursor(target, cx);
+            cx.stop_propagation();
+            return;
+        }
+
+        if modifiers.alt {
+            return;
+        }
+        let Some(char) = keystroke
+            .key_char
+            .as_ref()
+            .and_then(|text| text.chars().next())
+        else {
+            return;
+        };
+        let handled = match self.column {
+            Column::Hex => char.to_digit(16).is_some_and(|digit| {
+                self.write_nibble(digit as u8, cx);
+                true
+            }),
+            Column::Text => {
+                let is_printable = char.is_ascii() && !char.is_ascii_control();
+                if is_printable {
+                    let offset = self.cursor;
+                    self.hex_file.update(cx, |hex_file, cx| {
+                        hex_file.write_byte(offset, char as u8, false, cx)
+                    });
+                    self.selection_tail = None;
+                    self.move_cursor(offset + 1, cx);
+                }
+                is_printable
+            }
+        };
+        if handled {
+            cx.stop_propagation();
+        }
+    }
+
+    /// Overwrites the high nibble of the byte at the cursor, or its low nibble if the high one
+    /// was just typed, in which case the cursor then advances to the next byte.
+    fn write_nibble(&mut self, digit: u8, cx: &mut Context<Self>) {
+        let offset = self.cursor;
+        let Some(old_value) = self.hex_file.read(cx).bytes().get(offset) else {
+            return;
+        };
+        let write_low_nibble = self.pending_low_nibble;
+        let new_value = if write_low_nibble {
+            (old_value & 0xf0) | digit
+        } else {
+            (digit << 4) | (old_value & 0x0f)
+        };
+        self.hex_file.update(cx, |hex_file, cx| {
+            hex_file.write_byte(offset, new_value, write_low_nibble, cx)
+        });
+        self.selection_tail = None;
+        if write_low_nibble {
+            self.move_cursor(offset + 1, cx);
+        } else {
+            self.pending_low_nibble = true;
+            cx.notify();
+        }
+    }
+
+    fn switch_column(&mut self, _: &SwitchColumn, _: &mut Window, cx: &mut Context<Self>) {
+        self.column = match self.column {
+            Column::Hex => Column::Text,
+            Column::Text => Column::Hex,
+        };
+        self.pending_low_nibble = false;
+        cx.notify();
+    }
+
+    fn undo(&mut self, _: &Undo, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(offset) = self.hex_file.update(cx, |hex_file, cx| hex_file.undo(cx)) {
+            self.go_to_offset(offset, cx);
+        }
+    }
+
+    fn redo(&mut self, _: &Redo, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(offset) = self.hex_file.update(cx, |hex_file, cx| hex_file.redo(cx)) {
+            self.go_to_offset(offset, cx);
+        }
+    }
+
+    fn mouse_down(
+        &mut self,
+        offset: usize,
+        column: Column,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if event.modifiers.shift {
+            self.selection_tail.get_or_insert(self.cursor);
+        } else {
+            self.selection_tail = None;
+        }
+        self.column = column;
+        self.is_selecting = true;
+        window.focus(&self.focus_handle, cx);
+        self.move_cursor(offset, cx);
+    }
+
+    fn mouse_move(&mut self, offset: usize, event: &MouseMoveEvent, cx: &mut Context<Self>) {
+        if !self.is_selecting || !event.dragging() || offset == self.cursor {
+            return;
+        }
+        self.selection_tail.get_or_insert(self.cursor);
+        self.move_cursor(offset, cx);
+    }
+
+    fn mouse_up(&mut self, _: &MouseUpEvent, _: &mut Window, _: &mut Context<Self>) {
+        self.is_selecting = false;
+    }
+
+    fn toggle_search(&mut self, _: &ToggleSearch, window: &mut Window, cx: &mut Context<Self>) {
+        match self
+            .search
+            .as_ref()
+            .map(|search| search.query_editor.focus_handle(cx))
+        {
+            Some(query_focus_handle) if query_focus_handle.is_focused(window) => {
+                self.search = None;
+                window.focus(&self.focus_handle, cx);
+                cx.notify();
+            }
+            Some(query_focus_handle) => window.focus(&query_focus_handle, cx),
+            None => {
+                let query_editor = cx.new(|cx| {
+                    let mut editor = Editor::single_line(window, cx);
+                    editor.set_placeholder_text("Bytes like 7F 45 ?? 46, or \"text\"", window, cx);
+                    editor
+                });
+                let subscription =
+                    cx.subscribe(&query_editor, |this, _, event: &EditorEvent, cx| {
+                        if let EditorEvent::BufferEdited = event {
+                            this.update_matches(true, cx);
+                        }
+                    });
+                let query_focus_handle = query_editor.focus_handle(cx);
+                window.focus(&query_focus_handle, cx);
+                self.search = Some(Search {
+                    query_editor,
+                    matches: Vec::new(),
+                    active_match: None,
+                    error: None,
+                    pending_matches: Task::ready(()),
+                    _subscription: subscription,
+                });
+                cx.notify();
+            }
+        }
+    }
+
+    /// Searches the file for the query of the search bar, selecting the first match after the
+    /// cursor when `select_match` is true.
+    fn update_matches(&mut self, select_match: bool, cx: &mut Context<Self>) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        let query = search.query_editor.read(cx).text(cx);
+        if query.trim().is_empty() {
+            search.matches.clear();
+            search.active_match = None;
+            search.error = None;
+            search.pending_matches = Task::ready(());
+            cx.notify();
+            return;
+        }
+        let pattern = match BytePattern::parse(&query) {
+            Ok(pattern) => pattern,
+            Err(error) => {
+                search.matches.clear();
+                search.active_match = None;
+                search.error = Some(error.to_string().into());
+                search.pending_matches = Task::ready(());
+                cx.notify();
+                return;
+            }
+        };
+        search.error = None;
+
+        let bytes = self.hex_file.read(cx).snapshot();
+        let find_matches = cx.background_spawn(async move { pattern.find_all(&bytes) });
+        search.pending_matches = cx.spawn(async move |this, cx| {
+            let matches = find_matches.await;
+            this.update(cx, |this, cx| {
+                let cursor = this.cursor;
+                let Some(search) = this.search.as_mut() else {
+                    return;
+                };
+                let active_match = matches
+                    .iter()
+                    .position(|range| range.end > cursor)
+                    .or_else(|| (!matches.is_empty()).then_some(0));
+                search.matches = matches;
+                search.active_match = active_match;
+                if select_match && let Some(active_match) = active_match {
+                    let range = search.matches[active_match].clone();
+                    this.select_range(range, cx);
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn select_next_match(&mut self, _: &SelectNextMatch, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_match(true, cx);
+    }
+
+    fn select_previous_match(
+        &mut self,
+        _: &SelectPreviousMatch,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_match(false, cx);
+    }
+
+    fn select_match(&mut self, forward: bool, cx: &mut Context<Self>) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        let match_count = search.matches.len();
+        if match_count == 0 {
+            return;
+        }
+        let active_match = match search.active_match {
+            None => 0,
+            Some(ix) if forward => (ix + 1) % match_count,
+            Some(ix) => (ix + match_count - 1) % match_count,
+        };
+        search.active_match = Some(active_match);
+        let range = search.matches[active_match].clone();
+        self.select_range(range, cx);
+    }
+
+    fn is_match(&self, offset: usize) -> bool {
+        self.search.as_ref().is_some_and(|search| {
+            search
+                .matches
+                .binary_search_by(|range| {
+                    if range.end <= offset {
+                        Ordering::Less
+                    } else if range.start > offset {
+                        Ordering::Greater
+                    } else {
+                        Ordering::Equal
+                    }
+                })
+                .is_ok()
+        })
+    }
+
+    fn render_row(&self, row: usize, bytes: &HexBytes, cx: &Context<Self>) -> AnyElement {
+        let row_start = row * BYTES_PER_ROW;
+        let row_bytes = bytes
+            .range(row_start..row_start + BYTES_PER_ROW)
+            .collect::<Vec<_>>();
+        let selection = self.selection();
+
+        let hex_cells = row_bytes.iter().enumerate().map(|(ix, byte)| {
+            let offset = row_start + ix;
+            self.render_cell(
+                offset,
+                Column::Hex,
+                format!("{byte:02X}"),
+                *byte == 0,
+                &selection,
+                cx,
+            )
+            .when(ix == BYTES_PER_ROW / 2, |cell| cell.ml_2())
+        });
+        let text_cells = row_bytes.iter().enumerate().map(|(ix, byte)| {
+            let offset = row_start + ix;
+            let is_printable = byte.is_ascii_graphic() || *byte == b' ';
+            let text = if is_printable { *byte as char } else { '.' };
+            self.render_cell(
+                offset,
+                Column::Text,
+                text.to_string(),
+                !is_printable,
+                &selection,
+                cx,
+            )
+        });
+
+        h_flex()
+            .px_3()
+            .gap_4()
+            .child(
+                Label::new(format!("{row_start:08X}"))
+                    .buffer_font(cx)
+                    .color(Color::Muted),
+            )
+            .child(
+                h_flex()
+                    // Keep the text column aligned on the last row.
+                    .w(rems(BYTES_PER_ROW as f32 * 1.5 + 0.5))
+                    .children(hex_cells),
+            )
+            .child(h_flex().children(text_cells))
+            .into_any_element()
+    }
+
+    fn render_cell(
+        &self,
+        offset: usize,
+        column: Column,
+        text: String,
+        is_muted: bool,
+        selection: &Range<usize>,
+        cx: &Context<Self>,
+    ) -> Stateful<Div> {
+        let colors = cx.theme().colors();
+        let is_cursor = offset == self.cursor && column == self.column;
+        let id = match column {
+            Column::Hex => ("hex-byte", offset),
+            Column::Text => ("text-byte", offset),
+        };
+
+        div()
+            .id(id)
+            .when(column == Column::Hex, |cell| cell.px(rems(0.1)))
+            .text_buffer(cx)
+            .text_color(if is_muted {
+                colors.text_muted
+            } else {
+                colors.editor_foreground
+            })
+            .when(self.is_match(offset), |cell| {
+                cell.bg(colors.search_match_background)
+            })
+            .when(selection.len() > 1 && selection.contains(&offset), |cell| {
+                cell.bg(cx.theme().players().local().selection)
+            })
+            .when(is_cursor, |cell| {
+                cell.border_b_2()
+                    .border_color(cx.theme().players().local().cursor)
+            })
+            .child(text)
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, event, window, cx| {
+                    this.mouse_down(offset, column, event, window, cx)
+                }),
+            )
+            .on_mouse_move(
+                cx.listener(move |this, event, _, cx| this.mouse_move(offset, event, cx)),
+            )
+    }
+
+    fn render_search(&self, search: &Search, cx: &Context<Self>) -> impl IntoElement {
+        let match_label = match (&search.error, search.active_match) {
+            (Some(error), _) => Label::new(error.clone()).color(Color::Error),
+            (None, Some(ix)) => Label::new(format!("{}/{}", ix + 1, search.matches.len())),
+            (None, None) => Label::new("No matches").color(Color::Muted),
+        };
+
+        h_flex()
+            .key_context("HexEditorSearch")
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                div()
+                    .flex_1()
+                    .px_2()
+                    .py_1()
+                    .border_1()
+                    .rounded_md()
+                    .border_color(cx.theme().colors().border)
+                    .child(search.query_editor.clone()),
+            )
+            .child(match_label.size(LabelSize::Small))
+            .child(
+                IconButton::new("select-previous-match", IconName::ChevronLeft)
+                    .icon_size(IconSize::Small)
+                    .tooltip(|_, cx| {
+                        Tooltip::for_action("Select Previous Match", &SelectPreviousMatch, cx)
+                    })
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.select_previous_match(&SelectPreviousMatch, window, cx)
+                    })),
+            )
+            .child(
+                IconButton::new("select-next-match", IconName::ChevronRight)
+                    .icon_size(IconSize::Small)
+                    .tooltip(|_, cx| Tooltip::for_action("Select Next Match", &SelectNextMatch, cx))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.select_next_match(&SelectNextMatch, window, cx)
+                    })),
+            )
+    }
+
+    fn render_status(&self, len: usize, cx: &Context<Self>) -> impl IntoElement {
+        let selection = self.selection();
+        let status = if len == 0 {
+            "Empty file".to_string()
+        } else if selection.len() > 1 {
+            format!(
+                "{:#X}–{:#X} ({} bytes selected)",
+                selection.start,
+                selection.end - 1,
+                selection.len()
+            )
+        } else {
+            let byte = self
+                .hex_file
+                .read(cx)
+                .bytes()
+                .get(self.cursor)
+                .unwrap_or_default();
+            format!(
+                "Offset {:#X} ({}) · Value {byte:#04X} ({byte})",
+                self.cursor, self.cursor
+            )
+        };
+
+        h_flex()
+            .px_3()
+            .py_1()
+            .justify_between()
+            .border_t_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                Label::new(status)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                Label::new(format!("{len} bytes"))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+    }
+}
+
+impl EventEmitter<HexEditorEvent> for HexEditor {}
+
+impl Item for HexEditor {
+    type Event = HexEditorEvent;
+
+    fn to_item_events(event: &Self::Event, f: &mut dyn FnMut(ItemEvent)) {
+        match event {
+            HexEditorEvent::Edited => f(ItemEvent::Edit),
+            HexEditorEvent::TitleChanged => f(ItemEvent::UpdateTab),
+        }
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        f(self.hex_file.entity_id(), self.hex_file.read(cx))
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        let abs_path = self.hex_file.read(cx).abs_path(cx)?;
+        Some(abs_path.compact().to_string_lossy().into_owned().into())
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        let project_path = self.hex_file.read(cx).project_path(cx);
+
+        let label_color = if ItemSettings::get_global(cx).git_status {
+            let git_status = self
+                .project
+                .read(cx)
+                .project_path_git_status(&project_path, cx)
+                .map(|status| status.summary())
+                .unwrap_or_default();
+
+            self.project
+                .read(cx)
+                .entry_for_path(&project_path, cx)
+                .map(|entry| {
+                    entry_git_aware_label_color(git_status, entry.is_ignored, params.selected)
+                })
+                .unwrap_or_else(|| params.text_color())
+        } else {
+            params.text_color()
+        };
+
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .single_line()
+            .color(label_color)
+            .when(params.preview, |this| this.italic())
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _: usize, cx: &App) -> SharedString {
+        self.hex_file
+            .read(cx)
+            .file()
+            .file_name(cx)
+            .to_string()
+            .into()
+    }
+
+    fn tab_icon(&self, _: &Window, cx: &App) -> Option<Icon> {
+        let path = self.hex_file.read(cx).abs_path(cx)?;
+        ItemSettings::get_global(cx)
+            .file_icons
+            .then(|| FileIcons::get_icon(&path, cx))
+            .flatten()
+            .map(Icon::from_path)
+    }
+
+    fn breadcrumb_location(&self, cx: &App) -> ToolbarItemLocation {
+        if EditorSettings::get_global(cx).toolbar.breadcrumbs {
+            ToolbarItemLocation::PrimaryLeft
+        } else {
+            ToolbarItemLocation::Hidden
+        }
+    }
+
+    fn breadcrumbs(&self, cx: &App) -> Option<Vec<BreadcrumbText>> {
+        let project = self.project.read(cx);
+        let project_path = self.hex_file.read(cx).project_path(cx);
+        let mut path = project_path.path.clone();
+        if project.visible_worktrees(cx).count() > 1
+            && let Some(worktree) = project.worktree_for_id(project_path.worktree_id, cx)
+        {
+            path = worktree.read(cx).root_name().join(&path);
+        }
+
+        Some(vec![BreadcrumbText {
+            text: path.display(project.path_style(cx)).to_string(),
+            highlights: None,
+            font: Some(ThemeSettings::get_global(cx).buffer_font.clone()),
+        }])
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.hex_file.read(cx).is_dirty()
+    }
+
+    fn has_deleted_file(&self, cx: &App) -> bool {
+        self.hex_file.read(cx).file().disk_state().is_deleted()
+    }
+
+    fn can_save(&self, cx: &App) -> bool {
+        self.hex_file.read(cx).abs_path(cx).is_some()
+    }
+
+    fn save(
+        &mut self,
+        _options: SaveOptions,
+        project: Entity<Project>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        let fs = project.read(cx).fs().clone();
+        self.hex_file
+            .update(cx, |hex_file, cx| hex_file.save(fs, cx))
+    }
+
+    fn reload(
+        &mut self,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        self.hex_file.update(cx, |hex_file, cx| hex_file.reload(cx))
+    }
+
+    fn buffer_kind(&self, _: &App) -> workspace::item::ItemBufferKind {
+        workspace::item::ItemBufferKind::Singleton
+    }
+}
+
+impl Focusable for HexEditor {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for HexEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let len = self.hex_file.read(cx).len();
+        let row_count = len.div_ceil(BYTES_PER_ROW);
+
+        v_flex()
+            .key_context("HexEditor")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::toggle_search))
+            .on_action(cx.listener(Self::select_next_match))
+            .on_action(cx.listener(Self::select_previous_match))
+            .on_action(cx.listener(Self::switch_column))
+            .on_action(cx.listener(Self::undo))
+            .on_action(cx.listener(Self::redo))
+            .on_key_down(cx.listener(Self::key_down))
+            .on_mouse_up(MouseButton::Left, cx.listener(Self::mouse_up))
+            .on_mouse_up_out(MouseButton::Left, cx.listener(Self::mouse_up))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .when_some(self.search.as_ref(), |this, search| {
+                this.child(self.render_search(search, cx))
+            })
+            .child(
+                uniform_list(
+                    "hex-rows",
+                    row_count,
+                    cx.processor(|this, range: Range<usize>, _, cx| {
+                        let bytes = this.hex_file.read(cx).snapshot();
+                        range.map(|row| this.render_row(row, &bytes, cx)).collect()
+                    }),
+                )
+                .flex_1()
+                .py_1()
+                .track_scroll(&self.scroll_handle),
+            )
+            .child(self.render_status(len, cx))
+    }
+}
+
+impl ProjectItem for HexEditor {
+    type Item = HexFile;
+
+    fn for_project_item(
+        project: Entity<Project>,
+        _: Option<&Pane>,
+        item: Entity<Self::Item>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(item, project, window, cx)
+    }
+
+    fn for_broken_project_item(
+        abs_path: &Path,
+        is_local: bool,
+        e: &anyhow::Error,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<InvalidItemView>
+    where
+        Self: Sized,
+    {
+        Some(InvalidItemView::new(abs_path, is_local, e, window, cx))
+    }
+}
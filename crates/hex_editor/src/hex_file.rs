@@ -0,0 +1,601 @@
+use anyhow::{Context as _, Result, anyhow};
+use gpui::{App, AppContext as _, Context, Entity, EventEmitter, Task};
+use language::File as _;
+use project::{Fs, Project, ProjectEntryId, ProjectPath};
+use settings::Settings as _;
+use std::{ops::Range, path::PathBuf, sync::Arc};
+use worktree::{LoadedBinaryFile, Worktree, WorktreeSettings};
+
+/// Extensions of files that are opened in the hex editor rather than as text.
+const BINARY_EXTENSIONS: &[&str] = &[
+    "a", "bin", "class", "dll", "dylib", "exe", "o", "pyc", "so", "wasm",
+];
+
+/// The maximum number of matches reported when searching for a byte pattern.
+const MAX_MATCHES: usize = 10_000;
+
+/// The number of bytes stored in each page of a [`HexBytes`].
+const PAGE_LEN: usize = 64 * 1024;
+
+/// The contents of a file opened in the hex editor.
+///
+/// Edits overwrite bytes in place, so the length of the file never changes.
+pub struct HexFile {
+    file: Arc<worktree::File>,
+    bytes: HexBytes,
+    undo_stack: Vec<ByteEdit>,
+    redo_stack: Vec<ByteEdit>,
+    /// The size of the undo stack when the file was last saved, or `None` if that state can't be
+    /// reached by undoing or redoing anymore.
+    saved_undo_depth: Option<usize>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ByteEdit {
+    offset: usize,
+    old_value: u8,
+    new_value: u8,
+}
+
+pub enum HexFileEvent {
+    Edited,
+    DirtyChanged,
+    Saved,
+}
+
+impl EventEmitter<HexFileEvent> for HexFile {}
+
+impl HexFile {
+    pub fn open(
+        project: &Entity<Project>,
+        path: ProjectPath,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let Some(worktree) = project.read(cx).worktree_for_id(path.worktree_id, cx) else {
+            return Task::ready(Err(anyhow!("no worktree found for path {:?}", path.path)));
+        };
+        if let Err(error) = check_file_size(&worktree, &path, cx) {
+            return Task::ready(Err(error));
+        }
+        let load_file =
+            worktree.update(cx, |worktree, cx| worktree.load_binary_file(&path.path, cx));
+        cx.spawn(async move |cx| {
+            let LoadedBinaryFile { file, content } = load_file.await?;
+            Ok(cx.new(|_| Self {
+                file,
+                bytes: HexBytes::from(content),
+                undo_stack: Vec::new(),
+                redo_stack: Vec::new(),
+                saved_undo_depth: Some(0),
+            }))
+        })
+    }
+
+    pub fn bytes(&self) -> &HexBytes {
+        &self.bytes
+    }
+
+    /// Returns the current contents, without copying them.
+    pub fn snapshot(&self) -> HexBytes {
+        self.bytes.clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn file(&self) -> &Arc<worktree::File> {
+        &self.file
+    }
+
+    pub fn project_path(&self, cx: &App) -> ProjectPath {
+        ProjectPath {
+            worktree_id: self.file.worktree_id(cx),
+            path: self.file.path().clone(),
+        }
+    }
+
+    pub fn abs_path(&self, cx: &App) -> Option<PathBuf> {
+        Some(self.file.as_local()?.abs_path(cx))
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.saved_undo_depth != Some(self.undo_stack.len())
+    }
+
+    /// Overwrites the byte at `offset`. When `merge` is true and the previous edit wrote the same
+    /// byte, both are undone together.
+    pub fn write_byte(&mut self, offset: usize, value: u8, merge: bool, cx: &mut Context<Self>) {
+        let Some(old_value) = self.bytes.get(offset) else {
+            return;
+        };
+        let was_dirty = self.is_dirty();
+        let can_merge = merge
+            && self.redo_stack.is_empty()
+            && self.saved_undo_depth != Some(self.undo_stack.len());
+        if let Some(edit) = self
+            .undo_stack
+            .last_mut()
+            .filter(|edit| can_merge && edit.offset == offset)
+        {
+            edit.new_value = value;
+        } else {
+            if self
+                .saved_undo_depth
+                .is_some_and(|depth| depth > self.undo_stack.len())
+            {
+                self.saved_undo_depth = None;
+            }
+            self.undo_stack.push(ByteEdit {
+                offset,
+                old_value,
+                new_value: value,
+            });
+        }
+        self.redo_stack.clear();
+        self.bytes.set(offset, value);
+        self.edited(was_dirty, cx);
+    }
+
+    /// Reverts the last edit, returning the offset of the byte it changed.
+    pub fn undo(&mut self, cx: &mut Context<Self>) -> Option<usize> {
+        let was_dirty = self.is_dirty();
+        let edit = self.undo_stack.pop()?;
+        self.bytes.set(edit.offset, edit.old_value);
+        self.redo_stack.push(edit);
+        self.edited(was_dirty, cx);
+        Some(edit.offset)
+    }
+
+    /// Reapplies the last undone edit, returning the offset of the byte it changed.
+    pub fn redo(&mut self, cx: &mut Context<Self>) -> Option<usize> {
+        let was_dirty = self.is_dirty();
+        let edit = self.redo_stack.pop()?;
+        self.bytes.set(edit.offset, edit.new_value);
+        self.undo_stack.push(edit);
+        self.edited(was_dirty, cx);
+        Some(edit.offset)
+    }
+
+    /// Writes the contents back to disk.
+    pub fn save(&mut self, fs: Arc<dyn Fs>, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let Some(abs_path) = self.abs_path(cx) else {
+            return Task::ready(Err(anyhow!("cannot save a file from a remote project")));
+        };
+        let bytes = self.bytes.clone();
+        let undo_depth = self.undo_stack.len();
+        cx.spawn(async move |this, cx| {
+            let bytes = cx.background_spawn(async move { bytes.to_vec() }).await;
+            fs.write(&abs_path, &bytes)
+                .await
+                .with_context(|| format!("saving {abs_path:?}"))?;
+            this.update(cx, |this, cx| {
+                this.saved_undo_depth = Some(undo_depth);
+                cx.emit(HexFileEvent::DirtyChanged);
+                cx.emit(HexFileEvent::Saved);
+                cx.notify();
+            })
+        })
+    }
+
+    /// Discards any unsaved edits and reads the contents from disk again.
+    pub fn reload(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let project_path = self.project_path(cx);
+        if let Err(error) = check_file_size(&self.file.worktree, &project_path, cx) {
+            return Task::ready(Err(error));
+        }
+        let load_file = self.file.worktree.update(cx, |worktree, cx| {
+            worktree.load_binary_file(&project_path.path, cx)
+        });
+        cx.spawn(async move |this, cx| {
+            let LoadedBinaryFile { file, content } = load_file.await?;
+            this.update(cx, |this, cx| {
+                let was_dirty = this.is_dirty();
+                this.file = file;
+                this.bytes = HexBytes::from(content);
+                this.undo_stack.clear();
+                this.redo_stack.clear();
+                this.saved_undo_depth = Some(0);
+                this.edited(was_dirty, cx);
+            })
+        })
+    }
+
+    fn edited(&mut self, was_dirty: bool, cx: &mut Context<Self>) {
+        cx.emit(HexFileEvent::Edited);
+        if was_dirty != self.is_dirty() {
+            cx.emit(HexFileEvent::DirtyChanged);
+        }
+        cx.notify();
+    }
+}
+
+impl project::ProjectItem for HexFile {
+    fn try_open(
+        project: &Entity<Project>,
+        path: &ProjectPath,
+        cx: &mut App,
+    ) -> Option<Task<Result<Entity<Self>>>> {
+        if !project.read(cx).is_local() || !is_binary_file(path) {
+            return None;
+        }
+        Some(Self::open(project, path.clone(), cx))
+    }
+
+    fn entry_id(&self, _: &App) -> Option<ProjectEntryId> {
+        self.file.entry_id
+    }
+
+    fn project_path(&self, cx: &App) -> Option<ProjectPath> {
+        Some(self.project_path(cx))
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.is_dirty()
+    }
+}
+
+/// Files above the `large_file_threshold_mb` setting aren't opened, since the hex editor
+/// holds the whole file in memory.
+fn check_file_size(worktree: &Entity<Worktree>, path: &ProjectPath, cx: &App) -> Result<()> {
+    let Some(threshold) = WorktreeSettings::get(Some(path.into()), cx).large_file_threshold else {
+        return Ok(());
+    };
+    let size = worktree
+        .read(cx)
+        .entry_for_path(&path.path)
+        .map(|entry| entry.size);
+    if let Some(size) = size
+        && size > threshold
+    {
+        anyhow::bail!(
+            "{} is too large to open in the hex editor ({size} bytes, the limit set by \
+            `large_file_threshold_mb` is {threshold} bytes)",
+            path.path.display(util::paths::PathStyle::local()),
+        );
+    }
+    Ok(())
+}
+
+fn is_binary_file(path: &ProjectPath) -> bool {
+    path.path
+        .extension()
+        .map(str::to_lowercase)
+        .is_some_and(|extension| BINARY_EXTENSIONS.contains(&extension.as_str()))
+}
+
+/// The contents of a [`HexFile`], split into fixed-size pages so that snapshots are cheap to
+/// take, and overwriting a byte while a snapshot is in use only copies the page it's in.
+#[derive(Clone, Debug, Default)]
+pub struct HexBytes {
+    pages: Arc<Vec<Arc<Vec<u8>>>>,
+    len: usize,
+}
+
+impl From<Vec<u8>> for HexBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self {
+            len: bytes.len(),
+            pages: Arc::new(
+                bytes
+                    .chunks(PAGE_LEN)
+                    .map(|page| Arc::new(page.to_vec()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl HexBytes {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, offset: usize) -> Option<u8> {
+        let page = self.pages.get(offset / PAGE_LEN)?;
+        page.get(offset % PAGE_LEN).copied()
+    }
+
+    /// Returns the bytes in `range`, which is clipped to the end of the contents.
+    pub fn range(&self, range: Range<usize>) -> impl Iterator<Item = u8> + '_ {
+        let end = range.end.min(self.len);
+        (range.start..end).filter_map(|offset| self.get(offset))
+    }
+
+    fn set(&mut self, offset: usize, value: u8) {
+        let pages = Arc::make_mut(&mut self.pages);
+        if let Some(page) = pages.get_mut(offset / PAGE_LEN) {
+            Arc::make_mut(page)[offset % PAGE_LEN] = value;
+        }
+    }
+
+    /// Copies the contents into a contiguous buffer.
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.len);
+        for page in self.pages.iter() {
+            bytes.extend_from_slice(page);
+        }
+        bytes
+    }
+}
+
+/// A sequence of bytes to search for, where `None` matches any byte.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BytePattern(Vec<Option<u8>>);
+
+impl BytePattern {
+    /// Parses either a double-quoted string, which matches its UTF-8 bytes, or a sequence of
+    /// hexadecimal bytes like `DE AD BE EF`, where `??` matches any byte.
+    pub fn parse(query: &str) -> Result<Self> {
+        let query = query.trim();
+        if let Some(text) = query
+            .strip_prefix('"')
+            .and_then(|query| query.strip_suffix('"'))
+        {
+            anyhow::ensure!(!text.is_empty(), "the search text is empty");
+            return Ok(Self(text.bytes().map(Some).collect()));
+        }
+
+        let digits = query
+            .chars()
+            .filter(|char| !char.is_whitespace())
+            .collect::<Vec<_>>();
+        anyhow::ensure!(!digits.is_empty(), "the byte pattern is empty");
+        anyhow::ensure!(
+            digits.len() % 2 == 0,
+            "bytes must be written as pairs of hexadecimal digits"
+        );
+        digits
+            .chunks(2)
+            .map(|pair| match pair {
+                ['?', '?'] => Ok(None),
+                [high, low] => {
+                    let high = high.to_digit(16);
+                    let low = low.to_digit(16);
+                    match (high, low) {
+                        (Some(high), Some(low)) => Ok(Some((high * 16 + low) as u8)),
+                        _ => Err(anyhow!("invalid byte {}{}", pair[0], pair[1])),
+                    }
+                }
+                _ => unreachable!(),
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Self)
+    }
+
+    /// Returns the ranges of the non-overlapping occurrences of the pattern in `bytes`.
+    pub fn find_all(&self, bytes: &HexBytes) -> Vec<Range<usize>> {
+        let pattern = &self.0;
+        let mut matches = Vec::new();
+        let mut offset = 0;
+        while offset + pattern.len() <= bytes.len() && matches.len() < MAX_MATCHES {
+            let candidate = bytes.range(offset..offset + pattern.len());
+            if pattern
+                .iter()
+                .zip(candidate)
+                .all(|(expected, byte)| expected.is_none_or(|expected| expected == byte))
+            {
+                matches.push(offset..offset + pattern.len());
+                offset += pattern.len();
+            } else {
+                offset += 1;
+            }
+        }
+        matches
+    }
+}
+
+/// Parses an offset written either in decimal or in hexadecimal with a `0x` prefix.
+pub fn parse_offset(text: &str) -> Option<usize> {
+    let text = text.trim();
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => usize::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::{path, rel_path::rel_path};
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+        });
+    }
+
+    async fn open_test_file(
+        content: Vec<u8>,
+        cx: &mut TestAppContext,
+    ) -> (Arc<FakeFs>, Entity<HexFile>) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/root"), json!({})).await;
+        fs.insert_file(path!("/root/data.bin"), content).await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+        let worktree_id =
+            cx.update(|cx| project.read(cx).worktrees(cx).next().unwrap().read(cx).id());
+        let path = ProjectPath {
+            worktree_id,
+            path: rel_path("data.bin").into(),
+        };
+        let hex_file = cx
+            .update(|cx| HexFile::open(&project, path, cx))
+            .await
+            .unwrap();
+        (fs, hex_file)
+    }
+
+    #[gpui::test]
+    async fn test_write_undo_and_save(cx: &mut TestAppContext) {
+        init_test(cx);
+        let (fs, hex_file) = open_test_file(vec![0x00, 0x11, 0x22, 0x33], cx).await;
+
+        hex_file.update(cx, |hex_file, cx| {
+            assert!(!hex_file.is_dirty());
+
+            // Consecutive writes to the same byte are undone together.
+            hex_file.write_byte(1, 0xa0, false, cx);
+            hex_file.write_byte(1, 0xab, true, cx);
+            hex_file.write_byte(2, 0xcd, false, cx);
+            assert_eq!(hex_file.bytes().to_vec(), [0x00, 0xab, 0xcd, 0x33]);
+            assert!(hex_file.is_dirty());
+
+            assert_eq!(hex_file.undo(cx), Some(2));
+            assert_eq!(hex_file.bytes().to_vec(), [0x00, 0xab, 0x22, 0x33]);
+            assert_eq!(hex_file.undo(cx), Some(1));
+            assert_eq!(hex_file.bytes().to_vec(), [0x00, 0x11, 0x22, 0x33]);
+            assert_eq!(hex_file.undo(cx), None);
+            assert!(!hex_file.is_dirty());
+
+            assert_eq!(hex_file.redo(cx), Some(1));
+            assert!(hex_file.is_dirty());
+
+            // Writing past the end of the file does nothing.
+            hex_file.write_byte(4, 0xff, false, cx);
+            assert_eq!(hex_file.len(), 4);
+        });
+
+        hex_file
+            .update(cx, |hex_file, cx| hex_file.save(fs.clone(), cx))
+            .await
+            .unwrap();
+        assert_eq!(
+            fs.load_bytes(Path::new(path!("/root/data.bin")))
+                .await
+                .unwrap(),
+            [0x00, 0xab, 0x22, 0x33]
+        );
+
+        hex_file.update(cx, |hex_file, cx| {
+            assert!(!hex_file.is_dirty());
+
+            // Undoing past the saved state, or redoing back to it, changes whether the file
+            // is dirty.
+            hex_file.undo(cx);
+            assert!(hex_file.is_dirty());
+            hex_file.redo(cx);
+            assert!(!hex_file.is_dirty());
+
+            // A write after the saved state was undone can't return to it anymore.
+            hex_file.undo(cx);
+            hex_file.write_byte(3, 0xee, false, cx);
+            hex_file.undo(cx);
+            assert_eq!(hex_file.bytes().to_vec(), [0x00, 0x11, 0x22, 0x33]);
+            assert!(hex_file.is_dirty());
+            assert_eq!(hex_file.saved_undo_depth, None);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_large_files_are_not_opened(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(|cx| {
+            cx.update_global::<SettingsStore, _>(|store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings.project.worktree.large_file_threshold_mb = Some(1);
+                });
+            });
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/root"), json!({})).await;
+        fs.insert_file(path!("/root/small.bin"), vec![0; 1024 * 1024])
+            .await;
+        fs.insert_file(path!("/root/large.bin"), vec![0; 1024 * 1024 + 1])
+            .await;
+        let project = Project::test(fs, [path!("/root").as_ref()], cx).await;
+        let worktree_id =
+            cx.update(|cx| project.read(cx).worktrees(cx).next().unwrap().read(cx).id());
+
+        for (name, opens) in [("small.bin", true), ("large.bin", false)] {
+            let path = ProjectPath {
+                worktree_id,
+                path: rel_path(name).into(),
+            };
+            let result = cx.update(|cx| HexFile::open(&project, path, cx)).await;
+            assert_eq!(result.is_ok(), opens, "opening {name}");
+        }
+    }
+
+    #[test]
+    fn test_paged_bytes() {
+        let content = (0..PAGE_LEN * 2 + 10)
+            .map(|offset| offset as u8)
+            .collect::<Vec<_>>();
+        let mut bytes = HexBytes::from(content.clone());
+        assert_eq!(bytes.len(), content.len());
+        assert_eq!(bytes.get(PAGE_LEN + 1), Some(content[PAGE_LEN + 1]));
+        assert_eq!(bytes.get(content.len()), None);
+        assert_eq!(
+            bytes.range(PAGE_LEN - 2..PAGE_LEN + 2).collect::<Vec<_>>(),
+            content[PAGE_LEN - 2..PAGE_LEN + 2]
+        );
+        assert_eq!(bytes.range(content.len() - 2..content.len() + 5).count(), 2);
+
+        // Overwriting a byte only copies its page when a snapshot is still in use.
+        let snapshot = bytes.clone();
+        bytes.set(PAGE_LEN, 0xff);
+        assert_eq!(bytes.get(PAGE_LEN), Some(0xff));
+        assert_eq!(snapshot.get(PAGE_LEN), Some(content[PAGE_LEN]));
+        assert!(Arc::ptr_eq(&bytes.pages[0], &snapshot.pages[0]));
+        assert!(!Arc::ptr_eq(&bytes.pages[1], &snapshot.pages[1]));
+        assert_eq!(snapshot.to_vec(), content);
+    }
+
+    #[test]
+    fn test_parse_byte_pattern() {
+        assert_eq!(
+            BytePattern::parse("de AD be ef").unwrap(),
+            BytePattern(vec![Some(0xde), Some(0xad), Some(0xbe), Some(0xef)])
+        );
+        assert_eq!(
+            BytePattern::parse("7f ?? 4c").unwrap(),
+            BytePattern(vec![Some(0x7f), None, Some(0x4c)])
+        );
+        assert_eq!(
+            BytePattern::parse("\"ELF\"").unwrap(),
+            BytePattern(vec![Some(b'E'), Some(b'L'), Some(b'F')])
+        );
+        assert!(BytePattern::parse("abc").is_err());
+        assert!(BytePattern::parse("zz").is_err());
+        assert!(BytePattern::parse("  ").is_err());
+        assert!(BytePattern::parse("\"\"").is_err());
+    }
+
+    #[test]
+    fn test_find_byte_pattern() {
+        let bytes = HexBytes::from(vec![0x00, 0x7f, 0x45, 0x4c, 0x7f, 0x45, 0x45, 0x45, 0x45]);
+        let pattern = BytePattern::parse("7f ??").unwrap();
+        assert_eq!(pattern.find_all(&bytes), vec![1..3, 4..6]);
+
+        let pattern = BytePattern::parse("45 45").unwrap();
+        assert_eq!(pattern.find_all(&bytes), vec![5..7, 7..9]);
+
+        let pattern = BytePattern::parse("ff").unwrap();
+        assert!(pattern.find_all(&bytes).is_empty());
+    }
+
+    #[test]
+    fn test_parse_offset() {
+        assert_eq!(parse_offset("1024"), Some(1024));
+        assert_eq!(parse_offset(" 0x1F "), Some(0x1f));
+        assert_eq!(parse_offset("0XfF"), Some(0xff));
+        assert_eq!(parse_offset("0x"), None);
+        assert_eq!(parse_offset("12g"), None);
+    }
+}
@@ -123,6 +123,7 @@ pub struct Buffer {
     parse_status: (watch::Sender<ParseStatus>, watch::Receiver<ParseStatus>),
     non_text_state_update_count: usize,
     diagnostics: TreeMap<LanguageServerId, DiagnosticSet>,
+    local_diagnostics: TreeMap<&'static str, DiagnosticSet>,
     remote_selections: TreeMap<ReplicaId, SelectionSet>,
     diagnostics_timestamp: clock::Lamport,
     completion_triggers: BTreeSet<String>,
@@ -140,6 +141,7 @@ pub struct Buffer {
     encoding: &'static Encoding,
     has_bom: bool,
     reload_with_encoding_txns: HashMap<TransactionId, (&'static Encoding, bool)>,
+    is_large_file: bool,
 }
 
 #[derive(Debug)]
@@ -190,6 +192,7 @@ pub struct BufferSnapshot {
     pub syntax: SyntaxSnapshot,
     tree_sitter_data: Arc<TreeSitterData>,
     diagnostics: TreeMap<LanguageServerId, DiagnosticSet>,
+    local_diagnostics: TreeMap<&'static str, DiagnosticSet>,
     remote_selections: TreeMap<ReplicaId, SelectionSet>,
     language: Option<Arc<Language>>,
     file: Option<Arc<dyn File>>,
@@ -1146,6 +1149,7 @@ impl Buffer {
             language: None,
             remote_selections: Default::default(),
             diagnostics: Default::default(),
+            local_diagnostics: Default::default(),
             diagnostics_timestamp: Lamport::MIN,
             completion_triggers: Default::default(),
             completion_triggers_per_language_server: Default::default(),
@@ -1157,6 +1161,7 @@ impl Buffer {
             encoding: encoding_rs::UTF_8,
             has_bom: false,
             reload_with_encoding_txns: HashMap::default(),
+            is_large_file: false,
         }
     }
 
@@ -1184,6 +1189,7 @@ impl Buffer {
                 syntax,
                 file: None,
                 diagnostics: Default::default(),
+                local_diagnostics: Default::default(),
                 remote_selections: Default::default(),
                 tree_sitter_data: Arc::new(tree_sitter_data),
                 language,
@@ -1211,6 +1217,7 @@ impl Buffer {
             tree_sitter_data: Arc::new(tree_sitter_data),
             file: None,
             diagnostics: Default::default(),
+            local_diagnostics: Default::default(),
             remote_selections: Default::default(),
             language: None,
             non_text_state_update_count: 0,
@@ -1241,6 +1248,7 @@ impl Buffer {
             tree_sitter_data: Arc::new(tree_sitter_data),
             file: None,
             diagnostics: Default::default(),
+            local_diagnostics: Default::default(),
             remote_selections: Default::default(),
             language,
             non_text_state_update_count: 0,
@@ -1259,11 +1267,12 @@ impl Buffer {
             syntax_map.snapshot()
         };
 
-        let tree_sitter_data = if self.text.version() != *self.tree_sitter_data.version() {
-            Arc::new(TreeSitterData::new(text))
-        } else {
-            self.tree_sitter_data.clone()
-        };
+        let tree_sitter_data =
+            if !self.is_large_file && self.text.version() != *self.tree_sitter_data.version() {
+                Arc::new(TreeSitterData::new(text))
+            } else {
+                self.tree_sitter_data.clone()
+            };
 
         BufferSnapshot {
             text: text.clone(),
@@ -1272,6 +1281,7 @@ impl Buffer {
             file: self.file.clone(),
             remote_selections: self.remote_selections.clone(),
             diagnostics: self.diagnostics.clone(),
+            local_diagnostics: self.local_diagnostics.clone(),
             language: self.language.clone(),
             non_text_state_update_count: self.non_text_state_update_count,
             capability: self.capability,
@@ -1470,6 +1480,36 @@ impl Buffer {
         self.has_bom = has_bom;
     }
 
+    /// Returns whether the buffer was opened in large-file mode.
+    pub fn is_large_file(&self) -> bool {
+        self.is_large_file
+    }
+
+    /// Marks the buffer as opened in large-file mode, in which it is never parsed
+    /// and isn't reloaded automatically when its file changes on disk.
+    pub fn set_large_file(&mut self, is_large_file: bool) {
+        self.is_large_file = is_large_file;
+    }
+
+    /// Appends the next chunk of a file that is being loaded in large-file mode.
+    ///
+    /// The chunk isn't recorded in the undo history and, unless the buffer has already
+    /// been edited, is treated as part of the saved contents.
+    pub fn append_large_file_chunk(&mut self, text: String, cx: &mut Context<Self>) {
+        let was_dirty = self.is_dirty();
+        let end = self.len();
+        self.start_transaction();
+        self.edit([(end..end, text)], None, cx);
+        if !was_dirty {
+            self.saved_version = self.version();
+            self.has_unsaved_edits
+                .set((self.saved_version.clone(), false));
+        }
+        if let Some(transaction_id) = self.end_transaction(cx) {
+            self.forget_transaction(transaction_id);
+        }
+    }
+
     /// Assign a language to the buffer.
     pub fn set_language_async(&mut self, language: Option<Arc<Language>>, cx: &mut Context<Self>) {
         self.set_language_(language, cfg!(any(test, feature = "test-support")), cx);
@@ -1686,7 +1726,10 @@ impl Buffer {
             let new_state = new_file.disk_state();
             if old_state != new_state {
                 file_changed = true;
-                if !was_dirty && matches!(new_state, DiskState::Present { .. }) {
+                if !was_dirty
+                    && !self.is_large_file
+                    && matches!(new_state, DiskState::Present { .. })
+                {
                     cx.emit(BufferEvent::ReloadNeeded)
                 }
             }
@@ -1819,6 +1862,9 @@ impl Buffer {
     /// parsing in the background.
     #[ztracing::instrument(skip_all)]
     pub fn reparse(&mut self, cx: &mut Context<Self>, may_block: bool) {
+        if self.is_large_file {
+            return;
+        }
         if self.text.version() != *self.tree_sitter_data.version() {
             Self::invalidate_tree_sitter_data(&mut self.tree_sitter_data, self.text.snapshot());
         }
@@ -1957,6 +2003,33 @@ impl Buffer {
         }
     }
 
+    /// Assign to the buffer a set of diagnostics produced locally by `source`, rather
+    /// than by a language server. These diagnostics aren't replicated to collaborators.
+    pub fn set_local_diagnostics(
+        &mut self,
+        source: &'static str,
+        diagnostics: DiagnosticSet,
+        cx: &mut Context<Self>,
+    ) {
+        if diagnostics.is_empty() {
+            if self.local_diagnostics.remove(&source).is_none() {
+                return;
+            }
+        } else {
+            self.local_diagnostics.insert(source, diagnostics);
+        }
+        self.non_text_state_update_count += 1;
+        cx.notify();
+        cx.emit(BufferEvent::DiagnosticsUpdated);
+    }
+
+    /// Returns the diagnostics produced locally by `source`.
+    pub fn local_diagnostics(&self, source: &'static str) -> Vec<&DiagnosticEntry<Anchor>> {
+        self.local_diagnostics
+            .get(&source)
+            .map_or_else(Vec::new, |diagnostics| diagnostics.iter().collect())
+    }
+
     fn request_autoindent(&mut self, cx: &mut Context<Self>, block_budget: Option<Duration>) {
         if let Some(indent_sizes) = self.compute_autoindents() {
             let indent_sizes = cx.background_spawn(indent_sizes);
@@ -4168,6 +4241,71 @@ impl BufferSnapshot {
         result
     }
 
+    /// Returns the named children of the smallest syntax node enclosing the given range.
+    ///
+    /// When that node has a single named child spanning the same range, such as an expression
+    /// statement wrapping a call, the children of the innermost such node are returned instead.
+    pub fn syntax_children<'a, T: ToOffset>(
+        &'a self,
+        range: Range<T>,
+    ) -> Vec<tree_sitter::Node<'a>> {
+        let Some(mut node) = self.syntax_node_enclosing_range(range) else {
+            return Vec::new();
+        };
+        loop {
+            let mut cursor = node.walk();
+            let children = node.named_children(&mut cursor).collect::<Vec<_>>();
+            match children.as_slice() {
+                [child] if child.byte_range() == node.byte_range() => node = *child,
+                _ => return children,
+            }
+        }
+    }
+
+    /// Returns the named siblings of the smallest syntax node enclosing the given range,
+    /// including the node itself.
+    pub fn syntax_siblings<'a, T: ToOffset>(
+        &'a self,
+        range: Range<T>,
+    ) -> Vec<tree_sitter::Node<'a>> {
+        let Some(mut node) = self.syntax_node_enclosing_range(range) else {
+            return Vec::new();
+        };
+        while let Some(parent) = node.parent() {
+            if parent.byte_range() != node.byte_range() {
+                let mut cursor = parent.walk();
+                return parent.named_children(&mut cursor).collect();
+            }
+            node = parent;
+        }
+        Vec::new()
+    }
+
+    fn syntax_node_enclosing_range<'a, T: ToOffset>(
+        &'a self,
+        range: Range<T>,
+    ) -> Option<tree_sitter::Node<'a>> {
+        let range = range.start.to_offset(self)..range.end.to_offset(self);
+        let mut result: Option<tree_sitter::Node<'a>> = None;
+        for layer in self
+            .syntax
+            .layers_for_range(range.clone(), &self.text, true)
+        {
+            let mut cursor = layer.node().walk();
+            if !Self::goto_node_enclosing_range(&mut cursor, &range, false) {
+                continue;
+            }
+            let layer_result = cursor.node();
+            if let Some(previous_result) = &result
+                && previous_result.byte_range().len() <= layer_result.byte_range().len()
+            {
+                continue;
+            }
+            result = Some(layer_result);
+        }
+        result
+    }
+
     /// Returns the root syntax node within the given row
     pub fn syntax_root_ancestor(&self, position: Anchor) -> Option<tree_sitter::Node<'_>> {
         let start_offset = position.to_offset(self);
@@ -5215,7 +5353,7 @@ impl BufferSnapshot {
 
     /// Returns if the buffer contains any diagnostics.
     pub fn has_diagnostics(&self) -> bool {
-        !self.diagnostics.is_empty()
+        !self.diagnostics.is_empty() || !self.local_diagnostics.is_empty()
     }
 
     /// Returns all the diagnostics intersecting the given range.
@@ -5230,8 +5368,9 @@ impl BufferSnapshot {
     {
         let mut iterators: Vec<_> = self
             .diagnostics
-            .iter()
-            .map(|(_, collection)| {
+            .values()
+            .chain(self.local_diagnostics.values())
+            .map(|collection| {
                 collection
                     .range::<T, text::Anchor>(search_range.clone(), self, true, reversed)
                     .peekable()
@@ -5429,6 +5568,7 @@ impl Clone for BufferSnapshot {
             file: self.file.clone(),
             remote_selections: self.remote_selections.clone(),
             diagnostics: self.diagnostics.clone(),
+            local_diagnostics: self.local_diagnostics.clone(),
             language: self.language.clone(),
             tree_sitter_data: self.tree_sitter_data.clone(),
             non_text_state_update_count: self.non_text_state_update_count,
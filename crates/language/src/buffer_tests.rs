@@ -489,6 +489,75 @@ fn test_edit_events(cx: &mut gpui::App) {
     );
 }
 
+#[gpui::test]
+fn test_local_diagnostics(cx: &mut gpui::App) {
+    let buffer = cx.new(|cx| Buffer::local("one two three", cx));
+    let ops = Arc::new(Mutex::new(Vec::new()));
+    buffer.update(cx, |buffer, cx| {
+        let ops = ops.clone();
+        let this = cx.entity();
+        cx.subscribe(&this, move |_, _, event, _| {
+            if let BufferEvent::Operation { operation, .. } = event {
+                ops.lock().push(operation.clone());
+            }
+        })
+        .detach();
+
+        let diagnostic = |range: Range<PointUtf16>, message: &str| DiagnosticEntry {
+            range,
+            diagnostic: Diagnostic {
+                message: message.to_string(),
+                is_primary: true,
+                ..Default::default()
+            },
+        };
+        let local = DiagnosticSet::new(
+            [diagnostic(
+                PointUtf16::new(0, 4)..PointUtf16::new(0, 7),
+                "local",
+            )],
+            buffer,
+        );
+        buffer.set_local_diagnostics("test", local, cx);
+        let lsp = DiagnosticSet::new(
+            [diagnostic(
+                PointUtf16::new(0, 0)..PointUtf16::new(0, 3),
+                "lsp",
+            )],
+            buffer,
+        );
+        buffer.update_diagnostics(LanguageServerId(0), lsp, cx);
+    });
+
+    let snapshot = buffer.read(cx).snapshot();
+    assert!(snapshot.has_diagnostics());
+    assert_eq!(
+        snapshot
+            .diagnostics_in_range::<_, usize>(0..snapshot.len(), false)
+            .map(|entry| (entry.range, entry.diagnostic.message.as_str()))
+            .collect::<Vec<_>>(),
+        [(0..3, "lsp"), (4..7, "local")]
+    );
+    assert_eq!(buffer.read(cx).local_diagnostics("test").len(), 1);
+    assert_eq!(buffer.read(cx).buffer_diagnostics(None).len(), 1);
+    // Only the language server's diagnostics are replicated.
+    assert_eq!(ops.lock().len(), 1);
+
+    buffer.update(cx, |buffer, cx| {
+        let empty = DiagnosticSet::new([], buffer);
+        buffer.set_local_diagnostics("test", empty, cx);
+    });
+    assert!(buffer.read(cx).local_diagnostics("test").is_empty());
+    assert_eq!(
+        buffer
+            .read(cx)
+            .snapshot()
+            .diagnostics_in_range::<_, usize>(0..13, false)
+            .count(),
+        1
+    );
+}
+
 #[gpui::test]
 async fn test_apply_diff(cx: &mut TestAppContext) {
     let (text, offsets) = marked_text_offsets(
@@ -6,6 +6,7 @@ mod request;
 mod role;
 mod telemetry;
 pub mod tool_schema;
+mod traffic_log;
 
 #[cfg(any(test, feature = "test-support"))]
 pub mod fake_provider;
@@ -39,6 +40,7 @@ pub use crate::request::*;
 pub use crate::role::*;
 pub use crate::telemetry::*;
 pub use crate::tool_schema::LanguageModelToolSchemaFormat;
+pub use crate::traffic_log::*;
 pub use zed_env_vars::{EnvVar, env_var};
 
 pub const ANTHROPIC_PROVIDER_ID: LanguageModelProviderId =
@@ -843,6 +845,15 @@ pub trait LanguageModelProvider: 'static {
         cx: &mut App,
     ) -> AnyView;
     fn reset_credentials(&self, cx: &mut App) -> Task<Result<()>>;
+    /// Names of the credential profiles that requests to this provider can select via
+    /// [`LanguageModelRequest::credential_profile`]. Empty when the provider has a single key.
+    fn credential_profiles(&self, _cx: &App) -> Vec<SharedString> {
+        Vec::new()
+    }
+    /// The credential profile used by requests that don't select one.
+    fn default_credential_profile(&self, _cx: &App) -> Option<SharedString> {
+        None
+    }
 }
 
 #[derive(Default, Clone, PartialEq, Eq)]
@@ -452,6 +452,10 @@ pub struct LanguageModelRequest {
     pub temperature: Option<f32>,
     pub thinking_allowed: bool,
     pub thinking_effort: Option<String>,
+    /// The named credential profile to authenticate this request with. Providers that don't
+    /// support credential profiles ignore this, and `None` uses the provider's default profile.
+    #[serde(default)]
+    pub credential_profile: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
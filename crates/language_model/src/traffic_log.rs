@@ -0,0 +1,617 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures::channel::mpsc;
+use futures::{FutureExt, StreamExt, future::BoxFuture, stream::BoxStream};
+use gpui::{App, AsyncApp, Global};
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::{
+    LanguageModel, LanguageModelCompletionError, LanguageModelCompletionEvent, LanguageModelId,
+    LanguageModelName, LanguageModelProviderId, LanguageModelProviderName, LanguageModelRequest,
+    TokenUsage,
+};
+
+/// The maximum number of requests retained by the traffic log.
+const MAX_ENTRIES: usize = 100;
+
+/// Headers whose values are replaced before a request is stored in the traffic log.
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "api-key",
+    "x-api-key",
+    "x-goog-api-key",
+    "cookie",
+];
+
+/// Query parameters whose values are replaced before a request is stored in the traffic log.
+const SENSITIVE_QUERY_PARAMS: &[&str] = &["key", "api_key", "api-key", "access_token"];
+
+/// The placeholder that replaces redacted credentials.
+pub const REDACTED_API_KEY: &str = "$API_KEY";
+
+struct GlobalLanguageModelTrafficLog(Arc<LanguageModelTrafficLog>);
+
+impl Global for GlobalLanguageModelTrafficLog {}
+
+/// Records language model requests and the responses streamed back for them, so they can be
+/// inspected when a model misbehaves.
+///
+/// Recording is disabled until [`LanguageModelTrafficLog::set_enabled`] is called, which the
+/// inspector view does when it is first opened.
+#[derive(Default)]
+pub struct LanguageModelTrafficLog {
+    state: Mutex<TrafficLogState>,
+}
+
+#[derive(Default)]
+struct TrafficLogState {
+    enabled: bool,
+    next_entry_id: usize,
+    entries: VecDeque<Arc<Mutex<TrafficLogEntry>>>,
+    subscribers: Vec<mpsc::UnboundedSender<TrafficLogEvent>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TrafficLogEntryId(pub usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrafficLogEvent {
+    EntryAdded(TrafficLogEntryId),
+    EntryUpdated(TrafficLogEntryId),
+    Cleared,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrafficLogEntryStatus {
+    Pending,
+    Streaming,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl TrafficLogEntryStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Streaming => "streaming",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// A single request sent to a language model, along with everything that came back for it.
+#[derive(Clone, Debug)]
+pub struct TrafficLogEntry {
+    pub id: TrafficLogEntryId,
+    pub provider_id: LanguageModelProviderId,
+    pub provider_name: LanguageModelProviderName,
+    pub model_id: LanguageModelId,
+    pub model_name: LanguageModelName,
+    pub request: LanguageModelRequest,
+    /// The HTTP request that was sent, for providers that report it.
+    pub http_request: Option<HttpRequestSnapshot>,
+    pub status: TrafficLogEntryStatus,
+    pub started_at: Instant,
+    /// Time between sending the request and receiving the first response event.
+    pub time_to_first_event: Option<Duration>,
+    /// Time between sending the request and the end of the response stream.
+    pub duration: Option<Duration>,
+    /// Events as reported by the provider's wire protocol, before they are mapped to
+    /// [`LanguageModelCompletionEvent`]s.
+    pub raw_events: Vec<serde_json::Value>,
+    pub events: Vec<LanguageModelCompletionEvent>,
+    pub token_usage: Option<TokenUsage>,
+    pub error: Option<String>,
+}
+
+impl TrafficLogEntry {
+    pub fn to_json(&self) -> serde_json::Value {
+        let http_request = self.http_request.as_ref().map(|request| {
+            let body = serde_json::from_str::<serde_json::Value>(&request.body)
+                .unwrap_or_else(|_| serde_json::Value::String(request.body.clone()));
+            serde_json::json!({
+                "method": request.method,
+                "url": request.url,
+                "headers": request.headers,
+                "body": body,
+            })
+        });
+
+        serde_json::json!({
+            "provider": self.provider_id.0.as_ref(),
+            "model": self.model_id.0.as_ref(),
+            "status": self.status.label(),
+            "time_to_first_event_ms": self.time_to_first_event.map(|d| d.as_millis() as u64),
+            "duration_ms": self.duration.map(|d| d.as_millis() as u64),
+            "token_usage": self.token_usage,
+            "error": self.error,
+            "request": self.request,
+            "http_request": http_request,
+            "raw_events": self.raw_events,
+            "events": self.events,
+        })
+    }
+}
+
+/// An HTTP request as sent to a provider, with credentials redacted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpRequestSnapshot {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpRequestSnapshot {
+    /// Creates a snapshot of an HTTP request, redacting API keys found in the headers or
+    /// query string.
+    pub fn new(
+        method: impl Into<String>,
+        url: impl AsRef<str>,
+        headers: impl IntoIterator<Item = (impl Into<String>, impl AsRef<str>)>,
+        body: impl Into<String>,
+    ) -> Self {
+        Self {
+            method: method.into(),
+            url: redact_url(url.as_ref()),
+            headers: headers
+                .into_iter()
+                .map(|(name, value)| {
+                    let name = name.into();
+                    let value = redact_header_value(&name, value.as_ref());
+                    (name, value)
+                })
+                .collect(),
+            body: body.into(),
+        }
+    }
+
+    /// Redacts the values of the named headers as well. Used for headers whose names don't mark
+    /// them as credentials, such as gateway keys configured by the user.
+    pub fn redact_headers<'a>(mut self, names: impl IntoIterator<Item = &'a str>) -> Self {
+        for name in names {
+            for (header, value) in &mut self.headers {
+                if header.eq_ignore_ascii_case(name) {
+                    *value = REDACTED_API_KEY.to_string();
+                }
+            }
+        }
+        self
+    }
+
+    /// Renders the request as a `curl` command that can be pasted into a shell. Credentials are
+    /// referenced as `$API_KEY` so that they are picked up from the environment.
+    pub fn to_curl(&self) -> String {
+        let mut command = format!(
+            "curl -X {} {}",
+            self.method,
+            quote_shell_argument(&self.url)
+        );
+        for (name, value) in &self.headers {
+            command.push_str(" \\\n  -H ");
+            command.push_str(&quote_shell_argument(&format!("{name}: {value}")));
+        }
+        if !self.body.is_empty() {
+            command.push_str(" \\\n  --data-raw ");
+            command.push_str(&quote_shell_argument(&self.body));
+        }
+        command
+    }
+}
+
+fn redact_header_value(name: &str, value: &str) -> String {
+    if !SENSITIVE_HEADERS
+        .iter()
+        .any(|sensitive| name.eq_ignore_ascii_case(sensitive))
+    {
+        return value.to_string();
+    }
+
+    match value.split_once(' ') {
+        Some((scheme, _)) if name.eq_ignore_ascii_case("authorization") => {
+            format!("{scheme} {REDACTED_API_KEY}")
+        }
+        _ => REDACTED_API_KEY.to_string(),
+    }
+}
+
+fn redact_url(url: &str) -> String {
+    let Some((base, query)) = url.split_once('?') else {
+        return url.to_string();
+    };
+
+    let query = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _))
+                if SENSITIVE_QUERY_PARAMS
+                    .iter()
+                    .any(|sensitive| name.eq_ignore_ascii_case(sensitive)) =>
+            {
+                format!("{name}={REDACTED_API_KEY}")
+            }
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+    format!("{base}?{query}")
+}
+
+fn quote_shell_argument(argument: &str) -> String {
+    // Double quotes keep the `$API_KEY` placeholder expandable; everything else is escaped.
+    let mut quoted = String::with_capacity(argument.len() + 2);
+    quoted.push('"');
+    for (index, ch) in argument.char_indices() {
+        match ch {
+            '"' | '\\' | '`' => {
+                quoted.push('\\');
+                quoted.push(ch);
+            }
+            '$' if !argument[index..].starts_with(REDACTED_API_KEY) => quoted.push_str("\\$"),
+            _ => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl LanguageModelTrafficLog {
+    pub fn global(cx: &mut App) -> Arc<Self> {
+        if let Some(global) = cx.try_global::<GlobalLanguageModelTrafficLog>() {
+            global.0.clone()
+        } else {
+            let log = Arc::new(Self::default());
+            cx.set_global(GlobalLanguageModelTrafficLog(log.clone()));
+            log
+        }
+    }
+
+    /// Returns the traffic log if it has been created and recording is enabled.
+    pub fn try_global(cx: &AsyncApp) -> Option<Arc<Self>> {
+        cx.try_read_global::<GlobalLanguageModelTrafficLog, _>(|global, _| global.0.clone())
+            .filter(|log| log.is_enabled())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.state.lock().enabled
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.state.lock().enabled = enabled;
+    }
+
+    pub fn subscribe(&self) -> mpsc::UnboundedReceiver<TrafficLogEvent> {
+        let (tx, rx) = mpsc::unbounded();
+        self.state.lock().subscribers.push(tx);
+        rx
+    }
+
+    pub fn entry_ids(&self) -> Vec<TrafficLogEntryId> {
+        self.state
+            .lock()
+            .entries
+            .iter()
+            .map(|entry| entry.lock().id)
+            .collect()
+    }
+
+    /// Reads an entry without cloning it, since entries hold entire requests.
+    pub fn read_entry<R>(
+        &self,
+        id: TrafficLogEntryId,
+        read: impl FnOnce(&TrafficLogEntry) -> R,
+    ) -> Option<R> {
+        let entry = self
+            .state
+            .lock()
+            .entries
+            .iter()
+            .find(|entry| entry.lock().id == id)
+            .cloned()?;
+        let entry = entry.lock();
+        Some(read(&entry))
+    }
+
+    pub fn clear(&self) {
+        let mut state = self.state.lock();
+        state.entries.clear();
+        state.notify(TrafficLogEvent::Cleared);
+    }
+
+    /// Starts recording a request to the given model, if recording is enabled.
+    pub fn start_entry(
+        model: &dyn LanguageModel,
+        request: &LanguageModelRequest,
+        cx: &AsyncApp,
+    ) -> Option<TrafficLogEntryHandle> {
+        let log = Self::try_global(cx)?;
+        let entry = {
+            let mut state = log.state.lock();
+            let id = TrafficLogEntryId(state.next_entry_id);
+            state.next_entry_id += 1;
+            let entry = Arc::new(Mutex::new(TrafficLogEntry {
+                id,
+                provider_id: model.provider_id(),
+                provider_name: model.provider_name(),
+                model_id: model.id(),
+                model_name: model.name(),
+                request: request.clone(),
+                http_request: None,
+                status: TrafficLogEntryStatus::Pending,
+                started_at: Instant::now(),
+                time_to_first_event: None,
+                duration: None,
+                raw_events: Vec::new(),
+                events: Vec::new(),
+                token_usage: None,
+                error: None,
+            }));
+            state.entries.push_back(entry.clone());
+            while state.entries.len() > MAX_ENTRIES {
+                state.entries.pop_front();
+            }
+            state.notify(TrafficLogEvent::EntryAdded(id));
+            entry
+        };
+
+        Some(TrafficLogEntryHandle { log, entry })
+    }
+}
+
+impl TrafficLogState {
+    fn notify(&mut self, event: TrafficLogEvent) {
+        self.subscribers
+            .retain(|tx| tx.unbounded_send(event).is_ok());
+    }
+}
+
+/// A handle used by providers to record what happens to a request.
+#[derive(Clone)]
+pub struct TrafficLogEntryHandle {
+    log: Arc<LanguageModelTrafficLog>,
+    entry: Arc<Mutex<TrafficLogEntry>>,
+}
+
+impl TrafficLogEntryHandle {
+    fn update(&self, f: impl FnOnce(&mut TrafficLogEntry)) {
+        let id = {
+            let mut entry = self.entry.lock();
+            f(&mut entry);
+            entry.id
+        };
+        self.log
+            .state
+            .lock()
+            .notify(TrafficLogEvent::EntryUpdated(id));
+    }
+
+    pub fn set_http_request(&self, http_request: HttpRequestSnapshot) {
+        self.update(|entry| entry.http_request = Some(http_request));
+    }
+
+    /// Records an event as it was received from the provider's API.
+    pub fn record_raw_event(&self, event: &impl Serialize) {
+        if let Ok(event) = serde_json::to_value(event) {
+            self.update(|entry| {
+                mark_first_event(entry);
+                entry.raw_events.push(event);
+            });
+        }
+    }
+
+    pub fn record_event(
+        &self,
+        event: &Result<LanguageModelCompletionEvent, LanguageModelCompletionError>,
+    ) {
+        self.update(|entry| {
+            mark_first_event(entry);
+            match event {
+                Ok(LanguageModelCompletionEvent::UsageUpdate(usage)) => {
+                    entry.token_usage = Some(*usage);
+                    entry
+                        .events
+                        .push(LanguageModelCompletionEvent::UsageUpdate(*usage));
+                }
+                Ok(event) => entry.events.push(event.clone()),
+                Err(error) => entry.error = Some(error.to_string()),
+            }
+        });
+    }
+
+    pub fn finish(&self, error: Option<&LanguageModelCompletionError>) {
+        self.update(|entry| {
+            entry.duration = Some(entry.started_at.elapsed());
+            if let Some(error) = error {
+                entry.error = Some(error.to_string());
+                entry.status = TrafficLogEntryStatus::Failed;
+            } else if entry.error.is_some() {
+                entry.status = TrafficLogEntryStatus::Failed;
+            } else {
+                entry.status = TrafficLogEntryStatus::Completed;
+            }
+        });
+    }
+}
+
+fn mark_first_event(entry: &mut TrafficLogEntry) {
+    if entry.time_to_first_event.is_none() {
+        entry.time_to_first_event = Some(entry.started_at.elapsed());
+        entry.status = TrafficLogEntryStatus::Streaming;
+    }
+}
+
+/// Marks the request as cancelled if the response is dropped before the stream ends.
+struct CancelOnDrop(TrafficLogEntryHandle);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        let is_unfinished = matches!(
+            self.0.entry.lock().status,
+            TrafficLogEntryStatus::Pending | TrafficLogEntryStatus::Streaming
+        );
+        if is_unfinished {
+            self.0.update(|entry| {
+                entry.duration = Some(entry.started_at.elapsed());
+                entry.status = TrafficLogEntryStatus::Cancelled;
+            });
+        }
+    }
+}
+
+/// Wraps a provider's completion future so that every event it produces is recorded in the
+/// traffic log. Does nothing when `handle` is `None`.
+pub fn log_completion_traffic(
+    handle: Option<TrafficLogEntryHandle>,
+    future: BoxFuture<
+        'static,
+        Result<
+            BoxStream<'static, Result<LanguageModelCompletionEvent, LanguageModelCompletionError>>,
+            LanguageModelCompletionError,
+        >,
+    >,
+) -> BoxFuture<
+    'static,
+    Result<
+        BoxStream<'static, Result<LanguageModelCompletionEvent, LanguageModelCompletionError>>,
+        LanguageModelCompletionError,
+    >,
+> {
+    let Some(handle) = handle else {
+        return future;
+    };
+
+    async move {
+        let guard = CancelOnDrop(handle);
+        let stream = match future.await {
+            Ok(stream) => stream,
+            Err(error) => {
+                guard.0.finish(Some(&error));
+                return Err(error);
+            }
+        };
+
+        Ok(
+            futures::stream::unfold(Some((stream, guard)), |state| async move {
+                let (mut stream, guard) = state?;
+                match stream.next().await {
+                    Some(event) => {
+                        guard.0.record_event(&event);
+                        Some((event, Some((stream, guard))))
+                    }
+                    None => {
+                        guard.0.finish(None);
+                        None
+                    }
+                }
+            })
+            .boxed(),
+        )
+    }
+    .boxed()
+}
+
+/// Records every event of a provider's wire-level stream before it is mapped to
+/// [`LanguageModelCompletionEvent`]s. Returns the stream unchanged when `handle` is `None`.
+pub fn log_raw_traffic<T, E>(
+    handle: Option<TrafficLogEntryHandle>,
+    events: BoxStream<'static, Result<T, E>>,
+) -> BoxStream<'static, Result<T, E>>
+where
+    T: Serialize + Send + 'static,
+    E: Send + 'static,
+{
+    let Some(handle) = handle else {
+        return events;
+    };
+
+    events
+        .inspect(move |event| {
+            if let Ok(event) = event {
+                handle.record_raw_event(event);
+            }
+        })
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_request_snapshot_redacts_credentials() {
+        let snapshot = HttpRequestSnapshot::new(
+            "POST",
+            "https://example.com/v1/models/gemini:streamGenerateContent?alt=sse&key=secret",
+            [
+                ("Content-Type", "application/json"),
+                ("Authorization", "Bearer sk-secret"),
+                ("x-api-key", "sk-secret"),
+            ],
+            "{}",
+        );
+
+        assert_eq!(
+            snapshot.url,
+            "https://example.com/v1/models/gemini:streamGenerateContent?alt=sse&key=$API_KEY"
+        );
+        assert_eq!(
+            snapshot.headers,
+            vec![
+                ("Content-Type".to_string(), "application/json".to_string()),
+                ("Authorization".to_string(), "Bearer $API_KEY".to_string()),
+                ("x-api-key".to_string(), "$API_KEY".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_http_request_snapshot_redacts_named_headers() {
+        let snapshot = HttpRequestSnapshot::new(
+            "POST",
+            "https://example.com/v1/chat/completions",
+            [
+                ("Ocp-Apim-Subscription-Key", "gateway-secret"),
+                ("X-Provider", "openai"),
+            ],
+            "{}",
+        )
+        .redact_headers(["ocp-apim-subscription-key"]);
+
+        assert_eq!(
+            snapshot.headers,
+            vec![
+                (
+                    "Ocp-Apim-Subscription-Key".to_string(),
+                    "$API_KEY".to_string()
+                ),
+                ("X-Provider".to_string(), "openai".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_http_request_snapshot_to_curl() {
+        let snapshot = HttpRequestSnapshot::new(
+            "POST",
+            "https://example.com/v1/chat/completions",
+            [("Authorization", "Bearer sk-secret")],
+            r#"{"messages":[{"content":"echo \"$HOME\""}]}"#,
+        );
+
+        assert_eq!(
+            snapshot.to_curl(),
+            concat!(
+                "curl -X POST \"https://example.com/v1/chat/completions\" \\\n",
+                "  -H \"Authorization: Bearer $API_KEY\" \\\n",
+                "  --data-raw \"{\\\"messages\\\":[{\\\"content\\\":\\\"echo \\\\\\\"\\$HOME\\\\\\\"\\\"}]}\"",
+            )
+        );
+    }
+}
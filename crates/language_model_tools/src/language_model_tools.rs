@@ -0,0 +1,555 @@
+use std::{sync::Arc, time::Duration};
+
+use collections::{HashMap, HashSet};
+use futures::StreamExt;
+use gpui::{
+    App, ClipboardItem, Empty, Entity, EventEmitter, FocusHandle, Focusable, ListAlignment,
+    ListState, StyleRefinement, Task, TextStyleRefinement, Window, actions, list, prelude::*,
+};
+use language::LanguageRegistry;
+use language_model::{
+    LanguageModelTrafficLog, TrafficLogEntry, TrafficLogEntryId, TrafficLogEntryStatus,
+    TrafficLogEvent,
+};
+use markdown::{CodeBlockRenderer, Markdown, MarkdownElement, MarkdownStyle};
+use project::Project;
+use settings::Settings;
+use theme::ThemeSettings;
+use ui::{Tooltip, WithScrollbar, prelude::*};
+use workspace::{
+    Item, ItemHandle, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView, Workspace,
+};
+
+actions!(dev, [OpenLanguageModelLogs]);
+
+/// How often the Markdown of expanded entries is rebuilt while their responses stream in.
+const EXPANDED_REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(
+        |workspace: &mut Workspace, _window, _cx: &mut Context<Workspace>| {
+            workspace.register_action(|workspace, _: &OpenLanguageModelLogs, window, cx| {
+                let language_model_tools =
+                    Box::new(cx.new(|cx| LanguageModelTools::new(workspace.project().clone(), cx)));
+                workspace.add_item_to_active_pane(language_model_tools, None, true, window, cx);
+            });
+        },
+    )
+    .detach();
+}
+
+struct LanguageModelTools {
+    project: Entity<Project>,
+    focus_handle: FocusHandle,
+    traffic_log: Arc<LanguageModelTrafficLog>,
+    entry_ids: Vec<TrafficLogEntryId>,
+    list_state: ListState,
+    expanded: HashSet<TrafficLogEntryId>,
+    expanded_md: HashMap<TrafficLogEntryId, Entity<Markdown>>,
+    stale_expanded_md: HashSet<TrafficLogEntryId>,
+    refresh_expanded_md_task: Option<Task<()>>,
+    _subscription: Task<()>,
+}
+
+impl LanguageModelTools {
+    fn new(project: Entity<Project>, cx: &mut Context<Self>) -> Self {
+        let traffic_log = LanguageModelTrafficLog::global(cx);
+        traffic_log.set_enabled(true);
+
+        let mut events = traffic_log.subscribe();
+        let subscription = cx.spawn(async move |this, cx| {
+            while let Some(event) = events.next().await {
+                // Streaming responses produce an update per event, so coalesce whatever has
+                // queued up before touching the view.
+                let mut batch = vec![event];
+                while let Ok(Some(event)) = events.try_next() {
+                    batch.push(event);
+                }
+                if this
+                    .update(cx, |this, cx| this.handle_events(batch, cx))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        let entry_ids = traffic_log.entry_ids();
+        let list_state = ListState::new(entry_ids.len(), ListAlignment::Bottom, px(2048.));
+
+        Self {
+            project,
+            focus_handle: cx.focus_handle(),
+            traffic_log,
+            entry_ids,
+            list_state,
+            expanded: HashSet::default(),
+            expanded_md: HashMap::default(),
+            stale_expanded_md: HashSet::default(),
+            refresh_expanded_md_task: None,
+            _subscription: subscription,
+        }
+    }
+
+    fn handle_events(&mut self, events: Vec<TrafficLogEvent>, cx: &mut Context<Self>) {
+        let mut updated = HashSet::default();
+        for event in events {
+            match event {
+                TrafficLogEvent::EntryAdded(_) | TrafficLogEvent::Cleared => {
+                    self.entry_ids = self.traffic_log.entry_ids();
+                    self.expanded.retain(|id| self.entry_ids.contains(id));
+                    self.expanded_md.retain(|id, _| self.entry_ids.contains(id));
+                    self.list_state.reset(self.entry_ids.len());
+                }
+                TrafficLogEvent::EntryUpdated(id) => {
+                    updated.insert(id);
+                }
+            }
+        }
+
+        self.stale_expanded_md
+            .extend(updated.into_iter().filter(|id| self.expanded.contains(id)));
+        if !self.stale_expanded_md.is_empty() && self.refresh_expanded_md_task.is_none() {
+            self.refresh_expanded_md_task = Some(cx.spawn(async move |this, cx| {
+                cx.background_executor()
+                    .timer(EXPANDED_REFRESH_INTERVAL)
+                    .await;
+                this.update(cx, |this, cx| {
+                    this.refresh_expanded_md_task = None;
+                    for id in std::mem::take(&mut this.stale_expanded_md) {
+                        if this.expanded.contains(&id) {
+                            this.refresh_expanded_md(id, cx);
+                        }
+                    }
+                    cx.notify();
+                })
+                .ok();
+            }));
+        }
+        cx.notify();
+    }
+
+    fn refresh_expanded_md(&mut self, id: TrafficLogEntryId, cx: &mut Context<Self>) {
+        let Some(json) = self.traffic_log.read_entry(id, TrafficLogEntry::to_json) else {
+            return;
+        };
+        let language_registry = self.project.read(cx).languages().clone();
+        self.expanded_md
+            .insert(id, expanded_json_md(&json, &language_registry, cx));
+    }
+
+    fn serialize_entries(&self) -> String {
+        let entries = self
+            .entry_ids
+            .iter()
+            .filter_map(|id| self.traffic_log.read_entry(*id, TrafficLogEntry::to_json))
+            .collect::<Vec<_>>();
+        serde_json::to_string_pretty(&entries).unwrap_or_default()
+    }
+
+    fn clear_entries(&mut self, cx: &mut Context<Self>) {
+        self.traffic_log.clear();
+        self.entry_ids.clear();
+        self.expanded.clear();
+        self.expanded_md.clear();
+        self.stale_expanded_md.clear();
+        self.list_state.reset(0);
+        cx.notify();
+    }
+
+    fn render_entry(
+        &mut self,
+        index: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let Some(&id) = self.entry_ids.get(index) else {
+            return Empty.into_any();
+        };
+        let Some(summary) = self.traffic_log.read_entry(id, EntrySummary::new) else {
+            return Empty.into_any();
+        };
+
+        let base_size = TextSize::Editor.rems(cx);
+        let theme_settings = ThemeSettings::get_global(cx);
+        let text_style = window.text_style();
+        let colors = cx.theme().colors();
+        let expanded = self.expanded.contains(&id);
+
+        v_flex()
+            .id(index)
+            .group("entry")
+            .cursor_pointer()
+            .font_buffer(cx)
+            .w_full()
+            .py_3()
+            .pl_4()
+            .pr_5()
+            .gap_2()
+            .items_start()
+            .text_size(base_size)
+            .border_color(colors.border)
+            .border_b_1()
+            .hover(|this| this.bg(colors.element_background.opacity(0.5)))
+            .on_click(cx.listener(move |this, _, _, cx| {
+                if this.expanded.remove(&id) {
+                    this.expanded_md.remove(&id);
+                    this.stale_expanded_md.remove(&id);
+                } else {
+                    this.expanded.insert(id);
+                    this.refresh_expanded_md(id, cx);
+                    this.list_state.scroll_to_reveal_item(index);
+                }
+                cx.notify()
+            }))
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .flex_shrink_0()
+                    .child(
+                        Icon::new(if expanded {
+                            IconName::ChevronDown
+                        } else {
+                            IconName::ChevronRight
+                        })
+                        .color(Color::Muted)
+                        .size(IconSize::Small),
+                    )
+                    .child(
+                        Label::new(summary.provider.clone())
+                            .buffer_font(cx)
+                            .color(Color::Muted),
+                    )
+                    .child(Label::new(summary.model.clone()).buffer_font(cx))
+                    .child(div().flex_1())
+                    .children(summary.tokens.clone().map(|tokens| {
+                        Label::new(tokens)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                    }))
+                    .children(summary.latency.clone().map(|latency| {
+                        Label::new(latency)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                    }))
+                    .child(ui::Chip::new(summary.status.label()).label_color(
+                        match summary.status {
+                            TrafficLogEntryStatus::Failed => Color::Error,
+                            TrafficLogEntryStatus::Cancelled => Color::Warning,
+                            TrafficLogEntryStatus::Completed => Color::Success,
+                            TrafficLogEntryStatus::Pending | TrafficLogEntryStatus::Streaming => {
+                                Color::Muted
+                            }
+                        },
+                    ))
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .visible_on_hover("entry")
+                            .child(
+                                IconButton::new(("copy-curl", index), IconName::Terminal)
+                                    .icon_size(IconSize::Small)
+                                    .tooltip(Tooltip::text("Copy as curl"))
+                                    .disabled(!summary.has_http_request)
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        let curl = this.traffic_log.read_entry(id, |entry| {
+                                            entry.http_request.as_ref().map(|r| r.to_curl())
+                                        });
+                                        if let Some(curl) = curl.flatten() {
+                                            cx.write_to_clipboard(ClipboardItem::new_string(curl));
+                                        }
+                                        cx.stop_propagation();
+                                    })),
+                            )
+                            .child(
+                                IconButton::new(("copy-json", index), IconName::Copy)
+                                    .icon_size(IconSize::Small)
+                                    .tooltip(Tooltip::text("Copy as JSON"))
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        if let Some(json) = this
+                                            .traffic_log
+                                            .read_entry(id, TrafficLogEntry::to_json)
+                                        {
+                                            cx.write_to_clipboard(ClipboardItem::new_string(
+                                                serde_json::to_string_pretty(&json)
+                                                    .unwrap_or_default(),
+                                            ));
+                                        }
+                                        cx.stop_propagation();
+                                    })),
+                            ),
+                    ),
+            )
+            .when_some(summary.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .pl_6()
+                        .child(Label::new(error).size(LabelSize::Small).color(Color::Error)),
+                )
+            })
+            .when_some(
+                expanded.then(|| summary.missing_traffic_note()).flatten(),
+                |this, note| {
+                    this.child(
+                        div()
+                            .pl_6()
+                            .child(Label::new(note).size(LabelSize::Small).color(Color::Muted)),
+                    )
+                },
+            )
+            .when_some(
+                expanded
+                    .then(|| self.expanded_md.get(&id).cloned())
+                    .flatten(),
+                |this, json_md| {
+                    this.child(
+                        div().pl_6().w_full().child(
+                            MarkdownElement::new(
+                                json_md,
+                                MarkdownStyle {
+                                    base_text_style: text_style,
+                                    selection_background_color: colors.element_selection_background,
+                                    syntax: cx.theme().syntax().clone(),
+                                    code_block_overflow_x_scroll: true,
+                                    code_block: StyleRefinement {
+                                        text: TextStyleRefinement {
+                                            font_family: Some(
+                                                theme_settings.buffer_font.family.clone(),
+                                            ),
+                                            font_size: Some((base_size * 0.8).into()),
+                                            ..Default::default()
+                                        },
+                                        ..Default::default()
+                                    },
+                                    ..Default::default()
+                                },
+                            )
+                            .code_block_renderer(
+                                CodeBlockRenderer::Default {
+                                    copy_button: false,
+                                    copy_button_on_hover: true,
+                                    border: false,
+                                },
+                            ),
+                        ),
+                    )
+                },
+            )
+            .into_any()
+    }
+}
+
+/// The parts of a [`TrafficLogEntry`] shown in its header row.
+struct EntrySummary {
+    provider: SharedString,
+    model: SharedString,
+    status: TrafficLogEntryStatus,
+    latency: Option<SharedString>,
+    tokens: Option<SharedString>,
+    error: Option<SharedString>,
+    has_http_request: bool,
+    has_raw_events: bool,
+}
+
+impl EntrySummary {
+    fn new(entry: &TrafficLogEntry) -> Self {
+        let latency = match (entry.time_to_first_event, entry.duration) {
+            (Some(first_event), Some(duration)) => Some(format!(
+                "first event {} · total {}",
+                format_duration(first_event),
+                format_duration(duration)
+            )),
+            (Some(first_event), None) => {
+                Some(format!("first event {}", format_duration(first_event)))
+            }
+            (None, Some(duration)) => Some(format!("total {}", format_duration(duration))),
+            (None, None) => None,
+        };
+
+        let tokens = entry.token_usage.map(|usage| {
+            let mut tokens = format!("{} in · {} out", usage.input_tokens, usage.output_tokens);
+            if usage.cache_read_input_tokens > 0 || usage.cache_creation_input_tokens > 0 {
+                tokens.push_str(&format!(
+                    " · {} cached · {} cache write",
+                    usage.cache_read_input_tokens, usage.cache_creation_input_tokens
+                ));
+            }
+            tokens
+        });
+
+        Self {
+            provider: entry.provider_name.0.clone(),
+            model: entry.model_name.0.clone(),
+            status: entry.status,
+            latency: latency.map(Into::into),
+            tokens: tokens.map(Into::into),
+            error: entry.error.clone().map(Into::into),
+            has_http_request: entry.http_request.is_some(),
+            has_raw_events: !entry.raw_events.is_empty(),
+        }
+    }
+
+    /// Explains why parts of the entry are absent. Only some providers report the HTTP request
+    /// they send and the wire events they receive; the others are recorded through their
+    /// mapped completion events alone.
+    fn missing_traffic_note(&self) -> Option<SharedString> {
+        let has_response = matches!(
+            self.status,
+            TrafficLogEntryStatus::Streaming | TrafficLogEntryStatus::Completed
+        );
+        let note = match (self.has_http_request, self.has_raw_events || !has_response) {
+            (true, true) => return None,
+            (false, true) => "This provider doesn't report the HTTP request it sends.",
+            (true, false) => {
+                "This provider doesn't report raw response events; only mapped events are shown."
+            }
+            (false, false) => {
+                "This provider doesn't report the HTTP request or raw response events; only mapped events are shown."
+            }
+        };
+        Some(note.into())
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    if duration.as_secs() > 0 {
+        format!("{:.1}s", duration.as_secs_f64())
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
+
+fn expanded_json_md(
+    json: &serde_json::Value,
+    language_registry: &Arc<LanguageRegistry>,
+    cx: &mut App,
+) -> Entity<Markdown> {
+    let json = serde_json::to_string_pretty(json).unwrap_or_default();
+    let json_md = format!("```json\n{}\n```", json);
+    cx.new(|cx| Markdown::new(json_md.into(), Some(language_registry.clone()), None, cx))
+}
+
+enum LanguageModelToolsEvent {}
+
+impl EventEmitter<LanguageModelToolsEvent> for LanguageModelTools {}
+
+impl Item for LanguageModelTools {
+    type Event = LanguageModelToolsEvent;
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> ui::SharedString {
+        "Language Model Logs".into()
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(ui::Icon::new(IconName::ZedAssistant))
+    }
+}
+
+impl Focusable for LanguageModelTools {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for LanguageModelTools {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(if self.entry_ids.is_empty() {
+                h_flex()
+                    .size_full()
+                    .justify_center()
+                    .items_center()
+                    .child("No requests recorded yet")
+                    .into_any()
+            } else {
+                div()
+                    .size_full()
+                    .flex_grow()
+                    .child(
+                        list(self.list_state.clone(), cx.processor(Self::render_entry))
+                            .with_sizing_behavior(gpui::ListSizingBehavior::Auto)
+                            .size_full(),
+                    )
+                    .vertical_scrollbar_for(&self.list_state, window, cx)
+                    .into_any()
+            })
+    }
+}
+
+pub struct LanguageModelToolsToolbarItemView {
+    language_model_tools: Option<Entity<LanguageModelTools>>,
+}
+
+impl LanguageModelToolsToolbarItemView {
+    pub fn new() -> Self {
+        Self {
+            language_model_tools: None,
+        }
+    }
+}
+
+impl Render for LanguageModelToolsToolbarItemView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(language_model_tools) = self.language_model_tools.as_ref() else {
+            return Empty.into_any_element();
+        };
+
+        let language_model_tools = language_model_tools.clone();
+        let has_entries = !language_model_tools.read(cx).entry_ids.is_empty();
+
+        h_flex()
+            .gap_2()
+            .child(
+                IconButton::new("copy_all_requests", IconName::Copy)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text("Copy All Requests"))
+                    .disabled(!has_entries)
+                    .on_click({
+                        let language_model_tools = language_model_tools.clone();
+                        move |_, _window, cx| {
+                            let entries = language_model_tools.read(cx).serialize_entries();
+                            cx.write_to_clipboard(ClipboardItem::new_string(entries));
+                        }
+                    }),
+            )
+            .child(
+                IconButton::new("clear_requests", IconName::Trash)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text("Clear Requests"))
+                    .disabled(!has_entries)
+                    .on_click(cx.listener(move |_this, _, _window, cx| {
+                        language_model_tools.update(cx, |language_model_tools, cx| {
+                            language_model_tools.clear_entries(cx);
+                        });
+                    })),
+            )
+            .into_any()
+    }
+}
+
+impl EventEmitter<ToolbarItemEvent> for LanguageModelToolsToolbarItemView {}
+
+impl ToolbarItemView for LanguageModelToolsToolbarItemView {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> ToolbarItemLocation {
+        if let Some(item) = active_pane_item
+            && let Some(language_model_tools) = item.downcast::<LanguageModelTools>()
+        {
+            self.language_model_tools = Some(language_model_tools);
+            cx.notify();
+            return ToolbarItemLocation::PrimaryRight;
+        }
+        if self.language_model_tools.take().is_some() {
+            cx.notify();
+        }
+        ToolbarItemLocation::Hidden
+    }
+}
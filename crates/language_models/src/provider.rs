@@ -1,4 +1,6 @@
 pub mod anthropic;
+pub mod auth_command;
+pub mod azure_open_ai;
 pub mod bedrock;
 pub mod cloud;
 pub mod copilot_chat;
@@ -6,6 +8,7 @@ pub mod deepseek;
 pub mod google;
 pub mod lmstudio;
 pub mod mistral;
+pub mod mock;
 pub mod ollama;
 pub mod open_ai;
 pub mod open_ai_compatible;
@@ -12,8 +12,9 @@ use language_model::{
     LanguageModelCacheConfiguration, LanguageModelCompletionError, LanguageModelCompletionEvent,
     LanguageModelId, LanguageModelName, LanguageModelProvider, LanguageModelProviderId,
     LanguageModelProviderName, LanguageModelProviderState, LanguageModelRequest,
-    LanguageModelToolChoice, LanguageModelToolResultContent, LanguageModelToolUse, MessageContent,
-    RateLimiter, Role, StopReason, env_var,
+    LanguageModelToolChoice, LanguageModelToolResultContent, LanguageModelToolUse,
+    LanguageModelTrafficLog, MessageContent, RateLimiter, Role, StopReason, env_var,
+    log_completion_traffic, log_raw_traffic,
 };
 use settings::{Settings, SettingsStore};
 use std::pin::Pin;
@@ -590,6 +591,7 @@ impl LanguageModel for AnthropicModel {
             LanguageModelCompletionError,
         >,
     > {
+        let traffic = LanguageModelTrafficLog::start_entry(self, &request, cx);
         let request = into_anthropic(
             request,
             self.model.request_id().into(),
@@ -598,11 +600,12 @@ impl LanguageModel for AnthropicModel {
             self.model.mode(),
         );
         let request = self.stream_completion(request, cx);
+        let raw_traffic = traffic.clone();
         let future = self.request_limiter.stream(async move {
-            let response = request.await?;
+            let response = log_raw_traffic(raw_traffic, request.await?);
             Ok(AnthropicEventMapper::new().map_stream(response))
         });
-        async move { Ok(future.await?.boxed()) }.boxed()
+        log_completion_traffic(traffic, async move { Ok(future.await?.boxed()) }.boxed())
     }
 
     fn cache_configuration(&self) -> Option<LanguageModelCacheConfiguration> {
@@ -1103,6 +1106,7 @@ mod tests {
             tool_choice: None,
             thinking_allowed: true,
             thinking_effort: None,
+            credential_profile: None,
         };
 
         let anthropic_request = into_anthropic(
@@ -1157,6 +1161,7 @@ mod tests {
                 reasoning_details: None,
             }],
             thinking_effort: None,
+            credential_profile: None,
             thread_id: None,
             prompt_id: None,
             intent: None,
@@ -0,0 +1,277 @@
+use anyhow::{Context as _, Result, ensure};
+use collections::BTreeMap;
+use language_model::{LanguageModelCompletionError, LanguageModelProviderName, REDACTED_API_KEY};
+use open_ai::EndpointOptions;
+pub use settings::LanguageModelAuthCommand as AuthCommand;
+use smol::lock::Mutex;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const DEFAULT_CACHE_DURATION: Duration = Duration::from_secs(300);
+
+/// Runs a provider's [`AuthCommand`] and reuses its token until it's due for a refresh.
+///
+/// Clones share the same cached token.
+#[derive(Clone, Default)]
+pub struct AuthCommandTokenCache {
+    cached: Arc<Mutex<Option<CachedToken>>>,
+}
+
+struct CachedToken {
+    command: AuthCommand,
+    token: String,
+    fetched_at: Instant,
+}
+
+impl AuthCommandTokenCache {
+    /// Returns the header carrying a token from `command`, running it if the cached token was
+    /// produced by a different command or has expired.
+    pub async fn header(&self, command: &AuthCommand) -> Result<(String, String)> {
+        let mut cached = self.cached.lock().await;
+        if let Some(cached) = cached.as_ref()
+            && cached.command == *command
+            && cached.fetched_at.elapsed() < cache_duration(command)
+        {
+            return Ok(auth_header(command, &cached.token));
+        }
+
+        let token = run_auth_command(command).await?;
+        let header = auth_header(command, &token);
+        *cached = Some(CachedToken {
+            command: command.clone(),
+            token,
+            fetched_at: Instant::now(),
+        });
+        Ok(header)
+    }
+
+    /// Forgets the cached token, so the next request runs the command again.
+    pub fn clear(&self) {
+        if let Some(mut cached) = self.cached.try_lock() {
+            cached.take();
+        }
+    }
+}
+
+/// How a request authenticates: with an API key, or with a token printed by an [`AuthCommand`].
+#[derive(Clone)]
+pub enum RequestAuth {
+    ApiKey(Option<Arc<str>>),
+    Command {
+        command: AuthCommand,
+        tokens: AuthCommandTokenCache,
+    },
+}
+
+impl RequestAuth {
+    /// Prefers the auth command when one is configured, since it replaces the API key.
+    pub fn new(
+        api_key: Option<Arc<str>>,
+        auth_command: Option<&AuthCommand>,
+        tokens: &AuthCommandTokenCache,
+    ) -> Self {
+        match auth_command {
+            Some(command) => Self::Command {
+                command: command.clone(),
+                tokens: tokens.clone(),
+            },
+            None => Self::ApiKey(api_key),
+        }
+    }
+
+    /// Returns the API key to send, or the header that replaces it, running the auth command
+    /// if its cached token has expired.
+    pub async fn resolve(
+        self,
+        provider: LanguageModelProviderName,
+    ) -> Result<(Option<Arc<str>>, Option<(String, String)>), LanguageModelCompletionError> {
+        match self {
+            Self::ApiKey(Some(api_key)) => Ok((Some(api_key), None)),
+            Self::ApiKey(None) => Err(LanguageModelCompletionError::NoApiKey { provider }),
+            Self::Command { command, tokens } => {
+                let header = tokens.header(&command).await.map_err(|error| {
+                    LanguageModelCompletionError::AuthenticationError {
+                        provider,
+                        message: format!("{error:#}"),
+                    }
+                })?;
+                Ok((None, Some(header)))
+            }
+        }
+    }
+
+    /// Returns the authentication header to show in request snapshots. Tokens from an auth
+    /// command are never included, since the command hasn't necessarily run yet.
+    pub fn snapshot_header(&self, options: &EndpointOptions) -> (String, String) {
+        match self {
+            Self::ApiKey(api_key) => options.api_key_header(api_key.as_deref().unwrap_or_default()),
+            Self::Command { command, .. } => auth_header(command, REDACTED_API_KEY),
+        }
+    }
+}
+
+fn cache_duration(command: &AuthCommand) -> Duration {
+    command
+        .cache_seconds
+        .map_or(DEFAULT_CACHE_DURATION, Duration::from_secs)
+}
+
+/// Returns the header `command`'s token is sent in.
+pub fn auth_header(command: &AuthCommand, token: &str) -> (String, String) {
+    match command.header.as_ref() {
+        Some(header_name) => (header_name.clone(), token.to_string()),
+        None => ("Authorization".to_string(), format!("Bearer {token}")),
+    }
+}
+
+/// Returns the configured extra headers as a list that can be sent with a request.
+pub fn custom_headers(headers: &BTreeMap<String, String>) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect()
+}
+
+async fn run_auth_command(command: &AuthCommand) -> Result<String> {
+    let output = util::command::new_command(&command.command)
+        .args(&command.args)
+        .output()
+        .await
+        .with_context(|| format!("failed to run auth command `{}`", command.command))?;
+    ensure!(
+        output.status.success(),
+        "auth command `{}` failed with {}: {}",
+        command.command,
+        output.status,
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+
+    let token = String::from_utf8(output.stdout)
+        .with_context(|| format!("auth command `{}` printed invalid UTF-8", command.command))?
+        .trim()
+        .to_string();
+    ensure!(
+        !token.is_empty(),
+        "auth command `{}` printed no token",
+        command.command
+    );
+    Ok(token)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// A command whose token is the process id of the shell, so every run prints a new token.
+    fn pid_command(cache_seconds: Option<u64>) -> AuthCommand {
+        AuthCommand {
+            command: "sh".into(),
+            args: vec!["-c".into(), "echo token-$$".into()],
+            header: None,
+            cache_seconds,
+        }
+    }
+
+    #[test]
+    fn test_cached_token_is_reused() {
+        let tokens = AuthCommandTokenCache::default();
+        let command = pid_command(None);
+        smol::block_on(async {
+            let first = tokens.header(&command).await.unwrap();
+            assert_eq!(first.0, "Authorization");
+            assert!(first.1.starts_with("Bearer token-"));
+
+            // Clones share the cache.
+            let second = tokens.clone().header(&command).await.unwrap();
+            assert_eq!(first, second);
+        });
+    }
+
+    #[test]
+    fn test_expired_token_is_refreshed() {
+        let tokens = AuthCommandTokenCache::default();
+        let command = pid_command(Some(0));
+        smol::block_on(async {
+            let first = tokens.header(&command).await.unwrap();
+            let second = tokens.header(&command).await.unwrap();
+            assert_ne!(first, second);
+
+            // Changing the command invalidates the cached token too.
+            let tokens = AuthCommandTokenCache::default();
+            let first = tokens.header(&pid_command(None)).await.unwrap();
+            let command = AuthCommand {
+                header: Some("X-Gateway-Token".into()),
+                ..pid_command(None)
+            };
+            let second = tokens.header(&command).await.unwrap();
+            assert_eq!(second.0, "X-Gateway-Token");
+            assert_ne!(first.1, format!("Bearer {}", second.1));
+
+            // Clearing the cache forces the command to run again.
+            let third = tokens.header(&command).await.unwrap();
+            assert_eq!(second, third);
+            tokens.clear();
+            let fourth = tokens.header(&command).await.unwrap();
+            assert_ne!(third, fourth);
+        });
+    }
+
+    #[test]
+    fn test_command_failure() {
+        let tokens = AuthCommandTokenCache::default();
+        let failing = AuthCommand {
+            command: "sh".into(),
+            args: vec!["-c".into(), "echo 'not signed in' >&2; exit 3".into()],
+            header: None,
+            cache_seconds: None,
+        };
+        let silent = AuthCommand {
+            args: vec!["-c".into(), "true".into()],
+            ..failing.clone()
+        };
+        smol::block_on(async {
+            let error = tokens.header(&failing).await.unwrap_err().to_string();
+            assert!(error.contains("not signed in"), "{error}");
+
+            let error = tokens.header(&silent).await.unwrap_err().to_string();
+            assert!(error.contains("printed no token"), "{error}");
+
+            // Failures aren't cached, and surface as authentication errors.
+            let auth = RequestAuth::new(None, Some(&failing), &tokens);
+            let error = auth
+                .resolve(LanguageModelProviderName::new("Example"))
+                .await
+                .unwrap_err();
+            assert!(matches!(
+                error,
+                LanguageModelCompletionError::AuthenticationError { .. }
+            ));
+
+            let auth = RequestAuth::new(None, None, &tokens);
+            let error = auth
+                .resolve(LanguageModelProviderName::new("Example"))
+                .await
+                .unwrap_err();
+            assert!(matches!(
+                error,
+                LanguageModelCompletionError::NoApiKey { .. }
+            ));
+        });
+    }
+
+    #[test]
+    fn test_auth_command_replaces_api_key() {
+        let tokens = AuthCommandTokenCache::default();
+        let command = pid_command(None);
+        let auth = RequestAuth::new(Some("sk-secret".into()), Some(&command), &tokens);
+        assert_eq!(
+            auth.snapshot_header(&EndpointOptions::default()),
+            ("Authorization".to_string(), "Bearer $API_KEY".to_string())
+        );
+
+        let (api_key, header) =
+            smol::block_on(auth.resolve(LanguageModelProviderName::new("Example"))).unwrap();
+        assert_eq!(api_key, None);
+        assert!(header.unwrap().1.starts_with("Bearer token-"));
+    }
+}
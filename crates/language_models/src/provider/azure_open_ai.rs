@@ -0,0 +1,537 @@
+use anyhow::Result;
+use collections::BTreeMap;
+use futures::{FutureExt, StreamExt, future::BoxFuture};
+use gpui::{AnyView, App, AsyncApp, Context, Entity, SharedString, Task, Window};
+use http_client::HttpClient;
+use language_model::{
+    ApiKeyState, AuthenticateError, EnvVar, IconOrSvg, LanguageModel, LanguageModelCompletionError,
+    LanguageModelCompletionEvent, LanguageModelId, LanguageModelName, LanguageModelProvider,
+    LanguageModelProviderId, LanguageModelProviderName, LanguageModelProviderState,
+    LanguageModelRequest, LanguageModelToolChoice, LanguageModelTrafficLog, RateLimiter,
+    TrafficLogEntryHandle, env_var, log_completion_traffic,
+};
+use open_ai::{EndpointOptions, ResponseStreamEvent, stream_completion_with_options};
+pub use settings::AzureOpenAiAvailableModel as AvailableModel;
+use settings::{Settings, SettingsStore};
+use std::sync::{Arc, LazyLock};
+use ui::{ConfiguredApiCard, List, ListBulletItem, prelude::*};
+use ui_input::InputField;
+use util::ResultExt;
+
+use crate::provider::auth_command::{
+    AuthCommand, AuthCommandTokenCache, RequestAuth, custom_headers,
+};
+use crate::provider::open_ai::{OpenAiEventMapper, collect_tiktoken_messages, into_open_ai};
+use crate::provider::open_ai_compatible::http_request_snapshot;
+
+const PROVIDER_ID: LanguageModelProviderId = LanguageModelProviderId::new("azure_openai");
+const PROVIDER_NAME: LanguageModelProviderName = LanguageModelProviderName::new("Azure OpenAI");
+
+const API_KEY_ENV_VAR_NAME: &str = "AZURE_OPENAI_API_KEY";
+static API_KEY_ENV_VAR: LazyLock<EnvVar> = env_var!(API_KEY_ENV_VAR_NAME);
+
+/// Azure OpenAI expects the key in an `api-key` header rather than as a bearer token.
+const API_KEY_HEADER: &str = "api-key";
+
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct AzureOpenAiSettings {
+    pub endpoint: String,
+    pub api_version: String,
+    pub available_models: Vec<AvailableModel>,
+    pub headers: BTreeMap<String, String>,
+    pub auth_command: Option<AuthCommand>,
+}
+
+impl AzureOpenAiSettings {
+    /// The base URL of a deployment, to which OpenAI API paths like `/chat/completions` are
+    /// appended.
+    fn deployment_url(&self, deployment: &str) -> String {
+        format!(
+            "{}/openai/deployments/{}",
+            self.endpoint.trim_end_matches('/'),
+            urlencoding::encode(deployment)
+        )
+    }
+
+    fn endpoint_options(&self) -> EndpointOptions {
+        EndpointOptions {
+            query: Some(format!(
+                "api-version={}",
+                urlencoding::encode(&self.api_version)
+            )),
+            api_key_header: Some(API_KEY_HEADER.to_string()),
+        }
+    }
+}
+
+pub struct AzureOpenAiLanguageModelProvider {
+    http_client: Arc<dyn HttpClient>,
+    state: Entity<State>,
+}
+
+pub struct State {
+    api_key_state: ApiKeyState,
+    auth_command_tokens: AuthCommandTokenCache,
+}
+
+impl State {
+    fn is_authenticated(&self, cx: &App) -> bool {
+        let settings = AzureOpenAiLanguageModelProvider::settings(cx);
+        !settings.endpoint.is_empty()
+            && (settings.auth_command.is_some() || self.api_key_state.has_key())
+    }
+
+    fn set_api_key(&mut self, api_key: Option<String>, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let api_url = AzureOpenAiLanguageModelProvider::api_url(cx);
+        self.api_key_state
+            .store(api_url, api_key, |this| &mut this.api_key_state, cx)
+    }
+
+    fn authenticate(&mut self, cx: &mut Context<Self>) -> Task<Result<(), AuthenticateError>> {
+        let settings = AzureOpenAiLanguageModelProvider::settings(cx);
+        if settings.endpoint.is_empty() {
+            return Task::ready(Err(AuthenticateError::CredentialsNotFound));
+        }
+        if settings.auth_command.is_some() {
+            return Task::ready(Ok(()));
+        }
+        let api_url = AzureOpenAiLanguageModelProvider::api_url(cx);
+        self.api_key_state
+            .load_if_needed(api_url, |this| &mut this.api_key_state, cx)
+    }
+}
+
+impl AzureOpenAiLanguageModelProvider {
+    pub fn new(http_client: Arc<dyn HttpClient>, cx: &mut App) -> Self {
+        let state = cx.new(|cx| {
+            cx.observe_global::<SettingsStore>(|this: &mut State, cx| {
+                let api_url = Self::api_url(cx);
+                this.api_key_state
+                    .handle_url_change(api_url, |this| &mut this.api_key_state, cx);
+                cx.notify();
+            })
+            .detach();
+            State {
+                api_key_state: ApiKeyState::new(Self::api_url(cx), (*API_KEY_ENV_VAR).clone()),
+                auth_command_tokens: AuthCommandTokenCache::default(),
+            }
+        });
+
+        Self { http_client, state }
+    }
+
+    fn create_language_model(&self, model: AvailableModel) -> Arc<dyn LanguageModel> {
+        Arc::new(AzureOpenAiLanguageModel {
+            id: LanguageModelId::from(model.deployment.clone()),
+            model,
+            state: self.state.clone(),
+            http_client: self.http_client.clone(),
+            request_limiter: RateLimiter::new(4),
+        })
+    }
+
+    fn settings(cx: &App) -> &AzureOpenAiSettings {
+        &crate::AllLanguageModelSettings::get_global(cx).azure_openai
+    }
+
+    /// API keys are stored per resource endpoint.
+    fn api_url(cx: &App) -> SharedString {
+        SharedString::new(Self::settings(cx).endpoint.trim_end_matches('/'))
+    }
+}
+
+impl LanguageModelProviderState for AzureOpenAiLanguageModelProvider {
+    type ObservableEntity = State;
+
+    fn observable_entity(&self) -> Option<Entity<Self::ObservableEntity>> {
+        Some(self.state.clone())
+    }
+}
+
+impl LanguageModelProvider for AzureOpenAiLanguageModelProvider {
+    fn id(&self) -> LanguageModelProviderId {
+        PROVIDER_ID
+    }
+
+    fn name(&self) -> LanguageModelProviderName {
+        PROVIDER_NAME
+    }
+
+    fn icon(&self) -> IconOrSvg {
+        IconOrSvg::Icon(IconName::AiOpenAi)
+    }
+
+    fn default_model(&self, cx: &App) -> Option<Arc<dyn LanguageModel>> {
+        self.provided_models(cx).into_iter().next()
+    }
+
+    fn default_fast_model(&self, _cx: &App) -> Option<Arc<dyn LanguageModel>> {
+        None
+    }
+
+    fn provided_models(&self, cx: &App) -> Vec<Arc<dyn LanguageModel>> {
+        Self::settings(cx)
+            .available_models
+            .iter()
+            .map(|model| self.create_language_model(model.clone()))
+            .collect()
+    }
+
+    fn is_authenticated(&self, cx: &App) -> bool {
+        self.state.read(cx).is_authenticated(cx)
+    }
+
+    fn authenticate(&self, cx: &mut App) -> Task<Result<(), AuthenticateError>> {
+        self.state.update(cx, |state, cx| state.authenticate(cx))
+    }
+
+    fn configuration_view(
+        &self,
+        _target_agent: language_model::ConfigurationViewTargetAgent,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> AnyView {
+        cx.new(|cx| ConfigurationView::new(self.state.clone(), window, cx))
+            .into()
+    }
+
+    fn reset_credentials(&self, cx: &mut App) -> Task<Result<()>> {
+        self.state.update(cx, |state, cx| {
+            state.auth_command_tokens.clear();
+            state.set_api_key(None, cx)
+        })
+    }
+}
+
+pub struct AzureOpenAiLanguageModel {
+    id: LanguageModelId,
+    model: AvailableModel,
+    state: Entity<State>,
+    http_client: Arc<dyn HttpClient>,
+    request_limiter: RateLimiter,
+}
+
+impl AzureOpenAiLanguageModel {
+    fn stream_completion(
+        &self,
+        request: open_ai::Request,
+        traffic: Option<TrafficLogEntryHandle>,
+        cx: &AsyncApp,
+    ) -> BoxFuture<
+        'static,
+        Result<
+            futures::stream::BoxStream<'static, Result<ResponseStreamEvent>>,
+            LanguageModelCompletionError,
+        >,
+    > {
+        let http_client = self.http_client.clone();
+
+        let (auth, api_url, options, configured_headers) = self.state.read_with(cx, |state, cx| {
+            let settings = AzureOpenAiLanguageModelProvider::settings(cx);
+            let api_key = state
+                .api_key_state
+                .key(&AzureOpenAiLanguageModelProvider::api_url(cx));
+            (
+                RequestAuth::new(
+                    api_key,
+                    settings.auth_command.as_ref(),
+                    &state.auth_command_tokens,
+                ),
+                settings.deployment_url(&self.model.deployment),
+                settings.endpoint_options(),
+                settings.headers.clone(),
+            )
+        });
+        let mut additional_headers = custom_headers(&configured_headers);
+
+        if let Some(traffic) = traffic.as_ref() {
+            traffic.set_http_request(http_request_snapshot(
+                options.url(&api_url, "chat/completions"),
+                auth.snapshot_header(&options),
+                &additional_headers,
+                &configured_headers,
+                &request,
+            ));
+        }
+
+        let future = self.request_limiter.stream(async move {
+            let (api_key, auth_header) = auth.resolve(PROVIDER_NAME).await?;
+            additional_headers.extend(auth_header);
+            let request = stream_completion_with_options(
+                http_client.as_ref(),
+                PROVIDER_NAME.0.as_str(),
+                &api_url,
+                api_key.as_deref(),
+                request,
+                &additional_headers,
+                &options,
+            );
+            let response = request.await?;
+            Ok(response.inspect(move |event| {
+                if let (Some(traffic), Ok(event)) = (traffic.as_ref(), event) {
+                    traffic.record_raw_event(event);
+                }
+            }))
+        });
+
+        async move { Ok(future.await?.boxed()) }.boxed()
+    }
+}
+
+impl LanguageModel for AzureOpenAiLanguageModel {
+    fn id(&self) -> LanguageModelId {
+        self.id.clone()
+    }
+
+    fn name(&self) -> LanguageModelName {
+        LanguageModelName::from(
+            self.model
+                .display_name
+                .clone()
+                .unwrap_or_else(|| self.model.deployment.clone()),
+        )
+    }
+
+    fn provider_id(&self) -> LanguageModelProviderId {
+        PROVIDER_ID
+    }
+
+    fn provider_name(&self) -> LanguageModelProviderName {
+        PROVIDER_NAME
+    }
+
+    fn supports_tools(&self) -> bool {
+        self.model.supports_tools
+    }
+
+    fn supports_images(&self) -> bool {
+        self.model.supports_images
+    }
+
+    fn supports_tool_choice(&self, _choice: LanguageModelToolChoice) -> bool {
+        self.model.supports_tools
+    }
+
+    fn telemetry_id(&self) -> String {
+        format!("azure_openai/{}", self.model.deployment)
+    }
+
+    fn max_token_count(&self) -> u64 {
+        self.model.max_tokens
+    }
+
+    fn max_output_tokens(&self) -> Option<u64> {
+        self.model.max_output_tokens
+    }
+
+    fn count_tokens(
+        &self,
+        request: LanguageModelRequest,
+        cx: &App,
+    ) -> BoxFuture<'static, Result<u64>> {
+        let max_token_count = self.max_token_count();
+        cx.background_spawn(async move {
+            let messages = collect_tiktoken_messages(request);
+            // Deployment names don't identify the underlying model, so pick the tokenizer from
+            // the context size, as OpenAI-compatible providers do.
+            let model = if max_token_count >= 100_000 {
+                "gpt-4o"
+            } else {
+                "gpt-4"
+            };
+            tiktoken_rs::num_tokens_from_messages(model, &messages).map(|tokens| tokens as u64)
+        })
+        .boxed()
+    }
+
+    fn stream_completion(
+        &self,
+        request: LanguageModelRequest,
+        cx: &AsyncApp,
+    ) -> BoxFuture<
+        'static,
+        Result<
+            futures::stream::BoxStream<
+                'static,
+                Result<LanguageModelCompletionEvent, LanguageModelCompletionError>,
+            >,
+            LanguageModelCompletionError,
+        >,
+    > {
+        let traffic = LanguageModelTrafficLog::start_entry(self, &request, cx);
+        let request = into_open_ai(
+            request,
+            &self.model.deployment,
+            self.model.parallel_tool_calls,
+            false,
+            false,
+            self.max_output_tokens(),
+            None,
+        );
+        let completions = self.stream_completion(request, traffic.clone(), cx);
+        let future = async move {
+            let mapper = OpenAiEventMapper::new();
+            Ok(mapper.map_stream(completions.await?).boxed())
+        }
+        .boxed();
+        log_completion_traffic(traffic, future)
+    }
+}
+
+struct ConfigurationView {
+    api_key_editor: Entity<InputField>,
+    state: Entity<State>,
+    load_credentials_task: Option<Task<()>>,
+}
+
+impl ConfigurationView {
+    fn new(state: Entity<State>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let api_key_editor =
+            cx.new(|cx| InputField::new(window, cx, "00000000000000000000000000000000"));
+
+        cx.observe(&state, |_, _, cx| {
+            cx.notify();
+        })
+        .detach();
+
+        let load_credentials_task = Some(cx.spawn({
+            let state = state.clone();
+            async move |this, cx| {
+                let _ = state.update(cx, |state, cx| state.authenticate(cx)).await;
+
+                this.update(cx, |this, cx| {
+                    this.load_credentials_task = None;
+                    cx.notify();
+                })
+                .log_err();
+            }
+        }));
+
+        Self {
+            api_key_editor,
+            state,
+            load_credentials_task,
+        }
+    }
+
+    fn save_api_key(&mut self, _: &menu::Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        let api_key = self.api_key_editor.read(cx).text(cx).trim().to_string();
+        if api_key.is_empty() {
+            return;
+        }
+
+        let state = self.state.clone();
+        cx.spawn(async move |_, cx| {
+            state
+                .update(cx, |state, cx| state.set_api_key(Some(api_key), cx))
+                .await
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn reset_api_key(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.api_key_editor
+            .update(cx, |editor, cx| editor.set_text("", window, cx));
+
+        let state = self.state.clone();
+        cx.spawn(async move |_, cx| {
+            state
+                .update(cx, |state, cx| state.set_api_key(None, cx))
+                .await
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+impl Render for ConfigurationView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let settings = AzureOpenAiLanguageModelProvider::settings(cx);
+        let state = self.state.read(cx);
+
+        if self.load_credentials_task.is_some() {
+            div()
+                .child(Label::new("Loading credentials..."))
+                .into_any_element()
+        } else if settings.endpoint.is_empty() {
+            v_flex()
+                .gap_1()
+                .child(Label::new(
+                    "To use Azure OpenAI in Zed, configure your resource in your settings:",
+                ))
+                .child(
+                    List::new()
+                        .child(
+                            ListBulletItem::new("")
+                                .child(Label::new("Set"))
+                                .child(
+                                    Label::new("language_models.azure_openai.endpoint")
+                                        .inline_code(cx),
+                                )
+                                .child(Label::new("to your resource's endpoint")),
+                        )
+                        .child(
+                            ListBulletItem::new("")
+                                .child(Label::new("List your deployments in"))
+                                .child(Label::new("available_models").inline_code(cx)),
+                        ),
+                )
+                .into_any_element()
+        } else if let Some(auth_command) = settings.auth_command.as_ref() {
+            ConfiguredApiCard::new(format!("Authenticating with `{}`", auth_command.command))
+                .disabled(true)
+                .into_any_element()
+        } else if !state.is_authenticated(cx) {
+            v_flex()
+                .size_full()
+                .on_action(cx.listener(Self::save_api_key))
+                .child(Label::new(format!(
+                    "Paste an API key for {} below and hit enter:",
+                    settings.endpoint
+                )))
+                .child(self.api_key_editor.clone())
+                .child(
+                    Label::new(format!(
+                        "You can also set the {API_KEY_ENV_VAR_NAME} environment variable and restart Zed, or configure an auth command that prints an Entra ID token."
+                    ))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+                )
+                .into_any_element()
+        } else {
+            let env_var_set = state.api_key_state.is_from_env_var();
+            let configured_card_label = if env_var_set {
+                format!("API key set in {API_KEY_ENV_VAR_NAME} environment variable")
+            } else {
+                format!("API key configured for {}", settings.endpoint)
+            };
+            ConfiguredApiCard::new(configured_card_label)
+                .disabled(env_var_set)
+                .on_click(cx.listener(|this, _, window, cx| this.reset_api_key(window, cx)))
+                .into_any_element()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_deployment_urls() {
+        let settings = AzureOpenAiSettings {
+            endpoint: "https://contoso.openai.azure.com/".into(),
+            api_version: "2024-10-21".into(),
+            ..Default::default()
+        };
+        let options = settings.endpoint_options();
+
+        assert_eq!(
+            options.url(&settings.deployment_url("gpt-4o mini"), "chat/completions"),
+            "https://contoso.openai.azure.com/openai/deployments/gpt-4o%20mini/chat/completions?api-version=2024-10-21"
+        );
+        assert_eq!(
+            options.api_key_header(" secret "),
+            ("api-key".to_string(), "secret".to_string())
+        );
+    }
+}
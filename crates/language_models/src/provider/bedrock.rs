@@ -33,8 +33,8 @@ use language_model::{
     LanguageModelCompletionError, LanguageModelCompletionEvent, LanguageModelId, LanguageModelName,
     LanguageModelProvider, LanguageModelProviderId, LanguageModelProviderName,
     LanguageModelProviderState, LanguageModelRequest, LanguageModelToolChoice,
-    LanguageModelToolResultContent, LanguageModelToolUse, MessageContent, RateLimiter, Role,
-    TokenUsage, env_var,
+    LanguageModelToolResultContent, LanguageModelToolUse, LanguageModelTrafficLog, MessageContent,
+    RateLimiter, Role, TokenUsage, env_var, log_completion_traffic,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -689,6 +689,7 @@ impl LanguageModel for BedrockModel {
             LanguageModelCompletionError,
         >,
     > {
+        let traffic = LanguageModelTrafficLog::start_entry(self, &request, cx);
         let (region, allow_global, allow_extended_context) =
             cx.read_entity(&self.state, |state, _cx| {
                 (
@@ -701,7 +702,7 @@ impl LanguageModel for BedrockModel {
         let model_id = match self.model.cross_region_inference_id(&region, allow_global) {
             Ok(s) => s,
             Err(e) => {
-                return async move { Err(e.into()) }.boxed();
+                return log_completion_traffic(traffic, async move { Err(e.into()) }.boxed());
             }
         };
 
@@ -720,7 +721,12 @@ impl LanguageModel for BedrockModel {
             use_extended_context,
         ) {
             Ok(request) => request,
-            Err(err) => return futures::future::ready(Err(err.into())).boxed(),
+            Err(err) => {
+                return log_completion_traffic(
+                    traffic,
+                    futures::future::ready(Err(err.into())).boxed(),
+                );
+            }
         };
 
         let request = self.stream_completion(request, cx);
@@ -771,7 +777,7 @@ impl LanguageModel for BedrockModel {
             }
         });
 
-        async move { Ok(future.await?.boxed()) }.boxed()
+        log_completion_traffic(traffic, async move { Ok(future.await?.boxed()) }.boxed())
     }
 
     fn cache_configuration(&self) -> Option<LanguageModelCacheConfiguration> {
@@ -24,8 +24,9 @@ use language_model::{
     LanguageModelCompletionError, LanguageModelCompletionEvent, LanguageModelEffortLevel,
     LanguageModelId, LanguageModelName, LanguageModelProvider, LanguageModelProviderId,
     LanguageModelProviderName, LanguageModelProviderState, LanguageModelRequest,
-    LanguageModelToolChoice, LanguageModelToolSchemaFormat, LlmApiToken, NeedsLlmTokenRefresh,
-    PaymentRequiredError, RateLimiter, RefreshLlmTokenListener,
+    LanguageModelToolChoice, LanguageModelToolSchemaFormat, LanguageModelTrafficLog, LlmApiToken,
+    NeedsLlmTokenRefresh, PaymentRequiredError, RateLimiter, RefreshLlmTokenListener,
+    log_completion_traffic,
 };
 use release_channel::AppVersion;
 use schemars::JsonSchema;
@@ -728,6 +729,7 @@ impl LanguageModel for CloudLanguageModel {
             LanguageModelCompletionError,
         >,
     > {
+        let traffic = LanguageModelTrafficLog::start_entry(self, &request, cx);
         let thread_id = request.thread_id.clone();
         let prompt_id = request.prompt_id.clone();
         let intent = request.intent;
@@ -735,7 +737,7 @@ impl LanguageModel for CloudLanguageModel {
         let thinking_allowed = request.thinking_allowed;
         let enable_thinking = thinking_allowed && self.model.supports_thinking;
         let provider_name = provider_name(&self.model.provider);
-        match self.model.provider {
+        let future = match self.model.provider {
             cloud_llm_client::LanguageModelProvider::Anthropic => {
                 let effort = request
                     .thinking_effort
@@ -920,7 +922,8 @@ impl LanguageModel for CloudLanguageModel {
                 });
                 async move { Ok(future.await?.boxed()) }.boxed()
             }
-        }
+        };
+        log_completion_traffic(traffic, future)
     }
 }
 
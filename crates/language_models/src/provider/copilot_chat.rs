@@ -24,7 +24,8 @@ use language_model::{
     LanguageModelProvider, LanguageModelProviderId, LanguageModelProviderName,
     LanguageModelProviderState, LanguageModelRequest, LanguageModelRequestMessage,
     LanguageModelToolChoice, LanguageModelToolResultContent, LanguageModelToolSchemaFormat,
-    LanguageModelToolUse, MessageContent, RateLimiter, Role, StopReason, TokenUsage,
+    LanguageModelToolUse, LanguageModelTrafficLog, MessageContent, RateLimiter, Role, StopReason,
+    TokenUsage, log_completion_traffic,
 };
 use settings::SettingsStore;
 use ui::prelude::*;
@@ -316,6 +317,7 @@ impl LanguageModel for CopilotChatLanguageModel {
             LanguageModelCompletionError,
         >,
     > {
+        let traffic = LanguageModelTrafficLog::start_entry(self, &request, cx);
         let is_user_initiated = request.intent.is_none_or(|intent| match intent {
             CompletionIntent::UserPrompt
             | CompletionIntent::ThreadContextSummarization
@@ -343,12 +345,20 @@ impl LanguageModel for CopilotChatLanguageModel {
                     })
                     .await
             });
-            return async move { Ok(future.await?.boxed()) }.boxed();
+            return log_completion_traffic(
+                traffic,
+                async move { Ok(future.await?.boxed()) }.boxed(),
+            );
         }
 
         let copilot_request = match into_copilot_chat(&self.model, request) {
             Ok(request) => request,
-            Err(err) => return futures::future::ready(Err(err.into())).boxed(),
+            Err(err) => {
+                return log_completion_traffic(
+                    traffic,
+                    futures::future::ready(Err(err.into())).boxed(),
+                );
+            }
         };
         let is_streaming = copilot_request.stream;
 
@@ -366,7 +376,7 @@ impl LanguageModel for CopilotChatLanguageModel {
                 })
                 .await
         });
-        async move { Ok(future.await?.boxed()) }.boxed()
+        log_completion_traffic(traffic, async move { Ok(future.await?.boxed()) }.boxed())
     }
 }
 
@@ -930,6 +940,7 @@ fn into_copilot_responses(
         temperature,
         thinking_allowed: _,
         thinking_effort: _,
+        credential_profile: _,
     } = request;
 
     let mut input_items: Vec<responses::ResponseInputItem> = Vec::new();
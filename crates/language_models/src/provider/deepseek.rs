@@ -11,7 +11,8 @@ use language_model::{
     LanguageModelCompletionEvent, LanguageModelId, LanguageModelName, LanguageModelProvider,
     LanguageModelProviderId, LanguageModelProviderName, LanguageModelProviderState,
     LanguageModelRequest, LanguageModelToolChoice, LanguageModelToolResultContent,
-    LanguageModelToolUse, MessageContent, RateLimiter, Role, StopReason, TokenUsage, env_var,
+    LanguageModelToolUse, LanguageModelTrafficLog, MessageContent, RateLimiter, Role, StopReason,
+    TokenUsage, env_var, log_completion_traffic, log_raw_traffic,
 };
 pub use settings::DeepseekAvailableModel as AvailableModel;
 use settings::{Settings, SettingsStore};
@@ -303,14 +304,18 @@ impl LanguageModel for DeepSeekLanguageModel {
             LanguageModelCompletionError,
         >,
     > {
+        let traffic = LanguageModelTrafficLog::start_entry(self, &request, cx);
         let request = into_deepseek(request, &self.model, self.max_output_tokens());
         let stream = self.stream_completion(request, cx);
+        let raw_traffic = traffic.clone();
 
-        async move {
+        let future = async move {
             let mapper = DeepSeekEventMapper::new();
-            Ok(mapper.map_stream(stream.await?).boxed())
+            let stream = log_raw_traffic(raw_traffic, stream.await?);
+            Ok(mapper.map_stream(stream).boxed())
         }
-        .boxed()
+        .boxed();
+        log_completion_traffic(traffic, future)
     }
 }
 
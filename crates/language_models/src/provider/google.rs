@@ -10,7 +10,8 @@ use http_client::HttpClient;
 use language_model::{
     AuthenticateError, ConfigurationViewTargetAgent, EnvVar, LanguageModelCompletionError,
     LanguageModelCompletionEvent, LanguageModelToolChoice, LanguageModelToolSchemaFormat,
-    LanguageModelToolUse, LanguageModelToolUseId, MessageContent, StopReason,
+    LanguageModelToolUse, LanguageModelToolUseId, LanguageModelTrafficLog, MessageContent,
+    StopReason, log_completion_traffic, log_raw_traffic,
 };
 use language_model::{
     IconOrSvg, LanguageModel, LanguageModelId, LanguageModelName, LanguageModelProvider,
@@ -357,17 +358,20 @@ impl LanguageModel for GoogleLanguageModel {
             LanguageModelCompletionError,
         >,
     > {
+        let traffic = LanguageModelTrafficLog::start_entry(self, &request, cx);
         let request = into_google(
             request,
             self.model.request_id().to_string(),
             self.model.mode(),
         );
         let request = self.stream_completion(request, cx);
+        let raw_traffic = traffic.clone();
         let future = self.request_limiter.stream(async move {
             let response = request.await.map_err(LanguageModelCompletionError::from)?;
+            let response = log_raw_traffic(raw_traffic, response);
             Ok(GoogleEventMapper::new().map_stream(response))
         });
-        async move { Ok(future.await?.boxed()) }.boxed()
+        log_completion_traffic(traffic, async move { Ok(future.await?.boxed()) }.boxed())
     }
 }
 
@@ -6,8 +6,9 @@ use gpui::{AnyView, App, AsyncApp, Context, Entity, Subscription, Task};
 use http_client::HttpClient;
 use language_model::{
     AuthenticateError, LanguageModelCompletionError, LanguageModelCompletionEvent,
-    LanguageModelToolChoice, LanguageModelToolResultContent, LanguageModelToolUse, MessageContent,
-    StopReason, TokenUsage,
+    LanguageModelToolChoice, LanguageModelToolResultContent, LanguageModelToolUse,
+    LanguageModelTrafficLog, MessageContent, StopReason, TokenUsage, log_completion_traffic,
+    log_raw_traffic,
 };
 use language_model::{
     IconOrSvg, LanguageModel, LanguageModelId, LanguageModelName, LanguageModelProvider,
@@ -460,13 +461,17 @@ impl LanguageModel for LmStudioLanguageModel {
             LanguageModelCompletionError,
         >,
     > {
+        let traffic = LanguageModelTrafficLog::start_entry(self, &request, cx);
         let request = self.to_lmstudio_request(request);
         let completions = self.stream_completion(request, cx);
-        async move {
+        let raw_traffic = traffic.clone();
+        let future = async move {
             let mapper = LmStudioEventMapper::new();
-            Ok(mapper.map_stream(completions.await?).boxed())
+            let completions = log_raw_traffic(raw_traffic, completions.await?);
+            Ok(mapper.map_stream(completions).boxed())
         }
-        .boxed()
+        .boxed();
+        log_completion_traffic(traffic, future)
     }
 }
 
@@ -9,7 +9,8 @@ use language_model::{
     LanguageModelCompletionEvent, LanguageModelId, LanguageModelName, LanguageModelProvider,
     LanguageModelProviderId, LanguageModelProviderName, LanguageModelProviderState,
     LanguageModelRequest, LanguageModelToolChoice, LanguageModelToolResultContent,
-    LanguageModelToolUse, MessageContent, RateLimiter, Role, StopReason, TokenUsage, env_var,
+    LanguageModelToolUse, LanguageModelTrafficLog, MessageContent, RateLimiter, Role, StopReason,
+    TokenUsage, env_var, log_completion_traffic, log_raw_traffic,
 };
 pub use mistral::{MISTRAL_API_URL, StreamResponse};
 pub use settings::MistralAvailableModel as AvailableModel;
@@ -337,16 +338,19 @@ impl LanguageModel for MistralLanguageModel {
             LanguageModelCompletionError,
         >,
     > {
+        let traffic = LanguageModelTrafficLog::start_entry(self, &request, cx);
         let (request, affinity) =
             into_mistral(request, self.model.clone(), self.max_output_tokens());
         let stream = self.stream_completion(request, affinity, cx);
+        let raw_traffic = traffic.clone();
 
-        async move {
-            let stream = stream.await?;
+        let future = async move {
+            let stream = log_raw_traffic(raw_traffic, stream.await?);
             let mapper = MistralEventMapper::new();
             Ok(mapper.map_stream(stream).boxed())
         }
-        .boxed()
+        .boxed();
+        log_completion_traffic(traffic, future)
     }
 }
 
@@ -883,6 +887,7 @@ mod tests {
             stop: vec![],
             thinking_allowed: true,
             thinking_effort: None,
+            credential_profile: None,
         };
 
         let (mistral_request, affinity) =
@@ -919,6 +924,7 @@ mod tests {
             stop: vec![],
             thinking_allowed: true,
             thinking_effort: None,
+            credential_profile: None,
         };
 
         let (mistral_request, _) = into_mistral(request, mistral::Model::Pixtral12BLatest, None);
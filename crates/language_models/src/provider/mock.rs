@@ -0,0 +1,827 @@
+use anyhow::{Context as _, Result, anyhow};
+use cloud_llm_client::CompletionIntent;
+use collections::HashMap;
+use fs::Fs;
+use futures::{FutureExt, StreamExt, future::BoxFuture, stream::BoxStream};
+use gpui::{AnyView, App, AsyncApp, Context, Entity, SharedString, Task, Window};
+use http_client::StatusCode;
+use language_model::{
+    AuthenticateError, IconOrSvg, LanguageModel, LanguageModelCompletionError,
+    LanguageModelCompletionEvent, LanguageModelId, LanguageModelName, LanguageModelProvider,
+    LanguageModelProviderId, LanguageModelProviderName, LanguageModelProviderState,
+    LanguageModelRequest, LanguageModelToolChoice, LanguageModelToolUse, LanguageModelTrafficLog,
+    Role, StopReason, TokenUsage, log_completion_traffic,
+};
+use serde::Deserialize;
+use settings::{Settings, SettingsStore};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use ui::{List, ListBulletItem, prelude::*};
+use util::ResultExt;
+
+const PROVIDER_ID: LanguageModelProviderId = LanguageModelProviderId::new("mock");
+const PROVIDER_NAME: LanguageModelProviderName = LanguageModelProviderName::new("Mock");
+
+const DEFAULT_MODEL_NAME: &str = "mock";
+const DEFAULT_MAX_TOKENS: u64 = 200_000;
+const DEFAULT_THREAD_TITLE: &str = "Mock Conversation";
+const DEFAULT_THREAD_SUMMARY: &str = "The conversation so far was replayed from a mock fixture.";
+
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct MockSettings {
+    pub fixture_path: Option<PathBuf>,
+}
+
+/// A script of responses for the mock provider, loaded from a JSON or YAML file.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct MockFixture {
+    /// Models the provider exposes. A single `mock` model is used when empty.
+    #[serde(default)]
+    pub models: Vec<MockModel>,
+    #[serde(default)]
+    pub responses: Vec<MockResponse>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct MockModel {
+    pub name: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default = "default_max_tokens")]
+    pub max_tokens: u64,
+    #[serde(default = "default_true")]
+    pub supports_tools: bool,
+    #[serde(default)]
+    pub supports_images: bool,
+    #[serde(default)]
+    pub supports_thinking: bool,
+}
+
+fn default_max_tokens() -> u64 {
+    DEFAULT_MAX_TOKENS
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for MockModel {
+    fn default() -> Self {
+        Self {
+            name: DEFAULT_MODEL_NAME.into(),
+            display_name: None,
+            max_tokens: DEFAULT_MAX_TOKENS,
+            supports_tools: true,
+            supports_images: false,
+            supports_thinking: false,
+        }
+    }
+}
+
+/// A single scripted completion.
+///
+/// Responses with an `intent` answer requests with that intent, responses with `match` answer
+/// requests whose last user message contains that text, and the remaining responses are
+/// replayed in order for each thread.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct MockResponse {
+    #[serde(default, rename = "match")]
+    pub match_text: Option<String>,
+    #[serde(default)]
+    pub intent: Option<CompletionIntent>,
+    #[serde(default)]
+    pub events: Vec<MockEvent>,
+}
+
+impl MockResponse {
+    fn text(text: &str) -> Self {
+        Self {
+            events: vec![MockEvent::Text { text: text.into() }],
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MockEvent {
+    Text {
+        text: String,
+    },
+    Thinking {
+        text: String,
+        #[serde(default)]
+        signature: Option<String>,
+    },
+    ToolCall {
+        #[serde(default)]
+        id: Option<String>,
+        name: String,
+        #[serde(default)]
+        input: serde_json::Value,
+    },
+    Usage(TokenUsage),
+    /// Pauses the stream, to simulate a slow model.
+    Delay {
+        ms: u64,
+    },
+    /// Fails the completion. Uses the error for the given HTTP status when one is set.
+    Error {
+        message: String,
+        #[serde(default)]
+        status: Option<u16>,
+    },
+    RateLimit {
+        #[serde(default)]
+        retry_after_secs: Option<u64>,
+    },
+    Stop {
+        reason: StopReason,
+    },
+}
+
+impl MockFixture {
+    pub fn parse(path: &Path, contents: &str) -> Result<Self> {
+        let is_yaml = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| matches!(extension, "yaml" | "yml"));
+        if is_yaml {
+            serde_yaml::from_str(contents).context("failed to parse YAML mock fixture")
+        } else {
+            serde_json::from_str(contents).context("failed to parse JSON mock fixture")
+        }
+    }
+
+    fn models(&self) -> Vec<MockModel> {
+        if self.models.is_empty() {
+            vec![MockModel::default()]
+        } else {
+            self.models.clone()
+        }
+    }
+
+    /// Picks the response for `request`, advancing the thread's position in the script.
+    fn next_response(
+        &self,
+        request: &LanguageModelRequest,
+        cursors: &mut HashMap<String, usize>,
+    ) -> Result<MockResponse> {
+        if let Some(intent) = request.intent {
+            if let Some(response) = self
+                .responses
+                .iter()
+                .find(|response| response.intent == Some(intent))
+            {
+                return Ok(response.clone());
+            }
+            match intent {
+                CompletionIntent::ThreadSummarization => {
+                    return Ok(MockResponse::text(DEFAULT_THREAD_TITLE));
+                }
+                CompletionIntent::ThreadContextSummarization => {
+                    return Ok(MockResponse::text(DEFAULT_THREAD_SUMMARY));
+                }
+                _ => {}
+            }
+        }
+
+        let last_user_message = request
+            .messages
+            .iter()
+            .rev()
+            .find(|message| message.role == Role::User)
+            .map(|message| message.string_contents())
+            .unwrap_or_default();
+        if let Some(response) = self.responses.iter().find(|response| {
+            response.intent.is_none()
+                && response
+                    .match_text
+                    .as_ref()
+                    .is_some_and(|text| last_user_message.contains(text.as_str()))
+        }) {
+            return Ok(response.clone());
+        }
+
+        let cursor = cursors
+            .entry(request.thread_id.clone().unwrap_or_default())
+            .or_default();
+        let response = self
+            .responses
+            .iter()
+            .filter(|response| response.intent.is_none() && response.match_text.is_none())
+            .nth(*cursor)
+            .cloned()
+            .ok_or_else(|| anyhow!("the mock fixture has no responses left for this thread"))?;
+        *cursor += 1;
+        Ok(response)
+    }
+}
+
+enum MockStep {
+    Emit(Result<LanguageModelCompletionEvent, LanguageModelCompletionError>),
+    Wait(Duration),
+}
+
+fn mock_steps(response: MockResponse, tool_use_id_prefix: &str) -> Vec<MockStep> {
+    let mut steps = Vec::new();
+    let mut stop_reason = StopReason::EndTurn;
+    for (ix, event) in response.events.into_iter().enumerate() {
+        let event = match event {
+            MockEvent::Text { text } => LanguageModelCompletionEvent::Text(text),
+            MockEvent::Thinking { text, signature } => {
+                LanguageModelCompletionEvent::Thinking { text, signature }
+            }
+            MockEvent::ToolCall { id, name, input } => {
+                stop_reason = StopReason::ToolUse;
+                LanguageModelCompletionEvent::ToolUse(LanguageModelToolUse {
+                    id: id
+                        .unwrap_or_else(|| format!("{tool_use_id_prefix}-{ix}"))
+                        .into(),
+                    name: name.into(),
+                    raw_input: input.to_string(),
+                    input,
+                    is_input_complete: true,
+                    thought_signature: None,
+                })
+            }
+            MockEvent::Usage(usage) => LanguageModelCompletionEvent::UsageUpdate(usage),
+            MockEvent::Delay { ms } => {
+                steps.push(MockStep::Wait(Duration::from_millis(ms)));
+                continue;
+            }
+            MockEvent::Error { message, status } => {
+                let error = match status.and_then(|status| StatusCode::from_u16(status).ok()) {
+                    Some(status) => LanguageModelCompletionError::from_http_status(
+                        PROVIDER_NAME,
+                        status,
+                        message,
+                        None,
+                    ),
+                    None => LanguageModelCompletionError::Other(anyhow!(message)),
+                };
+                steps.push(MockStep::Emit(Err(error)));
+                return steps;
+            }
+            MockEvent::RateLimit { retry_after_secs } => {
+                steps.push(MockStep::Emit(Err(
+                    LanguageModelCompletionError::RateLimitExceeded {
+                        provider: PROVIDER_NAME,
+                        retry_after: retry_after_secs.map(Duration::from_secs),
+                    },
+                )));
+                return steps;
+            }
+            MockEvent::Stop { reason } => {
+                steps.push(MockStep::Emit(Ok(LanguageModelCompletionEvent::Stop(
+                    reason,
+                ))));
+                return steps;
+            }
+        };
+        steps.push(MockStep::Emit(Ok(event)));
+    }
+    steps.push(MockStep::Emit(Ok(LanguageModelCompletionEvent::Stop(
+        stop_reason,
+    ))));
+    steps
+}
+
+pub struct MockLanguageModelProvider {
+    state: Entity<State>,
+}
+
+pub struct State {
+    fixture_path: Option<PathBuf>,
+    fixture: Option<Arc<MockFixture>>,
+    load_error: Option<SharedString>,
+    cursors: HashMap<String, usize>,
+    completion_count: usize,
+}
+
+impl State {
+    fn is_authenticated(&self) -> bool {
+        self.fixture.is_some()
+    }
+
+    fn authenticate(&mut self, cx: &mut Context<Self>) -> Task<Result<(), AuthenticateError>> {
+        if self.is_authenticated() {
+            return Task::ready(Ok(()));
+        }
+        if self.fixture_path.is_none() {
+            return Task::ready(Err(AuthenticateError::CredentialsNotFound));
+        }
+
+        let load_task = self.load_fixture(cx);
+        cx.spawn(async move |_this, _cx| Ok(load_task.await?))
+    }
+
+    fn load_fixture(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        self.fixture = None;
+        self.load_error = None;
+        self.cursors.clear();
+        cx.notify();
+
+        let Some(path) = self.fixture_path.clone() else {
+            return Task::ready(Ok(()));
+        };
+        let fs = <dyn Fs>::global(cx);
+        cx.spawn(async move |this, cx| {
+            let fixture = fs
+                .load(&path)
+                .await
+                .with_context(|| format!("failed to read mock fixture {}", path.display()))
+                .and_then(|contents| MockFixture::parse(&path, &contents));
+            this.update(cx, |this, cx| {
+                let result = match fixture {
+                    Ok(fixture) => {
+                        this.fixture = Some(Arc::new(fixture));
+                        Ok(())
+                    }
+                    Err(error) => {
+                        this.load_error = Some(format!("{error:#}").into());
+                        Err(error)
+                    }
+                };
+                cx.notify();
+                result
+            })?
+        })
+    }
+
+    fn next_steps(
+        &mut self,
+        request: &LanguageModelRequest,
+    ) -> Result<Vec<MockStep>, LanguageModelCompletionError> {
+        let fixture = self
+            .fixture
+            .clone()
+            .ok_or_else(|| anyhow!("no mock fixture is loaded"))?;
+        let response = fixture.next_response(request, &mut self.cursors)?;
+        self.completion_count += 1;
+        Ok(mock_steps(
+            response,
+            &format!("mock-tool-use-{}", self.completion_count),
+        ))
+    }
+}
+
+impl MockLanguageModelProvider {
+    pub fn new(cx: &mut App) -> Self {
+        let state = cx.new(|cx| {
+            cx.observe_global::<SettingsStore>(|this: &mut State, cx| {
+                let fixture_path = Self::fixture_path(cx);
+                if this.fixture_path != fixture_path {
+                    this.fixture_path = fixture_path;
+                    this.load_fixture(cx).detach_and_log_err(cx);
+                }
+            })
+            .detach();
+            State {
+                fixture_path: Self::fixture_path(cx),
+                fixture: None,
+                load_error: None,
+                cursors: HashMap::default(),
+                completion_count: 0,
+            }
+        });
+
+        Self { state }
+    }
+
+    fn fixture_path(cx: &App) -> Option<PathBuf> {
+        let path = crate::AllLanguageModelSettings::get_global(cx)
+            .mock
+            .fixture_path
+            .as_ref()?;
+        match path.strip_prefix("~") {
+            Ok(relative_path) => Some(util::paths::home_dir().join(relative_path)),
+            Err(_) => Some(path.clone()),
+        }
+    }
+
+    fn create_language_model(&self, model: MockModel) -> Arc<dyn LanguageModel> {
+        Arc::new(MockLanguageModel {
+            id: LanguageModelId::from(model.name.clone()),
+            model,
+            state: self.state.clone(),
+        })
+    }
+}
+
+impl LanguageModelProviderState for MockLanguageModelProvider {
+    type ObservableEntity = State;
+
+    fn observable_entity(&self) -> Option<Entity<Self::ObservableEntity>> {
+        Some(self.state.clone())
+    }
+}
+
+impl LanguageModelProvider for MockLanguageModelProvider {
+    fn id(&self) -> LanguageModelProviderId {
+        PROVIDER_ID
+    }
+
+    fn name(&self) -> LanguageModelProviderName {
+        PROVIDER_NAME
+    }
+
+    fn icon(&self) -> IconOrSvg {
+        IconOrSvg::Icon(IconName::FileCode)
+    }
+
+    fn default_model(&self, cx: &App) -> Option<Arc<dyn LanguageModel>> {
+        self.provided_models(cx).into_iter().next()
+    }
+
+    fn default_fast_model(&self, cx: &App) -> Option<Arc<dyn LanguageModel>> {
+        self.default_model(cx)
+    }
+
+    fn provided_models(&self, cx: &App) -> Vec<Arc<dyn LanguageModel>> {
+        let Some(fixture) = self.state.read(cx).fixture.clone() else {
+            return Vec::new();
+        };
+        fixture
+            .models()
+            .into_iter()
+            .map(|model| self.create_language_model(model))
+            .collect()
+    }
+
+    fn is_authenticated(&self, cx: &App) -> bool {
+        self.state.read(cx).is_authenticated()
+    }
+
+    fn authenticate(&self, cx: &mut App) -> Task<Result<(), AuthenticateError>> {
+        self.state.update(cx, |state, cx| state.authenticate(cx))
+    }
+
+    fn configuration_view(
+        &self,
+        _target_agent: language_model::ConfigurationViewTargetAgent,
+        _window: &mut Window,
+        cx: &mut App,
+    ) -> AnyView {
+        cx.new(|cx| ConfigurationView::new(self.state.clone(), cx))
+            .into()
+    }
+
+    fn reset_credentials(&self, cx: &mut App) -> Task<Result<()>> {
+        self.state.update(cx, |state, cx| state.load_fixture(cx))
+    }
+}
+
+pub struct MockLanguageModel {
+    id: LanguageModelId,
+    model: MockModel,
+    state: Entity<State>,
+}
+
+impl LanguageModel for MockLanguageModel {
+    fn id(&self) -> LanguageModelId {
+        self.id.clone()
+    }
+
+    fn name(&self) -> LanguageModelName {
+        LanguageModelName::from(
+            self.model
+                .display_name
+                .clone()
+                .unwrap_or_else(|| self.model.name.clone()),
+        )
+    }
+
+    fn provider_id(&self) -> LanguageModelProviderId {
+        PROVIDER_ID
+    }
+
+    fn provider_name(&self) -> LanguageModelProviderName {
+        PROVIDER_NAME
+    }
+
+    fn supports_tools(&self) -> bool {
+        self.model.supports_tools
+    }
+
+    fn supports_tool_choice(&self, _choice: LanguageModelToolChoice) -> bool {
+        self.model.supports_tools
+    }
+
+    fn supports_images(&self) -> bool {
+        self.model.supports_images
+    }
+
+    fn supports_thinking(&self) -> bool {
+        self.model.supports_thinking
+    }
+
+    fn telemetry_id(&self) -> String {
+        format!("mock/{}", self.model.name)
+    }
+
+    fn max_token_count(&self) -> u64 {
+        self.model.max_tokens
+    }
+
+    fn count_tokens(
+        &self,
+        request: LanguageModelRequest,
+        cx: &App,
+    ) -> BoxFuture<'static, Result<u64>> {
+        cx.background_spawn(async move {
+            let characters = request
+                .messages
+                .iter()
+                .map(|message| message.string_contents().len())
+                .sum::<usize>();
+            Ok((characters / 4) as u64)
+        })
+        .boxed()
+    }
+
+    fn stream_completion(
+        &self,
+        request: LanguageModelRequest,
+        cx: &AsyncApp,
+    ) -> BoxFuture<
+        'static,
+        Result<
+            BoxStream<'static, Result<LanguageModelCompletionEvent, LanguageModelCompletionError>>,
+            LanguageModelCompletionError,
+        >,
+    > {
+        let traffic = LanguageModelTrafficLog::start_entry(self, &request, cx);
+        let steps = self
+            .state
+            .update(&mut cx.clone(), |state, _cx| state.next_steps(&request));
+        let executor = cx.background_executor().clone();
+
+        let future = async move {
+            let mut steps = steps?.into_iter().peekable();
+            // A script that starts with an error fails the request itself, the way a real
+            // provider rejects a request before it starts streaming.
+            if let Some(MockStep::Emit(Err(_))) = steps.peek()
+                && let Some(MockStep::Emit(Err(error))) = steps.next()
+            {
+                return Err(error);
+            }
+
+            let stream = futures::stream::iter(steps).filter_map(move |step| {
+                let executor = executor.clone();
+                async move {
+                    match step {
+                        MockStep::Emit(event) => Some(event),
+                        MockStep::Wait(duration) => {
+                            executor.timer(duration).await;
+                            None
+                        }
+                    }
+                }
+            });
+            Ok(stream.boxed())
+        }
+        .boxed();
+        log_completion_traffic(traffic, future)
+    }
+}
+
+struct ConfigurationView {
+    state: Entity<State>,
+}
+
+impl ConfigurationView {
+    fn new(state: Entity<State>, cx: &mut Context<Self>) -> Self {
+        cx.observe(&state, |_, _, cx| cx.notify()).detach();
+        state
+            .update(cx, |state, cx| state.authenticate(cx))
+            .detach();
+        Self { state }
+    }
+
+    fn reload_fixture(&self, cx: &mut App) {
+        self.state
+            .update(cx, |state, cx| state.load_fixture(cx))
+            .detach_and_log_err(cx);
+    }
+}
+
+impl Render for ConfigurationView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = self.state.read(cx);
+        let intro = "Replay scripted responses from a local fixture file, without network access.";
+
+        let status = if let Some(path) = state.fixture_path.as_ref() {
+            if let Some(error) = state.load_error.clone() {
+                Label::new(error).color(Color::Error).into_any_element()
+            } else if let Some(fixture) = state.fixture.as_ref() {
+                Label::new(format!(
+                    "Loaded {} responses from {}",
+                    fixture.responses.len(),
+                    path.display()
+                ))
+                .color(Color::Muted)
+                .into_any_element()
+            } else {
+                Label::new("Loading fixture...").into_any_element()
+            }
+        } else {
+            List::new()
+                .child(
+                    ListBulletItem::new("")
+                        .child(Label::new("Set"))
+                        .child(Label::new("language_models.mock.fixture_path").inline_code(cx))
+                        .child(Label::new("in your settings")),
+                )
+                .child(ListBulletItem::new(
+                    "Fixtures are JSON or YAML files with a list of scripted responses.",
+                ))
+                .into_any_element()
+        };
+
+        v_flex()
+            .gap_2()
+            .child(Label::new(intro))
+            .child(status)
+            .when(state.fixture_path.is_some(), |this| {
+                this.child(
+                    Button::new("reload-mock-fixture", "Reload Fixture")
+                        .icon_position(IconPosition::Start)
+                        .icon_size(IconSize::XSmall)
+                        .icon(IconName::RotateCw)
+                        .on_click(cx.listener(|this, _, _window, cx| this.reload_fixture(cx))),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use language_model::{LanguageModelRequestMessage, MessageContent};
+
+    fn request(thread_id: &str, user_message: &str) -> LanguageModelRequest {
+        LanguageModelRequest {
+            thread_id: Some(thread_id.into()),
+            messages: vec![LanguageModelRequestMessage {
+                role: Role::User,
+                content: vec![MessageContent::Text(user_message.into())],
+                cache: false,
+                reasoning_details: None,
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn events(steps: Vec<MockStep>) -> Vec<String> {
+        steps
+            .into_iter()
+            .map(|step| match step {
+                MockStep::Emit(Ok(event)) => format!("{event:?}"),
+                MockStep::Emit(Err(error)) => format!("error: {error}"),
+                MockStep::Wait(duration) => format!("wait {}ms", duration.as_millis()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_json_and_yaml_fixtures() {
+        let json = MockFixture::parse(
+            Path::new("fixture.json"),
+            r#"{
+                "models": [{ "name": "scripted", "supports_thinking": true }],
+                "responses": [
+                    { "events": [
+                        { "type": "thinking", "text": "Hmm" },
+                        { "type": "tool_call", "name": "read_file", "input": { "path": "a.rs" } },
+                        { "type": "usage", "input_tokens": 10, "output_tokens": 2 }
+                    ] }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let yaml = MockFixture::parse(
+            Path::new("fixture.yaml"),
+            "
+models:
+  - name: scripted
+    supports_thinking: true
+responses:
+  - events:
+      - type: thinking
+        text: Hmm
+      - type: tool_call
+        name: read_file
+        input:
+          path: a.rs
+      - type: usage
+        input_tokens: 10
+        output_tokens: 2
+",
+        )
+        .unwrap();
+
+        assert_eq!(json, yaml);
+        assert_eq!(json.models()[0].max_tokens, DEFAULT_MAX_TOKENS);
+        assert!(json.models()[0].supports_tools);
+        assert_eq!(
+            json.responses[0].events[1],
+            MockEvent::ToolCall {
+                id: None,
+                name: "read_file".into(),
+                input: serde_json::json!({ "path": "a.rs" }),
+            }
+        );
+        assert!(MockFixture::parse(Path::new("fixture.json"), "responses: []").is_err());
+    }
+
+    #[test]
+    fn replays_responses_per_thread() {
+        let fixture: MockFixture = serde_json::from_value(serde_json::json!({
+            "responses": [
+                { "events": [{ "type": "text", "text": "first" }] },
+                { "match": "help", "events": [{ "type": "text", "text": "matched" }] },
+                { "events": [{ "type": "text", "text": "second" }] },
+                { "intent": "generate_git_commit_message", "events": [{ "type": "text", "text": "Fix bug" }] }
+            ]
+        }))
+        .unwrap();
+        let mut cursors = HashMap::default();
+        let mut next = |request: LanguageModelRequest| {
+            fixture
+                .next_response(&request, &mut cursors)
+                .map(|response| events(mock_steps(response, "id"))[0].clone())
+                .map_err(|error| error.to_string())
+        };
+
+        assert_eq!(next(request("a", "hi")), Ok(r#"Text("first")"#.into()));
+        assert_eq!(
+            next(request("a", "please help")),
+            Ok(r#"Text("matched")"#.into())
+        );
+        assert_eq!(next(request("b", "hi")), Ok(r#"Text("first")"#.into()));
+        assert_eq!(next(request("a", "hi")), Ok(r#"Text("second")"#.into()));
+        assert!(next(request("a", "hi")).is_err());
+
+        let mut commit_message = request("a", "diff");
+        commit_message.intent = Some(CompletionIntent::GenerateGitCommitMessage);
+        assert_eq!(next(commit_message), Ok(r#"Text("Fix bug")"#.into()));
+
+        let mut title = request("a", "title");
+        title.intent = Some(CompletionIntent::ThreadSummarization);
+        assert_eq!(next(title), Ok(format!("Text({DEFAULT_THREAD_TITLE:?})")));
+    }
+
+    #[test]
+    fn maps_events_to_completion_events() {
+        let response: MockResponse = serde_json::from_value(serde_json::json!({
+            "events": [
+                { "type": "text", "text": "Reading" },
+                { "type": "delay", "ms": 250 },
+                { "type": "tool_call", "name": "read_file", "input": {} }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            events(mock_steps(response, "call")),
+            vec![
+                r#"Text("Reading")"#.to_string(),
+                "wait 250ms".to_string(),
+                format!(
+                    "{:?}",
+                    LanguageModelCompletionEvent::ToolUse(LanguageModelToolUse {
+                        id: "call-2".into(),
+                        name: "read_file".into(),
+                        raw_input: "{}".into(),
+                        input: serde_json::json!({}),
+                        is_input_complete: true,
+                        thought_signature: None,
+                    })
+                ),
+                format!(
+                    "{:?}",
+                    LanguageModelCompletionEvent::Stop(StopReason::ToolUse)
+                ),
+            ]
+        );
+
+        let response: MockResponse = serde_json::from_value(serde_json::json!({
+            "events": [
+                { "type": "text", "text": "Partial" },
+                { "type": "rate_limit", "retry_after_secs": 3 },
+                { "type": "text", "text": "unreachable" }
+            ]
+        }))
+        .unwrap();
+        let steps = mock_steps(response, "call");
+        assert_eq!(steps.len(), 2);
+        assert!(matches!(
+            steps[1],
+            MockStep::Emit(Err(LanguageModelCompletionError::RateLimitExceeded {
+                retry_after: Some(retry_after),
+                ..
+            })) if retry_after == Duration::from_secs(3)
+        ));
+    }
+}
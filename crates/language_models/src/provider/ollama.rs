@@ -9,7 +9,8 @@ use language_model::{
     LanguageModelCompletionEvent, LanguageModelId, LanguageModelName, LanguageModelProvider,
     LanguageModelProviderId, LanguageModelProviderName, LanguageModelProviderState,
     LanguageModelRequest, LanguageModelRequestTool, LanguageModelToolChoice, LanguageModelToolUse,
-    LanguageModelToolUseId, MessageContent, RateLimiter, Role, StopReason, TokenUsage, env_var,
+    LanguageModelToolUseId, LanguageModelTrafficLog, MessageContent, RateLimiter, Role, StopReason,
+    TokenUsage, env_var, log_completion_traffic,
 };
 use menu;
 use ollama::{
@@ -490,6 +491,7 @@ impl LanguageModel for OllamaLanguageModel {
             LanguageModelCompletionError,
         >,
     > {
+        let traffic = LanguageModelTrafficLog::start_entry(self, &request, cx);
         let request = self.to_ollama_request(request);
 
         let http_client = self.http_client.clone();
@@ -506,7 +508,7 @@ impl LanguageModel for OllamaLanguageModel {
             Ok(stream)
         });
 
-        future.map_ok(|f| f.boxed()).boxed()
+        log_completion_traffic(traffic, future.map_ok(|f| f.boxed()).boxed())
     }
 }
 
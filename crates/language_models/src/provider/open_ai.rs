@@ -10,8 +10,8 @@ use language_model::{
     LanguageModelProvider, LanguageModelProviderId, LanguageModelProviderName,
     LanguageModelProviderState, LanguageModelRequest, LanguageModelRequestMessage,
     LanguageModelToolChoice, LanguageModelToolResult, LanguageModelToolResultContent,
-    LanguageModelToolUse, LanguageModelToolUseId, MessageContent, RateLimiter, Role, StopReason,
-    TokenUsage, env_var,
+    LanguageModelToolUse, LanguageModelToolUseId, LanguageModelTrafficLog, MessageContent,
+    RateLimiter, Role, StopReason, TokenUsage, env_var, log_completion_traffic, log_raw_traffic,
 };
 use menu;
 use open_ai::responses::{
@@ -369,7 +369,8 @@ impl LanguageModel for OpenAiLanguageModel {
             LanguageModelCompletionError,
         >,
     > {
-        if self.model.supports_chat_completions() {
+        let traffic = LanguageModelTrafficLog::start_entry(self, &request, cx);
+        let future = if self.model.supports_chat_completions() {
             let request = into_open_ai(
                 request,
                 self.model.id(),
@@ -379,9 +380,11 @@ impl LanguageModel for OpenAiLanguageModel {
                 self.model.reasoning_effort(),
             );
             let completions = self.stream_completion(request, cx);
+            let raw_traffic = traffic.clone();
             async move {
                 let mapper = OpenAiEventMapper::new();
-                Ok(mapper.map_stream(completions.await?).boxed())
+                let completions = log_raw_traffic(raw_traffic, completions.await?);
+                Ok(mapper.map_stream(completions).boxed())
             }
             .boxed()
         } else {
@@ -399,7 +402,8 @@ impl LanguageModel for OpenAiLanguageModel {
                 Ok(mapper.map_stream(completions.await?).boxed())
             }
             .boxed()
-        }
+        };
+        log_completion_traffic(traffic, future)
     }
 }
 
@@ -408,13 +412,16 @@ pub fn into_open_ai(
     model_id: &str,
     supports_parallel_tool_calls: bool,
     supports_prompt_cache_key: bool,
+    supports_cache_control: bool,
     max_output_tokens: Option<u64>,
     reasoning_effort: Option<ReasoningEffort>,
 ) -> open_ai::Request {
     let stream = !model_id.starts_with("o1-");
+    let prompt_cache_key = request.thread_id.or_else(|| request.prompt_id.clone());
 
     let mut messages = Vec::new();
     for message in request.messages {
+        let is_cache_breakpoint = supports_cache_control && message.cache;
         for content in message.content {
             match content {
                 MessageContent::Text(text) | MessageContent::Thinking { text, .. } => {
@@ -427,7 +434,10 @@ pub fn into_open_ai(
                     };
                     if should_add {
                         add_message_content_part(
-                            open_ai::MessagePart::Text { text },
+                            open_ai::MessagePart::Text {
+                                text,
+                                cache_control: None,
+                            },
                             message.role,
                             &mut messages,
                         );
@@ -474,6 +484,7 @@ pub fn into_open_ai(
                         LanguageModelToolResultContent::Text(text) => {
                             vec![open_ai::MessagePart::Text {
                                 text: text.to_string(),
+                                cache_control: None,
                             }]
                         }
                         LanguageModelToolResultContent::Image(image) => {
@@ -493,6 +504,10 @@ pub fn into_open_ai(
                 }
             }
         }
+
+        if is_cache_breakpoint {
+            mark_cache_breakpoint(&mut messages);
+        }
     }
 
     open_ai::Request {
@@ -510,7 +525,7 @@ pub fn into_open_ai(
             None
         },
         prompt_cache_key: if supports_prompt_cache_key {
-            request.thread_id
+            prompt_cache_key
         } else {
             None
         },
@@ -546,7 +561,7 @@ pub fn into_open_ai_response(
 
     let LanguageModelRequest {
         thread_id,
-        prompt_id: _,
+        prompt_id,
         intent: _,
         messages,
         tools,
@@ -555,6 +570,7 @@ pub fn into_open_ai_response(
         temperature,
         thinking_allowed: _,
         thinking_effort: _,
+        credential_profile: _,
     } = request;
 
     let mut input_items = Vec::new();
@@ -592,7 +608,7 @@ pub fn into_open_ai_response(
         }),
         tools,
         prompt_cache_key: if supports_prompt_cache_key {
-            thread_id
+            thread_id.or(prompt_id)
         } else {
             None
         },
@@ -750,6 +766,23 @@ fn add_message_content_part(
     }
 }
 
+/// Marks the end of the most recent message as a prompt caching breakpoint,
+/// mirroring how the Anthropic provider places `cache_control` on the last
+/// content block of cached messages.
+fn mark_cache_breakpoint(messages: &mut [open_ai::RequestMessage]) {
+    let content = match messages.last_mut() {
+        Some(open_ai::RequestMessage::User { content })
+        | Some(open_ai::RequestMessage::System { content })
+        | Some(open_ai::RequestMessage::Tool { content, .. })
+        | Some(open_ai::RequestMessage::Assistant {
+            content: Some(content),
+            ..
+        }) => content,
+        Some(open_ai::RequestMessage::Assistant { content: None, .. }) | None => return,
+    };
+    content.mark_cache_breakpoint();
+}
+
 pub struct OpenAiEventMapper {
     tool_calls_by_index: HashMap<usize, RawToolCall>,
 }
@@ -780,12 +813,9 @@ impl OpenAiEventMapper {
     ) -> Vec<Result<LanguageModelCompletionEvent, LanguageModelCompletionError>> {
         let mut events = Vec::new();
         if let Some(usage) = event.usage {
-            events.push(Ok(LanguageModelCompletionEvent::UsageUpdate(TokenUsage {
-                input_tokens: usage.prompt_tokens,
-                output_tokens: usage.completion_tokens,
-                cache_creation_input_tokens: 0,
-                cache_read_input_tokens: 0,
-            })));
+            events.push(Ok(LanguageModelCompletionEvent::UsageUpdate(
+                token_usage_from_usage(&usage),
+            )));
         }
 
         let Some(choice) = event.choices.first() else {
@@ -1119,12 +1149,39 @@ impl OpenAiResponseEventMapper {
     }
 }
 
+/// OpenAI reports cached tokens as a subset of the prompt tokens, whereas
+/// `TokenUsage` counts them separately, so they're subtracted from the input.
+fn token_usage_from_usage(usage: &open_ai::Usage) -> TokenUsage {
+    let (cache_read_input_tokens, cache_creation_input_tokens) = usage
+        .prompt_tokens_details
+        .as_ref()
+        .map_or((0, 0), |details| {
+            (details.cached_tokens, details.cache_write_tokens)
+        });
+    TokenUsage {
+        input_tokens: usage
+            .prompt_tokens
+            .saturating_sub(cache_read_input_tokens)
+            .saturating_sub(cache_creation_input_tokens),
+        output_tokens: usage.completion_tokens,
+        cache_creation_input_tokens,
+        cache_read_input_tokens,
+    }
+}
+
 fn token_usage_from_response_usage(usage: &ResponsesUsage) -> TokenUsage {
+    let cache_read_input_tokens = usage
+        .input_tokens_details
+        .as_ref()
+        .map_or(0, |details| details.cached_tokens);
     TokenUsage {
-        input_tokens: usage.input_tokens.unwrap_or_default(),
+        input_tokens: usage
+            .input_tokens
+            .unwrap_or_default()
+            .saturating_sub(cache_read_input_tokens),
         output_tokens: usage.output_tokens.unwrap_or_default(),
         cache_creation_input_tokens: 0,
-        cache_read_input_tokens: 0,
+        cache_read_input_tokens,
     }
 }
 
@@ -1433,6 +1490,7 @@ mod tests {
             temperature: None,
             thinking_allowed: true,
             thinking_effort: None,
+            credential_profile: None,
         };
 
         // Validate that all models are supported by tiktoken-rs
@@ -1469,6 +1527,7 @@ mod tests {
                         input_tokens: Some(5),
                         output_tokens: Some(3),
                         total_tokens: Some(8),
+                        input_tokens_details: None,
                     }),
                     ..Default::default()
                 },
@@ -1570,6 +1629,7 @@ mod tests {
             temperature: None,
             thinking_allowed: false,
             thinking_effort: None,
+            credential_profile: None,
         };
 
         let response = into_open_ai_response(
@@ -1694,6 +1754,7 @@ mod tests {
                     input_tokens: Some(10),
                     output_tokens: Some(20),
                     total_tokens: Some(30),
+                    input_tokens_details: None,
                 }),
                 ..Default::default()
             },
@@ -1970,4 +2031,81 @@ mod tests {
             LanguageModelCompletionEvent::Stop(StopReason::ToolUse)
         ));
     }
+
+    #[test]
+    fn into_open_ai_marks_cache_breakpoints() {
+        let request = LanguageModelRequest {
+            thread_id: None,
+            prompt_id: Some("prompt-1".into()),
+            intent: None,
+            messages: vec![
+                LanguageModelRequestMessage {
+                    role: Role::System,
+                    content: vec![MessageContent::Text("system".into())],
+                    cache: true,
+                    reasoning_details: None,
+                },
+                LanguageModelRequestMessage {
+                    role: Role::User,
+                    content: vec![MessageContent::Text("hello".into())],
+                    cache: false,
+                    reasoning_details: None,
+                },
+            ],
+            tools: vec![],
+            tool_choice: None,
+            stop: vec![],
+            temperature: None,
+            thinking_allowed: true,
+            thinking_effort: None,
+            credential_profile: None,
+        };
+
+        let payload = into_open_ai(request.clone(), "model", false, true, true, None, None);
+        assert_eq!(payload.prompt_cache_key.as_deref(), Some("prompt-1"));
+        assert_eq!(
+            serde_json::to_value(&payload.messages).unwrap(),
+            json!([
+                {
+                    "role": "system",
+                    "content": [
+                        {
+                            "type": "text",
+                            "text": "system",
+                            "cache_control": { "type": "ephemeral" }
+                        }
+                    ]
+                },
+                { "role": "user", "content": "hello" }
+            ])
+        );
+
+        let payload = into_open_ai(request, "model", false, false, false, None, None);
+        assert_eq!(payload.prompt_cache_key, None);
+        assert_eq!(
+            serde_json::to_value(&payload.messages).unwrap()[0],
+            json!({ "role": "system", "content": "system" })
+        );
+    }
+
+    #[test]
+    fn chat_completion_usage_separates_cached_tokens() {
+        let usage: open_ai::Usage = serde_json::from_value(json!({
+            "prompt_tokens": 100,
+            "completion_tokens": 20,
+            "total_tokens": 120,
+            "prompt_tokens_details": { "cached_tokens": 60, "cache_write_tokens": 30 }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            token_usage_from_usage(&usage),
+            TokenUsage {
+                input_tokens: 10,
+                output_tokens: 20,
+                cache_creation_input_tokens: 30,
+                cache_read_input_tokens: 60,
+            }
+        );
+    }
 }
@@ -1,30 +1,36 @@
 use anyhow::{Result, anyhow};
 use convert_case::{Case, Casing};
+use fs::Fs;
 use futures::{AsyncReadExt, FutureExt, StreamExt, future::BoxFuture, stream};
 use gpui::{AnyView, App, AsyncApp, Context, Entity, SharedString, Task, Window};
 use http_client::{AsyncBody, HttpClient, Method, Request as HttpRequest};
 use language_model::{
-    ApiKeyState, AuthenticateError, EnvVar, IconOrSvg, LanguageModel, LanguageModelCompletionError,
-    LanguageModelCompletionEvent, LanguageModelId, LanguageModelName, LanguageModelProvider,
-    LanguageModelProviderId, LanguageModelProviderName, LanguageModelProviderState,
-    LanguageModelRequest, LanguageModelToolChoice, LanguageModelToolSchemaFormat, RateLimiter,
+    ApiKeyState, AuthenticateError, EnvVar, HttpRequestSnapshot, IconOrSvg, LanguageModel,
+    LanguageModelCacheConfiguration, LanguageModelCompletionError, LanguageModelCompletionEvent,
+    LanguageModelId, LanguageModelName, LanguageModelProvider, LanguageModelProviderId,
+    LanguageModelProviderName, LanguageModelProviderState, LanguageModelRequest,
+    LanguageModelToolChoice, LanguageModelToolSchemaFormat, LanguageModelTrafficLog, RateLimiter,
+    TrafficLogEntryHandle, log_completion_traffic,
 };
 use menu;
 use open_ai::{
-    ResponseStreamEvent,
+    EndpointOptions, ResponseStreamEvent,
     responses::{
         Request as ResponseRequest, StreamEvent as ResponsesStreamEvent,
-        stream_response_with_headers,
+        stream_response_with_options,
     },
-    stream_completion_with_headers,
+    stream_completion_with_options,
 };
 use serde::Deserialize;
-use settings::{Settings, SettingsStore};
+use settings::{Settings, SettingsStore, update_settings_file};
 use std::{collections::BTreeMap, sync::Arc};
 use ui::{ElevationIndex, Tooltip, prelude::*};
 use ui_input::InputField;
 use util::ResultExt;
 
+use crate::provider::auth_command::{
+    AuthCommand, AuthCommandTokenCache, RequestAuth, custom_headers,
+};
 use crate::provider::open_ai::{
     OpenAiEventMapper, OpenAiResponseEventMapper, into_open_ai, into_open_ai_response,
 };
@@ -36,6 +42,12 @@ const NANOGPT_API_KEY_ENV_VAR_NAME: &str = "NANOGPT_API_KEY";
 const NANOGPT_DEFAULT_MODEL_ID: &str = "minimax/minimax-m2.5";
 const NANOGPT_DEFAULT_MAX_INPUT_TOKENS: u64 = 200_000;
 
+fn profile_api_key_env_var_name(provider_id: &str, profile: &str) -> SharedString {
+    format!("{}_{}_API_KEY", provider_id, profile)
+        .to_case(Case::UpperSnake)
+        .into()
+}
+
 fn set_nanogpt_api_key_env_var(api_key: Option<&str>) {
     // SAFETY: This code intentionally mutates process environment variables to support the
     // NanoGPT-compatible client configuration path, and calls happen from serialized GPUI tasks.
@@ -78,6 +90,50 @@ impl ResolvedModel {
 pub struct OpenAiCompatibleSettings {
     pub api_url: String,
     pub available_models: Vec<AvailableModel>,
+    pub credential_profiles: Vec<SharedString>,
+    pub default_credential_profile: Option<SharedString>,
+    pub headers: BTreeMap<String, String>,
+    pub auth_command: Option<AuthCommand>,
+}
+
+/// The credential profile backed by the provider's original API key, which is stored under the
+/// bare API URL and read from the `<PROVIDER>_API_KEY` environment variable.
+pub const DEFAULT_CREDENTIAL_PROFILE: &str = "default";
+
+impl OpenAiCompatibleSettings {
+    fn has_credential_profile(&self, profile: &str) -> bool {
+        profile == DEFAULT_CREDENTIAL_PROFILE
+            || self
+                .credential_profiles
+                .iter()
+                .any(|candidate| candidate.as_ref() == profile)
+    }
+
+    fn default_credential_profile(&self) -> SharedString {
+        self.default_credential_profile
+            .clone()
+            .filter(|profile| self.has_credential_profile(profile))
+            .unwrap_or_else(|| DEFAULT_CREDENTIAL_PROFILE.into())
+    }
+
+    /// Resolves the profile a request should use, falling back to the default profile when the
+    /// requested one isn't configured for this provider.
+    fn resolve_credential_profile(&self, profile: Option<&str>) -> SharedString {
+        match profile {
+            Some(profile) if self.has_credential_profile(profile) => {
+                SharedString::new(profile.to_string())
+            }
+            _ => self.default_credential_profile(),
+        }
+    }
+
+    fn credential_profile_url(&self, profile: &str) -> SharedString {
+        if profile == DEFAULT_CREDENTIAL_PROFILE {
+            SharedString::new(self.api_url.as_str())
+        } else {
+            format!("{}#{}", self.api_url, profile).into()
+        }
+    }
 }
 
 pub struct OpenAiCompatibleLanguageModelProvider {
@@ -90,7 +146,9 @@ pub struct OpenAiCompatibleLanguageModelProvider {
 pub struct State {
     id: Arc<str>,
     api_key_state: ApiKeyState,
+    profile_api_key_states: BTreeMap<SharedString, ApiKeyState>,
     settings: OpenAiCompatibleSettings,
+    auth_command_tokens: AuthCommandTokenCache,
     http_client: Arc<dyn HttpClient>,
     dynamic_models: Vec<ResolvedModel>,
     fetch_dynamic_models_task: Option<Task<Result<(), LanguageModelCompletionError>>>,
@@ -99,18 +157,79 @@ pub struct State {
 
 impl State {
     fn is_authenticated(&self) -> bool {
-        self.api_key_state.has_key()
+        self.settings.auth_command.is_some() || self.api_key(None).is_some()
+    }
+
+    fn credential_profiles(&self) -> Vec<SharedString> {
+        if self.settings.credential_profiles.is_empty() {
+            return Vec::new();
+        }
+        std::iter::once(SharedString::from(DEFAULT_CREDENTIAL_PROFILE))
+            .chain(self.settings.credential_profiles.iter().cloned())
+            .collect()
+    }
+
+    fn api_key_state(&self, profile: &str) -> Option<&ApiKeyState> {
+        if profile == DEFAULT_CREDENTIAL_PROFILE {
+            Some(&self.api_key_state)
+        } else {
+            self.profile_api_key_states.get(profile)
+        }
+    }
+
+    fn api_key_state_mut(&mut self, profile: &SharedString) -> &mut ApiKeyState {
+        if profile.as_ref() == DEFAULT_CREDENTIAL_PROFILE {
+            return &mut self.api_key_state;
+        }
+        let url = self.settings.credential_profile_url(profile);
+        let id = self.id.clone();
+        self.profile_api_key_states
+            .entry(profile.clone())
+            .or_insert_with(|| {
+                ApiKeyState::new(url, EnvVar::new(profile_api_key_env_var_name(&id, profile)))
+            })
+    }
+
+    /// Returns how to authenticate a request with the given credential profile. A configured
+    /// auth command takes precedence over API keys.
+    fn request_auth(&self, profile: Option<&str>) -> RequestAuth {
+        RequestAuth::new(
+            self.api_key(profile),
+            self.settings.auth_command.as_ref(),
+            &self.auth_command_tokens,
+        )
+    }
+
+    /// Returns the API key for the given credential profile, or for the provider's default
+    /// profile when `None` or an unknown profile is given.
+    fn api_key(&self, profile: Option<&str>) -> Option<Arc<str>> {
+        let profile = self.settings.resolve_credential_profile(profile);
+        self.api_key_state(&profile)?
+            .key(&self.settings.credential_profile_url(&profile))
     }
 
     fn set_api_key(&mut self, api_key: Option<String>, cx: &mut Context<Self>) -> Task<Result<()>> {
-        if self.is_nanogpt() {
+        let profile = self.settings.default_credential_profile();
+        self.set_profile_api_key(profile, api_key, cx)
+    }
+
+    fn set_profile_api_key(
+        &mut self,
+        profile: SharedString,
+        api_key: Option<String>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if self.is_nanogpt() && profile == self.settings.default_credential_profile() {
             set_nanogpt_api_key_env_var(api_key.as_deref().filter(|value| !value.is_empty()));
         }
 
-        let api_url = SharedString::new(self.settings.api_url.as_str());
-        let store_task =
-            self.api_key_state
-                .store(api_url, api_key, |this| &mut this.api_key_state, cx);
+        let api_url = self.settings.credential_profile_url(&profile);
+        let store_task = self.api_key_state_mut(&profile).store(
+            api_url,
+            api_key,
+            move |this| this.api_key_state_mut(&profile),
+            cx,
+        );
 
         cx.spawn(async move |this, cx| {
             let result = store_task.await;
@@ -124,10 +243,15 @@ impl State {
     }
 
     fn authenticate(&mut self, cx: &mut Context<Self>) -> Task<Result<(), AuthenticateError>> {
-        let api_url = SharedString::new(self.settings.api_url.clone());
-        let authenticate_task =
-            self.api_key_state
-                .load_if_needed(api_url, |this| &mut this.api_key_state, cx);
+        let default_profile = self.settings.default_credential_profile();
+        for profile in self.credential_profiles() {
+            if profile != default_profile {
+                // Loading continues in the background; only the default profile gates
+                // authentication.
+                let _task = self.load_profile_api_key(profile, cx);
+            }
+        }
+        let authenticate_task = self.load_profile_api_key(default_profile, cx);
 
         cx.spawn(async move |this, cx| {
             let result = authenticate_task.await;
@@ -140,6 +264,19 @@ impl State {
         })
     }
 
+    fn load_profile_api_key(
+        &mut self,
+        profile: SharedString,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<(), AuthenticateError>> {
+        let api_url = self.settings.credential_profile_url(&profile);
+        self.api_key_state_mut(&profile).load_if_needed(
+            api_url,
+            move |this| this.api_key_state_mut(&profile),
+            cx,
+        )
+    }
+
     fn is_nanogpt(&self) -> bool {
         self.id.as_ref() == NANOGPT_PROVIDER_ID
     }
@@ -149,7 +286,7 @@ impl State {
             return;
         }
 
-        set_nanogpt_api_key_env_var(self.api_key_state.key(&self.settings.api_url).as_deref());
+        set_nanogpt_api_key_env_var(self.api_key(None).as_deref());
     }
 
     fn restart_dynamic_models_task(&mut self, cx: &mut Context<Self>) {
@@ -178,7 +315,7 @@ impl State {
     ) -> Task<Result<(), LanguageModelCompletionError>> {
         let http_client = self.http_client.clone();
         let api_url = self.settings.api_url.clone();
-        let api_key = self.api_key_state.key(&api_url);
+        let api_key = self.api_key(None);
 
         cx.spawn(async move |this, cx| {
             let models = fetch_nanogpt_models(http_client.as_ref(), &api_url, api_key.as_deref())
@@ -217,7 +354,7 @@ impl State {
     ) -> Task<Result<(), LanguageModelCompletionError>> {
         let http_client = self.http_client.clone();
         let api_url = self.settings.api_url.clone();
-        let api_key = self.api_key_state.key(&api_url);
+        let api_key = self.api_key(None);
 
         cx.spawn(async move |this, cx| {
             let provider_options = stream::iter(models.iter().cloned())
@@ -330,7 +467,17 @@ impl OpenAiCompatibleLanguageModelProvider {
                         |this| &mut this.api_key_state,
                         cx,
                     );
+                    let profiles_changed = this.settings.api_url != settings.api_url
+                        || this.settings.credential_profiles != settings.credential_profiles;
                     this.settings = settings;
+                    if profiles_changed {
+                        this.profile_api_key_states.clear();
+                        for profile in this.credential_profiles() {
+                            if profile.as_ref() != DEFAULT_CREDENTIAL_PROFILE {
+                                let _task = this.load_profile_api_key(profile, cx);
+                            }
+                        }
+                    }
                     this.sync_nanogpt_api_key_env();
                     this.restart_dynamic_models_task(cx);
                     cx.notify();
@@ -344,7 +491,9 @@ impl OpenAiCompatibleLanguageModelProvider {
                     SharedString::new(settings.api_url.as_str()),
                     EnvVar::new(api_key_env_var_name),
                 ),
+                profile_api_key_states: BTreeMap::new(),
                 settings,
+                auth_command_tokens: AuthCommandTokenCache::default(),
                 http_client: http_client.clone(),
                 dynamic_models: Vec::new(),
                 fetch_dynamic_models_task: None,
@@ -450,8 +599,20 @@ impl LanguageModelProvider for OpenAiCompatibleLanguageModelProvider {
     }
 
     fn reset_credentials(&self, cx: &mut App) -> Task<Result<()>> {
-        self.state
-            .update(cx, |state, cx| state.set_api_key(None, cx))
+        self.state.update(cx, |state, cx| {
+            state.auth_command_tokens.clear();
+            state.set_api_key(None, cx)
+        })
+    }
+
+    fn credential_profiles(&self, cx: &App) -> Vec<SharedString> {
+        self.state.read(cx).credential_profiles()
+    }
+
+    fn default_credential_profile(&self, cx: &App) -> Option<SharedString> {
+        let state = self.state.read(cx);
+        (!state.settings.credential_profiles.is_empty())
+            .then(|| state.settings.default_credential_profile())
     }
 }
 
@@ -469,6 +630,8 @@ impl OpenAiCompatibleLanguageModel {
     fn stream_completion(
         &self,
         mut request: open_ai::Request,
+        credential_profile: Option<String>,
+        traffic: Option<TrafficLogEntryHandle>,
         cx: &AsyncApp,
     ) -> BoxFuture<
         'static,
@@ -479,44 +642,55 @@ impl OpenAiCompatibleLanguageModel {
     > {
         let http_client = self.http_client.clone();
 
-        let (api_key, api_url) = self.state.read_with(cx, |state, _cx| {
-            let api_url = &state.settings.api_url;
+        let (auth, api_url, configured_headers) = self.state.read_with(cx, |state, _cx| {
             (
-                state.api_key_state.key(api_url),
+                state.request_auth(credential_profile.as_deref()),
                 state.settings.api_url.clone(),
+                state.settings.headers.clone(),
             )
         });
+        let mut additional_headers = custom_headers(&configured_headers);
 
         if self.model.provider_override.is_some() {
             request.billing_mode = Some("paygo".to_string());
         }
 
-        let additional_headers =
-            self.model
-                .provider_override
-                .as_ref()
-                .map_or_else(Vec::new, |provider| {
-                    vec![
-                        ("X-Provider".to_string(), provider.to_string()),
-                        ("X-Billing-Mode".to_string(), "paygo".to_string()),
-                    ]
-                });
+        if let Some(provider) = self.model.provider_override.as_ref() {
+            additional_headers.extend([
+                ("X-Provider".to_string(), provider.to_string()),
+                ("X-Billing-Mode".to_string(), "paygo".to_string()),
+            ]);
+        }
+
+        if let Some(traffic) = traffic.as_ref() {
+            traffic.set_http_request(http_request_snapshot(
+                format!("{api_url}/chat/completions"),
+                auth.snapshot_header(&EndpointOptions::default()),
+                &additional_headers,
+                &configured_headers,
+                &request,
+            ));
+        }
 
         let provider = self.provider_name.clone();
         let future = self.request_limiter.stream(async move {
-            let Some(api_key) = api_key else {
-                return Err(LanguageModelCompletionError::NoApiKey { provider });
-            };
-            let request = stream_completion_with_headers(
+            let (api_key, auth_header) = auth.resolve(provider.clone()).await?;
+            additional_headers.extend(auth_header);
+            let request = stream_completion_with_options(
                 http_client.as_ref(),
                 provider.0.as_str(),
                 &api_url,
-                &api_key,
+                api_key.as_deref(),
                 request,
                 &additional_headers,
+                &EndpointOptions::default(),
             );
             let response = request.await?;
-            Ok(response)
+            Ok(response.inspect(move |event| {
+                if let (Some(traffic), Ok(event)) = (traffic.as_ref(), event) {
+                    traffic.record_raw_event(event);
+                }
+            }))
         });
 
         async move { Ok(future.await?.boxed()) }.boxed()
@@ -525,46 +699,55 @@ impl OpenAiCompatibleLanguageModel {
     fn stream_response(
         &self,
         mut request: ResponseRequest,
+        credential_profile: Option<String>,
+        traffic: Option<TrafficLogEntryHandle>,
         cx: &AsyncApp,
     ) -> BoxFuture<'static, Result<futures::stream::BoxStream<'static, Result<ResponsesStreamEvent>>>>
     {
         let http_client = self.http_client.clone();
 
-        let (api_key, api_url) = self.state.read_with(cx, |state, _cx| {
-            let api_url = &state.settings.api_url;
+        let (auth, api_url, configured_headers) = self.state.read_with(cx, |state, _cx| {
             (
-                state.api_key_state.key(api_url),
+                state.request_auth(credential_profile.as_deref()),
                 state.settings.api_url.clone(),
+                state.settings.headers.clone(),
             )
         });
+        let mut additional_headers = custom_headers(&configured_headers);
 
         if self.model.provider_override.is_some() {
             request.billing_mode = Some("paygo".to_string());
         }
 
-        let additional_headers =
-            self.model
-                .provider_override
-                .as_ref()
-                .map_or_else(Vec::new, |provider| {
-                    vec![
-                        ("X-Provider".to_string(), provider.to_string()),
-                        ("X-Billing-Mode".to_string(), "paygo".to_string()),
-                    ]
-                });
+        if let Some(provider) = self.model.provider_override.as_ref() {
+            additional_headers.extend([
+                ("X-Provider".to_string(), provider.to_string()),
+                ("X-Billing-Mode".to_string(), "paygo".to_string()),
+            ]);
+        }
+
+        if let Some(traffic) = traffic.as_ref() {
+            traffic.set_http_request(http_request_snapshot(
+                format!("{api_url}/responses"),
+                auth.snapshot_header(&EndpointOptions::default()),
+                &additional_headers,
+                &configured_headers,
+                &request,
+            ));
+        }
 
         let provider = self.provider_name.clone();
         let future = self.request_limiter.stream(async move {
-            let Some(api_key) = api_key else {
-                return Err(LanguageModelCompletionError::NoApiKey { provider });
-            };
-            let request = stream_response_with_headers(
+            let (api_key, auth_header) = auth.resolve(provider.clone()).await?;
+            additional_headers.extend(auth_header);
+            let request = stream_response_with_options(
                 http_client.as_ref(),
                 provider.0.as_str(),
                 &api_url,
-                &api_key,
+                api_key.as_deref(),
                 request,
                 &additional_headers,
+                &EndpointOptions::default(),
             );
             let response = request.await?;
             Ok(response)
@@ -636,6 +819,17 @@ impl LanguageModel for OpenAiCompatibleLanguageModel {
         self.model.max_output_tokens
     }
 
+    fn cache_configuration(&self) -> Option<LanguageModelCacheConfiguration> {
+        self.model
+            .capabilities
+            .cache_control
+            .then_some(LanguageModelCacheConfiguration {
+                max_cache_anchors: 4,
+                should_speculate: false,
+                min_total_token: 2_048,
+            })
+    }
+
     fn count_tokens(
         &self,
         request: LanguageModelRequest,
@@ -671,16 +865,20 @@ impl LanguageModel for OpenAiCompatibleLanguageModel {
             LanguageModelCompletionError,
         >,
     > {
-        if self.model.capabilities.chat_completions {
+        let traffic = LanguageModelTrafficLog::start_entry(self, &request, cx);
+        let credential_profile = request.credential_profile.clone();
+        let future = if self.model.capabilities.chat_completions {
             let request = into_open_ai(
                 request,
                 &self.model.request_model,
                 self.model.capabilities.parallel_tool_calls,
                 self.model.capabilities.prompt_cache_key,
+                self.model.capabilities.cache_control,
                 self.max_output_tokens(),
                 None,
             );
-            let completions = self.stream_completion(request, cx);
+            let completions =
+                self.stream_completion(request, credential_profile, traffic.clone(), cx);
             async move {
                 let mapper = OpenAiEventMapper::new();
                 Ok(mapper.map_stream(completions.await?).boxed())
@@ -695,16 +893,42 @@ impl LanguageModel for OpenAiCompatibleLanguageModel {
                 self.max_output_tokens(),
                 None,
             );
-            let completions = self.stream_response(request, cx);
+            let completions =
+                self.stream_response(request, credential_profile, traffic.clone(), cx);
             async move {
                 let mapper = OpenAiResponseEventMapper::new();
                 Ok(mapper.map_stream(completions.await?).boxed())
             }
             .boxed()
-        }
+        };
+        log_completion_traffic(traffic, future)
     }
 }
 
+/// Captures a request for the traffic log. The values of user-configured headers are always
+/// redacted, since they often carry gateway keys.
+pub(crate) fn http_request_snapshot(
+    url: String,
+    auth_header: (String, String),
+    additional_headers: &[(String, String)],
+    configured_headers: &BTreeMap<String, String>,
+    body: &impl serde::Serialize,
+) -> HttpRequestSnapshot {
+    let headers = [
+        ("Content-Type".to_string(), "application/json".to_string()),
+        auth_header,
+    ]
+    .into_iter()
+    .chain(additional_headers.iter().cloned());
+    HttpRequestSnapshot::new(
+        "POST",
+        url,
+        headers,
+        serde_json::to_string(body).unwrap_or_default(),
+    )
+    .redact_headers(configured_headers.keys().map(String::as_str))
+}
+
 #[derive(Default, Deserialize)]
 struct NanogptModelsResponse {
     #[serde(default)]
@@ -745,7 +969,7 @@ struct NanogptProviderInfo {
     available: bool,
 }
 
-fn nanogpt_capabilities(capabilities: &[String]) -> ModelCapabilities {
+fn nanogpt_capabilities(model_id: &str, capabilities: &[String]) -> ModelCapabilities {
     let has_capability = |capability: &str| {
         capabilities
             .iter()
@@ -756,11 +980,22 @@ fn nanogpt_capabilities(capabilities: &[String]) -> ModelCapabilities {
         tools,
         images: has_capability("vision"),
         parallel_tool_calls: tools,
-        prompt_cache_key: false,
+        prompt_cache_key: true,
+        cache_control: nanogpt_supports_cache_control(model_id)
+            || has_capability("caching")
+            || has_capability("prompt-caching"),
         chat_completions: true,
     }
 }
 
+/// NanoGPT forwards `cache_control` breakpoints to upstream providers with
+/// explicit prompt caching; other upstreams cache automatically (if at all)
+/// and only need the `prompt_cache_key`.
+fn nanogpt_supports_cache_control(model_id: &str) -> bool {
+    let model_id = model_id.to_ascii_lowercase();
+    model_id.starts_with("anthropic/") || model_id.contains("claude")
+}
+
 fn nanogpt_api_base_url(api_url: &str) -> String {
     let trimmed = api_url.trim_end_matches('/');
     if let Some(stripped) = trimmed.strip_suffix("/v1") {
@@ -819,6 +1054,7 @@ async fn fetch_nanogpt_models(
             .filter(|max_tokens| *max_tokens > 0)
             .unwrap_or(NANOGPT_DEFAULT_MAX_INPUT_TOKENS);
         let max_output_tokens = model.max_output_tokens.filter(|max_tokens| *max_tokens > 0);
+        let capabilities = nanogpt_capabilities(&request_model, &model.capabilities);
 
         models.push(ResolvedModel {
             id: request_model.clone(),
@@ -827,7 +1063,7 @@ async fn fetch_nanogpt_models(
             max_tokens,
             max_output_tokens,
             max_completion_tokens: max_output_tokens,
-            capabilities: nanogpt_capabilities(&model.capabilities),
+            capabilities,
             provider_override: None,
         });
     }
@@ -919,6 +1155,8 @@ async fn fetch_nanogpt_model_providers(
 struct ConfigurationView {
     api_key_editor: Entity<InputField>,
     state: Entity<State>,
+    /// The credential profile whose key is being edited. `None` edits the default profile.
+    selected_profile: Option<SharedString>,
     load_credentials_task: Option<Task<()>>,
 }
 
@@ -958,10 +1196,50 @@ impl ConfigurationView {
         Self {
             api_key_editor,
             state,
+            selected_profile: None,
             load_credentials_task,
         }
     }
 
+    fn selected_profile(&self, cx: &App) -> SharedString {
+        self.state
+            .read(cx)
+            .settings
+            .resolve_credential_profile(self.selected_profile.as_deref())
+    }
+
+    fn select_profile(
+        &mut self,
+        profile: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.api_key_editor
+            .update(cx, |input, cx| input.set_text("", window, cx));
+        self.state
+            .update(cx, |state, cx| {
+                state.load_profile_api_key(profile.clone(), cx)
+            })
+            .detach();
+        self.selected_profile = Some(profile);
+        cx.notify();
+    }
+
+    fn use_profile_by_default(&mut self, profile: SharedString, cx: &mut Context<Self>) {
+        let provider_id = self.state.read(cx).id.clone();
+        let fs = <dyn Fs>::global(cx);
+        update_settings_file(fs, cx, move |settings, _| {
+            if let Some(settings) = settings
+                .language_models
+                .as_mut()
+                .and_then(|models| models.openai_compatible.as_mut())
+                .and_then(|providers| providers.get_mut(&provider_id))
+            {
+                settings.default_credential_profile = Some(profile.to_string());
+            }
+        });
+    }
+
     fn save_api_key(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
         let api_key = self.api_key_editor.read(cx).text(cx).trim().to_string();
         if api_key.is_empty() {
@@ -973,9 +1251,12 @@ impl ConfigurationView {
             .update(cx, |input, cx| input.set_text("", window, cx));
 
         let state = self.state.clone();
+        let profile = self.selected_profile(cx);
         cx.spawn_in(window, async move |_, cx| {
             state
-                .update(cx, |state, cx| state.set_api_key(Some(api_key), cx))
+                .update(cx, |state, cx| {
+                    state.set_profile_api_key(profile, Some(api_key), cx)
+                })
                 .await
         })
         .detach_and_log_err(cx);
@@ -986,24 +1267,83 @@ impl ConfigurationView {
             .update(cx, |input, cx| input.set_text("", window, cx));
 
         let state = self.state.clone();
+        let profile = self.selected_profile(cx);
         cx.spawn_in(window, async move |_, cx| {
             state
-                .update(cx, |state, cx| state.set_api_key(None, cx))
+                .update(cx, |state, cx| state.set_profile_api_key(profile, None, cx))
                 .await
         })
         .detach_and_log_err(cx);
     }
 
     fn should_render_editor(&self, cx: &Context<Self>) -> bool {
-        !self.state.read(cx).is_authenticated()
+        let profile = self.selected_profile(cx);
+        self.state.read(cx).api_key(Some(&profile)).is_none()
+    }
+
+    fn render_profile_selector(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let state = self.state.read(cx);
+        let profiles = state.credential_profiles();
+        if profiles.is_empty() {
+            return None;
+        }
+        let default_profile = state.settings.default_credential_profile();
+        let selected_profile = self.selected_profile(cx);
+
+        Some(
+            h_flex()
+                .mb_1()
+                .gap_1()
+                .flex_wrap()
+                .child(
+                    Label::new("Credential profile:")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .children(profiles.into_iter().enumerate().map(|(ix, profile)| {
+                    let label = if profile == default_profile {
+                        SharedString::from(format!("{profile} (default)"))
+                    } else {
+                        profile.clone()
+                    };
+                    Button::new(("credential-profile", ix), label)
+                        .label_size(LabelSize::Small)
+                        .toggle_state(profile == selected_profile)
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.select_profile(profile.clone(), window, cx)
+                        }))
+                }))
+                .when(selected_profile != default_profile, |this| {
+                    this.child(
+                        Button::new("use-credential-profile-by-default", "Use by Default")
+                            .label_size(LabelSize::Small)
+                            .icon(IconName::Check)
+                            .icon_size(IconSize::Small)
+                            .icon_position(IconPosition::Start)
+                            .on_click(cx.listener(move |this, _, _window, cx| {
+                                this.use_profile_by_default(selected_profile.clone(), cx)
+                            })),
+                    )
+                }),
+        )
     }
 }
 
 impl Render for ConfigurationView {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let profile_selector = self.render_profile_selector(cx);
+        let selected_profile = self.selected_profile(cx);
         let state = self.state.read(cx);
-        let env_var_set = state.api_key_state.is_from_env_var();
-        let env_var_name = state.api_key_state.env_var_name();
+        let (env_var_set, env_var_name) = match state.api_key_state(&selected_profile) {
+            Some(api_key_state) => (
+                api_key_state.is_from_env_var(),
+                api_key_state.env_var_name().clone(),
+            ),
+            None => (
+                false,
+                profile_api_key_env_var_name(&state.id, &selected_profile),
+            ),
+        };
         let setup_message = if state.is_nanogpt() {
             "To use nano-zed's agent with NanoGPT, you need to add a NanoGPT API key."
         } else {
@@ -1051,8 +1391,10 @@ impl Render for ConfigurationView {
                                 .child(Label::new(
                                     if env_var_set {
                                         format!("API key set in {env_var_name} environment variable")
-                                    } else {
+                                    } else if selected_profile.as_ref() == DEFAULT_CREDENTIAL_PROFILE {
                                         format!("API key configured for {}", &state.settings.api_url)
+                                    } else {
+                                        format!("API key configured for the \"{selected_profile}\" profile")
                                     }
                                 ))
                         ),
@@ -1079,7 +1421,145 @@ impl Render for ConfigurationView {
         if self.load_credentials_task.is_some() {
             div().child(Label::new("Loading credentials…")).into_any()
         } else {
-            v_flex().size_full().child(api_key_section).into_any()
+            v_flex()
+                .size_full()
+                .children(profile_selector)
+                .child(api_key_section)
+                .into_any()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use http_client::FakeHttpClient;
+
+    fn settings_with_profiles(
+        default_credential_profile: Option<&str>,
+    ) -> OpenAiCompatibleSettings {
+        OpenAiCompatibleSettings {
+            api_url: "https://api.example.com/v1".into(),
+            credential_profiles: vec!["team".into(), "personal".into()],
+            default_credential_profile: default_credential_profile.map(SharedString::from),
+            ..Default::default()
         }
     }
+
+    fn test_state(settings: OpenAiCompatibleSettings, cx: &mut TestAppContext) -> Entity<State> {
+        cx.new(|_| State {
+            id: "example".into(),
+            api_key_state: ApiKeyState::new(
+                SharedString::new(settings.api_url.as_str()),
+                EnvVar::new("EXAMPLE_API_KEY".into()),
+            ),
+            profile_api_key_states: BTreeMap::new(),
+            settings,
+            auth_command_tokens: AuthCommandTokenCache::default(),
+            http_client: FakeHttpClient::with_404_response(),
+            dynamic_models: Vec::new(),
+            fetch_dynamic_models_task: None,
+            fetch_provider_selection_task: None,
+        })
+    }
+
+    #[test]
+    fn test_resolve_credential_profile() {
+        let settings = settings_with_profiles(Some("team"));
+        assert_eq!(settings.resolve_credential_profile(None), "team");
+        assert_eq!(
+            settings.resolve_credential_profile(Some("personal")),
+            "personal"
+        );
+        assert_eq!(
+            settings.resolve_credential_profile(Some(DEFAULT_CREDENTIAL_PROFILE)),
+            DEFAULT_CREDENTIAL_PROFILE
+        );
+        // Profiles that aren't configured for the provider fall back to its default profile.
+        assert_eq!(settings.resolve_credential_profile(Some("unknown")), "team");
+
+        // An unknown default profile falls back to the provider's original key.
+        let settings = settings_with_profiles(Some("unknown"));
+        assert_eq!(
+            settings.resolve_credential_profile(None),
+            DEFAULT_CREDENTIAL_PROFILE
+        );
+        let settings = settings_with_profiles(None);
+        assert_eq!(
+            settings.resolve_credential_profile(Some("unknown")),
+            DEFAULT_CREDENTIAL_PROFILE
+        );
+    }
+
+    #[test]
+    fn test_credential_profile_keys() {
+        let settings = settings_with_profiles(None);
+        assert_eq!(
+            settings.credential_profile_url(DEFAULT_CREDENTIAL_PROFILE),
+            "https://api.example.com/v1"
+        );
+        assert_eq!(
+            settings.credential_profile_url("team"),
+            "https://api.example.com/v1#team"
+        );
+        assert_eq!(
+            profile_api_key_env_var_name("together_ai", "team"),
+            "TOGETHER_AI_TEAM_API_KEY"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_profile_api_keys(cx: &mut TestAppContext) {
+        let state = test_state(settings_with_profiles(None), cx);
+
+        state
+            .update(cx, |state, cx| {
+                state.set_profile_api_key(
+                    DEFAULT_CREDENTIAL_PROFILE.into(),
+                    Some("default-key".into()),
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        state
+            .update(cx, |state, cx| {
+                state.set_profile_api_key("team".into(), Some("team-key".into()), cx)
+            })
+            .await
+            .unwrap();
+
+        state.read_with(cx, |state, _| {
+            // Each profile's key is stored under its own `api_url#profile` keychain entry.
+            assert_eq!(
+                state.api_key_state("team").unwrap().url,
+                "https://api.example.com/v1#team"
+            );
+            assert_eq!(state.api_key_state.url, "https://api.example.com/v1");
+
+            assert_eq!(state.api_key(None).as_deref(), Some("default-key"));
+            assert_eq!(state.api_key(Some("team")).as_deref(), Some("team-key"));
+            // Unknown profiles use the default profile's key.
+            assert_eq!(
+                state.api_key(Some("unknown")).as_deref(),
+                Some("default-key")
+            );
+            // A configured profile without a key doesn't borrow another profile's key.
+            assert_eq!(state.api_key(Some("personal")), None);
+        });
+
+        // Making the team profile the default routes unselected requests to its key.
+        state.update(cx, |state, _| {
+            state.settings.default_credential_profile = Some("team".into());
+        });
+        state.read_with(cx, |state, _| {
+            assert_eq!(state.api_key(None).as_deref(), Some("team-key"));
+            assert_eq!(state.api_key(Some("unknown")).as_deref(), Some("team-key"));
+            assert_eq!(
+                state.api_key(Some(DEFAULT_CREDENTIAL_PROFILE)).as_deref(),
+                Some("default-key")
+            );
+        });
+    }
 }
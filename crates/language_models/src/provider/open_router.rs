@@ -8,8 +8,8 @@ use language_model::{
     LanguageModelCompletionEvent, LanguageModelId, LanguageModelName, LanguageModelProvider,
     LanguageModelProviderId, LanguageModelProviderName, LanguageModelProviderState,
     LanguageModelRequest, LanguageModelToolChoice, LanguageModelToolResultContent,
-    LanguageModelToolSchemaFormat, LanguageModelToolUse, MessageContent, RateLimiter, Role,
-    StopReason, TokenUsage, env_var,
+    LanguageModelToolSchemaFormat, LanguageModelToolUse, LanguageModelTrafficLog, MessageContent,
+    RateLimiter, Role, StopReason, TokenUsage, env_var, log_completion_traffic, log_raw_traffic,
 };
 use open_router::{
     Model, ModelMode as OpenRouterModelMode, OPEN_ROUTER_API_URL, ResponseStreamEvent, list_models,
@@ -373,13 +373,15 @@ impl LanguageModel for OpenRouterLanguageModel {
             LanguageModelCompletionError,
         >,
     > {
+        let traffic = LanguageModelTrafficLog::start_entry(self, &request, cx);
         let openrouter_request = into_open_router(request, &self.model, self.max_output_tokens());
         let request = self.stream_completion(openrouter_request, cx);
+        let raw_traffic = traffic.clone();
         let future = self.request_limiter.stream(async move {
-            let response = request.await?;
+            let response = log_raw_traffic(raw_traffic, request.await?);
             Ok(OpenRouterEventMapper::new().map_stream(response))
         });
-        async move { Ok(future.await?.boxed()) }.boxed()
+        log_completion_traffic(traffic, async move { Ok(future.await?.boxed()) }.boxed())
     }
 }
 
@@ -7,7 +7,8 @@ use language_model::{
     ApiKeyState, AuthenticateError, EnvVar, IconOrSvg, LanguageModel, LanguageModelCompletionError,
     LanguageModelCompletionEvent, LanguageModelId, LanguageModelName, LanguageModelProvider,
     LanguageModelProviderId, LanguageModelProviderName, LanguageModelProviderState,
-    LanguageModelRequest, LanguageModelToolChoice, RateLimiter, Role, env_var,
+    LanguageModelRequest, LanguageModelToolChoice, LanguageModelTrafficLog, RateLimiter, Role,
+    env_var, log_completion_traffic, log_raw_traffic,
 };
 use open_ai::ResponseStreamEvent;
 pub use settings::VercelAvailableModel as AvailableModel;
@@ -290,6 +291,7 @@ impl LanguageModel for VercelLanguageModel {
             LanguageModelCompletionError,
         >,
     > {
+        let traffic = LanguageModelTrafficLog::start_entry(self, &request, cx);
         let request = crate::provider::open_ai::into_open_ai(
             request,
             self.model.id(),
@@ -299,11 +301,14 @@ impl LanguageModel for VercelLanguageModel {
             None,
         );
         let completions = self.stream_completion(request, cx);
-        async move {
+        let raw_traffic = traffic.clone();
+        let future = async move {
             let mapper = crate::provider::open_ai::OpenAiEventMapper::new();
-            Ok(mapper.map_stream(completions.await?).boxed())
+            let completions = log_raw_traffic(raw_traffic, completions.await?);
+            Ok(mapper.map_stream(completions).boxed())
         }
-        .boxed()
+        .boxed();
+        log_completion_traffic(traffic, future)
     }
 }
 
@@ -1,12 +1,14 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 use collections::HashMap;
+use gpui::SharedString;
 use settings::RegisterSetting;
 
 use crate::provider::{
-    anthropic::AnthropicSettings, bedrock::AmazonBedrockSettings, cloud::ZedDotDevSettings,
-    deepseek::DeepSeekSettings, google::GoogleSettings, lmstudio::LmStudioSettings,
-    mistral::MistralSettings, ollama::OllamaSettings, open_ai::OpenAiSettings,
+    anthropic::AnthropicSettings, azure_open_ai::AzureOpenAiSettings,
+    bedrock::AmazonBedrockSettings, cloud::ZedDotDevSettings, deepseek::DeepSeekSettings,
+    google::GoogleSettings, lmstudio::LmStudioSettings, mistral::MistralSettings,
+    mock::MockSettings, ollama::OllamaSettings, open_ai::OpenAiSettings,
     open_ai_compatible::OpenAiCompatibleSettings, open_router::OpenRouterSettings,
     vercel::VercelSettings, x_ai::XAiSettings,
 };
@@ -14,11 +16,13 @@ use crate::provider::{
 #[derive(Debug, RegisterSetting)]
 pub struct AllLanguageModelSettings {
     pub anthropic: AnthropicSettings,
+    pub azure_openai: AzureOpenAiSettings,
     pub bedrock: AmazonBedrockSettings,
     pub deepseek: DeepSeekSettings,
     pub google: GoogleSettings,
     pub lmstudio: LmStudioSettings,
     pub mistral: MistralSettings,
+    pub mock: MockSettings,
     pub ollama: OllamaSettings,
     pub open_router: OpenRouterSettings,
     pub openai: OpenAiSettings,
@@ -34,11 +38,13 @@ impl settings::Settings for AllLanguageModelSettings {
     fn from_settings(content: &settings::SettingsContent) -> Self {
         let language_models = content.language_models.clone().unwrap();
         let anthropic = language_models.anthropic.unwrap();
+        let azure_openai = language_models.azure_openai.unwrap();
         let bedrock = language_models.bedrock.unwrap();
         let deepseek = language_models.deepseek.unwrap();
         let google = language_models.google.unwrap();
         let lmstudio = language_models.lmstudio.unwrap();
         let mistral = language_models.mistral.unwrap();
+        let mock = language_models.mock.unwrap();
         let ollama = language_models.ollama.unwrap();
         let open_router = language_models.open_router.unwrap();
         let openai = language_models.openai.unwrap();
@@ -51,6 +57,13 @@ impl settings::Settings for AllLanguageModelSettings {
                 api_url: anthropic.api_url.unwrap(),
                 available_models: anthropic.available_models.unwrap_or_default(),
             },
+            azure_openai: AzureOpenAiSettings {
+                endpoint: azure_openai.endpoint.unwrap_or_default(),
+                api_version: azure_openai.api_version.unwrap(),
+                available_models: azure_openai.available_models.unwrap_or_default(),
+                headers: azure_openai.headers.unwrap_or_default(),
+                auth_command: azure_openai.auth_command,
+            },
             bedrock: AmazonBedrockSettings {
                 available_models: bedrock.available_models.unwrap_or_default(),
                 region: bedrock.region,
@@ -77,6 +90,9 @@ impl settings::Settings for AllLanguageModelSettings {
                 api_url: mistral.api_url.unwrap(),
                 available_models: mistral.available_models.unwrap_or_default(),
             },
+            mock: MockSettings {
+                fixture_path: mock.fixture_path.map(PathBuf::from),
+            },
             ollama: OllamaSettings {
                 api_url: ollama.api_url.unwrap(),
                 auto_discover: ollama.auto_discover.unwrap_or(true),
@@ -99,6 +115,16 @@ impl settings::Settings for AllLanguageModelSettings {
                         OpenAiCompatibleSettings {
                             api_url: value.api_url,
                             available_models: value.available_models,
+                            credential_profiles: value
+                                .credential_profiles
+                                .into_iter()
+                                .map(SharedString::from)
+                                .collect(),
+                            default_credential_profile: value
+                                .default_credential_profile
+                                .map(SharedString::from),
+                            headers: value.headers,
+                            auth_command: value.auth_command,
                         },
                     )
                 })
@@ -0,0 +1,235 @@
+mod history_diff_view;
+mod local_history_panel;
+mod persistence;
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use gpui::{App, AppContext as _, Context, Entity, EventEmitter, Global, Task, actions, px};
+use language::{Buffer, BufferEvent};
+use project::{
+    Project,
+    buffer_store::{BufferStore, BufferStoreEvent},
+};
+use settings::{RegisterSetting, Settings};
+use std::{path::PathBuf, time::Duration};
+use text::{BufferId, Rope};
+use time::OffsetDateTime;
+use ui::Pixels;
+use util::ResultExt as _;
+use workspace::{Workspace, dock::DockPosition};
+
+pub use history_diff_view::HistoryDiffView;
+pub use local_history_panel::LocalHistoryPanel;
+pub use persistence::{DB, HistoryEntry, LocalHistoryDb};
+
+actions!(
+    local_history,
+    [
+        /// Toggles focus on the local history panel.
+        ToggleFocus
+    ]
+);
+
+#[derive(Debug, RegisterSetting)]
+pub struct LocalHistorySettings {
+    pub enabled: bool,
+    pub max_entries_per_file: usize,
+    pub max_age_days: u32,
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+}
+
+impl Settings for LocalHistorySettings {
+    fn from_settings(content: &settings::SettingsContent) -> Self {
+        let local_history = content.local_history.as_ref().unwrap();
+
+        Self {
+            enabled: local_history.enabled.unwrap(),
+            max_entries_per_file: local_history.max_entries_per_file.unwrap(),
+            max_age_days: local_history.max_age_days.unwrap(),
+            button: local_history.button.unwrap(),
+            dock: local_history.dock.unwrap().into(),
+            default_width: local_history.default_width.map(px).unwrap(),
+        }
+    }
+}
+
+/// How often the versions older than `max_age_days` are deleted, starting at launch.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+pub fn init(cx: &mut App) {
+    let local_history = cx.new(LocalHistory::new);
+    cx.set_global(GlobalLocalHistory(local_history.clone()));
+
+    cx.observe_new(move |project: &mut Project, _, cx| {
+        if !project.is_local() {
+            return;
+        }
+        let buffer_store = project.buffer_store().clone();
+        local_history.update(cx, |local_history, cx| {
+            local_history.watch_buffer_store(&buffer_store, cx)
+        });
+    })
+    .detach();
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<LocalHistoryPanel>(window, cx);
+        });
+    })
+    .detach();
+}
+
+struct GlobalLocalHistory(Entity<LocalHistory>);
+
+impl Global for GlobalLocalHistory {}
+
+#[derive(Clone, Debug)]
+pub enum LocalHistoryEvent {
+    EntriesChanged { abs_path: PathBuf },
+}
+
+/// Records the contents of local files every time they are saved, so that previous
+/// versions can be inspected and restored independently of version control.
+pub struct LocalHistory {
+    /// Contents of each open buffer as it was on disk when opened. These are recorded
+    /// alongside the first save, so the version a file had before it was edited can be
+    /// restored too.
+    opened_versions: HashMap<BufferId, OpenedVersion>,
+    _prune_task: Task<()>,
+}
+
+struct OpenedVersion {
+    text: Rope,
+    mtime: OffsetDateTime,
+}
+
+impl EventEmitter<LocalHistoryEvent> for LocalHistory {}
+
+impl LocalHistory {
+    fn new(cx: &mut Context<Self>) -> Self {
+        Self {
+            opened_versions: HashMap::default(),
+            _prune_task: cx.spawn(async move |_, cx| {
+                loop {
+                    let max_age_days =
+                        cx.update(|cx| LocalHistorySettings::get_global(cx).max_age_days);
+                    let oldest_allowed =
+                        OffsetDateTime::now_utc() - time::Duration::days(max_age_days.into());
+                    cx.background_spawn(async move { DB.prune(oldest_allowed).await })
+                        .await
+                        .context("pruning local history")
+                        .log_err();
+                    cx.background_executor().timer(PRUNE_INTERVAL).await;
+                }
+            }),
+        }
+    }
+
+    pub fn global(cx: &App) -> Option<Entity<Self>> {
+        cx.try_global::<GlobalLocalHistory>()
+            .map(|local_history| local_history.0.clone())
+    }
+
+    /// Returns the recorded versions of the file at `abs_path`, newest first.
+    pub fn entries(&self, abs_path: PathBuf, cx: &App) -> Task<Result<Vec<HistoryEntry>>> {
+        cx.background_spawn(async move { DB.entries(abs_path) })
+    }
+
+    /// Loads the contents of a recorded version.
+    pub fn load(&self, entry: &HistoryEntry, cx: &App) -> Task<Result<String>> {
+        let hash = entry.hash.clone();
+        cx.background_spawn(async move { DB.load(hash) })
+    }
+
+    fn watch_buffer_store(&mut self, buffer_store: &Entity<BufferStore>, cx: &mut Context<Self>) {
+        cx.subscribe(buffer_store, |this, _, event, cx| {
+            if let BufferStoreEvent::BufferAdded(buffer) = event {
+                this.watch_buffer(buffer, cx);
+            }
+        })
+        .detach();
+    }
+
+    fn watch_buffer(&mut self, buffer: &Entity<Buffer>, cx: &mut Context<Self>) {
+        let buffer_id = buffer.read(cx).remote_id();
+        if let Some(version) = opened_version(buffer.read(cx)) {
+            self.opened_versions.insert(buffer_id, version);
+        }
+
+        cx.subscribe(buffer, |this, buffer, event, cx| match event {
+            BufferEvent::Saved => this.record_saved_version(&buffer, cx),
+            BufferEvent::Reloaded => {
+                let buffer = buffer.read(cx);
+                if let Some(version) = opened_version(buffer) {
+                    this.opened_versions.insert(buffer.remote_id(), version);
+                }
+            }
+            _ => {}
+        })
+        .detach();
+        cx.observe_release(buffer, move |this, _, _| {
+            this.opened_versions.remove(&buffer_id);
+        })
+        .detach();
+    }
+
+    fn record_saved_version(&mut self, buffer: &Entity<Buffer>, cx: &mut Context<Self>) {
+        let settings = LocalHistorySettings::get_global(cx);
+        if !settings.enabled {
+            return;
+        }
+        let max_entries_per_file = settings.max_entries_per_file;
+
+        let buffer = buffer.read(cx);
+        let Some(file) = buffer.file().filter(|file| !file.is_private()) else {
+            return;
+        };
+        let Some(local_file) = file.as_local() else {
+            return;
+        };
+        if buffer.is_large_file() {
+            return;
+        }
+        let abs_path = local_file.abs_path(cx);
+        let opened_version = self.opened_versions.remove(&buffer.remote_id());
+        let text = buffer.as_rope().clone();
+        let saved_at = OffsetDateTime::now_utc();
+
+        cx.spawn(async move |this, cx| {
+            cx.background_spawn({
+                let abs_path = abs_path.clone();
+                async move {
+                    if let Some(opened_version) = opened_version {
+                        DB.record(
+                            abs_path.clone(),
+                            opened_version.text,
+                            opened_version.mtime,
+                            max_entries_per_file,
+                        )
+                        .await?;
+                    }
+                    DB.record(abs_path, text, saved_at, max_entries_per_file)
+                        .await
+                }
+            })
+            .await?;
+            this.update(cx, |_, cx| {
+                cx.emit(LocalHistoryEvent::EntriesChanged { abs_path })
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+fn opened_version(buffer: &Buffer) -> Option<OpenedVersion> {
+    if buffer.is_large_file() || buffer.is_dirty() {
+        return None;
+    }
+    let mtime = buffer.file()?.disk_state().mtime()?;
+    Some(OpenedVersion {
+        text: buffer.as_rope().clone(),
+        mtime: mtime.timestamp_for_user().into(),
+    })
+}
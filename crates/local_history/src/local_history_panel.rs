@@ -0,0 +1,433 @@
+use crate::{
+    HistoryDiffView, HistoryEntry, LocalHistory, LocalHistoryEvent, LocalHistorySettings,
+    ToggleFocus,
+};
+use anyhow::Result;
+use db::kvp::KEY_VALUE_STORE;
+use editor::Editor;
+use gpui::{
+    App, AsyncWindowContext, Context, Entity, EventEmitter, FocusHandle, Focusable, Render,
+    Subscription, Task, WeakEntity,
+};
+use language::Buffer;
+use project::Fs;
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use std::{path::PathBuf, sync::Arc};
+use time::{OffsetDateTime, UtcOffset};
+use time_format::TimestampFormat;
+use ui::{ListItem, ListItemSpacing, Tab, Tooltip, prelude::*};
+use util::{ResultExt, TryFutureExt};
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+const LOCAL_HISTORY_PANEL_KEY: &str = "LocalHistoryPanel";
+
+/// Lists the versions recorded in the local history for the file of the active editor.
+pub struct LocalHistoryPanel {
+    workspace: WeakEntity<Workspace>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    active_file: Option<ActiveFile>,
+    entries: Vec<HistoryEntry>,
+    local_timezone: UtcOffset,
+    pending_serialization: Task<Option<()>>,
+    load_entries_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+struct ActiveFile {
+    buffer: Entity<Buffer>,
+    abs_path: PathBuf,
+    display_path: SharedString,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedLocalHistoryPanel {
+    width: Option<Pixels>,
+}
+
+impl LocalHistoryPanel {
+    pub fn new(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let workspace_handle = workspace.weak_handle();
+        let workspace_entity = cx.entity();
+        let active_file = active_file(workspace, cx);
+
+        cx.new(|cx| {
+            let mut subscriptions =
+                vec![
+                    cx.subscribe_in(&workspace_entity, window, |this, _, event, window, cx| {
+                        if let workspace::Event::ActiveItemChanged = event {
+                            this.update_active_file(window, cx);
+                        }
+                    }),
+                ];
+            if let Some(local_history) = LocalHistory::global(cx) {
+                subscriptions.push(cx.subscribe_in(
+                    &local_history,
+                    window,
+                    |this, _, event, window, cx| {
+                        let LocalHistoryEvent::EntriesChanged { abs_path } = event;
+                        if this
+                            .active_file
+                            .as_ref()
+                            .is_some_and(|active_file| &active_file.abs_path == abs_path)
+                        {
+                            this.load_entries(window, cx);
+                        }
+                    },
+                ));
+            }
+
+            let mut this = Self {
+                workspace: workspace_handle,
+                fs,
+                focus_handle: cx.focus_handle(),
+                width: None,
+                active_file,
+                entries: Vec::new(),
+                local_timezone: UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC),
+                pending_serialization: Task::ready(None),
+                load_entries_task: Task::ready(()),
+                _subscriptions: subscriptions,
+            };
+            this.load_entries(window, cx);
+            this
+        })
+    }
+
+    pub fn load(
+        workspace: WeakEntity<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<Entity<Self>>> {
+        cx.spawn(async move |cx| {
+            let serialized_panel = if let Some(panel) = cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(LOCAL_HISTORY_PANEL_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedLocalHistoryPanel>(&panel)?)
+            } else {
+                None
+            };
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let panel = Self::new(workspace, window, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width.map(|w| w.round());
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        LOCAL_HISTORY_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedLocalHistoryPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn update_active_file(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let active_file = self
+            .workspace
+            .upgrade()
+            .and_then(|workspace| active_file(workspace.read(cx), cx));
+
+        let unchanged = match (&self.active_file, &active_file) {
+            (Some(old), Some(new)) => old.buffer == new.buffer,
+            (None, None) => true,
+            _ => false,
+        };
+        if unchanged {
+            return;
+        }
+
+        self.active_file = active_file;
+        self.entries.clear();
+        self.load_entries(window, cx);
+        cx.notify();
+    }
+
+    fn load_entries(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(active_file), Some(local_history)) =
+            (self.active_file.as_ref(), LocalHistory::global(cx))
+        else {
+            self.load_entries_task = Task::ready(());
+            return;
+        };
+
+        let entries = local_history
+            .read(cx)
+            .entries(active_file.abs_path.clone(), cx);
+        self.load_entries_task = cx.spawn_in(window, async move |this, cx| {
+            let Some(entries) = entries.await.log_err() else {
+                return;
+            };
+            this.update(cx, |this, cx| {
+                this.entries = entries;
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn open_diff(&mut self, entry: &HistoryEntry, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(active_file), Some(local_history)) =
+            (self.active_file.as_ref(), LocalHistory::global(cx))
+        else {
+            return;
+        };
+
+        let buffer = active_file.buffer.clone();
+        let file_name = buffer.read(cx).file().map_or_else(
+            || active_file.display_path.to_string(),
+            |file| file.file_name(cx).to_string(),
+        );
+        let saved_at = self.format_timestamp(entry.saved_at, TimestampFormat::EnhancedAbsolute);
+        let title: SharedString = format!("{file_name} ({saved_at}) ↔ Current").into();
+        let path: SharedString =
+            format!("{} ({saved_at}) ↔ Current", active_file.display_path).into();
+        let load = local_history.read(cx).load(entry, cx);
+        let workspace = self.workspace.clone();
+
+        cx.spawn_in(window, async move |_, cx| {
+            let text = load.await?;
+            workspace.update_in(cx, |workspace, window, cx| {
+                let project = workspace.project().clone();
+                let diff_view = cx
+                    .new(|cx| HistoryDiffView::new(buffer, text, title, path, project, window, cx));
+                workspace.add_item_to_active_pane(Box::new(diff_view), None, true, window, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn restore(&mut self, entry: &HistoryEntry, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(active_file), Some(local_history)) =
+            (self.active_file.as_ref(), LocalHistory::global(cx))
+        else {
+            return;
+        };
+
+        let buffer = active_file.buffer.clone();
+        let load = local_history.read(cx).load(entry, cx);
+        cx.spawn_in(window, async move |_, cx| {
+            let text = load.await?;
+            let diff = buffer.update(cx, |buffer, cx| buffer.diff(text, cx)).await;
+            buffer.update(cx, |buffer, cx| {
+                buffer.finalize_last_transaction();
+                buffer.apply_diff(diff, cx);
+                buffer.finalize_last_transaction();
+            });
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn format_timestamp(&self, timestamp: OffsetDateTime, format: TimestampFormat) -> String {
+        time_format::format_localized_timestamp(
+            timestamp,
+            OffsetDateTime::now_utc(),
+            self.local_timezone,
+            format,
+        )
+    }
+
+    fn render_entry(
+        &self,
+        ix: usize,
+        entry: &HistoryEntry,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let relative_time = self.format_timestamp(entry.saved_at, TimestampFormat::Relative);
+        let absolute_time =
+            self.format_timestamp(entry.saved_at, TimestampFormat::EnhancedAbsolute);
+        let size = util::size::format_file_size(entry.len as u64, true);
+
+        ListItem::new(ix)
+            .spacing(ListItemSpacing::Sparse)
+            .child(
+                v_flex().child(Label::new(relative_time)).child(
+                    Label::new(format!("{absolute_time} · {size}"))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                ),
+            )
+            .end_slot(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        IconButton::new(("diff", ix), IconName::Diff)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Compare with Current"))
+                            .on_click(cx.listener({
+                                let entry = entry.clone();
+                                move |this, _, window, cx| this.open_diff(&entry, window, cx)
+                            })),
+                    )
+                    .child(
+                        IconButton::new(("restore", ix), IconName::RotateCcw)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Restore This Version"))
+                            .on_click(cx.listener({
+                                let entry = entry.clone();
+                                move |this, _, window, cx| this.restore(&entry, window, cx)
+                            })),
+                    ),
+            )
+            .on_click(cx.listener({
+                let entry = entry.clone();
+                move |this, _, window, cx| this.open_diff(&entry, window, cx)
+            }))
+    }
+
+    fn render_message(&self, message: &'static str) -> impl IntoElement {
+        v_flex().p_4().child(
+            Label::new(message)
+                .color(Color::Muted)
+                .size(LabelSize::Small),
+        )
+    }
+}
+
+fn active_file(workspace: &Workspace, cx: &App) -> Option<ActiveFile> {
+    let editor = workspace.active_item_as::<Editor>(cx)?;
+    let buffer = editor.read(cx).buffer().read(cx).as_singleton()?;
+    let file = buffer.read(cx).file()?;
+    let abs_path = file.as_local()?.abs_path(cx);
+    let display_path = file.full_path(cx).to_string_lossy().into_owned().into();
+    Some(ActiveFile {
+        buffer,
+        abs_path,
+        display_path,
+    })
+}
+
+impl Render for LocalHistoryPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("LocalHistoryPanel")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    // Match the height of the tab bar so they line up.
+                    .h(Tab::container_height(cx))
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(
+                        Label::new(self.active_file.as_ref().map_or_else(
+                            || "Local History".into(),
+                            |file| file.display_path.clone(),
+                        ))
+                        .truncate(),
+                    ),
+            )
+            .map(|this| {
+                if self.active_file.is_none() {
+                    this.child(self.render_message("Open a file to see its local history."))
+                } else if self.entries.is_empty() {
+                    this.child(self.render_message("No versions of this file have been saved yet."))
+                } else {
+                    this.child(
+                        v_flex()
+                            .id("local-history-entries")
+                            .p_1()
+                            .flex_1()
+                            .overflow_y_scroll()
+                            .children(
+                                self.entries
+                                    .iter()
+                                    .enumerate()
+                                    .map(|(ix, entry)| self.render_entry(ix, entry, cx)),
+                            ),
+                    )
+                }
+            })
+    }
+}
+
+impl Focusable for LocalHistoryPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for LocalHistoryPanel {}
+
+impl Panel for LocalHistoryPanel {
+    fn persistent_name() -> &'static str {
+        "LocalHistoryPanel"
+    }
+
+    fn panel_key() -> &'static str {
+        LOCAL_HISTORY_PANEL_KEY
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        LocalHistorySettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            settings.local_history.get_or_insert_default().dock = Some(position.into())
+        });
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| LocalHistorySettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        LocalHistorySettings::get_global(cx)
+            .button
+            .then_some(IconName::HistoryRerun)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
+        Some("Local History")
+    }
+
+    fn toggle_action(&self) -> Box<dyn gpui::Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        10
+    }
+}
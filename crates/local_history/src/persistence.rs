@@ -0,0 +1,239 @@
+use anyhow::{Result, anyhow};
+use db::{
+    sqlez::{domain::Domain, thread_safe_connection::ThreadSafeConnection},
+    sqlez_macros::sql,
+};
+use sha2::{Digest, Sha256};
+use std::{path::PathBuf, sync::Arc};
+use text::Rope;
+use time::OffsetDateTime;
+
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// A single snapshot of a file recorded in the local history.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub id: i64,
+    /// Content hash used to deduplicate identical snapshots.
+    pub hash: Arc<str>,
+    pub saved_at: OffsetDateTime,
+    /// Length of the snapshot in bytes.
+    pub len: usize,
+}
+
+pub struct LocalHistoryDb(ThreadSafeConnection);
+
+impl Domain for LocalHistoryDb {
+    const NAME: &str = stringify!(LocalHistoryDb);
+
+    const MIGRATIONS: &[&str] = &[sql!(
+        CREATE TABLE local_history_contents (
+            hash TEXT PRIMARY KEY,
+            data BLOB NOT NULL
+        ) STRICT;
+        CREATE TABLE local_history_entries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            path BLOB NOT NULL,
+            hash TEXT NOT NULL,
+            saved_at INTEGER NOT NULL,
+            len INTEGER NOT NULL
+        ) STRICT;
+        CREATE INDEX local_history_entries_by_path ON local_history_entries (path, id);
+        CREATE INDEX local_history_entries_by_saved_at ON local_history_entries (saved_at);
+    )];
+}
+
+db::static_connection!(DB, LocalHistoryDb, []);
+
+impl LocalHistoryDb {
+    /// Records a snapshot of `text` for the file at `path`, unless it is identical
+    /// to the most recent snapshot of that file, and drops the file's oldest snapshots
+    /// beyond `max_entries_per_file`. Returns whether a new entry was added.
+    ///
+    /// Snapshots that expire, and contents no entry refers to anymore, are only deleted
+    /// by [`Self::prune`].
+    pub async fn record(
+        &self,
+        path: PathBuf,
+        text: Rope,
+        saved_at: OffsetDateTime,
+        max_entries_per_file: usize,
+    ) -> Result<bool> {
+        let text = text.to_string();
+        let hash = content_hash(&text);
+        let compressed = zstd::encode_all(text.as_bytes(), COMPRESSION_LEVEL)?;
+        let saved_at = saved_at.unix_timestamp();
+        let len = text.len();
+
+        self.write(move |conn| {
+            conn.with_savepoint("local_history_record", || {
+                let latest = conn.select_row_bound::<PathBuf, String>(sql!(
+                    SELECT hash FROM local_history_entries
+                        WHERE path = ?
+                        ORDER BY id DESC
+                        LIMIT 1
+                ))?(path.clone())?;
+                if latest.as_deref() == Some(hash.as_str()) {
+                    return Ok(false);
+                }
+
+                conn.exec_bound::<(&str, Vec<u8>)>(sql!(
+                    INSERT OR IGNORE INTO local_history_contents (hash, data) VALUES (?, ?)
+                ))?((hash.as_str(), compressed))?;
+
+                conn.exec_bound::<(PathBuf, &str, i64, usize)>(sql!(
+                    INSERT INTO local_history_entries (path, hash, saved_at, len)
+                        VALUES (?, ?, ?, ?)
+                ))?((path.clone(), hash.as_str(), saved_at, len))?;
+
+                conn.exec_bound::<(PathBuf, usize)>(sql!(
+                    DELETE FROM local_history_entries WHERE path = ?1 AND id NOT IN (
+                        SELECT id FROM local_history_entries
+                            WHERE path = ?1
+                            ORDER BY id DESC
+                            LIMIT ?2
+                    )
+                ))?((path, max_entries_per_file.max(1)))?;
+
+                Ok(true)
+            })
+        })
+        .await
+    }
+
+    /// Deletes the snapshots of every file recorded before `oldest_allowed`, along with
+    /// any contents no remaining snapshot refers to.
+    pub async fn prune(&self, oldest_allowed: OffsetDateTime) -> Result<()> {
+        let oldest_allowed = oldest_allowed.unix_timestamp();
+        self.write(move |conn| {
+            conn.with_savepoint("local_history_prune", || {
+                conn.exec_bound::<i64>(sql!(
+                    DELETE FROM local_history_entries WHERE saved_at < ?
+                ))?(oldest_allowed)?;
+                conn.exec(sql!(
+                    DELETE FROM local_history_contents WHERE hash NOT IN (
+                        SELECT DISTINCT hash FROM local_history_entries
+                    )
+                ))?()
+            })
+        })
+        .await
+    }
+
+    /// Returns the recorded snapshots of the file at `path`, newest first.
+    pub fn entries(&self, path: PathBuf) -> Result<Vec<HistoryEntry>> {
+        let rows: Vec<(i64, Arc<str>, i64, usize)> = self.select_bound(sql!(
+            SELECT id, hash, saved_at, len FROM local_history_entries
+                WHERE path = ?
+                ORDER BY id DESC
+        ))?(path)?;
+
+        rows.into_iter()
+            .map(|(id, hash, saved_at, len)| {
+                Ok(HistoryEntry {
+                    id,
+                    hash,
+                    saved_at: OffsetDateTime::from_unix_timestamp(saved_at)?,
+                    len,
+                })
+            })
+            .collect()
+    }
+
+    /// Loads the text of a snapshot previously returned by [`Self::entries`].
+    pub fn load(&self, hash: Arc<str>) -> Result<String> {
+        let data: Option<Vec<u8>> = self.select_row_bound(sql!(
+            SELECT data FROM local_history_contents WHERE hash = ? LIMIT 1
+        ))?(hash.clone())?;
+        let data = data.ok_or_else(|| anyhow!("local history snapshot {hash} not found"))?;
+        Ok(String::from_utf8(zstd::decode_all(&data[..])?)?)
+    }
+}
+
+fn content_hash(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::Duration;
+
+    const MAX_ENTRIES_PER_FILE: usize = 3;
+
+    #[gpui::test]
+    async fn test_record_deduplicates_and_trims() {
+        let db = LocalHistoryDb::open_test_db("test_record_deduplicates_and_trims").await;
+        let path = PathBuf::from("/project/src/main.rs");
+        let now = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+
+        assert!(
+            db.record(path.clone(), "one".into(), now, MAX_ENTRIES_PER_FILE)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !db.record(path.clone(), "one".into(), now, MAX_ENTRIES_PER_FILE)
+                .await
+                .unwrap()
+        );
+        for text in ["two", "three", "four"] {
+            assert!(
+                db.record(path.clone(), text.into(), now, MAX_ENTRIES_PER_FILE)
+                    .await
+                    .unwrap()
+            );
+        }
+
+        let entries = db.entries(path.clone()).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(db.load(entries[0].hash.clone()).unwrap(), "four");
+        assert_eq!(db.load(entries[2].hash.clone()).unwrap(), "two");
+
+        // The contents of trimmed entries are kept until the next prune.
+        assert_eq!(db.load(content_hash("one").into()).unwrap(), "one");
+        db.prune(now - Duration::days(30)).await.unwrap();
+        assert!(db.load(content_hash("one").into()).is_err());
+
+        // Going back to an earlier version is recorded as a new entry.
+        assert!(
+            db.record(path.clone(), "two".into(), now, MAX_ENTRIES_PER_FILE)
+                .await
+                .unwrap()
+        );
+        let entries = db.entries(path.clone()).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].hash, entries[2].hash);
+        assert!(db.entries("/other.rs".into()).unwrap().is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_prune_expires_old_entries() {
+        let db = LocalHistoryDb::open_test_db("test_prune_expires_old_entries").await;
+        let now = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        let long_ago = now - Duration::days(31);
+
+        db.record(
+            "/a.rs".into(),
+            "old a".into(),
+            long_ago,
+            MAX_ENTRIES_PER_FILE,
+        )
+        .await
+        .unwrap();
+        db.record("/b.rs".into(), "b".into(), now, MAX_ENTRIES_PER_FILE)
+            .await
+            .unwrap();
+
+        // Recording doesn't expire anything by itself.
+        assert_eq!(db.entries("/a.rs".into()).unwrap().len(), 1);
+
+        db.prune(now - Duration::days(30)).await.unwrap();
+        assert!(db.entries("/a.rs".into()).unwrap().is_empty());
+        assert!(db.load(content_hash("old a").into()).is_err());
+        let entries = db.entries("/b.rs".into()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].saved_at, now);
+        assert_eq!(entries[0].len, 1);
+    }
+}
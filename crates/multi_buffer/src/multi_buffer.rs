@@ -6838,6 +6838,53 @@ impl MultiBufferSnapshot {
         Some((node, excerpt.map_range_from_buffer(node_range)))
     }
 
+    /// Returns the ranges of the named children of the smallest syntax node enclosing `range`.
+    pub fn syntax_child_ranges<T: ToOffset>(
+        &self,
+        range: Range<T>,
+    ) -> Vec<Range<MultiBufferOffset>> {
+        let range = range.start.to_offset(self)..range.end.to_offset(self);
+        let Some(mut excerpt) = self.excerpt_containing(range.clone()) else {
+            return Vec::new();
+        };
+        let nodes = excerpt
+            .buffer()
+            .syntax_children(excerpt.map_range_to_buffer(range));
+        Self::node_ranges_in_excerpt(&mut excerpt, nodes)
+    }
+
+    /// Returns the ranges of the named siblings of the smallest syntax node enclosing `range`,
+    /// including that node.
+    pub fn syntax_sibling_ranges<T: ToOffset>(
+        &self,
+        range: Range<T>,
+    ) -> Vec<Range<MultiBufferOffset>> {
+        let range = range.start.to_offset(self)..range.end.to_offset(self);
+        let Some(mut excerpt) = self.excerpt_containing(range.clone()) else {
+            return Vec::new();
+        };
+        let nodes = excerpt
+            .buffer()
+            .syntax_siblings(excerpt.map_range_to_buffer(range));
+        Self::node_ranges_in_excerpt(&mut excerpt, nodes)
+    }
+
+    fn node_ranges_in_excerpt(
+        excerpt: &mut MultiBufferExcerpt<'_>,
+        nodes: Vec<tree_sitter::Node<'_>>,
+    ) -> Vec<Range<MultiBufferOffset>> {
+        nodes
+            .into_iter()
+            .filter_map(|node| {
+                let node_range = node.byte_range();
+                let node_range = BufferOffset(node_range.start)..BufferOffset(node_range.end);
+                excerpt
+                    .contains_buffer_range(node_range.clone())
+                    .then(|| excerpt.map_range_from_buffer(node_range))
+            })
+            .collect()
+    }
+
     pub fn outline(&self, theme: Option<&SyntaxTheme>) -> Option<Outline<Anchor>> {
         let (excerpt_id, _, buffer) = self.as_singleton()?;
         let outline = buffer.outline(theme);
@@ -352,21 +352,65 @@ impl MessageContent {
     pub fn push_part(&mut self, part: MessagePart) {
         match self {
             MessageContent::Plain(text) => {
-                *self =
-                    MessageContent::Multipart(vec![MessagePart::Text { text: text.clone() }, part]);
+                *self = MessageContent::Multipart(vec![
+                    MessagePart::Text {
+                        text: text.clone(),
+                        cache_control: None,
+                    },
+                    part,
+                ]);
             }
             MessageContent::Multipart(parts) if parts.is_empty() => match part {
-                MessagePart::Text { text } => *self = MessageContent::Plain(text),
-                MessagePart::Image { .. } => *self = MessageContent::Multipart(vec![part]),
+                MessagePart::Text {
+                    text,
+                    cache_control: None,
+                } => *self = MessageContent::Plain(text),
+                MessagePart::Text { .. } | MessagePart::Image { .. } => {
+                    *self = MessageContent::Multipart(vec![part])
+                }
             },
             MessageContent::Multipart(parts) => parts.push(part),
         }
     }
+
+    /// Marks the last text part of this content as a cache breakpoint,
+    /// converting plain content into a single text part if needed.
+    pub fn mark_cache_breakpoint(&mut self) {
+        let cache_control = Some(CacheControl {
+            cache_type: CacheControlType::Ephemeral,
+        });
+        match self {
+            MessageContent::Plain(text) => {
+                *self = MessageContent::Multipart(vec![MessagePart::Text {
+                    text: std::mem::take(text),
+                    cache_control,
+                }]);
+            }
+            MessageContent::Multipart(parts) => {
+                if let Some(MessagePart::Text {
+                    cache_control: part_cache_control,
+                    ..
+                }) = parts
+                    .iter_mut()
+                    .rev()
+                    .find(|part| matches!(part, MessagePart::Text { .. }))
+                {
+                    *part_cache_control = cache_control;
+                }
+            }
+        }
+    }
 }
 
 impl From<Vec<MessagePart>> for MessageContent {
     fn from(mut parts: Vec<MessagePart>) -> Self {
-        if let [MessagePart::Text { text }] = parts.as_mut_slice() {
+        if let [
+            MessagePart::Text {
+                text,
+                cache_control: None,
+            },
+        ] = parts.as_mut_slice()
+        {
             MessageContent::Plain(std::mem::take(text))
         } else {
             MessageContent::Multipart(parts)
@@ -378,11 +422,29 @@ impl From<Vec<MessagePart>> for MessageContent {
 #[serde(tag = "type")]
 pub enum MessagePart {
     #[serde(rename = "text")]
-    Text { text: String },
+    Text {
+        text: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
+    },
     #[serde(rename = "image_url")]
     Image { image_url: ImageUrl },
 }
 
+/// A prompt caching breakpoint, as accepted by OpenAI-compatible gateways that
+/// forward requests to providers with explicit caching (e.g. Anthropic).
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CacheControl {
+    #[serde(rename = "type")]
+    pub cache_type: CacheControlType,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheControlType {
+    Ephemeral,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct ImageUrl {
     pub url: String,
@@ -458,6 +520,19 @@ pub struct Usage {
     pub prompt_tokens: u64,
     pub completion_tokens: u64,
     pub total_tokens: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_tokens_details: Option<PromptTokensDetails>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct PromptTokensDetails {
+    /// Prompt tokens served from the provider's prompt cache.
+    #[serde(default)]
+    pub cached_tokens: u64,
+    /// Prompt tokens written to the prompt cache. Only reported by gateways
+    /// that proxy providers with explicit cache writes.
+    #[serde(default, alias = "cache_creation_tokens")]
+    pub cache_write_tokens: u64,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -562,12 +637,69 @@ pub async fn stream_completion_with_headers(
     request: Request,
     additional_headers: &[(String, String)],
 ) -> Result<BoxStream<'static, Result<ResponseStreamEvent>>, RequestError> {
-    let uri = format!("{api_url}/chat/completions");
+    stream_completion_with_options(
+        client,
+        provider_name,
+        api_url,
+        Some(api_key),
+        request,
+        additional_headers,
+        &EndpointOptions::default(),
+    )
+    .await
+}
+
+/// Describes an endpoint that doesn't follow OpenAI's URL or authentication conventions,
+/// such as an Azure OpenAI deployment.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EndpointOptions {
+    /// Query string appended to each request URL, without the leading `?`.
+    pub query: Option<String>,
+    /// Header to send the API key in verbatim. When `None`, the key is sent as a bearer token.
+    pub api_key_header: Option<String>,
+}
+
+impl EndpointOptions {
+    pub fn url(&self, api_url: &str, path: &str) -> String {
+        match self.query.as_deref() {
+            Some(query) if !query.is_empty() => format!("{api_url}/{path}?{query}"),
+            _ => format!("{api_url}/{path}"),
+        }
+    }
+
+    /// Returns the header that carries `api_key` on requests to this endpoint.
+    pub fn api_key_header(&self, api_key: &str) -> (String, String) {
+        match self.api_key_header.as_ref() {
+            Some(header_name) => (header_name.clone(), api_key.trim().to_string()),
+            None => (
+                "Authorization".to_string(),
+                format!("Bearer {}", api_key.trim()),
+            ),
+        }
+    }
+}
+
+/// Like [`stream_completion_with_headers`], but for endpoints described by `options`. When
+/// `api_key` is `None`, the request is authenticated by `additional_headers` alone.
+pub async fn stream_completion_with_options(
+    client: &dyn HttpClient,
+    provider_name: &str,
+    api_url: &str,
+    api_key: Option<&str>,
+    request: Request,
+    additional_headers: &[(String, String)],
+    options: &EndpointOptions,
+) -> Result<BoxStream<'static, Result<ResponseStreamEvent>>, RequestError> {
+    let uri = options.url(api_url, "chat/completions");
     let mut request_builder = HttpRequest::builder()
         .method(Method::POST)
         .uri(uri)
-        .header("Content-Type", "application/json")
-        .header("Authorization", format!("Bearer {}", api_key.trim()));
+        .header("Content-Type", "application/json");
+
+    if let Some(api_key) = api_key {
+        let (header_name, header_value) = options.api_key_header(api_key);
+        request_builder = request_builder.header(header_name, header_value);
+    }
 
     for (header_name, header_value) in additional_headers {
         request_builder = request_builder.header(header_name.as_str(), header_value.as_str());
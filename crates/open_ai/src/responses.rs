@@ -4,7 +4,7 @@ use http_client::{AsyncBody, HttpClient, Method, Request as HttpRequest};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::{ReasoningEffort, RequestError, Role, ToolChoice};
+use crate::{EndpointOptions, ReasoningEffort, RequestError, Role, ToolChoice};
 
 #[derive(Serialize, Debug)]
 pub struct Request {
@@ -214,6 +214,14 @@ pub struct ResponseUsage {
     pub output_tokens: Option<u64>,
     #[serde(default)]
     pub total_tokens: Option<u64>,
+    #[serde(default)]
+    pub input_tokens_details: Option<ResponseInputTokensDetails>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+pub struct ResponseInputTokensDetails {
+    #[serde(default)]
+    pub cached_tokens: u64,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -269,12 +277,39 @@ pub async fn stream_response_with_headers(
     request: Request,
     additional_headers: &[(String, String)],
 ) -> Result<BoxStream<'static, Result<StreamEvent>>, RequestError> {
-    let uri = format!("{api_url}/responses");
+    stream_response_with_options(
+        client,
+        provider_name,
+        api_url,
+        Some(api_key),
+        request,
+        additional_headers,
+        &EndpointOptions::default(),
+    )
+    .await
+}
+
+/// Like [`stream_response_with_headers`], but for endpoints described by `options`. When
+/// `api_key` is `None`, the request is authenticated by `additional_headers` alone.
+pub async fn stream_response_with_options(
+    client: &dyn HttpClient,
+    provider_name: &str,
+    api_url: &str,
+    api_key: Option<&str>,
+    request: Request,
+    additional_headers: &[(String, String)],
+    options: &EndpointOptions,
+) -> Result<BoxStream<'static, Result<StreamEvent>>, RequestError> {
+    let uri = options.url(api_url, "responses");
     let mut request_builder = HttpRequest::builder()
         .method(Method::POST)
         .uri(uri)
-        .header("Content-Type", "application/json")
-        .header("Authorization", format!("Bearer {}", api_key.trim()));
+        .header("Content-Type", "application/json");
+
+    if let Some(api_key) = api_key {
+        let (header_name, header_value) = options.api_key_header(api_key);
+        request_builder = request_builder.header(header_name, header_value);
+    }
 
     for (header_name, header_value) in additional_headers {
         request_builder = request_builder.header(header_name.as_str(), header_value.as_str());
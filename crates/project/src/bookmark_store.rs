@@ -0,0 +1,349 @@
+//! Module for managing bookmarks in a project.
+//!
+//! Bookmarks are anchored to their buffer while it's open, so that they move along with edits. While
+//! the buffer is closed, only the row of each bookmark is kept.
+use collections::{BTreeMap, HashMap};
+use gpui::{App, Context, Entity, EntityId, EventEmitter, SharedString, Subscription, WeakEntity};
+use language::{Buffer, BufferEvent};
+use std::{path::Path, sync::Arc};
+use text::{Bias, Point};
+
+use crate::buffer_store::{BufferStore, BufferStoreEvent};
+
+/// A bookmarked position in an open buffer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bookmark {
+    pub position: text::Anchor,
+    pub label: Option<SharedString>,
+}
+
+/// A bookmark as it's persisted, identified by its row in the file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SerializedBookmark {
+    pub row: u32,
+    pub label: Option<String>,
+}
+
+enum BookmarksInFile {
+    Open {
+        buffer: WeakEntity<Buffer>,
+        bookmarks: Vec<Bookmark>,
+    },
+    Closed(Vec<SerializedBookmark>),
+}
+
+pub enum BookmarkStoreEvent {
+    BookmarksUpdated,
+}
+
+impl EventEmitter<BookmarkStoreEvent> for BookmarkStore {}
+
+pub struct BookmarkStore {
+    buffer_store: Entity<BufferStore>,
+    bookmarks: BTreeMap<Arc<Path>, BookmarksInFile>,
+    watched_buffers: HashMap<EntityId, (Subscription, Subscription)>,
+    _subscription: Subscription,
+}
+
+impl BookmarkStore {
+    pub fn new(buffer_store: Entity<BufferStore>, cx: &mut Context<Self>) -> Self {
+        let subscription = cx.subscribe(&buffer_store, |this, _, event, cx| {
+            if let BufferStoreEvent::BufferAdded(buffer) = event {
+                this.buffer_opened(buffer, cx);
+            }
+        });
+        Self {
+            buffer_store,
+            bookmarks: BTreeMap::default(),
+            watched_buffers: HashMap::default(),
+            _subscription: subscription,
+        }
+    }
+
+    pub fn abs_path_from_buffer(buffer: &Entity<Buffer>, cx: &App) -> Option<Arc<Path>> {
+        worktree::File::from_dyn(buffer.read(cx).file())
+            .map(|file| file.worktree.read(cx).absolutize(&file.path))
+            .map(Arc::<Path>::from)
+    }
+
+    /// Adds a bookmark on the row of `position`, or removes the bookmark already on that row.
+    pub fn toggle_bookmark(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(bookmarks) = self.open_bookmarks_mut(buffer, cx) else {
+            return;
+        };
+        let snapshot = buffer.read(cx).text_snapshot();
+        let row = position.summary::<Point>(&snapshot).row;
+        let len_before = bookmarks.len();
+        bookmarks.retain(|bookmark| bookmark.position.summary::<Point>(&snapshot).row != row);
+        if bookmarks.len() == len_before {
+            bookmarks.push(Bookmark {
+                position: snapshot.anchor_before(Point::new(row, 0)),
+                label: None,
+            });
+        }
+        self.bookmarks_changed(cx);
+    }
+
+    /// Sets the label of the bookmark on the row of `position`, adding a bookmark if there is none.
+    pub fn set_bookmark_label(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        label: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(bookmarks) = self.open_bookmarks_mut(buffer, cx) else {
+            return;
+        };
+        let snapshot = buffer.read(cx).text_snapshot();
+        let row = position.summary::<Point>(&snapshot).row;
+        let label = label
+            .map(|label| label.trim().to_string())
+            .filter(|label| !label.is_empty())
+            .map(SharedString::from);
+        if let Some(bookmark) = bookmarks
+            .iter_mut()
+            .find(|bookmark| bookmark.position.summary::<Point>(&snapshot).row == row)
+        {
+            bookmark.label = label;
+        } else {
+            bookmarks.push(Bookmark {
+                position: snapshot.anchor_before(Point::new(row, 0)),
+                label,
+            });
+        }
+        self.bookmarks_changed(cx);
+    }
+
+    pub fn remove_bookmark(&mut self, abs_path: &Arc<Path>, row: u32, cx: &mut Context<Self>) {
+        match self.bookmarks.get_mut(abs_path) {
+            Some(BookmarksInFile::Open { buffer, bookmarks }) => {
+                let Some(buffer) = buffer.upgrade() else {
+                    return;
+                };
+                let snapshot = buffer.read(cx).text_snapshot();
+                bookmarks
+                    .retain(|bookmark| bookmark.position.summary::<Point>(&snapshot).row != row);
+            }
+            Some(BookmarksInFile::Closed(bookmarks)) => {
+                bookmarks.retain(|bookmark| bookmark.row != row);
+            }
+            None => return,
+        }
+        self.bookmarks_changed(cx);
+    }
+
+    pub fn clear_bookmarks(&mut self, cx: &mut Context<Self>) {
+        self.bookmarks.clear();
+        self.watched_buffers.clear();
+        cx.emit(BookmarkStoreEvent::BookmarksUpdated);
+        cx.notify();
+    }
+
+    /// Returns the bookmarks of an open buffer, in no particular order.
+    pub fn bookmarks_for_buffer(&self, buffer: &Entity<Buffer>, cx: &App) -> &[Bookmark] {
+        let Some(abs_path) = Self::abs_path_from_buffer(buffer, cx) else {
+            return &[];
+        };
+        match self.bookmarks.get(&abs_path) {
+            Some(BookmarksInFile::Open { bookmarks, .. }) => bookmarks,
+            _ => &[],
+        }
+    }
+
+    pub fn bookmark_at_row(
+        &self,
+        buffer: &Entity<Buffer>,
+        row: u32,
+        cx: &App,
+    ) -> Option<&Bookmark> {
+        let snapshot = buffer.read(cx).text_snapshot();
+        self.bookmarks_for_buffer(buffer, cx)
+            .iter()
+            .find(|bookmark| bookmark.position.summary::<Point>(&snapshot).row == row)
+    }
+
+    /// Returns every bookmark in the project, sorted by path and row.
+    pub fn all_bookmarks(&self, cx: &App) -> BTreeMap<Arc<Path>, Vec<SerializedBookmark>> {
+        self.bookmarks
+            .iter()
+            .filter_map(|(path, bookmarks_in_file)| {
+                let mut bookmarks = match bookmarks_in_file {
+                    BookmarksInFile::Open { buffer, bookmarks } => {
+                        let snapshot = buffer.upgrade()?.read(cx).text_snapshot();
+                        bookmarks
+                            .iter()
+                            .map(|bookmark| SerializedBookmark {
+                                row: bookmark.position.summary::<Point>(&snapshot).row,
+                                label: bookmark.label.as_ref().map(ToString::to_string),
+                            })
+                            .collect::<Vec<_>>()
+                    }
+                    BookmarksInFile::Closed(bookmarks) => bookmarks.clone(),
+                };
+                if bookmarks.is_empty() {
+                    return None;
+                }
+                bookmarks.sort_by_key(|bookmark| bookmark.row);
+                bookmarks.dedup_by_key(|bookmark| bookmark.row);
+                Some((path.clone(), bookmarks))
+            })
+            .collect()
+    }
+
+    pub fn with_serialized_bookmarks(
+        &mut self,
+        bookmarks: BTreeMap<Arc<Path>, Vec<SerializedBookmark>>,
+        cx: &mut Context<Self>,
+    ) {
+        self.watched_buffers.clear();
+        self.bookmarks = bookmarks
+            .into_iter()
+            .filter(|(_, bookmarks)| !bookmarks.is_empty())
+            .map(|(path, bookmarks)| (path, BookmarksInFile::Closed(bookmarks)))
+            .collect();
+
+        let open_buffers = self.buffer_store.read(cx).buffers().collect::<Vec<_>>();
+        for buffer in open_buffers {
+            self.buffer_opened(&buffer, cx);
+        }
+        cx.notify();
+    }
+
+    fn buffer_opened(&mut self, buffer: &Entity<Buffer>, cx: &mut Context<Self>) {
+        let Some(abs_path) = Self::abs_path_from_buffer(buffer, cx) else {
+            return;
+        };
+        let Some(BookmarksInFile::Closed(serialized)) = self.bookmarks.get(&abs_path) else {
+            return;
+        };
+
+        let snapshot = buffer.read(cx).text_snapshot();
+        let bookmarks = serialized
+            .iter()
+            .map(|bookmark| Bookmark {
+                position: snapshot
+                    .anchor_before(snapshot.clip_point(Point::new(bookmark.row, 0), Bias::Left)),
+                label: bookmark.label.clone().map(SharedString::from),
+            })
+            .collect();
+        self.bookmarks.insert(
+            abs_path,
+            BookmarksInFile::Open {
+                buffer: buffer.downgrade(),
+                bookmarks,
+            },
+        );
+        self.watch_buffer(buffer, cx);
+        cx.notify();
+    }
+
+    fn open_bookmarks_mut(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Option<&mut Vec<Bookmark>> {
+        let abs_path = Self::abs_path_from_buffer(buffer, cx)?;
+        if !matches!(
+            self.bookmarks.get(&abs_path),
+            Some(BookmarksInFile::Open { .. })
+        ) {
+            self.bookmarks
+                .insert(abs_path.clone(), BookmarksInFile::Closed(Vec::new()));
+            self.buffer_opened(buffer, cx);
+        }
+        match self.bookmarks.get_mut(&abs_path) {
+            Some(BookmarksInFile::Open { bookmarks, .. }) => Some(bookmarks),
+            _ => None,
+        }
+    }
+
+    fn watch_buffer(&mut self, buffer: &Entity<Buffer>, cx: &mut Context<Self>) {
+        let on_change = cx.subscribe(buffer, |this, buffer, event, cx| match event {
+            BufferEvent::Saved => {
+                cx.emit(BookmarkStoreEvent::BookmarksUpdated);
+            }
+            BufferEvent::FileHandleChanged => {
+                let entity_id = buffer.entity_id();
+                let Some(old_path) = this.path_for_open_buffer(entity_id) else {
+                    return;
+                };
+                if buffer
+                    .read(cx)
+                    .file()
+                    .is_none_or(|f| f.disk_state().is_deleted())
+                {
+                    this.bookmarks.remove(&old_path);
+                    this.watched_buffers.remove(&entity_id);
+                    this.bookmarks_changed(cx);
+                    return;
+                }
+                if let Some(new_path) = Self::abs_path_from_buffer(&buffer, cx)
+                    && new_path != old_path
+                    && let Some(bookmarks) = this.bookmarks.remove(&old_path)
+                {
+                    this.bookmarks.insert(new_path, bookmarks);
+                    this.bookmarks_changed(cx);
+                }
+            }
+            _ => {}
+        });
+
+        let entity_id = buffer.entity_id();
+        let on_release = cx.observe_release(buffer, move |this, buffer, _| {
+            this.watched_buffers.remove(&entity_id);
+            let Some(path) = this.path_for_open_buffer(entity_id) else {
+                return;
+            };
+            if let Some(BookmarksInFile::Open { bookmarks, .. }) = this.bookmarks.get(&path) {
+                let serialized = bookmarks
+                    .iter()
+                    .map(|bookmark| SerializedBookmark {
+                        row: bookmark.position.summary::<Point>(buffer).row,
+                        label: bookmark.label.as_ref().map(ToString::to_string),
+                    })
+                    .collect();
+                this.bookmarks
+                    .insert(path, BookmarksInFile::Closed(serialized));
+            }
+        });
+
+        self.watched_buffers
+            .insert(buffer.entity_id(), (on_change, on_release));
+    }
+
+    fn path_for_open_buffer(&self, entity_id: EntityId) -> Option<Arc<Path>> {
+        self.bookmarks
+            .iter()
+            .find(|(_, bookmarks_in_file)| match bookmarks_in_file {
+                BookmarksInFile::Open { buffer, .. } => buffer.entity_id() == entity_id,
+                BookmarksInFile::Closed(_) => false,
+            })
+            .map(|(path, _)| path.clone())
+    }
+
+    fn bookmarks_changed(&mut self, cx: &mut Context<Self>) {
+        self.bookmarks
+            .retain(|_, bookmarks_in_file| match bookmarks_in_file {
+                BookmarksInFile::Open { bookmarks, .. } => !bookmarks.is_empty(),
+                BookmarksInFile::Closed(bookmarks) => !bookmarks.is_empty(),
+            });
+        let open_buffers = self
+            .bookmarks
+            .values()
+            .filter_map(|bookmarks_in_file| match bookmarks_in_file {
+                BookmarksInFile::Open { buffer, .. } => Some(buffer.entity_id()),
+                BookmarksInFile::Closed(_) => None,
+            })
+            .collect::<Vec<_>>();
+        self.watched_buffers
+            .retain(|entity_id, _| open_buffers.contains(entity_id));
+        cx.emit(BookmarkStoreEvent::BookmarksUpdated);
+        cx.notify();
+    }
+}
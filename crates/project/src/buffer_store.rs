@@ -6,7 +6,7 @@ use crate::{
 use anyhow::{Context as _, Result, anyhow};
 use client::Client;
 use collections::{HashMap, HashSet, hash_map};
-use futures::{Future, FutureExt as _, channel::oneshot, future::Shared};
+use futures::{Future, FutureExt as _, StreamExt as _, channel::oneshot, future::Shared};
 use gpui::{
     App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, Subscription, Task, WeakEntity,
 };
@@ -26,7 +26,9 @@ use settings::Settings;
 use std::{io, sync::Arc, time::Instant};
 use text::{BufferId, ReplicaId};
 use util::{ResultExt as _, TryFutureExt, debug_panic, maybe, rel_path::RelPath};
-use worktree::{File, PathChange, ProjectEntryId, Worktree, WorktreeId, WorktreeSettings};
+use worktree::{
+    File, LargeFileChunks, PathChange, ProjectEntryId, Worktree, WorktreeId, WorktreeSettings,
+};
 
 /// A set of open buffers.
 pub struct BufferStore {
@@ -76,6 +78,8 @@ struct RemoteBufferStore {
 struct LocalBufferStore {
     local_buffer_ids_by_entry_id: HashMap<ProjectEntryId, BufferId>,
     worktree_store: Entity<WorktreeStore>,
+    /// Buffers opened in large-file mode whose remaining chunks are still being loaded.
+    large_file_loads: HashMap<BufferId, Task<()>>,
     _subscription: Subscription,
 }
 
@@ -389,6 +393,11 @@ impl LocalBufferStore {
         cx: &mut Context<BufferStore>,
     ) -> Task<Result<()>> {
         let buffer = buffer_handle.read(cx);
+        if self.large_file_loads.contains_key(&buffer.remote_id()) {
+            return Task::ready(Err(anyhow!(
+                "can't save a large file before it has finished loading"
+            )));
+        }
 
         let text = buffer.as_rope().clone();
         let line_ending = buffer.line_ending();
@@ -637,23 +646,35 @@ impl LocalBufferStore {
         worktree: Entity<Worktree>,
         cx: &mut Context<BufferStore>,
     ) -> Task<Result<Entity<Buffer>>> {
-        let load_file = worktree.update(cx, |worktree, cx| worktree.load_file(path.as_ref(), cx));
+        let load_file = worktree.update(cx, |worktree, cx| {
+            worktree.load_file_lazily(path.as_ref(), cx)
+        });
         cx.spawn(async move |this, cx| {
             let path = path.clone();
+            let mut remaining_chunks = None;
             let buffer = match load_file.await {
-                Ok(loaded) => {
+                Ok(mut loaded) => {
+                    remaining_chunks = loaded.remaining_chunks.take();
+                    let is_large_file = remaining_chunks.is_some();
                     let reservation = cx.reserve_entity::<Buffer>();
                     let buffer_id = BufferId::from(reservation.entity_id().as_non_zero_u64());
+                    let text = loaded.text;
                     let text_buffer = cx
                         .background_spawn(async move {
-                            text::Buffer::new(ReplicaId::LOCAL, buffer_id, loaded.text)
+                            text::Buffer::new(ReplicaId::LOCAL, buffer_id, text)
                         })
                         .await;
+                    // Large files are read-only until the user explicitly unlocks them.
+                    let capability = if is_large_file {
+                        Capability::Read
+                    } else {
+                        Capability::ReadWrite
+                    };
                     cx.insert_entity(reservation, |_| {
-                        let mut buffer =
-                            Buffer::build(text_buffer, Some(loaded.file), Capability::ReadWrite);
+                        let mut buffer = Buffer::build(text_buffer, Some(loaded.file), capability);
                         buffer.set_encoding(loaded.encoding);
                         buffer.set_has_bom(loaded.has_bom);
+                        buffer.set_large_file(is_large_file);
                         buffer
                     })
                 }
@@ -706,6 +727,15 @@ impl LocalBufferStore {
                     }
                 }
 
+                if let Some(chunks) = remaining_chunks {
+                    let load =
+                        Self::load_large_file_chunks(buffer.downgrade(), buffer_id, chunks, cx);
+                    this.as_local_mut()
+                        .unwrap()
+                        .large_file_loads
+                        .insert(buffer_id, load);
+                }
+
                 anyhow::Ok(())
             })??;
 
@@ -713,6 +743,51 @@ impl LocalBufferStore {
         })
     }
 
+    /// Appends the rest of a file opened in large-file mode to its buffer, reading and
+    /// decoding one chunk at a time on the background executor. Once this finishes, the
+    /// whole file is held in the buffer's rope, just as for any other file.
+    fn load_large_file_chunks(
+        buffer: WeakEntity<Buffer>,
+        buffer_id: BufferId,
+        mut chunks: LargeFileChunks,
+        cx: &mut Context<BufferStore>,
+    ) -> Task<()> {
+        cx.spawn(async move |this, cx| {
+            let result = async {
+                loop {
+                    let (chunk, rest) = cx
+                        .background_spawn(async move {
+                            let chunk = chunks.next().await;
+                            (chunk, chunks)
+                        })
+                        .await;
+                    chunks = rest;
+
+                    let Some(text) = chunk.transpose()? else {
+                        break;
+                    };
+                    if buffer
+                        .update(cx, |buffer, cx| buffer.append_large_file_chunk(text, cx))
+                        .is_err()
+                    {
+                        // The buffer was closed before the file finished loading.
+                        break;
+                    }
+                }
+                anyhow::Ok(())
+            }
+            .await;
+            result.context("loading large file").log_err();
+
+            this.update(cx, |this, _| {
+                if let Some(this) = this.as_local_mut() {
+                    this.large_file_loads.remove(&buffer_id);
+                }
+            })
+            .ok();
+        })
+    }
+
     fn create_buffer(
         &self,
         language: Option<Arc<Language>>,
@@ -776,6 +851,7 @@ impl BufferStore {
             state: BufferStoreState::Local(LocalBufferStore {
                 local_buffer_ids_by_entry_id: Default::default(),
                 worktree_store: worktree_store.clone(),
+                large_file_loads: Default::default(),
                 _subscription: cx.subscribe(&worktree_store, |this, _, event, cx| {
                     if let WorktreeStoreEvent::WorktreeAdded(worktree) = event {
                         let this = this.as_local_mut().unwrap();
@@ -6,6 +6,7 @@ pub mod pending_op;
 use crate::{
     ProjectEnvironment, ProjectItem, ProjectPath,
     buffer_store::{BufferStore, BufferStoreEvent},
+    project_settings::ProjectSettings,
     worktree_store::{WorktreeStore, WorktreeStoreEvent},
 };
 use anyhow::{Context as _, Result, anyhow, bail};
@@ -26,12 +27,15 @@ use futures::{
 };
 use git::{
     BuildPermalinkParams, GitHostingProviderRegistry, Oid, RunHook,
-    blame::Blame,
+    bisect::{BisectMark, BisectState, BisectStep},
+    blame::{Blame, BlameOptions, RevisionBlame},
     parse_git_remote_url,
+    rebase::{RebaseAction, RebaseControl, RebasePlan, RebaseProgress, RebaseTodoEntry},
     repository::{
-        Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions, DiffType, FetchOptions,
-        GitRepository, GitRepositoryCheckpoint, GraphCommitData, InitialGraphCommitData, LogOrder,
-        LogSource, PushOptions, Remote, RemoteCommandOutput, RepoPath, ResetMode,
+        Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions, CommitSignature,
+        CommitSigning, DiffType, FetchOptions, GitRepository, GitRepositoryCheckpoint,
+        GraphCommitData, InitialGraphCommitData, LogOrder, LogSource, PushOptions, ReflogEntry,
+        Remote, RemoteCommandOutput, RepoPath, ResetMode, SignatureStatus, SigningFormat, Tag,
         UpstreamTrackingStatus, Worktree as GitWorktree,
     },
     stash::{GitStash, StashEntry},
@@ -39,6 +43,7 @@ use git::{
         DiffTreeType, FileStatus, GitSummary, StatusCode, TrackedStatus, TreeDiff, TreeDiffStatus,
         UnmergedStatus, UnmergedStatusCode,
     },
+    submodule::{Submodule, SubmoduleCommand, SubmoduleStatus},
 };
 use gpui::{
     App, AppContext, AsyncApp, Context, Entity, EventEmitter, SharedString, Subscription, Task,
@@ -53,10 +58,10 @@ use pending_op::{PendingOp, PendingOpId, PendingOps, PendingOpsSummary};
 use postage::stream::Stream as _;
 use rpc::{
     AnyProtoClient, TypedEnvelope,
-    proto::{self, git_reset, split_repository_update},
+    proto::{self, git_bisect_mark, git_rebase_control, git_reset, split_repository_update},
 };
 use serde::Deserialize;
-use settings::WorktreeId;
+use settings::{Settings as _, SettingsLocation, WorktreeId};
 use smol::future::yield_now;
 use std::{
     cmp::Ordering,
@@ -68,7 +73,7 @@ use std::{
     str::FromStr,
     sync::{
         Arc,
-        atomic::{self, AtomicU64},
+        atomic::{self, AtomicBool, AtomicU64},
     },
     time::Instant,
 };
@@ -275,6 +280,11 @@ pub struct RepositorySnapshot {
     pub remote_origin_url: Option<String>,
     pub remote_upstream_url: Option<String>,
     pub stash_entries: GitStash,
+    pub rebase: Option<RebaseProgress>,
+    pub submodules: Arc<[Submodule]>,
+    pub bisect: Option<BisectState>,
+    /// Whether git's config signs commits by default (`commit.gpgSign`).
+    pub signs_commits_by_default: bool,
 }
 
 type JobId = u64;
@@ -304,6 +314,9 @@ pub struct Repository {
     // For a local repository, holds paths that have had worktree events since the last status scan completed,
     // and that should be examined during the next status scan.
     paths_needing_status_update: Vec<Vec<RepoPath>>,
+    // For a local repository, set when the next status scan must list the submodules again even if
+    // nothing they are recorded in has changed, such as after running a submodule command.
+    refresh_submodules: Arc<AtomicBool>,
     job_sender: mpsc::UnboundedSender<GitJob>,
     active_jobs: HashMap<JobId, JobInfo>,
     pending_ops: SumTree<PendingOps>,
@@ -520,6 +533,21 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_commit);
         client.add_entity_request_handler(Self::handle_run_hook);
         client.add_entity_request_handler(Self::handle_reset);
+        client.add_entity_request_handler(Self::handle_rebase_plan);
+        client.add_entity_request_handler(Self::handle_rebase_interactive);
+        client.add_entity_request_handler(Self::handle_rebase_control);
+        client.add_entity_request_handler(Self::handle_bisect_start);
+        client.add_entity_request_handler(Self::handle_bisect_mark);
+        client.add_entity_request_handler(Self::handle_bisect_reset);
+        client.add_entity_request_handler(Self::handle_cherry_pick);
+        client.add_entity_request_handler(Self::handle_revert);
+        client.add_entity_request_handler(Self::handle_create_tag);
+        client.add_entity_request_handler(Self::handle_get_tags);
+        client.add_entity_request_handler(Self::handle_delete_tag);
+        client.add_entity_request_handler(Self::handle_get_reflog);
+        client.add_entity_request_handler(Self::handle_checkout_commit);
+        client.add_entity_request_handler(Self::handle_blame_at_revision);
+        client.add_entity_request_handler(Self::handle_submodule_command);
         client.add_entity_request_handler(Self::handle_show);
         client.add_entity_request_handler(Self::handle_load_commit_diff);
         client.add_entity_request_handler(Self::handle_file_history);
@@ -1130,6 +1158,11 @@ impl GitStore {
         else {
             return Task::ready(Err(anyhow!("failed to find a git repository for buffer")));
         };
+        let location = buffer.file().map(|file| SettingsLocation {
+            worktree_id: file.worktree_id(cx),
+            path: file.path(),
+        });
+        let options = blame_options(location, cx);
         let content = match &version {
             Some(version) => buffer.rope_for_version(version),
             None => buffer.as_rope().clone(),
@@ -1146,7 +1179,7 @@ impl GitStore {
                 .map_err(|err| anyhow::anyhow!(err))?;
             match repository_state {
                 RepositoryState::Local(LocalRepositoryState { backend, .. }) => backend
-                    .blame(repo_path.clone(), content, line_ending)
+                    .blame(repo_path.clone(), content, line_ending, options)
                     .await
                     .with_context(|| format!("Failed to blame {:?}", repo_path.as_ref()))
                     .map(Some),
@@ -1946,6 +1979,46 @@ impl GitStore {
         })
     }
 
+    async fn handle_submodule_command(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitSubmoduleCommand>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let command = match envelope.payload.command() {
+            proto::git_submodule_command::Command::Init => SubmoduleCommand::Init,
+            proto::git_submodule_command::Command::Update => SubmoduleCommand::Update,
+            proto::git_submodule_command::Command::Sync => SubmoduleCommand::Sync,
+        };
+        let paths = envelope
+            .payload
+            .paths
+            .iter()
+            .map(|path| RepoPath::from_proto(path))
+            .collect::<Result<Vec<_>>>()?;
+        let askpass_id = envelope.payload.askpass_id;
+
+        let askpass = make_remote_delegate(
+            this,
+            envelope.payload.project_id,
+            repository_id,
+            askpass_id,
+            &mut cx,
+        );
+
+        let remote_output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.submodule_command(command, paths, askpass, cx)
+            })
+            .await??;
+
+        Ok(proto::RemoteMessageResponse {
+            stdout: remote_output.stdout,
+            stderr: remote_output.stderr,
+        })
+    }
+
     async fn handle_push(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::Push>,
@@ -2219,6 +2292,14 @@ impl GitStore {
                     CommitOptions {
                         amend: options.amend,
                         signoff: options.signoff,
+                        signing: CommitSigning {
+                            enabled: options.sign,
+                            format: options
+                                .signing_format
+                                .as_deref()
+                                .and_then(SigningFormat::from_name),
+                            key: options.signing_key,
+                        },
                     },
                     askpass,
                     cx,
@@ -2341,10 +2422,11 @@ impl GitStore {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
         let branch_name = envelope.payload.branch_name;
+        let base_branch = envelope.payload.base_branch;
 
         repository_handle
             .update(&mut cx, |repository_handle, _| {
-                repository_handle.create_branch(branch_name, None)
+                repository_handle.create_branch(branch_name, base_branch)
             })
             .await??;
 
@@ -2456,13 +2538,7 @@ impl GitStore {
                 repository_handle.show(envelope.payload.commit)
             })
             .await??;
-        Ok(proto::GitCommitDetails {
-            sha: commit.sha.into(),
-            message: commit.message.into(),
-            commit_timestamp: commit.commit_timestamp,
-            author_email: commit.author_email.into(),
-            author_name: commit.author_name.into(),
-        })
+        Ok(commit_details_to_proto(&commit))
     }
 
     async fn handle_load_commit_diff(
@@ -2537,6 +2613,7 @@ impl GitStore {
         let mode = match envelope.payload.mode() {
             git_reset::ResetMode::Soft => ResetMode::Soft,
             git_reset::ResetMode::Mixed => ResetMode::Mixed,
+            git_reset::ResetMode::Hard => ResetMode::Hard,
         };
 
         repository_handle
@@ -2547,6 +2624,268 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_rebase_plan(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebasePlan>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitRebasePlanResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let plan = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_plan(envelope.payload.base)
+            })
+            .await??;
+        Ok(proto::GitRebasePlanResponse {
+            base: plan.base.to_string(),
+            entries: plan
+                .entries
+                .iter()
+                .map(rebase_todo_entry_to_proto)
+                .collect(),
+        })
+    }
+
+    async fn handle_rebase_interactive(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseInteractive>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let entries = envelope
+            .payload
+            .entries
+            .into_iter()
+            .map(proto_to_rebase_todo_entry)
+            .collect::<Result<Vec<_>>>()?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_interactive(envelope.payload.base, entries)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_rebase_control(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseControl>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let control = match envelope.payload.control() {
+            git_rebase_control::Control::Continue => RebaseControl::Continue,
+            git_rebase_control::Control::Skip => RebaseControl::Skip,
+            git_rebase_control::Control::Abort => RebaseControl::Abort,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_control(control)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_bisect_start(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectStart>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitBisectStepResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let step = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_start(envelope.payload.bad, envelope.payload.good)
+            })
+            .await??;
+        Ok(bisect_step_to_proto(step))
+    }
+
+    async fn handle_bisect_mark(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectMark>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitBisectStepResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let mark = match envelope.payload.mark() {
+            git_bisect_mark::Mark::Good => BisectMark::Good,
+            git_bisect_mark::Mark::Bad => BisectMark::Bad,
+            git_bisect_mark::Mark::Skip => BisectMark::Skip,
+        };
+
+        let step = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_mark(mark, envelope.payload.commit)
+            })
+            .await??;
+        Ok(bisect_step_to_proto(step))
+    }
+
+    async fn handle_bisect_reset(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectReset>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_reset()
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_cherry_pick(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCherryPick>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.cherry_pick(envelope.payload.commits, envelope.payload.mainline)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_revert(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRevert>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.revert(envelope.payload.commits, envelope.payload.mainline)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_create_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCreateTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.create_tag(
+                    envelope.payload.name,
+                    envelope.payload.commit,
+                    envelope.payload.message,
+                    envelope.payload.sign,
+                )
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_get_tags(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetTags>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitTagsResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let tags = repository_handle
+            .update(&mut cx, |repository_handle, _| repository_handle.tags())
+            .await??;
+
+        Ok(proto::GitTagsResponse {
+            tags: tags.iter().map(tag_to_proto).collect(),
+        })
+    }
+
+    async fn handle_delete_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitDeleteTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.delete_tag(envelope.payload.name)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_get_reflog(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetReflog>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitReflogResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.reflog(envelope.payload.reference)
+            })
+            .await??;
+
+        Ok(proto::GitReflogResponse {
+            entries: entries.iter().map(reflog_entry_to_proto).collect(),
+        })
+    }
+
+    async fn handle_checkout_commit(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCheckoutCommit>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.checkout_commit(envelope.payload.commit)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_blame_at_revision(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBlameAtRevision>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitBlameAtRevisionResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = RepoPath::from_proto(&envelope.payload.path)?;
+
+        let blame = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.blame_at_revision(envelope.payload.revision, path, cx)
+            })
+            .await??;
+        Ok(proto::GitBlameAtRevisionResponse {
+            text: blame.text,
+            blame: Some(serialize_blame_buffer_response(Some(blame.blame))),
+        })
+    }
+
     async fn handle_checkout_files(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::GitCheckoutFiles>,
@@ -3441,6 +3780,10 @@ impl RepositorySnapshot {
             remote_origin_url: None,
             remote_upstream_url: None,
             stash_entries: Default::default(),
+            rebase: None,
+            submodules: Arc::default(),
+            bisect: None,
+            signs_commits_by_default: false,
             path_style,
         }
     }
@@ -3476,6 +3819,10 @@ impl RepositorySnapshot {
                 .collect(),
             remote_upstream_url: self.remote_upstream_url.clone(),
             remote_origin_url: self.remote_origin_url.clone(),
+            rebase: self.rebase.as_ref().map(rebase_progress_to_proto),
+            submodules: self.submodules.iter().map(submodule_to_proto).collect(),
+            bisect: self.bisect.as_ref().map(bisect_state_to_proto),
+            signs_commits_by_default: self.signs_commits_by_default,
         }
     }
 
@@ -3547,6 +3894,10 @@ impl RepositorySnapshot {
                 .collect(),
             remote_upstream_url: self.remote_upstream_url.clone(),
             remote_origin_url: self.remote_origin_url.clone(),
+            rebase: self.rebase.as_ref().map(rebase_progress_to_proto),
+            submodules: self.submodules.iter().map(submodule_to_proto).collect(),
+            bisect: self.bisect.as_ref().map(bisect_state_to_proto),
+            signs_commits_by_default: self.signs_commits_by_default,
         }
     }
 
@@ -3558,6 +3909,12 @@ impl RepositorySnapshot {
         self.statuses_by_path.summary().item_summary
     }
 
+    pub fn submodule_for_path(&self, path: &RepoPath) -> Option<&Submodule> {
+        self.submodules
+            .iter()
+            .find(|submodule| &submodule.path == path)
+    }
+
     pub fn status_for_path(&self, path: &RepoPath) -> Option<StatusEntry> {
         self.statuses_by_path
             .get(&PathKey(path.as_ref().clone()), ())
@@ -3760,6 +4117,7 @@ impl Repository {
             commit_message_buffer: None,
             askpass_delegates: Default::default(),
             paths_needing_status_update: Default::default(),
+            refresh_submodules: Arc::new(AtomicBool::new(true)),
             latest_askpass_id: 0,
             job_sender,
             job_id: 0,
@@ -3790,6 +4148,7 @@ impl Repository {
             git_store,
             pending_ops: Default::default(),
             paths_needing_status_update: Default::default(),
+            refresh_submodules: Default::default(),
             job_sender,
             repository_state,
             askpass_delegates: Default::default(),
@@ -4239,6 +4598,7 @@ impl Repository {
                             mode: match reset_mode {
                                 ResetMode::Soft => git_reset::ResetMode::Soft.into(),
                                 ResetMode::Mixed => git_reset::ResetMode::Mixed.into(),
+                                ResetMode::Hard => git_reset::ResetMode::Hard.into(),
                             },
                         })
                         .await?;
@@ -4249,45 +4609,491 @@ impl Repository {
         })
     }
 
-    pub fn show(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDetails>> {
+    pub fn rebase_plan(&mut self, base: Option<String>) -> oneshot::Receiver<Result<RebasePlan>> {
         let id = self.id;
         self.send_job(None, move |git_repo, _cx| async move {
             match git_repo {
                 RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
-                    backend.show(commit).await
+                    backend.rebase_plan(base).await
                 }
                 RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
-                    let resp = client
-                        .request(proto::GitShow {
+                    let response = client
+                        .request(proto::GitRebasePlan {
                             project_id: project_id.0,
                             repository_id: id.to_proto(),
-                            commit,
+                            base,
                         })
                         .await?;
 
-                    Ok(CommitDetails {
-                        sha: resp.sha.into(),
-                        message: resp.message.into(),
-                        commit_timestamp: resp.commit_timestamp,
-                        author_email: resp.author_email.into(),
-                        author_name: resp.author_name.into(),
+                    Ok(RebasePlan {
+                        base: response.base.into(),
+                        entries: response
+                            .entries
+                            .into_iter()
+                            .map(proto_to_rebase_todo_entry)
+                            .collect::<Result<_>>()?,
                     })
                 }
             }
         })
     }
 
-    pub fn load_commit_diff(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDiff>> {
+    pub fn rebase_interactive(
+        &mut self,
+        base: String,
+        entries: Vec<RebaseTodoEntry>,
+    ) -> oneshot::Receiver<Result<()>> {
         let id = self.id;
-        self.send_job(None, move |git_repo, cx| async move {
-            match git_repo {
-                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
-                    backend.load_commit(commit, cx).await
+        self.send_job(
+            Some("git rebase --interactive".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.rebase_interactive(base, entries, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRebaseInteractive {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                base,
+                                entries: entries.iter().map(rebase_todo_entry_to_proto).collect(),
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
                 }
-                RepositoryState::Remote(RemoteRepositoryState {
-                    client, project_id, ..
-                }) => {
-                    let response = client
+            },
+        )
+    }
+
+    pub fn rebase_control(&mut self, control: RebaseControl) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git rebase {}", control.as_arg()).into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.rebase_control(control, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRebaseControl {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                control: match control {
+                                    RebaseControl::Continue => {
+                                        git_rebase_control::Control::Continue.into()
+                                    }
+                                    RebaseControl::Skip => git_rebase_control::Control::Skip.into(),
+                                    RebaseControl::Abort => {
+                                        git_rebase_control::Control::Abort.into()
+                                    }
+                                },
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn bisect_start(
+        &mut self,
+        bad: Option<String>,
+        good: Vec<String>,
+    ) -> oneshot::Receiver<Result<Option<BisectStep>>> {
+        let id = self.id;
+        self.send_job(
+            Some("git bisect start".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_start(bad, good, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        let response = client
+                            .request(proto::GitBisectStart {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                bad,
+                                good,
+                            })
+                            .await?;
+
+                        Ok(proto_to_bisect_step(response))
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn bisect_mark(
+        &mut self,
+        mark: BisectMark,
+        commit: Option<String>,
+    ) -> oneshot::Receiver<Result<Option<BisectStep>>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git bisect {}", mark.as_arg()).into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_mark(mark, commit, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        let response = client
+                            .request(proto::GitBisectMark {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                mark: match mark {
+                                    BisectMark::Good => git_bisect_mark::Mark::Good,
+                                    BisectMark::Bad => git_bisect_mark::Mark::Bad,
+                                    BisectMark::Skip => git_bisect_mark::Mark::Skip,
+                                }
+                                .into(),
+                                commit,
+                            })
+                            .await?;
+
+                        Ok(proto_to_bisect_step(response))
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn bisect_reset(&mut self) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some("git bisect reset".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_reset(environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitBisectReset {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn cherry_pick(
+        &mut self,
+        commits: Vec<String>,
+        mainline: Option<u32>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git cherry-pick {}", commits.join(" ")).into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.cherry_pick(commits, mainline, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitCherryPick {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commits,
+                                mainline,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn revert(
+        &mut self,
+        commits: Vec<String>,
+        mainline: Option<u32>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git revert {}", commits.join(" ")).into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.revert(commits, mainline, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRevert {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commits,
+                                mainline,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn create_tag(
+        &mut self,
+        name: String,
+        commit: String,
+        message: Option<String>,
+        sign: bool,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git tag {name} {commit}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        backend
+                            .create_tag(name, commit, message, sign, environment)
+                            .await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitCreateTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name,
+                                commit,
+                                message,
+                                sign,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn tags(&mut self) -> oneshot::Receiver<Result<Vec<Tag>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.tags().await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitGetTags {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+
+                    Ok(response.tags.iter().map(proto_to_tag).collect())
+                }
+            }
+        })
+    }
+
+    pub fn delete_tag(&mut self, name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git tag --delete {name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(state) => state.backend.delete_tag(name).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitDeleteTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    /// Lists the reflog of `reference`, which is `HEAD` or a branch name.
+    pub fn reflog(&mut self, reference: String) -> oneshot::Receiver<Result<Vec<ReflogEntry>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.reflog(reference).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitGetReflog {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            reference,
+                        })
+                        .await?;
+
+                    Ok(response.entries.iter().map(proto_to_reflog_entry).collect())
+                }
+            }
+        })
+    }
+
+    /// Blames `path` as it was committed in `revision`, returning the file's contents there too.
+    pub fn blame_at_revision(
+        &mut self,
+        revision: String,
+        path: RepoPath,
+        cx: &App,
+    ) -> oneshot::Receiver<Result<RevisionBlame>> {
+        let id = self.id;
+        let project_path = self.repo_path_to_project_path(&path, cx);
+        let options = blame_options(
+            project_path.as_ref().map(|project_path| SettingsLocation {
+                worktree_id: project_path.worktree_id,
+                path: &project_path.path,
+            }),
+            cx,
+        );
+        self.send_job(None, move |repo, _| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => backend
+                    .blame_at_revision(revision, path.clone(), options)
+                    .await
+                    .with_context(|| format!("Failed to blame {:?}", path.as_ref())),
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitBlameAtRevision {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            revision,
+                            path: path.to_proto(),
+                        })
+                        .await?;
+
+                    Ok(RevisionBlame {
+                        text: response.text,
+                        blame: response
+                            .blame
+                            .and_then(deserialize_blame_buffer_response)
+                            .unwrap_or_default(),
+                    })
+                }
+            }
+        })
+    }
+
+    /// Checks out `commit` with a detached `HEAD`.
+    pub fn checkout_commit(&mut self, commit: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git switch --detach {commit}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.checkout_commit(commit, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitCheckoutCommit {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commit,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn show(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDetails>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.show(commit).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let resp = client
+                        .request(proto::GitShow {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            commit,
+                        })
+                        .await?;
+
+                    Ok(proto_to_commit_details(&resp))
+                }
+            }
+        })
+    }
+
+    /// Verifies the signatures of `commits` in one batch. Like the git graph's commit data,
+    /// this is only available for local repositories.
+    pub fn commit_signatures(
+        &mut self,
+        commits: Vec<Oid>,
+    ) -> oneshot::Receiver<Result<HashMap<Oid, Option<CommitSignature>>>> {
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.commit_signatures(commits).await
+                }
+                RepositoryState::Remote(_) => {
+                    bail!("commit signatures are not supported for remote repositories")
+                }
+            }
+        })
+    }
+
+    pub fn load_commit_diff(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDiff>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.load_commit(commit, cx).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState {
+                    client, project_id, ..
+                }) => {
+                    let response = client
                         .request(proto::LoadCommitDiff {
                             project_id: project_id.0,
                             repository_id: id.to_proto(),
@@ -5077,6 +5883,12 @@ impl Repository {
                             options: Some(proto::commit::CommitOptions {
                                 amend: options.amend,
                                 signoff: options.signoff,
+                                sign: options.signing.enabled,
+                                signing_format: options
+                                    .signing
+                                    .format
+                                    .map(|format| format.as_str().to_string()),
+                                signing_key: options.signing.key,
                             }),
                             askpass_id,
                         })
@@ -5132,6 +5944,72 @@ impl Repository {
         })
     }
 
+    pub fn submodule_command(
+        &mut self,
+        command: SubmoduleCommand,
+        paths: Vec<RepoPath>,
+        askpass: AskPassDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let refresh_submodules = self.refresh_submodules.clone();
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git submodule {}", command.as_arg()).into()),
+            move |git_repo, cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        let output = backend
+                            .submodule_command(command, paths, askpass, environment, cx)
+                            .await;
+                        refresh_submodules.store(true, atomic::Ordering::Release);
+                        output
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        askpass_delegates.lock().insert(askpass_id, askpass);
+                        let _defer = util::defer(|| {
+                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                            debug_assert!(askpass_delegate.is_some());
+                        });
+
+                        let response = client
+                            .request(proto::GitSubmoduleCommand {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                askpass_id,
+                                command: match command {
+                                    SubmoduleCommand::Init => {
+                                        proto::git_submodule_command::Command::Init
+                                    }
+                                    SubmoduleCommand::Update => {
+                                        proto::git_submodule_command::Command::Update
+                                    }
+                                    SubmoduleCommand::Sync => {
+                                        proto::git_submodule_command::Command::Sync
+                                    }
+                                }
+                                .into(),
+                                paths: paths.iter().map(|path| path.to_proto()).collect(),
+                            })
+                            .await
+                            .context("sending submodule request")?;
+
+                        Ok(RemoteCommandOutput {
+                            stdout: response.stdout,
+                            stderr: response.stderr,
+                        })
+                    }
+                }
+            },
+        )
+    }
+
     pub fn push(
         &mut self,
         branch: SharedString,
@@ -5710,6 +6588,7 @@ impl Repository {
                             project_id: project_id.0,
                             repository_id: id.to_proto(),
                             branch_name,
+                            base_branch,
                         })
                         .await?;
 
@@ -5882,6 +6761,18 @@ impl Repository {
         self.snapshot.stash_entries = new_stash_entries;
         self.snapshot.remote_upstream_url = update.remote_upstream_url;
         self.snapshot.remote_origin_url = update.remote_origin_url;
+        self.snapshot.rebase = update.rebase.as_ref().map(proto_to_rebase_progress);
+        self.snapshot.bisect = update.bisect.as_ref().map(proto_to_bisect_state);
+        self.snapshot.signs_commits_by_default = update.signs_commits_by_default;
+        let new_submodules = update
+            .submodules
+            .iter()
+            .filter_map(|submodule| proto_to_submodule(submodule).log_err())
+            .collect::<Arc<[_]>>();
+        if self.snapshot.submodules != new_submodules {
+            cx.emit(RepositoryEvent::StatusesChanged);
+        }
+        self.snapshot.submodules = new_submodules;
 
         let edits = update
             .removed_statuses
@@ -5994,6 +6885,8 @@ impl Repository {
                             this.id,
                             this.work_directory_abs_path.clone(),
                             this.snapshot.clone(),
+                            this.refresh_submodules
+                                .swap(false, atomic::Ordering::AcqRel),
                             backend.clone(),
                         )
                     })
@@ -6196,6 +7089,12 @@ impl Repository {
         updates_tx: Option<mpsc::UnboundedSender<DownstreamUpdate>>,
         cx: &mut Context<Self>,
     ) {
+        // Submodules are only listed during full scans, so edits to `.gitmodules` schedule one.
+        if paths.iter().any(|path| path.as_unix_str() == ".gitmodules") {
+            self.refresh_submodules
+                .store(true, atomic::Ordering::Release);
+            self.schedule_scan(updates_tx.clone(), cx);
+        }
         if !paths.is_empty() {
             self.paths_needing_status_update.push(paths);
         }
@@ -6413,6 +7312,16 @@ fn get_permalink_in_rust_registry_src(
     Ok(permalink)
 }
 
+fn blame_options(location: Option<SettingsLocation>, cx: &App) -> BlameOptions {
+    let blame_settings = &ProjectSettings::get(location, cx).git.blame;
+    BlameOptions {
+        detect_moves: blame_settings.detect_moves,
+        detect_copies: blame_settings.detect_copies,
+        ignore_revs_file: blame_settings.ignore_revs_file.clone(),
+        ignore_revs: blame_settings.ignore_revs.clone(),
+    }
+}
+
 fn serialize_blame_buffer_response(blame: Option<git::blame::Blame>) -> proto::BlameBufferResponse {
     let Some(blame) = blame else {
         return proto::BlameBufferResponse {
@@ -6522,6 +7431,44 @@ fn branch_to_proto(branch: &git::repository::Branch) -> proto::Branch {
     }
 }
 
+fn tag_to_proto(tag: &Tag) -> proto::GitTag {
+    proto::GitTag {
+        name: tag.name.to_string(),
+        sha: tag.sha.to_string(),
+        message: tag.message.as_ref().map(|message| message.to_string()),
+        timestamp: tag.timestamp,
+    }
+}
+
+fn proto_to_tag(proto: &proto::GitTag) -> Tag {
+    Tag {
+        name: proto.name.clone().into(),
+        sha: proto.sha.clone().into(),
+        message: proto.message.clone().map(Into::into),
+        timestamp: proto.timestamp,
+    }
+}
+
+fn reflog_entry_to_proto(entry: &ReflogEntry) -> proto::GitReflogEntry {
+    proto::GitReflogEntry {
+        selector: entry.selector.to_string(),
+        sha: entry.sha.to_string(),
+        message: entry.message.to_string(),
+        subject: entry.subject.to_string(),
+        timestamp: entry.timestamp,
+    }
+}
+
+fn proto_to_reflog_entry(proto: &proto::GitReflogEntry) -> ReflogEntry {
+    ReflogEntry {
+        selector: proto.selector.clone().into(),
+        sha: proto.sha.clone().into(),
+        message: proto.message.clone().into(),
+        subject: proto.subject.clone().into(),
+        timestamp: proto.timestamp,
+    }
+}
+
 fn worktree_to_proto(worktree: &git::repository::Worktree) -> proto::Worktree {
     proto::Worktree {
         path: worktree.path.to_string_lossy().to_string(),
@@ -6577,6 +7524,24 @@ fn commit_details_to_proto(commit: &CommitDetails) -> proto::GitCommitDetails {
         commit_timestamp: commit.commit_timestamp,
         author_email: commit.author_email.to_string(),
         author_name: commit.author_name.to_string(),
+        signature: commit
+            .signature
+            .as_ref()
+            .map(|signature| proto::GitCommitSignature {
+                status: match signature.status {
+                    SignatureStatus::Good => proto::git_commit_signature::Status::Good,
+                    SignatureStatus::GoodUnknownValidity => {
+                        proto::git_commit_signature::Status::GoodUnknownValidity
+                    }
+                    SignatureStatus::Expired => proto::git_commit_signature::Status::Expired,
+                    SignatureStatus::Revoked => proto::git_commit_signature::Status::Revoked,
+                    SignatureStatus::Bad => proto::git_commit_signature::Status::Bad,
+                    SignatureStatus::Unverified => proto::git_commit_signature::Status::Unverified,
+                }
+                .into(),
+                signer: signature.signer.to_string(),
+                key: signature.key.to_string(),
+            }),
     }
 }
 
@@ -6587,13 +7552,146 @@ fn proto_to_commit_details(proto: &proto::GitCommitDetails) -> CommitDetails {
         commit_timestamp: proto.commit_timestamp,
         author_email: proto.author_email.clone().into(),
         author_name: proto.author_name.clone().into(),
+        signature: proto.signature.as_ref().map(|signature| CommitSignature {
+            status: match signature.status() {
+                proto::git_commit_signature::Status::Good => SignatureStatus::Good,
+                proto::git_commit_signature::Status::GoodUnknownValidity => {
+                    SignatureStatus::GoodUnknownValidity
+                }
+                proto::git_commit_signature::Status::Expired => SignatureStatus::Expired,
+                proto::git_commit_signature::Status::Revoked => SignatureStatus::Revoked,
+                proto::git_commit_signature::Status::Bad => SignatureStatus::Bad,
+                proto::git_commit_signature::Status::Unverified => SignatureStatus::Unverified,
+            },
+            signer: signature.signer.clone().into(),
+            key: signature.key.clone().into(),
+        }),
     }
 }
 
+fn rebase_progress_to_proto(progress: &RebaseProgress) -> proto::GitRebaseProgress {
+    proto::GitRebaseProgress {
+        head_name: progress.head_name.as_ref().map(|name| name.to_string()),
+        onto: progress.onto.to_string(),
+        step: progress.step as u64,
+        total: progress.total as u64,
+        stopped_sha: progress.stopped_sha.as_ref().map(|sha| sha.to_string()),
+    }
+}
+
+fn submodule_to_proto(submodule: &Submodule) -> proto::GitSubmodule {
+    proto::GitSubmodule {
+        path: submodule.path.to_proto(),
+        sha: submodule.sha.to_string(),
+        status: match submodule.status {
+            SubmoduleStatus::Uninitialized => proto::git_submodule::Status::Uninitialized,
+            SubmoduleStatus::Current => proto::git_submodule::Status::Current,
+            SubmoduleStatus::OutOfSync => proto::git_submodule::Status::OutOfSync,
+            SubmoduleStatus::Conflicted => proto::git_submodule::Status::Conflicted,
+        }
+        .into(),
+    }
+}
+
+fn proto_to_submodule(proto: &proto::GitSubmodule) -> Result<Submodule> {
+    Ok(Submodule {
+        path: RepoPath::from_proto(&proto.path)?,
+        sha: proto.sha.clone().into(),
+        status: match proto.status() {
+            proto::git_submodule::Status::Uninitialized => SubmoduleStatus::Uninitialized,
+            proto::git_submodule::Status::Current => SubmoduleStatus::Current,
+            proto::git_submodule::Status::OutOfSync => SubmoduleStatus::OutOfSync,
+            proto::git_submodule::Status::Conflicted => SubmoduleStatus::Conflicted,
+        },
+    })
+}
+
+fn proto_to_rebase_progress(proto: &proto::GitRebaseProgress) -> RebaseProgress {
+    RebaseProgress {
+        head_name: proto.head_name.clone().map(SharedString::from),
+        onto: proto.onto.clone().into(),
+        step: proto.step as usize,
+        total: proto.total as usize,
+        stopped_sha: proto.stopped_sha.clone().map(SharedString::from),
+    }
+}
+
+fn bisect_state_to_proto(state: &BisectState) -> proto::GitBisectState {
+    proto::GitBisectState {
+        bad: state.bad.as_ref().map(|sha| sha.to_string()),
+        good: state.good.iter().map(|sha| sha.to_string()).collect(),
+        skipped: state.skipped.iter().map(|sha| sha.to_string()).collect(),
+        first_bad: state.first_bad.as_ref().map(|sha| sha.to_string()),
+    }
+}
+
+fn proto_to_bisect_state(proto: &proto::GitBisectState) -> BisectState {
+    BisectState {
+        bad: proto.bad.clone().map(SharedString::from),
+        good: proto.good.iter().cloned().map(SharedString::from).collect(),
+        skipped: proto
+            .skipped
+            .iter()
+            .cloned()
+            .map(SharedString::from)
+            .collect(),
+        first_bad: proto.first_bad.clone().map(SharedString::from),
+    }
+}
+
+fn bisect_step_to_proto(step: Option<BisectStep>) -> proto::GitBisectStepResponse {
+    proto::GitBisectStepResponse {
+        step: step.map(|step| match step {
+            BisectStep::Testing {
+                sha,
+                remaining,
+                steps,
+            } => proto::git_bisect_step_response::Step::Testing(proto::GitBisectTesting {
+                sha: sha.to_string(),
+                remaining: remaining as u64,
+                steps: steps as u64,
+            }),
+            BisectStep::Found { sha } => {
+                proto::git_bisect_step_response::Step::Found(sha.to_string())
+            }
+        }),
+    }
+}
+
+fn proto_to_bisect_step(proto: proto::GitBisectStepResponse) -> Option<BisectStep> {
+    Some(match proto.step? {
+        proto::git_bisect_step_response::Step::Testing(testing) => BisectStep::Testing {
+            sha: testing.sha.into(),
+            remaining: testing.remaining as usize,
+            steps: testing.steps as usize,
+        },
+        proto::git_bisect_step_response::Step::Found(sha) => BisectStep::Found { sha: sha.into() },
+    })
+}
+
+fn rebase_todo_entry_to_proto(entry: &RebaseTodoEntry) -> proto::GitRebaseTodoEntry {
+    proto::GitRebaseTodoEntry {
+        sha: entry.sha.to_string(),
+        subject: entry.subject.to_string(),
+        action: entry.action.as_str().to_string(),
+        message: entry.message.as_ref().map(|message| message.to_string()),
+    }
+}
+
+fn proto_to_rebase_todo_entry(proto: proto::GitRebaseTodoEntry) -> Result<RebaseTodoEntry> {
+    Ok(RebaseTodoEntry {
+        sha: proto.sha.into(),
+        subject: proto.subject.into(),
+        action: proto.action.parse::<RebaseAction>()?,
+        message: proto.message.map(SharedString::from),
+    })
+}
+
 async fn compute_snapshot(
     id: RepositoryId,
     work_directory_abs_path: Arc<Path>,
     prev_snapshot: RepositorySnapshot,
+    refresh_submodules: bool,
     backend: Arc<dyn GitRepository>,
 ) -> Result<(RepositorySnapshot, Vec<RepositoryEvent>)> {
     let mut events = Vec::new();
@@ -6623,22 +7721,60 @@ async fn compute_snapshot(
         events.push(RepositoryEvent::MergeHeadsChanged);
     }
 
-    if statuses_by_path != prev_snapshot.statuses_by_path {
-        events.push(RepositoryEvent::StatusesChanged)
-    }
-
     // Useful when branch is None in detached head state
     let head_commit = match backend.head_sha().await {
         Some(head_sha) => backend.show(head_sha).await.log_err(),
         None => None,
     };
 
-    if branch != prev_snapshot.branch || head_commit != prev_snapshot.head_commit {
+    // `git submodule status` inspects every submodule, so it only runs again when the commits
+    // recorded for them may have changed: HEAD moved, `.gitmodules` or a gitlink changed status,
+    // or a submodule command ran.
+    let status_changed = |path: &RepoPath| {
+        prev_snapshot
+            .status_for_path(path)
+            .map(|entry| entry.status)
+            != statuses_by_path
+                .get(&PathKey(path.as_ref().clone()), ())
+                .map(|entry| entry.status)
+    };
+    let submodules: Arc<[Submodule]> = if refresh_submodules
+        || head_commit != prev_snapshot.head_commit
+        || status_changed(&RepoPath::new(".gitmodules")?)
+        || prev_snapshot
+            .submodules
+            .iter()
+            .any(|submodule| status_changed(&submodule.path))
+    {
+        backend
+            .submodules()
+            .await
+            .log_err()
+            .unwrap_or_default()
+            .into()
+    } else {
+        prev_snapshot.submodules.clone()
+    };
+
+    if statuses_by_path != prev_snapshot.statuses_by_path || submodules != prev_snapshot.submodules
+    {
+        events.push(RepositoryEvent::StatusesChanged)
+    }
+
+    let rebase = backend.rebase_progress().await;
+    let bisect = backend.bisect_state().await;
+
+    if branch != prev_snapshot.branch
+        || head_commit != prev_snapshot.head_commit
+        || rebase != prev_snapshot.rebase
+        || bisect != prev_snapshot.bisect
+    {
         events.push(RepositoryEvent::BranchChanged);
     }
 
     let remote_origin_url = backend.remote_url("origin").await;
     let remote_upstream_url = backend.remote_url("upstream").await;
+    let signs_commits_by_default = backend.signs_commits_by_default().await;
 
     let snapshot = RepositorySnapshot {
         id,
@@ -6652,6 +7788,10 @@ async fn compute_snapshot(
         remote_origin_url,
         remote_upstream_url,
         stash_entries,
+        rebase,
+        submodules,
+        bisect,
+        signs_commits_by_default,
     };
 
     Ok((snapshot, events))
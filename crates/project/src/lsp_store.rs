@@ -2695,7 +2695,7 @@ impl LocalLspStore {
         let Some(file) = File::from_dyn(buffer.file()) else {
             return;
         };
-        if !file.is_local() {
+        if !file.is_local() || buffer.is_large_file() {
             return;
         }
 
@@ -4744,6 +4744,9 @@ impl LspStore {
     ) -> Option<language::AvailableLanguage> {
         // If the buffer has a language, set it and start the language server if we haven't already.
         let buffer = buffer_handle.read(cx);
+        if buffer.is_large_file() {
+            return None;
+        }
         let file = buffer.file()?;
 
         let content = buffer.as_rope();
@@ -1,5 +1,6 @@
 pub mod agent_registry_store;
 pub mod agent_server_store;
+pub mod bookmark_store;
 pub mod buffer_store;
 pub mod color_extractor;
 pub mod connection_manager;
@@ -54,6 +55,7 @@ pub use manifest_tree::ManifestTree;
 pub use project_search::{Search, SearchResults};
 
 use anyhow::{Context as _, Result, anyhow};
+use bookmark_store::BookmarkStore;
 use buffer_store::{BufferStore, BufferStoreEvent};
 use client::{
     Client, Collaborator, PendingEntitySubscription, ProjectId, TypedEnvelope, UserStore, proto,
@@ -211,6 +213,7 @@ pub struct Project {
     agent_server_store: Entity<AgentServerStore>,
 
     breakpoint_store: Entity<BreakpointStore>,
+    bookmark_store: Entity<BookmarkStore>,
     collab_client: Arc<client::Client>,
     join_project_response_message_id: u32,
     task_store: Entity<TaskStore>,
@@ -1196,6 +1199,7 @@ impl Project {
 
             let breakpoint_store =
                 cx.new(|_| BreakpointStore::local(worktree_store.clone(), buffer_store.clone()));
+            let bookmark_store = cx.new(|cx| BookmarkStore::new(buffer_store.clone(), cx));
 
             let dap_store = cx.new(|cx| {
                 DapStore::new_local(
@@ -1312,6 +1316,7 @@ impl Project {
                 fs,
                 remote_client: None,
                 breakpoint_store,
+                bookmark_store,
                 dap_store,
                 agent_server_store,
 
@@ -1475,6 +1480,7 @@ impl Project {
                     worktree_store.clone(),
                 )
             });
+            let bookmark_store = cx.new(|cx| BookmarkStore::new(buffer_store.clone(), cx));
 
             let dap_store = cx.new(|cx| {
                 DapStore::new_remote(
@@ -1513,6 +1519,7 @@ impl Project {
                 lsp_store,
                 context_server_store,
                 breakpoint_store,
+                bookmark_store,
                 dap_store,
                 join_project_response_message_id: 0,
                 client_state: ProjectClientState::Local,
@@ -1697,6 +1704,7 @@ impl Project {
                 worktree_store.clone(),
             )
         });
+        let bookmark_store = cx.new(|cx| BookmarkStore::new(buffer_store.clone(), cx));
         let dap_store = cx.new(|cx| {
             DapStore::new_collab(
                 remote_id,
@@ -1822,6 +1830,7 @@ impl Project {
                     replica_id,
                 },
                 breakpoint_store: breakpoint_store.clone(),
+                bookmark_store,
                 dap_store: dap_store.clone(),
                 git_store: git_store.clone(),
                 agent_server_store,
@@ -2054,6 +2063,11 @@ impl Project {
         self.breakpoint_store.clone()
     }
 
+    #[inline]
+    pub fn bookmark_store(&self) -> Entity<BookmarkStore> {
+        self.bookmark_store.clone()
+    }
+
     pub fn active_debug_session(&self, cx: &App) -> Option<(Entity<Session>, ActiveStackFrame)> {
         let active_position = self.breakpoint_store.read(cx).active_position()?;
         let session = self
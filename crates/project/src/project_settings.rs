@@ -5,7 +5,9 @@ use dap::adapters::DebugAdapterName;
 use fs::Fs;
 use futures::StreamExt as _;
 use git::repository::DEFAULT_WORKTREE_DIRECTORY;
-use gpui::{AsyncApp, BorrowAppContext, Context, Entity, EventEmitter, Subscription, Task};
+use gpui::{
+    AsyncApp, BorrowAppContext, Context, Entity, EventEmitter, SharedString, Subscription, Task,
+};
 use lsp::{DEFAULT_LSP_REQUEST_TIMEOUT_SECS, LanguageServerName};
 use paths::{
     EDITORCONFIG_NAME, local_debug_file_relative_path, local_settings_file_relative_path,
@@ -65,6 +67,9 @@ pub struct ProjectSettings {
     /// Default timeout for context server requests in seconds.
     pub context_server_timeout: u64,
 
+    /// The credential profile to use for each language model provider, keyed by provider id.
+    pub language_model_credential_profiles: HashMap<Arc<str>, SharedString>,
+
     /// Configuration for Diagnostics-related features.
     pub diagnostics: DiagnosticsSettings,
 
@@ -462,6 +467,8 @@ pub struct GitSettings {
     ///
     /// Default: ../worktrees
     pub worktree_directory: String,
+    /// How commits made from Zed are signed. Unset fields defer to git config.
+    pub commit_signing: settings::CommitSigningSettingsContent,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -519,12 +526,28 @@ pub struct InlineBlameSettings {
     pub show_commit_summary: bool,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct BlameSettings {
     /// Whether to show the avatar of the author of the commit.
     ///
     /// Default: true
     pub show_avatar: bool,
+    /// Whether to follow lines moved or copied within a file (`git blame -M`).
+    ///
+    /// Default: false
+    pub detect_moves: bool,
+    /// Whether to follow lines moved or copied from other files (`git blame -C`).
+    ///
+    /// Default: false
+    pub detect_copies: bool,
+    /// A file listing commits for blame to skip, relative to the repository root.
+    ///
+    /// Default: null
+    pub ignore_revs_file: Option<String>,
+    /// Commits for blame to skip.
+    ///
+    /// Default: []
+    pub ignore_revs: Vec<String>,
 }
 
 impl GitSettings {
@@ -638,9 +661,13 @@ impl Settings for ProjectSettings {
                 }
             },
             blame: {
-                let blame = git.blame.unwrap();
+                let blame = git.blame.clone().unwrap();
                 BlameSettings {
                     show_avatar: blame.show_avatar.unwrap(),
+                    detect_moves: blame.detect_moves.unwrap(),
+                    detect_copies: blame.detect_copies.unwrap(),
+                    ignore_revs_file: blame.ignore_revs_file,
+                    ignore_revs: blame.ignore_revs.unwrap(),
                 }
             },
             branch_picker: {
@@ -655,6 +682,7 @@ impl Settings for ProjectSettings {
                 .worktree_directory
                 .clone()
                 .unwrap_or_else(|| DEFAULT_WORKTREE_DIRECTORY.to_string()),
+            commit_signing: git.commit_signing.clone().unwrap_or_default(),
         };
         Self {
             context_servers: project
@@ -664,6 +692,12 @@ impl Settings for ProjectSettings {
                 .map(|(key, value)| (key, value.into()))
                 .collect(),
             context_server_timeout: project.context_server_timeout.unwrap_or(60),
+            language_model_credential_profiles: project
+                .language_model_credential_profiles
+                .clone()
+                .into_iter()
+                .map(|(provider, profile)| (provider, profile.into()))
+                .collect(),
             lsp: project
                 .lsp
                 .clone()
@@ -0,0 +1,394 @@
+use anyhow::{Result, bail};
+use collections::HashMap;
+use language::{Language, LanguageName, Node, Tree, with_parser, with_query_cursor};
+use parking_lot::Mutex;
+use regex::Regex;
+use std::{
+    fmt,
+    ops::Range,
+    sync::{Arc, LazyLock},
+};
+use streaming_iterator::StreamingIterator as _;
+use tree_sitter::Query;
+
+static METAVARIABLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\$([A-Z][A-Z0-9_]*)").expect("valid metavariable regex"));
+static TEMPLATE_VARIABLE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\$(\$|[A-Za-z_][A-Za-z0-9_]*)").expect("valid template variable regex")
+});
+static WORD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[A-Za-z_][A-Za-z0-9_]*").expect("valid word regex"));
+
+const PLACEHOLDER_PREFIX: &str = "zed_metavariable_";
+/// Capture name that marks the matched range in a raw tree-sitter query.
+const MATCH_CAPTURE: &str = "match";
+
+/// A syntax tree pattern, matched against every buffer using that buffer's own grammar.
+///
+/// A pattern is either a code snippet in which `$NAME` metavariables stand for any single
+/// syntax node, such as `$X.unwrap()`, or a tree-sitter query whose captures act as
+/// metavariables. Both are compiled lazily, once per language.
+pub struct StructuralPattern {
+    source: String,
+    literals: Vec<String>,
+    compiled: Mutex<HashMap<LanguageName, Option<Arc<CompiledPattern>>>>,
+}
+
+enum CompiledPattern {
+    Code(PatternNode),
+    Query(Query),
+}
+
+#[derive(Debug)]
+enum PatternNode {
+    Metavariable(String),
+    Leaf {
+        kind_id: u16,
+        text: String,
+    },
+    Branch {
+        kind_id: u16,
+        children: Vec<PatternNode>,
+    },
+}
+
+/// A structural match: the matched range and the ranges captured by each metavariable.
+struct StructuralMatch {
+    range: Range<usize>,
+    captures: HashMap<String, Range<usize>>,
+}
+
+impl fmt::Debug for StructuralPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StructuralPattern")
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StructuralPattern {
+    /// Creates a pattern, failing if `source` can't be a snippet or a query in any language.
+    pub fn new(source: &str) -> Result<Self> {
+        anyhow::ensure!(!source.trim().is_empty(), "structural pattern is empty");
+        check_delimiters(source)?;
+        let literals = if is_raw_query(source) {
+            Vec::new()
+        } else {
+            let without_metavariables = METAVARIABLE.replace_all(source, " ");
+            let mut literals = WORD
+                .find_iter(&without_metavariables)
+                .map(|word| word.as_str().to_string())
+                .collect::<Vec<_>>();
+            literals.sort();
+            literals.dedup();
+            literals
+        };
+        Ok(Self {
+            source: source.to_string(),
+            literals,
+            compiled: Mutex::default(),
+        })
+    }
+
+    /// Words that every matching file must contain, used to skip files without parsing them.
+    pub fn could_match(&self, text: &str) -> bool {
+        self.literals.iter().all(|literal| text.contains(literal))
+    }
+
+    pub fn find_matches(&self, language: &Arc<Language>, text: &str) -> Vec<Range<usize>> {
+        let Some(pattern) = self.compiled_for(language) else {
+            return Vec::new();
+        };
+        let Some(tree) = parse(language, text) else {
+            return Vec::new();
+        };
+        pattern
+            .matches(tree.root_node(), text)
+            .into_iter()
+            .map(|mat| mat.range)
+            .collect()
+    }
+
+    /// Renders `template` for `text`, which must be exactly a previous match of this pattern
+    /// in a buffer of the given `language`.
+    pub fn replacement_for(
+        &self,
+        language: &Arc<Language>,
+        text: &str,
+        template: &str,
+    ) -> Option<String> {
+        let pattern = self.compiled_for(language)?;
+        let (tree, padded) = parse_fragment(language, text)?;
+        let mat = pattern
+            .matches(tree.root_node(), &padded)
+            .into_iter()
+            .find(|mat| mat.range == (0..text.len()))?;
+        Some(render_template(template, &padded, &mat.captures))
+    }
+
+    fn compiled_for(&self, language: &Arc<Language>) -> Option<Arc<CompiledPattern>> {
+        let name = language.name();
+        if let Some(compiled) = self.compiled.lock().get(&name) {
+            return compiled.clone();
+        }
+        let compiled = CompiledPattern::new(&self.source, language).map(Arc::new);
+        self.compiled.lock().insert(name, compiled.clone());
+        compiled
+    }
+}
+
+/// Checks that the brackets of `source` are balanced outside of double-quoted strings, which
+/// both code and tree-sitter queries require.
+fn check_delimiters(source: &str) -> Result<()> {
+    let mut open = Vec::new();
+    let mut chars = source.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => {
+                        chars.next();
+                    }
+                    Some(_) => {}
+                    None => bail!("unterminated string in structural pattern"),
+                }
+            },
+            '(' | '[' | '{' => open.push(c),
+            ')' | ']' | '}' => {
+                let expected = match open.pop() {
+                    Some('(') => ')',
+                    Some('[') => ']',
+                    Some(_) => '}',
+                    None => bail!("unmatched `{c}` in structural pattern"),
+                };
+                if c != expected {
+                    bail!("expected `{expected}` but found `{c}` in structural pattern");
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(c) = open.pop() {
+        bail!("unclosed `{c}` in structural pattern");
+    }
+    Ok(())
+}
+
+/// Checks whether `source` is a pattern the user is likely to have written as a tree-sitter
+/// query rather than as code.
+fn is_raw_query(source: &str) -> bool {
+    source.trim_start().starts_with('(') && !METAVARIABLE.is_match(source)
+}
+
+impl CompiledPattern {
+    fn new(source: &str, language: &Arc<Language>) -> Option<Self> {
+        let grammar = language.grammar()?;
+        if is_raw_query(source)
+            && let Ok(query) = Query::new(&grammar.ts_language, source)
+        {
+            return Some(Self::Query(query));
+        }
+
+        let mut placeholders = HashMap::default();
+        let code = METAVARIABLE.replace_all(source, |captures: &regex::Captures| {
+            let placeholder = format!("{PLACEHOLDER_PREFIX}{}", &captures[1]);
+            placeholders.insert(placeholder.clone(), captures[1].to_string());
+            placeholder
+        });
+        let (tree, padded) = parse_fragment(language, code.trim())?;
+        let node = nodes_spanning(tree.root_node(), 0..code.trim().len())
+            .pop()
+            .filter(|node| !node.has_error())?;
+        Some(Self::Code(PatternNode::new(node, &padded, &placeholders)))
+    }
+
+    fn matches(&self, root: Node, text: &str) -> Vec<StructuralMatch> {
+        match self {
+            Self::Code(pattern) => {
+                let mut matches = Vec::new();
+                let mut cursor = root.walk();
+                'outer: loop {
+                    let node = cursor.node();
+                    let mut captures = HashMap::default();
+                    if node.is_named() && pattern.matches(node, text, &mut captures) {
+                        matches.push(StructuralMatch {
+                            range: node.byte_range(),
+                            captures,
+                        });
+                    } else if cursor.goto_first_child() {
+                        continue;
+                    }
+                    while !cursor.goto_next_sibling() {
+                        if !cursor.goto_parent() {
+                            break 'outer;
+                        }
+                    }
+                }
+                matches
+            }
+            Self::Query(query) => with_query_cursor(|cursor| {
+                let names = query.capture_names();
+                let mut matches = Vec::new();
+                let mut query_matches = cursor.matches(query, root, text.as_bytes());
+                while let Some(mat) = query_matches.next() {
+                    let mut captures = HashMap::default();
+                    let mut range: Option<Range<usize>> = None;
+                    let mut match_range = None;
+                    for capture in mat.captures {
+                        let name = names[capture.index as usize];
+                        let capture_range = capture.node.byte_range();
+                        if name == MATCH_CAPTURE {
+                            match_range = Some(capture_range.clone());
+                        }
+                        range = Some(match range {
+                            Some(range) => {
+                                range.start.min(capture_range.start)
+                                    ..range.end.max(capture_range.end)
+                            }
+                            None => capture_range.clone(),
+                        });
+                        captures.insert(name.to_string(), capture_range);
+                    }
+                    if let Some(range) = match_range.or(range) {
+                        matches.push(StructuralMatch { range, captures });
+                    }
+                }
+                matches.sort_by_key(|mat| (mat.range.start, std::cmp::Reverse(mat.range.end)));
+                let mut last_end = 0;
+                matches.retain(|mat| {
+                    let keep = mat.range.start >= last_end;
+                    if keep {
+                        last_end = mat.range.end;
+                    }
+                    keep
+                });
+                matches
+            }),
+        }
+    }
+}
+
+impl PatternNode {
+    fn new(node: Node, text: &str, placeholders: &HashMap<String, String>) -> Self {
+        let node_text = &text[node.byte_range()];
+        if let Some(name) = placeholders.get(node_text) {
+            return Self::Metavariable(name.clone());
+        }
+        let mut cursor = node.walk();
+        let children = node
+            .children(&mut cursor)
+            .filter(|child| !child.is_extra())
+            .collect::<Vec<_>>();
+        if children.is_empty() {
+            Self::Leaf {
+                kind_id: node.kind_id(),
+                text: node_text.to_string(),
+            }
+        } else {
+            Self::Branch {
+                kind_id: node.kind_id(),
+                children: children
+                    .into_iter()
+                    .map(|child| Self::new(child, text, placeholders))
+                    .collect(),
+            }
+        }
+    }
+
+    fn matches(
+        &self,
+        node: Node,
+        text: &str,
+        captures: &mut HashMap<String, Range<usize>>,
+    ) -> bool {
+        match self {
+            Self::Metavariable(name) => {
+                if let Some(captured) = captures.get(name) {
+                    text[captured.clone()] == text[node.byte_range()]
+                } else {
+                    captures.insert(name.clone(), node.byte_range());
+                    true
+                }
+            }
+            Self::Leaf {
+                kind_id,
+                text: expected,
+            } => node.kind_id() == *kind_id && text[node.byte_range()] == **expected,
+            Self::Branch { kind_id, children } => {
+                if node.kind_id() != *kind_id {
+                    return false;
+                }
+                let mut cursor = node.walk();
+                let node_children = node
+                    .children(&mut cursor)
+                    .filter(|child| !child.is_extra())
+                    .collect::<Vec<_>>();
+                node_children.len() == children.len()
+                    && children
+                        .iter()
+                        .zip(node_children)
+                        .all(|(pattern, child)| pattern.matches(child, text, captures))
+            }
+        }
+    }
+}
+
+fn parse(language: &Arc<Language>, text: &str) -> Option<Tree> {
+    let grammar = language.grammar()?;
+    with_parser(|parser| {
+        parser.set_language(&grammar.ts_language).ok()?;
+        parser.parse(text, None)
+    })
+}
+
+/// Parses a code fragment that may not be valid on its own at the top level of a file, such
+/// as an expression in a language that requires statements to be terminated.
+///
+/// Returns the tree along with the text it was parsed from, which starts with `text`.
+fn parse_fragment(language: &Arc<Language>, text: &str) -> Option<(Tree, String)> {
+    let mut fallback = None;
+    for suffix in ["", ";"] {
+        let padded = format!("{text}{suffix}");
+        let tree = parse(language, &padded)?;
+        if !tree.root_node().has_error() {
+            return Some((tree, padded));
+        }
+        fallback.get_or_insert((tree, padded));
+    }
+    fallback
+}
+
+/// Returns the nodes whose range is exactly `range`, from the outermost to the innermost.
+fn nodes_spanning(root: Node, range: Range<usize>) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    let mut node = root;
+    loop {
+        if node.byte_range() == range {
+            nodes.push(node);
+        }
+        let mut cursor = node.walk();
+        let Some(child) = node
+            .children(&mut cursor)
+            .find(|child| child.start_byte() <= range.start && child.end_byte() >= range.end)
+        else {
+            return nodes;
+        };
+        node = child;
+    }
+}
+
+fn render_template(template: &str, text: &str, captures: &HashMap<String, Range<usize>>) -> String {
+    TEMPLATE_VARIABLE
+        .replace_all(template, |variable: &regex::Captures| {
+            let name = &variable[1];
+            if name == "$" {
+                return "$".to_string();
+            }
+            match captures.get(name) {
+                Some(range) => text[range.clone()].to_string(),
+                None => variable[0].to_string(),
+            }
+        })
+        .into_owned()
+}
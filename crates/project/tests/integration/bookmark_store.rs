@@ -0,0 +1,86 @@
+use fs::FakeFs;
+use gpui::TestAppContext;
+use language::Point;
+use project::{Project, bookmark_store::SerializedBookmark};
+use serde_json::json;
+use settings::SettingsStore;
+use std::{collections::BTreeMap, path::Path, sync::Arc};
+use util::path;
+
+pub fn init_test(cx: &mut TestAppContext) {
+    zlog::init_test();
+
+    cx.update(|cx| {
+        let settings_store = SettingsStore::test(cx);
+        cx.set_global(settings_store);
+    });
+}
+
+#[gpui::test]
+async fn test_bookmark_follows_edits_and_survives_reopening(cx: &mut TestAppContext) {
+    init_test(cx);
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(path!("/root"), json!({ "a.txt": "one\ntwo\nthree\n" }))
+        .await;
+    let project = Project::test(fs, [path!("/root").as_ref()], cx).await;
+    let bookmark_store = project.read_with(cx, |project, _| project.bookmark_store());
+    let abs_path: Arc<Path> = Path::new(path!("/root/a.txt")).into();
+    let bookmarks =
+        |cx: &mut TestAppContext| bookmark_store.read_with(cx, |store, cx| store.all_bookmarks(cx));
+    let bookmark_on_row = |row| -> BTreeMap<Arc<Path>, Vec<SerializedBookmark>> {
+        BTreeMap::from_iter([(
+            abs_path.clone(),
+            vec![SerializedBookmark {
+                row,
+                label: Some("two".into()),
+            }],
+        )])
+    };
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/root/a.txt"), cx)
+        })
+        .await
+        .unwrap();
+    bookmark_store.update(cx, |store, cx| {
+        let position = buffer.read(cx).anchor_before(Point::new(1, 2));
+        store.toggle_bookmark(&buffer, position, cx);
+        store.set_bookmark_label(&buffer, position, Some("two".into()), cx);
+    });
+    assert_eq!(bookmarks(cx), bookmark_on_row(1));
+
+    // Lines inserted above the bookmark move it down.
+    buffer.update(cx, |buffer, cx| {
+        buffer.edit([(Point::zero()..Point::zero(), "zero\n")], None, cx)
+    });
+    assert_eq!(bookmarks(cx), bookmark_on_row(2));
+    project
+        .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+        .await
+        .unwrap();
+
+    // Closing the buffer keeps the row it ended up on.
+    let weak_buffer = buffer.downgrade();
+    cx.update(|_| drop(buffer));
+    cx.run_until_parked();
+    assert!(weak_buffer.upgrade().is_none());
+    assert_eq!(bookmarks(cx), bookmark_on_row(2));
+
+    // Reopening the buffer anchors the bookmark again.
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/root/a.txt"), cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+    bookmark_store.read_with(cx, |store, cx| {
+        let bookmark = store.bookmark_at_row(&buffer, 2, cx).unwrap();
+        assert_eq!(bookmark.label.as_deref(), Some("two"));
+    });
+    buffer.update(cx, |buffer, cx| {
+        buffer.edit([(Point::zero()..Point::new(1, 0), "")], None, cx)
+    });
+    assert_eq!(bookmarks(cx), bookmark_on_row(1));
+}
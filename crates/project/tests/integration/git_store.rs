@@ -1012,3 +1012,453 @@ mod git_traversal {
         pretty_assertions::assert_eq!(found_statuses, expected_statuses);
     }
 }
+
+mod repository_operations {
+    use std::{path::Path, sync::Arc};
+
+    use askpass::AskPassDelegate;
+    use fs::FakeFs;
+    use git::{
+        Oid,
+        bisect::{BisectMark, BisectStep},
+        rebase::{RebaseAction, RebaseControl, RebaseProgress},
+        repository::{InitialGraphCommitData, repo_path},
+        submodule::{Submodule, SubmoduleCommand, SubmoduleStatus},
+    };
+    use gpui::{Entity, TestAppContext};
+    use pretty_assertions::assert_eq;
+    use project::{Project, git_store::Repository};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    const DOT_GIT: &str = path!("/root/.git");
+
+    fn init_test(cx: &mut TestAppContext) {
+        zlog::init_test();
+
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+        });
+    }
+
+    async fn init_repository(cx: &mut TestAppContext) -> (Arc<FakeFs>, Entity<Repository>) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(path!("/root"), json!({ ".git": {}, "a.txt": "a" }))
+            .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+        cx.executor().run_until_parked();
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        (fs, repository)
+    }
+
+    fn oid(n: u8) -> Oid {
+        Oid::from_bytes(&[n; 20]).unwrap()
+    }
+
+    /// A linear history of `count` commits, newest first, like `git log` lists them.
+    fn linear_history(count: u8) -> Vec<Arc<InitialGraphCommitData>> {
+        (1..=count)
+            .rev()
+            .map(|n| {
+                Arc::new(InitialGraphCommitData {
+                    sha: oid(n),
+                    parents: (n > 1).then(|| oid(n - 1)).into_iter().collect(),
+                    ref_names: Vec::new(),
+                })
+            })
+            .collect()
+    }
+
+    #[gpui::test]
+    async fn test_interactive_rebase(cx: &mut TestAppContext) {
+        let (fs, repository) = init_repository(cx).await;
+        fs.set_graph_commits(Path::new(DOT_GIT), linear_history(4));
+        cx.executor().run_until_parked();
+
+        let plan = repository
+            .update(cx, |repository, _| repository.rebase_plan(None))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(plan.base, oid(1).to_string());
+        assert_eq!(
+            plan.entries
+                .iter()
+                .map(|entry| (entry.sha.to_string(), entry.action))
+                .collect::<Vec<_>>(),
+            [
+                (oid(2).to_string(), RebaseAction::Pick),
+                (oid(3).to_string(), RebaseAction::Pick),
+                (oid(4).to_string(), RebaseAction::Pick),
+            ]
+        );
+
+        let mut entries = plan.entries;
+        entries[1].action = RebaseAction::Edit;
+        entries[2].action = RebaseAction::Fixup;
+        repository
+            .update(cx, |repository, _| {
+                repository.rebase_interactive(plan.base.to_string(), entries.clone())
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        cx.executor().run_until_parked();
+
+        // The rebase stops at the `edit` step until it is continued.
+        repository.read_with(cx, |repository, _| {
+            assert_eq!(
+                repository.rebase,
+                Some(RebaseProgress {
+                    head_name: None,
+                    onto: oid(1).to_string().into(),
+                    step: 2,
+                    total: 3,
+                    stopped_sha: Some(oid(3).to_string().into()),
+                })
+            );
+        });
+        assert_eq!(
+            fs.with_git_state(Path::new(DOT_GIT), false, |state| state.rebase_todo.clone())
+                .unwrap(),
+            entries
+        );
+
+        repository
+            .update(cx, |repository, _| {
+                repository.rebase_control(RebaseControl::Continue)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        cx.executor().run_until_parked();
+        repository.read_with(cx, |repository, _| assert_eq!(repository.rebase, None));
+
+        let error = repository
+            .update(cx, |repository, _| {
+                repository.rebase_control(RebaseControl::Abort)
+            })
+            .await
+            .unwrap();
+        assert!(error.is_err(), "no rebase should be in progress");
+    }
+
+    #[gpui::test]
+    async fn test_cherry_pick_and_revert(cx: &mut TestAppContext) {
+        let (fs, repository) = init_repository(cx).await;
+
+        repository
+            .update(cx, |repository, _| {
+                repository.cherry_pick(vec![oid(1).to_string(), oid(2).to_string()], None)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        repository
+            .update(cx, |repository, _| {
+                repository.revert(vec![oid(3).to_string()], Some(1))
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(
+            repository
+                .update(cx, |repository, _| repository.revert(Vec::new(), None))
+                .await
+                .unwrap()
+                .is_err()
+        );
+
+        let (cherry_picks, reverts) = fs
+            .with_git_state(Path::new(DOT_GIT), false, |state| {
+                (state.cherry_picks.clone(), state.reverts.clone())
+            })
+            .unwrap();
+        assert_eq!(cherry_picks, [oid(1).to_string(), oid(2).to_string()]);
+        assert_eq!(reverts, [oid(3).to_string()]);
+    }
+
+    #[gpui::test]
+    async fn test_tags(cx: &mut TestAppContext) {
+        let (_fs, repository) = init_repository(cx).await;
+
+        repository
+            .update(cx, |repository, _| {
+                repository.create_tag("v1.0".into(), "HEAD".into(), None, false)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        repository
+            .update(cx, |repository, _| {
+                repository.create_tag(
+                    "v0.9".into(),
+                    oid(1).to_string(),
+                    Some("Beta release\n\nWith notes".into()),
+                    true,
+                )
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(
+            repository
+                .update(cx, |repository, _| {
+                    repository.create_tag("v1.0".into(), "HEAD".into(), None, false)
+                })
+                .await
+                .unwrap()
+                .is_err(),
+            "tag names are unique"
+        );
+
+        let tags = repository
+            .update(cx, |repository, _| repository.tags())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            tags.iter()
+                .map(|tag| (
+                    tag.name.to_string(),
+                    tag.sha.to_string(),
+                    tag.message.as_ref().map(ToString::to_string),
+                    tag.is_annotated(),
+                ))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    "v0.9".to_string(),
+                    oid(1).to_string(),
+                    Some("Beta release".to_string()),
+                    true
+                ),
+                ("v1.0".to_string(), "abc".to_string(), None, false),
+            ]
+        );
+
+        repository
+            .update(cx, |repository, _| repository.delete_tag("v0.9".into()))
+            .await
+            .unwrap()
+            .unwrap();
+        let tags = repository
+            .update(cx, |repository, _| repository.tags())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].name.as_ref(), "v1.0");
+    }
+
+    #[gpui::test]
+    async fn test_submodules(cx: &mut TestAppContext) {
+        let (fs, repository) = init_repository(cx).await;
+        let submodules = vec![
+            Submodule {
+                path: repo_path("libs/a"),
+                sha: oid(1).to_string().into(),
+                status: SubmoduleStatus::Uninitialized,
+            },
+            Submodule {
+                path: repo_path("libs/b"),
+                sha: oid(2).to_string().into(),
+                status: SubmoduleStatus::Current,
+            },
+        ];
+        fs.with_git_state(Path::new(DOT_GIT), true, |state| {
+            state.submodules = submodules.clone();
+        })
+        .unwrap();
+        cx.executor().run_until_parked();
+
+        // Rescans don't list the submodules again while nothing they are recorded in changed.
+        repository.read_with(cx, |repository, _| {
+            assert!(repository.submodules.is_empty());
+        });
+
+        fs.insert_file(path!("/root/.gitmodules"), b"[submodule \"a\"]".to_vec())
+            .await;
+        cx.executor().run_until_parked();
+        repository.read_with(cx, |repository, _| {
+            assert_eq!(&*repository.submodules, submodules.as_slice());
+        });
+
+        // Moving HEAD can change the commits recorded for the submodules.
+        fs.with_git_state(Path::new(DOT_GIT), true, |state| {
+            state.submodules[1].status = SubmoduleStatus::OutOfSync;
+            state.refs.insert("HEAD".into(), "def".into());
+        })
+        .unwrap();
+        cx.executor().run_until_parked();
+        repository.read_with(cx, |repository, _| {
+            assert_eq!(
+                repository
+                    .submodule_for_path(&repo_path("libs/b"))
+                    .map(|submodule| submodule.status),
+                Some(SubmoduleStatus::OutOfSync)
+            );
+        });
+
+        let askpass = AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {});
+        repository
+            .update(cx, |repository, cx| {
+                repository.submodule_command(
+                    SubmoduleCommand::Update,
+                    vec![repo_path("libs/b")],
+                    askpass,
+                    cx,
+                )
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        cx.executor().run_until_parked();
+        repository.read_with(cx, |repository, _| {
+            assert_eq!(
+                repository
+                    .submodules
+                    .iter()
+                    .map(|submodule| submodule.status)
+                    .collect::<Vec<_>>(),
+                [SubmoduleStatus::Uninitialized, SubmoduleStatus::Current]
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_bisect(cx: &mut TestAppContext) {
+        let (fs, repository) = init_repository(cx).await;
+        fs.set_graph_commits(Path::new(DOT_GIT), linear_history(5));
+        cx.executor().run_until_parked();
+
+        let step = repository
+            .update(cx, |repository, _| {
+                repository.bisect_start(Some(oid(5).to_string()), vec![oid(1).to_string()])
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            step,
+            Some(BisectStep::Testing {
+                sha: oid(3).to_string().into(),
+                remaining: 2,
+                steps: 2,
+            })
+        );
+
+        let step = repository
+            .update(cx, |repository, _| {
+                repository.bisect_mark(BisectMark::Bad, None)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            step,
+            Some(BisectStep::Testing {
+                sha: oid(2).to_string().into(),
+                remaining: 0,
+                steps: 0,
+            })
+        );
+
+        let step = repository
+            .update(cx, |repository, _| {
+                repository.bisect_mark(BisectMark::Good, None)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            step,
+            Some(BisectStep::Found {
+                sha: oid(3).to_string().into()
+            })
+        );
+        cx.executor().run_until_parked();
+        repository.read_with(cx, |repository, _| {
+            let bisect = repository.bisect.as_ref().unwrap();
+            assert_eq!(bisect.first_bad, Some(oid(3).to_string().into()));
+            assert!(bisect.is_good(&oid(2).to_string()));
+        });
+
+        repository
+            .update(cx, |repository, _| repository.bisect_reset())
+            .await
+            .unwrap()
+            .unwrap();
+        cx.executor().run_until_parked();
+        repository.read_with(cx, |repository, _| assert_eq!(repository.bisect, None));
+    }
+
+    #[gpui::test]
+    async fn test_reflog(cx: &mut TestAppContext) {
+        let (fs, repository) = init_repository(cx).await;
+        fs.insert_branches(Path::new(DOT_GIT), &["main"]);
+        fs.set_branch_name(Path::new(DOT_GIT), Some("main"));
+        cx.executor().run_until_parked();
+
+        for n in [1, 2] {
+            repository
+                .update(cx, |repository, _| {
+                    repository.checkout_commit(oid(n).to_string())
+                })
+                .await
+                .unwrap()
+                .unwrap();
+        }
+        cx.executor().run_until_parked();
+
+        let reflog = repository
+            .update(cx, |repository, _| repository.reflog("HEAD".into()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            reflog
+                .iter()
+                .map(|entry| (
+                    entry.selector.to_string(),
+                    entry.sha.to_string(),
+                    entry.message.to_string()
+                ))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    "HEAD@{0}".to_string(),
+                    oid(2).to_string(),
+                    format!("checkout: moving from {} to {}", oid(1), oid(2)),
+                ),
+                (
+                    "HEAD@{1}".to_string(),
+                    oid(1).to_string(),
+                    format!("checkout: moving from main to {}", oid(1)),
+                ),
+            ]
+        );
+        repository.read_with(cx, |repository, _| {
+            assert_eq!(repository.branch, None);
+            assert_eq!(
+                repository
+                    .head_commit
+                    .as_ref()
+                    .map(|commit| commit.sha.to_string()),
+                Some(oid(2).to_string())
+            );
+        });
+
+        assert!(
+            repository
+                .update(cx, |repository, _| repository.reflog("missing".into()))
+                .await
+                .unwrap()
+                .is_err()
+        );
+    }
+}
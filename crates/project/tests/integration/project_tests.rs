@@ -1,5 +1,6 @@
 #![allow(clippy::format_collect)]
 
+mod bookmark_store;
 mod color_extractor;
 mod context_server_store;
 mod debugger;
@@ -12183,6 +12184,68 @@ async fn test_read_only_files_empty_setting(cx: &mut gpui::TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_large_file_mode(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings(cx, |settings| {
+                settings.project.worktree.large_file_threshold_mb = Some(1);
+            });
+        });
+    });
+
+    // The second line ends exactly at the boundary of the first chunk, so its CRLF line
+    // ending is split across two chunks.
+    let mut content = "first\r\n".to_string();
+    content.push_str(&"a".repeat(worktree::LARGE_FILE_CHUNK_BYTES - content.len() - 1));
+    content.push_str("\r\n");
+    while content.len() < 2 * worktree::LARGE_FILE_CHUNK_BYTES + 1000 {
+        content.push_str(&"b".repeat(99));
+        content.push_str("\r\n");
+    }
+
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(path!("/root"), json!({ "small.log": "small\n" }))
+        .await;
+    fs.insert_file(path!("/root/large.log"), content.clone().into_bytes())
+        .await;
+
+    let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+
+    let small_buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/root/small.log"), cx)
+        })
+        .await
+        .unwrap();
+    small_buffer.read_with(cx, |buffer, _| {
+        assert!(!buffer.is_large_file());
+        assert!(!buffer.read_only());
+    });
+
+    let large_buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/root/large.log"), cx)
+        })
+        .await
+        .unwrap();
+    large_buffer.read_with(cx, |buffer, _| {
+        assert!(buffer.is_large_file());
+        assert!(buffer.read_only(), "Large files should be read-only");
+    });
+
+    cx.run_until_parked();
+    large_buffer.update(cx, |buffer, cx| {
+        assert_eq!(buffer.line_ending(), LineEnding::Windows);
+        assert!(buffer.text() == content.replace("\r\n", "\n"));
+        assert!(!buffer.is_dirty());
+        assert!(buffer.language().is_none());
+        assert_eq!(buffer.undo(cx), None, "Loading shouldn't be undoable");
+    });
+}
+
 #[gpui::test]
 async fn test_read_only_files_with_lock_files(cx: &mut gpui::TestAppContext) {
     init_test(cx);
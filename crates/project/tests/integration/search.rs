@@ -154,3 +154,97 @@ async fn test_multiline_regex(cx: &mut gpui::TestAppContext) {
     let results = search_query.search(&snapshot, None).await;
     assert_eq!(results, vec![0..6, 12..18]);
 }
+
+#[gpui::test]
+async fn test_structural_search(cx: &mut gpui::TestAppContext) {
+    let search_query = SearchQuery::structural(
+        "$X.unwrap()",
+        false,
+        Default::default(),
+        Default::default(),
+        false,
+        None,
+    )
+    .expect("Should be able to create a structural SearchQuery")
+    .with_replacement("$X?".to_string());
+
+    use language::{Buffer, rust_lang};
+    let text = Rope::from(
+        "fn main() {\n    let a = foo(1).unwrap();\n    let b = bar.unwrap_or(2);\n    baz . unwrap ( );\n}\n",
+    );
+    let snapshot = cx
+        .update(|app| Buffer::build_snapshot(text, Some(rust_lang()), None, app))
+        .await;
+
+    let results = search_query.search(&snapshot, None).await;
+    let matched_text = results
+        .into_iter()
+        .map(|range| snapshot.text_for_range(range).collect::<String>())
+        .collect::<Vec<_>>();
+    assert_eq!(matched_text, ["foo(1).unwrap()", "baz . unwrap ( )"]);
+    assert_eq!(
+        search_query
+            .replacement_for(&matched_text[0], snapshot.language())
+            .as_deref(),
+        Some("foo(1)?")
+    );
+    assert_eq!(
+        search_query
+            .replacement_for(&matched_text[1], snapshot.language())
+            .as_deref(),
+        Some("baz?")
+    );
+
+    // Text the pattern doesn't match, or a match without a known language, is never
+    // replaced by the raw template.
+    assert_eq!(
+        search_query.replacement_for("bar.unwrap_or(2)", snapshot.language()),
+        None
+    );
+    assert_eq!(search_query.replacement_for(&matched_text[0], None), None);
+}
+
+#[test]
+fn test_invalid_structural_pattern() {
+    let structural = |pattern: &str| {
+        SearchQuery::structural(
+            pattern,
+            false,
+            Default::default(),
+            Default::default(),
+            false,
+            None,
+        )
+    };
+
+    for (pattern, error) in [
+        ("  ", "structural pattern is empty"),
+        ("$X.unwrap(", "unclosed `(` in structural pattern"),
+        ("foo(1))", "unmatched `)` in structural pattern"),
+        (
+            "vec![$X)",
+            "expected `]` but found `)` in structural pattern",
+        ),
+        (
+            "(call_expression function: (identifier) @name",
+            "unclosed `(` in structural pattern",
+        ),
+        (r#"log("$X)"#, "unterminated string in structural pattern"),
+    ] {
+        match structural(pattern) {
+            Ok(_) => panic!("Invalid structural pattern {pattern:?} should not be accepted"),
+            Err(e) => assert_eq!(e.to_string(), error, "for pattern {pattern:?}"),
+        }
+    }
+
+    for valid_pattern in [
+        "$X.unwrap()",
+        r#"log(")", $X)"#,
+        r#"format!("\"{}", $X)"#,
+        "(call_expression function: (identifier) @match)",
+    ] {
+        if let Err(e) = structural(valid_pattern) {
+            panic!("Valid structural pattern {valid_pattern:?} should be accepted, but got: {e}");
+        }
+    }
+}
@@ -15,7 +15,11 @@ use editor::{
 };
 use file_icons::FileIcons;
 use git;
-use git::status::GitSummary;
+use git::{
+    repository::RepoPath,
+    status::GitSummary,
+    submodule::{Submodule, SubmoduleStatus},
+};
 use git_ui;
 use git_ui::file_diff_view::FileDiffView;
 use gpui::{
@@ -250,6 +254,7 @@ struct EntryDetails {
     filename_text_color: Color,
     diagnostic_severity: Option<DiagnosticSeverity>,
     git_status: GitSummary,
+    submodule: Option<Submodule>,
     is_private: bool,
     worktree_id: WorktreeId,
     canonical_path: Option<Arc<Path>>,
@@ -5044,6 +5049,7 @@ impl ProjectPanel {
 
         let filename_text_color = details.filename_text_color;
         let diagnostic_severity = details.diagnostic_severity;
+        let submodule = details.submodule.clone();
         let item_colors = get_item_color(is_sticky, cx);
 
         let canonical_path = details
@@ -5498,6 +5504,33 @@ impl ProjectPanel {
                                 .into_any_element(),
                         )
                     })
+                    .when_some(submodule, |this, submodule| {
+                        let status = submodule.status.label();
+                        this.end_slot::<AnyElement>(
+                            h_flex()
+                                .id("submodule_status")
+                                .pr_3()
+                                .gap_0p5()
+                                .tooltip(move |_window, cx| {
+                                    Tooltip::with_meta("Submodule", None, status, cx)
+                                })
+                                .child(
+                                    Icon::new(IconName::Box)
+                                        .size(IconSize::XSmall)
+                                        .color(Color::Muted),
+                                )
+                                .child(
+                                    Label::new(submodule.short_sha().to_string())
+                                        .size(LabelSize::XSmall)
+                                        .color(if submodule.status == SubmoduleStatus::Current {
+                                            Color::Muted
+                                        } else {
+                                            Color::Modified
+                                        }),
+                                )
+                                .into_any_element(),
+                        )
+                    })
                     .child(if let Some(icon) = &icon {
                         if let Some((_, decoration_color)) =
                             entry_diagnostic_aware_icon_decoration_and_color(diagnostic_severity)
@@ -5915,6 +5948,12 @@ impl ProjectPanel {
             .as_ref()
             .is_some_and(|e| e.is_cut() && e.items().contains(&selection));
 
+        let submodule = if entry.is_dir() && ProjectPanelSettings::get_global(cx).git_status {
+            self.submodule_for_entry(worktree_id, &entry.path, cx)
+        } else {
+            None
+        };
+
         EntryDetails {
             filename,
             icon,
@@ -5932,12 +5971,40 @@ impl ProjectPanel {
             filename_text_color,
             diagnostic_severity,
             git_status,
+            submodule,
             is_private: entry.is_private,
             worktree_id,
             canonical_path: entry.canonical_path.clone(),
         }
     }
 
+    fn submodule_for_entry(
+        &self,
+        worktree_id: WorktreeId,
+        path: &RelPath,
+        cx: &App,
+    ) -> Option<Submodule> {
+        let project = self.project.read(cx);
+        let path_style = project.path_style(cx);
+        let abs_path = project
+            .worktree_for_id(worktree_id, cx)?
+            .read(cx)
+            .absolutize(path);
+        project
+            .git_store()
+            .read(cx)
+            .repositories()
+            .values()
+            .find_map(|repository| {
+                let repository = repository.read(cx);
+                let relative_path = abs_path
+                    .strip_prefix(&repository.work_directory_abs_path)
+                    .ok()?;
+                let repo_path = RepoPath::from_std_path(relative_path, path_style).ok()?;
+                repository.submodule_for_path(&repo_path).cloned()
+            })
+    }
+
     fn dispatch_context(&self, window: &Window, cx: &Context<Self>) -> KeyContext {
         let mut dispatch_context = KeyContext::new_with_defaults();
         dispatch_context.add("ProjectPanel");
@@ -364,6 +364,26 @@ messages!(
     (KillKernel, Background),
     (GetRemoteProfilingData, Background),
     (GetRemoteProfilingDataResponse, Background),
+    (GitRebasePlan, Background),
+    (GitRebasePlanResponse, Background),
+    (GitRebaseInteractive, Background),
+    (GitRebaseControl, Background),
+    (GitCherryPick, Background),
+    (GitRevert, Background),
+    (GitCreateTag, Background),
+    (GitGetTags, Background),
+    (GitTagsResponse, Background),
+    (GitDeleteTag, Background),
+    (GitSubmoduleCommand, Background),
+    (GitBisectStart, Background),
+    (GitBisectStepResponse, Background),
+    (GitBisectMark, Background),
+    (GitBisectReset, Background),
+    (GitGetReflog, Background),
+    (GitReflogResponse, Background),
+    (GitCheckoutCommit, Background),
+    (GitBlameAtRevision, Background),
+    (GitBlameAtRevisionResponse, Background),
 );
 
 request_messages!(
@@ -563,6 +583,21 @@ request_messages!(
     (SpawnKernel, SpawnKernelResponse),
     (KillKernel, Ack),
     (GetRemoteProfilingData, GetRemoteProfilingDataResponse),
+    (GitRebasePlan, GitRebasePlanResponse),
+    (GitRebaseInteractive, Ack),
+    (GitRebaseControl, Ack),
+    (GitCherryPick, Ack),
+    (GitRevert, Ack),
+    (GitCreateTag, Ack),
+    (GitGetTags, GitTagsResponse),
+    (GitDeleteTag, Ack),
+    (GitSubmoduleCommand, RemoteMessageResponse),
+    (GitBisectStart, GitBisectStepResponse),
+    (GitBisectMark, GitBisectStepResponse),
+    (GitBisectReset, Ack),
+    (GitGetReflog, GitReflogResponse),
+    (GitCheckoutCommit, Ack),
+    (GitBlameAtRevision, GitBlameAtRevisionResponse),
 );
 
 lsp_messages!(
@@ -752,7 +787,22 @@ entity_messages!(
     FindSearchCandidatesChunk,
     FindSearchCandidatesCancelled,
     DownloadFileByPath,
-    GetRemoteProfilingData
+    GetRemoteProfilingData,
+    GitRebasePlan,
+    GitRebaseInteractive,
+    GitRebaseControl,
+    GitCherryPick,
+    GitRevert,
+    GitCreateTag,
+    GitGetTags,
+    GitDeleteTag,
+    GitSubmoduleCommand,
+    GitBisectStart,
+    GitBisectMark,
+    GitBisectReset,
+    GitGetReflog,
+    GitCheckoutCommit,
+    GitBlameAtRevision
 );
 
 entity_messages!(
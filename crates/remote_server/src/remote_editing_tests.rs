@@ -1917,6 +1917,175 @@ async fn test_remote_git_branches(cx: &mut TestAppContext, server_cx: &mut TestA
     assert_eq!(server_branch.name(), "totally-new-branch");
 }
 
+#[gpui::test]
+async fn test_remote_git_history_operations(
+    cx: &mut TestAppContext,
+    server_cx: &mut TestAppContext,
+) {
+    let fs = FakeFs::new(server_cx.executor());
+    fs.insert_tree(
+        path!("/code"),
+        json!({
+            "project1": {
+                ".git": {},
+                "README.md": "# project 1",
+            },
+        }),
+    )
+    .await;
+    let dot_git = Path::new(path!("/code/project1/.git"));
+    let oid = |n: u8| git::Oid::from_bytes(&[n; 20]).unwrap();
+    fs.set_graph_commits(
+        dot_git,
+        (1..=3)
+            .rev()
+            .map(|n| {
+                Arc::new(git::repository::InitialGraphCommitData {
+                    sha: oid(n),
+                    parents: (n > 1).then(|| oid(n - 1)).into_iter().collect(),
+                    ref_names: Vec::new(),
+                })
+            })
+            .collect(),
+    );
+    fs.with_git_state(dot_git, true, |state| {
+        state.submodules = vec![git::submodule::Submodule {
+            path: git::repository::repo_path("vendor/lib"),
+            sha: oid(1).to_string().into(),
+            status: git::submodule::SubmoduleStatus::Uninitialized,
+        }];
+    })
+    .unwrap();
+
+    let (project, _headless_project) = init_test(&fs, cx, server_cx).await;
+    project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree(path!("/code/project1"), true, cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+
+    let repository = project.update(cx, |project, cx| project.active_repository(cx).unwrap());
+    repository.read_with(cx, |repository, _| {
+        assert_eq!(
+            repository
+                .submodules
+                .iter()
+                .map(|submodule| (submodule.path.clone(), submodule.status))
+                .collect::<Vec<_>>(),
+            [(
+                git::repository::repo_path("vendor/lib"),
+                git::submodule::SubmoduleStatus::Uninitialized
+            )]
+        );
+    });
+
+    // Interactive rebase: the plan comes from the server and the progress is synced back.
+    let plan = repository
+        .update(cx, |repository, _| repository.rebase_plan(None))
+        .await
+        .unwrap()
+        .unwrap();
+    let mut entries = plan.entries;
+    assert_eq!(entries.len(), 2);
+    entries[0].action = git::rebase::RebaseAction::Edit;
+    repository
+        .update(cx, |repository, _| {
+            repository.rebase_interactive(plan.base.to_string(), entries)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    cx.run_until_parked();
+    repository.read_with(cx, |repository, _| {
+        let rebase = repository.rebase.as_ref().unwrap();
+        assert_eq!(rebase.stopped_sha, Some(oid(2).to_string().into()));
+        assert_eq!((rebase.step, rebase.total), (1, 2));
+    });
+    repository
+        .update(cx, |repository, _| {
+            repository.rebase_control(git::rebase::RebaseControl::Abort)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    cx.run_until_parked();
+    repository.read_with(cx, |repository, _| assert_eq!(repository.rebase, None));
+
+    // Cherry-pick and tags.
+    repository
+        .update(cx, |repository, _| {
+            repository.cherry_pick(vec![oid(2).to_string()], None)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        fs.with_git_state(dot_git, false, |state| state.cherry_picks.clone())
+            .unwrap(),
+        [oid(2).to_string()]
+    );
+    repository
+        .update(cx, |repository, _| {
+            repository.create_tag("v1".into(), oid(3).to_string(), Some("First".into()), false)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    let tags = repository
+        .update(cx, |repository, _| repository.tags())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0].sha, oid(3).to_string());
+    assert_eq!(tags[0].message.as_deref(), Some("First"));
+
+    // Bisect.
+    let step = repository
+        .update(cx, |repository, _| {
+            repository.bisect_start(Some(oid(3).to_string()), vec![oid(1).to_string()])
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        step,
+        Some(git::bisect::BisectStep::Testing {
+            sha: oid(2).to_string().into(),
+            remaining: 0,
+            steps: 0,
+        })
+    );
+    cx.run_until_parked();
+    repository.read_with(cx, |repository, _| {
+        assert_eq!(
+            repository
+                .bisect
+                .as_ref()
+                .and_then(|bisect| bisect.bad.clone()),
+            Some(oid(3).to_string().into())
+        );
+    });
+
+    // Checking out a commit shows up in the reflog.
+    repository
+        .update(cx, |repository, _| {
+            repository.checkout_commit(oid(1).to_string())
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    let reflog = repository
+        .update(cx, |repository, _| repository.reflog("HEAD".into()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(reflog[0].sha, oid(1).to_string());
+    assert_eq!(reflog[0].selector.as_ref(), "HEAD@{0}");
+}
+
 #[gpui::test]
 async fn test_remote_agent_fs_tool_calls(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
     let fs = FakeFs::new(server_cx.executor());
@@ -1119,6 +1119,19 @@ impl BufferSearchBar {
         self.query_editor.read(cx).text(cx)
     }
 
+    /// Fills the history of an unused search bar with queries from elsewhere, oldest first,
+    /// so that they can be recalled with the history navigation actions.
+    pub fn seed_search_history(&mut self, queries: impl IntoIterator<Item = String>) {
+        if self.search_history.len() > 0 {
+            return;
+        }
+        for query in queries {
+            self.search_history
+                .add(&mut self.search_history_cursor, query);
+        }
+        self.search_history_cursor.reset();
+    }
+
     pub fn replacement(&self, cx: &mut App) -> String {
         self.replacement_editor.read(cx).text(cx)
     }
@@ -1123,7 +1123,8 @@ pub struct ActionSequence(pub Vec<Box<dyn Action>>);
 register_action!(ActionSequence);
 
 impl ActionSequence {
-    fn build_sequence(
+    /// Builds a sequence from a JSON array of keymap actions.
+    pub fn build_sequence(
         value: Value,
         cx: &App,
     ) -> std::result::Result<Box<dyn Action>, ActionBuildError> {
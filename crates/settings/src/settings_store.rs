@@ -149,6 +149,7 @@ pub struct SettingsStore {
     global_settings: Option<Box<SettingsContent>>,
 
     extension_settings: Option<Box<SettingsContent>>,
+    vimrc_settings: Option<Box<SettingsContent>>,
     server_settings: Option<Box<SettingsContent>>,
 
     language_semantic_token_rules: HashMap<SharedString, SemanticTokenRules>,
@@ -306,6 +307,7 @@ impl SettingsStore {
             server_settings: None,
             user_settings: None,
             extension_settings: None,
+            vimrc_settings: None,
             language_semantic_token_rules: HashMap::default(),
 
             merged_settings: default_settings,
@@ -1005,6 +1007,13 @@ impl SettingsStore {
         Ok(())
     }
 
+    /// Sets the settings translated from the user's vimrc file. They take precedence over
+    /// the global settings, and are overridden by the user settings file.
+    pub fn set_vimrc_settings(&mut self, content: Option<SettingsContent>, cx: &mut App) {
+        self.vimrc_settings = content.map(Box::new);
+        self.recompute_values(None, cx);
+    }
+
     /// Add or remove a set of local settings via a JSON string.
     pub fn clear_local_settings(&mut self, root_id: WorktreeId, cx: &mut App) -> Result<()> {
         self.local_settings
@@ -1177,6 +1186,7 @@ impl SettingsStore {
             let mut merged = self.default_settings.as_ref().clone();
             merged.merge_from_option(self.extension_settings.as_deref());
             merged.merge_from_option(self.global_settings.as_deref());
+            merged.merge_from_option(self.vimrc_settings.as_deref());
             if let Some(user_settings) = self.user_settings.as_ref() {
                 merged.merge_from(&user_settings.content);
                 merged.merge_from_option(user_settings.for_release_channel());
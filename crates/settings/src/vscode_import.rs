@@ -177,6 +177,7 @@ impl VsCodeSettings {
             audio: None,
             auto_update: None,
             base_keymap: Some(BaseKeymapContent::VSCode),
+            bookmarks: None,
             calls: None,
             collaboration_panel: None,
             debugger: None,
@@ -195,6 +196,7 @@ impl VsCodeSettings {
             journal: None,
             language_models: None,
             line_indicator_format: None,
+            local_history: None,
             log: None,
             message_editor: None,
             node: self.node_binary_settings(),
@@ -208,6 +210,7 @@ impl VsCodeSettings {
             repl: None,
             server_url: None,
             session: None,
+            spell_check: None,
             status_bar: self.status_bar_settings_content(),
             tab_bar: self.tab_bar_settings_content(),
             tabs: self.item_settings_content(),
@@ -506,6 +509,7 @@ impl VsCodeSettings {
             dap: Default::default(),
             context_servers: self.context_servers(),
             context_server_timeout: None,
+            language_model_credential_profiles: Default::default(),
             load_direnv: None,
             slash_commands: None,
             git_hosting_providers: None,
@@ -1043,6 +1047,7 @@ impl VsCodeSettings {
                         .collect::<Vec<_>>()
                 })
                 .filter(|r| !r.is_empty()),
+            large_file_threshold_mb: None,
         }
     }
 }
@@ -1,4 +1,4 @@
-use collections::HashMap;
+use collections::{BTreeMap, HashMap};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings_macros::{MergeFrom, with_fallible_options};
@@ -10,11 +10,13 @@ use std::sync::Arc;
 #[derive(Default, Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema, MergeFrom)]
 pub struct AllLanguageModelSettingsContent {
     pub anthropic: Option<AnthropicSettingsContent>,
+    pub azure_openai: Option<AzureOpenAiSettingsContent>,
     pub bedrock: Option<AmazonBedrockSettingsContent>,
     pub deepseek: Option<DeepseekSettingsContent>,
     pub google: Option<GoogleSettingsContent>,
     pub lmstudio: Option<LmStudioSettingsContent>,
     pub mistral: Option<MistralSettingsContent>,
+    pub mock: Option<MockSettingsContent>,
     pub ollama: Option<OllamaSettingsContent>,
     pub open_router: Option<OpenRouterSettingsContent>,
     pub openai: Option<OpenAiSettingsContent>,
@@ -160,6 +162,46 @@ pub struct LmStudioAvailableModel {
     pub supports_images: bool,
 }
 
+#[with_fallible_options]
+#[derive(Default, Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema, MergeFrom)]
+pub struct AzureOpenAiSettingsContent {
+    /// The Azure OpenAI resource endpoint, e.g. `https://my-resource.openai.azure.com`.
+    pub endpoint: Option<String>,
+    /// The `api-version` query parameter sent with every request.
+    ///
+    /// Default: "2024-10-21"
+    pub api_version: Option<String>,
+    pub available_models: Option<Vec<AzureOpenAiAvailableModel>>,
+    /// Extra HTTP headers to send with every request, e.g. for an API gateway.
+    pub headers: Option<BTreeMap<String, String>>,
+    /// A command that prints an Entra ID access token, used instead of the API key.
+    pub auth_command: Option<LanguageModelAuthCommand>,
+}
+
+#[with_fallible_options]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema, MergeFrom)]
+pub struct AzureOpenAiAvailableModel {
+    /// The name of the model deployment in your Azure OpenAI resource.
+    pub deployment: String,
+    pub display_name: Option<String>,
+    pub max_tokens: u64,
+    pub max_output_tokens: Option<u64>,
+    #[serde(default = "default_true")]
+    pub supports_tools: bool,
+    #[serde(default)]
+    pub supports_images: bool,
+    #[serde(default)]
+    pub parallel_tool_calls: bool,
+}
+
+#[with_fallible_options]
+#[derive(Default, Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema, MergeFrom)]
+pub struct MockSettingsContent {
+    /// Path to a JSON or YAML fixture file with the scripted responses the mock
+    /// provider replays. The provider has no models until this is set.
+    pub fixture_path: Option<String>,
+}
+
 #[with_fallible_options]
 #[derive(Default, Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema, MergeFrom)]
 pub struct DeepseekSettingsContent {
@@ -232,6 +274,37 @@ pub enum OpenAiReasoningEffort {
 pub struct OpenAiCompatibleSettingsContent {
     pub api_url: String,
     pub available_models: Vec<OpenAiCompatibleAvailableModel>,
+    /// Names of additional credential profiles for this provider. Each profile has its own
+    /// API key in the system keychain, alongside the provider's "default" key.
+    #[serde(default)]
+    pub credential_profiles: Vec<String>,
+    /// The credential profile to use when neither the project nor the thread selects one.
+    ///
+    /// Default: "default"
+    pub default_credential_profile: Option<String>,
+    /// Extra HTTP headers to send with every request, e.g. for an API gateway.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// A command that prints a token to authenticate requests with, used instead of the
+    /// API key.
+    pub auth_command: Option<LanguageModelAuthCommand>,
+}
+
+/// A command that prints a short-lived credential, such as an Entra ID access token.
+#[with_fallible_options]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema, MergeFrom)]
+pub struct LanguageModelAuthCommand {
+    /// The program to run. Its standard output, trimmed, is used as the token.
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// The header to send the token in, verbatim. When unset, the token is sent as
+    /// `Authorization: Bearer <token>`.
+    pub header: Option<String>,
+    /// How long to reuse a token before running the command again, in seconds.
+    ///
+    /// Default: 300
+    pub cache_seconds: Option<u64>,
 }
 
 #[with_fallible_options]
@@ -268,6 +341,10 @@ pub struct OpenAiCompatibleModelCapabilities {
     pub images: bool,
     pub parallel_tool_calls: bool,
     pub prompt_cache_key: bool,
+    /// Whether the backend honors Anthropic-style `cache_control` breakpoints
+    /// on message content parts.
+    #[serde(default)]
+    pub cache_control: bool,
     #[serde(default = "default_true")]
     pub chat_completions: bool,
 }
@@ -279,6 +356,7 @@ impl Default for OpenAiCompatibleModelCapabilities {
             images: false,
             parallel_tool_calls: false,
             prompt_cache_key: false,
+            cache_control: false,
             chat_completions: default_true(),
         }
     }
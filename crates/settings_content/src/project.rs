@@ -71,6 +71,13 @@ pub struct ProjectSettingsContent {
     /// Default: 60
     pub context_server_timeout: Option<u64>,
 
+    /// The credential profile to use for each language model provider in this project,
+    /// keyed by provider id. Threads can still select a different profile.
+    ///
+    /// Example: {"language_model_credential_profiles": {"nanogpt": "team"}}
+    #[serde(default)]
+    pub language_model_credential_profiles: HashMap<Arc<str>, String>,
+
     /// Configuration for how direnv configuration should be loaded
     pub load_direnv: Option<DirenvSettings>,
 
@@ -138,6 +145,14 @@ pub struct WorktreeSettingsContent {
     /// external dependencies that should not be modified directly.
     /// Default: []
     pub read_only_files: Option<Vec<String>>,
+
+    /// Files at least this large, in megabytes, are opened in large-file mode: they are
+    /// loaded in chunks, opened as read-only, and skip syntax highlighting, language
+    /// servers and edit predictions. The whole file is still held in memory once it has
+    /// finished loading. Set to 0 to disable large-file mode.
+    ///
+    /// Default: 64
+    pub large_file_threshold_mb: Option<u64>,
 }
 
 #[with_fallible_options]
@@ -494,6 +509,50 @@ pub struct GitSettings {
     ///
     /// Default: ../worktrees
     pub worktree_directory: Option<String>,
+    /// How commits made from Zed are signed.
+    pub commit_signing: Option<CommitSigningSettingsContent>,
+}
+
+#[with_fallible_options]
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize, JsonSchema, MergeFrom)]
+#[serde(rename_all = "snake_case")]
+pub struct CommitSigningSettingsContent {
+    /// Whether to sign commits. When unset, git's `commit.gpgSign` config decides.
+    ///
+    /// Default: null
+    pub enabled: Option<bool>,
+    /// The signature format to use. When unset, git's `gpg.format` config decides.
+    ///
+    /// Default: null
+    pub format: Option<CommitSigningFormat>,
+    /// The key to sign with. For SSH signing this is a path to a public key or
+    /// a literal `key::` value. When unset, git's `user.signingKey` config decides.
+    ///
+    /// Default: null
+    pub key: Option<String>,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum CommitSigningFormat {
+    /// Sign with GPG.
+    Openpgp,
+    /// Sign with an SSH key.
+    Ssh,
+    /// Sign with an X.509 certificate via gpgsm.
+    X509,
 }
 
 #[with_fallible_options]
@@ -567,13 +626,33 @@ pub struct InlineBlameSettings {
 }
 
 #[with_fallible_options]
-#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize, JsonSchema, MergeFrom)]
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize, JsonSchema, MergeFrom)]
 #[serde(rename_all = "snake_case")]
 pub struct BlameSettings {
     /// Whether to show the avatar of the author of the commit.
     ///
     /// Default: true
     pub show_avatar: Option<bool>,
+    /// Whether to follow lines moved or copied within a file (`git blame -M`).
+    ///
+    /// Default: false
+    pub detect_moves: Option<bool>,
+    /// Whether to follow lines moved or copied from other files modified
+    /// in the same commit (`git blame -C`).
+    ///
+    /// Default: false
+    pub detect_copies: Option<bool>,
+    /// A file listing commits for blame to skip, relative to the repository root,
+    /// such as `.git-blame-ignore-revs`. Git's `blame.ignoreRevsFile` config is
+    /// honored in addition to this.
+    ///
+    /// Default: null
+    pub ignore_revs_file: Option<String>,
+    /// Commits for blame to skip, e.g. mass-reformatting commits. Lines they changed
+    /// are attributed to earlier commits.
+    ///
+    /// Default: []
+    pub ignore_revs: Option<Vec<String>>,
 }
 
 #[with_fallible_options]
@@ -125,6 +125,9 @@ pub struct SettingsContent {
     /// Default: VSCode
     pub base_keymap: Option<BaseKeymapContent>,
 
+    /// Configuration for the bookmarks panel.
+    pub bookmarks: Option<BookmarksSettingsContent>,
+
     /// Configuration for the collab panel visual settings.
     pub collaboration_panel: Option<PanelSettingsContent>,
 
@@ -161,6 +164,9 @@ pub struct SettingsContent {
 
     pub language_models: Option<AllLanguageModelSettingsContent>,
 
+    /// Configuration for the local history of saved files.
+    pub local_history: Option<LocalHistorySettingsContent>,
+
     pub outline_panel: Option<OutlinePanelSettingsContent>,
 
     pub project_panel: Option<ProjectPanelSettingsContent>,
@@ -181,6 +187,8 @@ pub struct SettingsContent {
 
     /// Configuration for session-related features
     pub session: Option<SessionSettingsContent>,
+    /// Configuration for spell checking comments, strings and prose.
+    pub spell_check: Option<SpellCheckSettingsContent>,
     /// Control what info is collected by Zed.
     pub telemetry: Option<TelemetrySettingsContent>,
 
@@ -782,8 +790,32 @@ pub struct VimSettingsContent {
     /// by default. The 'g' flag then toggles this behavior.,
     pub gdefault: Option<bool>,
     pub custom_digraphs: Option<HashMap<String, Arc<str>>>,
+    /// User-defined ex commands, keyed by the name typed after `:`.
+    ///
+    /// Names must start with an uppercase letter.
+    pub custom_commands: Option<HashMap<String, VimCustomCommandContent>>,
     pub highlight_on_yank_duration: Option<u64>,
     pub cursor_shape: Option<CursorShapeSettings>,
+    /// Whether to remember registers, the jump list, search history and `:` command history
+    /// across restarts.
+    ///
+    /// Default: true
+    pub persist_history: Option<bool>,
+}
+
+/// A user-defined ex command.
+#[with_fallible_options]
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Debug, JsonSchema, MergeFrom)]
+pub struct VimCustomCommandContent {
+    /// Actions to run in order. Each one is written like a keymap action: either an
+    /// action name, or an array of the action name and its arguments.
+    pub actions: Option<Vec<serde_json::Value>>,
+    /// A shell command to run instead of actions. When the command is given a range,
+    /// the lines in the range are filtered through it as with `:{range}!`; otherwise it
+    /// runs in a terminal. `<args>` is replaced with the arguments typed after the command.
+    pub shell: Option<String>,
+    /// The range to use when none is given, such as `%` for the whole buffer.
+    pub default_range: Option<String>,
 }
 
 #[derive(
@@ -896,6 +928,77 @@ pub struct JournalSettingsContent {
     pub hour_format: Option<HourFormat>,
 }
 
+/// Settings for spell checking comments, strings and prose.
+#[with_fallible_options]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct SpellCheckSettingsContent {
+    /// Whether to check the spelling of comments, string literals, Markdown and
+    /// commit messages.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// The dictionaries to check words against. Each entry is either the name of a
+    /// bundled dictionary, like `en_US`, or the path to a Hunspell `.dic` file with
+    /// a matching `.aff` file next to it.
+    ///
+    /// Default: ["en_US"]
+    pub dictionaries: Option<Vec<String>>,
+    /// Additional words to accept in every project.
+    ///
+    /// Default: []
+    pub words: Option<Vec<String>>,
+}
+
+/// Settings for the bookmarks panel.
+#[with_fallible_options]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct BookmarksSettingsContent {
+    /// Whether to show the bookmarks panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the bookmarks panel.
+    ///
+    /// Default: right
+    pub dock: Option<DockPosition>,
+    /// Default width of the bookmarks panel in pixels.
+    ///
+    /// Default: 300
+    #[serde(serialize_with = "crate::serialize_optional_f32_with_two_decimal_places")]
+    pub default_width: Option<f32>,
+}
+
+/// Settings for the local history of saved files, which is kept independently of git.
+#[with_fallible_options]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct LocalHistorySettingsContent {
+    /// Whether to record a snapshot of a file's contents every time it is saved.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+    /// The maximum number of snapshots kept for each file. Older snapshots are deleted.
+    ///
+    /// Default: 50
+    pub max_entries_per_file: Option<usize>,
+    /// The number of days after which snapshots are deleted.
+    ///
+    /// Default: 30
+    pub max_age_days: Option<u32>,
+    /// Whether to show the local history panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the local history panel.
+    ///
+    /// Default: right
+    pub dock: Option<DockPosition>,
+    /// Default width of the local history panel in pixels.
+    ///
+    /// Default: 300
+    #[serde(serialize_with = "crate::serialize_optional_f32_with_two_decimal_places")]
+    pub default_width: Option<f32>,
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum HourFormat {
@@ -2436,7 +2436,7 @@ fn editor_page() -> SettingsPage {
         ]
     }
 
-    fn vim_settings_section() -> [SettingsPageItem; 12] {
+    fn vim_settings_section() -> [SettingsPageItem; 14] {
         [
             SettingsPageItem::SectionHeader("Vim"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -2545,6 +2545,21 @@ fn editor_page() -> SettingsPage {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Persist History",
+                description: "Remember registers, the jump list, search history and `:` command history across restarts.",
+                field: Box::new(SettingField {
+                    json_path: Some("vim.persist_history"),
+                    pick: |settings_content| {
+                        settings_content.vim.as_ref()?.persist_history.as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content.vim.get_or_insert_default().persist_history = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
             SettingsPageItem::SettingItem(SettingItem {
                 title: "Cursor Shape - Normal Mode",
                 description: "Cursor shape for normal mode.",
@@ -2667,6 +2682,24 @@ fn editor_page() -> SettingsPage {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Custom Commands",
+                description: "User-defined ex commands that run actions or shell commands.",
+                field: Box::new(
+                    SettingField {
+                        json_path: Some("vim.custom_commands"),
+                        pick: |settings_content| {
+                            settings_content.vim.as_ref()?.custom_commands.as_ref()
+                        },
+                        write: |settings_content, value| {
+                            settings_content.vim.get_or_insert_default().custom_commands = value;
+                        },
+                    }
+                    .unimplemented(),
+                ),
+                metadata: None,
+                files: USER,
+            }),
         ]
     }
 
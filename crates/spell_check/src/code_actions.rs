@@ -0,0 +1,143 @@
+use crate::{DIAGNOSTIC_SOURCE, SpellCheckStore};
+use anyhow::{Result, anyhow};
+use editor::{CodeActionProvider, ExcerptId};
+use gpui::{App, Entity, Task, Window};
+use language::{Buffer, LanguageServerId};
+use project::{CodeAction, LspAction, ProjectTransaction};
+use serde::{Deserialize, Serialize};
+use std::{ops::Range, sync::Arc};
+
+const SPELL_CHECK_CODE_ACTION_PROVIDER_ID: &str = "spell_check";
+
+/// The number of replacements offered for a misspelled word.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Offers quick fixes for the misspelled words under the cursor: replacing them with
+/// suggestions from the dictionary, or adding them to a word list.
+pub struct SpellCheckCodeActionProvider;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum QuickFix {
+    Replace { text: String },
+    AddToProjectWords { word: String },
+    AddToUserWords { word: String },
+}
+
+impl CodeActionProvider for SpellCheckCodeActionProvider {
+    fn id(&self) -> Arc<str> {
+        SPELL_CHECK_CODE_ACTION_PROVIDER_ID.into()
+    }
+
+    fn code_actions(
+        &self,
+        buffer: &Entity<Buffer>,
+        range: Range<text::Anchor>,
+        _: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Vec<CodeAction>>> {
+        let snapshot = buffer.read(cx).snapshot();
+        let misspelled_ranges = snapshot
+            .diagnostics_in_range::<_, usize>(range, false)
+            .filter(|entry| entry.diagnostic.source.as_deref() == Some(DIAGNOSTIC_SOURCE))
+            .map(|entry| entry.range)
+            .collect::<Vec<_>>();
+        if misspelled_ranges.is_empty() {
+            return Task::ready(Ok(Vec::new()));
+        }
+
+        let store = SpellCheckStore::global(cx);
+        let store = store.read(cx);
+        let Some(dictionary) = store.dictionary() else {
+            return Task::ready(Ok(Vec::new()));
+        };
+        let has_project_words = store.word_list_path(buffer).is_some();
+        cx.background_spawn(async move {
+            let mut actions = Vec::new();
+            for range in misspelled_ranges {
+                let word = snapshot.text_for_range(range.clone()).collect::<String>();
+                let range = snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end);
+                for suggestion in dictionary.suggest(&word, MAX_SUGGESTIONS) {
+                    actions.push(code_action(
+                        range.clone(),
+                        format!("Replace with \"{suggestion}\""),
+                        QuickFix::Replace { text: suggestion },
+                    ));
+                }
+                if has_project_words {
+                    actions.push(code_action(
+                        range.clone(),
+                        format!("Add \"{word}\" to project dictionary"),
+                        QuickFix::AddToProjectWords { word: word.clone() },
+                    ));
+                }
+                actions.push(code_action(
+                    range,
+                    format!("Add \"{word}\" to user dictionary"),
+                    QuickFix::AddToUserWords { word },
+                ));
+            }
+            Ok(actions)
+        })
+    }
+
+    fn apply_code_action(
+        &self,
+        buffer: Entity<Buffer>,
+        action: CodeAction,
+        _excerpt_id: ExcerptId,
+        _push_to_history: bool,
+        _window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<ProjectTransaction>> {
+        let quick_fix = match &action.lsp_action {
+            LspAction::Action(lsp_action) => lsp_action
+                .data
+                .clone()
+                .and_then(|data| serde_json::from_value::<QuickFix>(data).ok()),
+            _ => None,
+        };
+        let Some(quick_fix) = quick_fix else {
+            return Task::ready(Err(anyhow!("not a spell check code action")));
+        };
+
+        match quick_fix {
+            QuickFix::Replace { text } => {
+                buffer.update(cx, |buffer, cx| {
+                    buffer.edit([(action.range, text)], None, cx);
+                });
+                Task::ready(Ok(ProjectTransaction::default()))
+            }
+            QuickFix::AddToProjectWords { word } => {
+                let task = SpellCheckStore::global(cx).update(cx, |store, cx| {
+                    store.add_to_project_words(&buffer, word, cx)
+                });
+                cx.background_spawn(async move {
+                    task.await?;
+                    Ok(ProjectTransaction::default())
+                })
+            }
+            QuickFix::AddToUserWords { word } => {
+                SpellCheckStore::global(cx)
+                    .read(cx)
+                    .add_to_user_words(word, cx);
+                Task::ready(Ok(ProjectTransaction::default()))
+            }
+        }
+    }
+}
+
+fn code_action(range: Range<text::Anchor>, title: String, quick_fix: QuickFix) -> CodeAction {
+    CodeAction {
+        // Applied by this provider rather than a language server, so the id is unused.
+        server_id: LanguageServerId(0),
+        range,
+        lsp_action: LspAction::Action(Box::new(lsp::CodeAction {
+            title,
+            kind: Some(lsp::CodeActionKind::QUICKFIX),
+            data: serde_json::to_value(quick_fix).ok(),
+            ..Default::default()
+        })),
+        resolved: true,
+    }
+}
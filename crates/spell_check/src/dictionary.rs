@@ -0,0 +1,526 @@
+use anyhow::{Context as _, Result, anyhow};
+use collections::{HashMap, HashSet};
+
+/// Words longer than this only get suggestions one edit away, since the number of
+/// candidates two edits away grows quadratically with the word's length.
+const MAX_TWO_EDIT_WORD_LEN: usize = 12;
+
+/// A set of correctly spelled words, loaded from a Hunspell-format dictionary.
+///
+/// Every form produced by the affix rules is expanded when the dictionary is parsed, so
+/// checking a word is a single lookup.
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    words: HashSet<String>,
+    /// Words that are accepted but never suggested, such as offensive terms.
+    no_suggest: HashSet<String>,
+    try_chars: Vec<char>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FlagMode {
+    Char,
+    Long,
+    Numeric,
+}
+
+#[derive(Debug)]
+struct AffixRule {
+    strip: String,
+    add: String,
+    condition: Vec<ConditionPart>,
+}
+
+#[derive(Debug)]
+struct Affix {
+    is_prefix: bool,
+    cross_product: bool,
+    rules: Vec<AffixRule>,
+}
+
+#[derive(Debug)]
+enum ConditionPart {
+    Any,
+    Chars { negated: bool, chars: Vec<char> },
+}
+
+impl Dictionary {
+    /// Parses a dictionary from the contents of its `.aff` and `.dic` files.
+    pub fn parse(aff: &str, dic: &str) -> Result<Self> {
+        let mut flag_mode = FlagMode::Char;
+        let mut try_chars = Vec::new();
+        let mut no_suggest_flag = None;
+        let mut only_in_compound_flag = None;
+        let mut affixes: HashMap<String, Affix> = HashMap::default();
+
+        let mut lines = aff.lines().enumerate();
+        while let Some((line_ix, line)) = lines.next() {
+            let mut fields = line.split_whitespace();
+            let Some(directive) = fields.next() else {
+                continue;
+            };
+            match directive {
+                "FLAG" => {
+                    flag_mode = match fields.next() {
+                        Some("long") => FlagMode::Long,
+                        Some("num") => FlagMode::Numeric,
+                        _ => FlagMode::Char,
+                    }
+                }
+                "TRY" => try_chars = fields.next().unwrap_or_default().chars().collect(),
+                "NOSUGGEST" => no_suggest_flag = fields.next().map(str::to_string),
+                // Compounding isn't supported, so words that are only valid inside compounds
+                // are left out entirely.
+                "ONLYINCOMPOUND" => only_in_compound_flag = fields.next().map(str::to_string),
+                "PFX" | "SFX" => {
+                    let flag = fields
+                        .next()
+                        .with_context(|| format!("missing affix flag on line {}", line_ix + 1))?;
+                    let cross_product = fields.next() == Some("Y");
+                    let count: usize = fields
+                        .next()
+                        .and_then(|count| count.parse().ok())
+                        .with_context(|| format!("invalid affix count on line {}", line_ix + 1))?;
+
+                    let mut rules = Vec::with_capacity(count);
+                    for _ in 0..count {
+                        let (line_ix, line) = lines
+                            .next()
+                            .ok_or_else(|| anyhow!("missing rules for affix {flag}"))?;
+                        rules.push(parse_affix_rule(line).with_context(|| {
+                            format!("invalid affix rule on line {}", line_ix + 1)
+                        })?);
+                    }
+                    affixes.insert(
+                        flag.to_string(),
+                        Affix {
+                            is_prefix: directive == "PFX",
+                            cross_product,
+                            rules,
+                        },
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        let mut words = HashSet::default();
+        let mut no_suggest = HashSet::default();
+        for (line_ix, line) in dic.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || (line_ix == 0 && line.parse::<usize>().is_ok()) {
+                continue;
+            }
+            let entry = line.split(['\t', ' ']).next().unwrap_or_default();
+            let (stem, flags) = match entry.split_once('/') {
+                Some((stem, flags)) => (stem, parse_flags(flags, flag_mode)),
+                None => (entry, Vec::new()),
+            };
+            let has_flag = |flag: &Option<String>| flag.as_ref().is_some_and(|f| flags.contains(f));
+            if has_flag(&only_in_compound_flag) {
+                continue;
+            }
+            if has_flag(&no_suggest_flag) {
+                expand_entry(stem, &flags, &affixes, &mut no_suggest);
+            }
+            expand_entry(stem, &flags, &affixes, &mut words);
+        }
+
+        // Suggestions are searched for in lowercase, so uppercase letters never help.
+        try_chars.retain(|char| !char.is_uppercase());
+        if try_chars.is_empty() {
+            try_chars = ('a'..='z').collect();
+        }
+        Ok(Self {
+            words,
+            no_suggest,
+            try_chars,
+        })
+    }
+
+    /// Builds a dictionary from a plain list of words, one per line. Lines starting with `#`
+    /// are ignored.
+    pub fn from_word_list(text: &str) -> Self {
+        Self::from_words(
+            text.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#')),
+        )
+    }
+
+    pub fn from_words<'a>(words: impl IntoIterator<Item = &'a str>) -> Self {
+        Self {
+            words: words.into_iter().map(str::to_string).collect(),
+            no_suggest: HashSet::default(),
+            try_chars: ('a'..='z').collect(),
+        }
+    }
+
+    /// Adds every word of `other` to this dictionary.
+    pub fn extend(&mut self, other: Dictionary) {
+        self.words.extend(other.words);
+        self.no_suggest.extend(other.no_suggest);
+        for char in other.try_chars {
+            if !self.try_chars.contains(&char) {
+                self.try_chars.push(char);
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Returns whether `word` is spelled correctly. A capitalized word is also accepted when
+    /// its lowercase form is in the dictionary.
+    pub fn contains(&self, word: &str) -> bool {
+        if self.words.contains(word) {
+            return true;
+        }
+        if let Some(possessive) = word.strip_suffix("'s").or_else(|| word.strip_suffix("’s"))
+            && !possessive.is_empty()
+        {
+            return self.contains(possessive);
+        }
+        is_capitalized(word) && self.words.contains(&word.to_lowercase())
+    }
+
+    /// Returns up to `limit` correctly spelled words close to `word`, best matches first.
+    pub fn suggest(&self, word: &str, limit: usize) -> Vec<String> {
+        let capitalized = is_capitalized(word);
+        let lowercase = word.to_lowercase();
+        let is_known = |candidate: &str| {
+            self.words.contains(candidate) && !self.no_suggest.contains(candidate)
+        };
+
+        let mut suggestions = Vec::new();
+        let mut seen = HashSet::default();
+        let edits = self.edits(&lowercase);
+        for candidate in &edits {
+            if is_known(candidate) && seen.insert(candidate.clone()) {
+                suggestions.push(candidate.clone());
+            }
+        }
+
+        let chars = lowercase.chars().collect::<Vec<_>>();
+        for split in 2..chars.len().saturating_sub(1) {
+            let head = chars[..split].iter().collect::<String>();
+            let tail = chars[split..].iter().collect::<String>();
+            if is_known(&head) && is_known(&tail) {
+                let candidate = format!("{head} {tail}");
+                if seen.insert(candidate.clone()) {
+                    suggestions.push(candidate);
+                }
+            }
+        }
+
+        if suggestions.is_empty() && chars.len() <= MAX_TWO_EDIT_WORD_LEN {
+            for edit in &edits {
+                for candidate in self.edits(edit) {
+                    if is_known(&candidate) && seen.insert(candidate.clone()) {
+                        suggestions.push(candidate);
+                    }
+                }
+            }
+        }
+
+        suggestions.truncate(limit);
+        if capitalized {
+            for suggestion in &mut suggestions {
+                *suggestion = capitalize(suggestion);
+            }
+        }
+        suggestions
+    }
+
+    /// Returns every string one edit away from `word`, ordered from the most to the least
+    /// likely typo: swapped letters, then extra, wrong and missing letters.
+    fn edits(&self, word: &str) -> Vec<String> {
+        let chars = word.chars().collect::<Vec<_>>();
+        let mut edits = Vec::new();
+        for ix in 0..chars.len().saturating_sub(1) {
+            let mut edit = chars.clone();
+            edit.swap(ix, ix + 1);
+            edits.push(edit.into_iter().collect());
+        }
+        for ix in 0..chars.len() {
+            let mut edit = chars.clone();
+            edit.remove(ix);
+            edits.push(edit.into_iter().collect());
+        }
+        for ix in 0..chars.len() {
+            for &replacement in &self.try_chars {
+                if replacement != chars[ix] {
+                    let mut edit = chars.clone();
+                    edit[ix] = replacement;
+                    edits.push(edit.into_iter().collect());
+                }
+            }
+        }
+        for ix in 0..=chars.len() {
+            for &insertion in &self.try_chars {
+                let mut edit = chars.clone();
+                edit.insert(ix, insertion);
+                edits.push(edit.into_iter().collect());
+            }
+        }
+        edits
+    }
+}
+
+fn parse_affix_rule(line: &str) -> Result<AffixRule> {
+    let mut fields = line.split_whitespace().skip(2);
+    let strip = fields.next().context("missing strip")?;
+    let add = fields.next().context("missing affix")?;
+    let condition = fields.next().unwrap_or(".");
+
+    // Continuation flags on the affix itself aren't supported, so they're dropped.
+    let add = add.split('/').next().unwrap_or_default();
+    Ok(AffixRule {
+        strip: if strip == "0" { "" } else { strip }.to_string(),
+        add: if add == "0" { "" } else { add }.to_string(),
+        condition: parse_condition(condition)?,
+    })
+}
+
+fn parse_condition(condition: &str) -> Result<Vec<ConditionPart>> {
+    let mut parts = Vec::new();
+    let mut chars = condition.chars();
+    while let Some(char) = chars.next() {
+        match char {
+            '.' => parts.push(ConditionPart::Any),
+            '[' => {
+                let mut set = String::new();
+                loop {
+                    match chars.next() {
+                        Some(']') => break,
+                        Some(char) => set.push(char),
+                        None => anyhow::bail!("unterminated character class in {condition:?}"),
+                    }
+                }
+                let (negated, set) = match set.strip_prefix('^') {
+                    Some(set) => (true, set),
+                    None => (false, set.as_str()),
+                };
+                parts.push(ConditionPart::Chars {
+                    negated,
+                    chars: set.chars().collect(),
+                });
+            }
+            char => parts.push(ConditionPart::Chars {
+                negated: false,
+                chars: vec![char],
+            }),
+        }
+    }
+    Ok(parts)
+}
+
+fn parse_flags(flags: &str, mode: FlagMode) -> Vec<String> {
+    match mode {
+        FlagMode::Char => flags.chars().map(String::from).collect(),
+        FlagMode::Long => flags
+            .chars()
+            .collect::<Vec<_>>()
+            .chunks(2)
+            .map(|flag| flag.iter().collect())
+            .collect(),
+        FlagMode::Numeric => flags.split(',').map(str::to_string).collect(),
+    }
+}
+
+impl AffixRule {
+    fn apply(&self, stem: &str, is_prefix: bool) -> Option<String> {
+        let stem_chars = stem.chars().collect::<Vec<_>>();
+        if self.condition.len() > stem_chars.len() {
+            return None;
+        }
+        let checked = if is_prefix {
+            &stem_chars[..self.condition.len()]
+        } else {
+            &stem_chars[stem_chars.len() - self.condition.len()..]
+        };
+        let matches = checked
+            .iter()
+            .zip(&self.condition)
+            .all(|(char, part)| match part {
+                ConditionPart::Any => true,
+                ConditionPart::Chars { negated, chars } => chars.contains(char) != *negated,
+            });
+        if !matches {
+            return None;
+        }
+
+        if is_prefix {
+            let rest = stem.strip_prefix(self.strip.as_str())?;
+            Some(format!("{}{rest}", self.add))
+        } else {
+            let rest = stem.strip_suffix(self.strip.as_str())?;
+            Some(format!("{rest}{}", self.add))
+        }
+    }
+}
+
+fn expand_entry(
+    stem: &str,
+    flags: &[String],
+    affixes: &HashMap<String, Affix>,
+    words: &mut HashSet<String>,
+) {
+    words.insert(stem.to_string());
+
+    let entry_affixes = flags
+        .iter()
+        .filter_map(|flag| affixes.get(flag))
+        .collect::<Vec<_>>();
+    let mut suffixed = Vec::new();
+    for affix in entry_affixes.iter().filter(|affix| !affix.is_prefix) {
+        for rule in &affix.rules {
+            if let Some(word) = rule.apply(stem, false) {
+                if affix.cross_product {
+                    suffixed.push(word.clone());
+                }
+                words.insert(word);
+            }
+        }
+    }
+    for affix in entry_affixes.iter().filter(|affix| affix.is_prefix) {
+        for rule in &affix.rules {
+            if let Some(word) = rule.apply(stem, true) {
+                words.insert(word);
+            }
+            if affix.cross_product {
+                for suffixed in &suffixed {
+                    if let Some(word) = rule.apply(suffixed, true) {
+                        words.insert(word);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn is_capitalized(word: &str) -> bool {
+    let mut chars = word.chars();
+    chars.next().is_some_and(char::is_uppercase) && chars.all(|char| !char.is_uppercase())
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = "
+SET UTF-8
+TRY esianrtolcdugmphbyfvkwz
+
+PFX U Y 1
+PFX U 0 un .
+
+SFX S Y 2
+SFX S y ies [^aeiou]y
+SFX S 0 s [^y]
+
+SFX D Y 2
+SFX D 0 d e
+SFX D 0 ed [^e]
+
+NOSUGGEST !
+ONLYINCOMPOUND c
+";
+
+    const DIC: &str = "6
+the
+receive/D
+do/U
+query/S
+lock/SDU
+thr/!
+hte/c
+";
+
+    #[test]
+    fn test_affix_expansion() {
+        let dictionary = Dictionary::parse(AFF, DIC).unwrap();
+        for word in [
+            "the", "receive", "received", "undo", "queries", "locks", "locked", "unlock",
+            "unlocked", "unlocks",
+        ] {
+            assert!(dictionary.contains(word), "{word} should be accepted");
+        }
+        for word in ["recieve", "receiveed", "querys", "unthe", "hte"] {
+            assert!(!dictionary.contains(word), "{word} should be rejected");
+        }
+        assert!(dictionary.contains("The"));
+        assert!(!dictionary.contains("tHe"));
+        assert!(dictionary.contains("query's"));
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let dictionary = Dictionary::parse(AFF, DIC).unwrap();
+        assert_eq!(dictionary.suggest("teh", 3), vec!["the"]);
+        // Words marked NOSUGGEST are accepted but never offered.
+        assert!(dictionary.contains("thr"));
+        assert!(!dictionary.suggest("th", 10).contains(&"thr".to_string()));
+        assert_eq!(dictionary.suggest("recieve", 3), vec!["receive"]);
+        assert_eq!(dictionary.suggest("Recieved", 3), vec!["Received"]);
+        assert_eq!(dictionary.suggest("thelock", 3), vec!["the lock"]);
+        assert!(
+            dictionary
+                .suggest("unlcokd", 3)
+                .contains(&"unlocked".to_string())
+        );
+    }
+
+    #[test]
+    fn test_bundled_dictionary() {
+        let dictionary = Dictionary::parse(
+            include_str!("../../../assets/dictionaries/en_US.aff"),
+            include_str!("../../../assets/dictionaries/en_US.dic"),
+        )
+        .unwrap();
+        for word in [
+            "the",
+            "receive",
+            "separate",
+            "occurred",
+            "returns",
+            "configured",
+            "elephant",
+            "umbrellas",
+            "whispered",
+            "gorgeous",
+            "happiness",
+            "ropes",
+        ] {
+            assert!(dictionary.contains(word), "{word} should be accepted");
+        }
+        for word in [
+            "teh",
+            "recieve",
+            "seperate",
+            "occured",
+            "auditmanager",
+            "bgallmeister",
+        ] {
+            assert!(!dictionary.contains(word), "{word} should be rejected");
+        }
+        assert!(
+            dictionary
+                .suggest("recieve", 5)
+                .contains(&"receive".to_string())
+        );
+    }
+}
@@ -0,0 +1,464 @@
+mod code_actions;
+mod dictionary;
+mod words;
+
+use anyhow::{Context as _, Result, anyhow};
+use collections::HashMap;
+use editor::Editor;
+use fs::Fs;
+use gpui::{
+    App, AppContext as _, AssetSource, Context, Entity, EntityId, Global, Subscription, Task,
+    WeakEntity,
+};
+use language::{
+    Buffer, BufferEvent, BufferSnapshot, Diagnostic, DiagnosticEntry, DiagnosticSet,
+    DiagnosticSeverity, DiagnosticSourceKind, PointUtf16, ToPointUtf16 as _,
+};
+use project::Project;
+use settings::{RegisterSetting, Settings, SettingsStore};
+use std::{
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::Arc,
+    time::Duration,
+};
+
+pub use code_actions::SpellCheckCodeActionProvider;
+pub use dictionary::Dictionary;
+pub use words::{Word, checked_ranges, is_prose_language, words};
+
+/// The source of the diagnostics reported for misspelled words. Misspellings are stored
+/// as a buffer's local diagnostics under this source, apart from any language server's.
+pub const DIAGNOSTIC_SOURCE: &str = "spell check";
+
+/// The name of a project's word list, inside its `.zed` directory.
+pub const PROJECT_WORD_LIST_FILE_NAME: &str = "dictionary.txt";
+
+const CHECK_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Buffers larger than this aren't checked.
+const MAX_CHECKED_BUFFER_LEN: usize = 4 * 1024 * 1024;
+
+#[derive(Clone, Debug, PartialEq, RegisterSetting)]
+pub struct SpellCheckSettings {
+    pub enabled: bool,
+    pub dictionaries: Vec<String>,
+    pub words: Vec<String>,
+}
+
+impl Settings for SpellCheckSettings {
+    fn from_settings(content: &settings::SettingsContent) -> Self {
+        let spell_check = content.spell_check.clone().unwrap();
+        Self {
+            enabled: spell_check.enabled.unwrap(),
+            dictionaries: spell_check.dictionaries.unwrap(),
+            words: spell_check.words.unwrap(),
+        }
+    }
+}
+
+pub fn init(fs: Arc<dyn Fs>, cx: &mut App) {
+    let store = cx.new(|cx| SpellCheckStore::new(fs, cx));
+    cx.set_global(GlobalSpellCheckStore(store));
+
+    cx.observe_new(|editor: &mut Editor, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+        if editor.mode().is_single_line() {
+            return;
+        }
+        let Some(buffer) = editor.buffer().read(cx).as_singleton() else {
+            return;
+        };
+        let project = editor.project().cloned();
+        SpellCheckStore::global(cx).update(cx, |store, cx| {
+            store.register_buffer(buffer, project.as_ref(), cx)
+        });
+        editor.add_code_action_provider(Rc::new(SpellCheckCodeActionProvider), window, cx);
+    })
+    .detach();
+}
+
+struct GlobalSpellCheckStore(Entity<SpellCheckStore>);
+
+impl Global for GlobalSpellCheckStore {}
+
+/// Checks the spelling of every buffer open in an editor, and reports the misspelled
+/// words as diagnostics of those buffers.
+pub struct SpellCheckStore {
+    fs: Arc<dyn Fs>,
+    settings: SpellCheckSettings,
+    dictionary: Option<Arc<Dictionary>>,
+    user_words: Arc<Dictionary>,
+    project_words: HashMap<Arc<Path>, Arc<Dictionary>>,
+    buffers: HashMap<EntityId, CheckedBuffer>,
+    _load_dictionary_task: Task<()>,
+    _settings_subscription: Subscription,
+}
+
+struct CheckedBuffer {
+    buffer: WeakEntity<Buffer>,
+    word_list_path: Option<Arc<Path>>,
+    _check_task: Task<()>,
+    _subscriptions: [Subscription; 2],
+}
+
+impl SpellCheckStore {
+    pub fn global(cx: &App) -> Entity<Self> {
+        cx.global::<GlobalSpellCheckStore>().0.clone()
+    }
+
+    fn new(fs: Arc<dyn Fs>, cx: &mut Context<Self>) -> Self {
+        let settings = SpellCheckSettings::get_global(cx).clone();
+        let mut this = Self {
+            fs,
+            user_words: Arc::new(Dictionary::from_words(
+                settings.words.iter().map(String::as_str),
+            )),
+            settings,
+            dictionary: None,
+            project_words: HashMap::default(),
+            buffers: HashMap::default(),
+            _load_dictionary_task: Task::ready(()),
+            _settings_subscription: cx.observe_global::<SettingsStore>(Self::settings_changed),
+        };
+        if this.settings.enabled {
+            this.load_dictionary(cx);
+        }
+        this
+    }
+
+    /// The dictionary words are checked against, once it has loaded.
+    pub fn dictionary(&self) -> Option<Arc<Dictionary>> {
+        self.dictionary.clone()
+    }
+
+    /// Returns the path of the word list for the project containing `buffer`, if it's part
+    /// of a local project.
+    pub fn word_list_path(&self, buffer: &Entity<Buffer>) -> Option<Arc<Path>> {
+        self.buffers
+            .get(&buffer.entity_id())?
+            .word_list_path
+            .clone()
+    }
+
+    fn settings_changed(&mut self, cx: &mut Context<Self>) {
+        let settings = SpellCheckSettings::get_global(cx).clone();
+        if settings == self.settings {
+            return;
+        }
+        let old_settings = std::mem::replace(&mut self.settings, settings);
+        if old_settings.words != self.settings.words {
+            self.user_words = Arc::new(Dictionary::from_words(
+                self.settings.words.iter().map(String::as_str),
+            ));
+        }
+        if self.settings.enabled
+            && (self.dictionary.is_none()
+                || old_settings.dictionaries != self.settings.dictionaries)
+        {
+            self.load_dictionary(cx);
+        } else {
+            self.check_all_buffers(cx);
+        }
+    }
+
+    fn load_dictionary(&mut self, cx: &mut Context<Self>) {
+        let names = self.settings.dictionaries.clone();
+        let fs = self.fs.clone();
+        let asset_source = cx.asset_source().clone();
+        self._load_dictionary_task = cx.spawn(async move |this, cx| {
+            let mut dictionary = Dictionary::default();
+            for name in names {
+                match load_dictionary(&name, fs.as_ref(), asset_source.as_ref(), cx).await {
+                    Ok(loaded) => dictionary.extend(loaded),
+                    Err(error) => log::error!("failed to load dictionary {name:?}: {error:#}"),
+                }
+            }
+            this.update(cx, |this, cx| {
+                this.dictionary = Some(Arc::new(dictionary));
+                this.check_all_buffers(cx);
+            })
+            .ok();
+        });
+    }
+
+    fn register_buffer(
+        &mut self,
+        buffer: Entity<Buffer>,
+        project: Option<&Entity<Project>>,
+        cx: &mut Context<Self>,
+    ) {
+        let buffer_id = buffer.entity_id();
+        let word_list_path = project_word_list_path(&buffer, project, cx);
+        if let Some(checked_buffer) = self.buffers.get_mut(&buffer_id) {
+            if checked_buffer.word_list_path.is_none() && word_list_path.is_some() {
+                checked_buffer.word_list_path = word_list_path.clone();
+                self.load_project_words(word_list_path, cx);
+            }
+            return;
+        }
+
+        let subscriptions = [
+            cx.subscribe(&buffer, move |this, buffer, event, cx| match event {
+                BufferEvent::Reparsed | BufferEvent::LanguageChanged(_) => {
+                    this.check_buffer(buffer_id, true, cx);
+                }
+                BufferEvent::Saved => {
+                    let saved_path = buffer
+                        .read(cx)
+                        .file()
+                        .and_then(|file| Some(file.as_local()?.abs_path(cx)));
+                    if let Some(saved_path) = saved_path
+                        && this.project_words.contains_key(saved_path.as_path())
+                    {
+                        this.project_words.remove(saved_path.as_path());
+                        this.load_project_words(Some(saved_path.into()), cx);
+                    }
+                }
+                _ => {}
+            }),
+            cx.observe_release(&buffer, move |this, _, _| {
+                this.buffers.remove(&buffer_id);
+            }),
+        ];
+        self.buffers.insert(
+            buffer_id,
+            CheckedBuffer {
+                buffer: buffer.downgrade(),
+                word_list_path: word_list_path.clone(),
+                _check_task: Task::ready(()),
+                _subscriptions: subscriptions,
+            },
+        );
+        if word_list_path
+            .as_ref()
+            .is_some_and(|path| !self.project_words.contains_key(path))
+        {
+            self.load_project_words(word_list_path, cx);
+        } else {
+            self.check_buffer(buffer_id, false, cx);
+        }
+    }
+
+    /// Loads the word list at `path`, then checks every buffer of its project again.
+    fn load_project_words(&mut self, path: Option<Arc<Path>>, cx: &mut Context<Self>) {
+        let Some(path) = path else {
+            return;
+        };
+        let fs = self.fs.clone();
+        cx.spawn(async move |this, cx| {
+            let words = match fs.load(&path).await {
+                Ok(text) => Dictionary::from_word_list(&text),
+                Err(_) => Dictionary::default(),
+            };
+            this.update(cx, |this, cx| {
+                this.project_words.insert(path.clone(), Arc::new(words));
+                this.check_buffers_with_word_list(&path, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Appends `word` to the word list of the project containing `buffer`.
+    pub fn add_to_project_words(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        word: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let Some(path) = self.word_list_path(buffer) else {
+            return Task::ready(Err(anyhow!("buffer isn't part of a local project")));
+        };
+        let fs = self.fs.clone();
+        cx.spawn(async move |this, cx| {
+            let mut text = fs.load(&path).await.unwrap_or_default();
+            if !text.is_empty() && !text.ends_with('\n') {
+                text.push('\n');
+            }
+            text.push_str(&word);
+            text.push('\n');
+            if let Some(parent) = path.parent() {
+                fs.create_dir(parent).await?;
+            }
+            fs.atomic_write(path.to_path_buf(), text.clone())
+                .await
+                .with_context(|| format!("writing word list {path:?}"))?;
+
+            this.update(cx, |this, cx| {
+                this.project_words
+                    .insert(path.clone(), Arc::new(Dictionary::from_word_list(&text)));
+                this.check_buffers_with_word_list(&path, cx);
+            })
+        })
+    }
+
+    /// Adds `word` to the words accepted in every project, in the user's settings.
+    pub fn add_to_user_words(&self, word: String, cx: &App) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            let words = settings
+                .spell_check
+                .get_or_insert_default()
+                .words
+                .get_or_insert_default();
+            if !words.contains(&word) {
+                words.push(word);
+            }
+        });
+    }
+
+    fn check_all_buffers(&mut self, cx: &mut Context<Self>) {
+        let buffer_ids = self.buffers.keys().copied().collect::<Vec<_>>();
+        for buffer_id in buffer_ids {
+            self.check_buffer(buffer_id, false, cx);
+        }
+    }
+
+    fn check_buffers_with_word_list(&mut self, path: &Arc<Path>, cx: &mut Context<Self>) {
+        let buffer_ids = self
+            .buffers
+            .iter()
+            .filter(|(_, buffer)| buffer.word_list_path.as_ref() == Some(path))
+            .map(|(buffer_id, _)| *buffer_id)
+            .collect::<Vec<_>>();
+        for buffer_id in buffer_ids {
+            self.check_buffer(buffer_id, false, cx);
+        }
+    }
+
+    fn check_buffer(&mut self, buffer_id: EntityId, debounce: bool, cx: &mut Context<Self>) {
+        let Some(checked_buffer) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let Some(buffer) = checked_buffer.buffer.upgrade() else {
+            self.buffers.remove(&buffer_id);
+            return;
+        };
+
+        let dictionary = self
+            .dictionary
+            .clone()
+            .filter(|_| self.settings.enabled)
+            .filter(|_| buffer.read(cx).len() <= MAX_CHECKED_BUFFER_LEN);
+        let Some(dictionary) = dictionary else {
+            checked_buffer._check_task = Task::ready(());
+            buffer.update(cx, |buffer, cx| {
+                let diagnostics = DiagnosticSet::new([], buffer);
+                buffer.set_local_diagnostics(DIAGNOSTIC_SOURCE, diagnostics, cx);
+            });
+            return;
+        };
+
+        let user_words = self.user_words.clone();
+        let project_words = checked_buffer
+            .word_list_path
+            .as_ref()
+            .and_then(|path| self.project_words.get(path).cloned());
+        let buffer = buffer.downgrade();
+        checked_buffer._check_task = cx.spawn(async move |_, cx| {
+            if debounce {
+                cx.background_executor().timer(CHECK_DEBOUNCE).await;
+            }
+            let Ok(snapshot) = buffer.read_with(cx, |buffer, _| buffer.snapshot()) else {
+                return;
+            };
+            let diagnostics = cx
+                .background_spawn(async move {
+                    let is_correct = |word: &str| {
+                        dictionary.contains(word)
+                            || user_words.contains(word)
+                            || project_words
+                                .as_ref()
+                                .is_some_and(|words| words.contains(word))
+                    };
+                    DiagnosticSet::new(misspellings(&snapshot, is_correct), &snapshot)
+                })
+                .await;
+            buffer
+                .update(cx, |buffer, cx| {
+                    buffer.set_local_diagnostics(DIAGNOSTIC_SOURCE, diagnostics, cx)
+                })
+                .ok();
+        });
+    }
+}
+
+/// Returns a diagnostic for every word in the checked ranges of `snapshot` that
+/// `is_correct` rejects.
+pub fn misspellings(
+    snapshot: &BufferSnapshot,
+    is_correct: impl Fn(&str) -> bool,
+) -> Vec<DiagnosticEntry<PointUtf16>> {
+    let mut diagnostics = Vec::new();
+    for range in checked_ranges(snapshot) {
+        let text = snapshot.text_for_range(range.clone()).collect::<String>();
+        for word in words(&text) {
+            if is_correct(word.text) {
+                continue;
+            }
+            let start = range.start + word.range.start;
+            let end = range.start + word.range.end;
+            diagnostics.push(DiagnosticEntry {
+                range: start.to_point_utf16(snapshot)..end.to_point_utf16(snapshot),
+                diagnostic: Diagnostic {
+                    source: Some(DIAGNOSTIC_SOURCE.to_string()),
+                    severity: DiagnosticSeverity::INFORMATION,
+                    message: format!("Unknown word \"{}\"", word.text),
+                    group_id: diagnostics.len(),
+                    is_primary: true,
+                    source_kind: DiagnosticSourceKind::Other,
+                    ..Diagnostic::default()
+                },
+            });
+        }
+    }
+    diagnostics
+}
+
+fn project_word_list_path(
+    buffer: &Entity<Buffer>,
+    project: Option<&Entity<Project>>,
+    cx: &App,
+) -> Option<Arc<Path>> {
+    let worktree_id = buffer.read(cx).file()?.worktree_id(cx);
+    let worktree = project?.read(cx).worktree_for_id(worktree_id, cx)?;
+    let worktree = worktree.read(cx);
+    if !worktree.is_local() {
+        return None;
+    }
+    let root = worktree.root_dir()?;
+    Some(
+        root.join(paths::local_settings_folder_name())
+            .join(PROJECT_WORD_LIST_FILE_NAME)
+            .into(),
+    )
+}
+
+/// Loads a bundled dictionary by name, or a Hunspell dictionary from the path of its
+/// `.dic` file.
+async fn load_dictionary(
+    name: &str,
+    fs: &dyn Fs,
+    asset_source: &dyn AssetSource,
+    cx: &gpui::AsyncApp,
+) -> Result<Dictionary> {
+    let (aff, dic) = if name.ends_with(".dic") {
+        let dic_path = PathBuf::from(shellexpand::tilde(name).as_ref());
+        let aff_path = dic_path.with_extension("aff");
+        (fs.load(&aff_path).await?, fs.load(&dic_path).await?)
+    } else {
+        let load_asset = |path: String| -> Result<String> {
+            let bytes = asset_source
+                .load(&path)?
+                .with_context(|| format!("no bundled dictionary named {name:?}"))?;
+            Ok(String::from_utf8(bytes.into_owned())?)
+        };
+        (
+            load_asset(format!("dictionaries/{name}.aff"))?,
+            load_asset(format!("dictionaries/{name}.dic"))?,
+        )
+    };
+    cx.background_spawn(async move { Dictionary::parse(&aff, &dic) })
+        .await
+}
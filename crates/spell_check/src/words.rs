@@ -0,0 +1,387 @@
+use language::{BufferSnapshot, LanguageName, ToOffset as _};
+use std::ops::Range;
+
+/// Words shorter than this are never reported, since they're mostly abbreviations.
+const MIN_WORD_LEN: usize = 3;
+
+/// Languages whose whole text is prose, rather than just their comments and strings.
+const PROSE_LANGUAGES: &[&str] = &["Markdown", "Markdown-Inline", "Git Commit"];
+
+/// Captures in code whose text is checked.
+const CHECKED_CODE_CAPTURES: &[&str] = &["comment", "string"];
+
+/// Captures nested inside checked code captures that aren't prose.
+const SKIPPED_CODE_CAPTURES: &[&str] = &["string.escape", "string.regex", "string.special"];
+
+/// Captures in prose languages that hold code, links or metadata.
+const SKIPPED_PROSE_CAPTURES: &[&str] = &[
+    "comment",
+    "constant",
+    "link_uri",
+    "markup.link.url",
+    "punctuation.embedded",
+    "string",
+    "tag",
+    "text.literal",
+    "variable",
+];
+
+/// Syntax nodes in prose languages that hold code but have no capture of their own.
+const SKIPPED_PROSE_NODES: &[&str] = &["fenced_code_block", "indented_code_block"];
+
+/// A word to check, with its byte range in the text it was found in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Word<'a> {
+    pub range: Range<usize>,
+    pub text: &'a str,
+}
+
+pub fn is_prose_language(name: &LanguageName) -> bool {
+    PROSE_LANGUAGES.contains(&name.as_ref())
+}
+
+/// Returns the sorted, disjoint byte ranges of `snapshot` whose text is checked: the
+/// comments and strings of code, and all of the text of prose languages except code spans,
+/// code blocks and links.
+pub fn checked_ranges(snapshot: &BufferSnapshot) -> Vec<Range<usize>> {
+    let mut prose_ranges = Vec::new();
+    let mut skipped_prose_ranges = Vec::new();
+    if snapshot
+        .language()
+        .is_some_and(|language| is_prose_language(&language.name()))
+    {
+        prose_ranges.push(0..snapshot.len());
+    }
+    // Layers are ordered by depth, so every layer after the first one is injected.
+    for (layer_ix, layer) in snapshot.syntax_layers().enumerate() {
+        let layer_ranges = match layer.included_sub_ranges {
+            Some(ranges) => ranges
+                .iter()
+                .map(|range| range.start.to_offset(snapshot)..range.end.to_offset(snapshot))
+                .collect(),
+            None => vec![layer.node().byte_range()],
+        };
+        if is_prose_language(&layer.language.name()) {
+            prose_ranges.extend(layer_ranges);
+            collect_nodes(layer.node(), SKIPPED_PROSE_NODES, &mut skipped_prose_ranges);
+        } else if layer_ix > 0 {
+            skipped_prose_ranges.extend(layer_ranges);
+        }
+    }
+
+    let mut code_ranges = Vec::new();
+    let mut skipped_code_ranges = Vec::new();
+    let mut matches = snapshot.matches(0..snapshot.len(), |grammar| {
+        grammar
+            .highlights_config
+            .as_ref()
+            .map(|config| &config.query)
+    });
+    while let Some(mat) = matches.peek() {
+        let is_prose = is_prose_language(&mat.language.name());
+        let capture_names = matches.grammars()[mat.grammar_index]
+            .highlights_config
+            .as_ref()
+            .map(|config| config.query.capture_names())
+            .unwrap_or_default();
+        for capture in mat.captures {
+            let Some(name) = capture_names.get(capture.index as usize) else {
+                continue;
+            };
+            let range = capture.node.byte_range();
+            if is_prose {
+                if matches_any_capture(name, SKIPPED_PROSE_CAPTURES) {
+                    skipped_prose_ranges.push(range);
+                }
+            } else if matches_any_capture(name, SKIPPED_CODE_CAPTURES) {
+                skipped_code_ranges.push(range);
+            } else if matches_any_capture(name, CHECKED_CODE_CAPTURES) {
+                code_ranges.push(range);
+            }
+        }
+        matches.advance();
+    }
+
+    let mut ranges = subtract_ranges(
+        merge_ranges(prose_ranges),
+        &merge_ranges(skipped_prose_ranges),
+    );
+    ranges.extend(subtract_ranges(
+        merge_ranges(code_ranges),
+        &merge_ranges(skipped_code_ranges),
+    ));
+    merge_ranges(ranges)
+}
+
+/// Splits `text` into the words to check. Identifiers are split into their camelCase and
+/// snake_case parts, while code spans, URLs, paths, acronyms and tokens containing digits
+/// are skipped.
+pub fn words(text: &str) -> Vec<Word<'_>> {
+    let code_spans = code_span_ranges(text);
+    let mut words = Vec::new();
+    for (chunk_start, chunk) in chunks(text) {
+        if is_code_like(chunk) {
+            continue;
+        }
+        for run in identifier_runs(chunk) {
+            let run_start = chunk_start + run.start;
+            if code_spans
+                .iter()
+                .any(|span| span.start <= run_start && run_start < span.end)
+            {
+                continue;
+            }
+            let run_text = &chunk[run.clone()];
+            if run_text.chars().any(|char| char.is_ascii_digit()) {
+                continue;
+            }
+            for part in identifier_parts(run_text) {
+                let part_text = &run_text[part.clone()];
+                if part_text.chars().count() < MIN_WORD_LEN
+                    || part_text.chars().all(|char| !char.is_lowercase())
+                {
+                    continue;
+                }
+                words.push(Word {
+                    range: run_start + part.start..run_start + part.end,
+                    text: part_text,
+                });
+            }
+        }
+    }
+    words
+}
+
+fn matches_any_capture(name: &str, prefixes: &[&str]) -> bool {
+    prefixes.iter().any(|prefix| {
+        name.strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+    })
+}
+
+fn collect_nodes(node: language::Node, kinds: &[&str], ranges: &mut Vec<Range<usize>>) {
+    let mut cursor = node.walk();
+    loop {
+        let node = cursor.node();
+        if kinds.contains(&node.kind()) {
+            ranges.push(node.byte_range());
+        } else if cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                return;
+            }
+        }
+    }
+}
+
+fn merge_ranges(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.sort_unstable_by_key(|range| (range.start, range.end));
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        if range.is_empty() {
+            continue;
+        }
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Removes `skipped` from `ranges`. Both must be sorted and disjoint.
+fn subtract_ranges(ranges: Vec<Range<usize>>, skipped: &[Range<usize>]) -> Vec<Range<usize>> {
+    let mut result = Vec::with_capacity(ranges.len());
+    let mut skipped = skipped.iter().peekable();
+    for mut range in ranges {
+        while let Some(skip) = skipped.peek() {
+            if skip.end <= range.start {
+                skipped.next();
+                continue;
+            }
+            if skip.start >= range.end {
+                break;
+            }
+            if skip.start > range.start {
+                result.push(range.start..skip.start);
+            }
+            if skip.end >= range.end {
+                range.start = range.end;
+                break;
+            }
+            range.start = skip.end;
+            skipped.next();
+        }
+        if !range.is_empty() {
+            result.push(range);
+        }
+    }
+    result
+}
+
+/// Returns the whitespace-separated chunks of `text`, with their byte offsets.
+fn chunks(text: &str) -> Vec<(usize, &str)> {
+    let mut chunks = Vec::new();
+    let mut chunk_start = None;
+    for (offset, char) in text.char_indices() {
+        if char.is_whitespace() {
+            if let Some(start) = chunk_start.take() {
+                chunks.push((start, &text[start..offset]));
+            }
+        } else {
+            chunk_start.get_or_insert(offset);
+        }
+    }
+    if let Some(start) = chunk_start {
+        chunks.push((start, &text[start..]));
+    }
+    chunks
+}
+
+/// Returns the byte ranges enclosed in backticks, which hold code even in comments.
+fn code_span_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut line_start = 0;
+    for line in text.split_inclusive('\n') {
+        let mut backticks = line.match_indices('`').map(|(ix, _)| line_start + ix);
+        while let (Some(start), Some(end)) = (backticks.next(), backticks.next()) {
+            ranges.push(start..end + 1);
+        }
+        line_start += line.len();
+    }
+    ranges
+}
+
+fn is_code_like(chunk: &str) -> bool {
+    if ["://", "::", "/", "\\", "@", "=", "$", "%", "->", "=>"]
+        .iter()
+        .any(|pattern| chunk.contains(pattern))
+    {
+        return true;
+    }
+
+    // Dotted names like `foo.bar` or `config.json`, as opposed to the end of a sentence.
+    let chars = chunk.chars().collect::<Vec<_>>();
+    chars.windows(3).any(|window| {
+        window[1] == '.' && window[0].is_alphanumeric() && window[2].is_alphanumeric()
+    })
+}
+
+/// Returns the runs of word characters in `chunk`. Apostrophes are kept when they're
+/// surrounded by letters, as in contractions.
+fn identifier_runs(chunk: &str) -> Vec<Range<usize>> {
+    let is_word_char = |char: char| char.is_alphanumeric() || char == '_';
+    let chars = chunk.char_indices().collect::<Vec<_>>();
+    let mut runs = Vec::new();
+    let mut run_start = None;
+    for (ix, &(offset, char)) in chars.iter().enumerate() {
+        let is_apostrophe = matches!(char, '\'' | '’')
+            && ix > 0
+            && chars[ix - 1].1.is_alphabetic()
+            && chars
+                .get(ix + 1)
+                .is_some_and(|(_, next)| next.is_alphabetic());
+        if is_word_char(char) || (is_apostrophe && run_start.is_some()) {
+            run_start.get_or_insert(offset);
+        } else if let Some(start) = run_start.take() {
+            runs.push(start..offset);
+        }
+    }
+    if let Some(start) = run_start {
+        runs.push(start..chunk.len());
+    }
+    runs
+}
+
+/// Splits an identifier into its words, at underscores and at camelCase boundaries.
+/// A run of capitals followed by a lowercase letter ends before its last capital, so
+/// `HTTPServer` becomes `HTTP` and `Server`.
+fn identifier_parts(identifier: &str) -> Vec<Range<usize>> {
+    let chars = identifier.char_indices().collect::<Vec<_>>();
+    let mut parts = Vec::new();
+    let mut part_start = None;
+    for (ix, &(offset, char)) in chars.iter().enumerate() {
+        if char == '_' {
+            if let Some(start) = part_start.take() {
+                parts.push(start..offset);
+            }
+            continue;
+        }
+
+        if let Some(start) = part_start
+            && char.is_uppercase()
+        {
+            let previous = chars[ix - 1].1;
+            let next_is_lowercase = chars
+                .get(ix + 1)
+                .is_some_and(|(_, next)| next.is_lowercase());
+            if previous.is_lowercase() || (previous.is_uppercase() && next_is_lowercase) {
+                parts.push(start..offset);
+                part_start = Some(offset);
+            }
+        }
+        part_start.get_or_insert(offset);
+    }
+    if let Some(start) = part_start {
+        parts.push(start..identifier.len());
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{AppContext as _, Entity, TestAppContext};
+    use language::{Buffer, markdown_lang, rust_lang};
+
+    fn word_texts(text: &str) -> Vec<&str> {
+        words(text).into_iter().map(|word| word.text).collect()
+    }
+
+    fn checked_words(buffer: &Entity<Buffer>, cx: &mut TestAppContext) -> Vec<String> {
+        let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+        let text = snapshot.text();
+        checked_ranges(&snapshot)
+            .into_iter()
+            .flat_map(|range| words(&text[range]))
+            .map(|word| word.text.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_words() {
+        assert_eq!(
+            word_texts("parseHTTPResponse max_retry_count"),
+            ["parse", "Response", "max", "retry", "count"]
+        );
+        assert_eq!(
+            word_texts("don't use `teh_value`, see https://exmaple.com or src/recieve.rs"),
+            ["don't", "use", "see"]
+        );
+        assert_eq!(
+            word_texts("The TODO list has utf8 and x86_64 in it."),
+            ["The", "list", "has", "and"]
+        );
+        let text = "// check  wrongg here";
+        let word = words(text).into_iter().nth(1).unwrap();
+        assert_eq!(&text[word.range], "wrongg");
+    }
+
+    #[gpui::test]
+    async fn test_checked_ranges_in_code(cx: &mut TestAppContext) {
+        let text = "// A commment.\nfn misspeled() {\n    let s = \"strng\\n\";\n}\n";
+        let buffer = cx.new(|cx| Buffer::local(text, cx).with_language(rust_lang(), cx));
+        cx.run_until_parked();
+        assert_eq!(checked_words(&buffer, cx), ["commment", "strng"]);
+    }
+
+    #[gpui::test]
+    async fn test_checked_ranges_in_markdown(cx: &mut TestAppContext) {
+        let text = "# Titel\n\nSome `cdoe` and mroe.\n\n```\nlet value = 1;\n```\n";
+        let buffer = cx.new(|cx| Buffer::local(text, cx).with_language(markdown_lang(), cx));
+        cx.run_until_parked();
+        assert_eq!(checked_words(&buffer, cx), ["Titel", "Some", "and", "mroe"]);
+    }
+}
@@ -18,7 +18,7 @@ use regex::Regex;
 use schemars::JsonSchema;
 use search::{BufferSearchBar, SearchOptions};
 use serde::Deserialize;
-use settings::{Settings, SettingsStore};
+use settings::{ActionSequence, Settings, SettingsStore};
 use std::{
     iter::Peekable,
     ops::{Deref, Range},
@@ -41,6 +41,7 @@ use zed_actions::{OpenDocs, RevealTarget};
 
 use crate::{
     ToggleMarksView, ToggleRegistersView, Vim, VimSettings,
+    helix::HelixPipeSelections,
     motion::{EndOfDocument, Motion, MotionKind, StartOfDocument},
     normal::{
         JoinLines,
@@ -1756,6 +1757,24 @@ fn generate_commands(_: &App) -> Vec<VimCommand> {
         VimCommand::new(("sort i", ""), SortLinesCaseInsensitive)
             .range(select_range)
             .default_range(CommandRange::buffer()),
+        VimCommand::new(("pipe", ""), ArgumentRequired).args(|_, args| {
+            Some(
+                HelixPipeSelections {
+                    command: args,
+                    replace: true,
+                }
+                .boxed_clone(),
+            )
+        }),
+        VimCommand::new(("pipe-to", ""), ArgumentRequired).args(|_, args| {
+            Some(
+                HelixPipeSelections {
+                    command: args,
+                    replace: false,
+                }
+                .boxed_clone(),
+            )
+        }),
         VimCommand::str(("E", "xplore"), "project_panel::ToggleFocus"),
         VimCommand::str(("H", "explore"), "project_panel::ToggleFocus"),
         VimCommand::str(("L", "explore"), "project_panel::ToggleFocus"),
@@ -1929,6 +1948,13 @@ pub fn command_interceptor(
         });
     }
 
+    if let Some(item) = custom_command(&range_prefix, query, &range, true, cx) {
+        return Task::ready(CommandInterceptResult {
+            results: vec![item],
+            exclusive: false,
+        });
+    }
+
     let Some((mut results, filenames)) =
         commands(cx).iter().enumerate().find_map(|(idx, command)| {
             let action = command.parse(query, &range, cx)?;
@@ -1968,7 +1994,12 @@ pub fn command_interceptor(
             ))
         })
     else {
-        return Task::ready(CommandInterceptResult::default());
+        return Task::ready(CommandInterceptResult {
+            results: custom_command(&range_prefix, query, &range, false, cx)
+                .into_iter()
+                .collect(),
+            exclusive: false,
+        });
     };
 
     if let Some((cmd_idx, parsed_query, display_string, no_args_positions)) = filenames {
@@ -2029,6 +2060,70 @@ pub fn command_interceptor(
     }
 }
 
+/// Parses an invocation of one of the user's `vim.custom_commands`. Unless `exact` is set, the
+/// name may be abbreviated to any prefix that matches a single command.
+fn custom_command(
+    range_prefix: &str,
+    query: &str,
+    range: &Option<CommandRange>,
+    exact: bool,
+    cx: &App,
+) -> Option<CommandInterceptItem> {
+    if !query.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return None;
+    }
+    let name_len = query
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(query.len());
+    let (name, args) = query.split_at(name_len);
+    let args = args.trim();
+
+    let custom_commands = &VimSettings::get_global(cx).custom_commands;
+    let (name, command) = if exact {
+        custom_commands.get_key_value(name)?
+    } else {
+        custom_commands
+            .iter()
+            .filter(|(candidate, _)| candidate.starts_with(name))
+            .exactly_one()
+            .ok()?
+    };
+
+    let range = range.clone().or_else(|| {
+        let (range, rest) = VimCommand::parse_range(command.default_range.as_deref()?);
+        rest.trim().is_empty().then_some(range).flatten()
+    });
+    let action = if let Some(shell) = &command.shell {
+        ShellExec {
+            command: shell.replace("<args>", args),
+            range,
+            is_read: false,
+        }
+        .boxed_clone()
+    } else {
+        let actions = command.actions.clone().unwrap_or_default();
+        let action = ActionSequence::build_sequence(serde_json::Value::Array(actions), cx)
+            .map_err(|error| anyhow!("invalid actions for command `{name}`: {error}"))
+            .log_err()?;
+        match range {
+            Some(range) => select_range(action, &range)?,
+            None => action,
+        }
+    };
+
+    let string = if args.is_empty() {
+        format!(":{range_prefix}{name}")
+    } else {
+        format!(":{range_prefix}{name} {args}")
+    };
+    let positions = generate_positions(&string, &format!("{range_prefix}{query}"));
+    Some(CommandInterceptItem {
+        action,
+        string,
+        positions,
+    })
+}
+
 fn generate_positions(string: &str, query: &str) -> Vec<usize> {
     let mut positions = Vec::new();
     let mut chars = query.chars();
@@ -2294,7 +2389,7 @@ impl Vim {
         }
     }
 
-    fn prepare_shell_command(
+    pub(crate) fn prepare_shell_command(
         &mut self,
         command: &str,
         _: &mut Window,
@@ -2635,10 +2730,12 @@ mod test {
         state::Mode,
         test::{NeovimBackedTestContext, VimTestContext},
     };
+    use collections::HashMap;
     use editor::{Editor, EditorSettings};
     use gpui::{Context, TestAppContext};
     use indoc::indoc;
-    use settings::Settings;
+    use serde_json::json;
+    use settings::{Settings, SettingsStore, VimCustomCommandContent};
     use util::path;
     use workspace::{OpenOptions, Workspace};
 
@@ -2760,6 +2857,34 @@ mod test {
         assert_eq!(fs.load(path).await.unwrap().replace("\r\n", "\n"), "@@\n");
     }
 
+    #[gpui::test]
+    async fn test_custom_commands(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings(cx, |s| {
+                s.vim.get_or_insert_default().custom_commands = Some(HashMap::from_iter([(
+                    "Remove".to_string(),
+                    VimCustomCommandContent {
+                        actions: Some(vec![json!("editor::DeleteLine")]),
+                        ..Default::default()
+                    },
+                )]));
+            });
+        });
+
+        cx.set_state("ˇa\nb\nc", Mode::Normal);
+        cx.simulate_keystrokes(":");
+        cx.simulate_input("Remove");
+        cx.simulate_keystrokes("enter");
+        cx.assert_state("ˇb\nc", Mode::Normal);
+
+        // Commands can be abbreviated.
+        cx.simulate_keystrokes(":");
+        cx.simulate_input("Rem");
+        cx.simulate_keystrokes("enter");
+        cx.assert_state("ˇc", Mode::Normal);
+    }
+
     #[gpui::test]
     async fn test_command_read(cx: &mut TestAppContext) {
         let mut cx = VimTestContext::new(cx, true).await;
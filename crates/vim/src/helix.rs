@@ -2,12 +2,18 @@ mod boundary;
 mod duplicate;
 mod object;
 mod paste;
+mod pipe;
 mod select;
 mod surround;
+mod syntax;
+
+pub use pipe::HelixPipeSelections;
+
+use std::ops::Range;
 
 use editor::display_map::DisplaySnapshot;
 use editor::{
-    DisplayPoint, Editor, EditorSettings, HideMouseCursorOrigin, MultiBufferOffset,
+    Anchor, DisplayPoint, Editor, EditorSettings, HideMouseCursorOrigin, MultiBufferOffset,
     SelectionEffects, ToOffset, ToPoint, movement,
 };
 use gpui::actions;
@@ -42,6 +48,18 @@ actions!(
         HelixSelectLine,
         /// Select all matches of a given pattern within the current selection.
         HelixSelectRegex,
+        /// Split the current selections on all matches of a given pattern.
+        HelixSplitSelection,
+        /// Shrink the selection to the previously selected syntax node, or to the first child node.
+        HelixShrinkSelection,
+        /// Select all named children of the syntax nodes covered by the selections.
+        HelixSelectAllChildren,
+        /// Select all named siblings of the syntax nodes covered by the selections.
+        HelixSelectAllSiblings,
+        /// Pipe each selection through a shell command, replacing it with the output.
+        HelixPipe,
+        /// Pipe each selection through a shell command, ignoring the output.
+        HelixPipeTo,
         /// Removes all but the one selection that was created last.
         /// `Newest` can eventually be `Primary`.
         HelixKeepNewestSelection,
@@ -68,6 +86,17 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     Vim::action(editor, cx, Vim::helix_goto_last_modification);
     Vim::action(editor, cx, Vim::helix_paste);
     Vim::action(editor, cx, Vim::helix_select_regex);
+    Vim::action(editor, cx, Vim::helix_split_selection);
+    Vim::action(editor, cx, Vim::helix_shrink_selection);
+    Vim::action(editor, cx, Vim::helix_select_all_children);
+    Vim::action(editor, cx, Vim::helix_select_all_siblings);
+    Vim::action(editor, cx, Vim::helix_pipe);
+    Vim::action(editor, cx, Vim::helix_pipe_to);
+    Vim::action(
+        editor,
+        cx,
+        |vim, action: &HelixPipeSelections, window, cx| action.run(vim, window, cx),
+    );
     Vim::action(editor, cx, Vim::helix_keep_newest_selection);
     Vim::action(editor, cx, |vim, _: &HelixDuplicateBelow, window, cx| {
         let times = Vim::take_count(cx);
@@ -529,6 +558,24 @@ impl Vim {
         _: &HelixSelectRegex,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) {
+        self.start_helix_regex_search(false, window, cx);
+    }
+
+    fn helix_split_selection(
+        &mut self,
+        _: &HelixSplitSelection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.start_helix_regex_search(true, window, cx);
+    }
+
+    fn start_helix_regex_search(
+        &mut self,
+        split: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) {
         Vim::take_forced_motion(cx);
         let Some(pane) = self.pane(window, cx) else {
@@ -575,6 +622,7 @@ impl Vim {
                         prior_operator: self.operator_stack.last().cloned(),
                         prior_mode: self.mode,
                         helix_select: true,
+                        helix_split: split,
                         _dismiss_subscription: None,
                     }
                 });
@@ -729,6 +777,45 @@ impl Vim {
         });
     }
 
+    /// Replaces each of `prior_selections` with the parts of it that lie between the currently
+    /// selected matches.
+    pub(crate) fn helix_split_selections(
+        &mut self,
+        prior_selections: Vec<Range<Anchor>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(cx, |_, editor, cx| {
+            let buffer = editor.buffer().read(cx).snapshot(cx);
+            let matches = editor
+                .selections
+                .all::<MultiBufferOffset>(&editor.display_snapshot(cx))
+                .into_iter()
+                .map(|selection| selection.range())
+                .collect::<Vec<_>>();
+            let mut pieces = Vec::new();
+            for selection in prior_selections {
+                let selection =
+                    selection.start.to_offset(&buffer)..selection.end.to_offset(&buffer);
+                let mut start = selection.start;
+                for range in &matches {
+                    if range.start >= start && range.end <= selection.end && !range.is_empty() {
+                        pieces.push(start..range.start);
+                        start = range.end;
+                    }
+                }
+                pieces.push(start..selection.end);
+            }
+            pieces.retain(|range| !range.is_empty());
+            if pieces.is_empty() {
+                return;
+            }
+            editor.change_selections(Default::default(), window, cx, |s| {
+                s.select_ranges(pieces);
+            });
+        });
+    }
+
     fn helix_keep_newest_selection(
         &mut self,
         _: &HelixKeepNewestSelection,
@@ -859,6 +946,7 @@ impl Vim {
 
 #[cfg(test)]
 mod test {
+    use editor::MultiBufferOffset;
     use gpui::{UpdateGlobal, VisualTestContext};
     use indoc::indoc;
     use project::FakeFs;
@@ -1561,6 +1649,36 @@ mod test {
         // cx.assert_state("ˇstuff one two one", Mode::HelixNormal);
     }
 
+    #[gpui::test]
+    async fn test_helix_split_selection(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+
+        cx.set_state("ˇone two one", Mode::HelixNormal);
+        cx.simulate_keystrokes("x shift-s t w o");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("enter");
+        cx.assert_state("«one ˇ»two« oneˇ»", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_helix_syntax_tree_selections(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+
+        cx.set_state("fn a(«x: u8, y: u8ˇ») {}", Mode::HelixNormal);
+        cx.simulate_keystrokes("alt-shift-i");
+        cx.assert_state("fn a(«x: u8ˇ», «y: u8ˇ») {}", Mode::HelixNormal);
+
+        cx.set_state("fn a(«x: u8ˇ», y: u8) {}", Mode::HelixNormal);
+        cx.simulate_keystrokes("alt-a");
+        cx.assert_state("fn a(«x: u8ˇ», «y: u8ˇ») {}", Mode::HelixNormal);
+
+        cx.set_state("fn a(«x: u8, y: u8ˇ») {}", Mode::HelixNormal);
+        cx.simulate_keystrokes("alt-i");
+        cx.assert_state("fn a(«x: u8ˇ», y: u8) {}", Mode::HelixNormal);
+    }
+
     #[gpui::test]
     async fn test_helix_select_next_match(cx: &mut gpui::TestAppContext) {
         let mut cx = VimTestContext::new(cx, true).await;
@@ -1848,4 +1966,78 @@ mod test {
             Mode::HelixSelect,
         );
     }
+
+    #[gpui::test]
+    async fn test_pipe_outputs_over_several_selections(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+
+        let piped_selections = |cx: &mut VimTestContext| {
+            cx.update_editor(|editor, window, cx| {
+                let vim = editor.addon::<VimAddon>().unwrap().entity.clone();
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                let outputs = editor
+                    .selections
+                    .all::<MultiBufferOffset>(&editor.display_snapshot(cx))
+                    .into_iter()
+                    .map(|selection| {
+                        let text = snapshot
+                            .text_for_range(selection.start..selection.end)
+                            .collect::<String>();
+                        (
+                            snapshot.anchor_before(selection.start)
+                                ..snapshot.anchor_after(selection.end),
+                            format!("<{}>", text.to_uppercase()),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                (vim, outputs)
+            })
+        };
+
+        cx.set_state(
+            indoc! {"
+            «oneˇ» two
+            three «fourˇ»
+            «fiveˇ» six"},
+            Mode::HelixNormal,
+        );
+        let (vim, outputs) = piped_selections(&mut cx);
+        cx.update(|window, cx| {
+            vim.update(cx, |vim, cx| {
+                vim.apply_pipe_outputs(outputs, false, window, cx)
+            })
+        });
+        cx.assert_state(
+            indoc! {"
+            «oneˇ» two
+            three «fourˇ»
+            «fiveˇ» six"},
+            Mode::HelixNormal,
+        );
+
+        let (vim, outputs) = piped_selections(&mut cx);
+        cx.update(|window, cx| {
+            vim.update(cx, |vim, cx| {
+                vim.apply_pipe_outputs(outputs, true, window, cx)
+            })
+        });
+        cx.assert_state(
+            indoc! {"
+            «<ONE>ˇ» two
+            three «<FOUR>ˇ»
+            «<FIVE>ˇ» six"},
+            Mode::HelixNormal,
+        );
+
+        // All the outputs are replaced in a single transaction.
+        cx.simulate_keystrokes("u");
+        cx.assert_state(
+            indoc! {"
+            «oneˇ» two
+            three «fourˇ»
+            «fiveˇ» six"},
+            Mode::HelixNormal,
+        );
+    }
 }
@@ -0,0 +1,174 @@
+use std::{ops::Range, process::Stdio};
+
+use anyhow::{Context as _, anyhow};
+use editor::{Anchor, MultiBufferOffset};
+use futures::AsyncWriteExt as _;
+use gpui::{Action, AppContext as _, Context, Window};
+use util::ResultExt;
+use workspace::notifications::NotifyResultExt;
+
+use crate::Vim;
+
+use super::{HelixPipe, HelixPipeTo};
+
+/// Pipes each selection through a shell command.
+///
+/// When `replace` is set, every selection is replaced by the command's output and the
+/// outputs are selected, otherwise the output is discarded.
+#[derive(Clone, Debug, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub struct HelixPipeSelections {
+    pub(crate) command: String,
+    pub(crate) replace: bool,
+}
+
+impl Vim {
+    pub(super) fn helix_pipe(
+        &mut self,
+        _: &HelixPipe,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.open_pipe_prompt("pipe ", window, cx);
+    }
+
+    pub(super) fn helix_pipe_to(
+        &mut self,
+        _: &HelixPipeTo,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.open_pipe_prompt("pipe-to ", window, cx);
+    }
+
+    fn open_pipe_prompt(&mut self, query: &str, window: &mut Window, cx: &mut Context<Self>) {
+        Vim::take_count(cx);
+        Vim::take_forced_motion(cx);
+        let Some(workspace) = self.workspace(window, cx) else {
+            return;
+        };
+        workspace.update(cx, |workspace, cx| {
+            command_palette::CommandPalette::toggle(workspace, query, window, cx);
+        })
+    }
+}
+
+impl HelixPipeSelections {
+    pub fn run(&self, vim: &mut Vim, window: &mut Window, cx: &mut Context<Vim>) {
+        let Some(workspace) = vim.workspace(window, cx) else {
+            return;
+        };
+        let project = workspace.read(cx).project().clone();
+        let command = vim.prepare_shell_command(&self.command, window, cx);
+
+        let Some(inputs) = vim.update_editor(cx, |_, editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            editor
+                .selections
+                .all::<MultiBufferOffset>(&editor.display_snapshot(cx))
+                .into_iter()
+                .map(|selection| {
+                    let range = snapshot.anchor_before(selection.start)
+                        ..snapshot.anchor_after(selection.end);
+                    let text = snapshot
+                        .text_for_range(selection.start..selection.end)
+                        .collect::<String>();
+                    (range, text)
+                })
+                .collect::<Vec<_>>()
+        }) else {
+            return;
+        };
+
+        let replace = self.replace;
+
+        // The command runs once per selection, and each process is only started once the
+        // previous one has exited, so piping many selections never spawns them all at once.
+        let task = cx.spawn_in(window, async move |vim, cx| {
+            let mut outputs = Vec::with_capacity(inputs.len());
+            for (range, input) in inputs {
+                let process =
+                    project.update(cx, |project, cx| project.exec_in_shell(command.clone(), cx));
+                let output = async {
+                    let mut process = process.await?;
+                    process.stdin(Stdio::piped());
+                    process.stdout(Stdio::piped());
+                    process.stderr(Stdio::piped());
+                    let mut running = process.spawn()?;
+                    if let Some(mut stdin) = running.stdin.take() {
+                        cx.background_spawn(async move {
+                            if stdin.write_all(input.as_bytes()).await.log_err().is_some() {
+                                stdin.flush().await.log_err();
+                            }
+                        })
+                        .detach();
+                    }
+                    let output = cx
+                        .background_spawn(running.output())
+                        .await
+                        .with_context(|| format!("running `{command}`"))?;
+                    if !output.status.success() {
+                        return Err(anyhow!(
+                            "`{command}` failed: {}",
+                            String::from_utf8_lossy(&output.stderr).trim()
+                        ));
+                    }
+                    anyhow::Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+                }
+                .await;
+
+                match output {
+                    Ok(output) => outputs.push((range, output)),
+                    Err(error) => {
+                        vim.update_in(cx, |vim, window, cx| {
+                            vim.cancel_running_command(window, cx);
+                            if let Some(workspace) = vim.workspace(window, cx) {
+                                workspace.update(cx, |workspace, cx| {
+                                    Err::<(), _>(error).notify_err(workspace, cx);
+                                });
+                            }
+                        })
+                        .log_err();
+                        return;
+                    }
+                }
+            }
+
+            vim.update_in(cx, |vim, window, cx| {
+                vim.apply_pipe_outputs(outputs, replace, window, cx);
+            })
+            .log_err();
+        });
+        vim.running_command.replace(task);
+    }
+}
+
+impl Vim {
+    /// Finishes a [`HelixPipeSelections`] run by replacing each piped range with its
+    /// output and selecting the outputs, or by leaving the buffer untouched when the
+    /// output is discarded.
+    pub(crate) fn apply_pipe_outputs(
+        &mut self,
+        outputs: Vec<(Range<Anchor>, String)>,
+        replace: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if replace {
+            self.update_editor(cx, |_, editor, cx| {
+                editor.transact(window, cx, |editor, window, cx| {
+                    editor.edit(
+                        outputs
+                            .iter()
+                            .map(|(range, text)| (range.clone(), text.clone())),
+                        cx,
+                    );
+                    editor.change_selections(Default::default(), window, cx, |s| {
+                        s.select_anchor_ranges(outputs.into_iter().map(|(range, _)| range));
+                    });
+                });
+            });
+        }
+        self.cancel_running_command(window, cx);
+    }
+}
@@ -0,0 +1,99 @@
+use std::ops::Range;
+
+use editor::{Editor, MultiBufferOffset, actions::SelectSmallerSyntaxNode};
+use multi_buffer::MultiBufferSnapshot;
+use ui::{Context, Window};
+
+use crate::Vim;
+
+use super::{HelixSelectAllChildren, HelixSelectAllSiblings, HelixShrinkSelection};
+
+impl Vim {
+    /// Replaces each selection with the named children of the syntax node it covers.
+    pub(super) fn helix_select_all_children(
+        &mut self,
+        _: &HelixSelectAllChildren,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(cx, |_, editor, cx| {
+            select_syntax_nodes(editor, window, cx, MultiBufferSnapshot::syntax_child_ranges);
+        });
+    }
+
+    /// Replaces each selection with the named siblings of the syntax node it covers,
+    /// including the node itself.
+    pub(super) fn helix_select_all_siblings(
+        &mut self,
+        _: &HelixSelectAllSiblings,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(cx, |_, editor, cx| {
+            select_syntax_nodes(
+                editor,
+                window,
+                cx,
+                MultiBufferSnapshot::syntax_sibling_ranges,
+            );
+        });
+    }
+
+    /// Undoes the last syntax node expansion, or selects the first child of each selected
+    /// node when there is nothing to undo.
+    pub(super) fn helix_shrink_selection(
+        &mut self,
+        _: &HelixShrinkSelection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(cx, |_, editor, cx| {
+            let before = selected_ranges(editor, cx);
+            editor.select_smaller_syntax_node(&SelectSmallerSyntaxNode, window, cx);
+            if selected_ranges(editor, cx) != before {
+                return;
+            }
+            select_syntax_nodes(editor, window, cx, |buffer, range| {
+                buffer
+                    .syntax_child_ranges(range)
+                    .into_iter()
+                    .take(1)
+                    .collect()
+            });
+        });
+    }
+}
+
+fn selected_ranges(editor: &Editor, cx: &mut Context<Editor>) -> Vec<Range<MultiBufferOffset>> {
+    editor
+        .selections
+        .all::<MultiBufferOffset>(&editor.display_snapshot(cx))
+        .into_iter()
+        .map(|selection| selection.range())
+        .collect()
+}
+
+/// Replaces every selection with the ranges returned by `nodes`, leaving selections for which
+/// no nodes are found untouched.
+fn select_syntax_nodes(
+    editor: &mut Editor,
+    window: &mut Window,
+    cx: &mut Context<Editor>,
+    nodes: impl Fn(&MultiBufferSnapshot, Range<MultiBufferOffset>) -> Vec<Range<MultiBufferOffset>>,
+) {
+    let buffer = editor.buffer().read(cx).snapshot(cx);
+    let mut new_ranges = Vec::new();
+    for range in selected_ranges(editor, cx) {
+        let ranges = nodes(&buffer, range.clone());
+        if ranges.is_empty() {
+            new_ranges.push(range);
+        } else {
+            new_ranges.extend(ranges);
+        }
+    }
+    new_ranges.sort_by_key(|range| range.start);
+    new_ranges.dedup();
+    editor.change_selections(Default::default(), window, cx, |s| {
+        s.select_ranges(new_ranges);
+    });
+}
@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::VecDeque, ops::Range, rc::Rc};
 
 use crate::{
     Vim,
@@ -7,8 +7,10 @@ use crate::{
     normal::InsertBefore,
     state::{Mode, Operator, RecordedSelection, ReplayableAction, VimGlobals},
 };
-use editor::Editor;
+use editor::{Anchor, Editor, SelectionEffects};
 use gpui::{Action, App, Context, Window, actions};
+use language::TransactionId;
+use search::ProjectSearchView;
 use workspace::Workspace;
 
 actions!(
@@ -25,6 +27,34 @@ actions!(
     ]
 );
 
+/// Moves a per-cursor macro replay on to its next cursor.
+#[derive(Clone, Debug, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+struct ReplayAtNextCursor;
+
+/// A macro being replayed independently at each of a set of cursors.
+pub struct CursorReplay {
+    mode: Mode,
+    pending: VecDeque<Range<Anchor>>,
+    finished: Vec<Range<Anchor>>,
+    started: bool,
+    pub(crate) transaction: Option<TransactionId>,
+}
+
+impl CursorReplay {
+    /// Ends a replay that stopped before visiting every cursor, grouping the edits made
+    /// up to that point into a single undo step.
+    pub(crate) fn abort(self, editor: &mut Editor, cx: &mut Context<Editor>) {
+        if let Some(transaction) = self.transaction {
+            editor.group_until_transaction(transaction, cx);
+        }
+    }
+}
+
+fn should_observe(action: &dyn Action) -> bool {
+    !ReplayAtNextCursor.partial_eq(action)
+}
+
 fn should_replay(action: &dyn Action) -> bool {
     // skip so that we don't leave the character palette open
     if editor::actions::ShowCharacterPalette.partial_eq(action) {
@@ -81,6 +111,10 @@ pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
         };
         vim.replay_register(register, window, cx)
     });
+
+    Vim::action(editor, cx, |vim, _: &ReplayAtNextCursor, window, cx| {
+        vim.replay_at_next_cursor(window, cx)
+    });
 }
 
 pub struct ReplayerState {
@@ -152,13 +186,25 @@ impl Replayer {
             let globals = Vim::globals(cx);
             globals.replayer.take();
             globals.dot_replaying = false;
+            if let Some(replay) = globals.cursor_replay.take()
+                && let Some(editor) = Workspace::for_window(window, cx).and_then(|workspace| {
+                    workspace
+                        .read(cx)
+                        .active_item(cx)
+                        .and_then(|item| item.act_as::<Editor>(cx))
+                })
+            {
+                editor.update(cx, |editor, cx| replay.abort(editor, cx));
+            }
             return;
         };
         match action {
             ReplayableAction::Action(action) => {
                 if should_replay(&*action) {
                     window.dispatch_action(action.boxed_clone(), cx);
-                    cx.defer(move |cx| Vim::globals(cx).observe_action(action.boxed_clone()));
+                    if should_observe(&*action) {
+                        cx.defer(move |cx| Vim::globals(cx).observe_action(action.boxed_clone()));
+                    }
                 }
             }
             ReplayableAction::Insertion {
@@ -198,26 +244,119 @@ impl Vim {
         self.clear_operator(window, cx)
     }
 
+    /// Replays the macro in `register`. With several cursors, the macro is replayed
+    /// independently at each of them.
     pub(crate) fn replay_register(
         &mut self,
-        mut register: char,
+        register: char,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        let cursors = self
+            .update_editor(cx, |_, editor, _| {
+                editor
+                    .selections
+                    .disjoint_anchor_ranges()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        if cursors.len() > 1 && !self.mode.is_visual() && Vim::globals(cx).cursor_replay.is_none() {
+            self.replay_register_at(register, cursors, window, cx);
+            return;
+        }
+
+        let Some(repeated_actions) = self.take_register_actions(register, window, cx) else {
+            return;
+        };
+        let mut replayer = Vim::globals(cx)
+            .replayer
+            .get_or_insert_with(Replayer::new)
+            .clone();
+        replayer.replay(repeated_actions, window, cx);
+    }
+
+    /// Replays the macro in `register` at the start of every match of the project search
+    /// whose results are shown in the current editor.
+    pub(crate) fn replay_register_at_search_results(
+        &mut self,
+        register: char,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(editor) = self.editor() else {
+            return;
+        };
+        let matches = self
+            .workspace(window, cx)
+            .and_then(|workspace| workspace.read(cx).active_item_as::<ProjectSearchView>(cx))
+            .filter(|search| search.read(cx).results_editor() == &editor)
+            .map(|search| search.read(cx).get_matches(cx))
+            .unwrap_or_default();
+        if matches.is_empty() {
+            self.replay_register(register, window, cx);
+            return;
+        }
+        let cursors = matches
+            .into_iter()
+            .map(|range| range.start..range.start)
+            .collect();
+        self.replay_register_at(register, cursors, window, cx);
+    }
+
+    fn replay_register_at(
+        &mut self,
+        register: char,
+        cursors: Vec<Range<Anchor>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repeated_actions) = self.take_register_actions(register, window, cx) else {
+            return;
+        };
+        let mut actions = Vec::new();
+        for _ in 0..cursors.len() {
+            actions.push(ReplayableAction::Action(ReplayAtNextCursor.boxed_clone()));
+            actions.extend(repeated_actions.iter().cloned());
+        }
+        actions.push(ReplayableAction::Action(ReplayAtNextCursor.boxed_clone()));
+
+        self.update_editor(cx, |_, editor, cx| {
+            editor
+                .buffer()
+                .update(cx, |multi, cx| multi.finalize_last_transaction(cx))
+        });
+        let mode = if self.mode.is_visual() {
+            Mode::Normal
+        } else {
+            self.mode
+        };
+        let globals = Vim::globals(cx);
+        globals.cursor_replay = Some(CursorReplay {
+            mode,
+            pending: cursors.into(),
+            finished: Vec::new(),
+            started: false,
+            transaction: None,
+        });
+        let mut replayer = globals.replayer.get_or_insert_with(Replayer::new).clone();
+        replayer.replay(actions, window, cx);
+    }
+
+    fn take_register_actions(
+        &mut self,
+        mut register: char,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Vec<ReplayableAction>> {
         let mut count = Vim::take_count(cx).unwrap_or(1);
         Vim::take_forced_motion(cx);
         self.clear_operator(window, cx);
 
         let globals = Vim::globals(cx);
         if register == '@' {
-            let Some(last) = globals.last_replayed_register else {
-                return;
-            };
-            register = last;
+            register = globals.last_replayed_register?;
         }
-        let Some(actions) = globals.recordings.get(&register) else {
-            return;
-        };
+        let actions = globals.recordings.get(&register)?;
 
         let mut repeated_actions = vec![];
         while count > 0 {
@@ -226,8 +365,52 @@ impl Vim {
         }
 
         globals.last_replayed_register = Some(register);
-        let mut replayer = globals.replayer.get_or_insert_with(Replayer::new).clone();
-        replayer.replay(repeated_actions, window, cx);
+        Some(repeated_actions)
+    }
+
+    /// Records where the macro left the previous cursor, then moves to the next one. Once
+    /// every cursor has been visited, all resulting cursors are restored and the edits are
+    /// grouped into a single undo step.
+    fn replay_at_next_cursor(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(mut replay) = Vim::globals(cx).cursor_replay.take() else {
+            return;
+        };
+        if replay.started {
+            if let Some(ranges) = self.update_editor(cx, |_, editor, _| {
+                editor
+                    .selections
+                    .disjoint_anchor_ranges()
+                    .collect::<Vec<_>>()
+            }) {
+                replay.finished.extend(ranges);
+            }
+        }
+        replay.started = true;
+        if self.mode != replay.mode {
+            self.switch_mode(replay.mode, false, window, cx);
+        }
+
+        if let Some(cursor) = replay.pending.pop_front() {
+            self.update_editor(cx, |_, editor, cx| {
+                editor.change_selections(
+                    SelectionEffects::no_scroll().nav_history(false),
+                    window,
+                    cx,
+                    |s| s.select_anchor_ranges([cursor]),
+                );
+            });
+            Vim::globals(cx).cursor_replay = Some(replay);
+            return;
+        }
+
+        self.update_editor(cx, |_, editor, cx| {
+            if let Some(transaction) = replay.transaction {
+                editor.group_until_transaction(transaction, cx);
+            }
+            editor.change_selections(Default::default(), window, cx, |s| {
+                s.select_anchor_ranges(replay.finished)
+            });
+        });
     }
 
     pub(crate) fn repeat(
@@ -402,7 +585,7 @@ mod test {
     use gpui::EntityInputHandler;
 
     use crate::{
-        VimGlobals,
+        Vim, VimGlobals,
         state::Mode,
         test::{NeovimBackedTestContext, VimTestContext},
     };
@@ -957,4 +1140,79 @@ mod test {
             the lazy dog"
         });
     }
+
+    #[gpui::test]
+    async fn test_replay_at_each_cursor(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇabc def", Mode::Normal);
+        cx.simulate_keystrokes("q a x w x q");
+        cx.assert_state("bc ˇef", Mode::Normal);
+
+        cx.set_state(
+            indoc! {"
+            one ˇtwo three
+            ˇfour five
+            six sevˇen eight"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes("@ a");
+        cx.run_until_parked();
+        cx.assert_state(
+            indoc! {"
+            one wo ˇhree
+            our ˇive
+            six sevn ˇight"},
+            Mode::Normal,
+        );
+
+        cx.simulate_keystrokes("u");
+        assert_eq!(
+            cx.buffer_text(),
+            indoc! {"
+            one two three
+            four five
+            six seven eight"}
+        );
+
+        // Cursors on the same line are visited one after the other rather than merged.
+        cx.set_state("ˇabc def", Mode::Normal);
+        cx.simulate_keystrokes("q b 0 x q");
+        cx.set_state("aˇbc ˇdef", Mode::Normal);
+        cx.simulate_keystrokes("@ b");
+        cx.run_until_parked();
+        cx.assert_state("ˇc def", Mode::Normal);
+    }
+
+    #[gpui::test]
+    async fn test_replay_at_each_cursor_stops(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        // `gn` finds no match, which stops the replay after the first cursor's `x`.
+        cx.set_state("ˇabc", Mode::Normal);
+        cx.simulate_keystrokes("q a x g n x q");
+        cx.assert_state("ˇc", Mode::Normal);
+
+        cx.set_state(
+            indoc! {"
+            ˇabc
+            ˇdef"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes("@ a");
+        cx.run_until_parked();
+        assert_eq!(cx.buffer_text(), "bc\ndef");
+        assert!(cx.update(|_, cx| Vim::globals(cx).cursor_replay.is_none()));
+
+        cx.simulate_keystrokes("u");
+        assert_eq!(cx.buffer_text(), "abc\ndef");
+
+        // Later replays still run at each cursor.
+        cx.set_state("ˇabc def", Mode::Normal);
+        cx.simulate_keystrokes("q b 0 x q");
+        cx.set_state("aˇbc ˇdef", Mode::Normal);
+        cx.simulate_keystrokes("@ b");
+        cx.run_until_parked();
+        cx.assert_state("ˇc def", Mode::Normal);
+    }
 }
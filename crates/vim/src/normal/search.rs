@@ -240,6 +240,7 @@ impl Vim {
                 return false;
             }
 
+            search_bar.seed_search_history(Vim::globals(cx).search_history.clone());
             search_bar.select_query(window, cx);
             cx.focus_self(window);
 
@@ -288,6 +289,7 @@ impl Vim {
             prior_operator: self.operator_stack.last().cloned(),
             prior_mode,
             helix_select: false,
+            helix_split: false,
             _dismiss_subscription: Some(subscription),
         }
     }
@@ -337,12 +339,21 @@ impl Vim {
                 let prior_mode = self.search.prior_mode;
                 let prior_operator = self.search.prior_operator.take();
 
-                let query = search_bar.query(cx).into();
-                Vim::globals(cx).registers.insert('/', query);
+                let query = search_bar.query(cx);
+                let globals = Vim::globals(cx);
+                globals.registers.insert('/', query.clone().into());
+                globals.push_search_history(query);
                 Some((prior_selections, prior_mode, prior_operator))
             })
         });
 
+        if self.search.helix_split {
+            self.search.helix_split = false;
+            let prior_selections = std::mem::take(&mut self.search.prior_selections);
+            self.helix_split_selections(prior_selections, window, cx);
+            return;
+        }
+
         let Some((mut prior_selections, prior_mode, prior_operator)) = result else {
             return;
         };
@@ -750,7 +761,7 @@ mod test {
     use std::time::Duration;
 
     use crate::{
-        state::Mode,
+        state::{Mode, VimGlobals},
         test::{NeovimBackedTestContext, VimTestContext},
     };
     use editor::{DisplayPoint, display_map::DisplayRow};
@@ -942,6 +953,20 @@ mod test {
         cx.assert_state("aa\nbb\nˇcc\ncc\ncc\n", Mode::Normal);
     }
 
+    #[gpui::test]
+    async fn test_search_history(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇaa\nbb\ncc\n", Mode::Normal);
+        cx.simulate_keystrokes("/ b b enter");
+        cx.simulate_keystrokes("/ c c enter");
+        cx.simulate_keystrokes("? b b enter");
+        cx.assert_state("aa\nˇbb\ncc\n", Mode::Normal);
+
+        let history = cx.read(|cx| cx.global::<VimGlobals>().search_history.clone());
+        assert_eq!(history, vec!["cc".to_string(), "bb".to_string()]);
+    }
+
     #[gpui::test]
     async fn test_non_vim_search(cx: &mut gpui::TestAppContext) {
         let mut cx = VimTestContext::new(cx, false).await;
@@ -1,18 +1,23 @@
 use crate::command::command_interceptor;
 use crate::motion::MotionKind;
-use crate::normal::repeat::Replayer;
+use crate::normal::repeat::{CursorReplay, Replayer};
 use crate::surrounds::SurroundsType;
 use crate::{ToggleMarksView, ToggleRegistersView, UseSystemClipboard, Vim, VimAddon, VimSettings};
 use crate::{motion::Motion, object::Object};
 use anyhow::Result;
 use collections::HashMap;
-use command_palette_hooks::{CommandPaletteFilter, GlobalCommandPaletteInterceptor};
+use command_palette_hooks::{
+    CommandPaletteFilter, GlobalCommandPaletteInterceptor, GlobalCommandPaletteQueryHistory,
+};
 use db::{
     sqlez::{domain::Domain, thread_safe_connection::ThreadSafeConnection},
     sqlez_macros::sql,
 };
 use editor::display_map::{is_invisible, replacement};
-use editor::{Anchor, ClipboardSelection, Editor, MultiBuffer, ToPoint as EditorToPoint};
+use editor::{
+    Anchor, ClipboardSelection, Editor, MultiBuffer, RestoredNavigationData,
+    ToPoint as EditorToPoint,
+};
 use gpui::{
     Action, App, AppContext, BorrowAppContext, ClipboardEntry, ClipboardItem, DismissEvent, Entity,
     EntityId, Global, HighlightStyle, StyledText, Subscription, Task, TextStyle, WeakEntity,
@@ -23,6 +28,7 @@ use picker::{Picker, PickerDelegate};
 use project::{Project, ProjectItem, ProjectPath};
 use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsStore};
+use std::any::Any;
 use std::borrow::BorrowMut;
 use std::collections::HashSet;
 use std::path::Path;
@@ -132,6 +138,7 @@ pub enum Operator {
     Register,
     RecordRegister,
     ReplayRegister,
+    ReplayRegisterAtSearchResults,
     ToggleComments,
     ReplaceWithRegister,
     Exchange,
@@ -208,12 +215,21 @@ impl From<String> for Register {
     }
 }
 
+/// Registers with more text than this aren't remembered across restarts.
+const MAX_PERSISTED_REGISTER_LEN: usize = 64 * 1024;
+/// The number of searches remembered across restarts.
+const MAX_PERSISTED_SEARCHES: usize = 100;
+/// The number of jumps remembered for each workspace across restarts.
+const MAX_PERSISTED_JUMPS: usize = 100;
+
 #[derive(Default)]
 pub struct VimGlobals {
     pub last_find: Option<Motion>,
 
     pub dot_recording: bool,
     pub dot_replaying: bool,
+    /// Set while the keystrokes of a `noremap` mapping are dispatched.
+    pub noremap: bool,
 
     /// pre_count is the number before an operator is specified (3 in 3d2d)
     pub pre_count: Option<usize>,
@@ -232,10 +248,13 @@ pub struct VimGlobals {
     pub last_recorded_register: Option<char>,
     pub last_replayed_register: Option<char>,
     pub replayer: Option<Replayer>,
+    pub cursor_replay: Option<CursorReplay>,
 
     pub last_yank: Option<SharedString>,
     pub registers: HashMap<char, Register>,
     pub recordings: HashMap<char, Vec<ReplayableAction>>,
+    /// Queries submitted with `/` and `?`, oldest first.
+    pub search_history: Vec<String>,
 
     pub focused_vim: Option<WeakEntity<Vim>>,
 
@@ -718,6 +737,13 @@ impl VimGlobals {
 
         cx.observe_global::<SettingsStore>(move |cx| {
             let is_enabled = Vim::enabled(cx);
+            if is_enabled {
+                // The `:` command history is the command palette's query history.
+                GlobalCommandPaletteQueryHistory::set_persisted(
+                    cx,
+                    VimSettings::get_global(cx).persist_history,
+                );
+            }
             if was_enabled == Some(is_enabled) {
                 return;
             }
@@ -728,6 +754,7 @@ impl VimGlobals {
                     filter.show_namespace(Vim::NAMESPACE);
                 });
                 GlobalCommandPaletteInterceptor::set(cx, command_interceptor);
+                VimGlobals::load_persisted_state(cx);
                 for window in cx.windows() {
                     if let Some(multi_workspace) = window.downcast::<MultiWorkspace>() {
                         multi_workspace
@@ -747,12 +774,22 @@ impl VimGlobals {
                 KeyBinding::set_vim_mode(cx, false);
                 *Vim::globals(cx) = VimGlobals::default();
                 GlobalCommandPaletteInterceptor::clear(cx);
+                GlobalCommandPaletteQueryHistory::clear(cx);
                 CommandPaletteFilter::update_global(cx, |filter, _| {
                     filter.hide_namespace(Vim::NAMESPACE);
                 });
             }
         })
         .detach();
+
+        cx.on_app_quit(|cx| {
+            let save_state = VimGlobals::save_persisted_state(cx);
+            async move {
+                save_state.await.log_err();
+            }
+        })
+        .detach();
+
         cx.observe_new(|workspace: &mut Workspace, _, cx| {
             Vim::update_globals(cx, |globals, cx| globals.register_workspace(workspace, cx));
         })
@@ -768,6 +805,101 @@ impl VimGlobals {
             })
         })
         .detach();
+
+        restore_jumps(cx);
+        cx.on_app_quit(|workspace, cx| {
+            let save_jumps = save_jumps(workspace, cx);
+            async move {
+                save_jumps.await.log_err();
+            }
+        })
+        .detach();
+        cx.on_release(|workspace, cx| save_jumps(workspace, cx).detach_and_log_err(cx))
+            .detach();
+    }
+
+    /// Restores the registers and search history remembered from previous sessions, keeping any
+    /// register that was written since.
+    fn load_persisted_state(cx: &mut App) {
+        if !VimSettings::get_global(cx).persist_history {
+            return;
+        }
+        cx.spawn(async move |cx| {
+            let (registers, search_history) = cx
+                .background_spawn(async move {
+                    anyhow::Ok((DB.get_registers()?, DB.get_search_history()?))
+                })
+                .await
+                .log_err()?;
+            cx.update(|cx| Vim::globals(cx).restore_persisted_state(registers, search_history));
+            Some(())
+        })
+        .detach();
+    }
+
+    fn restore_persisted_state(
+        &mut self,
+        registers: Vec<(char, Register)>,
+        search_history: Vec<String>,
+    ) {
+        for (name, register) in registers {
+            self.registers.entry(name).or_insert(register);
+        }
+        let recent_searches = std::mem::replace(&mut self.search_history, search_history);
+        for query in recent_searches {
+            self.push_search_history(query);
+        }
+    }
+
+    /// Writes the registers and search history to the database, or forgets the ones written by
+    /// previous sessions when they shouldn't be remembered.
+    fn save_persisted_state(cx: &mut App) -> Task<Result<()>> {
+        if !Vim::enabled(cx) {
+            return Task::ready(Ok(()));
+        }
+        let (registers, search_history) = Self::persisted_state(cx);
+        cx.background_spawn(async move {
+            DB.set_registers_and_search_history(registers, search_history)
+                .await
+        })
+    }
+
+    /// Returns the registers and search history to remember. Nothing is remembered when
+    /// `persist_history` is disabled, so writing the result clears what was saved before.
+    fn persisted_state(cx: &mut App) -> (Vec<SerializedRegister>, Vec<String>) {
+        if !VimSettings::get_global(cx).persist_history {
+            return (Vec::new(), Vec::new());
+        }
+        let globals = Vim::globals(cx);
+        let registers = globals
+            .registers
+            .iter()
+            .filter(|(name, register)| {
+                is_persisted_register(**name) && register.text.len() <= MAX_PERSISTED_REGISTER_LEN
+            })
+            .map(|(name, register)| SerializedRegister {
+                name: *name,
+                text: register.text.to_string(),
+                clipboard_selections: register
+                    .clipboard_selections
+                    .as_ref()
+                    .and_then(|selections| serde_json::to_string(selections).log_err()),
+            })
+            .collect();
+        (registers, globals.search_history.clone())
+    }
+
+    pub(crate) fn push_search_history(&mut self, query: String) {
+        if query.is_empty() {
+            return;
+        }
+        self.search_history.retain(|previous| previous != &query);
+        self.search_history.push(query);
+        let excess = self
+            .search_history
+            .len()
+            .saturating_sub(MAX_PERSISTED_SEARCHES);
+        self.search_history.drain(..excess);
     }
 
     pub(crate) fn write_registers(
@@ -1006,6 +1138,7 @@ pub struct SearchState {
     pub prior_operator: Option<Operator>,
     pub prior_mode: Mode,
     pub helix_select: bool,
+    pub helix_split: bool,
     pub _dismiss_subscription: Option<gpui::Subscription>,
 }
 
@@ -1047,6 +1180,7 @@ impl Operator {
             Operator::Register => "\"",
             Operator::RecordRegister => "q",
             Operator::ReplayRegister => "@",
+            Operator::ReplayRegisterAtSearchResults => "gz",
             Operator::ToggleComments => "gc",
             Operator::HelixMatch => "helix_m",
             Operator::HelixNext { .. } => "helix_next",
@@ -1102,6 +1236,7 @@ impl Operator {
             | Operator::Register
             | Operator::RecordRegister
             | Operator::ReplayRegister
+            | Operator::ReplayRegisterAtSearchResults
             | Operator::Replace
             | Operator::Digraph { .. }
             | Operator::Literal { .. }
@@ -1177,6 +1312,7 @@ impl Operator {
             | Operator::Register
             | Operator::RecordRegister
             | Operator::ReplayRegister
+            | Operator::ReplayRegisterAtSearchResults
             | Operator::HelixMatch => false,
         }
     }
@@ -1723,6 +1859,96 @@ impl MarksView {
     }
 }
 
+/// Whether a register is remembered across restarts. Like in Vim, these are the named, numbered,
+/// unnamed, small delete and last search registers.
+fn is_persisted_register(name: char) -> bool {
+    name.is_ascii_lowercase() || name.is_ascii_digit() || matches!(name, '"' | '-' | '/')
+}
+
+/// Restores the jump list of the workspace's active pane from its previous session.
+fn restore_jumps(cx: &mut Context<Workspace>) {
+    if !VimSettings::get_global(cx).persist_history {
+        return;
+    }
+    cx.spawn(async move |workspace, cx| {
+        let workspace_id = workspace
+            .read_with(cx, |workspace, _| workspace.database_id())
+            .ok()??;
+        let jumps = cx
+            .background_spawn(async move { DB.get_jumps(workspace_id) })
+            .await
+            .log_err()?;
+        workspace
+            .update(cx, |workspace, cx| prepend_jumps(workspace, jumps, cx))
+            .ok()
+    })
+    .detach();
+}
+
+/// Adds jumps remembered from a previous session before the oldest entry of the jump list of
+/// the workspace's active pane.
+fn prepend_jumps(
+    workspace: &mut Workspace,
+    jumps: Vec<(Arc<Path>, Point)>,
+    cx: &mut Context<Workspace>,
+) {
+    let project = workspace.project().read(cx);
+    let entries = jumps
+        .into_iter()
+        .filter_map(|(abs_path, cursor_position)| {
+            let project_path = project.find_project_path(&abs_path, cx)?;
+            let data: Arc<dyn Any + Send + Sync> =
+                Arc::new(RestoredNavigationData { cursor_position });
+            Some((project_path, Some(abs_path.to_path_buf()), data))
+        })
+        .collect();
+    workspace.active_pane().update(cx, |pane, _| {
+        pane.nav_history_mut().prepend_closed_entries(entries)
+    });
+}
+
+/// Writes the jump list of the workspace's active pane to the database.
+fn save_jumps(workspace: &Workspace, cx: &mut App) -> Task<Result<()>> {
+    let Some(workspace_id) = workspace.database_id() else {
+        return Task::ready(Ok(()));
+    };
+    if !Vim::enabled(cx) {
+        return Task::ready(Ok(()));
+    }
+    let jumps = persisted_jumps(workspace, cx);
+    cx.background_spawn(async move { DB.set_jumps(workspace_id, jumps).await })
+}
+
+/// Returns the jumps of the workspace's active pane to remember, oldest first. None are
+/// remembered when `persist_history` is disabled, so writing the result clears what was saved
+/// before.
+fn persisted_jumps(workspace: &Workspace, cx: &App) -> Vec<(Arc<Path>, Point)> {
+    let mut jumps = Vec::new();
+    if !VimSettings::get_global(cx).persist_history {
+        return jumps;
+    }
+    let project = workspace.project().read(cx);
+    workspace
+        .active_pane()
+        .read(cx)
+        .nav_history()
+        .for_each_backward_entry(cx, &mut |entry, (project_path, abs_path)| {
+            let Some(cursor_position) = entry
+                .data
+                .as_deref()
+                .and_then(editor::navigation_cursor_position)
+            else {
+                return;
+            };
+            if let Some(abs_path) = abs_path.or_else(|| project.absolute_path(&project_path, cx)) {
+                jumps.push((Arc::from(abs_path), cursor_position));
+            }
+        });
+    let excess = jumps.len().saturating_sub(MAX_PERSISTED_JUMPS);
+    jumps.drain(..excess);
+    jumps
+}
+
 pub struct VimDb(ThreadSafeConnection);
 
 impl Domain for VimDb {
@@ -1747,6 +1973,25 @@ impl Domain for VimDb {
             CREATE UNIQUE INDEX idx_vim_global_marks_paths
             ON vim_global_marks_paths(workspace_id, mark_name);
         ),
+        sql! (
+            CREATE TABLE vim_registers (
+                name TEXT PRIMARY KEY,
+                text TEXT NOT NULL,
+                clipboard_selections TEXT
+            );
+            CREATE TABLE vim_search_history (
+                position INTEGER PRIMARY KEY,
+                query TEXT NOT NULL
+            );
+            CREATE TABLE vim_jumps (
+                workspace_id INTEGER,
+                position INTEGER,
+                path BLOB,
+                point_row INTEGER,
+                point_column INTEGER,
+                PRIMARY KEY(workspace_id, position)
+            );
+        ),
     ];
 }
 
@@ -1758,6 +2003,12 @@ struct SerializedMark {
     points: Vec<Point>,
 }
 
+struct SerializedRegister {
+    name: char,
+    text: String,
+    clipboard_selections: Option<String>,
+}
+
 impl VimDb {
     pub(crate) async fn set_marks(
         &self,
@@ -1865,4 +2116,243 @@ impl VimDb {
         })
         .await
     }
+
+    async fn set_registers_and_search_history(
+        &self,
+        registers: Vec<SerializedRegister>,
+        search_history: Vec<String>,
+    ) -> Result<()> {
+        self.write(move |conn| {
+            conn.exec(sql!(DELETE FROM vim_registers))?()?;
+            let mut insert_register = conn.exec_bound(sql!(
+                INSERT INTO vim_registers (name, text, clipboard_selections)
+                VALUES (?, ?, ?)
+            ))?;
+            for register in registers {
+                insert_register((
+                    register.name.to_string(),
+                    register.text,
+                    register.clipboard_selections,
+                ))?;
+            }
+
+            conn.exec(sql!(DELETE FROM vim_search_history))?()?;
+            let mut insert_query = conn.exec_bound(sql!(
+                INSERT INTO vim_search_history (position, query) VALUES (?, ?)
+            ))?;
+            for (position, query) in search_history.into_iter().enumerate() {
+                insert_query((position as i64, query))?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    fn get_registers(&self) -> Result<Vec<(char, Register)>> {
+        let rows: Vec<(String, String, Option<String>)> = self.select(sql!(
+            SELECT name, text, clipboard_selections FROM vim_registers
+        ))?()?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(name, text, clipboard_selections)| {
+                let name = name.chars().next()?;
+                Some((
+                    name,
+                    Register {
+                        text: text.into(),
+                        clipboard_selections: clipboard_selections
+                            .and_then(|json| serde_json::from_str(&json).log_err()),
+                    },
+                ))
+            })
+            .collect())
+    }
+
+    fn get_search_history(&self) -> Result<Vec<String>> {
+        self.select(sql!(
+            SELECT query FROM vim_search_history ORDER BY position
+        ))?()
+    }
+
+    async fn set_jumps(
+        &self,
+        workspace_id: WorkspaceId,
+        jumps: Vec<(Arc<Path>, Point)>,
+    ) -> Result<()> {
+        self.write(move |conn| {
+            conn.exec_bound(sql!(
+                DELETE FROM vim_jumps WHERE workspace_id = ?
+            ))?(workspace_id)?;
+            let mut insert_jump = conn.exec_bound(sql!(
+                INSERT INTO vim_jumps
+                    (workspace_id, position, path, point_row, point_column)
+                VALUES
+                    (?, ?, ?, ?, ?)
+            ))?;
+            for (position, (path, point)) in jumps.into_iter().enumerate() {
+                insert_jump((workspace_id, position as i64, path, point.row, point.column))?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    fn get_jumps(&self, workspace_id: WorkspaceId) -> Result<Vec<(Arc<Path>, Point)>> {
+        let rows: Vec<(Arc<Path>, u32, u32)> = self.select_bound(sql!(
+            SELECT path, point_row, point_column FROM vim_jumps
+                WHERE workspace_id = ?
+                ORDER BY position
+        ))?(workspace_id)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(path, row, column)| (path, Point::new(row, column)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::VimTestContext;
+
+    #[gpui::test]
+    async fn test_persisted_registers_and_search_history(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        let db = VimDb::open_test_db("test_persisted_registers_and_search_history").await;
+
+        let selections = vec![ClipboardSelection {
+            len: 4,
+            is_entire_line: true,
+            first_line_indent: 2,
+            file_path: None,
+            line_range: Some(1..=1),
+        }];
+        let (registers, search_history) = cx.update(|_, cx| {
+            let globals = Vim::globals(cx);
+            globals.registers.insert(
+                'a',
+                Register {
+                    text: "one\n".into(),
+                    clipboard_selections: Some(selections.clone()),
+                },
+            );
+            globals.registers.insert('b', "two".to_string().into());
+            // Only the registers Vim remembers are persisted.
+            globals
+                .registers
+                .insert('+', "clipboard".to_string().into());
+            globals.push_search_history("first".into());
+            globals.push_search_history("second".into());
+            VimGlobals::persisted_state(cx)
+        });
+        db.set_registers_and_search_history(registers, search_history)
+            .await
+            .unwrap();
+
+        let registers = db.get_registers().unwrap();
+        let search_history = db.get_search_history().unwrap();
+        assert_eq!(search_history, ["first", "second"]);
+        cx.update(|_, cx| {
+            let globals = Vim::globals(cx);
+            *globals = VimGlobals::default();
+            // Registers written before the saved ones are restored take precedence.
+            globals.registers.insert('b', "newer".to_string().into());
+            globals.push_search_history("third".into());
+            globals.restore_persisted_state(registers, search_history);
+
+            let register_a = &globals.registers[&'a'];
+            assert_eq!(register_a.text.as_ref(), "one\n");
+            let restored_selections = register_a.clipboard_selections.as_ref().unwrap();
+            assert_eq!(restored_selections.len(), 1);
+            assert_eq!(restored_selections[0].len, 4);
+            assert!(restored_selections[0].is_entire_line);
+            assert_eq!(restored_selections[0].first_line_indent, 2);
+            assert_eq!(restored_selections[0].line_range, Some(1..=1));
+            assert_eq!(globals.registers[&'b'].text.as_ref(), "newer");
+            assert!(!globals.registers.contains_key(&'+'));
+            assert_eq!(globals.search_history, ["first", "second", "third"]);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_persist_history_disabled(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        let db = VimDb::open_test_db("test_persist_history_disabled").await;
+        let workspace_id = WorkspaceId::from_i64(1);
+
+        cx.update(|_, cx| {
+            let globals = Vim::globals(cx);
+            globals.registers.insert('a', "one".to_string().into());
+            globals.push_search_history("query".into());
+        });
+        let (registers, search_history) = cx.update(|_, cx| VimGlobals::persisted_state(cx));
+        db.set_registers_and_search_history(registers, search_history)
+            .await
+            .unwrap();
+        db.set_jumps(
+            workspace_id,
+            vec![(Arc::from(Path::new("/a.rs")), Point::new(1, 0))],
+        )
+        .await
+        .unwrap();
+        assert_eq!(db.get_registers().unwrap().len(), 1);
+        assert_eq!(db.get_jumps(workspace_id).unwrap().len(), 1);
+
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings(cx, |s| {
+                s.vim.get_or_insert_default().persist_history = Some(false);
+            });
+        });
+
+        // Saving with `persist_history` disabled forgets what earlier sessions saved.
+        let (registers, search_history) = cx.update(|_, cx| VimGlobals::persisted_state(cx));
+        assert!(registers.is_empty());
+        assert!(search_history.is_empty());
+        let jumps = cx.workspace(|workspace, _, cx| persisted_jumps(workspace, cx));
+        assert!(jumps.is_empty());
+        db.set_registers_and_search_history(registers, search_history)
+            .await
+            .unwrap();
+        db.set_jumps(workspace_id, jumps).await.unwrap();
+        assert!(db.get_registers().unwrap().is_empty());
+        assert!(db.get_search_history().unwrap().is_empty());
+        assert!(db.get_jumps(workspace_id).unwrap().is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_persisted_jumps(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        let db = VimDb::open_test_db("test_persisted_jumps").await;
+        let workspace_id = WorkspaceId::from_i64(1);
+
+        cx.set_state("ˇone\ntwo\nthree\nfour", Mode::Normal);
+        let abs_path: Arc<Path> = cx
+            .update_editor(|editor, _, cx| {
+                let buffer = editor.buffer().read(cx).as_singleton().unwrap();
+                buffer
+                    .read(cx)
+                    .file()
+                    .unwrap()
+                    .as_local()
+                    .unwrap()
+                    .abs_path(cx)
+            })
+            .into();
+        let jumps = vec![(abs_path.clone(), Point::new(2, 1))];
+        db.set_jumps(workspace_id, jumps.clone()).await.unwrap();
+        assert_eq!(db.get_jumps(workspace_id).unwrap(), jumps);
+
+        let restored = db.get_jumps(workspace_id).unwrap();
+        cx.workspace(|workspace, _, cx| prepend_jumps(workspace, restored, cx));
+        assert_eq!(
+            cx.workspace(|workspace, _, cx| persisted_jumps(workspace, cx)),
+            jumps
+        );
+
+        // Jumping back goes to the restored position.
+        cx.simulate_keystrokes("ctrl-o");
+        cx.assert_state("one\ntwo\ntˇhree\nfour", Mode::Normal);
+    }
 }
@@ -31,7 +31,13 @@ use search::{ProjectSearchView, project_search};
 use serde_json::json;
 use workspace::{DeploySearch, MultiWorkspace};
 
-use crate::{PushSneak, PushSneakBackward, VimAddon, insert::NormalBefore, motion, state::Mode};
+use crate::{
+    PushSneak, PushSneakBackward, Vim, VimAddon,
+    insert::NormalBefore,
+    motion,
+    normal::ChangeCase,
+    state::{Mode, ReplayableAction},
+};
 
 use util_macros::perf;
 
@@ -2822,3 +2828,72 @@ async fn test_project_search_opens_in_normal_mode(cx: &mut gpui::TestAppContext)
         assert_eq!(vim_mode, Some(Mode::Normal));
     });
 }
+
+#[gpui::test]
+async fn test_replay_register_at_search_results(cx: &mut gpui::TestAppContext) {
+    VimTestContext::init(cx);
+
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "file_a.rs": "foo bar foo",
+            "file_b.rs": "bar foo",
+        }),
+    )
+    .await;
+
+    let project = project::Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    let window_handle =
+        cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+    let workspace = window_handle
+        .read_with(cx, |mw, _| mw.workspace().clone())
+        .unwrap();
+
+    cx.update(|cx| {
+        VimTestContext::init_keybindings(true, cx);
+        Vim::globals(cx)
+            .recordings
+            .insert('a', vec![ReplayableAction::Action(Box::new(ChangeCase))]);
+    });
+
+    let cx = &mut VisualTestContext::from_window(window_handle.into(), cx);
+
+    workspace.update_in(cx, |workspace, window, cx| {
+        ProjectSearchView::deploy_search(workspace, &DeploySearch::default(), window, cx)
+    });
+
+    let search_view = workspace.update_in(cx, |workspace, _, cx| {
+        workspace
+            .active_pane()
+            .read(cx)
+            .items()
+            .find_map(|item| item.downcast::<ProjectSearchView>())
+            .expect("Project search view should be active")
+    });
+
+    project_search::perform_project_search(&search_view, "foo", cx);
+
+    search_view.update_in(cx, |search_view, window, cx| {
+        search_view
+            .results_editor()
+            .focus_handle(cx)
+            .focus(window, cx);
+    });
+    cx.simulate_keystrokes("g z a");
+    cx.run_until_parked();
+
+    let results_text = search_view.update(cx, |search_view, cx| {
+        search_view.results_editor().read(cx).text(cx)
+    });
+    assert!(results_text.contains("Foo bar Foo"), "{results_text:?}");
+    assert!(results_text.contains("bar Foo"), "{results_text:?}");
+    assert!(!results_text.contains("foo"), "{results_text:?}");
+
+    cx.simulate_keystrokes("u");
+    cx.run_until_parked();
+    let results_text = search_view.update(cx, |search_view, cx| {
+        search_view.results_editor().read(cx).text(cx)
+    });
+    assert!(!results_text.contains("Foo"), "{results_text:?}");
+}
@@ -17,6 +17,7 @@ mod replace;
 mod rewrap;
 mod state;
 mod surrounds;
+mod vimrc;
 mod visual;
 
 use crate::normal::paste::Paste as VimPaste;
@@ -55,6 +56,7 @@ use theme::ThemeSettings;
 use ui::{IntoElement, SharedString, px};
 use vim_mode_setting::HelixModeSetting;
 use vim_mode_setting::VimModeSetting;
+pub use vimrc::{Vimrc, handle_vimrc_file_changes, vimrc_key_bindings};
 use workspace::{self, Pane, Workspace};
 
 use crate::{
@@ -243,6 +245,8 @@ actions!(
         PushRecordRegister,
         /// Replays a register.
         PushReplayRegister,
+        /// Replays a register at every match of the project search.
+        PushReplayRegisterAtSearchResults,
         /// Replaces with register contents.
         PushReplaceWithRegister,
         /// Toggles comments.
@@ -858,6 +862,14 @@ impl Vim {
                 vim.push_operator(Operator::ReplayRegister, window, cx)
             });
 
+            Vim::action(
+                editor,
+                cx,
+                |vim, _: &PushReplayRegisterAtSearchResults, window, cx| {
+                    vim.push_operator(Operator::ReplayRegisterAtSearchResults, window, cx)
+                },
+            );
+
             Vim::action(
                 editor,
                 cx,
@@ -959,6 +971,7 @@ impl Vim {
             visual::register(editor, cx);
             change_list::register(editor, cx);
             digraph::register(editor, cx);
+            vimrc::register(editor, cx);
 
             if editor.is_focused(window) {
                 cx.defer_in(window, |vim, window, cx| {
@@ -1322,7 +1335,8 @@ impl Vim {
                         | Operator::Jump { .. }
                         | Operator::Register
                         | Operator::RecordRegister
-                        | Operator::ReplayRegister => CursorShape::Block,
+                        | Operator::ReplayRegister
+                        | Operator::ReplayRegisterAtSearchResults => CursorShape::Block,
 
                         // All other operators -> Underline cursor
                         _ => CursorShape::Underline,
@@ -1402,6 +1416,10 @@ impl Vim {
             context.add("VimCount");
         }
 
+        if cx.global::<VimGlobals>().noremap {
+            context.add("VimNoremap");
+        }
+
         if let Some(active_operator) = active_operator {
             if active_operator.is_waiting(self.mode) {
                 if matches!(active_operator, Operator::Literal { .. }) {
@@ -1621,6 +1639,9 @@ impl Vim {
         if let Some(replayer) = globals.replayer.take() {
             replayer.stop();
         }
+        if let Some(replay) = globals.cursor_replay.take() {
+            self.update_editor(cx, |_, editor, cx| replay.abort(editor, cx));
+        }
     }
 
     /// When finishing an action that modifies the buffer, stop recording.
@@ -1720,8 +1741,11 @@ impl Vim {
         &mut self,
         transaction_id: TransactionId,
         _window: &mut Window,
-        _: &mut Context<Self>,
+        cx: &mut Context<Self>,
     ) {
+        if let Some(replay) = Vim::globals(cx).cursor_replay.as_mut() {
+            replay.transaction.get_or_insert(transaction_id);
+        }
         let mode = if (self.mode == Mode::Insert
             || self.mode == Mode::Replace
             || self.mode == Mode::Normal)
@@ -2004,6 +2028,9 @@ impl Vim {
             Some(Operator::ReplayRegister) => {
                 self.replay_register(text.chars().next().unwrap(), window, cx)
             }
+            Some(Operator::ReplayRegisterAtSearchResults) => {
+                self.replay_register_at_search_results(text.chars().next().unwrap(), window, cx)
+            }
             Some(Operator::Register) => match self.mode {
                 Mode::Insert => {
                     self.update_editor(cx, |_, editor, cx| {
@@ -2101,8 +2128,10 @@ struct VimSettings {
     pub use_smartcase_find: bool,
     pub gdefault: bool,
     pub custom_digraphs: HashMap<String, Arc<str>>,
+    pub custom_commands: HashMap<String, settings::VimCustomCommandContent>,
     pub highlight_on_yank_duration: u64,
     pub cursor_shape: CursorShapeSettings,
+    pub persist_history: bool,
 }
 
 /// Cursor shape configuration for insert mode.
@@ -2187,8 +2216,10 @@ impl Settings for VimSettings {
             use_smartcase_find: vim.use_smartcase_find.unwrap(),
             gdefault: vim.gdefault.unwrap(),
             custom_digraphs: vim.custom_digraphs.unwrap(),
+            custom_commands: vim.custom_commands.unwrap(),
             highlight_on_yank_duration: vim.highlight_on_yank_duration.unwrap(),
             cursor_shape: vim.cursor_shape.unwrap().into(),
+            persist_history: vim.persist_history.unwrap(),
         }
     }
 }
@@ -0,0 +1,664 @@
+//! Support for a restricted subset of vimrc files.
+//!
+//! The `vimrc` file in the config directory may use `set`, the `map` and `noremap` families,
+//! `let mapleader` and `command!`. Options are translated into settings, mappings into key
+//! bindings that send keystrokes, and commands into `vim.custom_commands` entries. Anything
+//! else is reported in the log and ignored.
+
+use std::num::NonZeroU32;
+
+use anyhow::{Context as _, Result, bail};
+use editor::Editor;
+use futures::{StreamExt, channel::mpsc};
+use gpui::{Action, App, Context, Global, KeyBinding, Keystroke, Task};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use settings::{
+    KeybindSource, KeymapFile, KeymapFileLoadResult, RelativeLineNumbers, SettingsContent,
+    SettingsStore, SoftWrap, UseSystemClipboard, VimCustomCommandContent,
+};
+use util::ResultExt as _;
+
+use crate::Vim;
+
+const DEFAULT_LEADER: &str = "\\";
+
+/// Arguments to the map commands that don't change how a mapping is translated.
+const IGNORED_MAP_ARGUMENTS: &[&str] = &["<silent>", "<nowait>", "<unique>", "<buffer>"];
+
+/// The user's vimrc file, translated into settings and key bindings.
+#[derive(Default)]
+pub struct Vimrc {
+    settings: SettingsContent,
+    mappings: Vec<Mapping>,
+    errors: Vec<String>,
+}
+
+impl Global for Vimrc {}
+
+struct Mapping {
+    context: &'static str,
+    keystrokes: String,
+    action: Value,
+}
+
+/// Sends keystrokes to the focused editor without applying the vimrc's mappings to them, as the
+/// `noremap` family of commands does.
+#[derive(Clone, Deserialize, JsonSchema, PartialEq, Action)]
+#[action(namespace = vim)]
+pub struct SendKeystrokesNoremap(pub String);
+
+pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
+    Vim::action(
+        editor,
+        cx,
+        |vim, action: &SendKeystrokesNoremap, window, cx| {
+            let Some(workspace) = vim.workspace(window, cx) else {
+                return;
+            };
+            let keystrokes = action
+                .0
+                .split(' ')
+                .flat_map(|keystroke| Keystroke::parse(keystroke).log_err())
+                .map(|keystroke| {
+                    cx.keyboard_mapper()
+                        .map_key_equivalent(keystroke, false)
+                        .inner()
+                        .clone()
+                })
+                .collect();
+            cx.spawn_in(window, async move |_, cx| {
+                // The key context hides the vimrc's mappings while the keystrokes are dispatched.
+                // Draw before dispatching so the key context is up to date.
+                let task = cx.update(|window, cx| {
+                    Vim::globals(cx).noremap = true;
+                    window.draw(cx).clear();
+                    workspace.update(cx, |workspace, cx| {
+                        workspace.send_keystrokes_impl(keystrokes, window, cx)
+                    })
+                })?;
+                task.await;
+                cx.update(|window, cx| {
+                    Vim::globals(cx).noremap = false;
+                    window.refresh();
+                })
+            })
+            .detach_and_log_err(cx);
+        },
+    );
+}
+
+/// Loads the vimrc file whenever its contents change.
+pub fn handle_vimrc_file_changes(
+    mut vimrc_file_rx: mpsc::UnboundedReceiver<String>,
+    vimrc_watcher: Task<()>,
+    cx: &mut App,
+) {
+    let mut vim_enabled = Vim::enabled(cx);
+    cx.observe_global::<SettingsStore>(move |cx| {
+        let enabled = Vim::enabled(cx);
+        if enabled != vim_enabled {
+            vim_enabled = enabled;
+            Vimrc::update_settings(cx);
+        }
+    })
+    .detach();
+
+    cx.spawn(async move |cx| {
+        let _vimrc_watcher = vimrc_watcher;
+        while let Some(content) = vimrc_file_rx.next().await {
+            cx.update(|cx| {
+                let vimrc = Vimrc::parse(&content);
+                for error in &vimrc.errors {
+                    log::warn!("ignoring vimrc {error}");
+                }
+                cx.set_global(vimrc);
+                Vimrc::update_settings(cx);
+            });
+        }
+    })
+    .detach();
+}
+
+/// Returns the key bindings for the mappings in the user's vimrc file.
+pub fn vimrc_key_bindings(cx: &App) -> Vec<KeyBinding> {
+    let Some(vimrc) = cx.try_global::<Vimrc>() else {
+        return Vec::new();
+    };
+    if vimrc.mappings.is_empty() {
+        return Vec::new();
+    }
+
+    // Each mapping gets its own section so that later mappings take precedence over earlier
+    // ones, as they do in Vim. None of them apply to the keystrokes of a `noremap` mapping.
+    let keymap = vimrc
+        .mappings
+        .iter()
+        .map(|mapping| {
+            let mut bindings = serde_json::Map::new();
+            bindings.insert(mapping.keystrokes.clone(), mapping.action.clone());
+            let context = format!("{} && !VimNoremap", mapping.context);
+            json!({ "context": context, "bindings": bindings })
+        })
+        .collect::<Vec<_>>();
+
+    let mut key_bindings = match KeymapFile::load(&Value::Array(keymap).to_string(), cx) {
+        KeymapFileLoadResult::Success { key_bindings } => key_bindings,
+        KeymapFileLoadResult::SomeFailedToLoad {
+            key_bindings,
+            error_message,
+        } => {
+            log::warn!("some vimrc mappings failed to load: {}", error_message.0);
+            key_bindings
+        }
+        KeymapFileLoadResult::JsonParseFailure { error } => {
+            log::error!("failed to load vimrc mappings: {error}");
+            Vec::new()
+        }
+    };
+    for key_binding in &mut key_bindings {
+        key_binding.set_meta(KeybindSource::Vim.meta());
+    }
+    key_bindings
+}
+
+impl Vimrc {
+    fn update_settings(cx: &mut App) {
+        let settings = Vim::enabled(cx)
+            .then(|| cx.try_global::<Vimrc>().map(|vimrc| vimrc.settings.clone()))
+            .flatten();
+        SettingsStore::update_global(cx, |store, cx| store.set_vimrc_settings(settings, cx));
+    }
+
+    fn parse(content: &str) -> Self {
+        let mut vimrc = Self::default();
+        let mut leader = DEFAULT_LEADER.to_string();
+        for (ix, line) in content.lines().enumerate() {
+            let line = line.trim().trim_start_matches(':');
+            if line.is_empty() || line.starts_with('"') {
+                continue;
+            }
+            if let Err(error) = vimrc.parse_line(line, &mut leader) {
+                vimrc.errors.push(format!("line {}: {error}", ix + 1));
+            }
+        }
+        vimrc
+    }
+
+    fn parse_line(&mut self, line: &str, leader: &mut String) -> Result<()> {
+        let (command, args) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let args = args.trim_start();
+        match command {
+            "set" | "se" | "setlocal" | "setl" => {
+                for option in args.split_whitespace() {
+                    self.set_option(option)?;
+                }
+                Ok(())
+            }
+            "let" => {
+                *leader = parse_mapleader(args)?;
+                Ok(())
+            }
+            "command" | "command!" | "com" | "com!" => self.define_command(args),
+            _ => {
+                let Some(context) = map_context(command) else {
+                    bail!("unsupported command `{command}`");
+                };
+                self.map(context, command.contains("noremap"), args, leader)
+            }
+        }
+    }
+
+    fn set_option(&mut self, option: &str) -> Result<()> {
+        if let Some((name, value)) = option.split_once('=') {
+            let number = || {
+                value
+                    .parse::<u32>()
+                    .with_context(|| format!("invalid value `{value}` for `{name}`"))
+            };
+            match name {
+                "tabstop" | "ts" | "shiftwidth" | "sw" | "softtabstop" | "sts" => {
+                    self.settings.project.all_languages.defaults.tab_size =
+                        Some(NonZeroU32::new(number()?).context("tab size must not be zero")?);
+                }
+                "scrolloff" | "so" => {
+                    self.settings.editor.vertical_scroll_margin = Some(number()? as f32);
+                }
+                "textwidth" | "tw" => {
+                    self.settings
+                        .project
+                        .all_languages
+                        .defaults
+                        .preferred_line_length = Some(number()?);
+                }
+                "clipboard" | "cb" => {
+                    let use_system_clipboard = if value
+                        .split(',')
+                        .any(|value| value == "unnamed" || value == "unnamedplus")
+                    {
+                        UseSystemClipboard::Always
+                    } else {
+                        UseSystemClipboard::Never
+                    };
+                    self.settings
+                        .vim
+                        .get_or_insert_default()
+                        .use_system_clipboard = Some(use_system_clipboard);
+                }
+                _ => bail!("unsupported option `{name}`"),
+            }
+            return Ok(());
+        }
+
+        let (name, enabled) = match option.strip_prefix("no") {
+            Some(name) => (name, false),
+            None => (option, true),
+        };
+        match name {
+            "number" | "nu" => {
+                self.settings
+                    .editor
+                    .gutter
+                    .get_or_insert_default()
+                    .line_numbers = Some(enabled);
+            }
+            "relativenumber" | "rnu" => {
+                self.settings.editor.relative_line_numbers = Some(if enabled {
+                    RelativeLineNumbers::Enabled
+                } else {
+                    RelativeLineNumbers::Disabled
+                });
+            }
+            "wrap" => {
+                self.settings.project.all_languages.defaults.soft_wrap = Some(if enabled {
+                    SoftWrap::EditorWidth
+                } else {
+                    SoftWrap::None
+                });
+            }
+            "ignorecase" | "ic" => {
+                self.settings
+                    .editor
+                    .search
+                    .get_or_insert_default()
+                    .case_sensitive = Some(!enabled);
+            }
+            "smartcase" | "scs" => {
+                self.settings.vim.get_or_insert_default().use_smartcase_find = Some(enabled);
+            }
+            "gdefault" | "gd" => {
+                self.settings.vim.get_or_insert_default().gdefault = Some(enabled);
+            }
+            "expandtab" | "et" => {
+                self.settings.project.all_languages.defaults.hard_tabs = Some(!enabled);
+            }
+            _ => bail!("unsupported option `{option}`"),
+        }
+        Ok(())
+    }
+
+    fn map(
+        &mut self,
+        context: &'static str,
+        noremap: bool,
+        args: &str,
+        leader: &str,
+    ) -> Result<()> {
+        let mut args = args;
+        while let Some(rest) = IGNORED_MAP_ARGUMENTS
+            .iter()
+            .find_map(|argument| args.strip_prefix(argument))
+        {
+            args = rest.trim_start();
+        }
+        if args.starts_with("<expr>") {
+            bail!("expression mappings are not supported");
+        }
+
+        let (lhs, rhs) = args
+            .split_once(char::is_whitespace)
+            .context("missing right-hand side of mapping")?;
+        let rhs = rhs.trim();
+        let action = if rhs.eq_ignore_ascii_case("<nop>") {
+            Value::Null
+        } else if noremap {
+            json!(["vim::SendKeystrokesNoremap", translate_keys(rhs, leader)])
+        } else {
+            json!(["workspace::SendKeystrokes", translate_keys(rhs, leader)])
+        };
+        self.mappings.push(Mapping {
+            context,
+            keystrokes: translate_keys(lhs, leader),
+            action,
+        });
+        Ok(())
+    }
+
+    fn define_command(&mut self, args: &str) -> Result<()> {
+        let mut args = args;
+        let mut default_range = None;
+        while let Some(rest) = args.strip_prefix('-') {
+            let (attribute, rest) = rest
+                .split_once(char::is_whitespace)
+                .context("missing command name")?;
+            match attribute {
+                "range=%" => default_range = Some("%".to_string()),
+                "range" | "bang" | "bar" | "buffer" => {}
+                _ if attribute.starts_with("nargs=")
+                    || attribute.starts_with("complete=")
+                    || attribute.starts_with("range=") => {}
+                _ => bail!("unsupported command attribute `-{attribute}`"),
+            }
+            args = rest.trim_start();
+        }
+
+        let (name, definition) = args
+            .split_once(char::is_whitespace)
+            .context("missing command definition")?;
+        if !name.starts_with(|c: char| c.is_ascii_uppercase())
+            || !name.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            bail!("invalid command name `{name}`, names must start with an uppercase letter");
+        }
+
+        let definition = definition.trim().trim_start_matches(':');
+        let command = if let Some(shell) = definition.strip_prefix('!') {
+            VimCustomCommandContent {
+                shell: Some(shell.trim().to_string()),
+                default_range,
+                ..Default::default()
+            }
+        } else {
+            let keystrokes = translate_keys(&format!(":{definition}<CR>"), DEFAULT_LEADER);
+            VimCustomCommandContent {
+                actions: Some(vec![json!(["workspace::SendKeystrokes", keystrokes])]),
+                default_range,
+                ..Default::default()
+            }
+        };
+        self.settings
+            .vim
+            .get_or_insert_default()
+            .custom_commands
+            .get_or_insert_default()
+            .insert(name.to_string(), command);
+        Ok(())
+    }
+}
+
+fn map_context(command: &str) -> Option<&'static str> {
+    Some(match command {
+        "map" | "noremap" => "VimControl && !menu",
+        "nmap" | "nnoremap" => "vim_mode == normal && !menu",
+        "vmap" | "vnoremap" | "xmap" | "xnoremap" => "vim_mode == visual && !menu",
+        "omap" | "onoremap" => "vim_mode == operator",
+        "imap" | "inoremap" | "map!" | "noremap!" => "vim_mode == insert",
+        _ => return None,
+    })
+}
+
+fn parse_mapleader(args: &str) -> Result<String> {
+    let (name, value) = args
+        .split_once('=')
+        .context("expected `let mapleader = \"{key}\"`")?;
+    let name = name.trim();
+    if name != "mapleader" && name != "g:mapleader" {
+        bail!("unsupported variable `{name}`");
+    }
+
+    let value = value.trim();
+    let leader = if let Some(value) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        value.replace("\\<", "<").replace("\\\\", "\\")
+    } else if let Some(value) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        value.to_string()
+    } else {
+        bail!("expected a quoted string for `{name}`");
+    };
+    if leader.is_empty() {
+        bail!("`{name}` must not be empty");
+    }
+    Ok(leader)
+}
+
+/// Translates keys in Vim's notation, like `<C-w>j` or `<leader>ff`, into a space-separated list
+/// of keystrokes. Unrecognized `<...>` sequences are typed literally, as in Vim.
+fn translate_keys(keys: &str, leader: &str) -> String {
+    let mut keystrokes = Vec::new();
+    let mut rest = keys;
+    while let Some(c) = rest.chars().next() {
+        if c == '<'
+            && let Some(end) = rest.find('>')
+        {
+            let name = &rest[1..end];
+            if name.eq_ignore_ascii_case("leader") {
+                keystrokes.push(translate_keys(leader, DEFAULT_LEADER));
+                rest = &rest[end + 1..];
+                continue;
+            }
+            if let Some(keystroke) = translate_special_key(name) {
+                keystrokes.push(keystroke);
+                rest = &rest[end + 1..];
+                continue;
+            }
+        }
+        keystrokes.push(translate_char(c));
+        rest = &rest[c.len_utf8()..];
+    }
+    keystrokes.join(" ")
+}
+
+fn translate_char(c: char) -> String {
+    match c {
+        ' ' => "space".to_string(),
+        '\t' => "tab".to_string(),
+        c if c.is_ascii_uppercase() => format!("shift-{}", c.to_ascii_lowercase()),
+        c => c.to_string(),
+    }
+}
+
+fn translate_special_key(name: &str) -> Option<String> {
+    let mut modifiers = Vec::new();
+    let mut key = name;
+    while key.len() > 2 && key.as_bytes()[1] == b'-' {
+        let modifier = match key.as_bytes()[0].to_ascii_lowercase() {
+            b'c' => "ctrl",
+            b's' => "shift",
+            b'a' | b'm' => "alt",
+            b'd' => "cmd",
+            _ => return None,
+        };
+        modifiers.push(modifier);
+        key = &key[2..];
+    }
+
+    let lowercase = key.to_ascii_lowercase();
+    let key = match lowercase.as_str() {
+        "cr" | "enter" | "return" => "enter",
+        "esc" => "escape",
+        "space" => "space",
+        "tab" => "tab",
+        "bs" => "backspace",
+        "del" => "delete",
+        "up" => "up",
+        "down" => "down",
+        "left" => "left",
+        "right" => "right",
+        "home" => "home",
+        "end" => "end",
+        "pageup" => "pageup",
+        "pagedown" => "pagedown",
+        "insert" => "insert",
+        "lt" => "<",
+        "bar" => "|",
+        "bslash" => "\\",
+        key if key.len() == 1 && !modifiers.is_empty() => key,
+        key if key.starts_with('f')
+            && key[1..].parse::<u8>().is_ok_and(|n| (1..=24).contains(&n)) =>
+        {
+            key
+        }
+        _ => return None,
+    };
+
+    modifiers.push(key);
+    Some(modifiers.join("-"))
+}
+
+#[cfg(test)]
+mod test {
+    use gpui::{point, px, size};
+
+    use super::*;
+    use crate::{state::Mode, test::VimTestContext};
+
+    #[test]
+    fn test_translate_keys() {
+        assert_eq!(translate_keys("gj", DEFAULT_LEADER), "g j");
+        assert_eq!(translate_keys("Y", DEFAULT_LEADER), "shift-y");
+        assert_eq!(translate_keys("y$", DEFAULT_LEADER), "y $");
+        assert_eq!(translate_keys("<C-w>j", DEFAULT_LEADER), "ctrl-w j");
+        assert_eq!(
+            translate_keys("<C-S-Tab>", DEFAULT_LEADER),
+            "ctrl-shift-tab"
+        );
+        assert_eq!(translate_keys(":w<CR>", DEFAULT_LEADER), ": w enter");
+        assert_eq!(translate_keys("<leader>ff", " "), "space f f");
+        assert_eq!(translate_keys("<Leader>w", "<Space>"), "space w");
+        assert_eq!(translate_keys("<lt><F5>", DEFAULT_LEADER), "< f5");
+        assert_eq!(translate_keys("<<", DEFAULT_LEADER), "< <");
+        assert_eq!(translate_keys("<foo>", DEFAULT_LEADER), "< f o o >");
+    }
+
+    #[test]
+    fn test_parse_vimrc() {
+        let vimrc = Vimrc::parse(
+            r#"
+            " Options
+            set number norelativenumber
+            set tabstop=4 expandtab
+            set clipboard=unnamedplus
+            set spell
+
+            let mapleader = "\<Space>"
+            nnoremap <silent> <leader>w :w<CR>
+            inoremap jk <Esc>
+            vmap Q <Nop>
+            map Y y$
+            command! -range=% Fmt !prettier
+            command Wa wall
+            "#,
+        );
+
+        assert_eq!(vimrc.errors, vec!["line 6: unsupported option `spell`"]);
+
+        let settings = &vimrc.settings;
+        assert_eq!(
+            settings.editor.gutter.as_ref().unwrap().line_numbers,
+            Some(true)
+        );
+        assert_eq!(
+            settings.editor.relative_line_numbers,
+            Some(RelativeLineNumbers::Disabled)
+        );
+        let defaults = &settings.project.all_languages.defaults;
+        assert_eq!(defaults.tab_size, NonZeroU32::new(4));
+        assert_eq!(defaults.hard_tabs, Some(false));
+
+        let vim = settings.vim.as_ref().unwrap();
+        assert_eq!(vim.use_system_clipboard, Some(UseSystemClipboard::Always));
+        let commands = vim.custom_commands.as_ref().unwrap();
+        assert_eq!(
+            commands["Fmt"],
+            VimCustomCommandContent {
+                shell: Some("prettier".to_string()),
+                default_range: Some("%".to_string()),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            commands["Wa"].actions,
+            Some(vec![json!([
+                "workspace::SendKeystrokes",
+                ": w a l l enter"
+            ])])
+        );
+
+        let mappings = vimrc
+            .mappings
+            .iter()
+            .map(|mapping| {
+                (
+                    mapping.context,
+                    mapping.keystrokes.as_str(),
+                    mapping.action.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            mappings,
+            vec![
+                (
+                    "vim_mode == normal && !menu",
+                    "space w",
+                    json!(["vim::SendKeystrokesNoremap", ": w enter"])
+                ),
+                (
+                    "vim_mode == insert",
+                    "j k",
+                    json!(["vim::SendKeystrokesNoremap", "escape"])
+                ),
+                ("vim_mode == visual && !menu", "shift-q", Value::Null),
+                (
+                    "VimControl && !menu",
+                    "shift-y",
+                    json!(["workspace::SendKeystrokes", "y $"])
+                ),
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_noremap_does_not_apply_mappings(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.update(|_, cx| {
+            cx.set_global(Vimrc::parse("nnoremap n nzz"));
+            cx.bind_keys(vimrc_key_bindings(cx));
+        });
+
+        let line_height = cx.update_editor(|editor, window, cx| {
+            editor
+                .style(cx)
+                .text
+                .line_height_in_pixels(window.rem_size())
+        });
+        cx.simulate_window_resize(cx.window, size(px(1000.), line_height * 10.));
+
+        // Every twentieth line matches the search.
+        let state = |cursor_row: usize| {
+            (0..60)
+                .map(|row| {
+                    let cursor = if row == cursor_row { "ˇ" } else { "" };
+                    let line = if row % 20 == 0 { "foo" } else { "bar" };
+                    format!("{cursor}{line}\n")
+                })
+                .collect::<String>()
+        };
+        cx.set_state(&state(0), Mode::Normal);
+        cx.simulate_keystrokes("/ f o o enter");
+        cx.assert_state(&state(20), Mode::Normal);
+
+        // `n` still jumps to the next match instead of triggering its own mapping, and `zz`
+        // centers it.
+        cx.simulate_keystrokes("n");
+        cx.run_until_parked();
+        cx.assert_state(&state(40), Mode::Normal);
+        cx.update_editor(|editor, window, cx| {
+            let visible_rows = editor.visible_line_count().unwrap() as u32;
+            assert_eq!(
+                editor.snapshot(window, cx).scroll_position(),
+                point(0., (40 - visible_rows / 2) as f64)
+            );
+        });
+    }
+}
@@ -4549,6 +4549,51 @@ impl NavHistory {
             })
     }
 
+    /// Calls `f` with each entry that navigating back would visit, from the oldest to the most
+    /// recent one.
+    pub fn for_each_backward_entry(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(&NavigationEntry, (ProjectPath, Option<PathBuf>)),
+    ) {
+        let borrowed_history = self.0.lock();
+        for entry in &borrowed_history.backward_stack {
+            if let Some(project_and_abs_path) = borrowed_history.paths_by_item.get(&entry.item.id())
+            {
+                f(entry, project_and_abs_path.clone());
+            } else if let Some(item) = entry.item.upgrade()
+                && let Some(path) = item.project_path(cx)
+            {
+                f(entry, (path, None));
+            }
+        }
+    }
+
+    /// Inserts entries for files that aren't open before the oldest backward entry, such as
+    /// the history of a previous session. Navigating to one of them reopens its file and passes
+    /// its data to the reopened item.
+    pub fn prepend_closed_entries(
+        &mut self,
+        entries: Vec<(ProjectPath, Option<PathBuf>, Arc<dyn Any + Send + Sync>)>,
+    ) {
+        let state = &mut *self.0.lock();
+        for (project_path, abs_path, data) in entries.into_iter().rev() {
+            if state.backward_stack.len() >= MAX_NAVIGATION_HISTORY_LEN {
+                break;
+            }
+            let item = WeakEntity::<InvalidItemView>::new_invalid();
+            state
+                .paths_by_item
+                .insert(item.entity_id(), (project_path, abs_path));
+            state.backward_stack.push_front(NavigationEntry {
+                item: Arc::new(item),
+                data: Some(data),
+                timestamp: 0,
+                is_preview: false,
+            });
+        }
+    }
+
     pub fn set_mode(&mut self, mode: NavigationMode) {
         self.0.lock().mode = mode;
     }
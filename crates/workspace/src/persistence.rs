@@ -21,6 +21,7 @@ use db::{
 };
 use gpui::{Axis, Bounds, Task, WindowBounds, WindowId, point, size};
 use project::{
+    bookmark_store::SerializedBookmark,
     debugger::breakpoint_store::{BreakpointState, SourceBreakpoint},
     trusted_worktrees::{DbTrustedPaths, RemoteHostLocation},
 };
@@ -970,6 +971,17 @@ impl Domain for WorkspaceDb {
         sql!(
             ALTER TABLE remote_connections ADD COLUMN use_podman BOOLEAN;
         ),
+        sql!(
+            CREATE TABLE bookmarks (
+                workspace_id INTEGER NOT NULL,
+                path TEXT NOT NULL,
+                row INTEGER NOT NULL,
+                label TEXT,
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            );
+        ),
     ];
 
     // Allow recovering from bad migration that was initially shipped to nightly
@@ -1105,6 +1117,7 @@ impl WorkspaceDb {
             docks,
             session_id: None,
             breakpoints: self.breakpoints(workspace_id),
+            bookmarks: self.bookmarks(workspace_id),
             window_id,
             user_toolchains: self.user_toolchains(workspace_id, remote_connection_id),
         })
@@ -1195,6 +1208,7 @@ impl WorkspaceDb {
             docks,
             session_id: None,
             breakpoints: self.breakpoints(workspace_id),
+            bookmarks: self.bookmarks(workspace_id),
             window_id,
             user_toolchains: self.user_toolchains(workspace_id, remote_connection_id),
         })
@@ -1246,6 +1260,33 @@ impl WorkspaceDb {
         }
     }
 
+    fn bookmarks(&self, workspace_id: WorkspaceId) -> BTreeMap<Arc<Path>, Vec<SerializedBookmark>> {
+        let bookmarks: Result<Vec<(PathBuf, u32, Option<String>)>> = self
+            .select_bound(sql! {
+                SELECT path, row, label
+                FROM bookmarks
+                WHERE workspace_id = ?
+                ORDER BY path, row
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match bookmarks {
+            Ok(bookmarks) => {
+                let mut map: BTreeMap<Arc<Path>, Vec<SerializedBookmark>> = Default::default();
+                for (path, row, label) in bookmarks {
+                    map.entry(path.into())
+                        .or_default()
+                        .push(SerializedBookmark { row, label });
+                }
+                map
+            }
+            Err(msg) => {
+                log::error!("Bookmarks query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
     fn user_toolchains(
         &self,
         workspace_id: WorkspaceId,
@@ -1374,6 +1415,22 @@ impl WorkspaceDb {
                     }
                 }
 
+                conn.exec_bound(
+                    sql!(
+                        DELETE FROM bookmarks WHERE workspace_id = ?1;
+                    )
+                )?(workspace.id).context("Clearing old bookmarks")?;
+
+                for (path, bookmarks) in workspace.bookmarks {
+                    for bookmark in bookmarks {
+                        conn.exec_bound(sql!(
+                            INSERT INTO bookmarks (workspace_id, path, row, label)
+                            VALUES (?1, ?2, ?3, ?4);
+                        ))?((workspace.id, path.as_ref(), bookmark.row, bookmark.label))
+                        .context("Storing bookmark")?;
+                    }
+                }
+
                 conn.exec_bound(
                     sql!(
                         DELETE FROM user_toolchains WHERE workspace_id = ?1;
@@ -2568,6 +2625,7 @@ mod tests {
             },
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2689,6 +2747,7 @@ mod tests {
             },
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2723,6 +2782,7 @@ mod tests {
             breakpoints: collections::BTreeMap::default(),
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2737,6 +2797,51 @@ mod tests {
         assert!(empty_breakpoints.is_none());
     }
 
+    #[gpui::test]
+    async fn test_bookmarks() {
+        zlog::init_test();
+
+        let db = WorkspaceDb::open_test_db("test_bookmarks").await;
+        let id = db.next_id().await.unwrap();
+
+        let path: Arc<Path> = Arc::from(Path::new("/tmp/test_bookmarks.rs"));
+        let bookmarks = vec![
+            SerializedBookmark {
+                row: 3,
+                label: None,
+            },
+            SerializedBookmark {
+                row: 42,
+                label: Some("parser entry point".into()),
+            },
+        ];
+
+        let mut workspace = SerializedWorkspace {
+            id,
+            paths: PathList::new(&["/tmp"]),
+            location: SerializedWorkspaceLocation::Local,
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            breakpoints: Default::default(),
+            session_id: None,
+            window_id: None,
+            bookmarks: collections::BTreeMap::from_iter([(path.clone(), bookmarks.clone())]),
+            user_toolchains: Default::default(),
+        };
+
+        db.save_workspace(workspace.clone()).await;
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(loaded.bookmarks.get(&path), Some(&bookmarks));
+
+        workspace.bookmarks = Default::default();
+        db.save_workspace(workspace).await;
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert!(loaded.bookmarks.is_empty());
+    }
+
     #[gpui::test]
     async fn test_next_id_stability() {
         zlog::init_test();
@@ -2821,6 +2926,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2836,6 +2942,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2943,6 +3050,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: Some(999),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2977,6 +3085,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: Some(1),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2992,6 +3101,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: Some(2),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3034,6 +3144,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: Some(3),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3072,6 +3183,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(10),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3087,6 +3199,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(20),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3102,6 +3215,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(30),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3117,6 +3231,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3143,6 +3258,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(50),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3158,6 +3274,7 @@ mod tests {
             centered_layout: false,
             session_id: Some("session-id-3".to_owned()),
             window_id: Some(60),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3215,6 +3332,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         }
     }
@@ -3256,6 +3374,7 @@ mod tests {
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
             window_id: Some(window_id),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         })
         .collect::<Vec<_>>();
@@ -3368,6 +3487,7 @@ mod tests {
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
             window_id: Some(window_id),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         })
         .collect::<Vec<_>>();
@@ -3729,6 +3849,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3805,6 +3926,7 @@ mod tests {
                 session_id: Some("test-session".to_owned()),
                 breakpoints: Default::default(),
                 window_id: Some(*window_id),
+                bookmarks: Default::default(),
                 user_toolchains: Default::default(),
             })
             .await;
@@ -4146,6 +4268,7 @@ mod tests {
             session_id: Some("remove-test-session".to_owned()),
             breakpoints: Default::default(),
             window_id: Some(99),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         })
         .await;
@@ -4228,6 +4351,7 @@ mod tests {
             session_id: Some(session_id.to_owned()),
             breakpoints: Default::default(),
             window_id: Some(window_id_val),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         })
         .await;
@@ -4244,6 +4368,7 @@ mod tests {
             session_id: Some(session_id.to_owned()),
             breakpoints: Default::default(),
             window_id: Some(window_id_val),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         })
         .await;
@@ -4322,6 +4447,7 @@ mod tests {
             session_id: Some("pending-removal-session".to_owned()),
             breakpoints: Default::default(),
             window_id: Some(88),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         })
         .await;
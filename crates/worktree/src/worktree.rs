@@ -53,6 +53,7 @@ use std::{
     ffi::OsStr,
     fmt,
     future::Future,
+    io,
     mem::{self},
     ops::{Deref, DerefMut, Range},
     path::{Path, PathBuf},
@@ -107,6 +108,40 @@ pub struct LoadedFile {
     pub text: String,
     pub encoding: &'static Encoding,
     pub has_bom: bool,
+    /// The rest of the file when it was loaded in large-file mode, in which case
+    /// `text` only holds its first chunk.
+    pub remaining_chunks: Option<LargeFileChunks>,
+}
+
+/// The number of bytes of a large file that are read and decoded at a time.
+pub const LARGE_FILE_CHUNK_BYTES: usize = 4 * 1024 * 1024;
+
+/// The not-yet-loaded remainder of a file opened in large-file mode, which is read
+/// and decoded lazily, one chunk at a time, as the stream is polled. The stream isn't
+/// capped: it yields chunks until the end of the file.
+pub struct LargeFileChunks {
+    /// The size of the whole file on disk, in bytes.
+    pub file_len: u64,
+    chunks: stream::BoxStream<'static, Result<String>>,
+}
+
+impl fmt::Debug for LargeFileChunks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LargeFileChunks")
+            .field("file_len", &self.file_len)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Stream for LargeFileChunks {
+    type Item = Result<String>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.chunks.poll_next_unpin(cx)
+    }
 }
 
 pub struct LoadedBinaryFile {
@@ -718,7 +753,27 @@ impl Worktree {
 
     pub fn load_file(&self, path: &RelPath, cx: &Context<Worktree>) -> Task<Result<LoadedFile>> {
         match self {
-            Worktree::Local(this) => this.load_file(path, cx),
+            Worktree::Local(this) => this.load_file(path, false, cx),
+            Worktree::Remote(_) => {
+                Task::ready(Err(anyhow!("remote worktrees can't yet load files")))
+            }
+        }
+    }
+
+    /// Like [`Worktree::load_file`], but files above the `large_file_threshold_mb` setting
+    /// are loaded in large-file mode: only their first chunk is read up front, and the rest
+    /// is returned as [`LoadedFile::remaining_chunks`].
+    ///
+    /// This only defers reading the file, so that it can be shown before it has finished
+    /// loading. Callers that consume every chunk still end up holding the whole file in
+    /// memory; nothing is paged back out.
+    pub fn load_file_lazily(
+        &self,
+        path: &RelPath,
+        cx: &Context<Worktree>,
+    ) -> Task<Result<LoadedFile>> {
+        match self {
+            Worktree::Local(this) => this.load_file(path, true, cx),
             Worktree::Remote(_) => {
                 Task::ready(Err(anyhow!("remote worktrees can't yet load files")))
             }
@@ -1334,15 +1389,28 @@ impl LocalWorktree {
     }
 
     #[ztracing::instrument(skip_all)]
-    fn load_file(&self, path: &RelPath, cx: &Context<Worktree>) -> Task<Result<LoadedFile>> {
+    fn load_file(
+        &self,
+        path: &RelPath,
+        lazily: bool,
+        cx: &Context<Worktree>,
+    ) -> Task<Result<LoadedFile>> {
         let path = Arc::from(path);
         let abs_path = self.absolutize(&path);
         let fs = self.fs.clone();
         let entry = self.refresh_entry(path.clone(), None, cx);
         let is_private = self.is_path_private(path.as_ref());
+        let large_file_threshold = self.settings.large_file_threshold.filter(|_| lazily);
 
         let this = cx.weak_entity();
         cx.background_spawn(async move {
+            let file_len = fs
+                .metadata(&abs_path)
+                .await
+                .ok()
+                .flatten()
+                .map(|metadata| metadata.len);
+
             // WARN: Temporary workaround for #27283.
             //       We are not efficient with our memory usage per file, and use in excess of 64GB for a 10GB file
             //       Therefore, as a temporary workaround to prevent system freezes, we just bail before opening a file
@@ -1351,13 +1419,30 @@ impl LocalWorktree {
             //       reasonable limit
             {
                 const FILE_SIZE_MAX: u64 = 6 * 1024 * 1024 * 1024; // 6GB
-                if let Ok(Some(metadata)) = fs.metadata(&abs_path).await
-                    && metadata.len >= FILE_SIZE_MAX
+                if let Some(file_len) = file_len
+                    && file_len >= FILE_SIZE_MAX
                 {
                     anyhow::bail!("File is too large to load");
                 }
             }
-            let (text, encoding, has_bom) = decode_file_text(fs.as_ref(), &abs_path).await?;
+            let (text, encoding, has_bom, remaining_chunks) = match (file_len, large_file_threshold)
+            {
+                (Some(file_len), Some(threshold)) if file_len >= threshold => {
+                    let (text, encoding, has_bom, chunks) =
+                        decode_file_text_lazily(fs.as_ref(), &abs_path).await?;
+                    (
+                        text,
+                        encoding,
+                        has_bom,
+                        Some(LargeFileChunks { file_len, chunks }),
+                    )
+                }
+                _ => {
+                    let (text, encoding, has_bom) =
+                        decode_file_text(fs.as_ref(), &abs_path).await?;
+                    (text, encoding, has_bom, None)
+                }
+            };
 
             let worktree = this.upgrade().context("worktree was dropped")?;
             let file = match entry.await? {
@@ -1390,6 +1475,7 @@ impl LocalWorktree {
                 text,
                 encoding,
                 has_bom,
+                remaining_chunks,
             })
         })
     }
@@ -5979,6 +6065,122 @@ async fn decode_file_text(
     decode_byte_full(content, bom_encoding, byte_content)
 }
 
+/// Decodes the first [`LARGE_FILE_CHUNK_BYTES`] of a file, returning a stream that decodes
+/// the rest of it chunk by chunk.
+async fn decode_file_text_lazily(
+    fs: &dyn Fs,
+    abs_path: &Path,
+) -> Result<(
+    String,
+    &'static Encoding,
+    bool,
+    stream::BoxStream<'static, Result<String>>,
+)> {
+    let mut file = fs
+        .open_sync(&abs_path)
+        .await
+        .with_context(|| format!("opening file {abs_path:?}"))?;
+    let first_bytes = read_chunk(file.as_mut(), LARGE_FILE_CHUNK_BYTES)
+        .with_context(|| format!("reading bytes of the file {abs_path:?}"))?;
+    let (bom_encoding, byte_content) =
+        decode_byte_header(&first_bytes[..first_bytes.len().min(FILE_ANALYSIS_BYTES)]);
+    anyhow::ensure!(
+        byte_content != ByteContent::Binary,
+        "Binary files are not supported"
+    );
+    let (encoding, has_bom) = match (bom_encoding, byte_content) {
+        (Some(encoding), _) => (encoding, true),
+        (None, ByteContent::Utf16Le) => (encoding_rs::UTF_16LE, false),
+        (None, ByteContent::Utf16Be) => (encoding_rs::UTF_16BE, false),
+        (None, _) => (detect_chunk_encoding(&first_bytes), false),
+    };
+    let mut decoder = if has_bom {
+        encoding.new_decoder_with_bom_removal()
+    } else {
+        encoding.new_decoder_without_bom_handling()
+    };
+
+    let reached_eof = first_bytes.len() < LARGE_FILE_CHUNK_BYTES;
+    let mut text = decode_chunk(&mut decoder, &first_bytes, reached_eof);
+    // A CRLF line ending may be split across two chunks, so a trailing carriage return
+    // is carried over to the next chunk.
+    let carriage_return = !reached_eof && text.ends_with('\r');
+    if carriage_return {
+        text.pop();
+    }
+    let abs_path = abs_path.to_path_buf();
+    let state = (!reached_eof).then_some((file, decoder, carriage_return));
+    let chunks = stream::unfold(state, move |state| {
+        let abs_path = abs_path.clone();
+        async move {
+            let (mut file, mut decoder, carriage_return) = state?;
+            match read_chunk(file.as_mut(), LARGE_FILE_CHUNK_BYTES) {
+                Ok(bytes) => {
+                    let reached_eof = bytes.len() < LARGE_FILE_CHUNK_BYTES;
+                    let mut text = String::new();
+                    if carriage_return {
+                        text.push('\r');
+                    }
+                    text.push_str(&decode_chunk(&mut decoder, &bytes, reached_eof));
+                    let carriage_return = !reached_eof && text.ends_with('\r');
+                    if carriage_return {
+                        text.pop();
+                    }
+                    let state = (!reached_eof).then_some((file, decoder, carriage_return));
+                    Some((Ok(text), state))
+                }
+                Err(error) => Some((
+                    Err(anyhow::Error::from(error)
+                        .context(format!("reading remaining bytes of the file {abs_path:?}"))),
+                    None,
+                )),
+            }
+        }
+    })
+    .boxed();
+    Ok((text, encoding, has_bom, chunks))
+}
+
+/// Reads up to `len` bytes, returning fewer only when the end of the file is reached.
+fn read_chunk(file: &mut (dyn io::Read + Send + Sync), len: usize) -> io::Result<Vec<u8>> {
+    let mut bytes = vec![0; len];
+    let mut filled = 0;
+    while filled < len {
+        let n = file.read(&mut bytes[filled..])?;
+        if n == 0 {
+            break;
+        }
+        filled += n;
+    }
+    bytes.truncate(filled);
+    Ok(bytes)
+}
+
+fn decode_chunk(decoder: &mut encoding_rs::Decoder, bytes: &[u8], last: bool) -> String {
+    let capacity = decoder
+        .max_utf8_buffer_length(bytes.len())
+        .unwrap_or(bytes.len());
+    let mut text = String::with_capacity(capacity);
+    // The buffer is large enough for the whole chunk, so the decoder never stops early.
+    let _ = decoder.decode_to_string(bytes, &mut text, last);
+    text
+}
+
+/// Like the detection in [`decode_byte_full`], but for a chunk that may end in the middle
+/// of a multi-byte character.
+fn detect_chunk_encoding(bytes: &[u8]) -> &'static Encoding {
+    let is_utf8 = match std::str::from_utf8(bytes) {
+        Ok(_) => true,
+        Err(error) => error.error_len().is_none(),
+    };
+    if is_utf8 && !bytes.contains(&b'\x1b') {
+        return encoding_rs::UTF_8;
+    }
+    let mut detector = EncodingDetector::new();
+    detector.feed(bytes, false);
+    detector.guess(None, true)
+}
+
 fn decode_byte_header(prefix: &[u8]) -> (Option<&'static Encoding>, ByteContent) {
     if let Some((encoding, _bom_len)) = Encoding::for_bom(prefix) {
         return (Some(encoding), ByteContent::Unknown);
@@ -21,6 +21,8 @@ pub struct WorktreeSettings {
     pub private_files: PathMatcher,
     pub hidden_files: PathMatcher,
     pub read_only_files: PathMatcher,
+    /// The size in bytes above which files are opened in large-file mode, if enabled.
+    pub large_file_threshold: Option<u64>,
 }
 
 impl WorktreeSettings {
@@ -97,6 +99,10 @@ impl Settings for WorktreeSettings {
             read_only_files: path_matchers(read_only_files, "read_only_files")
                 .log_err()
                 .unwrap_or_default(),
+            large_file_threshold: worktree
+                .large_file_threshold_mb
+                .filter(|&megabytes| megabytes > 0)
+                .map(|megabytes| megabytes.saturating_mul(1024 * 1024)),
         }
     }
 }
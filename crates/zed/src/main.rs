@@ -402,6 +402,11 @@ fn main() {
         fs.clone(),
         paths::keymap_file().clone(),
     );
+    let (vimrc_file_rx, vimrc_watcher) = watch_config_file(
+        &app.background_executor(),
+        fs.clone(),
+        paths::vimrc_file().clone(),
+    );
 
     let (shell_env_loaded_tx, shell_env_loaded_rx) = oneshot::channel();
     if !stdout_is_a_pty() {
@@ -639,6 +644,7 @@ fn main() {
         language_model::init(app_state.client.clone(), cx);
         language_models::init(app_state.user_store.clone(), app_state.client.clone(), cx);
         acp_tools::init(cx);
+        language_model_tools::init(cx);
         zed::telemetry_log::init(cx);
         zed::remote_debug::init(cx);
         edit_prediction_ui::init(cx);
@@ -669,8 +675,12 @@ fn main() {
 
         editor::init(cx);
         image_viewer::init(cx);
+        hex_editor::init(cx);
         repl::notebook::init(cx);
         diagnostics::init(cx);
+        spell_check::init(app_state.fs.clone(), cx);
+        local_history::init(cx);
+        bookmarks::init(cx);
 
         audio::init(cx);
         workspace::init(app_state.clone(), cx);
@@ -697,6 +707,7 @@ fn main() {
             wrap_div_with_search_actions: search::buffer_search::register_pane_search_actions,
         });
         vim::init(cx);
+        vim::handle_vimrc_file_changes(vimrc_file_rx, vimrc_watcher, cx);
         terminal_view::init(cx);
         journal::init(app_state.clone(), cx);
         encoding_selector::init(cx);
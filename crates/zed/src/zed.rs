@@ -632,6 +632,9 @@ fn initialize_panels(
             workspace_handle.clone(),
             cx.clone(),
         );
+        let local_history_panel =
+            local_history::LocalHistoryPanel::load(workspace_handle.clone(), cx.clone());
+        let bookmarks_panel = bookmarks::BookmarksPanel::load(workspace_handle.clone(), cx.clone());
         let debug_panel = DebugPanel::load(workspace_handle.clone(), cx);
 
         async fn add_panel_when_ready(
@@ -656,6 +659,8 @@ fn initialize_panels(
             add_panel_when_ready(git_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(channels_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(notification_panel, workspace_handle.clone(), cx.clone()),
+            add_panel_when_ready(local_history_panel, workspace_handle.clone(), cx.clone()),
+            add_panel_when_ready(bookmarks_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(debug_panel, workspace_handle.clone(), cx.clone()),
             initialize_agent_panel(workspace_handle, prompt_builder, cx.clone()).map(|r| r.log_err()),
         );
@@ -1197,6 +1202,9 @@ fn initialize_pane(
             toolbar.add_item(dap_log_item, window, cx);
             let acp_tools_item = cx.new(|_| acp_tools::AcpToolsToolbarItemView::new());
             toolbar.add_item(acp_tools_item, window, cx);
+            let language_model_tools_item =
+                cx.new(|_| language_model_tools::LanguageModelToolsToolbarItemView::new());
+            toolbar.add_item(language_model_tools_item, window, cx);
             let telemetry_log_item =
                 cx.new(|cx| telemetry_log::TelemetryLogToolbarItemView::new(window, cx));
             toolbar.add_item(telemetry_log_item, window, cx);
@@ -1628,6 +1636,14 @@ pub fn handle_keymap_file_changes(
     let mut old_vim_enabled = VimModeSetting::get_global(cx).0;
     let mut old_helix_enabled = vim_mode_setting::HelixModeSetting::get_global(cx).0;
 
+    cx.observe_global::<vim::Vimrc>({
+        let base_keymap_tx = base_keymap_tx.clone();
+        move |_| {
+            base_keymap_tx.unbounded_send(()).ok();
+        }
+    })
+    .detach();
+
     cx.observe_global::<SettingsStore>(move |cx| {
         let new_base_keymap = *BaseKeymap::get_global(cx);
         let new_vim_enabled = VimModeSetting::get_global(cx).0;
@@ -1840,6 +1856,7 @@ pub fn load_default_keymap(cx: &mut App) {
         cx.bind_keys(
             KeymapFile::load_asset(VIM_KEYMAP_PATH, Some(KeybindSource::Vim), cx).unwrap(),
         );
+        cx.bind_keys(vim::vimrc_key_bindings(cx));
     }
 }
 
@@ -4799,6 +4816,7 @@ mod tests {
                 "auto_update",
                 "branch_picker",
                 "bedrock",
+                "bookmarks",
                 "branches",
                 "buffer_search",
                 "channel_modal",
@@ -4825,6 +4843,7 @@ mod tests {
                 "git_panel",
                 "git_picker",
                 "go_to_line",
+                "hex_editor",
                 "highlights_tree_view",
                 "icon_theme_selector",
                 "image_viewer",
@@ -4835,6 +4854,7 @@ mod tests {
                 "language_selector",
                 "welcome",
                 "line_ending_selector",
+                "local_history",
                 "lsp_tool",
                 "markdown",
                 "menu",
@@ -5018,6 +5038,7 @@ mod tests {
                 cx,
             );
             image_viewer::init(cx);
+            hex_editor::init(cx);
             language_model::init(app_state.client.clone(), cx);
             language_models::init(app_state.user_store.clone(), app_state.client.clone(), cx);
             web_search::init(cx);
@@ -243,6 +243,18 @@ pub mod workspace {
     );
 }
 
+pub mod hex_editor {
+    use gpui::actions;
+
+    actions!(
+        hex_editor,
+        [
+            /// Opens the active file in the hex editor.
+            OpenAsHex
+        ]
+    );
+}
+
 pub mod git {
     use gpui::actions;
 
@@ -264,6 +276,10 @@ pub mod git {
             Branch,
             /// Opens the git stash selector.
             ViewStash,
+            /// Opens the git tag selector.
+            ViewTags,
+            /// Opens the git reflog, to find and recover commits `HEAD` or a branch pointed at.
+            ViewReflog,
             /// Opens the git worktree selector.
             Worktree,
             /// Creates a pull request for the current branch.